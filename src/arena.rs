@@ -0,0 +1,2799 @@
+// Generated by `pragrat generate ast --mode arena`. Do not edit.
+use std::ops::Index;
+
+use crate::ast::{self, ExprContext, BoolOperator, Operator, UnaryOperator, CmpOperator};
+use crate::types::{Identifier, Literal, Span};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NodeId {
+	Mod(ModId),
+	Stmt(StmtId),
+	Expr(ExprId),
+	Pattern(PatternId),
+	TypeParam(TypeParamId),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ModId(u32);
+
+impl ModId {
+	/// Position of the node in its arena, usable to index side tables.
+	pub fn index(self) -> usize {
+		self.0 as usize
+	}
+}
+
+impl From<ModId> for NodeId {
+	fn from(id: ModId) -> Self {
+		NodeId::Mod(id)
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StmtId(u32);
+
+impl StmtId {
+	/// Position of the node in its arena, usable to index side tables.
+	pub fn index(self) -> usize {
+		self.0 as usize
+	}
+}
+
+impl From<StmtId> for NodeId {
+	fn from(id: StmtId) -> Self {
+		NodeId::Stmt(id)
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExprId(u32);
+
+impl ExprId {
+	/// Position of the node in its arena, usable to index side tables.
+	pub fn index(self) -> usize {
+		self.0 as usize
+	}
+}
+
+impl From<ExprId> for NodeId {
+	fn from(id: ExprId) -> Self {
+		NodeId::Expr(id)
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PatternId(u32);
+
+impl PatternId {
+	/// Position of the node in its arena, usable to index side tables.
+	pub fn index(self) -> usize {
+		self.0 as usize
+	}
+}
+
+impl From<PatternId> for NodeId {
+	fn from(id: PatternId) -> Self {
+		NodeId::Pattern(id)
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TypeParamId(u32);
+
+impl TypeParamId {
+	/// Position of the node in its arena, usable to index side tables.
+	pub fn index(self) -> usize {
+		self.0 as usize
+	}
+}
+
+impl From<TypeParamId> for NodeId {
+	fn from(id: TypeParamId) -> Self {
+		NodeId::TypeParam(id)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Mod {
+	Module(Module),
+	Interactive(Interactive),
+	Expression(Expression),
+	FunctionType(FunctionType),
+}
+
+impl Mod {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		match self {
+			Mod::Module(node) => node.for_each_child(f),
+			Mod::Interactive(node) => node.for_each_child(f),
+			Mod::Expression(node) => node.for_each_child(f),
+			Mod::FunctionType(node) => node.for_each_child(f),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Module {
+	pub body: Vec<StmtId>,
+	pub type_ignores: Vec<TypeIgnore>,
+}
+
+impl Module {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+		for x in &self.type_ignores {
+			x.for_each_child(f);
+		}
+	}
+}
+
+impl From<Module> for Mod {
+	fn from(node: Module) -> Self {
+		Mod::Module(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Interactive {
+	pub body: Vec<StmtId>,
+}
+
+impl Interactive {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+	}
+}
+
+impl From<Interactive> for Mod {
+	fn from(node: Interactive) -> Self {
+		Mod::Interactive(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Expression {
+	pub body: ExprId,
+}
+
+impl Expression {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.body));
+	}
+}
+
+impl From<Expression> for Mod {
+	fn from(node: Expression) -> Self {
+		Mod::Expression(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FunctionType {
+	pub argtypes: Vec<ExprId>,
+	pub returns: ExprId,
+}
+
+impl FunctionType {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.argtypes {
+			f(NodeId::Expr(*x));
+		}
+		f(NodeId::Expr(self.returns));
+	}
+}
+
+impl From<FunctionType> for Mod {
+	fn from(node: FunctionType) -> Self {
+		Mod::FunctionType(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Stmt {
+	FunctionDef(FunctionDef),
+	AsyncFunctionDef(AsyncFunctionDef),
+	ClassDef(ClassDef),
+	Return(Return),
+	Delete(Delete),
+	Assign(Assign),
+	TypeAlias(TypeAlias),
+	AugAssign(AugAssign),
+	AnnAssign(AnnAssign),
+	For(For),
+	AsyncFor(AsyncFor),
+	While(While),
+	If(If),
+	With(With),
+	AsyncWith(AsyncWith),
+	Match(Match),
+	Raise(Raise),
+	Try(Try),
+	TryStar(TryStar),
+	Assert(Assert),
+	Import(Import),
+	ImportFrom(ImportFrom),
+	Global(Global),
+	Nonlocal(Nonlocal),
+	ExprStmt(ExprStmt),
+	Pass(Pass),
+	Break(Break),
+	Continue(Continue),
+	ErrorStmt(ErrorStmt),
+}
+
+impl Stmt {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		match self {
+			Stmt::FunctionDef(node) => node.for_each_child(f),
+			Stmt::AsyncFunctionDef(node) => node.for_each_child(f),
+			Stmt::ClassDef(node) => node.for_each_child(f),
+			Stmt::Return(node) => node.for_each_child(f),
+			Stmt::Delete(node) => node.for_each_child(f),
+			Stmt::Assign(node) => node.for_each_child(f),
+			Stmt::TypeAlias(node) => node.for_each_child(f),
+			Stmt::AugAssign(node) => node.for_each_child(f),
+			Stmt::AnnAssign(node) => node.for_each_child(f),
+			Stmt::For(node) => node.for_each_child(f),
+			Stmt::AsyncFor(node) => node.for_each_child(f),
+			Stmt::While(node) => node.for_each_child(f),
+			Stmt::If(node) => node.for_each_child(f),
+			Stmt::With(node) => node.for_each_child(f),
+			Stmt::AsyncWith(node) => node.for_each_child(f),
+			Stmt::Match(node) => node.for_each_child(f),
+			Stmt::Raise(node) => node.for_each_child(f),
+			Stmt::Try(node) => node.for_each_child(f),
+			Stmt::TryStar(node) => node.for_each_child(f),
+			Stmt::Assert(node) => node.for_each_child(f),
+			Stmt::Import(node) => node.for_each_child(f),
+			Stmt::ImportFrom(node) => node.for_each_child(f),
+			Stmt::Global(node) => node.for_each_child(f),
+			Stmt::Nonlocal(node) => node.for_each_child(f),
+			Stmt::ExprStmt(node) => node.for_each_child(f),
+			Stmt::Pass(node) => node.for_each_child(f),
+			Stmt::Break(node) => node.for_each_child(f),
+			Stmt::Continue(node) => node.for_each_child(f),
+			Stmt::ErrorStmt(node) => node.for_each_child(f),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FunctionDef {
+	pub name: Identifier,
+	pub args: Box<Arguments>,
+	pub body: Vec<StmtId>,
+	pub decorator_list: Vec<ExprId>,
+	pub returns: Option<ExprId>,
+	pub type_comment: Option<String>,
+	pub type_params: Vec<TypeParamId>,
+	pub span: Span,
+}
+
+impl FunctionDef {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		self.args.for_each_child(f);
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+		for x in &self.decorator_list {
+			f(NodeId::Expr(*x));
+		}
+		if let Some(x) = &self.returns {
+			f(NodeId::Expr(*x));
+		}
+		for x in &self.type_params {
+			f(NodeId::TypeParam(*x));
+		}
+	}
+}
+
+impl From<FunctionDef> for Stmt {
+	fn from(node: FunctionDef) -> Self {
+		Stmt::FunctionDef(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AsyncFunctionDef {
+	pub name: Identifier,
+	pub args: Box<Arguments>,
+	pub body: Vec<StmtId>,
+	pub decorator_list: Vec<ExprId>,
+	pub returns: Option<ExprId>,
+	pub type_comment: Option<String>,
+	pub type_params: Vec<TypeParamId>,
+	pub span: Span,
+}
+
+impl AsyncFunctionDef {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		self.args.for_each_child(f);
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+		for x in &self.decorator_list {
+			f(NodeId::Expr(*x));
+		}
+		if let Some(x) = &self.returns {
+			f(NodeId::Expr(*x));
+		}
+		for x in &self.type_params {
+			f(NodeId::TypeParam(*x));
+		}
+	}
+}
+
+impl From<AsyncFunctionDef> for Stmt {
+	fn from(node: AsyncFunctionDef) -> Self {
+		Stmt::AsyncFunctionDef(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ClassDef {
+	pub name: Identifier,
+	pub bases: Vec<ExprId>,
+	pub keywords: Vec<Keyword>,
+	pub body: Vec<StmtId>,
+	pub decorator_list: Vec<ExprId>,
+	pub type_params: Vec<TypeParamId>,
+	pub span: Span,
+}
+
+impl ClassDef {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.bases {
+			f(NodeId::Expr(*x));
+		}
+		for x in &self.keywords {
+			x.for_each_child(f);
+		}
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+		for x in &self.decorator_list {
+			f(NodeId::Expr(*x));
+		}
+		for x in &self.type_params {
+			f(NodeId::TypeParam(*x));
+		}
+	}
+}
+
+impl From<ClassDef> for Stmt {
+	fn from(node: ClassDef) -> Self {
+		Stmt::ClassDef(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Return {
+	pub value: Option<ExprId>,
+	pub span: Span,
+}
+
+impl Return {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		if let Some(x) = &self.value {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<Return> for Stmt {
+	fn from(node: Return) -> Self {
+		Stmt::Return(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Delete {
+	pub targets: Vec<ExprId>,
+	pub span: Span,
+}
+
+impl Delete {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.targets {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<Delete> for Stmt {
+	fn from(node: Delete) -> Self {
+		Stmt::Delete(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Assign {
+	pub targets: Vec<ExprId>,
+	pub value: ExprId,
+	pub type_comment: Option<String>,
+	pub span: Span,
+}
+
+impl Assign {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.targets {
+			f(NodeId::Expr(*x));
+		}
+		f(NodeId::Expr(self.value));
+	}
+}
+
+impl From<Assign> for Stmt {
+	fn from(node: Assign) -> Self {
+		Stmt::Assign(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypeAlias {
+	pub name: ExprId,
+	pub type_params: Vec<TypeParamId>,
+	pub value: ExprId,
+	pub span: Span,
+}
+
+impl TypeAlias {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.name));
+		for x in &self.type_params {
+			f(NodeId::TypeParam(*x));
+		}
+		f(NodeId::Expr(self.value));
+	}
+}
+
+impl From<TypeAlias> for Stmt {
+	fn from(node: TypeAlias) -> Self {
+		Stmt::TypeAlias(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AugAssign {
+	pub target: ExprId,
+	pub op: Operator,
+	pub value: ExprId,
+	pub span: Span,
+}
+
+impl AugAssign {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.target));
+		f(NodeId::Expr(self.value));
+	}
+}
+
+impl From<AugAssign> for Stmt {
+	fn from(node: AugAssign) -> Self {
+		Stmt::AugAssign(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AnnAssign {
+	pub target: ExprId,
+	pub annotation: ExprId,
+	pub value: Option<ExprId>,
+	pub simple: i32,
+	pub span: Span,
+}
+
+impl AnnAssign {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.target));
+		f(NodeId::Expr(self.annotation));
+		if let Some(x) = &self.value {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<AnnAssign> for Stmt {
+	fn from(node: AnnAssign) -> Self {
+		Stmt::AnnAssign(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct For {
+	pub target: ExprId,
+	pub iter: ExprId,
+	pub body: Vec<StmtId>,
+	pub orelse: Vec<StmtId>,
+	pub type_comment: Option<String>,
+	pub span: Span,
+}
+
+impl For {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.target));
+		f(NodeId::Expr(self.iter));
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+		for x in &self.orelse {
+			f(NodeId::Stmt(*x));
+		}
+	}
+}
+
+impl From<For> for Stmt {
+	fn from(node: For) -> Self {
+		Stmt::For(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AsyncFor {
+	pub target: ExprId,
+	pub iter: ExprId,
+	pub body: Vec<StmtId>,
+	pub orelse: Vec<StmtId>,
+	pub type_comment: Option<String>,
+	pub span: Span,
+}
+
+impl AsyncFor {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.target));
+		f(NodeId::Expr(self.iter));
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+		for x in &self.orelse {
+			f(NodeId::Stmt(*x));
+		}
+	}
+}
+
+impl From<AsyncFor> for Stmt {
+	fn from(node: AsyncFor) -> Self {
+		Stmt::AsyncFor(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct While {
+	pub test: ExprId,
+	pub body: Vec<StmtId>,
+	pub orelse: Vec<StmtId>,
+	pub span: Span,
+}
+
+impl While {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.test));
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+		for x in &self.orelse {
+			f(NodeId::Stmt(*x));
+		}
+	}
+}
+
+impl From<While> for Stmt {
+	fn from(node: While) -> Self {
+		Stmt::While(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct If {
+	pub test: ExprId,
+	pub body: Vec<StmtId>,
+	pub orelse: Vec<StmtId>,
+	pub span: Span,
+}
+
+impl If {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.test));
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+		for x in &self.orelse {
+			f(NodeId::Stmt(*x));
+		}
+	}
+}
+
+impl From<If> for Stmt {
+	fn from(node: If) -> Self {
+		Stmt::If(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct With {
+	pub items: Vec<WithItem>,
+	pub body: Vec<StmtId>,
+	pub type_comment: Option<String>,
+	pub span: Span,
+}
+
+impl With {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.items {
+			x.for_each_child(f);
+		}
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+	}
+}
+
+impl From<With> for Stmt {
+	fn from(node: With) -> Self {
+		Stmt::With(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AsyncWith {
+	pub items: Vec<WithItem>,
+	pub body: Vec<StmtId>,
+	pub type_comment: Option<String>,
+	pub span: Span,
+}
+
+impl AsyncWith {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.items {
+			x.for_each_child(f);
+		}
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+	}
+}
+
+impl From<AsyncWith> for Stmt {
+	fn from(node: AsyncWith) -> Self {
+		Stmt::AsyncWith(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Match {
+	pub subject: ExprId,
+	pub cases: Vec<MatchCase>,
+	pub span: Span,
+}
+
+impl Match {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.subject));
+		for x in &self.cases {
+			x.for_each_child(f);
+		}
+	}
+}
+
+impl From<Match> for Stmt {
+	fn from(node: Match) -> Self {
+		Stmt::Match(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Raise {
+	pub exc: Option<ExprId>,
+	pub cause: Option<ExprId>,
+	pub span: Span,
+}
+
+impl Raise {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		if let Some(x) = &self.exc {
+			f(NodeId::Expr(*x));
+		}
+		if let Some(x) = &self.cause {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<Raise> for Stmt {
+	fn from(node: Raise) -> Self {
+		Stmt::Raise(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Try {
+	pub body: Vec<StmtId>,
+	pub handlers: Vec<ExceptHandler>,
+	pub orelse: Vec<StmtId>,
+	pub finalbody: Vec<StmtId>,
+	pub span: Span,
+}
+
+impl Try {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+		for x in &self.handlers {
+			x.for_each_child(f);
+		}
+		for x in &self.orelse {
+			f(NodeId::Stmt(*x));
+		}
+		for x in &self.finalbody {
+			f(NodeId::Stmt(*x));
+		}
+	}
+}
+
+impl From<Try> for Stmt {
+	fn from(node: Try) -> Self {
+		Stmt::Try(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TryStar {
+	pub body: Vec<StmtId>,
+	pub handlers: Vec<ExceptHandler>,
+	pub orelse: Vec<StmtId>,
+	pub finalbody: Vec<StmtId>,
+	pub span: Span,
+}
+
+impl TryStar {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+		for x in &self.handlers {
+			x.for_each_child(f);
+		}
+		for x in &self.orelse {
+			f(NodeId::Stmt(*x));
+		}
+		for x in &self.finalbody {
+			f(NodeId::Stmt(*x));
+		}
+	}
+}
+
+impl From<TryStar> for Stmt {
+	fn from(node: TryStar) -> Self {
+		Stmt::TryStar(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Assert {
+	pub test: ExprId,
+	pub msg: Option<ExprId>,
+	pub span: Span,
+}
+
+impl Assert {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.test));
+		if let Some(x) = &self.msg {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<Assert> for Stmt {
+	fn from(node: Assert) -> Self {
+		Stmt::Assert(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Import {
+	pub names: Vec<Alias>,
+	pub span: Span,
+}
+
+impl Import {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.names {
+			x.for_each_child(f);
+		}
+	}
+}
+
+impl From<Import> for Stmt {
+	fn from(node: Import) -> Self {
+		Stmt::Import(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ImportFrom {
+	pub module: Option<Identifier>,
+	pub names: Vec<Alias>,
+	pub level: i32,
+	pub span: Span,
+}
+
+impl ImportFrom {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.names {
+			x.for_each_child(f);
+		}
+	}
+}
+
+impl From<ImportFrom> for Stmt {
+	fn from(node: ImportFrom) -> Self {
+		Stmt::ImportFrom(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Global {
+	pub names: Vec<Identifier>,
+	pub span: Span,
+}
+
+impl Global {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+impl From<Global> for Stmt {
+	fn from(node: Global) -> Self {
+		Stmt::Global(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Nonlocal {
+	pub names: Vec<Identifier>,
+	pub span: Span,
+}
+
+impl Nonlocal {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+impl From<Nonlocal> for Stmt {
+	fn from(node: Nonlocal) -> Self {
+		Stmt::Nonlocal(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExprStmt {
+	pub value: ExprId,
+	pub span: Span,
+}
+
+impl ExprStmt {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.value));
+	}
+}
+
+impl From<ExprStmt> for Stmt {
+	fn from(node: ExprStmt) -> Self {
+		Stmt::ExprStmt(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Pass {
+	pub span: Span,
+}
+
+impl Pass {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+impl From<Pass> for Stmt {
+	fn from(node: Pass) -> Self {
+		Stmt::Pass(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Break {
+	pub span: Span,
+}
+
+impl Break {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+impl From<Break> for Stmt {
+	fn from(node: Break) -> Self {
+		Stmt::Break(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Continue {
+	pub span: Span,
+}
+
+impl Continue {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+impl From<Continue> for Stmt {
+	fn from(node: Continue) -> Self {
+		Stmt::Continue(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ErrorStmt {
+	pub span: Span,
+}
+
+impl ErrorStmt {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+impl From<ErrorStmt> for Stmt {
+	fn from(node: ErrorStmt) -> Self {
+		Stmt::ErrorStmt(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Expr {
+	BoolOp(BoolOp),
+	NamedExpr(NamedExpr),
+	BinOp(BinOp),
+	UnaryOp(UnaryOp),
+	Lambda(Lambda),
+	IfExp(IfExp),
+	Dict(Dict),
+	Set(Set),
+	ListComp(ListComp),
+	SetComp(SetComp),
+	DictComp(DictComp),
+	GeneratorExp(GeneratorExp),
+	Await(Await),
+	Yield(Yield),
+	YieldFrom(YieldFrom),
+	Compare(Compare),
+	Call(Call),
+	FormattedValue(FormattedValue),
+	JoinedStr(JoinedStr),
+	Constant(Constant),
+	Attribute(Attribute),
+	Subscript(Subscript),
+	Starred(Starred),
+	Name(Name),
+	List(List),
+	Tuple(Tuple),
+	Slice(Slice),
+	ErrorExpr(ErrorExpr),
+}
+
+impl Expr {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		match self {
+			Expr::BoolOp(node) => node.for_each_child(f),
+			Expr::NamedExpr(node) => node.for_each_child(f),
+			Expr::BinOp(node) => node.for_each_child(f),
+			Expr::UnaryOp(node) => node.for_each_child(f),
+			Expr::Lambda(node) => node.for_each_child(f),
+			Expr::IfExp(node) => node.for_each_child(f),
+			Expr::Dict(node) => node.for_each_child(f),
+			Expr::Set(node) => node.for_each_child(f),
+			Expr::ListComp(node) => node.for_each_child(f),
+			Expr::SetComp(node) => node.for_each_child(f),
+			Expr::DictComp(node) => node.for_each_child(f),
+			Expr::GeneratorExp(node) => node.for_each_child(f),
+			Expr::Await(node) => node.for_each_child(f),
+			Expr::Yield(node) => node.for_each_child(f),
+			Expr::YieldFrom(node) => node.for_each_child(f),
+			Expr::Compare(node) => node.for_each_child(f),
+			Expr::Call(node) => node.for_each_child(f),
+			Expr::FormattedValue(node) => node.for_each_child(f),
+			Expr::JoinedStr(node) => node.for_each_child(f),
+			Expr::Constant(node) => node.for_each_child(f),
+			Expr::Attribute(node) => node.for_each_child(f),
+			Expr::Subscript(node) => node.for_each_child(f),
+			Expr::Starred(node) => node.for_each_child(f),
+			Expr::Name(node) => node.for_each_child(f),
+			Expr::List(node) => node.for_each_child(f),
+			Expr::Tuple(node) => node.for_each_child(f),
+			Expr::Slice(node) => node.for_each_child(f),
+			Expr::ErrorExpr(node) => node.for_each_child(f),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BoolOp {
+	pub op: BoolOperator,
+	pub values: Vec<ExprId>,
+	pub span: Span,
+}
+
+impl BoolOp {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.values {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<BoolOp> for Expr {
+	fn from(node: BoolOp) -> Self {
+		Expr::BoolOp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NamedExpr {
+	pub target: ExprId,
+	pub value: ExprId,
+	pub span: Span,
+}
+
+impl NamedExpr {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.target));
+		f(NodeId::Expr(self.value));
+	}
+}
+
+impl From<NamedExpr> for Expr {
+	fn from(node: NamedExpr) -> Self {
+		Expr::NamedExpr(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BinOp {
+	pub left: ExprId,
+	pub op: Operator,
+	pub right: ExprId,
+	pub span: Span,
+}
+
+impl BinOp {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.left));
+		f(NodeId::Expr(self.right));
+	}
+}
+
+impl From<BinOp> for Expr {
+	fn from(node: BinOp) -> Self {
+		Expr::BinOp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnaryOp {
+	pub op: UnaryOperator,
+	pub operand: ExprId,
+	pub span: Span,
+}
+
+impl UnaryOp {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.operand));
+	}
+}
+
+impl From<UnaryOp> for Expr {
+	fn from(node: UnaryOp) -> Self {
+		Expr::UnaryOp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Lambda {
+	pub args: Box<Arguments>,
+	pub body: ExprId,
+	pub span: Span,
+}
+
+impl Lambda {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		self.args.for_each_child(f);
+		f(NodeId::Expr(self.body));
+	}
+}
+
+impl From<Lambda> for Expr {
+	fn from(node: Lambda) -> Self {
+		Expr::Lambda(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IfExp {
+	pub test: ExprId,
+	pub body: ExprId,
+	pub orelse: ExprId,
+	pub span: Span,
+}
+
+impl IfExp {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.test));
+		f(NodeId::Expr(self.body));
+		f(NodeId::Expr(self.orelse));
+	}
+}
+
+impl From<IfExp> for Expr {
+	fn from(node: IfExp) -> Self {
+		Expr::IfExp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Dict {
+	pub keys: Vec<Option<ExprId>>,
+	pub values: Vec<ExprId>,
+	pub span: Span,
+}
+
+impl Dict {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in self.keys.iter().flatten() {
+			f(NodeId::Expr(*x));
+		}
+		for x in &self.values {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<Dict> for Expr {
+	fn from(node: Dict) -> Self {
+		Expr::Dict(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Set {
+	pub elts: Vec<ExprId>,
+	pub span: Span,
+}
+
+impl Set {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.elts {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<Set> for Expr {
+	fn from(node: Set) -> Self {
+		Expr::Set(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ListComp {
+	pub elt: ExprId,
+	pub generators: Vec<Comprehension>,
+	pub span: Span,
+}
+
+impl ListComp {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.elt));
+		for x in &self.generators {
+			x.for_each_child(f);
+		}
+	}
+}
+
+impl From<ListComp> for Expr {
+	fn from(node: ListComp) -> Self {
+		Expr::ListComp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SetComp {
+	pub elt: ExprId,
+	pub generators: Vec<Comprehension>,
+	pub span: Span,
+}
+
+impl SetComp {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.elt));
+		for x in &self.generators {
+			x.for_each_child(f);
+		}
+	}
+}
+
+impl From<SetComp> for Expr {
+	fn from(node: SetComp) -> Self {
+		Expr::SetComp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DictComp {
+	pub key: ExprId,
+	pub value: ExprId,
+	pub generators: Vec<Comprehension>,
+	pub span: Span,
+}
+
+impl DictComp {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.key));
+		f(NodeId::Expr(self.value));
+		for x in &self.generators {
+			x.for_each_child(f);
+		}
+	}
+}
+
+impl From<DictComp> for Expr {
+	fn from(node: DictComp) -> Self {
+		Expr::DictComp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GeneratorExp {
+	pub elt: ExprId,
+	pub generators: Vec<Comprehension>,
+	pub span: Span,
+}
+
+impl GeneratorExp {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.elt));
+		for x in &self.generators {
+			x.for_each_child(f);
+		}
+	}
+}
+
+impl From<GeneratorExp> for Expr {
+	fn from(node: GeneratorExp) -> Self {
+		Expr::GeneratorExp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Await {
+	pub value: ExprId,
+	pub span: Span,
+}
+
+impl Await {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.value));
+	}
+}
+
+impl From<Await> for Expr {
+	fn from(node: Await) -> Self {
+		Expr::Await(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Yield {
+	pub value: Option<ExprId>,
+	pub span: Span,
+}
+
+impl Yield {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		if let Some(x) = &self.value {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<Yield> for Expr {
+	fn from(node: Yield) -> Self {
+		Expr::Yield(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct YieldFrom {
+	pub value: ExprId,
+	pub span: Span,
+}
+
+impl YieldFrom {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.value));
+	}
+}
+
+impl From<YieldFrom> for Expr {
+	fn from(node: YieldFrom) -> Self {
+		Expr::YieldFrom(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Compare {
+	pub left: ExprId,
+	pub ops: Vec<CmpOperator>,
+	pub comparators: Vec<ExprId>,
+	pub span: Span,
+}
+
+impl Compare {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.left));
+		for x in &self.comparators {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<Compare> for Expr {
+	fn from(node: Compare) -> Self {
+		Expr::Compare(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Call {
+	pub func: ExprId,
+	pub args: Vec<ExprId>,
+	pub keywords: Vec<Keyword>,
+	pub span: Span,
+}
+
+impl Call {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.func));
+		for x in &self.args {
+			f(NodeId::Expr(*x));
+		}
+		for x in &self.keywords {
+			x.for_each_child(f);
+		}
+	}
+}
+
+impl From<Call> for Expr {
+	fn from(node: Call) -> Self {
+		Expr::Call(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FormattedValue {
+	pub value: ExprId,
+	pub conversion: i32,
+	pub format_spec: Option<ExprId>,
+	pub span: Span,
+}
+
+impl FormattedValue {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.value));
+		if let Some(x) = &self.format_spec {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<FormattedValue> for Expr {
+	fn from(node: FormattedValue) -> Self {
+		Expr::FormattedValue(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct JoinedStr {
+	pub values: Vec<ExprId>,
+	pub span: Span,
+}
+
+impl JoinedStr {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.values {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<JoinedStr> for Expr {
+	fn from(node: JoinedStr) -> Self {
+		Expr::JoinedStr(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Constant {
+	pub value: Literal,
+	pub kind: Option<String>,
+	pub span: Span,
+}
+
+impl Constant {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+impl From<Constant> for Expr {
+	fn from(node: Constant) -> Self {
+		Expr::Constant(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Attribute {
+	pub value: ExprId,
+	pub attr: Identifier,
+	pub ctx: ExprContext,
+	pub span: Span,
+}
+
+impl Attribute {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.value));
+	}
+}
+
+impl From<Attribute> for Expr {
+	fn from(node: Attribute) -> Self {
+		Expr::Attribute(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Subscript {
+	pub value: ExprId,
+	pub slice: ExprId,
+	pub ctx: ExprContext,
+	pub span: Span,
+}
+
+impl Subscript {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.value));
+		f(NodeId::Expr(self.slice));
+	}
+}
+
+impl From<Subscript> for Expr {
+	fn from(node: Subscript) -> Self {
+		Expr::Subscript(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Starred {
+	pub value: ExprId,
+	pub ctx: ExprContext,
+	pub span: Span,
+}
+
+impl Starred {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.value));
+	}
+}
+
+impl From<Starred> for Expr {
+	fn from(node: Starred) -> Self {
+		Expr::Starred(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Name {
+	pub id: Identifier,
+	pub ctx: ExprContext,
+	pub span: Span,
+}
+
+impl Name {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+impl From<Name> for Expr {
+	fn from(node: Name) -> Self {
+		Expr::Name(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct List {
+	pub elts: Vec<ExprId>,
+	pub ctx: ExprContext,
+	pub span: Span,
+}
+
+impl List {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.elts {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<List> for Expr {
+	fn from(node: List) -> Self {
+		Expr::List(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Tuple {
+	pub elts: Vec<ExprId>,
+	pub ctx: ExprContext,
+	pub span: Span,
+}
+
+impl Tuple {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.elts {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<Tuple> for Expr {
+	fn from(node: Tuple) -> Self {
+		Expr::Tuple(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Slice {
+	pub lower: Option<ExprId>,
+	pub upper: Option<ExprId>,
+	pub step: Option<ExprId>,
+	pub span: Span,
+}
+
+impl Slice {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		if let Some(x) = &self.lower {
+			f(NodeId::Expr(*x));
+		}
+		if let Some(x) = &self.upper {
+			f(NodeId::Expr(*x));
+		}
+		if let Some(x) = &self.step {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<Slice> for Expr {
+	fn from(node: Slice) -> Self {
+		Expr::Slice(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ErrorExpr {
+	pub span: Span,
+}
+
+impl ErrorExpr {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+impl From<ErrorExpr> for Expr {
+	fn from(node: ErrorExpr) -> Self {
+		Expr::ErrorExpr(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Pattern {
+	MatchValue(MatchValue),
+	MatchSingleton(MatchSingleton),
+	MatchSequence(MatchSequence),
+	MatchMapping(MatchMapping),
+	MatchClass(MatchClass),
+	MatchStar(MatchStar),
+	MatchAs(MatchAs),
+	MatchOr(MatchOr),
+}
+
+impl Pattern {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		match self {
+			Pattern::MatchValue(node) => node.for_each_child(f),
+			Pattern::MatchSingleton(node) => node.for_each_child(f),
+			Pattern::MatchSequence(node) => node.for_each_child(f),
+			Pattern::MatchMapping(node) => node.for_each_child(f),
+			Pattern::MatchClass(node) => node.for_each_child(f),
+			Pattern::MatchStar(node) => node.for_each_child(f),
+			Pattern::MatchAs(node) => node.for_each_child(f),
+			Pattern::MatchOr(node) => node.for_each_child(f),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchValue {
+	pub value: ExprId,
+	pub span: Span,
+}
+
+impl MatchValue {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.value));
+	}
+}
+
+impl From<MatchValue> for Pattern {
+	fn from(node: MatchValue) -> Self {
+		Pattern::MatchValue(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchSingleton {
+	pub value: Literal,
+	pub span: Span,
+}
+
+impl MatchSingleton {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+impl From<MatchSingleton> for Pattern {
+	fn from(node: MatchSingleton) -> Self {
+		Pattern::MatchSingleton(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchSequence {
+	pub patterns: Vec<PatternId>,
+	pub span: Span,
+}
+
+impl MatchSequence {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.patterns {
+			f(NodeId::Pattern(*x));
+		}
+	}
+}
+
+impl From<MatchSequence> for Pattern {
+	fn from(node: MatchSequence) -> Self {
+		Pattern::MatchSequence(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchMapping {
+	pub keys: Vec<ExprId>,
+	pub patterns: Vec<PatternId>,
+	pub rest: Option<Identifier>,
+	pub span: Span,
+}
+
+impl MatchMapping {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.keys {
+			f(NodeId::Expr(*x));
+		}
+		for x in &self.patterns {
+			f(NodeId::Pattern(*x));
+		}
+	}
+}
+
+impl From<MatchMapping> for Pattern {
+	fn from(node: MatchMapping) -> Self {
+		Pattern::MatchMapping(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchClass {
+	pub cls: ExprId,
+	pub patterns: Vec<PatternId>,
+	pub kwd_attrs: Vec<Identifier>,
+	pub kwd_patterns: Vec<PatternId>,
+	pub span: Span,
+}
+
+impl MatchClass {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.cls));
+		for x in &self.patterns {
+			f(NodeId::Pattern(*x));
+		}
+		for x in &self.kwd_patterns {
+			f(NodeId::Pattern(*x));
+		}
+	}
+}
+
+impl From<MatchClass> for Pattern {
+	fn from(node: MatchClass) -> Self {
+		Pattern::MatchClass(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchStar {
+	pub name: Option<Identifier>,
+	pub span: Span,
+}
+
+impl MatchStar {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+impl From<MatchStar> for Pattern {
+	fn from(node: MatchStar) -> Self {
+		Pattern::MatchStar(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchAs {
+	pub pattern: Option<PatternId>,
+	pub name: Option<Identifier>,
+	pub span: Span,
+}
+
+impl MatchAs {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		if let Some(x) = &self.pattern {
+			f(NodeId::Pattern(*x));
+		}
+	}
+}
+
+impl From<MatchAs> for Pattern {
+	fn from(node: MatchAs) -> Self {
+		Pattern::MatchAs(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchOr {
+	pub patterns: Vec<PatternId>,
+	pub span: Span,
+}
+
+impl MatchOr {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.patterns {
+			f(NodeId::Pattern(*x));
+		}
+	}
+}
+
+impl From<MatchOr> for Pattern {
+	fn from(node: MatchOr) -> Self {
+		Pattern::MatchOr(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TypeParam {
+	TypeVar(TypeVar),
+	ParamSpec(ParamSpec),
+	TypeVarTuple(TypeVarTuple),
+}
+
+impl TypeParam {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		match self {
+			TypeParam::TypeVar(node) => node.for_each_child(f),
+			TypeParam::ParamSpec(node) => node.for_each_child(f),
+			TypeParam::TypeVarTuple(node) => node.for_each_child(f),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypeVar {
+	pub name: Identifier,
+	pub bound: Option<ExprId>,
+	pub default_value: Option<ExprId>,
+	pub span: Span,
+}
+
+impl TypeVar {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		if let Some(x) = &self.bound {
+			f(NodeId::Expr(*x));
+		}
+		if let Some(x) = &self.default_value {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<TypeVar> for TypeParam {
+	fn from(node: TypeVar) -> Self {
+		TypeParam::TypeVar(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParamSpec {
+	pub name: Identifier,
+	pub default_value: Option<ExprId>,
+	pub span: Span,
+}
+
+impl ParamSpec {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		if let Some(x) = &self.default_value {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<ParamSpec> for TypeParam {
+	fn from(node: ParamSpec) -> Self {
+		TypeParam::ParamSpec(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypeVarTuple {
+	pub name: Identifier,
+	pub default_value: Option<ExprId>,
+	pub span: Span,
+}
+
+impl TypeVarTuple {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		if let Some(x) = &self.default_value {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+impl From<TypeVarTuple> for TypeParam {
+	fn from(node: TypeVarTuple) -> Self {
+		TypeParam::TypeVarTuple(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Comprehension {
+	pub target: ExprId,
+	pub iter: ExprId,
+	pub ifs: Vec<ExprId>,
+	pub is_async: bool,
+}
+
+impl Comprehension {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.target));
+		f(NodeId::Expr(self.iter));
+		for x in &self.ifs {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Arguments {
+	pub posonlyargs: Vec<Arg>,
+	pub args: Vec<Arg>,
+	pub vararg: Option<Box<Arg>>,
+	pub kwonlyargs: Vec<Arg>,
+	pub kw_defaults: Vec<Option<ExprId>>,
+	pub kwarg: Option<Box<Arg>>,
+	pub defaults: Vec<ExprId>,
+}
+
+impl Arguments {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		for x in &self.posonlyargs {
+			x.for_each_child(f);
+		}
+		for x in &self.args {
+			x.for_each_child(f);
+		}
+		if let Some(x) = &self.vararg {
+			x.for_each_child(f);
+		}
+		for x in &self.kwonlyargs {
+			x.for_each_child(f);
+		}
+		for x in self.kw_defaults.iter().flatten() {
+			f(NodeId::Expr(*x));
+		}
+		if let Some(x) = &self.kwarg {
+			x.for_each_child(f);
+		}
+		for x in &self.defaults {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Arg {
+	pub arg: Identifier,
+	pub annotation: Option<ExprId>,
+	pub type_comment: Option<String>,
+	pub span: Span,
+}
+
+impl Arg {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		if let Some(x) = &self.annotation {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Keyword {
+	pub arg: Option<Identifier>,
+	pub value: ExprId,
+	pub span: Span,
+}
+
+impl Keyword {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.value));
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExceptHandler {
+	pub r#type: Option<ExprId>,
+	pub name: Option<Identifier>,
+	pub body: Vec<StmtId>,
+	pub span: Span,
+}
+
+impl ExceptHandler {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		if let Some(x) = &self.r#type {
+			f(NodeId::Expr(*x));
+		}
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Alias {
+	pub name: Identifier,
+	pub asname: Option<Identifier>,
+	pub span: Span,
+}
+
+impl Alias {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WithItem {
+	pub context_expr: ExprId,
+	pub optional_vars: Option<ExprId>,
+}
+
+impl WithItem {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Expr(self.context_expr));
+		if let Some(x) = &self.optional_vars {
+			f(NodeId::Expr(*x));
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchCase {
+	pub pattern: PatternId,
+	pub guard: Option<ExprId>,
+	pub body: Vec<StmtId>,
+}
+
+impl MatchCase {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {
+		f(NodeId::Pattern(self.pattern));
+		if let Some(x) = &self.guard {
+			f(NodeId::Expr(*x));
+		}
+		for x in &self.body {
+			f(NodeId::Stmt(*x));
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypeIgnore {
+	pub lineno: i32,
+	pub tag: String,
+}
+
+impl TypeIgnore {
+	/// Calls `f` with the id of every direct child node.
+	pub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {}
+}
+
+/// Owns every node of a tree, one `Vec` per node kind.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Ast {
+	mods: Vec<Mod>,
+	mod_parents: Vec<Option<NodeId>>,
+	stmts: Vec<Stmt>,
+	stmt_parents: Vec<Option<NodeId>>,
+	exprs: Vec<Expr>,
+	expr_parents: Vec<Option<NodeId>>,
+	patterns: Vec<Pattern>,
+	pattern_parents: Vec<Option<NodeId>>,
+	typeparams: Vec<TypeParam>,
+	typeparam_parents: Vec<Option<NodeId>>,
+}
+
+impl Ast {
+	/// Returns the node that has `id` as a direct child, if any.
+	pub fn parent(&self, id: impl Into<NodeId>) -> Option<NodeId> {
+		match id.into() {
+			NodeId::Mod(id) => self.mod_parents[id.index()],
+			NodeId::Stmt(id) => self.stmt_parents[id.index()],
+			NodeId::Expr(id) => self.expr_parents[id.index()],
+			NodeId::Pattern(id) => self.pattern_parents[id.index()],
+			NodeId::TypeParam(id) => self.typeparam_parents[id.index()],
+		}
+	}
+
+	fn set_parent(&mut self, id: NodeId, parent: NodeId) {
+		match id {
+			NodeId::Mod(id) => self.mod_parents[id.index()] = Some(parent),
+			NodeId::Stmt(id) => self.stmt_parents[id.index()] = Some(parent),
+			NodeId::Expr(id) => self.expr_parents[id.index()] = Some(parent),
+			NodeId::Pattern(id) => self.pattern_parents[id.index()] = Some(parent),
+			NodeId::TypeParam(id) => self.typeparam_parents[id.index()] = Some(parent),
+		}
+	}
+
+	/// Adds a node whose children are already in the arena and becomes their parent.
+	pub fn alloc_mod(&mut self, node: impl Into<Mod>) -> ModId {
+		let node = node.into();
+		let id = ModId(u32::try_from(self.mods.len()).expect("Arena is full"));
+		node.for_each_child(&mut |child| self.set_parent(child, id.into()));
+		self.mods.push(node);
+		self.mod_parents.push(None);
+		id
+	}
+
+	pub fn r#mod(&self, id: ModId) -> &Mod {
+		&self.mods[id.index()]
+	}
+
+	/// Children replaced through this reference keep their old parent entries.
+	pub fn mod_mut(&mut self, id: ModId) -> &mut Mod {
+		&mut self.mods[id.index()]
+	}
+
+	pub fn mods(&self) -> impl ExactSizeIterator<Item = (ModId, &Mod)> {
+		self.mods.iter().enumerate().map(|(i, node)| (ModId(i as u32), node))
+	}
+
+	/// Copies a tree of the enum AST into the arena, returning the id of its root.
+	pub fn lower_mod(&mut self, node: &ast::Mod) -> ModId {
+		let node = match node {
+			ast::Mod::Module(node) => Mod::Module(Module {
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+				type_ignores: node.type_ignores.iter().map(|x| self.lower_typeignore(x)).collect(),
+			}),
+			ast::Mod::Interactive(node) => Mod::Interactive(Interactive {
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+			}),
+			ast::Mod::Expression(node) => Mod::Expression(Expression {
+				body: self.lower_expr(&node.body),
+			}),
+			ast::Mod::FunctionType(node) => Mod::FunctionType(FunctionType {
+				argtypes: node.argtypes.iter().map(|x| self.lower_expr(x)).collect(),
+				returns: self.lower_expr(&node.returns),
+			}),
+		};
+		self.alloc_mod(node)
+	}
+
+	/// Adds a node whose children are already in the arena and becomes their parent.
+	pub fn alloc_stmt(&mut self, node: impl Into<Stmt>) -> StmtId {
+		let node = node.into();
+		let id = StmtId(u32::try_from(self.stmts.len()).expect("Arena is full"));
+		node.for_each_child(&mut |child| self.set_parent(child, id.into()));
+		self.stmts.push(node);
+		self.stmt_parents.push(None);
+		id
+	}
+
+	pub fn stmt(&self, id: StmtId) -> &Stmt {
+		&self.stmts[id.index()]
+	}
+
+	/// Children replaced through this reference keep their old parent entries.
+	pub fn stmt_mut(&mut self, id: StmtId) -> &mut Stmt {
+		&mut self.stmts[id.index()]
+	}
+
+	pub fn stmts(&self) -> impl ExactSizeIterator<Item = (StmtId, &Stmt)> {
+		self.stmts.iter().enumerate().map(|(i, node)| (StmtId(i as u32), node))
+	}
+
+	/// Copies a tree of the enum AST into the arena, returning the id of its root.
+	pub fn lower_stmt(&mut self, node: &ast::Stmt) -> StmtId {
+		let node = match node {
+			ast::Stmt::FunctionDef(node) => Stmt::FunctionDef(FunctionDef {
+				name: node.name.clone(),
+				args: Box::new(self.lower_arguments(&node.args)),
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+				decorator_list: node.decorator_list.iter().map(|x| self.lower_expr(x)).collect(),
+				returns: node.returns.as_ref().map(|x| self.lower_expr(x)),
+				type_comment: node.type_comment.clone(),
+				type_params: node.type_params.iter().map(|x| self.lower_typeparam(x)).collect(),
+				span: node.span,
+			}),
+			ast::Stmt::AsyncFunctionDef(node) => Stmt::AsyncFunctionDef(AsyncFunctionDef {
+				name: node.name.clone(),
+				args: Box::new(self.lower_arguments(&node.args)),
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+				decorator_list: node.decorator_list.iter().map(|x| self.lower_expr(x)).collect(),
+				returns: node.returns.as_ref().map(|x| self.lower_expr(x)),
+				type_comment: node.type_comment.clone(),
+				type_params: node.type_params.iter().map(|x| self.lower_typeparam(x)).collect(),
+				span: node.span,
+			}),
+			ast::Stmt::ClassDef(node) => Stmt::ClassDef(ClassDef {
+				name: node.name.clone(),
+				bases: node.bases.iter().map(|x| self.lower_expr(x)).collect(),
+				keywords: node.keywords.iter().map(|x| self.lower_keyword(x)).collect(),
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+				decorator_list: node.decorator_list.iter().map(|x| self.lower_expr(x)).collect(),
+				type_params: node.type_params.iter().map(|x| self.lower_typeparam(x)).collect(),
+				span: node.span,
+			}),
+			ast::Stmt::Return(node) => Stmt::Return(Return {
+				value: node.value.as_ref().map(|x| self.lower_expr(x)),
+				span: node.span,
+			}),
+			ast::Stmt::Delete(node) => Stmt::Delete(Delete {
+				targets: node.targets.iter().map(|x| self.lower_expr(x)).collect(),
+				span: node.span,
+			}),
+			ast::Stmt::Assign(node) => Stmt::Assign(Assign {
+				targets: node.targets.iter().map(|x| self.lower_expr(x)).collect(),
+				value: self.lower_expr(&node.value),
+				type_comment: node.type_comment.clone(),
+				span: node.span,
+			}),
+			ast::Stmt::TypeAlias(node) => Stmt::TypeAlias(TypeAlias {
+				name: self.lower_expr(&node.name),
+				type_params: node.type_params.iter().map(|x| self.lower_typeparam(x)).collect(),
+				value: self.lower_expr(&node.value),
+				span: node.span,
+			}),
+			ast::Stmt::AugAssign(node) => Stmt::AugAssign(AugAssign {
+				target: self.lower_expr(&node.target),
+				op: node.op,
+				value: self.lower_expr(&node.value),
+				span: node.span,
+			}),
+			ast::Stmt::AnnAssign(node) => Stmt::AnnAssign(AnnAssign {
+				target: self.lower_expr(&node.target),
+				annotation: self.lower_expr(&node.annotation),
+				value: node.value.as_ref().map(|x| self.lower_expr(x)),
+				simple: node.simple,
+				span: node.span,
+			}),
+			ast::Stmt::For(node) => Stmt::For(For {
+				target: self.lower_expr(&node.target),
+				iter: self.lower_expr(&node.iter),
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+				orelse: node.orelse.iter().map(|x| self.lower_stmt(x)).collect(),
+				type_comment: node.type_comment.clone(),
+				span: node.span,
+			}),
+			ast::Stmt::AsyncFor(node) => Stmt::AsyncFor(AsyncFor {
+				target: self.lower_expr(&node.target),
+				iter: self.lower_expr(&node.iter),
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+				orelse: node.orelse.iter().map(|x| self.lower_stmt(x)).collect(),
+				type_comment: node.type_comment.clone(),
+				span: node.span,
+			}),
+			ast::Stmt::While(node) => Stmt::While(While {
+				test: self.lower_expr(&node.test),
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+				orelse: node.orelse.iter().map(|x| self.lower_stmt(x)).collect(),
+				span: node.span,
+			}),
+			ast::Stmt::If(node) => Stmt::If(If {
+				test: self.lower_expr(&node.test),
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+				orelse: node.orelse.iter().map(|x| self.lower_stmt(x)).collect(),
+				span: node.span,
+			}),
+			ast::Stmt::With(node) => Stmt::With(With {
+				items: node.items.iter().map(|x| self.lower_withitem(x)).collect(),
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+				type_comment: node.type_comment.clone(),
+				span: node.span,
+			}),
+			ast::Stmt::AsyncWith(node) => Stmt::AsyncWith(AsyncWith {
+				items: node.items.iter().map(|x| self.lower_withitem(x)).collect(),
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+				type_comment: node.type_comment.clone(),
+				span: node.span,
+			}),
+			ast::Stmt::Match(node) => Stmt::Match(Match {
+				subject: self.lower_expr(&node.subject),
+				cases: node.cases.iter().map(|x| self.lower_matchcase(x)).collect(),
+				span: node.span,
+			}),
+			ast::Stmt::Raise(node) => Stmt::Raise(Raise {
+				exc: node.exc.as_ref().map(|x| self.lower_expr(x)),
+				cause: node.cause.as_ref().map(|x| self.lower_expr(x)),
+				span: node.span,
+			}),
+			ast::Stmt::Try(node) => Stmt::Try(Try {
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+				handlers: node.handlers.iter().map(|x| self.lower_excepthandler(x)).collect(),
+				orelse: node.orelse.iter().map(|x| self.lower_stmt(x)).collect(),
+				finalbody: node.finalbody.iter().map(|x| self.lower_stmt(x)).collect(),
+				span: node.span,
+			}),
+			ast::Stmt::TryStar(node) => Stmt::TryStar(TryStar {
+				body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+				handlers: node.handlers.iter().map(|x| self.lower_excepthandler(x)).collect(),
+				orelse: node.orelse.iter().map(|x| self.lower_stmt(x)).collect(),
+				finalbody: node.finalbody.iter().map(|x| self.lower_stmt(x)).collect(),
+				span: node.span,
+			}),
+			ast::Stmt::Assert(node) => Stmt::Assert(Assert {
+				test: self.lower_expr(&node.test),
+				msg: node.msg.as_ref().map(|x| self.lower_expr(x)),
+				span: node.span,
+			}),
+			ast::Stmt::Import(node) => Stmt::Import(Import {
+				names: node.names.iter().map(|x| self.lower_alias(x)).collect(),
+				span: node.span,
+			}),
+			ast::Stmt::ImportFrom(node) => Stmt::ImportFrom(ImportFrom {
+				module: node.module.clone(),
+				names: node.names.iter().map(|x| self.lower_alias(x)).collect(),
+				level: node.level,
+				span: node.span,
+			}),
+			ast::Stmt::Global(node) => Stmt::Global(Global {
+				names: node.names.clone(),
+				span: node.span,
+			}),
+			ast::Stmt::Nonlocal(node) => Stmt::Nonlocal(Nonlocal {
+				names: node.names.clone(),
+				span: node.span,
+			}),
+			ast::Stmt::ExprStmt(node) => Stmt::ExprStmt(ExprStmt {
+				value: self.lower_expr(&node.value),
+				span: node.span,
+			}),
+			ast::Stmt::Pass(node) => Stmt::Pass(Pass {
+				span: node.span,
+			}),
+			ast::Stmt::Break(node) => Stmt::Break(Break {
+				span: node.span,
+			}),
+			ast::Stmt::Continue(node) => Stmt::Continue(Continue {
+				span: node.span,
+			}),
+			ast::Stmt::ErrorStmt(node) => Stmt::ErrorStmt(ErrorStmt {
+				span: node.span,
+			}),
+		};
+		self.alloc_stmt(node)
+	}
+
+	/// Adds a node whose children are already in the arena and becomes their parent.
+	pub fn alloc_expr(&mut self, node: impl Into<Expr>) -> ExprId {
+		let node = node.into();
+		let id = ExprId(u32::try_from(self.exprs.len()).expect("Arena is full"));
+		node.for_each_child(&mut |child| self.set_parent(child, id.into()));
+		self.exprs.push(node);
+		self.expr_parents.push(None);
+		id
+	}
+
+	pub fn expr(&self, id: ExprId) -> &Expr {
+		&self.exprs[id.index()]
+	}
+
+	/// Children replaced through this reference keep their old parent entries.
+	pub fn expr_mut(&mut self, id: ExprId) -> &mut Expr {
+		&mut self.exprs[id.index()]
+	}
+
+	pub fn exprs(&self) -> impl ExactSizeIterator<Item = (ExprId, &Expr)> {
+		self.exprs.iter().enumerate().map(|(i, node)| (ExprId(i as u32), node))
+	}
+
+	/// Copies a tree of the enum AST into the arena, returning the id of its root.
+	pub fn lower_expr(&mut self, node: &ast::Expr) -> ExprId {
+		let node = match node {
+			ast::Expr::BoolOp(node) => Expr::BoolOp(BoolOp {
+				op: node.op,
+				values: node.values.iter().map(|x| self.lower_expr(x)).collect(),
+				span: node.span,
+			}),
+			ast::Expr::NamedExpr(node) => Expr::NamedExpr(NamedExpr {
+				target: self.lower_expr(&node.target),
+				value: self.lower_expr(&node.value),
+				span: node.span,
+			}),
+			ast::Expr::BinOp(node) => Expr::BinOp(BinOp {
+				left: self.lower_expr(&node.left),
+				op: node.op,
+				right: self.lower_expr(&node.right),
+				span: node.span,
+			}),
+			ast::Expr::UnaryOp(node) => Expr::UnaryOp(UnaryOp {
+				op: node.op,
+				operand: self.lower_expr(&node.operand),
+				span: node.span,
+			}),
+			ast::Expr::Lambda(node) => Expr::Lambda(Lambda {
+				args: Box::new(self.lower_arguments(&node.args)),
+				body: self.lower_expr(&node.body),
+				span: node.span,
+			}),
+			ast::Expr::IfExp(node) => Expr::IfExp(IfExp {
+				test: self.lower_expr(&node.test),
+				body: self.lower_expr(&node.body),
+				orelse: self.lower_expr(&node.orelse),
+				span: node.span,
+			}),
+			ast::Expr::Dict(node) => Expr::Dict(Dict {
+				keys: node.keys.iter().map(|x| x.as_ref().map(|x| self.lower_expr(x))).collect(),
+				values: node.values.iter().map(|x| self.lower_expr(x)).collect(),
+				span: node.span,
+			}),
+			ast::Expr::Set(node) => Expr::Set(Set {
+				elts: node.elts.iter().map(|x| self.lower_expr(x)).collect(),
+				span: node.span,
+			}),
+			ast::Expr::ListComp(node) => Expr::ListComp(ListComp {
+				elt: self.lower_expr(&node.elt),
+				generators: node.generators.iter().map(|x| self.lower_comprehension(x)).collect(),
+				span: node.span,
+			}),
+			ast::Expr::SetComp(node) => Expr::SetComp(SetComp {
+				elt: self.lower_expr(&node.elt),
+				generators: node.generators.iter().map(|x| self.lower_comprehension(x)).collect(),
+				span: node.span,
+			}),
+			ast::Expr::DictComp(node) => Expr::DictComp(DictComp {
+				key: self.lower_expr(&node.key),
+				value: self.lower_expr(&node.value),
+				generators: node.generators.iter().map(|x| self.lower_comprehension(x)).collect(),
+				span: node.span,
+			}),
+			ast::Expr::GeneratorExp(node) => Expr::GeneratorExp(GeneratorExp {
+				elt: self.lower_expr(&node.elt),
+				generators: node.generators.iter().map(|x| self.lower_comprehension(x)).collect(),
+				span: node.span,
+			}),
+			ast::Expr::Await(node) => Expr::Await(Await {
+				value: self.lower_expr(&node.value),
+				span: node.span,
+			}),
+			ast::Expr::Yield(node) => Expr::Yield(Yield {
+				value: node.value.as_ref().map(|x| self.lower_expr(x)),
+				span: node.span,
+			}),
+			ast::Expr::YieldFrom(node) => Expr::YieldFrom(YieldFrom {
+				value: self.lower_expr(&node.value),
+				span: node.span,
+			}),
+			ast::Expr::Compare(node) => Expr::Compare(Compare {
+				left: self.lower_expr(&node.left),
+				ops: node.ops.clone(),
+				comparators: node.comparators.iter().map(|x| self.lower_expr(x)).collect(),
+				span: node.span,
+			}),
+			ast::Expr::Call(node) => Expr::Call(Call {
+				func: self.lower_expr(&node.func),
+				args: node.args.iter().map(|x| self.lower_expr(x)).collect(),
+				keywords: node.keywords.iter().map(|x| self.lower_keyword(x)).collect(),
+				span: node.span,
+			}),
+			ast::Expr::FormattedValue(node) => Expr::FormattedValue(FormattedValue {
+				value: self.lower_expr(&node.value),
+				conversion: node.conversion,
+				format_spec: node.format_spec.as_ref().map(|x| self.lower_expr(x)),
+				span: node.span,
+			}),
+			ast::Expr::JoinedStr(node) => Expr::JoinedStr(JoinedStr {
+				values: node.values.iter().map(|x| self.lower_expr(x)).collect(),
+				span: node.span,
+			}),
+			ast::Expr::Constant(node) => Expr::Constant(Constant {
+				value: node.value.clone(),
+				kind: node.kind.clone(),
+				span: node.span,
+			}),
+			ast::Expr::Attribute(node) => Expr::Attribute(Attribute {
+				value: self.lower_expr(&node.value),
+				attr: node.attr.clone(),
+				ctx: node.ctx,
+				span: node.span,
+			}),
+			ast::Expr::Subscript(node) => Expr::Subscript(Subscript {
+				value: self.lower_expr(&node.value),
+				slice: self.lower_expr(&node.slice),
+				ctx: node.ctx,
+				span: node.span,
+			}),
+			ast::Expr::Starred(node) => Expr::Starred(Starred {
+				value: self.lower_expr(&node.value),
+				ctx: node.ctx,
+				span: node.span,
+			}),
+			ast::Expr::Name(node) => Expr::Name(Name {
+				id: node.id.clone(),
+				ctx: node.ctx,
+				span: node.span,
+			}),
+			ast::Expr::List(node) => Expr::List(List {
+				elts: node.elts.iter().map(|x| self.lower_expr(x)).collect(),
+				ctx: node.ctx,
+				span: node.span,
+			}),
+			ast::Expr::Tuple(node) => Expr::Tuple(Tuple {
+				elts: node.elts.iter().map(|x| self.lower_expr(x)).collect(),
+				ctx: node.ctx,
+				span: node.span,
+			}),
+			ast::Expr::Slice(node) => Expr::Slice(Slice {
+				lower: node.lower.as_ref().map(|x| self.lower_expr(x)),
+				upper: node.upper.as_ref().map(|x| self.lower_expr(x)),
+				step: node.step.as_ref().map(|x| self.lower_expr(x)),
+				span: node.span,
+			}),
+			ast::Expr::ErrorExpr(node) => Expr::ErrorExpr(ErrorExpr {
+				span: node.span,
+			}),
+		};
+		self.alloc_expr(node)
+	}
+
+	/// Adds a node whose children are already in the arena and becomes their parent.
+	pub fn alloc_pattern(&mut self, node: impl Into<Pattern>) -> PatternId {
+		let node = node.into();
+		let id = PatternId(u32::try_from(self.patterns.len()).expect("Arena is full"));
+		node.for_each_child(&mut |child| self.set_parent(child, id.into()));
+		self.patterns.push(node);
+		self.pattern_parents.push(None);
+		id
+	}
+
+	pub fn pattern(&self, id: PatternId) -> &Pattern {
+		&self.patterns[id.index()]
+	}
+
+	/// Children replaced through this reference keep their old parent entries.
+	pub fn pattern_mut(&mut self, id: PatternId) -> &mut Pattern {
+		&mut self.patterns[id.index()]
+	}
+
+	pub fn patterns(&self) -> impl ExactSizeIterator<Item = (PatternId, &Pattern)> {
+		self.patterns.iter().enumerate().map(|(i, node)| (PatternId(i as u32), node))
+	}
+
+	/// Copies a tree of the enum AST into the arena, returning the id of its root.
+	pub fn lower_pattern(&mut self, node: &ast::Pattern) -> PatternId {
+		let node = match node {
+			ast::Pattern::MatchValue(node) => Pattern::MatchValue(MatchValue {
+				value: self.lower_expr(&node.value),
+				span: node.span,
+			}),
+			ast::Pattern::MatchSingleton(node) => Pattern::MatchSingleton(MatchSingleton {
+				value: node.value.clone(),
+				span: node.span,
+			}),
+			ast::Pattern::MatchSequence(node) => Pattern::MatchSequence(MatchSequence {
+				patterns: node.patterns.iter().map(|x| self.lower_pattern(x)).collect(),
+				span: node.span,
+			}),
+			ast::Pattern::MatchMapping(node) => Pattern::MatchMapping(MatchMapping {
+				keys: node.keys.iter().map(|x| self.lower_expr(x)).collect(),
+				patterns: node.patterns.iter().map(|x| self.lower_pattern(x)).collect(),
+				rest: node.rest.clone(),
+				span: node.span,
+			}),
+			ast::Pattern::MatchClass(node) => Pattern::MatchClass(MatchClass {
+				cls: self.lower_expr(&node.cls),
+				patterns: node.patterns.iter().map(|x| self.lower_pattern(x)).collect(),
+				kwd_attrs: node.kwd_attrs.clone(),
+				kwd_patterns: node.kwd_patterns.iter().map(|x| self.lower_pattern(x)).collect(),
+				span: node.span,
+			}),
+			ast::Pattern::MatchStar(node) => Pattern::MatchStar(MatchStar {
+				name: node.name.clone(),
+				span: node.span,
+			}),
+			ast::Pattern::MatchAs(node) => Pattern::MatchAs(MatchAs {
+				pattern: node.pattern.as_ref().map(|x| self.lower_pattern(x)),
+				name: node.name.clone(),
+				span: node.span,
+			}),
+			ast::Pattern::MatchOr(node) => Pattern::MatchOr(MatchOr {
+				patterns: node.patterns.iter().map(|x| self.lower_pattern(x)).collect(),
+				span: node.span,
+			}),
+		};
+		self.alloc_pattern(node)
+	}
+
+	/// Adds a node whose children are already in the arena and becomes their parent.
+	pub fn alloc_typeparam(&mut self, node: impl Into<TypeParam>) -> TypeParamId {
+		let node = node.into();
+		let id = TypeParamId(u32::try_from(self.typeparams.len()).expect("Arena is full"));
+		node.for_each_child(&mut |child| self.set_parent(child, id.into()));
+		self.typeparams.push(node);
+		self.typeparam_parents.push(None);
+		id
+	}
+
+	pub fn typeparam(&self, id: TypeParamId) -> &TypeParam {
+		&self.typeparams[id.index()]
+	}
+
+	/// Children replaced through this reference keep their old parent entries.
+	pub fn typeparam_mut(&mut self, id: TypeParamId) -> &mut TypeParam {
+		&mut self.typeparams[id.index()]
+	}
+
+	pub fn typeparams(&self) -> impl ExactSizeIterator<Item = (TypeParamId, &TypeParam)> {
+		self.typeparams.iter().enumerate().map(|(i, node)| (TypeParamId(i as u32), node))
+	}
+
+	/// Copies a tree of the enum AST into the arena, returning the id of its root.
+	pub fn lower_typeparam(&mut self, node: &ast::TypeParam) -> TypeParamId {
+		let node = match node {
+			ast::TypeParam::TypeVar(node) => TypeParam::TypeVar(TypeVar {
+				name: node.name.clone(),
+				bound: node.bound.as_ref().map(|x| self.lower_expr(x)),
+				default_value: node.default_value.as_ref().map(|x| self.lower_expr(x)),
+				span: node.span,
+			}),
+			ast::TypeParam::ParamSpec(node) => TypeParam::ParamSpec(ParamSpec {
+				name: node.name.clone(),
+				default_value: node.default_value.as_ref().map(|x| self.lower_expr(x)),
+				span: node.span,
+			}),
+			ast::TypeParam::TypeVarTuple(node) => TypeParam::TypeVarTuple(TypeVarTuple {
+				name: node.name.clone(),
+				default_value: node.default_value.as_ref().map(|x| self.lower_expr(x)),
+				span: node.span,
+			}),
+		};
+		self.alloc_typeparam(node)
+	}
+
+	fn lower_comprehension(&mut self, node: &ast::Comprehension) -> Comprehension {
+		Comprehension {
+			target: self.lower_expr(&node.target),
+			iter: self.lower_expr(&node.iter),
+			ifs: node.ifs.iter().map(|x| self.lower_expr(x)).collect(),
+			is_async: node.is_async,
+		}
+	}
+
+	fn lower_arguments(&mut self, node: &ast::Arguments) -> Arguments {
+		Arguments {
+			posonlyargs: node.posonlyargs.iter().map(|x| self.lower_arg(x)).collect(),
+			args: node.args.iter().map(|x| self.lower_arg(x)).collect(),
+			vararg: node.vararg.as_ref().map(|x| Box::new(self.lower_arg(x))),
+			kwonlyargs: node.kwonlyargs.iter().map(|x| self.lower_arg(x)).collect(),
+			kw_defaults: node.kw_defaults.iter().map(|x| x.as_ref().map(|x| self.lower_expr(x))).collect(),
+			kwarg: node.kwarg.as_ref().map(|x| Box::new(self.lower_arg(x))),
+			defaults: node.defaults.iter().map(|x| self.lower_expr(x)).collect(),
+		}
+	}
+
+	fn lower_arg(&mut self, node: &ast::Arg) -> Arg {
+		Arg {
+			arg: node.arg.clone(),
+			annotation: node.annotation.as_ref().map(|x| self.lower_expr(x)),
+			type_comment: node.type_comment.clone(),
+			span: node.span,
+		}
+	}
+
+	fn lower_keyword(&mut self, node: &ast::Keyword) -> Keyword {
+		Keyword {
+			arg: node.arg.clone(),
+			value: self.lower_expr(&node.value),
+			span: node.span,
+		}
+	}
+
+	fn lower_excepthandler(&mut self, node: &ast::ExceptHandler) -> ExceptHandler {
+		ExceptHandler {
+			r#type: node.r#type.as_ref().map(|x| self.lower_expr(x)),
+			name: node.name.clone(),
+			body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+			span: node.span,
+		}
+	}
+
+	fn lower_alias(&mut self, node: &ast::Alias) -> Alias {
+		Alias {
+			name: node.name.clone(),
+			asname: node.asname.clone(),
+			span: node.span,
+		}
+	}
+
+	fn lower_withitem(&mut self, node: &ast::WithItem) -> WithItem {
+		WithItem {
+			context_expr: self.lower_expr(&node.context_expr),
+			optional_vars: node.optional_vars.as_ref().map(|x| self.lower_expr(x)),
+		}
+	}
+
+	fn lower_matchcase(&mut self, node: &ast::MatchCase) -> MatchCase {
+		MatchCase {
+			pattern: self.lower_pattern(&node.pattern),
+			guard: node.guard.as_ref().map(|x| self.lower_expr(x)),
+			body: node.body.iter().map(|x| self.lower_stmt(x)).collect(),
+		}
+	}
+
+	fn lower_typeignore(&mut self, node: &ast::TypeIgnore) -> TypeIgnore {
+		TypeIgnore {
+			lineno: node.lineno,
+			tag: node.tag.clone(),
+		}
+	}
+
+}
+
+impl Index<ModId> for Ast {
+	type Output = Mod;
+
+	fn index(&self, id: ModId) -> &Mod {
+		self.r#mod(id)
+	}
+}
+
+impl Index<StmtId> for Ast {
+	type Output = Stmt;
+
+	fn index(&self, id: StmtId) -> &Stmt {
+		self.stmt(id)
+	}
+}
+
+impl Index<ExprId> for Ast {
+	type Output = Expr;
+
+	fn index(&self, id: ExprId) -> &Expr {
+		self.expr(id)
+	}
+}
+
+impl Index<PatternId> for Ast {
+	type Output = Pattern;
+
+	fn index(&self, id: PatternId) -> &Pattern {
+		self.pattern(id)
+	}
+}
+
+impl Index<TypeParamId> for Ast {
+	type Output = TypeParam;
+
+	fn index(&self, id: TypeParamId) -> &TypeParam {
+		self.typeparam(id)
+	}
+}
+
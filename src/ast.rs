@@ -0,0 +1,2554 @@
+// Generated by `pragrat generate ast --mode enum`. Do not edit.
+use crate::dump::{Dump, DumpValue};
+use crate::types::{Identifier, Literal, Span};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type"))]
+pub enum Mod {
+	Module(Module),
+	Interactive(Interactive),
+	Expression(Expression),
+	FunctionType(FunctionType),
+}
+
+impl Mod {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Mod::Module(_) => "Module",
+			Mod::Interactive(_) => "Interactive",
+			Mod::Expression(_) => "Expression",
+			Mod::FunctionType(_) => "FunctionType",
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Module {
+	pub body: Vec<Stmt>,
+	pub type_ignores: Vec<TypeIgnore>,
+}
+
+impl From<Module> for Mod {
+	fn from(node: Module) -> Self {
+		Mod::Module(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interactive {
+	pub body: Vec<Stmt>,
+}
+
+impl From<Interactive> for Mod {
+	fn from(node: Interactive) -> Self {
+		Mod::Interactive(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Expression {
+	pub body: Box<Expr>,
+}
+
+impl From<Expression> for Mod {
+	fn from(node: Expression) -> Self {
+		Mod::Expression(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FunctionType {
+	pub argtypes: Vec<Expr>,
+	pub returns: Box<Expr>,
+}
+
+impl From<FunctionType> for Mod {
+	fn from(node: FunctionType) -> Self {
+		Mod::FunctionType(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type"))]
+pub enum Stmt {
+	FunctionDef(FunctionDef),
+	AsyncFunctionDef(AsyncFunctionDef),
+	ClassDef(ClassDef),
+	Return(Return),
+	Delete(Delete),
+	Assign(Assign),
+	TypeAlias(TypeAlias),
+	AugAssign(AugAssign),
+	AnnAssign(AnnAssign),
+	For(For),
+	AsyncFor(AsyncFor),
+	While(While),
+	If(If),
+	With(With),
+	AsyncWith(AsyncWith),
+	Match(Match),
+	Raise(Raise),
+	Try(Try),
+	TryStar(TryStar),
+	Assert(Assert),
+	Import(Import),
+	ImportFrom(ImportFrom),
+	Global(Global),
+	Nonlocal(Nonlocal),
+	#[cfg_attr(feature = "serde", serde(rename = "Expr"))]
+	ExprStmt(ExprStmt),
+	Pass(Pass),
+	Break(Break),
+	Continue(Continue),
+	#[cfg_attr(feature = "serde", serde(rename = "Error"))]
+	ErrorStmt(ErrorStmt),
+}
+
+impl Stmt {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Stmt::FunctionDef(_) => "FunctionDef",
+			Stmt::AsyncFunctionDef(_) => "AsyncFunctionDef",
+			Stmt::ClassDef(_) => "ClassDef",
+			Stmt::Return(_) => "Return",
+			Stmt::Delete(_) => "Delete",
+			Stmt::Assign(_) => "Assign",
+			Stmt::TypeAlias(_) => "TypeAlias",
+			Stmt::AugAssign(_) => "AugAssign",
+			Stmt::AnnAssign(_) => "AnnAssign",
+			Stmt::For(_) => "For",
+			Stmt::AsyncFor(_) => "AsyncFor",
+			Stmt::While(_) => "While",
+			Stmt::If(_) => "If",
+			Stmt::With(_) => "With",
+			Stmt::AsyncWith(_) => "AsyncWith",
+			Stmt::Match(_) => "Match",
+			Stmt::Raise(_) => "Raise",
+			Stmt::Try(_) => "Try",
+			Stmt::TryStar(_) => "TryStar",
+			Stmt::Assert(_) => "Assert",
+			Stmt::Import(_) => "Import",
+			Stmt::ImportFrom(_) => "ImportFrom",
+			Stmt::Global(_) => "Global",
+			Stmt::Nonlocal(_) => "Nonlocal",
+			Stmt::ExprStmt(_) => "Expr",
+			Stmt::Pass(_) => "Pass",
+			Stmt::Break(_) => "Break",
+			Stmt::Continue(_) => "Continue",
+			Stmt::ErrorStmt(_) => "Error",
+		}
+	}
+}
+
+impl Stmt {
+	pub fn span(&self) -> Span {
+		match self {
+			Stmt::FunctionDef(node) => node.span,
+			Stmt::AsyncFunctionDef(node) => node.span,
+			Stmt::ClassDef(node) => node.span,
+			Stmt::Return(node) => node.span,
+			Stmt::Delete(node) => node.span,
+			Stmt::Assign(node) => node.span,
+			Stmt::TypeAlias(node) => node.span,
+			Stmt::AugAssign(node) => node.span,
+			Stmt::AnnAssign(node) => node.span,
+			Stmt::For(node) => node.span,
+			Stmt::AsyncFor(node) => node.span,
+			Stmt::While(node) => node.span,
+			Stmt::If(node) => node.span,
+			Stmt::With(node) => node.span,
+			Stmt::AsyncWith(node) => node.span,
+			Stmt::Match(node) => node.span,
+			Stmt::Raise(node) => node.span,
+			Stmt::Try(node) => node.span,
+			Stmt::TryStar(node) => node.span,
+			Stmt::Assert(node) => node.span,
+			Stmt::Import(node) => node.span,
+			Stmt::ImportFrom(node) => node.span,
+			Stmt::Global(node) => node.span,
+			Stmt::Nonlocal(node) => node.span,
+			Stmt::ExprStmt(node) => node.span,
+			Stmt::Pass(node) => node.span,
+			Stmt::Break(node) => node.span,
+			Stmt::Continue(node) => node.span,
+			Stmt::ErrorStmt(node) => node.span,
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FunctionDef {
+	pub name: Identifier,
+	pub args: Box<Arguments>,
+	pub body: Vec<Stmt>,
+	pub decorator_list: Vec<Expr>,
+	pub returns: Option<Box<Expr>>,
+	pub type_comment: Option<String>,
+	pub type_params: Vec<TypeParam>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<FunctionDef> for Stmt {
+	fn from(node: FunctionDef) -> Self {
+		Stmt::FunctionDef(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AsyncFunctionDef {
+	pub name: Identifier,
+	pub args: Box<Arguments>,
+	pub body: Vec<Stmt>,
+	pub decorator_list: Vec<Expr>,
+	pub returns: Option<Box<Expr>>,
+	pub type_comment: Option<String>,
+	pub type_params: Vec<TypeParam>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<AsyncFunctionDef> for Stmt {
+	fn from(node: AsyncFunctionDef) -> Self {
+		Stmt::AsyncFunctionDef(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassDef {
+	pub name: Identifier,
+	pub bases: Vec<Expr>,
+	pub keywords: Vec<Keyword>,
+	pub body: Vec<Stmt>,
+	pub decorator_list: Vec<Expr>,
+	pub type_params: Vec<TypeParam>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<ClassDef> for Stmt {
+	fn from(node: ClassDef) -> Self {
+		Stmt::ClassDef(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Return {
+	pub value: Option<Box<Expr>>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Return> for Stmt {
+	fn from(node: Return) -> Self {
+		Stmt::Return(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Delete {
+	pub targets: Vec<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Delete> for Stmt {
+	fn from(node: Delete) -> Self {
+		Stmt::Delete(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Assign {
+	pub targets: Vec<Expr>,
+	pub value: Box<Expr>,
+	pub type_comment: Option<String>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Assign> for Stmt {
+	fn from(node: Assign) -> Self {
+		Stmt::Assign(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeAlias {
+	pub name: Box<Expr>,
+	pub type_params: Vec<TypeParam>,
+	pub value: Box<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<TypeAlias> for Stmt {
+	fn from(node: TypeAlias) -> Self {
+		Stmt::TypeAlias(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AugAssign {
+	pub target: Box<Expr>,
+	pub op: Operator,
+	pub value: Box<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<AugAssign> for Stmt {
+	fn from(node: AugAssign) -> Self {
+		Stmt::AugAssign(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnnAssign {
+	pub target: Box<Expr>,
+	pub annotation: Box<Expr>,
+	pub value: Option<Box<Expr>>,
+	pub simple: i32,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<AnnAssign> for Stmt {
+	fn from(node: AnnAssign) -> Self {
+		Stmt::AnnAssign(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct For {
+	pub target: Box<Expr>,
+	pub iter: Box<Expr>,
+	pub body: Vec<Stmt>,
+	pub orelse: Vec<Stmt>,
+	pub type_comment: Option<String>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<For> for Stmt {
+	fn from(node: For) -> Self {
+		Stmt::For(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AsyncFor {
+	pub target: Box<Expr>,
+	pub iter: Box<Expr>,
+	pub body: Vec<Stmt>,
+	pub orelse: Vec<Stmt>,
+	pub type_comment: Option<String>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<AsyncFor> for Stmt {
+	fn from(node: AsyncFor) -> Self {
+		Stmt::AsyncFor(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct While {
+	pub test: Box<Expr>,
+	pub body: Vec<Stmt>,
+	pub orelse: Vec<Stmt>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<While> for Stmt {
+	fn from(node: While) -> Self {
+		Stmt::While(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct If {
+	pub test: Box<Expr>,
+	pub body: Vec<Stmt>,
+	pub orelse: Vec<Stmt>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<If> for Stmt {
+	fn from(node: If) -> Self {
+		Stmt::If(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct With {
+	pub items: Vec<WithItem>,
+	pub body: Vec<Stmt>,
+	pub type_comment: Option<String>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<With> for Stmt {
+	fn from(node: With) -> Self {
+		Stmt::With(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AsyncWith {
+	pub items: Vec<WithItem>,
+	pub body: Vec<Stmt>,
+	pub type_comment: Option<String>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<AsyncWith> for Stmt {
+	fn from(node: AsyncWith) -> Self {
+		Stmt::AsyncWith(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Match {
+	pub subject: Box<Expr>,
+	pub cases: Vec<MatchCase>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Match> for Stmt {
+	fn from(node: Match) -> Self {
+		Stmt::Match(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Raise {
+	pub exc: Option<Box<Expr>>,
+	pub cause: Option<Box<Expr>>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Raise> for Stmt {
+	fn from(node: Raise) -> Self {
+		Stmt::Raise(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Try {
+	pub body: Vec<Stmt>,
+	pub handlers: Vec<ExceptHandler>,
+	pub orelse: Vec<Stmt>,
+	pub finalbody: Vec<Stmt>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Try> for Stmt {
+	fn from(node: Try) -> Self {
+		Stmt::Try(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TryStar {
+	pub body: Vec<Stmt>,
+	pub handlers: Vec<ExceptHandler>,
+	pub orelse: Vec<Stmt>,
+	pub finalbody: Vec<Stmt>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<TryStar> for Stmt {
+	fn from(node: TryStar) -> Self {
+		Stmt::TryStar(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Assert {
+	pub test: Box<Expr>,
+	pub msg: Option<Box<Expr>>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Assert> for Stmt {
+	fn from(node: Assert) -> Self {
+		Stmt::Assert(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Import {
+	pub names: Vec<Alias>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Import> for Stmt {
+	fn from(node: Import) -> Self {
+		Stmt::Import(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImportFrom {
+	pub module: Option<Identifier>,
+	pub names: Vec<Alias>,
+	pub level: i32,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<ImportFrom> for Stmt {
+	fn from(node: ImportFrom) -> Self {
+		Stmt::ImportFrom(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Global {
+	pub names: Vec<Identifier>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Global> for Stmt {
+	fn from(node: Global) -> Self {
+		Stmt::Global(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Nonlocal {
+	pub names: Vec<Identifier>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Nonlocal> for Stmt {
+	fn from(node: Nonlocal) -> Self {
+		Stmt::Nonlocal(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExprStmt {
+	pub value: Box<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<ExprStmt> for Stmt {
+	fn from(node: ExprStmt) -> Self {
+		Stmt::ExprStmt(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pass {
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Pass> for Stmt {
+	fn from(node: Pass) -> Self {
+		Stmt::Pass(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Break {
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Break> for Stmt {
+	fn from(node: Break) -> Self {
+		Stmt::Break(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Continue {
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Continue> for Stmt {
+	fn from(node: Continue) -> Self {
+		Stmt::Continue(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorStmt {
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<ErrorStmt> for Stmt {
+	fn from(node: ErrorStmt) -> Self {
+		Stmt::ErrorStmt(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type"))]
+pub enum Expr {
+	BoolOp(BoolOp),
+	NamedExpr(NamedExpr),
+	BinOp(BinOp),
+	UnaryOp(UnaryOp),
+	Lambda(Lambda),
+	IfExp(IfExp),
+	Dict(Dict),
+	Set(Set),
+	ListComp(ListComp),
+	SetComp(SetComp),
+	DictComp(DictComp),
+	GeneratorExp(GeneratorExp),
+	Await(Await),
+	Yield(Yield),
+	YieldFrom(YieldFrom),
+	Compare(Compare),
+	Call(Call),
+	FormattedValue(FormattedValue),
+	JoinedStr(JoinedStr),
+	Constant(Constant),
+	Attribute(Attribute),
+	Subscript(Subscript),
+	Starred(Starred),
+	Name(Name),
+	List(List),
+	Tuple(Tuple),
+	Slice(Slice),
+	#[cfg_attr(feature = "serde", serde(rename = "Error"))]
+	ErrorExpr(ErrorExpr),
+}
+
+impl Expr {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Expr::BoolOp(_) => "BoolOp",
+			Expr::NamedExpr(_) => "NamedExpr",
+			Expr::BinOp(_) => "BinOp",
+			Expr::UnaryOp(_) => "UnaryOp",
+			Expr::Lambda(_) => "Lambda",
+			Expr::IfExp(_) => "IfExp",
+			Expr::Dict(_) => "Dict",
+			Expr::Set(_) => "Set",
+			Expr::ListComp(_) => "ListComp",
+			Expr::SetComp(_) => "SetComp",
+			Expr::DictComp(_) => "DictComp",
+			Expr::GeneratorExp(_) => "GeneratorExp",
+			Expr::Await(_) => "Await",
+			Expr::Yield(_) => "Yield",
+			Expr::YieldFrom(_) => "YieldFrom",
+			Expr::Compare(_) => "Compare",
+			Expr::Call(_) => "Call",
+			Expr::FormattedValue(_) => "FormattedValue",
+			Expr::JoinedStr(_) => "JoinedStr",
+			Expr::Constant(_) => "Constant",
+			Expr::Attribute(_) => "Attribute",
+			Expr::Subscript(_) => "Subscript",
+			Expr::Starred(_) => "Starred",
+			Expr::Name(_) => "Name",
+			Expr::List(_) => "List",
+			Expr::Tuple(_) => "Tuple",
+			Expr::Slice(_) => "Slice",
+			Expr::ErrorExpr(_) => "Error",
+		}
+	}
+}
+
+impl Expr {
+	pub fn span(&self) -> Span {
+		match self {
+			Expr::BoolOp(node) => node.span,
+			Expr::NamedExpr(node) => node.span,
+			Expr::BinOp(node) => node.span,
+			Expr::UnaryOp(node) => node.span,
+			Expr::Lambda(node) => node.span,
+			Expr::IfExp(node) => node.span,
+			Expr::Dict(node) => node.span,
+			Expr::Set(node) => node.span,
+			Expr::ListComp(node) => node.span,
+			Expr::SetComp(node) => node.span,
+			Expr::DictComp(node) => node.span,
+			Expr::GeneratorExp(node) => node.span,
+			Expr::Await(node) => node.span,
+			Expr::Yield(node) => node.span,
+			Expr::YieldFrom(node) => node.span,
+			Expr::Compare(node) => node.span,
+			Expr::Call(node) => node.span,
+			Expr::FormattedValue(node) => node.span,
+			Expr::JoinedStr(node) => node.span,
+			Expr::Constant(node) => node.span,
+			Expr::Attribute(node) => node.span,
+			Expr::Subscript(node) => node.span,
+			Expr::Starred(node) => node.span,
+			Expr::Name(node) => node.span,
+			Expr::List(node) => node.span,
+			Expr::Tuple(node) => node.span,
+			Expr::Slice(node) => node.span,
+			Expr::ErrorExpr(node) => node.span,
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoolOp {
+	pub op: BoolOperator,
+	pub values: Vec<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<BoolOp> for Expr {
+	fn from(node: BoolOp) -> Self {
+		Expr::BoolOp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamedExpr {
+	pub target: Box<Expr>,
+	pub value: Box<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<NamedExpr> for Expr {
+	fn from(node: NamedExpr) -> Self {
+		Expr::NamedExpr(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinOp {
+	pub left: Box<Expr>,
+	pub op: Operator,
+	pub right: Box<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<BinOp> for Expr {
+	fn from(node: BinOp) -> Self {
+		Expr::BinOp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnaryOp {
+	pub op: UnaryOperator,
+	pub operand: Box<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<UnaryOp> for Expr {
+	fn from(node: UnaryOp) -> Self {
+		Expr::UnaryOp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lambda {
+	pub args: Box<Arguments>,
+	pub body: Box<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Lambda> for Expr {
+	fn from(node: Lambda) -> Self {
+		Expr::Lambda(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IfExp {
+	pub test: Box<Expr>,
+	pub body: Box<Expr>,
+	pub orelse: Box<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<IfExp> for Expr {
+	fn from(node: IfExp) -> Self {
+		Expr::IfExp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dict {
+	pub keys: Vec<Option<Expr>>,
+	pub values: Vec<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Dict> for Expr {
+	fn from(node: Dict) -> Self {
+		Expr::Dict(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Set {
+	pub elts: Vec<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Set> for Expr {
+	fn from(node: Set) -> Self {
+		Expr::Set(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ListComp {
+	pub elt: Box<Expr>,
+	pub generators: Vec<Comprehension>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<ListComp> for Expr {
+	fn from(node: ListComp) -> Self {
+		Expr::ListComp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetComp {
+	pub elt: Box<Expr>,
+	pub generators: Vec<Comprehension>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<SetComp> for Expr {
+	fn from(node: SetComp) -> Self {
+		Expr::SetComp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DictComp {
+	pub key: Box<Expr>,
+	pub value: Box<Expr>,
+	pub generators: Vec<Comprehension>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<DictComp> for Expr {
+	fn from(node: DictComp) -> Self {
+		Expr::DictComp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneratorExp {
+	pub elt: Box<Expr>,
+	pub generators: Vec<Comprehension>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<GeneratorExp> for Expr {
+	fn from(node: GeneratorExp) -> Self {
+		Expr::GeneratorExp(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Await {
+	pub value: Box<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Await> for Expr {
+	fn from(node: Await) -> Self {
+		Expr::Await(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Yield {
+	pub value: Option<Box<Expr>>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Yield> for Expr {
+	fn from(node: Yield) -> Self {
+		Expr::Yield(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct YieldFrom {
+	pub value: Box<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<YieldFrom> for Expr {
+	fn from(node: YieldFrom) -> Self {
+		Expr::YieldFrom(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Compare {
+	pub left: Box<Expr>,
+	pub ops: Vec<CmpOperator>,
+	pub comparators: Vec<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Compare> for Expr {
+	fn from(node: Compare) -> Self {
+		Expr::Compare(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Call {
+	pub func: Box<Expr>,
+	pub args: Vec<Expr>,
+	pub keywords: Vec<Keyword>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Call> for Expr {
+	fn from(node: Call) -> Self {
+		Expr::Call(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FormattedValue {
+	pub value: Box<Expr>,
+	pub conversion: i32,
+	pub format_spec: Option<Box<Expr>>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<FormattedValue> for Expr {
+	fn from(node: FormattedValue) -> Self {
+		Expr::FormattedValue(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JoinedStr {
+	pub values: Vec<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<JoinedStr> for Expr {
+	fn from(node: JoinedStr) -> Self {
+		Expr::JoinedStr(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Constant {
+	pub value: Literal,
+	pub kind: Option<String>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Constant> for Expr {
+	fn from(node: Constant) -> Self {
+		Expr::Constant(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attribute {
+	pub value: Box<Expr>,
+	pub attr: Identifier,
+	pub ctx: ExprContext,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Attribute> for Expr {
+	fn from(node: Attribute) -> Self {
+		Expr::Attribute(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Subscript {
+	pub value: Box<Expr>,
+	pub slice: Box<Expr>,
+	pub ctx: ExprContext,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Subscript> for Expr {
+	fn from(node: Subscript) -> Self {
+		Expr::Subscript(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Starred {
+	pub value: Box<Expr>,
+	pub ctx: ExprContext,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Starred> for Expr {
+	fn from(node: Starred) -> Self {
+		Expr::Starred(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Name {
+	pub id: Identifier,
+	pub ctx: ExprContext,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Name> for Expr {
+	fn from(node: Name) -> Self {
+		Expr::Name(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct List {
+	pub elts: Vec<Expr>,
+	pub ctx: ExprContext,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<List> for Expr {
+	fn from(node: List) -> Self {
+		Expr::List(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tuple {
+	pub elts: Vec<Expr>,
+	pub ctx: ExprContext,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Tuple> for Expr {
+	fn from(node: Tuple) -> Self {
+		Expr::Tuple(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Slice {
+	pub lower: Option<Box<Expr>>,
+	pub upper: Option<Box<Expr>>,
+	pub step: Option<Box<Expr>>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<Slice> for Expr {
+	fn from(node: Slice) -> Self {
+		Expr::Slice(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorExpr {
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<ErrorExpr> for Expr {
+	fn from(node: ErrorExpr) -> Self {
+		Expr::ErrorExpr(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type"))]
+pub enum Pattern {
+	MatchValue(MatchValue),
+	MatchSingleton(MatchSingleton),
+	MatchSequence(MatchSequence),
+	MatchMapping(MatchMapping),
+	MatchClass(MatchClass),
+	MatchStar(MatchStar),
+	MatchAs(MatchAs),
+	MatchOr(MatchOr),
+}
+
+impl Pattern {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Pattern::MatchValue(_) => "MatchValue",
+			Pattern::MatchSingleton(_) => "MatchSingleton",
+			Pattern::MatchSequence(_) => "MatchSequence",
+			Pattern::MatchMapping(_) => "MatchMapping",
+			Pattern::MatchClass(_) => "MatchClass",
+			Pattern::MatchStar(_) => "MatchStar",
+			Pattern::MatchAs(_) => "MatchAs",
+			Pattern::MatchOr(_) => "MatchOr",
+		}
+	}
+}
+
+impl Pattern {
+	pub fn span(&self) -> Span {
+		match self {
+			Pattern::MatchValue(node) => node.span,
+			Pattern::MatchSingleton(node) => node.span,
+			Pattern::MatchSequence(node) => node.span,
+			Pattern::MatchMapping(node) => node.span,
+			Pattern::MatchClass(node) => node.span,
+			Pattern::MatchStar(node) => node.span,
+			Pattern::MatchAs(node) => node.span,
+			Pattern::MatchOr(node) => node.span,
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchValue {
+	pub value: Box<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<MatchValue> for Pattern {
+	fn from(node: MatchValue) -> Self {
+		Pattern::MatchValue(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchSingleton {
+	pub value: Literal,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<MatchSingleton> for Pattern {
+	fn from(node: MatchSingleton) -> Self {
+		Pattern::MatchSingleton(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchSequence {
+	pub patterns: Vec<Pattern>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<MatchSequence> for Pattern {
+	fn from(node: MatchSequence) -> Self {
+		Pattern::MatchSequence(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchMapping {
+	pub keys: Vec<Expr>,
+	pub patterns: Vec<Pattern>,
+	pub rest: Option<Identifier>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<MatchMapping> for Pattern {
+	fn from(node: MatchMapping) -> Self {
+		Pattern::MatchMapping(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchClass {
+	pub cls: Box<Expr>,
+	pub patterns: Vec<Pattern>,
+	pub kwd_attrs: Vec<Identifier>,
+	pub kwd_patterns: Vec<Pattern>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<MatchClass> for Pattern {
+	fn from(node: MatchClass) -> Self {
+		Pattern::MatchClass(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchStar {
+	pub name: Option<Identifier>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<MatchStar> for Pattern {
+	fn from(node: MatchStar) -> Self {
+		Pattern::MatchStar(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchAs {
+	pub pattern: Option<Box<Pattern>>,
+	pub name: Option<Identifier>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<MatchAs> for Pattern {
+	fn from(node: MatchAs) -> Self {
+		Pattern::MatchAs(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchOr {
+	pub patterns: Vec<Pattern>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<MatchOr> for Pattern {
+	fn from(node: MatchOr) -> Self {
+		Pattern::MatchOr(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type"))]
+pub enum TypeParam {
+	TypeVar(TypeVar),
+	ParamSpec(ParamSpec),
+	TypeVarTuple(TypeVarTuple),
+}
+
+impl TypeParam {
+	pub fn name(&self) -> &'static str {
+		match self {
+			TypeParam::TypeVar(_) => "TypeVar",
+			TypeParam::ParamSpec(_) => "ParamSpec",
+			TypeParam::TypeVarTuple(_) => "TypeVarTuple",
+		}
+	}
+}
+
+impl TypeParam {
+	pub fn span(&self) -> Span {
+		match self {
+			TypeParam::TypeVar(node) => node.span,
+			TypeParam::ParamSpec(node) => node.span,
+			TypeParam::TypeVarTuple(node) => node.span,
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeVar {
+	pub name: Identifier,
+	pub bound: Option<Box<Expr>>,
+	pub default_value: Option<Box<Expr>>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<TypeVar> for TypeParam {
+	fn from(node: TypeVar) -> Self {
+		TypeParam::TypeVar(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamSpec {
+	pub name: Identifier,
+	pub default_value: Option<Box<Expr>>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<ParamSpec> for TypeParam {
+	fn from(node: ParamSpec) -> Self {
+		TypeParam::ParamSpec(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeVarTuple {
+	pub name: Identifier,
+	pub default_value: Option<Box<Expr>>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+impl From<TypeVarTuple> for TypeParam {
+	fn from(node: TypeVarTuple) -> Self {
+		TypeParam::TypeVarTuple(node)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type", rename = "comprehension"))]
+pub struct Comprehension {
+	pub target: Box<Expr>,
+	pub iter: Box<Expr>,
+	pub ifs: Vec<Expr>,
+	pub is_async: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type", rename = "arguments"))]
+pub struct Arguments {
+	pub posonlyargs: Vec<Arg>,
+	pub args: Vec<Arg>,
+	pub vararg: Option<Box<Arg>>,
+	pub kwonlyargs: Vec<Arg>,
+	pub kw_defaults: Vec<Option<Expr>>,
+	pub kwarg: Option<Box<Arg>>,
+	pub defaults: Vec<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type", rename = "arg"))]
+pub struct Arg {
+	pub arg: Identifier,
+	pub annotation: Option<Box<Expr>>,
+	pub type_comment: Option<String>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type", rename = "keyword"))]
+pub struct Keyword {
+	pub arg: Option<Identifier>,
+	pub value: Box<Expr>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type", rename = "ExceptHandler"))]
+pub struct ExceptHandler {
+	pub r#type: Option<Box<Expr>>,
+	pub name: Option<Identifier>,
+	pub body: Vec<Stmt>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type", rename = "alias"))]
+pub struct Alias {
+	pub name: Identifier,
+	pub asname: Option<Identifier>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type", rename = "withitem"))]
+pub struct WithItem {
+	pub context_expr: Box<Expr>,
+	pub optional_vars: Option<Box<Expr>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type", rename = "match_case"))]
+pub struct MatchCase {
+	pub pattern: Box<Pattern>,
+	pub guard: Option<Box<Expr>>,
+	pub body: Vec<Stmt>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type", rename = "TypeIgnore"))]
+pub struct TypeIgnore {
+	pub lineno: i32,
+	pub tag: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type"))]
+pub enum ExprContext {
+	Load,
+	Store,
+	Del,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type"))]
+pub enum BoolOperator {
+	And,
+	Or,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type"))]
+pub enum Operator {
+	Add,
+	Sub,
+	Mult,
+	MatMult,
+	Div,
+	Mod,
+	Pow,
+	LShift,
+	RShift,
+	BitOr,
+	BitXor,
+	BitAnd,
+	FloorDiv,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type"))]
+pub enum UnaryOperator {
+	Invert,
+	Not,
+	UAdd,
+	USub,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(tag = "_type"))]
+pub enum CmpOperator {
+	Eq,
+	NotEq,
+	Lt,
+	LtE,
+	Gt,
+	GtE,
+	Is,
+	IsNot,
+	In,
+	NotIn,
+}
+
+impl Dump for Mod {
+	fn to_dump(&self) -> DumpValue {
+		match self {
+			Mod::Module(node) => node.to_dump(),
+			Mod::Interactive(node) => node.to_dump(),
+			Mod::Expression(node) => node.to_dump(),
+			Mod::FunctionType(node) => node.to_dump(),
+		}
+	}
+}
+
+impl Dump for Module {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("body", self.body.to_dump()),
+			("type_ignores", self.type_ignores.to_dump()),
+		];
+		DumpValue::node("Module", fields)
+	}
+}
+
+impl Dump for Interactive {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("body", self.body.to_dump()),
+		];
+		DumpValue::node("Interactive", fields)
+	}
+}
+
+impl Dump for Expression {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("body", self.body.to_dump()),
+		];
+		DumpValue::node("Expression", fields)
+	}
+}
+
+impl Dump for FunctionType {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("argtypes", self.argtypes.to_dump()),
+			("returns", self.returns.to_dump()),
+		];
+		DumpValue::node("FunctionType", fields)
+	}
+}
+
+impl Dump for Stmt {
+	fn to_dump(&self) -> DumpValue {
+		match self {
+			Stmt::FunctionDef(node) => node.to_dump(),
+			Stmt::AsyncFunctionDef(node) => node.to_dump(),
+			Stmt::ClassDef(node) => node.to_dump(),
+			Stmt::Return(node) => node.to_dump(),
+			Stmt::Delete(node) => node.to_dump(),
+			Stmt::Assign(node) => node.to_dump(),
+			Stmt::TypeAlias(node) => node.to_dump(),
+			Stmt::AugAssign(node) => node.to_dump(),
+			Stmt::AnnAssign(node) => node.to_dump(),
+			Stmt::For(node) => node.to_dump(),
+			Stmt::AsyncFor(node) => node.to_dump(),
+			Stmt::While(node) => node.to_dump(),
+			Stmt::If(node) => node.to_dump(),
+			Stmt::With(node) => node.to_dump(),
+			Stmt::AsyncWith(node) => node.to_dump(),
+			Stmt::Match(node) => node.to_dump(),
+			Stmt::Raise(node) => node.to_dump(),
+			Stmt::Try(node) => node.to_dump(),
+			Stmt::TryStar(node) => node.to_dump(),
+			Stmt::Assert(node) => node.to_dump(),
+			Stmt::Import(node) => node.to_dump(),
+			Stmt::ImportFrom(node) => node.to_dump(),
+			Stmt::Global(node) => node.to_dump(),
+			Stmt::Nonlocal(node) => node.to_dump(),
+			Stmt::ExprStmt(node) => node.to_dump(),
+			Stmt::Pass(node) => node.to_dump(),
+			Stmt::Break(node) => node.to_dump(),
+			Stmt::Continue(node) => node.to_dump(),
+			Stmt::ErrorStmt(node) => node.to_dump(),
+		}
+	}
+}
+
+impl Dump for FunctionDef {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("name", self.name.to_dump()),
+			("args", self.args.to_dump()),
+			("body", self.body.to_dump()),
+			("decorator_list", self.decorator_list.to_dump()),
+			("returns", self.returns.to_dump()),
+			("type_comment", self.type_comment.to_dump()),
+			("type_params", self.type_params.to_dump()),
+		];
+		DumpValue::node("FunctionDef", fields).with_span(self.span)
+	}
+}
+
+impl Dump for AsyncFunctionDef {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("name", self.name.to_dump()),
+			("args", self.args.to_dump()),
+			("body", self.body.to_dump()),
+			("decorator_list", self.decorator_list.to_dump()),
+			("returns", self.returns.to_dump()),
+			("type_comment", self.type_comment.to_dump()),
+			("type_params", self.type_params.to_dump()),
+		];
+		DumpValue::node("AsyncFunctionDef", fields).with_span(self.span)
+	}
+}
+
+impl Dump for ClassDef {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("name", self.name.to_dump()),
+			("bases", self.bases.to_dump()),
+			("keywords", self.keywords.to_dump()),
+			("body", self.body.to_dump()),
+			("decorator_list", self.decorator_list.to_dump()),
+			("type_params", self.type_params.to_dump()),
+		];
+		DumpValue::node("ClassDef", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Return {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("value", self.value.to_dump()),
+		];
+		DumpValue::node("Return", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Delete {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("targets", self.targets.to_dump()),
+		];
+		DumpValue::node("Delete", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Assign {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("targets", self.targets.to_dump()),
+			("value", self.value.to_dump()),
+			("type_comment", self.type_comment.to_dump()),
+		];
+		DumpValue::node("Assign", fields).with_span(self.span)
+	}
+}
+
+impl Dump for TypeAlias {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("name", self.name.to_dump()),
+			("type_params", self.type_params.to_dump()),
+			("value", self.value.to_dump()),
+		];
+		DumpValue::node("TypeAlias", fields).with_span(self.span)
+	}
+}
+
+impl Dump for AugAssign {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("target", self.target.to_dump()),
+			("op", self.op.to_dump()),
+			("value", self.value.to_dump()),
+		];
+		DumpValue::node("AugAssign", fields).with_span(self.span)
+	}
+}
+
+impl Dump for AnnAssign {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("target", self.target.to_dump()),
+			("annotation", self.annotation.to_dump()),
+			("value", self.value.to_dump()),
+			("simple", self.simple.to_dump()),
+		];
+		DumpValue::node("AnnAssign", fields).with_span(self.span)
+	}
+}
+
+impl Dump for For {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("target", self.target.to_dump()),
+			("iter", self.iter.to_dump()),
+			("body", self.body.to_dump()),
+			("orelse", self.orelse.to_dump()),
+			("type_comment", self.type_comment.to_dump()),
+		];
+		DumpValue::node("For", fields).with_span(self.span)
+	}
+}
+
+impl Dump for AsyncFor {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("target", self.target.to_dump()),
+			("iter", self.iter.to_dump()),
+			("body", self.body.to_dump()),
+			("orelse", self.orelse.to_dump()),
+			("type_comment", self.type_comment.to_dump()),
+		];
+		DumpValue::node("AsyncFor", fields).with_span(self.span)
+	}
+}
+
+impl Dump for While {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("test", self.test.to_dump()),
+			("body", self.body.to_dump()),
+			("orelse", self.orelse.to_dump()),
+		];
+		DumpValue::node("While", fields).with_span(self.span)
+	}
+}
+
+impl Dump for If {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("test", self.test.to_dump()),
+			("body", self.body.to_dump()),
+			("orelse", self.orelse.to_dump()),
+		];
+		DumpValue::node("If", fields).with_span(self.span)
+	}
+}
+
+impl Dump for With {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("items", self.items.to_dump()),
+			("body", self.body.to_dump()),
+			("type_comment", self.type_comment.to_dump()),
+		];
+		DumpValue::node("With", fields).with_span(self.span)
+	}
+}
+
+impl Dump for AsyncWith {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("items", self.items.to_dump()),
+			("body", self.body.to_dump()),
+			("type_comment", self.type_comment.to_dump()),
+		];
+		DumpValue::node("AsyncWith", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Match {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("subject", self.subject.to_dump()),
+			("cases", self.cases.to_dump()),
+		];
+		DumpValue::node("Match", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Raise {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("exc", self.exc.to_dump()),
+			("cause", self.cause.to_dump()),
+		];
+		DumpValue::node("Raise", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Try {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("body", self.body.to_dump()),
+			("handlers", self.handlers.to_dump()),
+			("orelse", self.orelse.to_dump()),
+			("finalbody", self.finalbody.to_dump()),
+		];
+		DumpValue::node("Try", fields).with_span(self.span)
+	}
+}
+
+impl Dump for TryStar {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("body", self.body.to_dump()),
+			("handlers", self.handlers.to_dump()),
+			("orelse", self.orelse.to_dump()),
+			("finalbody", self.finalbody.to_dump()),
+		];
+		DumpValue::node("TryStar", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Assert {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("test", self.test.to_dump()),
+			("msg", self.msg.to_dump()),
+		];
+		DumpValue::node("Assert", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Import {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("names", self.names.to_dump()),
+		];
+		DumpValue::node("Import", fields).with_span(self.span)
+	}
+}
+
+impl Dump for ImportFrom {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("module", self.module.to_dump()),
+			("names", self.names.to_dump()),
+			("level", self.level.to_dump()),
+		];
+		DumpValue::node("ImportFrom", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Global {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("names", self.names.to_dump()),
+		];
+		DumpValue::node("Global", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Nonlocal {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("names", self.names.to_dump()),
+		];
+		DumpValue::node("Nonlocal", fields).with_span(self.span)
+	}
+}
+
+impl Dump for ExprStmt {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("value", self.value.to_dump()),
+		];
+		DumpValue::node("Expr", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Pass {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+		];
+		DumpValue::node("Pass", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Break {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+		];
+		DumpValue::node("Break", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Continue {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+		];
+		DumpValue::node("Continue", fields).with_span(self.span)
+	}
+}
+
+impl Dump for ErrorStmt {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+		];
+		DumpValue::node("Error", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Expr {
+	fn to_dump(&self) -> DumpValue {
+		match self {
+			Expr::BoolOp(node) => node.to_dump(),
+			Expr::NamedExpr(node) => node.to_dump(),
+			Expr::BinOp(node) => node.to_dump(),
+			Expr::UnaryOp(node) => node.to_dump(),
+			Expr::Lambda(node) => node.to_dump(),
+			Expr::IfExp(node) => node.to_dump(),
+			Expr::Dict(node) => node.to_dump(),
+			Expr::Set(node) => node.to_dump(),
+			Expr::ListComp(node) => node.to_dump(),
+			Expr::SetComp(node) => node.to_dump(),
+			Expr::DictComp(node) => node.to_dump(),
+			Expr::GeneratorExp(node) => node.to_dump(),
+			Expr::Await(node) => node.to_dump(),
+			Expr::Yield(node) => node.to_dump(),
+			Expr::YieldFrom(node) => node.to_dump(),
+			Expr::Compare(node) => node.to_dump(),
+			Expr::Call(node) => node.to_dump(),
+			Expr::FormattedValue(node) => node.to_dump(),
+			Expr::JoinedStr(node) => node.to_dump(),
+			Expr::Constant(node) => node.to_dump(),
+			Expr::Attribute(node) => node.to_dump(),
+			Expr::Subscript(node) => node.to_dump(),
+			Expr::Starred(node) => node.to_dump(),
+			Expr::Name(node) => node.to_dump(),
+			Expr::List(node) => node.to_dump(),
+			Expr::Tuple(node) => node.to_dump(),
+			Expr::Slice(node) => node.to_dump(),
+			Expr::ErrorExpr(node) => node.to_dump(),
+		}
+	}
+}
+
+impl Dump for BoolOp {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("op", self.op.to_dump()),
+			("values", self.values.to_dump()),
+		];
+		DumpValue::node("BoolOp", fields).with_span(self.span)
+	}
+}
+
+impl Dump for NamedExpr {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("target", self.target.to_dump()),
+			("value", self.value.to_dump()),
+		];
+		DumpValue::node("NamedExpr", fields).with_span(self.span)
+	}
+}
+
+impl Dump for BinOp {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("left", self.left.to_dump()),
+			("op", self.op.to_dump()),
+			("right", self.right.to_dump()),
+		];
+		DumpValue::node("BinOp", fields).with_span(self.span)
+	}
+}
+
+impl Dump for UnaryOp {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("op", self.op.to_dump()),
+			("operand", self.operand.to_dump()),
+		];
+		DumpValue::node("UnaryOp", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Lambda {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("args", self.args.to_dump()),
+			("body", self.body.to_dump()),
+		];
+		DumpValue::node("Lambda", fields).with_span(self.span)
+	}
+}
+
+impl Dump for IfExp {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("test", self.test.to_dump()),
+			("body", self.body.to_dump()),
+			("orelse", self.orelse.to_dump()),
+		];
+		DumpValue::node("IfExp", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Dict {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("keys", self.keys.to_dump()),
+			("values", self.values.to_dump()),
+		];
+		DumpValue::node("Dict", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Set {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("elts", self.elts.to_dump()),
+		];
+		DumpValue::node("Set", fields).with_span(self.span)
+	}
+}
+
+impl Dump for ListComp {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("elt", self.elt.to_dump()),
+			("generators", self.generators.to_dump()),
+		];
+		DumpValue::node("ListComp", fields).with_span(self.span)
+	}
+}
+
+impl Dump for SetComp {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("elt", self.elt.to_dump()),
+			("generators", self.generators.to_dump()),
+		];
+		DumpValue::node("SetComp", fields).with_span(self.span)
+	}
+}
+
+impl Dump for DictComp {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("key", self.key.to_dump()),
+			("value", self.value.to_dump()),
+			("generators", self.generators.to_dump()),
+		];
+		DumpValue::node("DictComp", fields).with_span(self.span)
+	}
+}
+
+impl Dump for GeneratorExp {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("elt", self.elt.to_dump()),
+			("generators", self.generators.to_dump()),
+		];
+		DumpValue::node("GeneratorExp", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Await {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("value", self.value.to_dump()),
+		];
+		DumpValue::node("Await", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Yield {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("value", self.value.to_dump()),
+		];
+		DumpValue::node("Yield", fields).with_span(self.span)
+	}
+}
+
+impl Dump for YieldFrom {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("value", self.value.to_dump()),
+		];
+		DumpValue::node("YieldFrom", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Compare {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("left", self.left.to_dump()),
+			("ops", self.ops.to_dump()),
+			("comparators", self.comparators.to_dump()),
+		];
+		DumpValue::node("Compare", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Call {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("func", self.func.to_dump()),
+			("args", self.args.to_dump()),
+			("keywords", self.keywords.to_dump()),
+		];
+		DumpValue::node("Call", fields).with_span(self.span)
+	}
+}
+
+impl Dump for FormattedValue {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("value", self.value.to_dump()),
+			("conversion", self.conversion.to_dump()),
+			("format_spec", self.format_spec.to_dump()),
+		];
+		DumpValue::node("FormattedValue", fields).with_span(self.span)
+	}
+}
+
+impl Dump for JoinedStr {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("values", self.values.to_dump()),
+		];
+		DumpValue::node("JoinedStr", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Constant {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("value", self.value.to_dump()),
+			("kind", self.kind.to_dump()),
+		];
+		DumpValue::node("Constant", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Attribute {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("value", self.value.to_dump()),
+			("attr", self.attr.to_dump()),
+			("ctx", self.ctx.to_dump()),
+		];
+		DumpValue::node("Attribute", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Subscript {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("value", self.value.to_dump()),
+			("slice", self.slice.to_dump()),
+			("ctx", self.ctx.to_dump()),
+		];
+		DumpValue::node("Subscript", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Starred {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("value", self.value.to_dump()),
+			("ctx", self.ctx.to_dump()),
+		];
+		DumpValue::node("Starred", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Name {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("id", self.id.to_dump()),
+			("ctx", self.ctx.to_dump()),
+		];
+		DumpValue::node("Name", fields).with_span(self.span)
+	}
+}
+
+impl Dump for List {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("elts", self.elts.to_dump()),
+			("ctx", self.ctx.to_dump()),
+		];
+		DumpValue::node("List", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Tuple {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("elts", self.elts.to_dump()),
+			("ctx", self.ctx.to_dump()),
+		];
+		DumpValue::node("Tuple", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Slice {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("lower", self.lower.to_dump()),
+			("upper", self.upper.to_dump()),
+			("step", self.step.to_dump()),
+		];
+		DumpValue::node("Slice", fields).with_span(self.span)
+	}
+}
+
+impl Dump for ErrorExpr {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+		];
+		DumpValue::node("Error", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Pattern {
+	fn to_dump(&self) -> DumpValue {
+		match self {
+			Pattern::MatchValue(node) => node.to_dump(),
+			Pattern::MatchSingleton(node) => node.to_dump(),
+			Pattern::MatchSequence(node) => node.to_dump(),
+			Pattern::MatchMapping(node) => node.to_dump(),
+			Pattern::MatchClass(node) => node.to_dump(),
+			Pattern::MatchStar(node) => node.to_dump(),
+			Pattern::MatchAs(node) => node.to_dump(),
+			Pattern::MatchOr(node) => node.to_dump(),
+		}
+	}
+}
+
+impl Dump for MatchValue {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("value", self.value.to_dump()),
+		];
+		DumpValue::node("MatchValue", fields).with_span(self.span)
+	}
+}
+
+impl Dump for MatchSingleton {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("value", self.value.to_dump()),
+		];
+		DumpValue::node("MatchSingleton", fields).with_span(self.span)
+	}
+}
+
+impl Dump for MatchSequence {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("patterns", self.patterns.to_dump()),
+		];
+		DumpValue::node("MatchSequence", fields).with_span(self.span)
+	}
+}
+
+impl Dump for MatchMapping {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("keys", self.keys.to_dump()),
+			("patterns", self.patterns.to_dump()),
+			("rest", self.rest.to_dump()),
+		];
+		DumpValue::node("MatchMapping", fields).with_span(self.span)
+	}
+}
+
+impl Dump for MatchClass {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("cls", self.cls.to_dump()),
+			("patterns", self.patterns.to_dump()),
+			("kwd_attrs", self.kwd_attrs.to_dump()),
+			("kwd_patterns", self.kwd_patterns.to_dump()),
+		];
+		DumpValue::node("MatchClass", fields).with_span(self.span)
+	}
+}
+
+impl Dump for MatchStar {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("name", self.name.to_dump()),
+		];
+		DumpValue::node("MatchStar", fields).with_span(self.span)
+	}
+}
+
+impl Dump for MatchAs {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("pattern", self.pattern.to_dump()),
+			("name", self.name.to_dump()),
+		];
+		DumpValue::node("MatchAs", fields).with_span(self.span)
+	}
+}
+
+impl Dump for MatchOr {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("patterns", self.patterns.to_dump()),
+		];
+		DumpValue::node("MatchOr", fields).with_span(self.span)
+	}
+}
+
+impl Dump for TypeParam {
+	fn to_dump(&self) -> DumpValue {
+		match self {
+			TypeParam::TypeVar(node) => node.to_dump(),
+			TypeParam::ParamSpec(node) => node.to_dump(),
+			TypeParam::TypeVarTuple(node) => node.to_dump(),
+		}
+	}
+}
+
+impl Dump for TypeVar {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("name", self.name.to_dump()),
+			("bound", self.bound.to_dump()),
+			("default_value", self.default_value.to_dump()),
+		];
+		DumpValue::node("TypeVar", fields).with_span(self.span)
+	}
+}
+
+impl Dump for ParamSpec {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("name", self.name.to_dump()),
+			("default_value", self.default_value.to_dump()),
+		];
+		DumpValue::node("ParamSpec", fields).with_span(self.span)
+	}
+}
+
+impl Dump for TypeVarTuple {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("name", self.name.to_dump()),
+			("default_value", self.default_value.to_dump()),
+		];
+		DumpValue::node("TypeVarTuple", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Comprehension {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("target", self.target.to_dump()),
+			("iter", self.iter.to_dump()),
+			("ifs", self.ifs.to_dump()),
+			("is_async", self.is_async.to_dump()),
+		];
+		DumpValue::node("comprehension", fields)
+	}
+}
+
+impl Dump for Arguments {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("posonlyargs", self.posonlyargs.to_dump()),
+			("args", self.args.to_dump()),
+			("vararg", self.vararg.to_dump()),
+			("kwonlyargs", self.kwonlyargs.to_dump()),
+			("kw_defaults", self.kw_defaults.to_dump()),
+			("kwarg", self.kwarg.to_dump()),
+			("defaults", self.defaults.to_dump()),
+		];
+		DumpValue::node("arguments", fields)
+	}
+}
+
+impl Dump for Arg {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("arg", self.arg.to_dump()),
+			("annotation", self.annotation.to_dump()),
+			("type_comment", self.type_comment.to_dump()),
+		];
+		DumpValue::node("arg", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Keyword {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("arg", self.arg.to_dump()),
+			("value", self.value.to_dump()),
+		];
+		DumpValue::node("keyword", fields).with_span(self.span)
+	}
+}
+
+impl Dump for ExceptHandler {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("type", self.r#type.to_dump()),
+			("name", self.name.to_dump()),
+			("body", self.body.to_dump()),
+		];
+		DumpValue::node("ExceptHandler", fields).with_span(self.span)
+	}
+}
+
+impl Dump for Alias {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("name", self.name.to_dump()),
+			("asname", self.asname.to_dump()),
+		];
+		DumpValue::node("alias", fields).with_span(self.span)
+	}
+}
+
+impl Dump for WithItem {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("context_expr", self.context_expr.to_dump()),
+			("optional_vars", self.optional_vars.to_dump()),
+		];
+		DumpValue::node("withitem", fields)
+	}
+}
+
+impl Dump for MatchCase {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("pattern", self.pattern.to_dump()),
+			("guard", self.guard.to_dump()),
+			("body", self.body.to_dump()),
+		];
+		DumpValue::node("match_case", fields)
+	}
+}
+
+impl Dump for TypeIgnore {
+	fn to_dump(&self) -> DumpValue {
+		let fields = vec![
+			("lineno", self.lineno.to_dump()),
+			("tag", self.tag.to_dump()),
+		];
+		DumpValue::node("TypeIgnore", fields)
+	}
+}
+
+impl Dump for ExprContext {
+	fn to_dump(&self) -> DumpValue {
+		let name = match self {
+			ExprContext::Load => "Load",
+			ExprContext::Store => "Store",
+			ExprContext::Del => "Del",
+		};
+		DumpValue::node(name, vec![])
+	}
+}
+
+impl Dump for BoolOperator {
+	fn to_dump(&self) -> DumpValue {
+		let name = match self {
+			BoolOperator::And => "And",
+			BoolOperator::Or => "Or",
+		};
+		DumpValue::node(name, vec![])
+	}
+}
+
+impl Dump for Operator {
+	fn to_dump(&self) -> DumpValue {
+		let name = match self {
+			Operator::Add => "Add",
+			Operator::Sub => "Sub",
+			Operator::Mult => "Mult",
+			Operator::MatMult => "MatMult",
+			Operator::Div => "Div",
+			Operator::Mod => "Mod",
+			Operator::Pow => "Pow",
+			Operator::LShift => "LShift",
+			Operator::RShift => "RShift",
+			Operator::BitOr => "BitOr",
+			Operator::BitXor => "BitXor",
+			Operator::BitAnd => "BitAnd",
+			Operator::FloorDiv => "FloorDiv",
+		};
+		DumpValue::node(name, vec![])
+	}
+}
+
+impl Dump for UnaryOperator {
+	fn to_dump(&self) -> DumpValue {
+		let name = match self {
+			UnaryOperator::Invert => "Invert",
+			UnaryOperator::Not => "Not",
+			UnaryOperator::UAdd => "UAdd",
+			UnaryOperator::USub => "USub",
+		};
+		DumpValue::node(name, vec![])
+	}
+}
+
+impl Dump for CmpOperator {
+	fn to_dump(&self) -> DumpValue {
+		let name = match self {
+			CmpOperator::Eq => "Eq",
+			CmpOperator::NotEq => "NotEq",
+			CmpOperator::Lt => "Lt",
+			CmpOperator::LtE => "LtE",
+			CmpOperator::Gt => "Gt",
+			CmpOperator::GtE => "GtE",
+			CmpOperator::Is => "Is",
+			CmpOperator::IsNot => "IsNot",
+			CmpOperator::In => "In",
+			CmpOperator::NotIn => "NotIn",
+		};
+		DumpValue::node(name, vec![])
+	}
+}
+
@@ -0,0 +1,207 @@
+//! Syntax checking of the Python files under a directory, for `pragrat check`.
+
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+
+use crate::files::{parse_files_streaming, ParseOptions, ParseStats};
+use crate::parser::SyntaxError;
+use crate::types::Span;
+
+/// Extensions of the files checked.
+const EXTENSIONS: [&str; 2] = ["py", "pyi"];
+
+/// The Python files found by `find_sources`.
+#[derive(Clone, Debug, Default)]
+pub struct Sources {
+    pub paths: Vec<PathBuf>,
+    /// The paths that could not be walked, such as unreadable directories, each failing with
+    /// an error located at line 0 like a file that cannot be read.
+    pub errors: Vec<(PathBuf, SyntaxError)>,
+}
+
+/// Finds the Python files under `root`, or `root` itself if it is a file, in sorted order.
+/// Hidden files and those ignored by `.gitignore` files are skipped, as are files and
+/// directories whose path relative to `root` matches an `exclude` glob. If there are
+/// `include` globs, a file must also match one of them.
+pub fn find_sources(
+    root: &Path,
+    include: &[String],
+    exclude: &[String],
+) -> Result<Sources, globset::Error> {
+    let include = glob_set(include)?;
+    let exclude = glob_set(exclude)?;
+    let relative = |path: &Path| path.strip_prefix(root).unwrap_or(path).to_path_buf();
+    let walk = WalkBuilder::new(root)
+        .require_git(false)
+        .filter_entry({
+            let exclude = exclude.clone();
+            let root = root.to_path_buf();
+            move |entry| {
+                let path = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+                path.as_os_str().is_empty() || !exclude.is_match(path)
+            }
+        })
+        .build();
+    let mut errors = vec![];
+    let mut paths: Vec<PathBuf> = walk
+        .filter_map(|entry| {
+            entry
+                .map_err(|error| errors.push(walk_error(root, error)))
+                .ok()
+        })
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+        })
+        .map(ignore::DirEntry::into_path)
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| EXTENSIONS.iter().any(|e| extension == *e))
+        })
+        .filter(|path| include.is_empty() || include.is_match(relative(path)))
+        .collect();
+    paths.sort();
+    errors.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(Sources { paths, errors })
+}
+
+/// The path a walk `error` is about, or `root` if it names none, and the error it fails with.
+fn walk_error(root: &Path, error: ignore::Error) -> (PathBuf, SyntaxError) {
+    let span = Span::default();
+    match error {
+        ignore::Error::WithPath { path, err } => {
+            // Walking wraps I/O errors in a message that repeats the path.
+            let message = match err.io_error().and_then(std::error::Error::source) {
+                Some(cause) => cause.to_string(),
+                None => err.to_string(),
+            };
+            (path, SyntaxError::new(message, span))
+        }
+        ignore::Error::WithDepth { err, .. } => walk_error(root, *err),
+        ignore::Error::Loop { ref child, .. } => {
+            (child.clone(), SyntaxError::new(error.to_string(), span))
+        }
+        error => (
+            root.to_path_buf(),
+            SyntaxError::new(error.to_string(), span),
+        ),
+    }
+}
+
+/// Parses `paths` in parallel, returning the files that failed with their errors, in sorted
+/// order, and the statistics of the run. The modules parsed are dropped right away.
+pub fn check_files(
+    paths: &[PathBuf],
+    options: &ParseOptions,
+) -> (Vec<(PathBuf, Vec<SyntaxError>)>, ParseStats) {
+    let mut failed = vec![];
+    let stats = parse_files_streaming(paths, options, |path, result| {
+        if let Err(errors) = result {
+            failed.push((path.to_path_buf(), errors));
+        }
+    });
+    failed.sort_by(|a, b| a.0.cmp(&b.0));
+    (failed, stats)
+}
+
+/// Renders `error`, found in `path`, as `path:line:column: SyntaxError: message` with a
+/// 1-based column and the class of the error, or as `path: message` if the file could not
+/// be read.
+pub fn render_error(path: &Path, error: &SyntaxError) -> String {
+    if error.span.lineno == 0 {
+        return format!("{}: {}", path.display(), error);
+    }
+    format!(
+        "{}:{}:{}: {}: {}",
+        path.display(),
+        error.span.lineno,
+        error.span.col_offset + 1,
+        error.kind,
+        error
+    )
+}
+
+fn glob_set(globs: &[String]) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        builder.add(Glob::new(glob)?);
+    }
+    builder.build()
+}
+
+#[test]
+fn test_check() {
+    use std::fs;
+
+    let root = std::env::temp_dir().join(format!("pragrat-check-{}", std::process::id()));
+    let files = [
+        ("a.py", "x = 1\n"),
+        ("b.pyi", "def f(x: int) -> int: ...\n"),
+        ("notes.txt", "not python\n"),
+        ("pkg/bad.py", "x = 1 +\nf(a b)\n"),
+        ("pkg/generated_pb2.py", "x = $\n"),
+        ("build/out.py", "x = $\n"),
+        (".hidden/c.py", "x = $\n"),
+        ("tests/test_a.py", "assert True\n"),
+        (".gitignore", "build/\n"),
+    ];
+    for (path, contents) in files {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+    let relative = |paths: Vec<PathBuf>| -> Vec<String> {
+        paths
+            .iter()
+            .map(|path| path.strip_prefix(&root).unwrap().display().to_string())
+            .collect()
+    };
+
+    let sources = find_sources(&root, &[], &["*_pb2.py".to_string()]).unwrap();
+    assert!(sources.errors.is_empty());
+    let sources = sources.paths;
+    assert_eq!(
+        relative(sources.clone()),
+        ["a.py", "b.pyi", "pkg/bad.py", "tests/test_a.py"]
+    );
+    let (failed, stats) = check_files(&sources, &ParseOptions::default());
+    assert_eq!((stats.files, stats.failed), (4, 1));
+    let errors: Vec<String> = failed
+        .iter()
+        .flat_map(|(path, errors)| errors.iter().map(|error| render_error(path, error)))
+        .collect();
+    let bad = root.join("pkg/bad.py").display().to_string();
+    assert_eq!(
+        errors,
+        [
+            format!("{}:1:8: SyntaxError: invalid syntax", bad),
+            format!(
+                "{}:2:3: SyntaxError: invalid syntax. Perhaps you forgot a comma?",
+                bad
+            ),
+        ]
+    );
+
+    let sources = find_sources(&root, &["tests/**".to_string()], &[]).unwrap();
+    assert_eq!(relative(sources.paths), ["tests/test_a.py"]);
+    let exclude = ["pkg".to_string(), "tests".to_string()];
+    let sources = find_sources(&root, &[], &exclude).unwrap();
+    assert_eq!(relative(sources.paths), ["a.py", "b.pyi"]);
+    assert!(find_sources(&root, &["[".to_string()], &[]).is_err());
+    let file = root.join("a.py");
+    assert_eq!(find_sources(&file, &[], &[]).unwrap().paths, [file]);
+
+    // A path that cannot be walked fails like a file that cannot be read.
+    let missing = root.join("missing");
+    let sources = find_sources(&missing, &[], &[]).unwrap();
+    assert!(sources.paths.is_empty());
+    let [(path, error)] = &sources.errors[..] else {
+        panic!("{:?}", sources.errors);
+    };
+    assert_eq!(path, &missing);
+    assert!(render_error(path, error).starts_with(&format!("{}: ", missing.display())));
+    fs::remove_dir_all(&root).unwrap();
+}
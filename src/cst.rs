@@ -0,0 +1,672 @@
+//! A concrete syntax tree for codemods, like LibCST: every token of the source, whitespace,
+//! comments and parentheses included, belongs to exactly one node, so the tree prints back
+//! to the exact text it was parsed from and an edit to one node leaves the rest as written.
+//!
+//! The tree is laid over the lossless token stream along the spans of the AST. Each located
+//! AST node becomes a `CstNode` owning the tokens within its span, along with the
+//! parentheses wrapping it. Whitespace and comments before a node's first token or after its
+//! last belong to the parent, so replacing a node keeps its surroundings. Nodes without a
+//! span, such as `arguments` or `comprehension`, leave their children to their parent.
+
+use std::{collections::HashMap, fmt};
+
+use crate::ast::*;
+use crate::parser::{parse_with_version, Mode, SyntaxError};
+use crate::tokenize::is_trivia;
+use crate::tokenizer::Tokenizer;
+use crate::types::{PythonVersion, Span, TokenType};
+use crate::visit::{self, Visitor};
+
+/// The kind of AST node a `CstNode` stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NodeType {
+    Mod,
+    Stmt,
+    Expr,
+    Pattern,
+    TypeParam,
+    ExceptHandler,
+    Arg,
+    Keyword,
+    Alias,
+}
+
+/// A node of the tree, with the tokens and trivia it owns and its child nodes in source order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CstNode {
+    pub node_type: NodeType,
+    /// The CPython class name of the AST node, e.g. `BinOp`.
+    pub name: &'static str,
+    /// Where the AST node was in the source the tree was parsed from.
+    pub span: Span,
+    /// Parentheses wrapping the node, the first and last of its tokens.
+    pub parens: usize,
+    pub children: Vec<CstElement>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CstElement {
+    Node(CstNode),
+    Token(CstToken),
+    /// Spaces, tabs, form feeds and backslash continuations between tokens.
+    Whitespace(String),
+}
+
+/// A token as written, including comments and the newlines of blank lines, which the parser
+/// never sees.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CstToken {
+    pub token_type: TokenType,
+    pub text: String,
+}
+
+/// Edits a tree node by node, like LibCST's `CSTTransformer`.
+pub trait Transformer {
+    /// Called before the children of `node` are transformed. Returning `false` leaves them
+    /// as they are.
+    fn visit(&mut self, _node: &CstNode) -> bool {
+        true
+    }
+
+    /// Called after the children of `node` are transformed, returning the node replacing it.
+    fn leave(&mut self, node: CstNode) -> CstNode {
+        node
+    }
+}
+
+/// Parses `source` into the concrete syntax tree of its `Module`.
+pub fn parse_cst(source: &str, version: PythonVersion) -> Result<CstNode, SyntaxError> {
+    build(source, Mode::Exec, version)
+}
+
+impl CstNode {
+    /// Parses `source` as a single expression, to replace a node with. The whitespace and
+    /// comments after the expression are dropped, but not its parentheses.
+    pub fn expression(source: &str, version: PythonVersion) -> Result<CstNode, SyntaxError> {
+        let root = build(source, Mode::Eval, version)?;
+        Ok(root.into_first_node())
+    }
+
+    /// Parses `source` as a single statement, to replace a node with. Lines after the first
+    /// are kept as written, so they should be indented for where the statement goes.
+    pub fn statement(source: &str, version: PythonVersion) -> Result<CstNode, SyntaxError> {
+        let root = build(source, Mode::Exec, version)?;
+        if root.nodes().count() != 1 {
+            return Err(SyntaxError::new("expected a single statement", root.span));
+        }
+        Ok(root.into_first_node())
+    }
+
+    /// The exact source text of the node.
+    pub fn code(&self) -> String {
+        let mut code = String::new();
+        self.write_code(&mut code);
+        code
+    }
+
+    /// Parses the code of the node into its AST: an expression in `Mode::Eval`, within
+    /// parentheses so that it may span lines, and anything else as a module. A statement
+    /// nested in a block only parses as part of its module.
+    pub fn to_ast(&self, version: PythonVersion) -> Result<Mod, SyntaxError> {
+        match self.node_type {
+            NodeType::Expr => parse_with_version(&format!("({})", self), Mode::Eval, version),
+            _ => parse_with_version(&self.code(), Mode::Exec, version),
+        }
+    }
+
+    /// The child nodes, in source order.
+    pub fn nodes(&self) -> impl Iterator<Item = &CstNode> {
+        self.children.iter().filter_map(|child| match child {
+            CstElement::Node(node) => Some(node),
+            _ => None,
+        })
+    }
+
+    /// The tokens the node owns itself, not those of its children.
+    pub fn tokens(&self) -> impl Iterator<Item = &CstToken> {
+        self.children.iter().filter_map(|child| match child {
+            CstElement::Token(token) => Some(token),
+            _ => None,
+        })
+    }
+
+    /// The node and all the nodes under it, in source order.
+    pub fn descendants(&self) -> Vec<&CstNode> {
+        let mut nodes = vec![];
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            nodes.push(node);
+            let children: Vec<&CstNode> = node.nodes().collect();
+            stack.extend(children.into_iter().rev());
+        }
+        nodes
+    }
+
+    /// Wraps the node in parentheses, as a replacement needs if it binds looser than the
+    /// node it replaces.
+    pub fn parenthesize(mut self) -> CstNode {
+        let paren = |token_type, text: &str| {
+            CstElement::Token(CstToken {
+                token_type,
+                text: text.to_string(),
+            })
+        };
+        self.children.insert(0, paren(TokenType::LPar, "("));
+        self.children.push(paren(TokenType::RPar, ")"));
+        self.parens += 1;
+        self
+    }
+
+    /// Transforms the node and those under it depth first, returning the node replacing it.
+    pub fn transform(mut self, transformer: &mut impl Transformer) -> CstNode {
+        if transformer.visit(&self) {
+            self.children = std::mem::take(&mut self.children)
+                .into_iter()
+                .map(|child| match child {
+                    CstElement::Node(node) => CstElement::Node(node.transform(transformer)),
+                    other => other,
+                })
+                .collect();
+        }
+        transformer.leave(self)
+    }
+
+    fn write_code(&self, code: &mut String) {
+        for child in &self.children {
+            match child {
+                CstElement::Node(node) => node.write_code(code),
+                CstElement::Token(token) => code.push_str(&token.text),
+                CstElement::Whitespace(text) => code.push_str(text),
+            }
+        }
+    }
+
+    fn into_first_node(self) -> CstNode {
+        self.children
+            .into_iter()
+            .find_map(|child| match child {
+                CstElement::Node(node) => Some(node),
+                _ => None,
+            })
+            .expect("A parsed root has a child node")
+    }
+}
+
+impl fmt::Display for CstNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// A token or the whitespace before it, with its byte offsets in the source.
+struct Leaf {
+    element: CstElement,
+    start: usize,
+    end: usize,
+}
+
+impl Leaf {
+    /// Whether the leaf is a token the parser sees.
+    fn is_significant(&self) -> bool {
+        matches!(&self.element, CstElement::Token(token) if !is_trivia(&token.token_type))
+    }
+
+    fn is(&self, token_type: TokenType) -> bool {
+        matches!(&self.element, CstElement::Token(token) if token.token_type == token_type)
+    }
+}
+
+/// A located AST node, with its byte offsets and its located descendants.
+struct Frame {
+    node_type: NodeType,
+    name: &'static str,
+    span: Span,
+    start: usize,
+    end: usize,
+    children: Vec<Frame>,
+}
+
+/// Collects the located nodes of an AST into frames.
+struct Collector<'a> {
+    line_starts: &'a [usize],
+    stack: Vec<Frame>,
+}
+
+impl Collector<'_> {
+    fn enter(&mut self, node_type: NodeType, name: &'static str, span: Span) {
+        let offset = |lineno: usize, col: usize| match self.line_starts.get(lineno.max(1) - 1) {
+            Some(start) => start + col,
+            None => usize::MAX,
+        };
+        self.stack.push(Frame {
+            node_type,
+            name,
+            span,
+            start: offset(span.lineno, span.col_offset),
+            end: offset(span.end_lineno, span.end_col_offset),
+            children: vec![],
+        });
+    }
+
+    fn exit(&mut self) {
+        let mut frame = self.stack.pop().expect("Unbalanced frames");
+        frame.children.sort_by_key(|child| child.start);
+        // Decorators come before the span of their definition.
+        for child in &frame.children {
+            frame.start = frame.start.min(child.start);
+            frame.end = frame.end.max(child.end);
+        }
+        self.stack
+            .last_mut()
+            .expect("Unbalanced frames")
+            .children
+            .push(frame);
+    }
+}
+
+impl Visitor for Collector<'_> {
+    fn visit_stmt(&mut self, node: &Stmt) {
+        self.enter(NodeType::Stmt, node.name(), node.span());
+        visit::walk_stmt(self, node);
+        self.exit();
+    }
+
+    fn visit_expr(&mut self, node: &Expr) {
+        self.enter(NodeType::Expr, node.name(), node.span());
+        visit::walk_expr(self, node);
+        self.exit();
+    }
+
+    fn visit_pattern(&mut self, node: &Pattern) {
+        self.enter(NodeType::Pattern, node.name(), node.span());
+        visit::walk_pattern(self, node);
+        self.exit();
+    }
+
+    fn visit_typeparam(&mut self, node: &TypeParam) {
+        self.enter(NodeType::TypeParam, node.name(), node.span());
+        visit::walk_typeparam(self, node);
+        self.exit();
+    }
+
+    fn visit_excepthandler(&mut self, node: &ExceptHandler) {
+        self.enter(NodeType::ExceptHandler, "ExceptHandler", node.span);
+        visit::walk_excepthandler(self, node);
+        self.exit();
+    }
+
+    fn visit_arg(&mut self, node: &Arg) {
+        self.enter(NodeType::Arg, "arg", node.span);
+        visit::walk_arg(self, node);
+        self.exit();
+    }
+
+    fn visit_keyword(&mut self, node: &Keyword) {
+        self.enter(NodeType::Keyword, "keyword", node.span);
+        visit::walk_keyword(self, node);
+        self.exit();
+    }
+
+    fn visit_alias(&mut self, node: &Alias) {
+        self.enter(NodeType::Alias, "alias", node.span);
+        visit::walk_alias(self, node);
+        self.exit();
+    }
+}
+
+/// Parses `source` in `mode` and lays its AST over its tokens.
+fn build(source: &str, mode: Mode, version: PythonVersion) -> Result<CstNode, SyntaxError> {
+    let tree = parse_with_version(source, mode, version)?;
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+
+    let mut collector = Collector {
+        line_starts: &line_starts,
+        stack: vec![],
+    };
+    collector.enter(NodeType::Mod, tree.name(), Span::default());
+    collector.visit_mod(&tree);
+    let mut root = collector.stack.pop().expect("Unbalanced frames");
+    root.children.sort_by_key(|child| child.start);
+
+    let leaves = leaves(source, &line_starts, version);
+    let builder = Builder {
+        matching: matching_brackets(&leaves),
+        leaves,
+    };
+    let mut leaves = builder.leaves.iter().map(|leaf| leaf.element.clone());
+    Ok(builder.node(root, 0, builder.leaves.len(), &mut leaves))
+}
+
+/// Splits `source` into its tokens and the whitespace between them. Tokens without text,
+/// such as `DEDENT`, are dropped.
+fn leaves(source: &str, line_starts: &[usize], version: PythonVersion) -> Vec<Leaf> {
+    let mut tokenizer = Tokenizer::new(source.to_string()).with_version(version);
+    tokenizer.scan_tokens();
+    let offset = |lineno: usize, col: usize| match line_starts.get(lineno.max(1) - 1) {
+        Some(start) => (start + col).min(source.len()),
+        None => source.len(),
+    };
+    let mut leaves = vec![];
+    let mut cursor = 0;
+    for token in tokenizer.tokens() {
+        let start = offset(token.span.lineno, token.span.col_offset).max(cursor);
+        let end = offset(token.span.end_lineno, token.span.end_col_offset);
+        if end <= start {
+            continue;
+        }
+        if start > cursor {
+            leaves.push(Leaf {
+                element: CstElement::Whitespace(source[cursor..start].to_string()),
+                start: cursor,
+                end: start,
+            });
+        }
+        leaves.push(Leaf {
+            element: CstElement::Token(CstToken {
+                token_type: token.token_type.clone(),
+                text: source[start..end].to_string(),
+            }),
+            start,
+            end,
+        });
+        cursor = end;
+    }
+    if cursor < source.len() {
+        leaves.push(Leaf {
+            element: CstElement::Whitespace(source[cursor..].to_string()),
+            start: cursor,
+            end: source.len(),
+        });
+    }
+    leaves
+}
+
+/// The index of the closing bracket of each opening bracket among `leaves`.
+fn matching_brackets(leaves: &[Leaf]) -> HashMap<usize, usize> {
+    let mut matching = HashMap::new();
+    let mut open = vec![];
+    for (i, leaf) in leaves.iter().enumerate() {
+        if leaf.is(TokenType::LPar) || leaf.is(TokenType::LSqB) || leaf.is(TokenType::LBrace) {
+            open.push(i);
+        } else if leaf.is(TokenType::RPar) || leaf.is(TokenType::RSqB) || leaf.is(TokenType::RBrace)
+        {
+            if let Some(start) = open.pop() {
+                matching.insert(start, i);
+            }
+        }
+    }
+    matching
+}
+
+struct Builder {
+    leaves: Vec<Leaf>,
+    matching: HashMap<usize, usize>,
+}
+
+impl Builder {
+    /// Builds the node of `frame` from the leaves in `start..end`, taking their elements in
+    /// order from `elements`.
+    fn node(
+        &self,
+        frame: Frame,
+        start: usize,
+        end: usize,
+        elements: &mut impl Iterator<Item = CstElement>,
+    ) -> CstNode {
+        let is_class = frame.name == "ClassDef";
+        let mut children = vec![];
+        let mut cursor = start;
+        let mut sibling = None;
+        for (child, (mut child_start, mut child_end)) in self.layout(frame.children, start, end) {
+            let mut parens = 0;
+            if matches!(child.node_type, NodeType::Expr | NodeType::Pattern) {
+                let own = (sibling.unwrap_or(cursor), cursor, end);
+                while let Some((open, close)) =
+                    self.wrapping_parens(child_start, child_end, own, is_class)
+                {
+                    (child_start, child_end) = (open, close + 1);
+                    parens += 1;
+                }
+            }
+            children.extend(elements.take(child_start - cursor));
+            let mut node = self.node(child, child_start, child_end, elements);
+            node.parens = parens;
+            children.push(CstElement::Node(node));
+            sibling = Some(child_start);
+            cursor = child_end;
+        }
+        children.extend(elements.take(end - cursor));
+        CstNode {
+            node_type: frame.node_type,
+            name: frame.name,
+            span: frame.span,
+            parens: 0,
+            children,
+        }
+    }
+
+    /// The leaves of each child in `children` that fits in `start..end` after the previous
+    /// one. Children that do not fit, or hold no token, are replaced by their own children.
+    fn layout(
+        &self,
+        children: Vec<Frame>,
+        start: usize,
+        end: usize,
+    ) -> Vec<(Frame, (usize, usize))> {
+        let mut pending = children;
+        pending.reverse();
+        let mut laid_out = vec![];
+        let mut cursor = start;
+        while let Some(child) = pending.pop() {
+            match self.leaf_range(&child, cursor, end) {
+                Some(range) => {
+                    cursor = range.1;
+                    laid_out.push((child, range));
+                }
+                None => {
+                    pending.extend(child.children);
+                    pending.sort_by_key(|frame| std::cmp::Reverse(frame.start));
+                }
+            }
+        }
+        laid_out
+    }
+
+    /// The leaves from the first to the last significant token in the span of `frame`, if
+    /// any, within `start..end`.
+    fn leaf_range(&self, frame: &Frame, start: usize, end: usize) -> Option<(usize, usize)> {
+        let inside = |leaf: &&Leaf| leaf.is_significant() && leaf.start >= frame.start;
+        let first = start
+            + self.leaves[start..end]
+                .iter()
+                .position(|leaf| inside(&leaf))?;
+        let last = first
+            + self.leaves[first..end]
+                .iter()
+                .rposition(|leaf| leaf.is_significant() && leaf.end <= frame.end)?;
+        let mut first = first;
+        // The span of a decorated definition starts after its first `@`.
+        if matches!(frame.name, "FunctionDef" | "AsyncFunctionDef" | "ClassDef") {
+            if let Some(at) = self.previous_significant(first, start) {
+                if self.leaves[at].is(TokenType::At) {
+                    first = at;
+                }
+            }
+        }
+        Some((first, last + 1))
+    }
+
+    /// The parentheses directly around the leaves `start..end` of a child, if they are its
+    /// own rather than those of a call or a class definition. The leaves of its parent before
+    /// it are those from `sibling`, the start of the previous child, and the parent's own
+    /// leaves around it are those in `lower..upper`.
+    fn wrapping_parens(
+        &self,
+        start: usize,
+        end: usize,
+        (sibling, lower, upper): (usize, usize, usize),
+        is_class: bool,
+    ) -> Option<(usize, usize)> {
+        let open = self.previous_significant(start, lower)?;
+        let close = end
+            + self.leaves[end..upper]
+                .iter()
+                .position(Leaf::is_significant)?;
+        if !self.leaves[open].is(TokenType::LPar) || self.matching.get(&open) != Some(&close) {
+            return None;
+        }
+        // Parentheses right after a sibling, like `f` in `f(x)`, are those of a call.
+        if let Some(before) = self.previous_significant(open, sibling) {
+            let leaf = &self.leaves[before];
+            let after_class_name = leaf.is(TokenType::Name) || leaf.is(TokenType::RSqB);
+            if before < lower || (is_class && after_class_name) {
+                return None;
+            }
+        }
+        Some((open, close))
+    }
+
+    /// The last significant leaf before `index`, if any from `lower` on.
+    fn previous_significant(&self, index: usize, lower: usize) -> Option<usize> {
+        (lower..index)
+            .rev()
+            .find(|&i| self.leaves[i].is_significant())
+    }
+}
+
+#[test]
+fn test_cst_round_trip() {
+    use crate::dump::dump;
+
+    let sources = [
+        "",
+        "x",
+        "\n\n# only a comment",
+        "x = 1  # trailing\r\ny = (  # open\r\n    2\r\n)\r\n",
+        "if x:\n\ty = [\n  1,\n\t2,  # two\n]\n\n\n\telse_ = \\\n    3\nz\n",
+        "@a.b(c)\n@ d  # comment\nasync def f[T](x: (int), /, *a, k=(1), **kw) -> (T):\n    '''doc'''\n    return (yield)\n",
+        "class A[T](B, (C), metaclass=(M)):\n    x: int = 1; y = 2;\n",
+        "f'a{b!r:>{w}}c{{d}}' rf\"\\{e=}\" 'x' f'''{\n    g\n}'''\n",
+        "match (p):\n    case (1 | 2) as y if (y):\n        pass\n    case C(a=(b)):\n        pass\n",
+        "with (open(a) as b, c):\n    pass\nwith (a):\n    pass\n",
+        "try:\n    pass\nexcept* (E) as e:\n    raise (e) from (None)\nfinally:\n    del (x), y\n",
+        "print((a), (b for b in c), *(d), **(e))\n((f))(g)[(h)]\x0c\nlambda: (i)\n",
+        "from . import (a as b,\n    c)\nimport d.e as f\n",
+    ];
+    for source in sources {
+        let cst = parse_cst(source, PythonVersion::default()).unwrap();
+        assert_eq!(cst.code(), source);
+        assert_eq!(
+            dump(&cst.to_ast(PythonVersion::default()).unwrap(), None, true),
+            dump(
+                &crate::parser::parse(source, Mode::Exec).unwrap(),
+                None,
+                true
+            )
+        );
+        // Every expression parses on its own, but the parts of f-strings.
+        let mut stack = vec![&cst];
+        while let Some(node) = stack.pop() {
+            if node.node_type == NodeType::Expr && !matches!(node.name, "Starred" | "Slice") {
+                assert!(node.to_ast(PythonVersion::default()).is_ok(), "{}", node);
+            }
+            if node.name != "JoinedStr" {
+                stack.extend(node.nodes());
+            }
+        }
+    }
+    assert!(parse_cst("x = (", PythonVersion::default()).is_err());
+}
+
+#[test]
+fn test_cst_structure() {
+    let source = "@d\ndef f(x):\n    return g((x), y)  # c\nclass A(B): pass\n";
+    let cst = parse_cst(source, PythonVersion::default()).unwrap();
+    let summary: Vec<String> = cst
+        .descendants()
+        .iter()
+        .map(|node| format!("{} {:?} {}", node.name, node.code(), node.parens))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            format!("Module {:?} 0", source),
+            "FunctionDef \"@d\\ndef f(x):\\n    return g((x), y)\" 0".to_string(),
+            "Name \"d\" 0".to_string(),
+            "arg \"x\" 0".to_string(),
+            "Return \"return g((x), y)\" 0".to_string(),
+            "Call \"g((x), y)\" 0".to_string(),
+            "Name \"g\" 0".to_string(),
+            "Name \"(x)\" 1".to_string(),
+            "Name \"y\" 0".to_string(),
+            "ClassDef \"class A(B): pass\" 0".to_string(),
+            "Name \"B\" 0".to_string(),
+            "Pass \"pass\" 0".to_string(),
+        ]
+    );
+    // Trivia around a node belongs to its parent.
+    let comments: Vec<&str> = cst
+        .tokens()
+        .filter(|token| token.token_type == TokenType::Comment)
+        .map(|token| token.text.as_str())
+        .collect();
+    assert_eq!(comments, ["# c"]);
+
+    let call = CstNode::expression("(f(a))  # c", PythonVersion::default()).unwrap();
+    assert_eq!(
+        (call.name, call.code().as_str(), call.parens),
+        ("Call", "(f(a))", 1)
+    );
+    let statement = CstNode::statement("x = 1  # c\n", PythonVersion::default()).unwrap();
+    assert_eq!(
+        (statement.name, statement.code().as_str()),
+        ("Assign", "x = 1")
+    );
+    assert!(CstNode::statement("x\ny\n", PythonVersion::default()).is_err());
+}
+
+#[test]
+fn test_cst_transform() {
+    use crate::dump::dump;
+
+    /// Replaces the name `old` with `new`, and `total` with `a + b`.
+    struct Rename;
+    impl Transformer for Rename {
+        fn visit(&mut self, node: &CstNode) -> bool {
+            // Leave nested functions alone.
+            node.name != "FunctionDef" || node.span.lineno == 1
+        }
+
+        fn leave(&mut self, node: CstNode) -> CstNode {
+            let version = PythonVersion::default();
+            match (node.name, node.code().as_str()) {
+                ("Name", "old") => CstNode::expression("new", version).unwrap(),
+                ("Name", "total") => CstNode::expression("a + b", version)
+                    .unwrap()
+                    .parenthesize(),
+                _ => node,
+            }
+        }
+    }
+
+    let source =
+        "def f(old):\n    # keep   this\n    return [old ,  old]*total  # why\n    def g(): old\n";
+    let cst = parse_cst(source, PythonVersion::default()).unwrap();
+    let transformed = cst.transform(&mut Rename);
+    let expected = "def f(old):\n    # keep   this\n    return [new ,  new]*(a + b)  # why\n    def g(): old\n";
+    assert_eq!(transformed.code(), expected);
+    assert_eq!(
+        dump(
+            &transformed.to_ast(PythonVersion::default()).unwrap(),
+            None,
+            false
+        ),
+        dump(
+            &crate::parser::parse(expected, Mode::Exec).unwrap(),
+            None,
+            false
+        )
+    );
+}
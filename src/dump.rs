@@ -0,0 +1,566 @@
+//! Text rendering of AST nodes matching CPython's `ast.dump` (as of Python 3.12), along with
+//! the S-expressions and JSON printed by `pragrat parse`.
+
+use crate::types::{Literal, Span};
+
+/// A node, or a value held by one, the way `ast.dump` sees it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DumpValue {
+    Node {
+        name: &'static str,
+        fields: Vec<(&'static str, DumpValue)>,
+        attributes: Vec<(&'static str, DumpValue)>,
+    },
+    List(Vec<DumpValue>),
+    /// A scalar, already rendered with Python's `repr()`.
+    Repr(String),
+    /// An unset optional field. `ast.dump` leaves these out.
+    None,
+}
+
+impl DumpValue {
+    pub fn node(name: &'static str, fields: Vec<(&'static str, DumpValue)>) -> Self {
+        DumpValue::Node {
+            name,
+            fields,
+            attributes: vec![],
+        }
+    }
+
+    /// Adds the location attributes printed by `include_attributes`.
+    pub fn with_span(mut self, span: Span) -> Self {
+        if let DumpValue::Node { attributes, .. } = &mut self {
+            *attributes = vec![
+                ("lineno", span.lineno.to_dump()),
+                ("col_offset", span.col_offset.to_dump()),
+                ("end_lineno", span.end_lineno.to_dump()),
+                ("end_col_offset", span.end_col_offset.to_dump()),
+            ];
+        }
+        self
+    }
+}
+
+/// Implemented by every AST node and field type.
+pub trait Dump {
+    fn to_dump(&self) -> DumpValue;
+}
+
+impl<T: Dump + ?Sized> Dump for Box<T> {
+    fn to_dump(&self) -> DumpValue {
+        (**self).to_dump()
+    }
+}
+
+impl<T: Dump> Dump for Option<T> {
+    fn to_dump(&self) -> DumpValue {
+        match self {
+            Some(value) => value.to_dump(),
+            None => DumpValue::None,
+        }
+    }
+}
+
+impl<T: Dump> Dump for Vec<T> {
+    fn to_dump(&self) -> DumpValue {
+        DumpValue::List(self.iter().map(Dump::to_dump).collect())
+    }
+}
+
+impl Dump for String {
+    fn to_dump(&self) -> DumpValue {
+        DumpValue::Repr(str_repr(self))
+    }
+}
+
+/// ASDL `int` fields such as `is_async` are printed as numbers.
+impl Dump for bool {
+    fn to_dump(&self) -> DumpValue {
+        DumpValue::Repr(u8::from(*self).to_string())
+    }
+}
+
+impl Dump for i32 {
+    fn to_dump(&self) -> DumpValue {
+        DumpValue::Repr(self.to_string())
+    }
+}
+
+impl Dump for usize {
+    fn to_dump(&self) -> DumpValue {
+        DumpValue::Repr(self.to_string())
+    }
+}
+
+impl Dump for Literal {
+    fn to_dump(&self) -> DumpValue {
+        DumpValue::Repr(match self {
+            Literal::String(value) => str_repr(value),
+            Literal::Bytes(value) => bytes_repr(value),
+            Literal::Number(value) => number_repr(value),
+            Literal::Bool(true) => "True".to_string(),
+            Literal::Bool(false) => "False".to_string(),
+            Literal::None => "None".to_string(),
+            Literal::Ellipsis => "Ellipsis".to_string(),
+        })
+    }
+}
+
+/// How `pragrat parse` prints the AST.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum DumpFormat {
+    /// The text of `ast.dump`.
+    Text,
+    /// The nodes as serialized with the `serde` feature, tagged with their `_type`.
+    Json,
+    /// S-expressions such as `(Name :id 'x' :ctx (Load))`.
+    Sexpr,
+}
+
+/// Renders `node` like `ast.dump(node, indent=indent, include_attributes=include_attributes)`.
+pub fn dump(node: &impl Dump, indent: Option<usize>, include_attributes: bool) -> String {
+    let formatter = Formatter {
+        indent: indent.map(|width| " ".repeat(width)),
+        include_attributes,
+    };
+    formatter.format(&node.to_dump(), 0).0
+}
+
+/// Renders `node` as an S-expression, a list of the node's name and its fields as
+/// `:field value` pairs. With `indent`, nodes and lists that do not fit the `ast.dump` notion
+/// of simple are broken over lines.
+pub fn dump_sexpr(node: &impl Dump, indent: Option<usize>, include_attributes: bool) -> String {
+    let formatter = Formatter {
+        indent: indent.map(|width| " ".repeat(width)),
+        include_attributes,
+    };
+    formatter.sexpr(&node.to_dump(), 0).0
+}
+
+/// Renders `node` as JSON, without the location attributes unless `include_attributes`.
+#[cfg(feature = "serde")]
+pub fn dump_json(
+    node: &impl serde::Serialize,
+    indent: Option<usize>,
+    include_attributes: bool,
+) -> String {
+    use serde::Serialize;
+
+    fn strip_attributes(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                // `TypeIgnore` has a `lineno` field, but no `end_col_offset`.
+                if map.contains_key("end_col_offset") {
+                    for key in ["lineno", "col_offset", "end_lineno", "end_col_offset"] {
+                        map.remove(key);
+                    }
+                }
+                map.values_mut().for_each(strip_attributes);
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(strip_attributes),
+            _ => {}
+        }
+    }
+
+    let mut value = serde_json::to_value(node).expect("AST nodes serialize to JSON");
+    if !include_attributes {
+        strip_attributes(&mut value);
+    }
+    let Some(width) = indent else {
+        return value.to_string();
+    };
+    let indent = " ".repeat(width);
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut out = Vec::new();
+    let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+    value
+        .serialize(&mut serializer)
+        .expect("JSON values serialize");
+    String::from_utf8(out).expect("JSON is UTF-8")
+}
+
+struct Formatter {
+    indent: Option<String>,
+    include_attributes: bool,
+}
+
+impl Formatter {
+    /// Port of `_format` in CPython's `Lib/ast.py`. Returns the text and whether it is "simple",
+    /// i.e. may stay on the line of its parent.
+    fn format(&self, value: &DumpValue, level: usize) -> (String, bool) {
+        let (level, prefix, sep) = match &self.indent {
+            Some(indent) => {
+                let level = level + 1;
+                let prefix = format!("\n{}", indent.repeat(level));
+                let sep = format!(",\n{}", indent.repeat(level));
+                (level, prefix, sep)
+            }
+            None => (level, String::new(), ", ".to_string()),
+        };
+        match value {
+            DumpValue::Node {
+                name,
+                fields,
+                attributes,
+            } => {
+                let mut args = vec![];
+                let mut all_simple = true;
+                let attributes = if self.include_attributes {
+                    attributes.as_slice()
+                } else {
+                    &[]
+                };
+                for (field, value) in fields.iter().chain(attributes) {
+                    if *value == DumpValue::None {
+                        continue;
+                    }
+                    let (value, simple) = self.format(value, level);
+                    all_simple &= simple;
+                    args.push(format!("{}={}", field, value));
+                }
+                if all_simple && args.len() <= 3 {
+                    return (format!("{}({})", name, args.join(", ")), args.is_empty());
+                }
+                (format!("{}({}{})", name, prefix, args.join(&sep)), false)
+            }
+            DumpValue::List(items) if items.is_empty() => ("[]".to_string(), true),
+            DumpValue::List(items) => {
+                let items: Vec<String> = items
+                    .iter()
+                    .map(|item| self.format(item, level).0)
+                    .collect();
+                (format!("[{}{}]", prefix, items.join(&sep)), false)
+            }
+            DumpValue::Repr(repr) => (repr.clone(), true),
+            DumpValue::None => ("None".to_string(), true),
+        }
+    }
+
+    /// `format` for `dump_sexpr`.
+    fn sexpr(&self, value: &DumpValue, level: usize) -> (String, bool) {
+        let (level, prefix, sep) = match &self.indent {
+            Some(indent) => {
+                let sep = format!("\n{}", indent.repeat(level + 1));
+                (level + 1, sep.clone(), sep)
+            }
+            None => (level, String::new(), " ".to_string()),
+        };
+        match value {
+            DumpValue::Node {
+                name,
+                fields,
+                attributes,
+            } => {
+                let mut args = vec![];
+                let mut all_simple = true;
+                let attributes = if self.include_attributes {
+                    attributes.as_slice()
+                } else {
+                    &[]
+                };
+                for (field, value) in fields.iter().chain(attributes) {
+                    if *value == DumpValue::None {
+                        continue;
+                    }
+                    let (value, simple) = self.sexpr(value, level);
+                    all_simple &= simple;
+                    args.push(format!(":{} {}", field, value));
+                }
+                if args.is_empty() {
+                    return (format!("({})", name), true);
+                }
+                if all_simple && args.len() <= 3 {
+                    return (format!("({} {})", name, args.join(" ")), false);
+                }
+                (format!("({}{}{})", name, sep, args.join(&sep)), false)
+            }
+            DumpValue::List(items) if items.is_empty() => ("()".to_string(), true),
+            DumpValue::List(items) => {
+                let items: Vec<String> =
+                    items.iter().map(|item| self.sexpr(item, level).0).collect();
+                (format!("({}{})", prefix, items.join(&sep)), false)
+            }
+            DumpValue::Repr(repr) => (repr.clone(), true),
+            DumpValue::None => ("None".to_string(), true),
+        }
+    }
+}
+
+/// Python's `repr()` of a `str`.
+pub fn str_repr(value: &str) -> String {
+    let quote = if value.contains('\'') && !value.contains('"') {
+        '"'
+    } else {
+        '\''
+    };
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push(quote);
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c == quote => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if is_printable(c) => out.push(c),
+            c if (c as u32) < 0x100 => out.push_str(&format!("\\x{:02x}", c as u32)),
+            c if (c as u32) < 0x10000 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push_str(&format!("\\U{:08x}", c as u32)),
+        }
+    }
+    out.push(quote);
+    out
+}
+
+/// Python's `repr()` of a `bytes`.
+pub fn bytes_repr(value: &[u8]) -> String {
+    let quote = if value.contains(&b'\'') && !value.contains(&b'"') {
+        b'"'
+    } else {
+        b'\''
+    };
+    let mut out = String::with_capacity(value.len() + 3);
+    out.push('b');
+    out.push(char::from(quote));
+    for &b in value {
+        match b {
+            b'\\' => out.push_str("\\\\"),
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b'\t' => out.push_str("\\t"),
+            b if b == quote => {
+                out.push('\\');
+                out.push(char::from(b));
+            }
+            0x20..=0x7e => out.push(char::from(b)),
+            b => out.push_str(&format!("\\x{:02x}", b)),
+        }
+    }
+    out.push(char::from(quote));
+    out
+}
+
+/// Mirrors `str.isprintable()`, which decides what `repr()` escapes.
+pub(crate) fn is_printable(c: char) -> bool {
+    let c = c as u32;
+    crate::unicode::NON_PRINTABLE
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                std::cmp::Ordering::Less
+            } else if start > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_err()
+}
+
+/// Python's `repr()` of the value of a number literal, e.g. `16` for `0x10` or `1e+16` for
+/// `10_000_000_000_000_000.0`.
+pub fn number_repr(literal: &str) -> String {
+    let text = literal.replace('_', "").to_ascii_lowercase();
+    if let Some(imag) = text.strip_suffix('j') {
+        // A complex with a zero real part prints its imaginary part without a forced `.0`.
+        let repr = float_repr(imag.parse().unwrap_or(f64::INFINITY));
+        let repr = repr.strip_suffix(".0").unwrap_or(&repr);
+        return format!("{}j", repr);
+    }
+    let radix = match text.get(..2) {
+        Some("0x") => 16,
+        Some("0o") => 8,
+        Some("0b") => 2,
+        _ if text.contains(['.', 'e']) => {
+            return float_repr(text.parse().unwrap_or(f64::INFINITY));
+        }
+        _ => 10,
+    };
+    let digits = if radix == 10 { &text[..] } else { &text[2..] };
+    to_decimal(digits, radix)
+}
+
+/// Converts an arbitrarily long unsigned integer to base 10.
+fn to_decimal(digits: &str, radix: u32) -> String {
+    // Little-endian limbs in base 10^9.
+    const BASE: u64 = 1_000_000_000;
+    let mut limbs: Vec<u64> = vec![0];
+    for digit in digits.chars().filter_map(|c| c.to_digit(radix)) {
+        let mut carry = u64::from(digit);
+        for limb in limbs.iter_mut() {
+            let value = *limb * u64::from(radix) + carry;
+            *limb = value % BASE;
+            carry = value / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+    }
+    let mut out = limbs.last().unwrap().to_string();
+    for limb in limbs.iter().rev().skip(1) {
+        out.push_str(&format!("{:09}", limb));
+    }
+    out
+}
+
+/// Python's `repr()` of a `float`: the shortest round-tripping digits, in scientific notation
+/// when the exponent is below -4 or at least 16.
+pub fn float_repr(value: f64) -> String {
+    if value.is_infinite() {
+        return if value > 0.0 { "inf" } else { "-inf" }.to_string();
+    }
+    if value.is_nan() {
+        return "nan".to_string();
+    }
+    if value == 0.0 {
+        let zero = match value.is_sign_negative() {
+            true => "-0.0",
+            false => "0.0",
+        };
+        return zero.to_string();
+    }
+    // Ryu gives the shortest digits, rounding ties to even as CPython does, in a notation
+    // of its own.
+    let mut buffer = ryu::Buffer::new();
+    let shortest = buffer.format_finite(value);
+    let (sign, shortest) = match shortest.strip_prefix('-') {
+        Some(shortest) => ("-", shortest),
+        None => ("", shortest),
+    };
+    let (mantissa, exponent) = shortest.split_once('e').unwrap_or((shortest, "0"));
+    let point = mantissa.find('.').unwrap_or(mantissa.len());
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let leading = digits.len() - digits.trim_start_matches('0').len();
+    let digits = digits.trim_matches('0');
+    let exponent = exponent.parse::<i32>().unwrap() + point as i32 - leading as i32 - 1;
+    if !(-4..16).contains(&exponent) {
+        let mantissa = match digits.split_at(1) {
+            (first, "") => first.to_string(),
+            (first, rest) => format!("{}.{}", first, rest),
+        };
+        let exp_sign = if exponent < 0 { '-' } else { '+' };
+        return format!("{}{}e{}{:02}", sign, mantissa, exp_sign, exponent.abs());
+    }
+    let point = exponent + 1;
+    let (int_part, frac_part) = if point <= 0 {
+        let zeros = "0".repeat(point.unsigned_abs() as usize);
+        ("0".to_string(), format!("{}{}", zeros, digits))
+    } else if point as usize >= digits.len() {
+        let zeros = "0".repeat(point as usize - digits.len());
+        (format!("{}{}", digits, zeros), String::new())
+    } else {
+        let (int_part, frac_part) = digits.split_at(point as usize);
+        (int_part.to_string(), frac_part.to_string())
+    };
+    let frac_part = if frac_part.is_empty() {
+        "0"
+    } else {
+        &frac_part
+    };
+    format!("{}{}.{}", sign, int_part, frac_part)
+}
+
+#[test]
+fn test_reprs() {
+    assert_eq!(str_repr("a"), "'a'");
+    assert_eq!(str_repr("it's"), "\"it's\"");
+    assert_eq!(str_repr("'\"\n\u{7}é"), "'\\'\"\\n\\x07é'");
+    assert_eq!(str_repr("\u{fffe}\u{fdd0}"), "'\\ufffe\\ufdd0'");
+    // Unassigned code points and the Mongolian vowel separator (Cf) are escaped too.
+    assert_eq!(
+        str_repr("\u{378}\u{180e}\u{1c8a}\u{d7ff}\u{1c88}"),
+        "'\\u0378\\u180e\\u1c8a\\ud7ff\u{1c88}'"
+    );
+    assert_eq!(bytes_repr(b"a\0\"'"), "b'a\\x00\"\\''");
+    assert_eq!(bytes_repr(b"\xff\t"), "b'\\xff\\t'");
+    let numbers = [
+        ("1", "1"),
+        ("0x10", "16"),
+        ("0b_1010", "10"),
+        ("0o777", "511"),
+        ("1_000", "1000"),
+        (
+            "0xffffffffffffffffffffffff",
+            "79228162514264337593543950335",
+        ),
+        ("1.0", "1.0"),
+        ("1.", "1.0"),
+        (".5", "0.5"),
+        ("1E5", "100000.0"),
+        ("1e16", "1e+16"),
+        ("0.0001", "0.0001"),
+        ("0.00001", "1e-05"),
+        ("123456789012345678.0", "1.2345678901234568e+17"),
+        ("1e400", "inf"),
+        // Ties between the shortest digits go to the even one.
+        ("99.99880981445312", "99.99880981445312"),
+        ("0.0000028610229492187500", "2.86102294921875e-06"),
+        ("0.19999999999999998", "0.19999999999999998"),
+        ("5e-324", "5e-324"),
+        ("1.7976931348623157e308", "1.7976931348623157e+308"),
+        ("0.0", "0.0"),
+        ("1j", "1j"),
+        ("2.0j", "2j"),
+        ("1.5J", "1.5j"),
+        ("1e20j", "1e+20j"),
+    ];
+    for (literal, repr) in numbers {
+        assert_eq!(number_repr(literal), repr, "{}", literal);
+    }
+}
+
+#[test]
+fn test_dump_sexpr() {
+    use crate::parser::{parse, Mode};
+
+    let tree = parse("x = f(1)\n", Mode::Exec).unwrap();
+    assert_eq!(
+        dump_sexpr(&tree, None, false),
+        "(Module :body ((Assign :targets ((Name :id 'x' :ctx (Store))) :value (Call :func (Name :id 'f' :ctx (Load)) :args ((Constant :value 1)) :keywords ()))) :type_ignores ())"
+    );
+    assert_eq!(
+        dump_sexpr(&tree, Some(2), false),
+        "(Module
+  :body (
+    (Assign
+      :targets (
+        (Name :id 'x' :ctx (Store)))
+      :value (Call
+        :func (Name :id 'f' :ctx (Load))
+        :args (
+          (Constant :value 1))
+        :keywords ())))
+  :type_ignores ())"
+    );
+    let tree = parse("x\n", Mode::Eval).unwrap();
+    assert_eq!(
+        dump_sexpr(&tree, None, true),
+        "(Expression :body (Name :id 'x' :ctx (Load) :lineno 1 :col_offset 0 :end_lineno 1 :end_col_offset 1))"
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_dump_json() {
+    use crate::parser::{parse, Mode};
+
+    let tree = parse("x\n", Mode::Eval).unwrap();
+    assert_eq!(
+        dump_json(&tree, None, false),
+        r#"{"_type":"Expression","body":{"_type":"Name","id":"x","ctx":{"_type":"Load"}}}"#
+    );
+    assert_eq!(
+        dump_json(&tree, Some(1), true),
+        "{\n \"_type\": \"Expression\",\n \"body\": {\n  \"_type\": \"Name\",\n  \"id\": \"x\",\n  \"ctx\": {\n   \"_type\": \"Load\"\n  },\n  \"lineno\": 1,\n  \"col_offset\": 0,\n  \"end_lineno\": 1,\n  \"end_col_offset\": 1\n }\n}"
+    );
+    let type_ignore = crate::ast::TypeIgnore {
+        lineno: 1,
+        tag: "[a]".to_string(),
+    };
+    assert_eq!(
+        dump_json(&type_ignore, None, false),
+        r#"{"_type":"TypeIgnore","lineno":1,"tag":"[a]"}"#
+    );
+}
@@ -0,0 +1,246 @@
+//! Parsing of many files at once on a work-stealing thread pool, with throughput statistics.
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+use rayon::prelude::*;
+
+use crate::ast::Module;
+use crate::parser::{parse_recovering, SyntaxError};
+use crate::types::{PythonVersion, Span};
+
+/// The module parsed from a file, or its syntax errors.
+pub type FileResult = Result<Module, Vec<SyntaxError>>;
+
+/// How `parse_files` parses.
+#[derive(Clone, Debug)]
+pub struct ParseOptions {
+    /// Syntax added after this version is an error.
+    pub version: PythonVersion,
+    /// Threads of the pool, one per core if `None`.
+    pub threads: Option<usize>,
+    /// Results parsed but not yet handled before workers wait, which bounds the modules held
+    /// in memory.
+    pub buffer: usize,
+    /// Number of the slowest files kept in the statistics.
+    pub slowest: usize,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            version: PythonVersion::default(),
+            threads: None,
+            buffer: 64,
+            slowest: 10,
+        }
+    }
+}
+
+/// Aggregate statistics of a `parse_files` or `parse_files_streaming` run.
+#[derive(Clone, Debug, Default)]
+pub struct ParseStats {
+    pub files: usize,
+    /// Files with syntax errors, or that could not be read.
+    pub failed: usize,
+    pub bytes: usize,
+    /// Wall-clock time of the whole run.
+    pub elapsed: Duration,
+    /// The slowest files and how long each took to read and parse, slowest first.
+    pub slowest: Vec<(PathBuf, Duration)>,
+}
+
+impl ParseStats {
+    pub fn files_per_second(&self) -> f64 {
+        self.files as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    pub fn megabytes_per_second(&self) -> f64 {
+        self.bytes as f64 / 1e6 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    fn record(&mut self, path: &Path, bytes: usize, failed: bool, time: Duration, keep: usize) {
+        self.files += 1;
+        self.failed += usize::from(failed);
+        self.bytes += bytes;
+        let at = self.slowest.partition_point(|(_, slower)| *slower >= time);
+        if at < keep {
+            self.slowest.insert(at, (path.to_path_buf(), time));
+            self.slowest.truncate(keep);
+        }
+    }
+}
+
+impl fmt::Display for ParseStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Parsed {} files ({:.1} MB, {} failed) in {:.2?}: {:.0} files/s, {:.1} MB/s",
+            self.files,
+            self.bytes as f64 / 1e6,
+            self.failed,
+            self.elapsed,
+            self.files_per_second(),
+            self.megabytes_per_second()
+        )?;
+        for (path, time) in &self.slowest {
+            writeln!(f, "{:>10.2?}  {}", time, path.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the modules at `paths` in parallel, returning the result of each in the order of
+/// `paths` along with the statistics of the run. A file that cannot be read fails with a
+/// single error holding the I/O error's message, located at line 0.
+pub fn parse_files(
+    paths: &[PathBuf],
+    options: &ParseOptions,
+) -> (Vec<(PathBuf, FileResult)>, ParseStats) {
+    let mut results: Vec<Option<FileResult>> = paths.iter().map(|_| None).collect();
+    let stats = parse_indexed(paths, options, |i, result| results[i] = Some(result));
+    let results = paths
+        .iter()
+        .cloned()
+        .zip(results.into_iter().map(Option::unwrap))
+        .collect();
+    (results, stats)
+}
+
+/// Parses the modules at `paths` in parallel like `parse_files`, handing each result to
+/// `on_result` on the calling thread as soon as it is ready, in no particular order. At most
+/// `options.buffer` results wait to be handled, so only the modules `on_result` keeps are
+/// held in memory.
+pub fn parse_files_streaming(
+    paths: &[PathBuf],
+    options: &ParseOptions,
+    mut on_result: impl FnMut(&Path, FileResult),
+) -> ParseStats {
+    parse_indexed(paths, options, |i, result| on_result(&paths[i], result))
+}
+
+/// Parses `paths` in parallel, handing each result to `on_result` with the index of its path.
+fn parse_indexed(
+    paths: &[PathBuf],
+    options: &ParseOptions,
+    mut on_result: impl FnMut(usize, FileResult),
+) -> ParseStats {
+    let start = Instant::now();
+    let mut stats = ParseStats::default();
+    let (sender, receiver) = mpsc::sync_channel(options.buffer.max(1));
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            let parse_all = || {
+                let indexed = paths.par_iter().enumerate();
+                indexed.for_each_with(sender, |sender, (i, path)| {
+                    let start = Instant::now();
+                    let (bytes, result) = parse_file(path, options.version);
+                    // The receiver only hangs up once every result is in.
+                    let _ = sender.send((i, bytes, result, start.elapsed()));
+                })
+            };
+            let pool = options.threads.map(|threads| {
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .expect("Failed to start the thread pool")
+            });
+            match pool {
+                Some(pool) => pool.install(parse_all),
+                None => parse_all(),
+            }
+        });
+        for (i, bytes, result, time) in receiver {
+            let failed = result.is_err();
+            stats.record(&paths[i], bytes, failed, time, options.slowest);
+            on_result(i, result);
+        }
+    });
+    stats.elapsed = start.elapsed();
+    stats
+}
+
+/// Reads and parses the module at `path`, returning its size in bytes along with the result.
+fn parse_file(path: &Path, version: PythonVersion) -> (usize, FileResult) {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(error) => {
+            let span = Span::default();
+            return (0, Err(vec![SyntaxError::new(error.to_string(), span)]));
+        }
+    };
+    let (module, errors) = parse_recovering(&source, version);
+    let result = if errors.is_empty() {
+        Ok(module)
+    } else {
+        Err(errors)
+    };
+    (source.len(), result)
+}
+
+#[test]
+fn test_parse_files() {
+    let root = std::env::temp_dir().join(format!("pragrat-files-{}", std::process::id()));
+    fs::create_dir_all(&root).unwrap();
+    let mut paths = vec![];
+    for i in 0..20 {
+        let path = root.join(format!("m{}.py", i));
+        let source = match i % 5 {
+            0 => "x = (\n".to_string(),
+            _ => format!("def f{}(a):\n    return a + {}\n", i, i).repeat(i),
+        };
+        fs::write(&path, source).unwrap();
+        paths.push(path);
+    }
+    paths.push(root.join("missing.py"));
+
+    let options = ParseOptions {
+        threads: Some(3),
+        buffer: 2,
+        slowest: 4,
+        ..ParseOptions::default()
+    };
+    let (results, stats) = parse_files(&paths, &options);
+    assert_eq!((stats.files, stats.failed), (21, 5));
+    assert_eq!(
+        results.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+        paths.iter().collect::<Vec<_>>()
+    );
+    for (i, (_, result)) in results.iter().enumerate() {
+        match result {
+            Ok(module) => assert_eq!(module.body.len(), i, "{}", i),
+            Err(errors) if i == 20 => assert_eq!(errors[0].span.lineno, 0),
+            Err(errors) => {
+                assert_eq!(i % 5, 0);
+                assert_eq!(errors[0].to_string(), "'(' was never closed");
+            }
+        }
+    }
+
+    let mut seen = vec![];
+    let stats = parse_files_streaming(&paths, &options, |path, result| {
+        seen.push((path.to_path_buf(), result.is_ok()));
+    });
+    seen.sort();
+    let mut expected: Vec<(PathBuf, bool)> = results
+        .iter()
+        .map(|(path, result)| (path.clone(), result.is_ok()))
+        .collect();
+    expected.sort();
+    assert_eq!(seen, expected);
+    assert_eq!((stats.files, stats.failed), (21, 5));
+    let bytes: u64 = paths
+        .iter()
+        .filter_map(|path| fs::metadata(path).ok())
+        .map(|m| m.len())
+        .sum();
+    assert_eq!(stats.bytes as u64, bytes);
+    assert_eq!(stats.slowest.len(), 4);
+    assert!(stats.slowest.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    assert!(stats.to_string().starts_with("Parsed 21 files"));
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,3420 @@
+//! A code formatter in the style of Black (as of 24.x). The source is parsed into the
+//! concrete syntax tree, whose tokens are laid out again one logical line at a time: lines
+//! longer than the line length are split at their brackets, then at their delimiters of the
+//! lowest priority, the way Black's `linegen.py` does. Comments stay where they were, while
+//! whitespace, blank lines, string prefixes and quotes, numbers, docstrings and redundant
+//! parentheses are normalized. Like Black, the result is only returned if it parses to the
+//! same AST as the source and formats to itself.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use crate::ast::*;
+use crate::cst::{parse_cst, CstElement, CstNode, CstToken, NodeType};
+use crate::dump::dump;
+use crate::parser::{parse_with_version, Mode, SyntaxError};
+use crate::types::{Literal, PythonVersion, TokenType};
+use crate::visit::{walk_constant_expr_mut, walk_exprstmt_stmt_mut, VisitorMut};
+
+/// How `format_source` lays out code, like Black's `Mode`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// The width lines are split to fit in, counting wide East Asian characters twice.
+    pub line_length: usize,
+    /// The version the source is parsed with.
+    pub version: PythonVersion,
+    /// Explode collections and calls ending with a trailing comma one item per line.
+    pub magic_trailing_comma: bool,
+    /// Prefer double quotes and lowercase string prefixes.
+    pub string_normalization: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            line_length: 88,
+            version: PythonVersion::default(),
+            magic_trailing_comma: true,
+            string_normalization: true,
+        }
+    }
+}
+
+/// Why a source could not be formatted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FormatError {
+    Syntax(SyntaxError),
+    /// The formatted code did not parse to the AST of the source, or changed when formatted
+    /// again. This is a bug in the formatter, so the source is left as it is.
+    Unsafe(String),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::Syntax(error) => write!(f, "{}", error),
+            FormatError::Unsafe(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<SyntaxError> for FormatError {
+    fn from(error: SyntaxError) -> Self {
+        FormatError::Syntax(error)
+    }
+}
+
+/// Formats the Python module in `source`, checking that the result is equivalent and stable
+/// like Black's `--safe` mode.
+pub fn format_source(source: &str, options: &FormatOptions) -> Result<String, FormatError> {
+    let formatted = format_unchecked(source, options)?;
+    if formatted == source {
+        return Ok(formatted);
+    }
+    let expected = normalized_dump(parse_with_version(source, Mode::Exec, options.version)?);
+    let actual = match parse_with_version(&formatted, Mode::Exec, options.version) {
+        Ok(tree) => normalized_dump(tree),
+        Err(error) => {
+            return Err(FormatError::Unsafe(format!(
+                "produced invalid code: {}",
+                error.render("<formatted>", &formatted)
+            )))
+        }
+    };
+    if actual != expected {
+        return Err(FormatError::Unsafe(
+            "produced code that is not equivalent to the source".to_string(),
+        ));
+    }
+    if format_unchecked(&formatted, options)? != formatted {
+        return Err(FormatError::Unsafe(
+            "produced different code on the second pass of the formatter".to_string(),
+        ));
+    }
+    Ok(formatted)
+}
+
+/// Formats `source` without the safety checks of `format_source`.
+fn format_unchecked(source: &str, options: &FormatOptions) -> Result<String, FormatError> {
+    let tree = parse_cst(source, options.version)?;
+    let varargs_comma = uses_modern_syntax(&tree);
+    let lines = LineBuilder::new(options).build(&tree);
+    let blank_lines = EmptyLineTracker::blank_lines(&lines);
+    let mut formatted = String::new();
+    let mut after = 0;
+    for (line, (before, next_after)) in lines.into_iter().zip(blank_lines) {
+        formatted.push_str(&"\n".repeat(before + after));
+        after = next_after;
+        if line.verbatim {
+            formatted.push_str(&line.render());
+            continue;
+        }
+        for line in transform_line(line, options, varargs_comma) {
+            formatted.push_str(&line.render());
+        }
+    }
+    Ok(formatted)
+}
+
+/// Whether `tree` uses syntax newer than Python 3.5, so that trailing commas may follow
+/// `*args` and `**kwargs` like Black allows once the target versions it infers are all newer.
+fn uses_modern_syntax(tree: &CstNode) -> bool {
+    tree.descendants().into_iter().any(|node| {
+        let modern_token = node.tokens().any(|token| match token.token_type {
+            TokenType::FStringStart | TokenType::ColonEqual => true,
+            TokenType::Number => token.text.contains('_'),
+            TokenType::String => {
+                let prefix = &token.text[..token.text.find(['\'', '"']).unwrap_or(0)];
+                prefix.contains(['f', 'F'])
+            }
+            TokenType::Slash => matches!(node.name, "FunctionDef" | "AsyncFunctionDef" | "Lambda"),
+            _ => false,
+        });
+        modern_token
+            || node.node_type == NodeType::TypeParam
+            || matches!(node.name, "Match" | "TryStar" | "TypeAlias")
+            || has_vararg_trailing_comma(node)
+            || (node.name == "Return"
+                && node.nodes().any(|value| {
+                    value.name == "Tuple"
+                        && value.parens == 0
+                        && value.nodes().any(|element| element.name == "Starred")
+                }))
+    })
+}
+
+/// Whether `node` is a call or definition with a trailing comma after arguments or
+/// parameters including varargs.
+fn has_vararg_trailing_comma(node: &CstNode) -> bool {
+    if !matches!(
+        node.name,
+        "Call" | "FunctionDef" | "AsyncFunctionDef" | "ClassDef"
+    ) {
+        return false;
+    }
+    let mut vararg = false;
+    let mut comma = false;
+    for element in &node.children {
+        match element {
+            CstElement::Node(child) => {
+                comma = false;
+                vararg |= child.name == "Starred"
+                    || (child.node_type == NodeType::Keyword
+                        && first_token(child).is_some_and(|token| token.text == "**"));
+            }
+            CstElement::Token(token) => match token.token_type {
+                TokenType::Star | TokenType::DoubleStar => vararg = true,
+                TokenType::Comma => comma = true,
+                TokenType::RPar if comma && vararg => return true,
+                _ if !is_trivia(&token.token_type) => comma = false,
+                _ => {}
+            },
+            CstElement::Whitespace(_) => {}
+        }
+    }
+    false
+}
+
+/// Dumps `tree` for comparison, ignoring what formatting may change: the `u` prefix of
+/// strings and the indentation of docstrings, with all standalone strings taken for one as
+/// in Black's `_stringify_ast`.
+fn normalized_dump(mut tree: Mod) -> String {
+    struct Normalize;
+    impl VisitorMut for Normalize {
+        fn visit_exprstmt_stmt_mut(&mut self, node: &mut ExprStmt) {
+            if let Expr::Constant(Constant {
+                value: Literal::String(value),
+                ..
+            }) = node.value.as_mut()
+            {
+                let lines: Vec<&str> = value.lines().map(str::trim).collect();
+                *value = lines.join("\n").trim().to_string();
+            }
+            walk_exprstmt_stmt_mut(self, node);
+        }
+
+        fn visit_constant_expr_mut(&mut self, node: &mut Constant) {
+            node.kind = None;
+            walk_constant_expr_mut(self, node);
+        }
+    }
+    Normalize.visit_mod_mut(&mut tree);
+    dump(&tree, None, false)
+}
+
+const COMPREHENSION_PRIORITY: u8 = 20;
+const COMMA_PRIORITY: u8 = 18;
+const TERNARY_PRIORITY: u8 = 16;
+const LOGIC_PRIORITY: u8 = 14;
+const STRING_PRIORITY: u8 = 12;
+const COMPARATOR_PRIORITY: u8 = 10;
+const DOT_PRIORITY: u8 = 1;
+
+/// Priority of splitting before a binary operator, from `MATH_PRIORITIES` in Black.
+fn math_priority(operator: &str) -> u8 {
+    match operator {
+        "|" => 9,
+        "^" => 8,
+        "&" => 7,
+        "<<" | ">>" => 6,
+        "+" | "-" => 5,
+        "*" | "/" | "//" | "%" | "@" => 4,
+        "**" => 1,
+        _ => 0,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    /// Names, keywords and other leaves without a kind of their own.
+    Name,
+    Number,
+    String,
+    /// An opening bracket, with an empty value while invisible.
+    Open(char),
+    Close(char),
+    Comma,
+    Colon,
+    Operator,
+    /// A comment after code, rendered at the end of the line.
+    Comment,
+    /// A comment on a line of its own, which the line must be split around.
+    StandaloneComment,
+}
+
+/// What a pair of brackets belongs to, which decides how lines split at it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Bracket {
+    /// The arguments of a call or class, or the parameters of a definition.
+    Call,
+    Subscript,
+    /// A parenthesized expression, tuple, collection or comprehension, or the invisible
+    /// parentheses the formatter adds where they would be redundant.
+    Atom,
+    /// The names imported by `from ... import`.
+    Import,
+}
+
+/// The part a leaf plays in its statement, which decides the whitespace around it and
+/// whether lines split at it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Role {
+    Atom,
+    Open(Bracket),
+    Close(Bracket),
+    /// A comma, counting twice within the arguments of a call or definition.
+    Comma {
+        arglist: bool,
+    },
+    /// A binary or keyword operator, splitting the line before it at `priority` if nonzero.
+    Operator(u8),
+    /// `**`, hugged by simple operands.
+    Power {
+        hug: bool,
+    },
+    /// `-`, `+` and `~` of a unary operation.
+    Unary,
+    /// `*` and `**` unpacking, and the `@` of a decorator.
+    Prefix,
+    /// `=` of a keyword argument, or of a default without an annotation.
+    KeywordEqual,
+    Colon,
+    /// `:` of a slice, spaced like a binary operator when the slice is not simple.
+    SliceColon {
+        complex: bool,
+    },
+    Dot,
+    /// A dot of the module of `from ... import`, spaced after `from`.
+    ImportDot,
+    /// The `*` of `except*`.
+    ExceptStar,
+}
+
+#[derive(Clone, Debug)]
+struct Leaf {
+    /// Unique within the source, so that a leaf is found again in the lines split off.
+    id: usize,
+    kind: Kind,
+    role: Role,
+    value: String,
+    /// The whitespace before the leaf on its line.
+    prefix: String,
+    bracket_depth: usize,
+    /// The id of the opening bracket, for a closing one.
+    opening_bracket: Option<usize>,
+    /// The line of the source the leaf starts on, or 0 for one added by the formatter.
+    lineno: usize,
+}
+
+impl Leaf {
+    fn is_open(&self) -> bool {
+        matches!(self.kind, Kind::Open(_))
+    }
+
+    fn is_close(&self) -> bool {
+        matches!(self.kind, Kind::Close(_))
+    }
+
+    fn is_bracket(&self) -> bool {
+        self.is_open() || self.is_close()
+    }
+
+    /// Whether the leaf is a pair of parentheses the formatter added, or made invisible.
+    fn is_invisible(&self) -> bool {
+        self.is_bracket() && self.value.is_empty()
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        self.kind == Kind::Name && self.value == keyword
+    }
+
+    /// Shows an invisible parenthesis, when the line is split at it.
+    fn ensure_visible(&mut self) {
+        match self.kind {
+            Kind::Open(bracket) | Kind::Close(bracket) if self.value.is_empty() => {
+                self.value = bracket.to_string();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Tracks the brackets of a line as leaves are appended, and the priority of splitting after
+/// each leaf at the outermost depth.
+#[derive(Clone, Debug, Default)]
+struct BracketTracker {
+    depth: usize,
+    /// The ids of the open brackets, by their depth and closing bracket.
+    bracket_match: HashMap<(usize, char), usize>,
+    delimiters: HashMap<usize, u8>,
+    previous: Option<(Kind, Role)>,
+    previous_id: usize,
+    for_loop_depths: Vec<usize>,
+    lambda_argument_depths: Vec<usize>,
+    /// The ids of the invisible parentheses.
+    invisible: Vec<usize>,
+}
+
+impl BracketTracker {
+    fn mark(&mut self, leaf: &mut Leaf) {
+        if leaf.kind == Kind::Comment || (self.depth == 0 && leaf.is_close()) {
+            return;
+        }
+        // `for` and `lambda` open a level of depth closed by `in` and `:`, so that the
+        // commas of their targets and parameters are not delimiters.
+        if self.for_loop_depths.last() == Some(&self.depth) && leaf.is_keyword("in") {
+            self.depth -= 1;
+            self.for_loop_depths.pop();
+        }
+        if self.lambda_argument_depths.last() == Some(&self.depth) && leaf.kind == Kind::Colon {
+            self.depth -= 1;
+            self.lambda_argument_depths.pop();
+        }
+        if let Kind::Close(bracket) = leaf.kind {
+            self.depth -= 1;
+            leaf.opening_bracket = self.bracket_match.remove(&(self.depth, bracket));
+            if leaf.value.is_empty() {
+                self.invisible.push(leaf.id);
+            }
+        }
+        leaf.bracket_depth = self.depth;
+        if self.depth == 0 {
+            let before = self.split_before_priority(leaf);
+            if before > 0 && self.previous.is_some() {
+                self.delimiters.insert(self.previous_id, before);
+            }
+            if leaf.kind == Kind::Comma {
+                self.delimiters.insert(leaf.id, COMMA_PRIORITY);
+            }
+        }
+        if let Kind::Open(bracket) = leaf.kind {
+            self.bracket_match
+                .insert((self.depth, closing_bracket(bracket)), leaf.id);
+            self.depth += 1;
+            if leaf.value.is_empty() {
+                self.invisible.push(leaf.id);
+            }
+        }
+        self.previous = Some((leaf.kind, leaf.role));
+        self.previous_id = leaf.id;
+        if leaf.is_keyword("lambda") {
+            self.depth += 1;
+            self.lambda_argument_depths.push(self.depth);
+        }
+        if leaf.is_keyword("for") {
+            self.depth += 1;
+            self.for_loop_depths.push(self.depth);
+        }
+    }
+
+    /// The priority of splitting before `leaf`, like `is_split_before_delimiter`.
+    fn split_before_priority(&self, leaf: &Leaf) -> u8 {
+        match leaf.role {
+            Role::Operator(priority) => priority,
+            Role::Power { .. } => math_priority("**"),
+            // Method calls chained on the result of a call or subscript split before their
+            // dots, like fluent interfaces.
+            Role::Dot if matches!(self.previous, Some((Kind::Close(_), _))) => DOT_PRIORITY,
+            _ if leaf.kind == Kind::String && matches!(self.previous, Some((Kind::String, _))) => {
+                STRING_PRIORITY
+            }
+            _ => 0,
+        }
+    }
+
+    fn any_open_brackets(&self) -> bool {
+        !self.bracket_match.is_empty()
+    }
+
+    /// Whether a `for` or `lambda` awaits its `in` or `:`.
+    fn any_open_for_or_lambda(&self) -> bool {
+        !self.for_loop_depths.is_empty() || !self.lambda_argument_depths.is_empty()
+    }
+
+    /// The highest priority of the delimiters, except after the leaves in `exclude`.
+    fn max_delimiter_priority(&self, exclude: &[usize]) -> u8 {
+        self.delimiters
+            .iter()
+            .filter(|(id, _)| !exclude.contains(id))
+            .map(|(_, priority)| *priority)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn delimiter_count_with_priority(&self, priority: u8) -> usize {
+        self.delimiters
+            .values()
+            .filter(|delimiter| **delimiter == priority)
+            .count()
+    }
+}
+
+fn closing_bracket(opening: char) -> char {
+    match opening {
+        '(' => ')',
+        '[' => ']',
+        _ => '}',
+    }
+}
+
+/// The width of `text` in a terminal, with wide East Asian characters counting twice and
+/// combining characters not at all, like Black's `str_width`.
+fn str_width(text: &str) -> usize {
+    if text.is_ascii() {
+        return text.len();
+    }
+    text.chars()
+        .map(|c| match c as u32 {
+            0x0300..=0x036F | 0x200B..=0x200F => 0,
+            0x1100..=0x115F
+            | 0x2E80..=0x303E
+            | 0x3041..=0x33FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xA000..=0xA4CF
+            | 0xAC00..=0xD7A3
+            | 0xF900..=0xFAFF
+            | 0xFE30..=0xFE4F
+            | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6
+            | 0x1F300..=0x1F64F
+            | 0x1F900..=0x1F9FF
+            | 0x20000..=0x2FFFD
+            | 0x30000..=0x3FFFD => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+/// A line of leaves at an indentation depth, either a logical line of the source or a part
+/// of one split off.
+#[derive(Clone, Debug, Default)]
+struct Line {
+    depth: usize,
+    leaves: Vec<Leaf>,
+    /// Comments after code, each with the id of the leaf it follows.
+    comments: Vec<(usize, Leaf)>,
+    tracker: BracketTracker,
+    inside_brackets: bool,
+    /// Whether the body of brackets split off should be split at its delimiters too.
+    should_split_rhs: bool,
+    /// The id of the closing bracket after a trailing comma that explodes its contents.
+    magic_trailing_comma: Option<usize>,
+    /// Blank lines before the logical line in the source.
+    before: usize,
+    /// Written as in the source, with the whitespace of each leaf kept: lines continued with
+    /// backslashes outside brackets, `# fmt: off` regions and statements ending with
+    /// `# fmt: skip`.
+    verbatim: bool,
+    /// Whether trailing commas are removed where they fit, rather than exploding brackets.
+    skip_magic_trailing_comma: bool,
+    /// The brackets the line is the body of, once split off.
+    bracket: Option<Bracket>,
+}
+
+impl Line {
+    /// An empty line to split `self` into.
+    fn split_off(&self, depth: usize, inside_brackets: bool) -> Self {
+        Line {
+            depth,
+            inside_brackets,
+            skip_magic_trailing_comma: self.skip_magic_trailing_comma,
+            bracket: self.bracket,
+            ..Line::default()
+        }
+    }
+
+    /// Appends `leaf`, with the whitespace before it unless `preformatted`. Brackets are
+    /// tracked for lines split off within brackets, or when `track_brackets`.
+    fn append(&mut self, mut leaf: Leaf, preformatted: bool, track_brackets: bool) {
+        if self.leaves.is_empty() && leaf.kind != Kind::Comment {
+            leaf.prefix.clear();
+        } else if !preformatted {
+            leaf.prefix = match self.leaves.last() {
+                Some(previous) => whitespace(previous, &leaf).to_string(),
+                None => "  ".to_string(),
+            };
+        }
+        if self.inside_brackets || !preformatted || track_brackets {
+            self.tracker.mark(&mut leaf);
+            if self.has_magic_trailing_comma(&leaf) {
+                if self.skip_magic_trailing_comma {
+                    self.remove_trailing_comma();
+                } else {
+                    self.magic_trailing_comma = Some(leaf.id);
+                }
+            }
+        }
+        if let Some(leaf) = self.append_comment(leaf) {
+            self.leaves.push(leaf);
+        }
+    }
+
+    /// Like `append`, but refuses to put code after a standalone comment, or a standalone
+    /// comment after code, outside brackets.
+    fn append_safe(&mut self, leaf: Leaf, preformatted: bool) -> Result<(), Leaf> {
+        if (self.tracker.depth == 0 || self.tracker.any_open_for_or_lambda())
+            && (self.is_comment()
+                || (!self.leaves.is_empty() && leaf.kind == Kind::StandaloneComment))
+        {
+            return Err(leaf);
+        }
+        self.append(leaf, preformatted, false);
+        Ok(())
+    }
+
+    /// Files a comment after the leaf it follows, returning any other leaf.
+    fn append_comment(&mut self, mut comment: Leaf) -> Option<Leaf> {
+        if comment.kind == Kind::StandaloneComment && self.tracker.any_open_brackets() {
+            // Verbatim lines keep the line break before it.
+            if !self.verbatim {
+                comment.prefix.clear();
+            }
+            return Some(comment);
+        }
+        if comment.kind != Kind::Comment {
+            return Some(comment);
+        }
+        let Some(mut last) = self.leaves.len().checked_sub(1) else {
+            comment.kind = Kind::StandaloneComment;
+            comment.prefix.clear();
+            return Some(comment);
+        };
+        // A comment after invisible parentheses around a single leaf follows the leaf, so
+        // that it stays with it when the parentheses are split. Type comments stay put.
+        if self.leaves[last].kind == Kind::Close(')')
+            && self.leaves[last].value.is_empty()
+            && last >= 2
+            && self.leaves[last].opening_bracket == Some(self.leaves[last - 2].id)
+            && !comment.value.starts_with("# type:")
+        {
+            last -= 1;
+        }
+        self.comments.push((self.leaves[last].id, comment));
+        None
+    }
+
+    fn remove_trailing_comma(&mut self) {
+        let Some(comma) = self.leaves.pop() else {
+            return;
+        };
+        if let Some(last) = self.leaves.last() {
+            for (id, _) in &mut self.comments {
+                if *id == comma.id {
+                    *id = last.id;
+                }
+            }
+        }
+    }
+
+    fn comments_after(&self, leaf: &Leaf) -> Vec<Leaf> {
+        self.comments
+            .iter()
+            .filter(|(id, _)| *id == leaf.id)
+            .map(|(_, comment)| comment.clone())
+            .collect()
+    }
+
+    /// Whether `closing` ends brackets with a trailing comma that keeps them exploded.
+    fn has_magic_trailing_comma(&self, closing: &Leaf) -> bool {
+        let Role::Close(bracket) = closing.role else {
+            return false;
+        };
+        if !matches!(self.leaves.last(), Some(leaf) if leaf.kind == Kind::Comma) {
+            return false;
+        }
+        let Some(opening) = closing.opening_bracket else {
+            return false;
+        };
+        match closing.kind {
+            Kind::Close(']') => {
+                bracket != Bracket::Subscript || !self.is_one_sequence_between(opening, closing)
+            }
+            Kind::Close(')') => self.is_import() || !self.is_one_sequence_between(opening, closing),
+            _ => true,
+        }
+    }
+
+    /// Whether the brackets from `opening` to `closing` hold at most one comma at their
+    /// outermost depth, as in a one-tuple. Commas of arguments count twice.
+    fn is_one_sequence_between(&self, opening: usize, closing: &Leaf) -> bool {
+        let depth = closing.bracket_depth + 1;
+        let Some(start) = self.leaves.iter().position(|leaf| leaf.id == opening) else {
+            return false;
+        };
+        let mut commas = 0;
+        for leaf in &self.leaves[start + 1..] {
+            if leaf.id == closing.id {
+                break;
+            }
+            if leaf.bracket_depth == depth && leaf.kind == Kind::Comma {
+                commas += 1;
+                if let Role::Comma { arglist: true } = leaf.role {
+                    commas += 1;
+                    break;
+                }
+            }
+        }
+        commas < 2
+    }
+
+    fn first_keyword(&self) -> &str {
+        match self.leaves.first() {
+            Some(leaf) if leaf.kind == Kind::Name => &leaf.value,
+            _ => "",
+        }
+    }
+
+    fn is_comment(&self) -> bool {
+        self.leaves.len() == 1 && self.leaves[0].kind == Kind::StandaloneComment
+    }
+
+    fn is_decorator(&self) -> bool {
+        matches!(self.leaves.first(), Some(leaf) if leaf.role == Role::Prefix && leaf.value == "@")
+    }
+
+    fn is_import(&self) -> bool {
+        matches!(self.first_keyword(), "import" | "from")
+    }
+
+    fn is_class(&self) -> bool {
+        self.first_keyword() == "class"
+    }
+
+    fn is_def(&self) -> bool {
+        match self.first_keyword() {
+            "def" => true,
+            "async" => matches!(self.leaves.get(1), Some(leaf) if leaf.is_keyword("def")),
+            _ => false,
+        }
+    }
+
+    /// Whether the line is a definition whose body is `...` on the same line.
+    fn is_stub_def(&self) -> bool {
+        let n = self.leaves.len();
+        self.is_def()
+            && n >= 2
+            && self.leaves[n - 2].kind == Kind::Colon
+            && self.leaves[n - 1].value == "..."
+    }
+
+    fn is_triple_quoted_string(&self) -> bool {
+        matches!(self.leaves.first(), Some(leaf) if leaf.kind == Kind::String
+            && (leaf.value.starts_with("\"\"\"") || leaf.value.starts_with("'''")))
+    }
+
+    fn opens_block(&self) -> bool {
+        matches!(self.leaves.last(), Some(leaf) if leaf.kind == Kind::Colon)
+    }
+
+    fn contains_standalone_comments(&self) -> bool {
+        self.leaves
+            .iter()
+            .any(|leaf| leaf.kind == Kind::StandaloneComment)
+    }
+
+    /// Whether a `# type: ignore` comment ends a line that was a single line in the source,
+    /// where splitting would move it off the code it silences.
+    fn contains_unsplittable_type_ignore(&self) -> bool {
+        let mut lines = self
+            .leaves
+            .iter()
+            .map(|leaf| leaf.lineno)
+            .filter(|&lineno| lineno != 0);
+        let first = lines.next().unwrap_or(0);
+        let last = lines.next_back().unwrap_or(first);
+        first == last
+            && self.leaves.iter().rev().take(2).any(|leaf| {
+                self.comments.iter().any(|(id, comment)| {
+                    *id == leaf.id && comment.value.starts_with("# type: ignore")
+                })
+            })
+    }
+
+    fn contains_multiline_strings(&self) -> bool {
+        self.leaves
+            .iter()
+            .any(|leaf| leaf.kind == Kind::String && leaf.value.contains('\n'))
+    }
+
+    /// The line as written, without its indentation or trailing newline.
+    fn content(&self) -> String {
+        let mut content = String::new();
+        for (i, leaf) in self.leaves.iter().enumerate() {
+            if i > 0 {
+                content.push_str(&leaf.prefix);
+            }
+            content.push_str(&leaf.value);
+        }
+        for (_, comment) in &self.comments {
+            content.push_str(&comment.prefix);
+            content.push_str(&comment.value);
+        }
+        content
+    }
+
+    fn render(&self) -> String {
+        if self.leaves.is_empty() {
+            return "\n".to_string();
+        }
+        format!("{}{}\n", "    ".repeat(self.depth), self.content())
+    }
+
+    /// Whether the line fits in the line length as it is, with no comment to split at.
+    fn is_short_enough(&self, line_length: usize) -> bool {
+        let rendered = self.render();
+        let rendered = rendered.trim_end_matches('\n');
+        !rendered.contains('\n')
+            && str_width(rendered) <= line_length
+            && !self.contains_standalone_comments()
+    }
+}
+
+/// The whitespace between `previous` and `leaf` on a line, like Black's `whitespace`.
+fn whitespace(previous: &Leaf, leaf: &Leaf) -> &'static str {
+    const NO: &str = "";
+    const SPACE: &str = " ";
+    match leaf.kind {
+        Kind::Comment => return "  ",
+        Kind::StandaloneComment | Kind::Comma | Kind::Close(_) => return NO,
+        Kind::Colon if !matches!(leaf.role, Role::SliceColon { .. }) => return NO,
+        _ => {}
+    }
+    match leaf.role {
+        Role::Open(Bracket::Call | Bracket::Subscript)
+        | Role::Dot
+        | Role::ExceptStar
+        | Role::KeywordEqual
+        | Role::Power { hug: true } => return NO,
+        Role::ImportDot => {
+            return match previous.is_keyword("from") {
+                true => SPACE,
+                false => NO,
+            }
+        }
+        Role::SliceColon { complex } => {
+            return match previous.role {
+                _ if previous.is_open() => NO,
+                Role::SliceColon { .. } => NO,
+                _ if previous.kind == Kind::Comma => SPACE,
+                _ if complex => SPACE,
+                _ => NO,
+            }
+        }
+        _ => {}
+    }
+    match previous.role {
+        _ if previous.is_open() => NO,
+        Role::Unary | Role::Prefix | Role::Dot | Role::KeywordEqual | Role::Power { hug: true } => {
+            NO
+        }
+        Role::ImportDot if !leaf.is_keyword("import") => NO,
+        Role::SliceColon { complex } if !complex => NO,
+        _ => SPACE,
+    }
+}
+
+/// Lowercases the prefix of a string, removing `u`, like `normalize_string_prefix`.
+fn normalize_string_prefix(value: &str) -> String {
+    let quote = value.find(['\'', '"']).unwrap_or(0);
+    let prefix: String = value[..quote]
+        .chars()
+        .filter(|c| !matches!(c, 'u' | 'U'))
+        .map(|c| match c {
+            'F' => 'f',
+            'B' => 'b',
+            _ => c,
+        })
+        .collect();
+    format!("{}{}", prefix, &value[quote..])
+}
+
+/// The number of backslashes right before byte `end` of `text`.
+fn backslashes_before(text: &str, end: usize) -> usize {
+    text[..end]
+        .bytes()
+        .rev()
+        .take_while(|b| *b == b'\\')
+        .count()
+}
+
+/// Removes the backslash escaping each `quote` in `body` that needs none.
+fn unescape_quotes(body: &str, quote: &str) -> String {
+    let mut result = String::with_capacity(body.len());
+    let mut start = 0;
+    let mut i = 0;
+    while i < body.len() {
+        if body[i..].starts_with(quote) {
+            if backslashes_before(body, i) % 2 == 1 {
+                result.push_str(&body[start..i - 1]);
+                start = i;
+            }
+            i += quote.len();
+        } else {
+            i += body[i..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    result.push_str(&body[start..]);
+    result
+}
+
+/// Escapes each unescaped `quote` in `body`.
+fn escape_quotes(body: &str, quote: &str) -> String {
+    let mut result = String::with_capacity(body.len());
+    let mut start = 0;
+    let mut i = 0;
+    while i < body.len() {
+        if body[i..].starts_with(quote) {
+            if backslashes_before(body, i).is_multiple_of(2) {
+                result.push_str(&body[start..i]);
+                result.push('\\');
+                start = i;
+            }
+            i += quote.len();
+        } else {
+            i += body[i..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    result.push_str(&body[start..]);
+    result
+}
+
+/// Whether a replacement field of an f-string body holds a backslash.
+fn has_backslash_in_replacement_field(body: &str) -> bool {
+    let bytes = body.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'{' {
+            if bytes.get(i + 1) == Some(&b'{') {
+                i += 2;
+                continue;
+            }
+            let mut j = i + 1;
+            while j < bytes.len() && !(bytes[j] == b'}' && bytes.get(j + 1) != Some(&b'}')) {
+                j += 1;
+            }
+            if body[i + 1..j.min(body.len())].contains('\\') {
+                return true;
+            }
+            i = j;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Prefers double quotes, unless they need more escapes than the quotes written, like
+/// `normalize_string_quotes`.
+fn normalize_string_quotes(value: &str) -> String {
+    let quote = value.find(['\'', '"']).unwrap_or(0);
+    let (prefix, rest) = value.split_at(quote);
+    let (orig_quote, new_quote) = if rest.starts_with("\"\"\"") {
+        return value.to_string();
+    } else if rest.starts_with("'''") {
+        ("'''", "\"\"\"")
+    } else if rest.starts_with('"') {
+        ("\"", "'")
+    } else {
+        ("'", "\"")
+    };
+    let mut body = &rest[orig_quote.len()..rest.len() - orig_quote.len()];
+    let mut value = value.to_string();
+    let unescaped;
+    let new_body = if prefix.to_ascii_lowercase().contains('r') {
+        let unescaped_quote = body
+            .match_indices(new_quote)
+            .any(|(i, _)| backslashes_before(body, i).is_multiple_of(2));
+        if unescaped_quote {
+            return value;
+        }
+        body.to_string()
+    } else {
+        unescaped = unescape_quotes(body, new_quote);
+        if unescaped != body {
+            body = &unescaped;
+            value = format!("{}{}{}{}", prefix, orig_quote, body, orig_quote);
+        }
+        escape_quotes(&unescape_quotes(body, orig_quote), new_quote)
+    };
+    if prefix.to_ascii_lowercase().contains('f') && has_backslash_in_replacement_field(&new_body) {
+        return value;
+    }
+    let mut new_body = new_body;
+    if new_quote == "\"\"\"" && new_body.ends_with('"') {
+        new_body.pop();
+        new_body.push_str("\\\"");
+    }
+    let orig_escapes = body.matches('\\').count();
+    let new_escapes = new_body.matches('\\').count();
+    if new_escapes > orig_escapes || (new_escapes == orig_escapes && orig_quote == "\"") {
+        return value;
+    }
+    format!("{}{}{}{}", prefix, new_quote, new_body, new_quote)
+}
+
+/// Lowercases the hex digits of `\x`, `\u` and `\U` escapes and uppercases the names of
+/// `\N{...}` escapes, outside raw strings.
+fn normalize_unicode_escapes(value: &str) -> String {
+    let quote = value.find(['\'', '"']).unwrap_or(0);
+    let prefix = value[..quote].to_ascii_lowercase();
+    if prefix.contains('r') {
+        return value.to_string();
+    }
+    let bytes_literal = prefix.contains('b');
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.char_indices().peekable();
+    let mut backslashes = 0;
+    while let Some((i, c)) = chars.next() {
+        result.push(c);
+        if c != '\\' {
+            backslashes = 0;
+            continue;
+        }
+        backslashes += 1;
+        if backslashes % 2 == 0 {
+            continue;
+        }
+        let rest = &value[i + 1..];
+        let hex_digits = match rest.chars().next() {
+            Some('x') => 2,
+            Some('u') if !bytes_literal => 4,
+            Some('U') if !bytes_literal => 8,
+            Some('N') if !bytes_literal && rest[1..].starts_with('{') => {
+                if let Some(end) = rest.find('}') {
+                    result.push_str(&rest[..2]);
+                    result.push_str(&rest[2..end].to_ascii_uppercase());
+                    for _ in 0..rest[..end].chars().count() {
+                        chars.next();
+                    }
+                    backslashes = 0;
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        let escape = &rest[1..];
+        if escape.len() >= hex_digits
+            && escape.as_bytes()[..hex_digits]
+                .iter()
+                .all(u8::is_ascii_hexdigit)
+        {
+            result.push_str(&rest[..1]);
+            result.push_str(&escape[..hex_digits].to_ascii_lowercase());
+            for _ in 0..=hex_digits {
+                chars.next();
+            }
+            backslashes = 0;
+        }
+    }
+    result
+}
+
+/// Normalizes the case and redundant digits of a number, like `normalize_numeric_literal`.
+fn normalize_number(value: &str) -> String {
+    fn float_or_int(text: &str) -> String {
+        match text.split_once('.') {
+            Some((before, after)) => format!(
+                "{}.{}",
+                if before.is_empty() { "0" } else { before },
+                if after.is_empty() { "0" } else { after }
+            ),
+            None => text.to_string(),
+        }
+    }
+    let text = value.to_ascii_lowercase();
+    if text.starts_with("0b") || text.starts_with("0o") {
+        text
+    } else if let Some(digits) = text.strip_prefix("0x") {
+        format!("0x{}", digits.to_ascii_uppercase())
+    } else if let Some((before, after)) = text.split_once('e') {
+        let after = after.strip_prefix('+').unwrap_or(after);
+        format!("{}e{}", float_or_int(before), after)
+    } else if let Some(number) = text.strip_suffix('j') {
+        format!("{}j", float_or_int(number))
+    } else {
+        float_or_int(&text)
+    }
+}
+
+/// Normalizes a comment, like Black's `make_comment`: a space after the `#` unless it starts
+/// a shebang, a type comment or another comment of its own kind.
+fn normalize_comment(text: &str) -> String {
+    let content = text.trim_end();
+    let content = content.strip_prefix('#').unwrap_or(content);
+    let content = match content.strip_prefix('\u{a0}') {
+        Some(rest) if !rest.trim_start().starts_with("type:") => format!(" {}", rest),
+        _ => content.to_string(),
+    };
+    match content.chars().next() {
+        Some(c) if !" !:#'".contains(c) => format!("# {}", content),
+        _ => format!("#{}", content),
+    }
+}
+
+/// Formats a docstring indented at `depth`, like Black's `visit_default` for docstrings.
+fn normalize_docstring(value: &str, depth: usize, options: &FormatOptions) -> String {
+    let value = match options.string_normalization {
+        true => normalize_string_quotes(&normalize_string_prefix(value)),
+        false => value.to_string(),
+    };
+    let quote = value.find(['\'', '"']).unwrap_or(0);
+    let (prefix, rest) = value.split_at(quote);
+    let quote_char = &rest[..1];
+    let quote_len = if rest.len() >= 6 && rest.starts_with(&quote_char.repeat(3)) {
+        3
+    } else {
+        1
+    };
+    let original = &rest[quote_len..rest.len() - quote_len];
+    let indent = "    ".repeat(depth);
+    let mut docstring = match original.contains('\n') {
+        true => fix_docstring(original, &indent),
+        false => original.trim().to_string(),
+    };
+    if !docstring.is_empty() {
+        // Pad the docstring so that its quotes do not run into those around it.
+        if docstring.starts_with(quote_char) {
+            docstring.insert(0, ' ');
+        }
+        if docstring.ends_with(quote_char)
+            || backslashes_before(&docstring, docstring.len()) % 2 == 1
+        {
+            docstring.push(' ');
+        }
+    } else if !original.is_empty() {
+        docstring.push(' ');
+    }
+    let quote = quote_char.repeat(quote_len);
+    let last_line = docstring.rsplit('\n').next().unwrap_or("");
+    if quote_len == 3
+        && docstring.contains('\n')
+        && str_width(last_line) + quote_len > options.line_length
+    {
+        format!("{}{}{}\n{}{}", prefix, quote, docstring, indent, quote)
+    } else {
+        format!("{}{}{}{}", prefix, quote, docstring, quote)
+    }
+}
+
+/// Strips the first line of a docstring and reindents the others to `indent`, like the
+/// `trim` of PEP 257.
+fn fix_docstring(docstring: &str, indent: &str) -> String {
+    let lines: Vec<String> = docstring.split('\n').map(expand_leading_tabs).collect();
+    let mut trimmed = vec![lines[0].trim().to_string()];
+    let common = lines[1..]
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min();
+    if let Some(common) = common {
+        for (i, line) in lines.iter().enumerate().skip(1) {
+            let line = line.get(common..).unwrap_or("").trim_end();
+            trimmed.push(match line.is_empty() && i < lines.len() - 1 {
+                true => String::new(),
+                false => format!("{}{}", indent, line),
+            });
+        }
+    }
+    trimmed.join("\n")
+}
+
+/// Expands the tabs in the indentation of a line to multiples of eight columns.
+fn expand_leading_tabs(line: &str) -> String {
+    let content = line.trim_start_matches([' ', '\t']);
+    let mut expanded = String::new();
+    for c in line[..line.len() - content.len()].chars() {
+        match c {
+            '\t' => expanded.push_str(&" ".repeat(8 - expanded.len() % 8)),
+            _ => expanded.push(c),
+        }
+    }
+    expanded.push_str(content);
+    expanded
+}
+
+/// How the parentheses around a node are written, like Black's `normalize_invisible_parens`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Parens {
+    /// As in the source.
+    Keep,
+    /// Made invisible, or invisible ones added if there are none, so that the line can still
+    /// be split there.
+    Invisible,
+    /// Added around the node.
+    Visible,
+    /// Made invisible, without adding any.
+    Hidden,
+    /// The parentheses of a tuple made invisible too.
+    HiddenTuple,
+}
+
+/// Tokens that do not take part in the layout of a line.
+/// The depth of the statement `child` in the body of `owner`, a statement at `depth` or
+/// the module.
+fn statement_depth(owner: &CstNode, child: &CstNode, depth: usize) -> usize {
+    match owner.name {
+        _ if owner.node_type == NodeType::Mod => depth,
+        "If" if matches!(first_token(child), Some(token) if token.token_type == TokenType::Elif) => {
+            depth
+        }
+        "Match" => depth + 2,
+        _ => depth + 1,
+    }
+}
+
+const FMT_OFF: [&str; 3] = ["# fmt: off", "# fmt:off", "# yapf: disable"];
+const FMT_ON: [&str; 3] = ["# fmt: on", "# fmt:on", "# yapf: enable"];
+const FMT_SKIP: [&str; 2] = ["# fmt: skip", "# fmt:skip"];
+
+/// The source text of `element`.
+fn element_code(element: &CstElement) -> String {
+    match element {
+        CstElement::Node(node) => node.code(),
+        CstElement::Token(token) => token.text.clone(),
+        CstElement::Whitespace(whitespace) => whitespace.clone(),
+    }
+}
+
+fn is_trivia(token_type: &TokenType) -> bool {
+    matches!(token_type, TokenType::Comment | TokenType::NL)
+}
+
+/// The indices of the opening parentheses starting `node` and of the closing ones ending it,
+/// the outermost first: those wrapping it, then any of its own, as a tuple's.
+fn edge_parens(node: &CstNode) -> (Vec<usize>, Vec<usize>) {
+    fn scan<'a>(
+        elements: impl Iterator<Item = (usize, &'a CstElement)>,
+        paren: TokenType,
+    ) -> Vec<usize> {
+        let mut found = vec![];
+        for (i, element) in elements {
+            match element {
+                CstElement::Whitespace(_) => {}
+                CstElement::Token(token) if is_trivia(&token.token_type) => {}
+                CstElement::Token(token) if token.token_type == paren => found.push(i),
+                _ => break,
+            }
+        }
+        found
+    }
+    let children = node.children.iter().enumerate();
+    (
+        scan(children.clone(), TokenType::LPar),
+        scan(children.rev(), TokenType::RPar),
+    )
+}
+
+/// Whether a tuple, generator or sequence pattern is written within parentheses of its own.
+fn owns_parens(node: &CstNode) -> bool {
+    matches!(node.name, "Tuple" | "GeneratorExp" | "MatchSequence")
+        && edge_parens(node).0.len() > node.parens
+}
+
+/// The first token of `node` itself, unless a child node comes before it.
+fn first_token(node: &CstNode) -> Option<&CstToken> {
+    for element in &node.children {
+        match element {
+            CstElement::Whitespace(_) => {}
+            CstElement::Token(token) if is_trivia(&token.token_type) => {}
+            CstElement::Token(token) => return Some(token),
+            CstElement::Node(_) => return None,
+        }
+    }
+    None
+}
+
+/// The operator token of a binary or unary operation.
+fn operator(node: &CstNode) -> Option<&TokenType> {
+    let (lpars, rpars) = edge_parens(node);
+    node.children
+        .iter()
+        .enumerate()
+        .find_map(|(i, element)| match element {
+            CstElement::Token(token)
+                if !is_trivia(&token.token_type)
+                    && !lpars[..node.parens].contains(&i)
+                    && !rpars[..node.parens].contains(&i) =>
+            {
+                Some(&token.token_type)
+            }
+            _ => None,
+        })
+}
+
+/// The number of strings implicitly concatenated in a string or f-string node.
+fn string_parts(node: &CstNode) -> usize {
+    let starts = node
+        .tokens()
+        .filter(|token| {
+            matches!(
+                token.token_type,
+                TokenType::String | TokenType::FStringStart
+            )
+        })
+        .count();
+    let strings = node
+        .nodes()
+        .filter(|child| {
+            child.name == "Constant"
+                && matches!(first_token(child), Some(token) if token.token_type == TokenType::String)
+        })
+        .count();
+    match node.name {
+        "Constant" | "JoinedStr" => starts + strings,
+        _ => 0,
+    }
+}
+
+/// Whether `node` is a single string on more than one line, which keeps its place after
+/// keywords and `=`.
+fn is_multiline_string(node: &CstNode) -> bool {
+    node.parens == 0 && string_parts(node) == 1 && node.code().contains('\n')
+}
+
+/// Whether the operands of the slice need spaces around its colons, like Black's
+/// `is_complex_subscript`.
+fn is_complex_slice(node: &CstNode) -> bool {
+    node.descendants()
+        .iter()
+        .skip(1)
+        .any(|child| match child.name {
+            "BinOp" | "BoolOp" | "Compare" | "Lambda" | "IfExp" | "Starred" | "NamedExpr"
+            | "Attribute" | "Call" | "Subscript" | "Await" => true,
+            "UnaryOp" => operator(child) == Some(&TokenType::Not),
+            _ => false,
+        })
+}
+
+/// The parentheses of a node where Black normalizes them: redundant ones made invisible,
+/// and invisible ones added where there are none.
+fn normalize_parens(child: &CstNode, keep_walrus: bool, remove_tuple: bool) -> Parens {
+    if child.parens > 0 {
+        return match child.name {
+            "Yield" | "YieldFrom" => Parens::Keep,
+            "NamedExpr" if keep_walrus => Parens::Keep,
+            _ => Parens::Hidden,
+        };
+    }
+    if owns_parens(child) {
+        let elements: Vec<&CstNode> = child.nodes().collect();
+        let removable = remove_tuple
+            && child.name == "Tuple"
+            && elements.len() > 1
+            && elements
+                .iter()
+                .all(|element| !matches!(element.name, "Starred" | "NamedExpr"));
+        return match removable {
+            true => Parens::HiddenTuple,
+            false => Parens::Keep,
+        };
+    }
+    let bracketed =
+        matches!(first_token(child), Some(token) if token.token_type == TokenType::LSqB);
+    if matches!(child.name, "Tuple" | "MatchSequence") && child.nodes().count() == 1 && !bracketed {
+        return Parens::Visible;
+    }
+    if is_multiline_string(child) {
+        return Parens::Keep;
+    }
+    Parens::Invisible
+}
+
+/// Whether an annotation is a collection, a union or several strings, which a definition
+/// may split within invisible parentheses.
+fn is_atom_like(node: &CstNode) -> bool {
+    match node.name {
+        "List" | "ListComp" | "Dict" | "DictComp" | "Set" | "SetComp" => true,
+        "BinOp" => operator(node) == Some(&TokenType::VBar),
+        "Constant" | "JoinedStr" => string_parts(node) > 1,
+        _ => false,
+    }
+}
+
+/// Whether `node` is a docstring: a plain string alone in the first statement of a module or
+/// an indented block.
+fn is_docstring(node: &CstNode) -> bool {
+    let mut nodes = node.nodes();
+    let (Some(constant), None) = (nodes.next(), nodes.next()) else {
+        return false;
+    };
+    let mut tokens = constant.tokens();
+    let (Some(string), None) = (tokens.next(), tokens.next()) else {
+        return false;
+    };
+    let prefix = &string.text[..string.text.find(['\'', '"']).unwrap_or(0)];
+    node.name == "Expr"
+        && constant.parens == 0
+        && constant.nodes().next().is_none()
+        && string.token_type == TokenType::String
+        && !prefix.contains(['b', 'B', 'f', 'F'])
+        && !string.text.split('\\').skip(1).any(|rest| {
+            rest.trim_start_matches([' ', '\t', '\x0c'])
+                .starts_with('\n')
+        })
+}
+
+/// The kind of leaf a token becomes.
+fn token_kind(token_type: &TokenType) -> Kind {
+    use TokenType::*;
+    match token_type {
+        Number => Kind::Number,
+        String => Kind::String,
+        LPar => Kind::Open('('),
+        LSqB => Kind::Open('['),
+        LBrace => Kind::Open('{'),
+        RPar => Kind::Close(')'),
+        RSqB => Kind::Close(']'),
+        RBrace => Kind::Close('}'),
+        Comma => Kind::Comma,
+        Colon => Kind::Colon,
+        Name | SoftKeyword | Ellipsis | And | As | Assert | Break | Class | Continue | Def
+        | Del | Elif | Else | Except | False | Finally | For | From | Global | If | Import | In
+        | Is | Lambda | None | Nonlocal | Not | Or | Pass | Raise | Return | True | Try | While
+        | With | Yield | Await | Async => Kind::Name,
+        _ => Kind::Operator,
+    }
+}
+
+/// The role of a token owned by `owner`, after the token `previous` of the same owner. The
+/// token is at `index` among the children of `owner`, and `call` tells whether the
+/// parentheses of a generator are those of the call it is the only argument of.
+fn token_role(
+    owner: &CstNode,
+    index: usize,
+    token: &CstToken,
+    previous: Option<&TokenType>,
+    call: bool,
+) -> Role {
+    use TokenType::*;
+    let comprehension = matches!(
+        owner.name,
+        "ListComp" | "SetComp" | "DictComp" | "GeneratorExp"
+    );
+    let bracket = match owner.name {
+        "Call" | "FunctionDef" | "AsyncFunctionDef" | "ClassDef" | "TypeAlias" | "MatchClass" => {
+            Bracket::Call
+        }
+        "GeneratorExp" if call => Bracket::Call,
+        "Subscript" => Bracket::Subscript,
+        "ImportFrom" => Bracket::Import,
+        _ => Bracket::Atom,
+    };
+    match &token.token_type {
+        LPar | LSqB | LBrace => Role::Open(bracket),
+        RPar | RSqB | RBrace => Role::Close(bracket),
+        Comma => Role::Comma {
+            arglist: matches!(
+                owner.name,
+                "Call" | "FunctionDef" | "AsyncFunctionDef" | "ClassDef"
+            ),
+        },
+        Colon if owner.name == "Slice" => Role::SliceColon {
+            complex: is_complex_slice(owner),
+        },
+        Colon => Role::Colon,
+        Dot | Ellipsis if owner.name == "ImportFrom" => Role::ImportDot,
+        Dot => Role::Dot,
+        DoubleStar if owner.name == "BinOp" => Role::Power { hug: false },
+        Star if owner.name == "ExceptHandler" => Role::ExceptStar,
+        Star | DoubleStar | At if owner.name != "BinOp" => Role::Prefix,
+        Minus | Plus | Tilde if owner.name == "UnaryOp" => Role::Unary,
+        // The marker of positional-only parameters.
+        Slash if owner.name != "BinOp" => Role::Atom,
+        Star | At | Minus | Plus | VBar | Circumflex | Amper | LeftShift | RightShift | Slash
+        | DoubleSlash | Percent => Role::Operator(math_priority(&token.text)),
+        Less | Greater | EqEqual | NotEqual | LessEqual | GreaterEqual | Is => {
+            Role::Operator(COMPARATOR_PRIORITY)
+        }
+        In | Not if owner.name == "Compare" => match (&token.token_type, previous) {
+            (In, Some(Not)) | (Not, Some(Is)) => Role::Operator(0),
+            _ => Role::Operator(COMPARATOR_PRIORITY),
+        },
+        And | Or => Role::Operator(LOGIC_PRIORITY),
+        If | Else if owner.name == "IfExp" => Role::Operator(TERNARY_PRIORITY),
+        For if comprehension && previous == Some(&Async) => Role::Operator(0),
+        If | For | Async if comprehension => Role::Operator(COMPREHENSION_PRIORITY),
+        Equal => match owner.name {
+            "keyword" | "MatchClass" | "Lambda" => Role::KeywordEqual,
+            "FunctionDef" | "AsyncFunctionDef" => {
+                // A default after an annotation is spaced like an assignment.
+                let annotated = owner.children[..index]
+                    .iter()
+                    .rev()
+                    .find_map(|element| match element {
+                        CstElement::Node(node) => Some(node),
+                        _ => Option::None,
+                    })
+                    .is_some_and(|arg| arg.tokens().any(|token| token.token_type == Colon));
+                match annotated {
+                    true => Role::Operator(0),
+                    false => Role::KeywordEqual,
+                }
+            }
+            _ => Role::Operator(0),
+        },
+        other if token_kind(other) == Kind::Operator => Role::Operator(0),
+        _ => Role::Atom,
+    }
+}
+
+/// The parentheses at the edges of a node: those wrapping it, and those written invisible.
+struct Edges {
+    wrapping: Vec<usize>,
+    hidden: Vec<usize>,
+}
+
+/// Lays the concrete syntax tree out as logical lines, like Black's `LineGenerator`: one
+/// per simple statement, clause header and decorator, and one per comment on a line of its
+/// own, with strings, numbers and parentheses normalized on the way.
+struct LineBuilder<'a> {
+    options: &'a FormatOptions,
+    lines: Vec<Line>,
+    current: Line,
+    next_id: usize,
+    /// Comments on lines of their own outside brackets, with the blank lines before each and
+    /// its column, until the depth of the line after them is known.
+    comments: Vec<(String, usize, usize)>,
+    /// The column of the last line at each depth, which decides the depth of the comments
+    /// before a dedent.
+    columns: Vec<usize>,
+    /// The depth of the last line of code.
+    last_depth: usize,
+    /// The newlines since the last token, one more than the blank lines.
+    newlines: usize,
+    /// The line of the source being read, from 1.
+    lineno: usize,
+    column: usize,
+    /// Whether a token was seen on the current line of the source.
+    code_on_line: bool,
+    /// Whitespace continuing the line with a backslash before the next token.
+    continuation: Option<String>,
+    /// Whether the next string is a docstring.
+    docstring: bool,
+}
+
+impl<'a> LineBuilder<'a> {
+    fn new(options: &'a FormatOptions) -> Self {
+        LineBuilder {
+            options,
+            lines: vec![],
+            current: Line {
+                skip_magic_trailing_comma: !options.magic_trailing_comma,
+                ..Line::default()
+            },
+            next_id: 0,
+            comments: vec![],
+            columns: vec![],
+            last_depth: 0,
+            newlines: 0,
+            lineno: 1,
+            column: 0,
+            code_on_line: false,
+            continuation: None,
+            docstring: false,
+        }
+    }
+
+    fn build(mut self, tree: &CstNode) -> Vec<Line> {
+        self.walk(tree, 0, Parens::Keep, false);
+        self.start_line(0);
+        self.flush_comments(0);
+        self.lines
+    }
+
+    fn leaf(&mut self, kind: Kind, role: Role, value: String) -> Leaf {
+        self.next_id += 1;
+        Leaf {
+            id: self.next_id,
+            kind,
+            role,
+            lineno: if value.is_empty() { 0 } else { self.lineno },
+            value,
+            prefix: String::new(),
+            bracket_depth: 0,
+            opening_bracket: None,
+        }
+    }
+
+    /// Ends the current line and starts one at `depth`.
+    fn start_line(&mut self, depth: usize) {
+        if !self.current.leaves.is_empty() {
+            let mut line = std::mem::take(&mut self.current);
+            hug_power_operators(&mut line);
+            self.last_depth = line.depth;
+            self.current.skip_magic_trailing_comma = line.skip_magic_trailing_comma;
+            self.lines.push(line);
+        }
+        self.current.depth = depth;
+        self.continuation = None;
+    }
+
+    /// Writes out the comments before the current line: those indented like a block just
+    /// closed stay in it, like the tokenizer of `blib2to3` decides.
+    fn flush_comments(&mut self, depth: usize) {
+        let mut level = self.last_depth.max(depth);
+        for (value, before, column) in std::mem::take(&mut self.comments) {
+            while level > depth && column < self.columns.get(level).copied().unwrap_or(0) {
+                level -= 1;
+            }
+            let mut line = Line {
+                depth: level,
+                before,
+                verbatim: value.contains('\n'),
+                ..Line::default()
+            };
+            let leaf = self.leaf(Kind::StandaloneComment, Role::Atom, value);
+            line.append(leaf, true, false);
+            self.lines.push(line);
+        }
+    }
+
+    fn push(&mut self, kind: Kind, role: Role, value: String) {
+        let mut leaf = self.leaf(kind, role, value);
+        if self.current.leaves.is_empty() {
+            let depth = self.current.depth;
+            self.flush_comments(depth);
+            self.current.before = self.newlines.saturating_sub(1);
+            self.columns.resize(depth + 1, 0);
+            self.columns[depth] = self.column;
+            self.continuation = None;
+        }
+        let after_comment = self.current.verbatim
+            && (self.current.leaves.last())
+                .is_some_and(|last| last.kind == Kind::StandaloneComment);
+        match self.continuation.take() {
+            Some(whitespace) if !self.current.tracker.any_open_brackets() => {
+                leaf.prefix = whitespace;
+                self.current.verbatim = true;
+                self.current.append(leaf, true, true);
+            }
+            // Code after a comment in the brackets of a verbatim line stays on its own line.
+            _ if after_comment => {
+                leaf.prefix = format!("\n{}", " ".repeat(self.column));
+                self.current.append(leaf, true, true);
+            }
+            _ => self.current.append(leaf, false, false),
+        }
+        self.newlines = 0;
+        self.code_on_line = true;
+    }
+
+    fn comment(&mut self, text: &str) {
+        let value = normalize_comment(text);
+        if self.code_on_line {
+            let leaf = self.leaf(Kind::Comment, Role::Atom, value);
+            match self.lines.last_mut() {
+                // After a statement ended by a semicolon.
+                Some(line) if self.current.leaves.is_empty() => {
+                    let last = line.leaves.last().map_or(0, |leaf| leaf.id);
+                    line.comments.push((
+                        last,
+                        Leaf {
+                            prefix: "  ".to_string(),
+                            ..leaf
+                        },
+                    ));
+                }
+                _ => self.current.append(leaf, false, false),
+            }
+        } else if self.current.tracker.any_open_brackets() {
+            let mut leaf = self.leaf(Kind::StandaloneComment, Role::Atom, value);
+            match self.current.verbatim {
+                true => {
+                    leaf.prefix = format!("\n{}", " ".repeat(self.column));
+                    self.current.append(leaf, true, true);
+                }
+                false => self.current.append(leaf, false, false),
+            }
+        } else {
+            self.comments
+                .push((value, self.newlines.saturating_sub(1), self.column));
+        }
+        self.newlines = 0;
+    }
+
+    fn string(&mut self, text: &str) {
+        let mut value = normalize_unicode_escapes(text);
+        if std::mem::take(&mut self.docstring) {
+            value = normalize_docstring(&value, self.current.depth, self.options);
+        }
+        if self.options.string_normalization {
+            value = normalize_string_quotes(&normalize_string_prefix(&value));
+        }
+        self.push(Kind::String, Role::Atom, value);
+    }
+
+    fn advance(&mut self, text: &str) {
+        self.lineno += text.matches('\n').count();
+        match text.rfind('\n') {
+            Some(newline) => self.column = text[newline + 1..].chars().count(),
+            None => self.column += text.chars().count(),
+        }
+    }
+
+    /// Lays out `node`, a statement at `depth` or a part of one, with its parentheses
+    /// written as `parens` says.
+    fn walk(&mut self, node: &CstNode, depth: usize, parens: Parens, call: bool) {
+        let (lpars, rpars) = edge_parens(node);
+        let wrapping = node.parens.min(lpars.len()).min(rpars.len());
+        let mut edges = Edges {
+            wrapping: [&lpars[..wrapping], &rpars[..wrapping]].concat(),
+            hidden: vec![],
+        };
+        if matches!(
+            parens,
+            Parens::Invisible | Parens::Hidden | Parens::HiddenTuple
+        ) {
+            edges.hidden = edges.wrapping.clone();
+        }
+        if parens == Parens::HiddenTuple {
+            edges.hidden.extend(lpars.get(wrapping));
+            edges.hidden.extend(rpars.get(wrapping));
+        }
+        let added = match parens {
+            Parens::Invisible if node.parens == 0 => Some(""),
+            Parens::Visible if node.parens == 0 => Some("("),
+            _ => None,
+        };
+        if let Some(value) = added {
+            self.push(
+                Kind::Open('('),
+                Role::Open(Bracket::Atom),
+                value.to_string(),
+            );
+        }
+        let nodes: Vec<usize> = (0..node.children.len())
+            .filter(|i| matches!(node.children[*i], CstElement::Node(_)))
+            .collect();
+        // The targets of `del` and the names imported from a module are wrapped together.
+        let range = match node.name {
+            "Delete" if nodes.len() > 1 => Some(Bracket::Atom),
+            "ImportFrom"
+                if !node
+                    .tokens()
+                    .any(|token| token.token_type == TokenType::LPar)
+                    && !matches!(node.nodes().last(), Some(alias) if alias.code() == "*") =>
+            {
+                Some(Bracket::Import)
+            }
+            _ => None,
+        };
+        let (first_node, last_node) = (nodes.first().copied(), nodes.last().copied());
+        let mut previous: Option<&TokenType> = None;
+        let mut statements = 0;
+        let mut skip_until = 0;
+        for (i, element) in node.children.iter().enumerate() {
+            if i < skip_until {
+                continue;
+            }
+            let string_part = match element {
+                CstElement::Node(_) => true,
+                CstElement::Token(token) => {
+                    matches!(
+                        token.token_type,
+                        TokenType::String | TokenType::FStringStart
+                    )
+                }
+                CstElement::Whitespace(_) => false,
+            };
+            if node.name == "JoinedStr" && string_part {
+                skip_until = self.fstrings(node, i);
+                continue;
+            }
+            match element {
+                CstElement::Whitespace(whitespace) => {
+                    if whitespace.contains('\\') {
+                        self.continuation = Some(whitespace.clone());
+                    }
+                    self.advance(whitespace);
+                }
+                CstElement::Token(token)
+                    if token.token_type == TokenType::Comment
+                        && FMT_OFF.contains(&normalize_comment(&token.text).as_str())
+                        && !self.code_on_line
+                        && matches!(node.node_type, NodeType::Mod | NodeType::Stmt) =>
+                {
+                    skip_until = self.fmt_off(node, i, depth);
+                }
+                CstElement::Token(token) => {
+                    skip_until = self.token(node, i, token, previous, depth, call, &edges);
+                    if !is_trivia(&token.token_type) {
+                        previous = Some(&token.token_type);
+                    }
+                }
+                CstElement::Node(child) => {
+                    if let (Some(bracket), Some(first)) = (range, first_node) {
+                        if i == first {
+                            self.push(Kind::Open('('), Role::Open(bracket), String::new());
+                        }
+                    }
+                    if child.node_type == NodeType::Stmt {
+                        skip_until = self.statement(node, i, child, depth, previous, statements);
+                        statements += 1;
+                    } else if child.node_type == NodeType::ExceptHandler {
+                        self.start_line(depth);
+                        self.walk(child, depth, Parens::Keep, false);
+                    } else {
+                        let parens = self.child_parens(
+                            node,
+                            child,
+                            previous,
+                            Some(i) == first_node,
+                            nodes.len(),
+                        );
+                        // A generator that is the only argument owns the call's parentheses.
+                        let call = node.name == "Call"
+                            && !node.children.iter().skip_while(|element| !matches!(element, CstElement::Node(_))).any(
+                                |element| matches!(element, CstElement::Token(token) if token.token_type == TokenType::LPar),
+                            );
+                        self.walk(child, depth, parens, call);
+                    }
+                    if let (Some(bracket), Some(last)) = (range, last_node) {
+                        if i == last {
+                            self.push(Kind::Close(')'), Role::Close(bracket), String::new());
+                        }
+                    }
+                }
+            }
+        }
+        if added.is_some() {
+            let value = added.unwrap_or_default().replace('(', ")");
+            self.push(Kind::Close(')'), Role::Close(Bracket::Atom), value);
+        }
+    }
+
+    /// Lays out the token at `index` among the children of `owner`, returning the index of
+    /// the next child to lay out.
+    #[allow(clippy::too_many_arguments)]
+    fn token(
+        &mut self,
+        owner: &CstNode,
+        index: usize,
+        token: &CstToken,
+        previous: Option<&TokenType>,
+        depth: usize,
+        call: bool,
+        edges: &Edges,
+    ) -> usize {
+        match token.token_type {
+            TokenType::NL | TokenType::Newline => {
+                self.newlines += 1;
+                self.lineno += 1;
+                self.column = 0;
+                self.code_on_line = false;
+                if token.token_type == TokenType::Newline {
+                    self.start_line(self.current.depth);
+                }
+                return index + 1;
+            }
+            TokenType::Comment => {
+                self.comment(&token.text);
+                self.advance(&token.text);
+                return index + 1;
+            }
+            TokenType::Indent => {
+                self.column = token.text.chars().count();
+                return index + 1;
+            }
+            TokenType::Semi => {
+                self.start_line(self.current.depth);
+                self.advance(&token.text);
+                return index + 1;
+            }
+            _ => {}
+        }
+        let statement = matches!(owner.node_type, NodeType::Stmt | NodeType::ExceptHandler);
+        if statement && !self.current.tracker.any_open_brackets() {
+            let definition = matches!(owner.name, "FunctionDef" | "AsyncFunctionDef" | "ClassDef");
+            match token.token_type {
+                TokenType::Else | TokenType::Finally => self.start_line(depth),
+                TokenType::At | TokenType::Async if definition => self.start_line(depth),
+                TokenType::Def | TokenType::Class if previous != Some(&TokenType::Async) => {
+                    self.start_line(depth)
+                }
+                TokenType::Name | TokenType::SoftKeyword
+                    if owner.name == "Match" && token.text == "case" =>
+                {
+                    self.start_line(depth + 1)
+                }
+                _ => {}
+            }
+        }
+        if owner.name == "ClassDef" && token.token_type == TokenType::LPar {
+            // `class A():` loses its empty parentheses.
+            let close = owner.children[index + 1..]
+                .iter()
+                .position(|element| !matches!(element, CstElement::Whitespace(_)));
+            if let Some(offset) = close {
+                if matches!(&owner.children[index + 1 + offset], CstElement::Token(token)
+                    if token.token_type == TokenType::RPar)
+                {
+                    return index + offset + 2;
+                }
+            }
+        }
+        let kind = token_kind(&token.token_type);
+        let mut role = token_role(owner, index, token, previous, call);
+        let mut value = token.text.clone();
+        if edges.wrapping.contains(&index) {
+            role = match kind {
+                Kind::Open(_) => Role::Open(Bracket::Atom),
+                _ => Role::Close(Bracket::Atom),
+            };
+        }
+        let own_parens = matches!(owner.name, "ImportFrom" | "With" | "AsyncWith")
+            && matches!(token.token_type, TokenType::LPar | TokenType::RPar);
+        if edges.hidden.contains(&index) || (own_parens && !edges.wrapping.contains(&index)) {
+            value.clear();
+        }
+        match kind {
+            Kind::String => self.string(&token.text),
+            Kind::Number => self.push(kind, role, normalize_number(&value)),
+            _ => self.push(kind, role, value),
+        }
+        self.advance(&token.text);
+        index + 1
+    }
+
+    /// Lays out the strings of a `JoinedStr` from the child at `index` of `owner`, each
+    /// f-string as one string as Black does before Python 3.12, returning the index of the
+    /// next child to lay out. The literal parts of implicitly concatenated strings are merged
+    /// into one node, so a string may start or end inside a child.
+    fn fstrings(&mut self, owner: &CstNode, index: usize) -> usize {
+        fn flatten<'a>(element: &'a CstElement, elements: &mut Vec<&'a CstElement>) {
+            match element {
+                CstElement::Node(node) => {
+                    for child in &node.children {
+                        flatten(child, elements);
+                    }
+                }
+                _ => elements.push(element),
+            }
+        }
+        let mut text = String::new();
+        let mut nesting = 0;
+        for (i, element) in owner.children.iter().enumerate().skip(index) {
+            let mut elements = vec![];
+            flatten(element, &mut elements);
+            for element in elements {
+                match element {
+                    CstElement::Token(token)
+                        if nesting > 0 || token.token_type == TokenType::FStringStart =>
+                    {
+                        text.push_str(&token.text);
+                        match token.token_type {
+                            TokenType::FStringStart => nesting += 1,
+                            TokenType::FStringEnd => nesting -= 1,
+                            _ => {}
+                        }
+                        if nesting == 0 {
+                            self.string(&text);
+                            self.advance(&text);
+                            text.clear();
+                        }
+                    }
+                    CstElement::Whitespace(whitespace) if nesting > 0 => text.push_str(whitespace),
+                    CstElement::Whitespace(whitespace) => {
+                        if whitespace.contains('\\') {
+                            self.continuation = Some(whitespace.clone());
+                        }
+                        self.advance(whitespace);
+                    }
+                    CstElement::Token(token) => match token.token_type {
+                        TokenType::String => {
+                            self.string(&token.text);
+                            self.advance(&token.text);
+                        }
+                        TokenType::NL => {
+                            self.newlines += 1;
+                            self.lineno += 1;
+                            self.column = 0;
+                            self.code_on_line = false;
+                        }
+                        TokenType::Comment => {
+                            self.comment(&token.text);
+                            self.advance(&token.text);
+                        }
+                        _ => return i,
+                    },
+                    CstElement::Node(_) => unreachable!("flattened"),
+                }
+            }
+        }
+        owner.children.len()
+    }
+
+    /// Lays out the statement `child` at `index` among the children of `owner`, after
+    /// `statements` others, returning the index of the next child to lay out.
+    fn statement(
+        &mut self,
+        owner: &CstNode,
+        index: usize,
+        child: &CstNode,
+        depth: usize,
+        previous: Option<&TokenType>,
+        statements: usize,
+    ) -> usize {
+        let depth = statement_depth(owner, child, depth);
+        // Like a dummy implementation, `...` stays on the line of its definition.
+        let stub = previous == Some(&TokenType::Colon)
+            && matches!(owner.name, "FunctionDef" | "AsyncFunctionDef" | "ClassDef")
+            && child.name == "Expr"
+            && child.code() == "...";
+        if !stub {
+            self.start_line(depth);
+            if let Some(end) = self.fmt_skip(owner, index, child) {
+                return end;
+            }
+        }
+        self.docstring = is_docstring(child)
+            && match owner.node_type {
+                NodeType::Mod => statements == 0,
+                // A body on the line of its header goes on a line of its own.
+                _ => matches!(previous, Some(TokenType::Indent | TokenType::Colon)),
+            };
+        self.walk(child, depth, Parens::Keep, false);
+        index + 1
+    }
+
+    /// Writes the simple statement `child` at `index` among the children of `owner` as it
+    /// is if a `# fmt: skip` comment follows it on its line, returning the index of the
+    /// next child to lay out.
+    fn fmt_skip(&mut self, owner: &CstNode, index: usize, child: &CstNode) -> Option<usize> {
+        let compound = matches!(
+            child.name,
+            "FunctionDef"
+                | "AsyncFunctionDef"
+                | "ClassDef"
+                | "If"
+                | "For"
+                | "AsyncFor"
+                | "While"
+                | "With"
+                | "AsyncWith"
+                | "Try"
+                | "TryStar"
+                | "Match"
+        );
+        let after_semicolon = owner.children[..index]
+            .iter()
+            .rev()
+            .find(|element| !matches!(element, CstElement::Whitespace(_)))
+            .is_some_and(|element| {
+                matches!(element, CstElement::Token(token) if token.token_type == TokenType::Semi)
+            });
+        let end = (index + 1..owner.children.len())
+            .find(|i| !matches!(owner.children[*i], CstElement::Whitespace(_)))?;
+        let skip = matches!(&owner.children[end], CstElement::Token(token)
+            if token.token_type == TokenType::Comment
+                && FMT_SKIP.contains(&normalize_comment(&token.text).as_str()));
+        if compound || after_semicolon || !skip {
+            return Option::None;
+        }
+        let text: String = owner.children[index..=end]
+            .iter()
+            .map(element_code)
+            .collect();
+        self.push(Kind::StandaloneComment, Role::Atom, text.clone());
+        self.current.verbatim = true;
+        self.advance(&text);
+        Some(end + 1)
+    }
+
+    /// Writes the region from the `# fmt: off` comment at `index` among the children of
+    /// `owner` to the `# fmt: on` comment ending it, or to the end of the block, as it is,
+    /// returning the index of the next child to lay out.
+    fn fmt_off(&mut self, owner: &CstNode, index: usize, depth: usize) -> usize {
+        let mut last = index;
+        let mut statement = Option::None;
+        for (i, element) in owner.children.iter().enumerate().skip(index + 1) {
+            match element {
+                CstElement::Token(token) if token.token_type == TokenType::Comment => {
+                    if FMT_ON.contains(&normalize_comment(&token.text).as_str()) {
+                        break;
+                    }
+                    last = i;
+                }
+                CstElement::Token(token)
+                    if matches!(
+                        token.token_type,
+                        TokenType::NL | TokenType::Newline | TokenType::Indent | TokenType::Dedent
+                    ) => {}
+                CstElement::Node(node) if node.node_type == NodeType::Stmt => {
+                    statement = statement.or(Some(node));
+                    last = i;
+                }
+                CstElement::Whitespace(_) => {}
+                _ => break,
+            }
+        }
+        let text: String = owner.children[index..=last]
+            .iter()
+            .map(element_code)
+            .collect();
+        let region = text.trim_end();
+        self.comments.push((
+            region.to_string(),
+            self.newlines.saturating_sub(1),
+            self.column,
+        ));
+        // The region goes at the depth of its statements, not of the line after it.
+        if let Some(statement) = statement {
+            let depth = statement_depth(owner, statement, depth);
+            self.flush_comments(depth);
+            self.columns.resize(depth + 1, 0);
+            self.columns[depth] = self.column;
+            self.last_depth = depth;
+        }
+        self.newlines = text[region.len()..].matches('\n').count();
+        self.advance(&text);
+        last + 1
+    }
+
+    /// How the parentheses of `child` are written, after the token `previous` of `owner`.
+    fn child_parens(
+        &self,
+        owner: &CstNode,
+        child: &CstNode,
+        previous: Option<&TokenType>,
+        first: bool,
+        count: usize,
+    ) -> Parens {
+        use TokenType::*;
+        match (owner.name, previous) {
+            ("Assign", Some(Equal)) | ("AnnAssign", Some(Equal)) | ("AugAssign", Some(_)) => {
+                return normalize_parens(child, true, false)
+            }
+            // Unpacking to a tuple on the left of an assignment.
+            ("Assign", Option::None)
+                if child.name == "Tuple" && child.parens == 0 && !owns_parens(child) =>
+            {
+                return normalize_parens(child, true, false)
+            }
+            ("Return", _) | ("Assert", _) => return normalize_parens(child, true, false),
+            ("Delete", _) if count == 1 => return normalize_parens(child, true, false),
+            ("If" | "While", Some(If | Elif | While)) | ("ExceptHandler", Some(Except)) => {
+                return normalize_parens(child, false, false)
+            }
+            ("For" | "AsyncFor", Some(For)) => return normalize_parens(child, true, true),
+            ("With" | "AsyncWith", Some(With | Comma | LPar)) => {
+                let keep =
+                    matches!(child.name, "Yield" | "YieldFrom" | "NamedExpr") || owns_parens(child);
+                return match child.parens > 0 && !keep {
+                    true => Parens::Hidden,
+                    false => Parens::Keep,
+                };
+            }
+            ("Match", _) if first || child.node_type == NodeType::Pattern => {
+                return normalize_parens(child, false, false)
+            }
+            ("FunctionDef" | "AsyncFunctionDef", Some(RArrow)) => {
+                return match child.parens > 0 || owns_parens(child) {
+                    true => normalize_parens(child, false, false),
+                    false => Parens::Invisible,
+                }
+            }
+            ("arg", Some(Colon)) if child.parens > 0 => {
+                return normalize_parens(child, false, false)
+            }
+            ("arg", Some(Colon)) if is_atom_like(child) => return Parens::Invisible,
+            ("Expr", _)
+                if child.name == "BinOp"
+                    && child.parens == 0
+                    && matches!(
+                        operator(child),
+                        Some(Plus | Minus | LeftShift | RightShift | Circumflex | Amper)
+                    ) =>
+            {
+                return Parens::Invisible
+            }
+            ("Await", _) if child.parens > 0 => {
+                let simple = match child.name {
+                    "Name" | "Attribute" | "Call" | "Subscript" => {
+                        !child.descendants().iter().any(|node| node.name == "Await")
+                    }
+                    "Constant" => string_parts(child) < 2,
+                    _ => false,
+                };
+                if simple {
+                    return Parens::Hidden;
+                }
+            }
+            // `-x ** y` becomes `-(x ** y)`.
+            ("UnaryOp", _)
+                if child.name == "BinOp"
+                    && child.parens == 0
+                    && operator(child) == Some(&DoubleStar)
+                    && operator(owner) != Some(&Not) =>
+            {
+                if let Some(base) = child.nodes().next() {
+                    if base.parens > 0
+                        || !matches!(base.name, "Attribute" | "Call" | "Subscript" | "Await")
+                    {
+                        return Parens::Visible;
+                    }
+                }
+            }
+            // `1 .real` becomes `(1).real`.
+            ("Attribute", Option::None) if child.name == "Constant" && child.parens == 0 => {
+                if let Some(number) = first_token(child) {
+                    let text = number.text.to_ascii_lowercase();
+                    if number.token_type == Number
+                        && !text.starts_with("0x")
+                        && !text.starts_with("0b")
+                        && !text.starts_with("0o")
+                        && !text.contains('j')
+                    {
+                        return Parens::Visible;
+                    }
+                }
+            }
+            _ => {}
+        }
+        // Conditional expressions get invisible parentheses, unless already in some.
+        let parenthesized =
+            matches!(self.current.leaves.last(), Some(leaf) if leaf.kind == Kind::Open('('));
+        match child.name == "IfExp" && child.parens == 0 && !parenthesized {
+            true => Parens::Invisible,
+            false => Parens::Keep,
+        }
+    }
+}
+
+/// Hugs `**` between simple operands, like Black's `hug_power_op`.
+fn hug_power_operators(line: &mut Line) {
+    let leaves = &line.leaves;
+    let is_name = |leaf: &Leaf| leaf.kind == Kind::Name || leaf.role == Role::Dot;
+    let is_simple_lookup = |mut index: usize, forward: bool| -> bool {
+        loop {
+            let leaf = &leaves[index];
+            let disallowed = match forward {
+                true => matches!(leaf.kind, Kind::Open('(' | '[')),
+                false => matches!(leaf.kind, Kind::Close(')' | ']')),
+            };
+            if disallowed {
+                return false;
+            }
+            if !is_name(leaf) || leaf.value == "for" {
+                return true;
+            }
+            match forward {
+                true if index + 1 < leaves.len() => index += 1,
+                false if index > 0 => index -= 1,
+                _ => return true,
+            }
+        }
+    };
+    let is_simple_operand = |index: usize, forward: bool| -> bool {
+        let leaf = &leaves[index];
+        if leaf.kind == Kind::Name || leaf.kind == Kind::Number {
+            return is_simple_lookup(index, forward);
+        }
+        if leaf.role == Role::Unary && index + 1 < leaves.len() {
+            let next = &leaves[index + 1];
+            if next.kind == Kind::Name || next.kind == Kind::Number {
+                return is_simple_lookup(index + 1, true);
+            }
+        }
+        false
+    };
+    let hugged: Vec<usize> = (1..leaves.len().saturating_sub(1))
+        .filter(|i| {
+            matches!(leaves[*i].role, Role::Power { .. })
+                && is_simple_operand(i - 1, false)
+                && leaves[i - 1].value != "lambda"
+                && is_simple_operand(i + 1, true)
+        })
+        .collect();
+    for i in hugged {
+        line.leaves[i].role = Role::Power { hug: true };
+        line.leaves[i].prefix.clear();
+        line.leaves[i + 1].prefix.clear();
+    }
+}
+
+/// The ways `transform_line` tries to split a line, in the order of Black's `transformers`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Transform {
+    /// At the delimiters of the lowest priority, one part per line.
+    Delimiter,
+    /// Around the comments on lines of their own.
+    StandaloneComment,
+    /// At the last opening bracket, and its closing bracket.
+    RightHand,
+    /// At the first opening bracket, and its closing bracket, for definitions.
+    LeftHand,
+}
+
+/// Why a line could not be split one way, so that the next is tried.
+struct CannotSplit;
+
+type Split = Result<Vec<Line>, CannotSplit>;
+
+/// The three lines a line is split into at a pair of brackets.
+struct BracketSplit {
+    head: Line,
+    body: Line,
+    tail: Line,
+    opening: usize,
+    closing: usize,
+}
+
+/// Splits the lines of one logical line, remembering which invisible parentheses were made
+/// visible: as in Black, where leaves are shared between the attempts, a split that is
+/// given up on leaves them visible.
+struct Splitter<'a> {
+    options: &'a FormatOptions,
+    visible: HashSet<usize>,
+    next_id: usize,
+    /// Whether trailing commas may follow `*args` and `**kwargs`, as the syntax of the
+    /// source requires Python 3.6 at least.
+    varargs_comma: bool,
+}
+
+/// Splits `line` into lines that fit in the line length where possible, like Black's
+/// `transform_line`.
+fn transform_line(line: Line, options: &FormatOptions, varargs_comma: bool) -> Vec<Line> {
+    let next_id = line.leaves.iter().map(|leaf| leaf.id).max().unwrap_or(0) + 1;
+    let mut splitter = Splitter {
+        options,
+        visible: HashSet::new(),
+        next_id,
+        varargs_comma,
+    };
+    splitter.transform(line, false)
+}
+
+impl Splitter<'_> {
+    /// Splits `line`, with `force_parens` keeping the optional parentheses rather than
+    /// omitting them.
+    fn transform(&mut self, line: Line, force_parens: bool) -> Vec<Line> {
+        if line.is_comment() {
+            return vec![line];
+        }
+        let line_str = line.render().trim_end_matches('\n').to_string();
+        let transforms: &[Transform] = if !line.should_split_rhs
+            && line.magic_trailing_comma.is_none()
+            && (line.is_short_enough(self.options.line_length)
+                || line.contains_unsplittable_type_ignore())
+            && !(line.inside_brackets && line.contains_standalone_comments())
+        {
+            &[]
+        } else if line.is_def() {
+            &[Transform::LeftHand]
+        } else if line.inside_brackets {
+            &[
+                Transform::Delimiter,
+                Transform::StandaloneComment,
+                Transform::RightHand,
+            ]
+        } else {
+            &[Transform::RightHand]
+        };
+        for transform in transforms {
+            if let Ok(lines) = self.run(&line, *transform, force_parens, &line_str) {
+                return lines;
+            }
+        }
+        vec![line]
+    }
+
+    /// Splits `line` one way and the lines split off recursively, like `run_transformer`.
+    fn run(
+        &mut self,
+        line: &Line,
+        transform: Transform,
+        force_parens: bool,
+        line_str: &str,
+    ) -> Split {
+        let mut result = vec![];
+        for part in self.apply(line, transform, force_parens)? {
+            if part.render().trim_end_matches('\n') == line_str {
+                return Err(CannotSplit);
+            }
+            result.extend(self.transform(part, force_parens));
+        }
+        // When the optional parentheses were omitted and the first line is still too long,
+        // see whether keeping them gives lines that all fit.
+        let line_length = self.options.line_length;
+        if transform == Transform::RightHand
+            && !force_parens
+            && !line.tracker.invisible.is_empty()
+            && !line
+                .tracker
+                .invisible
+                .iter()
+                .any(|id| self.visible.contains(id))
+            && !line.contains_multiline_strings()
+            && !result[0].is_short_enough(line_length)
+        {
+            let visible = self.visible.clone();
+            let mut copy = line.split_off(line.depth, line.inside_brackets);
+            for leaf in &line.leaves {
+                copy.append(leaf.clone(), false, false);
+                for comment in line.comments_after(leaf) {
+                    copy.append(comment, true, false);
+                }
+            }
+            if let Ok(lines) = self.run(&copy, transform, true, line_str) {
+                if lines.iter().all(|line| line.is_short_enough(line_length)) {
+                    result = lines;
+                }
+            }
+            self.visible = visible;
+        }
+        Ok(result)
+    }
+
+    fn apply(&mut self, line: &Line, transform: Transform, force_parens: bool) -> Split {
+        match transform {
+            Transform::Delimiter => self.delimiter_split(line),
+            Transform::StandaloneComment => standalone_comment_split(line),
+            Transform::LeftHand => self.left_hand_split(line),
+            Transform::RightHand => self.rhs(line, force_parens),
+        }
+    }
+
+    /// Splits at the last brackets, leaving trailers that fit on the last line, like the
+    /// `_rhs` transformer of Black.
+    fn rhs(&mut self, line: &Line, force_parens: bool) -> Split {
+        let mut trailers = Trailers::default();
+        while let Some(omit) = trailers.next(line, &self.visible, self.options.line_length) {
+            let lines = self.right_hand_split(line, &omit, force_parens)?;
+            if lines[0].is_short_enough(self.options.line_length) {
+                return Ok(lines);
+            }
+        }
+        self.right_hand_split(line, &[], force_parens)
+    }
+}
+
+/// The length of `leaf` and the comments after it on `line`, or `None` for a multiline
+/// string, like Black's `enumerate_with_length`.
+fn leaf_length(line: &Line, leaf: &Leaf, visible: &HashSet<usize>) -> Option<usize> {
+    if leaf.value.contains('\n') {
+        return None;
+    }
+    let value = match leaf.is_invisible() && visible.contains(&leaf.id) {
+        true => 1,
+        false => leaf.value.chars().count(),
+    };
+    let comments: usize = line
+        .comments_after(leaf)
+        .iter()
+        .map(|comment| comment.value.chars().count())
+        .sum();
+    Some(leaf.prefix.chars().count() + value + comments)
+}
+
+/// The sets of closing brackets a right hand split may leave at the end of the last line,
+/// from none to as many trailers as fit, like Black's `generate_trailers_to_omit`.
+#[derive(Default)]
+struct Trailers {
+    started: bool,
+    done: bool,
+    /// The number of leaves from the end already seen.
+    seen: usize,
+    length: usize,
+    omit: Vec<usize>,
+    opening: Option<usize>,
+    closing: Option<usize>,
+    inner: Vec<usize>,
+}
+
+impl Trailers {
+    fn next(
+        &mut self,
+        line: &Line,
+        visible: &HashSet<usize>,
+        line_length: usize,
+    ) -> Option<Vec<usize>> {
+        if !self.started {
+            self.started = true;
+            self.length = 4 * line.depth;
+            if line.magic_trailing_comma.is_none() {
+                return Some(vec![]);
+            }
+        }
+        while !self.done && self.seen < line.leaves.len() {
+            let index = line.leaves.len() - 1 - self.seen;
+            self.seen += 1;
+            let leaf = &line.leaves[index];
+            let Some(length) = leaf_length(line, leaf, visible) else {
+                self.done = true;
+                break;
+            };
+            self.length += length;
+            if self.length > line_length {
+                self.done = true;
+                break;
+            }
+            let previous = index.checked_sub(1).map(|i| &line.leaves[i]);
+            let trailing_comma = leaf.is_close()
+                && previous.is_some_and(|previous| previous.kind == Kind::Comma)
+                && leaf.opening_bracket.is_some_and(|opening| {
+                    leaf.kind != Kind::Close(')') || !line.is_one_sequence_between(opening, leaf)
+                });
+            if let Some(opening) = self.opening {
+                if trailing_comma {
+                    // A trailer holding brackets with a trailing comma explodes anyway.
+                    self.done = true;
+                    break;
+                }
+                if leaf.id == opening {
+                    self.opening = None;
+                } else if leaf.is_close() {
+                    self.inner.push(leaf.id);
+                }
+                continue;
+            }
+            if !leaf.is_close() {
+                continue;
+            }
+            if previous.is_some_and(Leaf::is_open) {
+                // Empty brackets would fail a split, so they are only omitted along with
+                // the trailer they are in.
+                self.inner.push(leaf.id);
+                continue;
+            }
+            let mut omit = None;
+            if let Some(closing) = self.closing {
+                self.omit.push(closing);
+                self.omit.append(&mut self.inner);
+                omit = Some(self.omit.clone());
+            }
+            if trailing_comma {
+                // Brackets with a trailing comma are never omitted, as they must explode.
+                self.done = true;
+            } else if !leaf.is_invisible() || visible.contains(&leaf.id) {
+                self.opening = leaf.opening_bracket;
+                self.closing = Some(leaf.id);
+            }
+            if omit.is_some() {
+                return omit;
+            }
+        }
+        None
+    }
+}
+
+/// The part of a line split at brackets that a line built by `build_line` is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Component {
+    Head,
+    Body,
+    Tail,
+}
+
+impl Splitter<'_> {
+    /// A copy of `leaf`, shown if its invisible parentheses were made visible.
+    fn reveal(&self, leaf: &Leaf) -> Leaf {
+        let mut leaf = leaf.clone();
+        if self.visible.contains(&leaf.id) {
+            leaf.ensure_visible();
+        }
+        leaf
+    }
+
+    fn comma(&mut self) -> Leaf {
+        self.next_id += 1;
+        Leaf {
+            id: self.next_id,
+            kind: Kind::Comma,
+            role: Role::Comma { arglist: false },
+            value: ",".to_string(),
+            prefix: String::new(),
+            bracket_depth: 0,
+            opening_bracket: None,
+            lineno: 0,
+        }
+    }
+
+    /// Builds a line of `leaves` from `original` split at `opening`, like Black's
+    /// `bracket_split_build_line`: the body is indented, and gets a trailing comma where
+    /// it lists imports or the only parameter of a definition.
+    fn build_line(
+        &mut self,
+        mut leaves: Vec<Leaf>,
+        original: &Line,
+        opening: &Leaf,
+        component: Component,
+    ) -> Line {
+        let mut result = original.split_off(original.depth, false);
+        if component == Component::Body {
+            result.inside_brackets = true;
+            result.depth += 1;
+            result.bracket = match opening.role {
+                Role::Open(bracket) => Some(bracket),
+                _ => None,
+            };
+            let no_commas = original.is_def()
+                && opening.role == Role::Open(Bracket::Call)
+                && !opening.value.is_empty()
+                && !leaves.iter().any(|leaf| leaf.kind == Kind::Comma);
+            if !leaves.is_empty() && (original.is_import() || no_commas) {
+                if let Some(i) = leaves
+                    .iter()
+                    .rposition(|leaf| leaf.kind != Kind::StandaloneComment)
+                {
+                    if leaves[i].kind != Kind::Comma {
+                        let comma = self.comma();
+                        leaves.insert(i + 1, comma);
+                    }
+                }
+            }
+        }
+        let tracked = match component {
+            Component::Head => leaves_inside_matching_brackets(&leaves),
+            _ => HashSet::new(),
+        };
+        for leaf in leaves {
+            let comments = original.comments_after(&leaf);
+            let track = tracked.contains(&leaf.id);
+            result.append(leaf, true, track);
+            for comment in comments {
+                result.append(comment, true, false);
+            }
+        }
+        if component == Component::Body && should_split_line(&result, opening) {
+            result.should_split_rhs = true;
+        }
+        result
+    }
+
+    /// Splits `line` at the last pair of brackets whose closing one is not in `omit`, like
+    /// Black's `_first_right_hand_split`.
+    fn first_right_hand_split(
+        &mut self,
+        line: &Line,
+        omit: &[usize],
+    ) -> Result<BracketSplit, CannotSplit> {
+        let (mut tail, mut body, mut head) = (vec![], vec![], vec![]);
+        let mut component = Component::Tail;
+        let (mut opening, mut closing) = (None, None);
+        for leaf in line.leaves.iter().rev() {
+            if component == Component::Body && opening == Some(leaf.id) {
+                component = match body.is_empty() {
+                    true => Component::Tail,
+                    false => Component::Head,
+                };
+            }
+            let current = match component {
+                Component::Tail => &mut tail,
+                Component::Body => &mut body,
+                Component::Head => &mut head,
+            };
+            current.push(self.reveal(leaf));
+            if component == Component::Tail && leaf.is_close() && !omit.contains(&leaf.id) {
+                opening = leaf.opening_bracket;
+                closing = Some(leaf.id);
+                component = Component::Body;
+            }
+        }
+        let (Some(opening), Some(closing)) = (opening, closing) else {
+            return Err(CannotSplit);
+        };
+        if head.is_empty() {
+            return Err(CannotSplit);
+        }
+        let Some(bracket) = line.leaves.iter().find(|leaf| leaf.id == opening) else {
+            return Err(CannotSplit);
+        };
+        let bracket = self.reveal(bracket);
+        tail.reverse();
+        body.reverse();
+        head.reverse();
+        Ok(BracketSplit {
+            head: self.build_line(head, line, &bracket, Component::Head),
+            body: self.build_line(body, line, &bracket, Component::Body),
+            tail: self.build_line(tail, line, &bracket, Component::Tail),
+            opening,
+            closing,
+        })
+    }
+
+    fn right_hand_split(&mut self, line: &Line, omit: &[usize], force_parens: bool) -> Split {
+        let split = self.first_right_hand_split(line, omit)?;
+        self.maybe_split_omitting_optional_parens(split, line, omit, force_parens)
+    }
+
+    /// Splits at the brackets of `split`, unless they are optional parentheses that read
+    /// better omitted, like Black's `_maybe_split_omitting_optional_parens`.
+    fn maybe_split_omitting_optional_parens(
+        &mut self,
+        mut split: BracketSplit,
+        line: &Line,
+        omit: &[usize],
+        force_parens: bool,
+    ) -> Split {
+        let optional = |leaf: Option<&Leaf>, kind| {
+            leaf.is_some_and(|leaf| leaf.kind == kind && leaf.value.is_empty())
+        };
+        if !force_parens
+            && optional(split.head.leaves.last(), Kind::Open('('))
+            && optional(split.tail.leaves.first(), Kind::Close(')'))
+            && !line.is_import()
+            && self.can_omit_invisible_parens(&split)
+        {
+            let omit: Vec<usize> = [&[split.closing][..], omit].concat();
+            let result = match self.first_right_hand_split(line, &omit) {
+                Ok(oop) if self.prefer_split_omitting_optional_parens(&oop, &split) => {
+                    Some(self.maybe_split_omitting_optional_parens(oop, line, &omit, force_parens))
+                }
+                Ok(_) => None,
+                Err(error) => Some(Err(error)),
+            };
+            match result {
+                Some(Ok(lines)) => return Ok(lines),
+                Some(Err(error)) => {
+                    let line_length = self.options.line_length;
+                    if !(can_be_split(&split.body) || split.body.is_short_enough(line_length)) {
+                        return Err(error);
+                    }
+                    // The head or the tail would never fit on one line.
+                    if split.head.contains_multiline_strings()
+                        || split.tail.contains_multiline_strings()
+                    {
+                        return Err(error);
+                    }
+                }
+                None => {}
+            }
+        }
+        self.visible.insert(split.opening);
+        self.visible.insert(split.closing);
+        if let Some(leaf) = split.head.leaves.last_mut() {
+            leaf.ensure_visible();
+        }
+        if let Some(leaf) = split.tail.leaves.first_mut() {
+            leaf.ensure_visible();
+        }
+        Ok([split.head, split.body, split.tail]
+            .into_iter()
+            .filter(|line| !line.leaves.is_empty())
+            .collect())
+    }
+
+    /// Whether the split omitting optional parentheses is better than the split at them,
+    /// like Black's `_prefer_split_rhs_oop_over_rhs`.
+    fn prefer_split_omitting_optional_parens(
+        &self,
+        oop: &BracketSplit,
+        split: &BracketSplit,
+    ) -> bool {
+        let is_equal = |leaf: &Leaf| leaf.value == "=";
+        let head = &split.head.leaves;
+        // Unless the split is right after the `=` of an assignment whose left side has
+        // brackets, fits and won't explode.
+        if !(head.len() >= 2 && is_equal(&head[head.len() - 2])) {
+            return true;
+        }
+        if !head[..head.len() - 1].iter().any(Leaf::is_bracket) {
+            return true;
+        }
+        if !split.head.is_short_enough(self.options.line_length - 1) {
+            return true;
+        }
+        if split.head.magic_trailing_comma.is_some() {
+            return true;
+        }
+        // With several targets, more of them on the first line is better.
+        let equals = head.iter().filter(|leaf| is_equal(leaf)).count();
+        let oop_equals = oop.head.leaves.iter().filter(|leaf| is_equal(leaf)).count();
+        if equals > 1 && equals > oop_equals {
+            return false;
+        }
+        let closing_bracket_after_assign = oop
+            .head
+            .leaves
+            .iter()
+            .rev()
+            .take_while(|leaf| !is_equal(leaf))
+            .any(Leaf::is_close);
+        closing_bracket_after_assign
+            || (oop_equals > 0 && oop.head.is_short_enough(self.options.line_length))
+    }
+
+    /// Whether the body of `split` reads well without the optional parentheses around it,
+    /// like Black's `can_omit_invisible_parens`.
+    fn can_omit_invisible_parens(&self, split: &BracketSplit) -> bool {
+        let line = &split.body;
+        let line_length = self.options.line_length;
+        // Standalone comments need the parentheses to be split off, unless they are
+        // within other brackets.
+        let mut closing: Option<&Leaf> = None;
+        for leaf in line.leaves.iter().rev() {
+            if closing.is_some_and(|closing| closing.opening_bracket == Some(leaf.id)) {
+                closing = None;
+            }
+            if leaf.kind == Kind::StandaloneComment && closing.is_none() {
+                return false;
+            }
+            let opened_in_line = |opening: Option<usize>| {
+                opening.is_some_and(|opening| line.leaves.iter().any(|leaf| leaf.id == opening))
+            };
+            if closing.is_none()
+                && leaf.is_close()
+                && opened_in_line(leaf.opening_bracket)
+                && !leaf.value.is_empty()
+            {
+                closing = Some(leaf);
+            }
+        }
+        let tracker = &line.tracker;
+        if tracker.delimiters.is_empty() {
+            return true;
+        }
+        let priority = tracker.max_delimiter_priority(&[]);
+        if tracker.delimiter_count_with_priority(priority) > 1 {
+            return false;
+        }
+        if priority == DOT_PRIORITY {
+            // A single method call stranded on its own line doesn't need the parentheses.
+            return true;
+        }
+        let leaves = &line.leaves;
+        if leaves.len() < 2 {
+            return false;
+        }
+        let (first, second) = (&leaves[0], &leaves[1]);
+        if first.is_open() && !second.is_close() && self.can_omit_opening_paren(line, first) {
+            return true;
+        }
+        let (penultimate, last) = (&leaves[leaves.len() - 2], &leaves[leaves.len() - 1]);
+        let closes = match last.kind {
+            Kind::Close(')' | '}') => true,
+            Kind::Close(']') => last.role != Role::Close(Bracket::Subscript),
+            _ => false,
+        };
+        if closes {
+            if penultimate.is_open() {
+                return false;
+            }
+            if first.kind == Kind::String && first.value.contains('\n') {
+                return true;
+            }
+            if self.can_omit_closing_paren(line, last, line_length) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Whether what follows the brackets `first` opens fits, up to the next opening bracket.
+    fn can_omit_opening_paren(&self, line: &Line, first: &Leaf) -> bool {
+        let mut remainder = false;
+        let mut length = 4 * line.depth;
+        for leaf in &line.leaves {
+            let Some(leaf_length) = leaf_length(line, leaf, &self.visible) else {
+                return false;
+            };
+            if leaf.is_close() && leaf.opening_bracket == Some(first.id) {
+                remainder = true;
+            }
+            if remainder {
+                length += leaf_length;
+                if length > self.options.line_length {
+                    return false;
+                }
+                if leaf.is_open() {
+                    remainder = false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Whether the line fits up to the brackets `last` closes, or has others to split at.
+    fn can_omit_closing_paren(&self, line: &Line, last: &Leaf, line_length: usize) -> bool {
+        let mut length = 4 * line.depth;
+        let mut seen_other_brackets = false;
+        for leaf in &line.leaves {
+            let Some(leaf_length) = leaf_length(line, leaf, &self.visible) else {
+                return false;
+            };
+            length += leaf_length;
+            if Some(leaf.id) == last.opening_bracket {
+                if seen_other_brackets || length <= line_length {
+                    return true;
+                }
+            } else if leaf.is_open() {
+                seen_other_brackets = true;
+            }
+        }
+        false
+    }
+}
+
+impl Splitter<'_> {
+    /// Splits a definition at its first opening bracket, like Black's `left_hand_split`, so
+    /// that its parameters go on lines of their own.
+    fn left_hand_split(&mut self, line: &Line) -> Split {
+        let (mut head, mut body, mut tail) = (vec![], vec![], vec![]);
+        let mut component = Component::Head;
+        let mut matching: Option<Leaf> = None;
+        for leaf in &line.leaves {
+            if component == Component::Body
+                && leaf.is_close()
+                && leaf.opening_bracket == matching.as_ref().map(|leaf| leaf.id)
+            {
+                self.visible.insert(leaf.id);
+                self.visible.extend(leaf.opening_bracket);
+                component = match body.is_empty() {
+                    true => Component::Head,
+                    false => Component::Tail,
+                };
+            }
+            let current = match component {
+                Component::Head => &mut head,
+                Component::Body => &mut body,
+                Component::Tail => &mut tail,
+            };
+            current.push(leaf.clone());
+            if component == Component::Head && leaf.is_open() {
+                matching = Some(leaf.clone());
+                component = Component::Body;
+            }
+        }
+        let Some(matching) = matching else {
+            return Err(CannotSplit);
+        };
+        if tail.is_empty() {
+            return Err(CannotSplit);
+        }
+        let reveal = |splitter: &Self, leaves: Vec<Leaf>| -> Vec<Leaf> {
+            leaves.iter().map(|leaf| splitter.reveal(leaf)).collect()
+        };
+        let (head, body, tail) = (reveal(self, head), reveal(self, body), reveal(self, tail));
+        let matching = self.reveal(&matching);
+        Ok([
+            self.build_line(head, line, &matching, Component::Head),
+            self.build_line(body, line, &matching, Component::Body),
+            self.build_line(tail, line, &matching, Component::Tail),
+        ]
+        .into_iter()
+        .filter(|line| !line.leaves.is_empty())
+        .collect())
+    }
+
+    /// Splits a line within brackets after each of its delimiters of the lowest priority,
+    /// like Black's `delimiter_split`, adding a trailing comma to a list of items.
+    fn delimiter_split(&mut self, line: &Line) -> Split {
+        let Some(last_leaf) = line.leaves.last() else {
+            return Err(CannotSplit);
+        };
+        let tracker = &line.tracker;
+        let delimiter = tracker.max_delimiter_priority(&[last_leaf.id]);
+        if delimiter == 0 {
+            return Err(CannotSplit);
+        }
+        if delimiter == DOT_PRIORITY && tracker.delimiter_count_with_priority(delimiter) == 1 {
+            // Splitting a single attribute from its object looks wrong.
+            return Err(CannotSplit);
+        }
+        let mut lines = vec![];
+        let mut current = line.split_off(line.depth, line.inside_brackets);
+        let mut lowest_depth = usize::MAX;
+        let mut trailing_comma_safe = true;
+        let last_code = line
+            .leaves
+            .iter()
+            .rposition(|leaf| leaf.kind != Kind::StandaloneComment);
+        for (i, leaf) in line.leaves.iter().enumerate() {
+            for leaf in std::iter::once(leaf.clone()).chain(line.comments_after(leaf)) {
+                if let Err(leaf) = current.append_safe(leaf, true) {
+                    let next = line.split_off(line.depth, line.inside_brackets);
+                    lines.push(std::mem::replace(&mut current, next));
+                    current.append(leaf, false, false);
+                }
+            }
+            lowest_depth = lowest_depth.min(leaf.bracket_depth);
+            if leaf.bracket_depth == lowest_depth && is_vararg(line, leaf) {
+                trailing_comma_safe &= self.varargs_comma;
+            }
+            if last_leaf.kind == Kind::StandaloneComment && Some(i) == last_code {
+                self.add_trailing_comma(&mut current, trailing_comma_safe, delimiter);
+            }
+            if tracker.delimiters.get(&leaf.id) == Some(&delimiter) {
+                let next = line.split_off(line.depth, line.inside_brackets);
+                lines.push(std::mem::replace(&mut current, next));
+            }
+        }
+        if !current.leaves.is_empty() {
+            if last_leaf.kind != Kind::StandaloneComment {
+                self.add_trailing_comma(&mut current, trailing_comma_safe, delimiter);
+            }
+            lines.push(current);
+        }
+        Ok(lines)
+    }
+
+    fn add_trailing_comma(&mut self, line: &mut Line, safe: bool, delimiter: u8) {
+        let last = line.leaves.last().map(|leaf| leaf.kind);
+        if safe
+            && delimiter == COMMA_PRIORITY
+            && !matches!(last, Some(Kind::Comma | Kind::StandaloneComment) | None)
+        {
+            let comma = self.comma();
+            line.append(comma, false, false);
+        }
+    }
+}
+
+/// Splits a line within brackets around its comments on lines of their own, like Black's
+/// `standalone_comment_split`.
+fn standalone_comment_split(line: &Line) -> Split {
+    if !line.contains_standalone_comments() {
+        return Err(CannotSplit);
+    }
+    let mut lines = vec![];
+    let mut current = line.split_off(line.depth, line.inside_brackets);
+    for leaf in &line.leaves {
+        for leaf in std::iter::once(leaf.clone()).chain(line.comments_after(leaf)) {
+            if let Err(leaf) = current.append_safe(leaf, true) {
+                let next = line.split_off(line.depth, line.inside_brackets);
+                lines.push(std::mem::replace(&mut current, next));
+                current.append(leaf, false, false);
+            }
+        }
+    }
+    if !current.leaves.is_empty() {
+        lines.push(current);
+    }
+    Ok(lines)
+}
+
+/// Whether `leaf` unpacks arguments, or marks parameters keyword or positional only, which
+/// older versions of Python allow no trailing comma after.
+fn is_vararg(line: &Line, leaf: &Leaf) -> bool {
+    line.bracket == Some(Bracket::Call)
+        && match leaf.role {
+            Role::Prefix => matches!(leaf.value.as_str(), "*" | "**"),
+            _ => leaf.value == "/",
+        }
+}
+
+/// The ids of the leaves within pairs of brackets matched in `leaves`, with the brackets.
+fn leaves_inside_matching_brackets(leaves: &[Leaf]) -> HashSet<usize> {
+    let mut ids = HashSet::new();
+    let Some(start) = leaves.iter().position(Leaf::is_open) else {
+        return ids;
+    };
+    let mut stack: Vec<(char, usize)> = vec![];
+    for (i, leaf) in leaves.iter().enumerate().skip(start) {
+        if let Kind::Open(bracket) = leaf.kind {
+            stack.push((closing_bracket(bracket), i));
+        }
+        if let Kind::Close(bracket) = leaf.kind {
+            match stack.last() {
+                Some((closing, _)) if *closing == bracket => {
+                    let (_, start) = stack.pop().unwrap_or_default();
+                    ids.extend(leaves[start..=i].iter().map(|leaf| leaf.id));
+                }
+                _ => break,
+            }
+        }
+    }
+    ids
+}
+
+/// Whether the body of brackets should be split at its delimiters right away: when it is a
+/// collection, the names of an import, or ends with a magic trailing comma.
+fn should_split_line(line: &Line, opening: &Leaf) -> bool {
+    let Some(last) = line.leaves.last() else {
+        return false;
+    };
+    let trailing_comma = last.kind == Kind::Comma;
+    line.tracker.max_delimiter_priority(&[last.id]) == COMMA_PRIORITY
+        && ((!line.skip_magic_trailing_comma && trailing_comma)
+            || matches!(opening.role, Role::Open(Bracket::Atom | Bracket::Import)))
+}
+
+/// Whether `line` might be split, ruling out single leaves and chains of calls on a
+/// string, like Black's `can_be_split`.
+fn can_be_split(line: &Line) -> bool {
+    let leaves = &line.leaves;
+    if leaves.len() < 2 {
+        return false;
+    }
+    if leaves[0].kind == Kind::String && leaves[1].role == Role::Dot {
+        let (mut calls, mut dots) = (0, 0);
+        let mut next = &leaves[leaves.len() - 1];
+        for leaf in leaves[..leaves.len() - 1].iter().rev() {
+            if leaf.is_open() {
+                if !next.is_close() {
+                    return false;
+                }
+                calls += 1;
+            } else if leaf.role == Role::Dot {
+                dots += 1;
+            } else if leaf.kind == Kind::Name {
+                if !(next.role == Role::Dot || next.is_open()) {
+                    return false;
+                }
+            } else if !leaf.is_close() {
+                return false;
+            }
+            if dots > 1 && calls > 1 {
+                return false;
+            }
+            next = leaf;
+        }
+    }
+    true
+}
+
+/// The blank lines before a logical line, and after it.
+#[derive(Clone, Copy, Debug)]
+struct Block {
+    /// Less the blank lines after the previous line, so possibly negative.
+    before: isize,
+    after: usize,
+}
+
+/// Decides the blank lines around the logical lines, like Black's `EmptyLineTracker`.
+#[derive(Default)]
+struct EmptyLineTracker {
+    blocks: Vec<Block>,
+    /// The depths of the definitions whose bodies may not have ended yet.
+    previous_defs: Vec<usize>,
+    /// The first of the comments before the current line, which the blank lines before a
+    /// definition go before.
+    semantic_leading_comment: Option<usize>,
+}
+
+impl EmptyLineTracker {
+    /// The blank lines before and after each of `lines`.
+    fn blank_lines(lines: &[Line]) -> Vec<(usize, usize)> {
+        let mut tracker = EmptyLineTracker::default();
+        for i in 0..lines.len() {
+            tracker.maybe_empty_lines(lines, i);
+        }
+        if let Some(last) = tracker.blocks.last_mut() {
+            last.after = 0;
+        }
+        tracker
+            .blocks
+            .iter()
+            .map(|block| (block.before.max(0) as usize, block.after))
+            .collect()
+    }
+
+    fn maybe_empty_lines(&mut self, lines: &[Line], index: usize) {
+        let line = &lines[index];
+        let (before, after) = self.empty_lines(lines, index);
+        let previous_after = self.blocks.last().map_or(0, |block| block.after);
+        let mut before = match index {
+            0 => 0,
+            _ => before as isize - previous_after as isize,
+        };
+        // Always one blank line after a module docstring.
+        if index == 1
+            && lines[0].leaves.len() == 1
+            && lines[0].is_triple_quoted_string()
+            && !(line.is_class() || line.is_def())
+        {
+            before = 1;
+        }
+        self.blocks.push(Block { before, after });
+        if line.is_comment() {
+            let after_decorator = index > 0 && lines[index - 1].is_decorator();
+            if index == 0
+                || (!after_decorator && (self.semantic_leading_comment.is_none() || before > 0))
+            {
+                self.semantic_leading_comment = Some(index);
+            }
+        } else if !line.is_decorator() {
+            self.semantic_leading_comment = None;
+        }
+    }
+
+    fn empty_lines(&mut self, lines: &[Line], index: usize) -> (usize, usize) {
+        let line = &lines[index];
+        let max_allowed = match line.depth {
+            0 => 2,
+            _ => 1,
+        };
+        let mut before = line.before.min(max_allowed);
+        let user_went_with_explicit_empty_lines = before > 0;
+        let depth = line.depth;
+        while let Some(&def_depth) = self.previous_defs.last() {
+            if def_depth < depth {
+                break;
+            }
+            before = if depth > 0 {
+                1
+            } else if def_depth > 0
+                && line.opens_block()
+                && !matches!(
+                    line.first_keyword(),
+                    "with" | "try" | "for" | "while" | "if" | "match"
+                )
+            {
+                // A clause depending on a statement with an indented definition, like
+                // the `else` of a `try` or an `if`, follows it closely.
+                1
+            } else {
+                2
+            };
+            self.previous_defs.pop();
+        }
+        let previous = match index {
+            0 => None,
+            _ => Some(&lines[index - 1]),
+        };
+        if line.is_decorator() || line.is_def() || line.is_class() {
+            if !line.is_decorator() {
+                self.previous_defs.push(depth);
+            }
+            return match previous {
+                Some(_) => self.empty_lines_for_class_or_def(
+                    lines,
+                    index,
+                    before,
+                    user_went_with_explicit_empty_lines,
+                ),
+                None => (0, 0),
+            };
+        }
+        let Some(previous) = previous else {
+            return (before, 0);
+        };
+        if previous.is_import() && !line.is_import() && depth == previous.depth {
+            return (before.max(1), 0);
+        }
+        if previous.is_class() && line.is_triple_quoted_string() {
+            return (0, 1);
+        }
+        // Blank lines may start a block, except the body of a function or a docstring.
+        if previous.opens_block() && (previous.is_def() || is_docstring_line(line)) {
+            return (0, 0);
+        }
+        (before, 0)
+    }
+
+    fn empty_lines_for_class_or_def(
+        &mut self,
+        lines: &[Line],
+        index: usize,
+        before: usize,
+        user_went_with_explicit_empty_lines: bool,
+    ) -> (usize, usize) {
+        let (line, previous) = (&lines[index], &lines[index - 1]);
+        if previous.is_decorator() {
+            return (0, 0);
+        }
+        if previous.depth < line.depth && (previous.is_class() || previous.is_def()) {
+            return (usize::from(user_went_with_explicit_empty_lines), 0);
+        }
+        let mut comment_to_add_newlines = None;
+        if previous.is_comment() && previous.depth == line.depth && before == 0 {
+            match self.semantic_leading_comment {
+                Some(comment)
+                    if comment > 0
+                        && !lines[comment - 1].is_class()
+                        && !lines[comment - 1].opens_block()
+                        && self.blocks[comment].before <= 1 =>
+                {
+                    comment_to_add_newlines = Some(comment);
+                }
+                _ => return (0, 0),
+            }
+        }
+        let mut newlines = match line.depth {
+            0 => 2,
+            _ => 1,
+        };
+        // Overloads and methods with dummy implementations may stay together.
+        if previous.is_stub_def()
+            && !user_went_with_explicit_empty_lines
+            && (line.depth > 0 || line.is_decorator() || def_name(line) == def_name(previous))
+        {
+            newlines = 0;
+        }
+        if let Some(comment) = comment_to_add_newlines {
+            let previous_after = self.blocks[comment - 1].after as isize;
+            let block = &mut self.blocks[comment];
+            block.before = block.before.max(newlines as isize) - previous_after;
+            newlines = 0;
+        }
+        (newlines, 0)
+    }
+}
+
+/// The name a definition line defines.
+fn def_name(line: &Line) -> Option<&str> {
+    let keyword = line.leaves.iter().position(|leaf| leaf.is_keyword("def"))?;
+    line.leaves.get(keyword + 1).map(|leaf| leaf.value.as_str())
+}
+
+/// Whether `line` is a plain string alone, as a docstring is.
+fn is_docstring_line(line: &Line) -> bool {
+    match line.leaves.as_slice() {
+        [leaf] if leaf.kind == Kind::String => {
+            let prefix = &leaf.value[..leaf.value.find(['\'', '"']).unwrap_or(0)];
+            !prefix.contains(['b', 'B', 'f', 'F'])
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+fn assert_formats(source: &str, expected: &str, options: &FormatOptions) {
+    let formatted = format_source(source, options).unwrap();
+    assert_eq!(formatted, expected, "formatting {:?}", source);
+    assert_eq!(format_source(expected, options).unwrap(), expected);
+}
+
+#[test]
+fn test_format() {
+    let cases = [
+        ("x  =  [1,2 ,3]\n", "x = [1, 2, 3]\n"),
+        ("print ( 'a' , b'b', F'{x}' )", "print(\"a\", b\"b\", f\"{x}\")\n"),
+        ("x = 'it\\'s'\ny = 'say \"hi\"'\n", "x = \"it's\"\ny = 'say \"hi\"'\n"),
+        ("x = 0XABCDEF + 1E5J + 0O17\n", "x = 0xABCDEF + 1e5j + 0o17\n"),
+        ("if (a):\n  pass\nwhile (b): pass\n", "if a:\n    pass\nwhile b:\n    pass\n"),
+        ("for (x, y) in z:\n    print((x))\n", "for x, y in z:\n    print((x))\n"),
+        ("x = a if b else c  # comment\n", "x = a if b else c  # comment\n"),
+        (
+            "result = some_function_name(argument_number_one, argument_number_two, argument_number_three)\n",
+            "result = some_function_name(\n    argument_number_one, argument_number_two, argument_number_three\n)\n",
+        ),
+        (
+            "foo(a,)\n",
+            "foo(\n    a,\n)\n",
+        ),
+        (
+            "def f(a,):\n    return\n",
+            "def f(\n    a,\n):\n    return\n",
+        ),
+        (
+            "from a import (b)\nfrom c import d, e\n",
+            "from a import b\nfrom c import d, e\n",
+        ),
+        (
+            "def f(\n    argument_one, argument_two, argument_three, argument_four, argument_five, argument_six\n): pass\n",
+            "def f(\n    argument_one,\n    argument_two,\n    argument_three,\n    argument_four,\n    argument_five,\n    argument_six,\n):\n    pass\n",
+        ),
+        ("x = [  # comment\n    1, 2\n]\n", "x = [1, 2]  # comment\n"),
+        (
+            "def f():\n   '''   Docstring.   '''\n",
+            "def f():\n    \"\"\"Docstring.\"\"\"\n",
+        ),
+        ("print(* args, ** kwargs)\nx = a[1 :2]\nf(a = 1)\n", "print(*args, **kwargs)\nx = a[1:2]\nf(a=1)\n"),
+        ("x = a[b+1 :]\ny = i ** 2 + x.y ** f(z)\n", "x = a[b + 1 :]\ny = i**2 + x.y ** f(z)\n"),
+        (
+            "a, b = f(['a',])\n",
+            "a, b = f(\n    [\n        \"a\",\n    ]\n)\n",
+        ),
+        (
+            "a, b = f([], ['a', 'b',], [])\n",
+            "a, b = f(\n    [],\n    [\n        \"a\",\n        \"b\",\n    ],\n    [],\n)\n",
+        ),
+        (
+            "match x:\n    case [*_]: pass\n    case y,: pass\n",
+            "match x:\n    case [*_]:\n        pass\n    case (y,):\n        pass\n",
+        ),
+        ("def f(): r'''\nDoc.'''\n", "def f():\n    r\"\"\"\n    Doc.\"\"\"\n"),
+    ];
+    for (source, expected) in cases {
+        assert_formats(source, expected, &FormatOptions::default());
+    }
+}
+
+#[test]
+fn test_format_blank_lines() {
+    let cases = [
+        (
+            "import os\ndef f(): pass\nclass C:\n\n    '''Doc.'''\n    x = 1\n    def g(self): ...\n\n\n\n    def h(self): ...\nx = 1\n",
+            "import os\n\n\ndef f():\n    pass\n\n\nclass C:\n    \"\"\"Doc.\"\"\"\n\n    x = 1\n\n    def g(self): ...\n\n    def h(self): ...\n\n\nx = 1\n",
+        ),
+        (
+            "'''Module docstring.'''\nimport os\n",
+            "\"\"\"Module docstring.\"\"\"\n\nimport os\n",
+        ),
+        ("def f():\n    \"\"\n", "def f():\n    \"\"\n"),
+        ("''\n", "\"\"\n"),
+        (
+            "@overload\ndef f(x: int) -> int: ...\n@overload\ndef f(x: str) -> str: ...\ndef f(x): return x\n",
+            "@overload\ndef f(x: int) -> int: ...\n@overload\ndef f(x: str) -> str: ...\ndef f(x):\n    return x\n",
+        ),
+        (
+            "if x:\n\n    a = 1\n\n\n\n    b = 2\n# comment\ndef f():\n\n    return\n",
+            "if x:\n\n    a = 1\n\n    b = 2\n\n\n# comment\ndef f():\n    return\n",
+        ),
+    ];
+    for (source, expected) in cases {
+        assert_formats(source, expected, &FormatOptions::default());
+    }
+}
+
+#[test]
+fn test_format_comments_and_pragmas() {
+    let cases = [
+        (
+            "x = [\n    1, 2,\n    # the rest\n    3,\n]\n",
+            "x = [\n    1,\n    2,\n    # the rest\n    3,\n]\n",
+        ),
+        (
+            "def f():\n    x = 1\n    # trailing\n#  dedented\ny = 2 #no space\n",
+            "def f():\n    x = 1\n    # trailing\n\n\n#  dedented\ny = 2  # no space\n",
+        ),
+        (
+            "# fmt: off\ncustom = [\n  1,2,\n  3,4,\n]\n# fmt: on\nother  =  1\n",
+            "# fmt: off\ncustom = [\n  1,2,\n  3,4,\n]\n# fmt: on\nother = 1\n",
+        ),
+        (
+            "a  =  [1,2]  # fmt: skip\nb  =  [1,2]\n",
+            "a  =  [1,2]  # fmt: skip\nb = [1, 2]\n",
+        ),
+        (
+            "assert x, \\\n    [1,\n     # c\n     2]\n",
+            "assert x, \\\n    [1,\n     # c\n     2]\n",
+        ),
+        (
+            "render_iterable = renderable.__rich_console__(self, _options)  # type: ignore[union-attr]\n",
+            "render_iterable = renderable.__rich_console__(self, _options)  # type: ignore[union-attr]\n",
+        ),
+    ];
+    for (source, expected) in cases {
+        assert_formats(source, expected, &FormatOptions::default());
+    }
+}
+
+#[test]
+fn test_format_options() {
+    let source = "f(a, b,)\nx = 'single'\n";
+    let options = FormatOptions {
+        magic_trailing_comma: false,
+        string_normalization: false,
+        ..FormatOptions::default()
+    };
+    assert_formats(source, "f(a, b)\nx = 'single'\n", &options);
+    let options = FormatOptions {
+        line_length: 10,
+        ..FormatOptions::default()
+    };
+    assert_formats(
+        "x = [1, 2, 3]\n",
+        "x = [\n    1,\n    2,\n    3,\n]\n",
+        &options,
+    );
+}
+
+#[test]
+fn test_format_errors() {
+    let error = format_source("def f(:\n", &FormatOptions::default()).unwrap_err();
+    assert!(matches!(error, FormatError::Syntax(_)));
+    let options = FormatOptions {
+        version: PythonVersion::Py38,
+        ..FormatOptions::default()
+    };
+    assert!(format_source("match x:\n    case 1: pass\n", &options).is_err());
+    assert_eq!(format_source("", &FormatOptions::default()).unwrap(), "");
+    assert_eq!(
+        format_source("\n\n", &FormatOptions::default()).unwrap(),
+        ""
+    );
+}
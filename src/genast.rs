@@ -1,123 +1,1550 @@
 use std::{
-    fs::File,
+    fs,
     io::{Result, Write},
+    path::Path,
 };
 
-pub fn generate_ast(output_dir: &str) {
-    define_ast(
-        output_dir,
-        "Expr",
-        &vec![
-            "BoolOp         = op: Token, values: Vec<Box<dyn Expr<T>>>",
-            "NamedExpr      = target: Box<dyn Expr<T>>, value: Box<dyn Expr<T>>",
-            "BinOp          = left: Box<dyn Expr<T>>, op: Token, right: Box<dyn Expr<T>>",
-            "UnaryOp        = op: Token, operand: Box<dyn Expr<T>>",
-            "Lambda         = args: Arguments, body: Box<dyn Expr<T>>",
-            "IfExp          = test: Box<dyn Expr<T>>, body: Box<dyn Expr<T>>, orelse: Box<dyn Expr<T>>",
-            "Dict           = keys: Vec<Box<dyn Expr<T>>>, values: Vec<Box<dyn Expr<T>>>",
-            "Set            = elts: Vec<Box<dyn Expr<T>>>",
-            "ListComp       = elt: Box<dyn Expr<T>>, generators: Vec<Comprehension>",
-            "SetComp        = elt: Box<dyn Expr<T>>, generators: Vec<Comprehension>",
-            "DictComp       = key: Box<dyn Expr<T>>, value: Box<dyn Expr<T>>, generators: Vec<Comprehension>",
-            "GeneratorExp   = key: Box<dyn Expr<T>>, value: Box<dyn Expr<T>>, generators: Vec<Comprehension>",
-            "AwaitExpr      = value: Box<dyn Expr<T>>",
-            "YieldExpr      = value: Option<Box<dyn Expr<T>>>",
-            "YieldFrom      = value: Box<dyn Expr<T>>",
-            "Compare        = left: Box<dyn Expr<T>>, ops: Vec<Token>, comparators: Vec<Box<dyn Expr>>",
-            "Call           = func: Box<dyn Expr<T>>, Args: Vec<Box<dyn Expr<T>>>, keywords: Vec<Keyword>",
-            "FormattedValue = value: Box<dyn Expr<T>>, conversion: Int, format_spec: Box<dyn Expr<T>>",
-            "JoinedStr      = values: Vec<Box<dyn Expr<T>>>",
-            "Constant       = value: Literal, kind: Option<String>",
-            "Attribute      = value: Box<dyn Expr<T>>, attr: Identifier, ctx: ExprContext",
-            "Subscript      = value: Box<dyn Expr<T>>, slice: Box<dyn Expr>, ctx: ExprContext",
-            "Starred        = value: Box<dyn Expr<T>>, ctx: ExprContext",
-            "Name           = id: Identifier, ctx: ExprContext",
-            "List           = elts: Vec<Box<dyn Expr<T>>>, ctx: ExprContext",
-            "Tuple          = elts: Vec<Box<dyn Expr<T>>>, ctx: ExprContext",
-            "Slice          = lower: Option<Box<dyn Expr<T>>>, upper: Option<Box<dyn Expr<T>>>, step: Option<Box<dyn Expr<T>>>",
-        ],
-    )
-    .expect("Failed to generate AST expressions");
-}
-
-fn define_ast(output_dir: &str, base_name: &str, types: &Vec<&str>) -> Result<()> {
-    let path: String = format!("{}/{}.rs", output_dir, base_name.to_lowercase());
-    match File::create(path) {
-        Ok(mut f) => {
-            f.write(b"use crate::types::{Literal, Token};\n\n")?;
-            define_visitor(&mut f, base_name, &types)?;
-            f.write(format!("trait {}<T> {{\n", base_name).as_bytes())?;
-            // The base accept() method.
-            f.write(b"\tfn accept(&self, visitor: Box<dyn Visitor<T>>) -> T;\n")?;
-            f.write(b"}\n\n")?;
-            for t in types {
-                let substrs: Vec<&str> = t.split("=").collect();
-                let class_name = substrs[0].trim();
-                let fields = substrs[1].trim();
-                define_type(&mut f, base_name, class_name, fields)?;
+use similar::TextDiff;
+
+/// Layout of the generated AST.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum AstMode {
+    /// Structs behind `Box<dyn Expr<T>>`, dispatched through `Visitor<T>` (`expr.rs`).
+    Trait,
+    /// Closed enums over owned, boxed children that can be matched, cloned and compared (`ast.rs`),
+    /// with their visitors and folder (`visit.rs`).
+    Enum,
+    /// Nodes stored in per-kind arenas and linked by typed ids (`arena.rs`).
+    Arena,
+}
+
+pub fn generate_ast(output_dir: &str, mode: AstMode) {
+    for (file_name, contents) in render_ast(mode) {
+        fs::write(Path::new(output_dir).join(file_name), contents)
+            .expect("Failed to write generated AST");
+    }
+}
+
+/// Generates the files of `mode` in memory, as `(file name, contents)` pairs.
+pub fn render_ast(mode: AstMode) -> Vec<(&'static str, String)> {
+    let mut files = Vec::new();
+    let mut render = |file_name, define: &dyn Fn(&mut Vec<u8>) -> Result<()>| {
+        let mut out = Vec::new();
+        define(&mut out).expect("Failed to generate AST expressions");
+        let contents = String::from_utf8(out).expect("Generated code is not UTF-8");
+        files.push((file_name, contents));
+    };
+    match mode {
+        AstMode::Trait => render("expr.rs", &|f| define_ast(f, "Expr", &expr_types())),
+        AstMode::Enum => {
+            render("ast.rs", &|f| define_enum_ast(f));
+            render("visit.rs", &|f| define_visit(f));
+        }
+        AstMode::Arena => render("arena.rs", &|f| define_arena_ast(f)),
+    }
+    files
+}
+
+/// Compares the files of `mode` with those in `output_dir`, returning a unified diff
+/// for every file that is missing or out of date.
+pub fn check_ast(output_dir: &str, mode: AstMode) -> Vec<String> {
+    render_ast(mode)
+        .into_iter()
+        .filter_map(|(file_name, expected)| {
+            let path = Path::new(output_dir).join(file_name);
+            let actual = fs::read_to_string(&path).unwrap_or_default();
+            if actual == expected {
+                return None;
             }
+            let path = path.display().to_string();
+            let diff = TextDiff::from_lines(&actual, &expected)
+                .unified_diff()
+                .header(&path, &format!("{} (generated)", path))
+                .to_string();
+            Some(diff)
+        })
+        .collect()
+}
+
+fn expr_types() -> Vec<&'static str> {
+    vec![
+        "BoolOp         = op: BoolOperator, values: Vec<Expr>",
+        "NamedExpr      = target: Expr, value: Expr",
+        "BinOp          = left: Expr, op: Operator, right: Expr",
+        "UnaryOp        = op: UnaryOperator, operand: Expr",
+        "Lambda         = args: Arguments, body: Expr",
+        "IfExp          = test: Expr, body: Expr, orelse: Expr",
+        "Dict           = keys: Vec<Option<Expr>>, values: Vec<Expr>",
+        "Set            = elts: Vec<Expr>",
+        "ListComp       = elt: Expr, generators: Vec<Comprehension>",
+        "SetComp        = elt: Expr, generators: Vec<Comprehension>",
+        "DictComp       = key: Expr, value: Expr, generators: Vec<Comprehension>",
+        "GeneratorExp   = elt: Expr, generators: Vec<Comprehension>",
+        "Await          = value: Expr",
+        "Yield          = value: Option<Expr>",
+        "YieldFrom      = value: Expr",
+        "Compare        = left: Expr, ops: Vec<CmpOperator>, comparators: Vec<Expr>",
+        "Call           = func: Expr, args: Vec<Expr>, keywords: Vec<Keyword>",
+        "FormattedValue = value: Expr, conversion: i32, format_spec: Option<Expr>",
+        "JoinedStr      = values: Vec<Expr>",
+        "Constant       = value: Literal, kind: Option<String>",
+        "Attribute      = value: Expr, attr: Identifier, ctx: ExprContext",
+        "Subscript      = value: Expr, slice: Expr, ctx: ExprContext",
+        "Starred        = value: Expr, ctx: ExprContext",
+        "Name           = id: Identifier, ctx: ExprContext",
+        "List           = elts: Vec<Expr>, ctx: ExprContext",
+        "Tuple          = elts: Vec<Expr>, ctx: ExprContext",
+        "Slice          = lower: Option<Expr>, upper: Option<Expr>, step: Option<Expr>",
+        "ErrorExpr      =",
+    ]
+}
+
+fn stmt_types() -> Vec<&'static str> {
+    vec![
+        "FunctionDef      = name: Identifier, args: Arguments, body: Vec<Stmt>, decorator_list: Vec<Expr>, returns: Option<Expr>, type_comment: Option<String>, type_params: Vec<TypeParam>",
+        "AsyncFunctionDef = name: Identifier, args: Arguments, body: Vec<Stmt>, decorator_list: Vec<Expr>, returns: Option<Expr>, type_comment: Option<String>, type_params: Vec<TypeParam>",
+        "ClassDef         = name: Identifier, bases: Vec<Expr>, keywords: Vec<Keyword>, body: Vec<Stmt>, decorator_list: Vec<Expr>, type_params: Vec<TypeParam>",
+        "Return           = value: Option<Expr>",
+        "Delete           = targets: Vec<Expr>",
+        "Assign           = targets: Vec<Expr>, value: Expr, type_comment: Option<String>",
+        "TypeAlias        = name: Expr, type_params: Vec<TypeParam>, value: Expr",
+        "AugAssign        = target: Expr, op: Operator, value: Expr",
+        "AnnAssign        = target: Expr, annotation: Expr, value: Option<Expr>, simple: i32",
+        "For              = target: Expr, iter: Expr, body: Vec<Stmt>, orelse: Vec<Stmt>, type_comment: Option<String>",
+        "AsyncFor         = target: Expr, iter: Expr, body: Vec<Stmt>, orelse: Vec<Stmt>, type_comment: Option<String>",
+        "While            = test: Expr, body: Vec<Stmt>, orelse: Vec<Stmt>",
+        "If               = test: Expr, body: Vec<Stmt>, orelse: Vec<Stmt>",
+        "With             = items: Vec<WithItem>, body: Vec<Stmt>, type_comment: Option<String>",
+        "AsyncWith        = items: Vec<WithItem>, body: Vec<Stmt>, type_comment: Option<String>",
+        "Match            = subject: Expr, cases: Vec<MatchCase>",
+        "Raise            = exc: Option<Expr>, cause: Option<Expr>",
+        "Try              = body: Vec<Stmt>, handlers: Vec<ExceptHandler>, orelse: Vec<Stmt>, finalbody: Vec<Stmt>",
+        "TryStar          = body: Vec<Stmt>, handlers: Vec<ExceptHandler>, orelse: Vec<Stmt>, finalbody: Vec<Stmt>",
+        "Assert           = test: Expr, msg: Option<Expr>",
+        "Import           = names: Vec<Alias>",
+        "ImportFrom       = module: Option<Identifier>, names: Vec<Alias>, level: i32",
+        "Global           = names: Vec<Identifier>",
+        "Nonlocal         = names: Vec<Identifier>",
+        "ExprStmt         = value: Expr",
+        "Pass             =",
+        "Break            =",
+        "Continue         =",
+        "ErrorStmt        =",
+    ]
+}
+
+fn mod_types() -> Vec<&'static str> {
+    vec![
+        "Module           = body: Vec<Stmt>, type_ignores: Vec<TypeIgnore>",
+        "Interactive      = body: Vec<Stmt>",
+        "Expression       = body: Expr",
+        "FunctionType     = argtypes: Vec<Expr>, returns: Expr",
+    ]
+}
+
+fn pattern_types() -> Vec<&'static str> {
+    vec![
+        "MatchValue       = value: Expr",
+        "MatchSingleton   = value: Literal",
+        "MatchSequence    = patterns: Vec<Pattern>",
+        "MatchMapping     = keys: Vec<Expr>, patterns: Vec<Pattern>, rest: Option<Identifier>",
+        "MatchClass       = cls: Expr, patterns: Vec<Pattern>, kwd_attrs: Vec<Identifier>, kwd_patterns: Vec<Pattern>",
+        "MatchStar        = name: Option<Identifier>",
+        "MatchAs          = pattern: Option<Pattern>, name: Option<Identifier>",
+        "MatchOr          = patterns: Vec<Pattern>",
+    ]
+}
+
+fn type_param_types() -> Vec<&'static str> {
+    vec![
+        "TypeVar          = name: Identifier, bound: Option<Expr>, default_value: Option<Expr>",
+        "ParamSpec        = name: Identifier, default_value: Option<Expr>",
+        "TypeVarTuple     = name: Identifier, default_value: Option<Expr>",
+    ]
+}
+
+/// Nodes with several constructors, generated as one enum per base name.
+fn sum_types() -> Vec<(&'static str, Vec<&'static str>)> {
+    vec![
+        ("Mod", mod_types()),
+        ("Stmt", stmt_types()),
+        ("Expr", expr_types()),
+        ("Pattern", pattern_types()),
+        ("TypeParam", type_param_types()),
+    ]
+}
+
+/// Nodes with a single constructor, generated as plain structs.
+fn product_types() -> Vec<&'static str> {
+    vec![
+        "Comprehension  = target: Expr, iter: Expr, ifs: Vec<Expr>, is_async: bool",
+        "Arguments      = posonlyargs: Vec<Arg>, args: Vec<Arg>, vararg: Option<Arg>, kwonlyargs: Vec<Arg>, kw_defaults: Vec<Option<Expr>>, kwarg: Option<Arg>, defaults: Vec<Expr>",
+        "Arg            = arg: Identifier, annotation: Option<Expr>, type_comment: Option<String>",
+        "Keyword        = arg: Option<Identifier>, value: Expr",
+        "ExceptHandler  = type: Option<Expr>, name: Option<Identifier>, body: Vec<Stmt>",
+        "Alias          = name: Identifier, asname: Option<Identifier>",
+        "WithItem       = context_expr: Expr, optional_vars: Option<Expr>",
+        "MatchCase      = pattern: Pattern, guard: Option<Expr>, body: Vec<Stmt>",
+        "TypeIgnore     = lineno: i32, tag: String",
+    ]
+}
+
+/// Field-less enums such as operators and expression contexts.
+fn simple_types() -> Vec<&'static str> {
+    vec![
+        "ExprContext    = Load | Store | Del",
+        "BoolOperator   = And | Or",
+        "Operator       = Add | Sub | Mult | MatMult | Div | Mod | Pow | LShift | RShift | BitOr | BitXor | BitAnd | FloorDiv",
+        "UnaryOperator  = Invert | Not | UAdd | USub",
+        "CmpOperator    = Eq | NotEq | Lt | LtE | Gt | GtE | Is | IsNot | In | NotIn",
+    ]
+}
+
+fn simple_type_names() -> Vec<&'static str> {
+    simple_types()
+        .into_iter()
+        .map(|t| {
+            t.split_once('=')
+                .expect("Missing '=' in type definition")
+                .0
+                .trim()
+        })
+        .collect()
+}
+
+/// Products that carry a source span, like every constructor of the sum types but `Mod`.
+const LOCATED_PRODUCTS: [&str; 4] = ["Arg", "Keyword", "ExceptHandler", "Alias"];
+
+/// Whether nodes of this class record where they appear in the source, mirroring the
+/// `attributes (int lineno, int col_offset, int? end_lineno, int? end_col_offset)` of CPython's ASDL.
+fn is_located(class_name: &str) -> bool {
+    LOCATED_PRODUCTS.contains(&class_name)
+        || sum_types()
+            .iter()
+            .filter(|(base_name, _)| *base_name != "Mod")
+            .flat_map(|(_, types)| types)
+            .any(|t| t.split_once('=').map(|(name, _)| name.trim()) == Some(class_name))
+}
+
+/// CPython class names that differ from the generated ones, which are otherwise kept for
+/// constructors of sum types and lowercased for products, e.g. `comprehension`.
+const PYTHON_NAMES: [(&str, &str); 6] = [
+    // `Expr` already names the expression enum.
+    ("ExprStmt", "Expr"),
+    // Placeholders for what error recovery skipped, which CPython has no node for.
+    ("ErrorExpr", "Error"),
+    ("ErrorStmt", "Error"),
+    ("ExceptHandler", "ExceptHandler"),
+    ("MatchCase", "match_case"),
+    ("TypeIgnore", "TypeIgnore"),
+];
+
+fn python_name(class_name: &str) -> String {
+    if let Some((_, name)) = PYTHON_NAMES.iter().find(|(name, _)| *name == class_name) {
+        return name.to_string();
+    }
+    if product_types()
+        .iter()
+        .any(|t| parse_type(t).0 == class_name)
+    {
+        return class_name.to_lowercase();
+    }
+    class_name.to_string()
+}
+
+/// Splits a `Name = field: Type, ...` definition into its name and fields, appending the
+/// `span` attribute of located nodes.
+fn parse_type(t: &str) -> (&str, Vec<(&str, &str)>) {
+    let (class_name, field_list) = t.split_once('=').expect("Missing '=' in type definition");
+    let class_name = class_name.trim();
+    let mut fields: Vec<(&str, &str)> = field_list
+        .split(", ")
+        .filter(|field| !field.trim().is_empty())
+        .map(|field| {
+            let (name, ty) = field.split_once(':').expect("Missing ':' in field");
+            (name.trim(), ty.trim())
+        })
+        .collect();
+    if is_located(class_name) {
+        fields.push(("span", "Span"));
+    }
+    (class_name, fields)
+}
+
+/// Rewrites the innermost type of a field, e.g. `Expr` in `Vec<Option<Expr>>`.
+/// The callback is also told whether that type sits inside a `Vec`.
+fn map_field_type(ty: &str, in_vec: bool, leaf: &dyn Fn(&str, bool) -> String) -> String {
+    for wrapper in ["Vec", "Option"] {
+        if let Some(inner) = strip_wrapper(ty, wrapper) {
+            let inner = map_field_type(inner, in_vec || wrapper == "Vec", leaf);
+            return format!("{}<{}>", wrapper, inner);
         }
-        Err(e) => panic!("{}", e),
+    }
+    leaf(ty, in_vec)
+}
+
+/// Returns `T` for a `wrapper<T>` field type.
+fn strip_wrapper<'a>(ty: &'a str, wrapper: &str) -> Option<&'a str> {
+    ty.strip_prefix(wrapper)
+        .and_then(|t| t.strip_prefix('<'))
+        .and_then(|t| t.strip_suffix('>'))
+}
+
+/// Returns the innermost type of a field, e.g. `Expr` for `Vec<Option<Expr>>`.
+fn leaf_type(ty: &str) -> &str {
+    match strip_wrapper(ty, "Vec").or_else(|| strip_wrapper(ty, "Option")) {
+        Some(inner) => leaf_type(inner),
+        None => ty,
+    }
+}
+
+/// Escapes names that collide with Rust keywords, e.g. `await` or `type`.
+fn ident(name: &str) -> String {
+    const KEYWORDS: [&str; 17] = [
+        "as", "async", "await", "break", "continue", "else", "for", "if", "in", "match", "mod",
+        "return", "try", "type", "use", "while", "yield",
+    ];
+    if KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn define_ast(f: &mut impl Write, base_name: &str, types: &[&str]) -> Result<()> {
+    writeln!(f, "use crate::types::{{Identifier, Literal, Span}};\n")?;
+    define_visitor(f, base_name, types)?;
+    writeln!(f, "trait {}<T> {{", base_name)?;
+    // The base accept() method.
+    writeln!(f, "\tfn accept(&self, visitor: Box<dyn Visitor<T>>) -> T;")?;
+    writeln!(f, "}}\n")?;
+    for t in types {
+        let (class_name, fields) = parse_type(t);
+        define_type(f, base_name, class_name, &fields)?;
+    }
+    Ok(())
+}
+
+fn trait_field_type(base_name: &str, ty: &str) -> String {
+    map_field_type(ty, false, &|leaf, _| {
+        if leaf == base_name {
+            format!("Box<dyn {}<T>>", leaf)
+        } else {
+            leaf.to_string()
+        }
+    })
+}
+
+fn generic_cls_name(base_name: &str, class_name: &str, fields: &[(&str, &str)]) -> String {
+    let is_generic = fields
+        .iter()
+        .any(|(_, ty)| trait_field_type(base_name, ty).contains("<T>"));
+    if is_generic {
+        format!("{}<T>", class_name)
+    } else {
+        class_name.to_string()
+    }
+}
+
+fn define_visitor(f: &mut impl Write, base_name: &str, types: &[&str]) -> Result<()> {
+    writeln!(f, "trait Visitor<T> {{")?;
+    for t in types {
+        let (class_name, fields) = parse_type(t);
+        writeln!(
+            f,
+            "\tfn visit_{}_{}(&self, {}: &{}) -> T;",
+            class_name.to_lowercase(),
+            base_name.to_lowercase(),
+            ident(&class_name.to_lowercase()),
+            generic_cls_name(base_name, class_name, &fields),
+        )?;
+    }
+    writeln!(f, "}}\n")?;
+    Ok(())
+}
+
+fn define_type(
+    f: &mut impl Write,
+    base_name: &str,
+    class_name: &str,
+    fields: &[(&str, &str)],
+) -> Result<()> {
+    let generic_cls_name = generic_cls_name(base_name, class_name, fields);
+    writeln!(f, "struct {} {{", generic_cls_name)?;
+
+    // Fields.
+    for (name, ty) in fields {
+        writeln!(f, "\t{}: {},", ident(name), trait_field_type(base_name, ty))?;
+    }
+    writeln!(f, "}}\n")?;
+
+    // Visitor pattern.
+    writeln!(f, "impl<T> {}<T> for {} {{", base_name, generic_cls_name)?;
+    writeln!(
+        f,
+        "\tfn accept(&self, visitor: Box<dyn Visitor<T>>) -> T {{"
+    )?;
+    writeln!(
+        f,
+        "\t\tvisitor.visit_{}_{}(self)",
+        class_name.to_lowercase(),
+        base_name.to_lowercase()
+    )?;
+    writeln!(f, "\t}}")?;
+    writeln!(f, "}}\n")?;
+    Ok(())
+}
+
+/// Generated derives shared by every node of the enum AST.
+const ENUM_DERIVES: &str = "#[derive(Clone, Debug, PartialEq, Eq, Hash)]";
+
+/// Derives `Serialize` and `Deserialize` behind the `serde` feature, with the given
+/// `#[serde(...)]` container attributes if any.
+fn serde_derives(attrs: &str) -> String {
+    let attrs = if attrs.is_empty() {
+        String::new()
+    } else {
+        format!(", serde({})", attrs)
     };
+    format!(
+        "#[cfg_attr(feature = \"serde\", derive(serde::Serialize, serde::Deserialize){})]",
+        attrs
+    )
+}
+
+/// Nodes are tagged with their CPython class name, e.g. `{"_type": "BinOp", ...}`.
+const SERDE_TAG: &str = "tag = \"_type\"";
+
+/// Sum types and products, the types that generated code treats as nodes.
+fn node_names() -> Vec<&'static str> {
+    let mut nodes: Vec<&str> = sum_types()
+        .iter()
+        .map(|(base_name, _)| *base_name)
+        .collect();
+    nodes.extend(product_types().iter().map(|t| parse_type(t).0));
+    nodes
+}
+
+fn define_enum_ast(f: &mut impl Write) -> Result<()> {
+    let sums = sum_types();
+    let products = product_types();
+    let nodes = node_names();
+
+    writeln!(
+        f,
+        "// Generated by `pragrat generate ast --mode enum`. Do not edit."
+    )?;
+    writeln!(f, "use crate::dump::{{Dump, DumpValue}};")?;
+    writeln!(f, "use crate::types::{{Identifier, Literal, Span}};\n")?;
+    for (base_name, types) in &sums {
+        define_enum(f, base_name, types, &serde_derives(SERDE_TAG))?;
+        define_name(f, base_name, types)?;
+        if is_located(parse_type(types[0]).0) {
+            define_span(f, base_name, types)?;
+        }
+        for t in types {
+            let (class_name, fields) = parse_type(t);
+            // The tag of the enum already names the variant.
+            define_enum_struct(f, class_name, &fields, &nodes, &serde_derives(""))?;
+            writeln!(f, "impl From<{}> for {} {{", class_name, base_name)?;
+            writeln!(f, "\tfn from(node: {}) -> Self {{", class_name)?;
+            writeln!(f, "\t\t{}::{}(node)", base_name, class_name)?;
+            writeln!(f, "\t}}")?;
+            writeln!(f, "}}\n")?;
+        }
+    }
+    for t in &products {
+        let (class_name, fields) = parse_type(t);
+        let attrs = format!("{}, rename = \"{}\"", SERDE_TAG, python_name(class_name));
+        define_enum_struct(f, class_name, &fields, &nodes, &serde_derives(&attrs))?;
+    }
+    for t in simple_types() {
+        let (enum_name, variants) = t.split_once('=').expect("Missing '=' in type definition");
+        writeln!(f, "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]")?;
+        writeln!(f, "{}", serde_derives(SERDE_TAG))?;
+        writeln!(f, "pub enum {} {{", enum_name.trim())?;
+        for variant in variants.split('|') {
+            writeln!(f, "\t{},", variant.trim())?;
+        }
+        writeln!(f, "}}\n")?;
+    }
+    define_dump(f)?;
     Ok(())
 }
 
-fn define_visitor(f: &mut File, base_name: &str, types: &Vec<&str>) -> Result<()> {
-    f.write(format!("trait Visitor<T> {{\n").as_bytes())?;
+/// Generates `name()` for a sum type, returning the CPython class name of the node.
+fn define_name(f: &mut impl Write, base_name: &str, types: &[&str]) -> Result<()> {
+    writeln!(f, "impl {} {{", base_name)?;
+    writeln!(f, "\tpub fn name(&self) -> &'static str {{")?;
+    writeln!(f, "\t\tmatch self {{")?;
     for t in types {
-        let substrs: Vec<&str> = t.split("=").collect();
-        let class_name = substrs[0].trim();
-        let fields = substrs[1].trim();
-        let is_generic = fields.contains("<T>");
-        let generic_cls_name = if is_generic {
-            format!("{}<T>", class_name)
+        let (class_name, _) = parse_type(t);
+        writeln!(
+            f,
+            "\t\t\t{}::{}(_) => \"{}\",",
+            base_name,
+            class_name,
+            python_name(class_name)
+        )?;
+    }
+    writeln!(f, "\t\t}}")?;
+    writeln!(f, "\t}}")?;
+    writeln!(f, "}}\n")?;
+    Ok(())
+}
+
+/// Generates `span()` for a sum type whose constructors are all located.
+fn define_span(f: &mut impl Write, base_name: &str, types: &[&str]) -> Result<()> {
+    writeln!(f, "impl {} {{", base_name)?;
+    writeln!(f, "\tpub fn span(&self) -> Span {{")?;
+    writeln!(f, "\t\tmatch self {{")?;
+    for t in types {
+        let (class_name, _) = parse_type(t);
+        writeln!(f, "\t\t\t{}::{}(node) => node.span,", base_name, class_name)?;
+    }
+    writeln!(f, "\t\t}}")?;
+    writeln!(f, "\t}}")?;
+    writeln!(f, "}}\n")?;
+    Ok(())
+}
+
+/// Generates the `Dump` impls that render the enum AST like Python's `ast.dump`.
+fn define_dump(f: &mut impl Write) -> Result<()> {
+    for (base_name, types) in sum_types() {
+        writeln!(f, "impl Dump for {} {{", base_name)?;
+        writeln!(f, "\tfn to_dump(&self) -> DumpValue {{")?;
+        writeln!(f, "\t\tmatch self {{")?;
+        for t in &types {
+            let (class_name, _) = parse_type(t);
+            writeln!(
+                f,
+                "\t\t\t{}::{}(node) => node.to_dump(),",
+                base_name, class_name
+            )?;
+        }
+        writeln!(f, "\t\t}}")?;
+        writeln!(f, "\t}}")?;
+        writeln!(f, "}}\n")?;
+        for t in &types {
+            let (class_name, fields) = parse_type(t);
+            define_dump_struct(f, class_name, &python_name(class_name), &fields)?;
+        }
+    }
+    for t in product_types() {
+        let (class_name, fields) = parse_type(t);
+        define_dump_struct(f, class_name, &python_name(class_name), &fields)?;
+    }
+    for t in simple_types() {
+        let (enum_name, variants) = t.split_once('=').expect("Missing '=' in type definition");
+        let enum_name = enum_name.trim();
+        writeln!(f, "impl Dump for {} {{", enum_name)?;
+        writeln!(f, "\tfn to_dump(&self) -> DumpValue {{")?;
+        writeln!(f, "\t\tlet name = match self {{")?;
+        for variant in variants.split('|') {
+            let variant = variant.trim();
+            writeln!(f, "\t\t\t{}::{} => \"{}\",", enum_name, variant, variant)?;
+        }
+        writeln!(f, "\t\t}};")?;
+        writeln!(f, "\t\tDumpValue::node(name, vec![])")?;
+        writeln!(f, "\t}}")?;
+        writeln!(f, "}}\n")?;
+    }
+    Ok(())
+}
+
+fn define_dump_struct(
+    f: &mut impl Write,
+    class_name: &str,
+    python_name: &str,
+    fields: &[(&str, &str)],
+) -> Result<()> {
+    writeln!(f, "impl Dump for {} {{", class_name)?;
+    writeln!(f, "\tfn to_dump(&self) -> DumpValue {{")?;
+    writeln!(f, "\t\tlet fields = vec![")?;
+    let mut located = false;
+    for (name, ty) in fields {
+        if *ty == "Span" {
+            located = true;
+            continue;
+        }
+        writeln!(f, "\t\t\t(\"{}\", self.{}.to_dump()),", name, ident(name))?;
+    }
+    writeln!(f, "\t\t];")?;
+    if located {
+        writeln!(
+            f,
+            "\t\tDumpValue::node(\"{}\", fields).with_span(self.span)",
+            python_name
+        )?;
+    } else {
+        writeln!(f, "\t\tDumpValue::node(\"{}\", fields)", python_name)?;
+    }
+    writeln!(f, "\t}}")?;
+    writeln!(f, "}}\n")?;
+    Ok(())
+}
+
+/// `attrs` are written after the derives, e.g. those of `serde_derives`.
+fn define_enum(f: &mut impl Write, base_name: &str, types: &[&str], attrs: &str) -> Result<()> {
+    writeln!(f, "{}", ENUM_DERIVES)?;
+    if !attrs.is_empty() {
+        writeln!(f, "{}", attrs)?;
+    }
+    writeln!(f, "pub enum {} {{", base_name)?;
+    for t in types {
+        let (class_name, _) = parse_type(t);
+        let python_name = python_name(class_name);
+        if !attrs.is_empty() && python_name != class_name {
+            writeln!(
+                f,
+                "\t#[cfg_attr(feature = \"serde\", serde(rename = \"{}\"))]",
+                python_name
+            )?;
+        }
+        writeln!(f, "\t{}({}),", class_name, class_name)?;
+    }
+    writeln!(f, "}}\n")?;
+    Ok(())
+}
+
+fn define_enum_struct(
+    f: &mut impl Write,
+    class_name: &str,
+    fields: &[(&str, &str)],
+    nodes: &[&str],
+    attrs: &str,
+) -> Result<()> {
+    writeln!(f, "{}", ENUM_DERIVES)?;
+    writeln!(f, "{}", attrs)?;
+    writeln!(f, "pub struct {} {{", class_name)?;
+    for (name, ty) in fields {
+        if *ty == "Span" {
+            // Location attributes sit next to the fields, as in `ast.dump`.
+            writeln!(f, "\t#[cfg_attr(feature = \"serde\", serde(flatten))]")?;
+        }
+        // Children held directly (or optionally) are boxed to keep the enums finite.
+        let ty = map_field_type(ty, false, &|leaf, in_vec| {
+            if !in_vec && nodes.contains(&leaf) {
+                format!("Box<{}>", leaf)
+            } else {
+                leaf.to_string()
+            }
+        });
+        writeln!(f, "\tpub {}: {},", ident(name), ty)?;
+    }
+    writeln!(f, "}}\n")?;
+    Ok(())
+}
+
+/// Generates `Visitor`, `VisitorMut` and `Fold` for the enum AST, along with the `walk_*` and
+/// `fold_*` functions implementing their default traversal.
+fn define_visit(f: &mut impl Write) -> Result<()> {
+    let sums = sum_types();
+    let products = product_types();
+    let nodes = node_names();
+
+    writeln!(
+        f,
+        "// Generated by `pragrat generate ast --mode enum`. Do not edit."
+    )?;
+    writeln!(f, "use crate::ast::*;\n")?;
+
+    // (method suffix, node type, variant of) for every node that gets a method.
+    let mut methods: Vec<(String, &str, Option<&str>)> = Vec::new();
+    for (base_name, types) in &sums {
+        methods.push((base_name.to_lowercase(), base_name, None));
+        for t in types {
+            let (class_name, _) = parse_type(t);
+            let suffix = format!("{}_{}", class_name.to_lowercase(), base_name.to_lowercase());
+            methods.push((suffix, class_name, Some(base_name)));
+        }
+    }
+    for t in &products {
+        let (class_name, _) = parse_type(t);
+        methods.push((class_name.to_lowercase(), class_name, None));
+    }
+
+    writeln!(
+        f,
+        "/// Walks a tree by reference, like Python's `ast.NodeVisitor`. Every method defaults to"
+    )?;
+    writeln!(
+        f,
+        "/// visiting the node's children, so a pass only overrides the nodes it cares about."
+    )?;
+    writeln!(f, "pub trait Visitor {{")?;
+    for (suffix, class_name, _) in &methods {
+        writeln!(
+            f,
+            "\tfn visit_{}(&mut self, node: &{}) {{",
+            suffix, class_name
+        )?;
+        writeln!(f, "\t\twalk_{}(self, node);", suffix)?;
+        writeln!(f, "\t}}")?;
+    }
+    writeln!(f, "}}\n")?;
+    writeln!(
+        f,
+        "/// Walks a tree by mutable reference so that nodes can be edited in place."
+    )?;
+    writeln!(f, "pub trait VisitorMut {{")?;
+    for (suffix, class_name, _) in &methods {
+        writeln!(
+            f,
+            "\tfn visit_{}_mut(&mut self, node: &mut {}) {{",
+            suffix, class_name
+        )?;
+        writeln!(f, "\t\twalk_{}_mut(self, node);", suffix)?;
+        writeln!(f, "\t}}")?;
+    }
+    writeln!(f, "}}\n")?;
+    writeln!(
+        f,
+        "/// Rebuilds a tree by value, like Python's `ast.NodeTransformer`. The node returned by a"
+    )?;
+    writeln!(f, "/// method replaces the one it was given.")?;
+    writeln!(f, "pub trait Fold {{")?;
+    for (suffix, class_name, base_name) in &methods {
+        let output = base_name.unwrap_or(class_name);
+        writeln!(
+            f,
+            "\tfn fold_{}(&mut self, node: {}) -> {} {{",
+            suffix, class_name, output
+        )?;
+        if base_name.is_some() {
+            writeln!(f, "\t\tfold_{}(self, node).into()", suffix)?;
         } else {
-            class_name.to_string()
-        };
-        f.write(
-            format!(
-                "\tfn visit_{}_{}(&self, {}: &{}) -> T;\n",
-                class_name.to_lowercase(),
-                base_name.to_lowercase(),
+            writeln!(f, "\t\tfold_{}(self, node)", suffix)?;
+        }
+        writeln!(f, "\t}}")?;
+    }
+    writeln!(f, "}}\n")?;
+
+    for (base_name, types) in &sums {
+        let suffix = base_name.to_lowercase();
+        for (trait_name, method, generic) in [("Visitor", "", "&"), ("VisitorMut", "_mut", "&mut ")]
+        {
+            writeln!(
+                f,
+                "pub fn walk_{}{}<V: {} + ?Sized>(visitor: &mut V, node: {}{}) {{",
+                suffix, method, trait_name, generic, base_name
+            )?;
+            writeln!(f, "\tmatch node {{")?;
+            for t in types {
+                let (class_name, _) = parse_type(t);
+                writeln!(
+                    f,
+                    "\t\t{}::{}(node) => visitor.visit_{}_{}{}(node),",
+                    base_name,
+                    class_name,
+                    class_name.to_lowercase(),
+                    suffix,
+                    method
+                )?;
+            }
+            writeln!(f, "\t}}")?;
+            writeln!(f, "}}\n")?;
+        }
+        writeln!(
+            f,
+            "pub fn fold_{}<F: Fold + ?Sized>(folder: &mut F, node: {}) -> {} {{",
+            suffix, base_name, base_name
+        )?;
+        writeln!(f, "\tmatch node {{")?;
+        for t in types {
+            let (class_name, _) = parse_type(t);
+            writeln!(
+                f,
+                "\t\t{}::{}(node) => folder.fold_{}_{}(node),",
+                base_name,
+                class_name,
                 class_name.to_lowercase(),
-                generic_cls_name,
-            )
-            .as_bytes(),
+                suffix
+            )?;
+        }
+        writeln!(f, "\t}}")?;
+        writeln!(f, "}}\n")?;
+    }
+
+    let structs = sums
+        .iter()
+        .flat_map(|(base_name, types)| types.iter().map(move |t| (Some(*base_name), *t)))
+        .chain(products.iter().map(|t| (None, *t)));
+    for (base_name, t) in structs {
+        let (class_name, fields) = parse_type(t);
+        let suffix = match base_name {
+            Some(base_name) => {
+                format!("{}_{}", class_name.to_lowercase(), base_name.to_lowercase())
+            }
+            None => class_name.to_lowercase(),
+        };
+        let children: Vec<&(&str, &str)> = fields
+            .iter()
+            .filter(|(_, ty)| nodes.contains(&leaf_type(ty)))
+            .collect();
+        for (borrow, trait_name, method, generic) in [
+            (Borrow::Shared, "Visitor", "", "&"),
+            (Borrow::Mut, "VisitorMut", "_mut", "&mut "),
+        ] {
+            if children.is_empty() {
+                writeln!(
+                    f,
+                    "pub fn walk_{}{}<V: {} + ?Sized>(_visitor: &mut V, _node: {}{}) {{}}\n",
+                    suffix, method, trait_name, generic, class_name
+                )?;
+                continue;
+            }
+            writeln!(
+                f,
+                "pub fn walk_{}{}<V: {} + ?Sized>(visitor: &mut V, node: {}{}) {{",
+                suffix, method, trait_name, generic, class_name
+            )?;
+            for (name, ty) in &children {
+                let place = format!("node.{}", ident(name));
+                let body = for_each_node(&place, false, ty, borrow, 1, &|place, is_ref, ty| {
+                    let prefix = if is_ref { "" } else { generic };
+                    format!(
+                        "visitor.visit_{}{}({}{});",
+                        ty.to_lowercase(),
+                        method,
+                        prefix,
+                        place
+                    )
+                });
+                write!(f, "{}", body)?;
+            }
+            writeln!(f, "}}\n")?;
+        }
+        let folder = if children.is_empty() {
+            "_folder"
+        } else {
+            "folder"
+        };
+        writeln!(
+            f,
+            "pub fn fold_{}<F: Fold + ?Sized>({}: &mut F, node: {}) -> {} {{",
+            suffix, folder, class_name, class_name
         )?;
+        if children.is_empty() {
+            writeln!(f, "\tnode")?;
+        } else {
+            writeln!(f, "\t{} {{", class_name)?;
+            for (name, ty) in &fields {
+                let place = format!("node.{}", ident(name));
+                writeln!(
+                    f,
+                    "\t\t{}: {},",
+                    ident(name),
+                    fold_field(&place, ty, false, &nodes)
+                )?;
+            }
+            writeln!(f, "\t}}")?;
+        }
+        writeln!(f, "}}\n")?;
     }
-    f.write(b"}\n\n")?;
     Ok(())
 }
 
-fn define_type(f: &mut File, base_name: &str, class_name: &str, field_list: &str) -> Result<()> {
-    let is_generic = field_list.contains("<T>");
-    let generic_cls_name = if is_generic {
-        format!("{}<T>", class_name)
+/// Builds the expression folding `place`, an owned field of the enum AST.
+fn fold_field(place: &str, ty: &str, in_vec: bool, nodes: &[&str]) -> String {
+    let leaf = leaf_type(ty);
+    if !nodes.contains(&leaf) {
+        place.to_string()
+    } else if let Some(inner) = strip_wrapper(ty, "Vec") {
+        let value = fold_field("x", inner, true, nodes);
+        format!("{}.into_iter().map(|x| {}).collect()", place, value)
+    } else if let Some(inner) = strip_wrapper(ty, "Option") {
+        let value = fold_field("x", inner, in_vec, nodes);
+        format!("{}.map(|x| {})", place, value)
+    } else if in_vec {
+        format!("folder.fold_{}({})", leaf.to_lowercase(), place)
     } else {
-        class_name.to_string()
-    };
+        // Directly held children are boxed, see `define_enum_struct`.
+        format!("Box::new(folder.fold_{}(*{}))", leaf.to_lowercase(), place)
+    }
+}
 
-    f.write(format!("struct {} {{\n", generic_cls_name).as_bytes())?;
+fn define_arena_ast(f: &mut impl Write) -> Result<()> {
+    let sums = sum_types();
+    let products = product_types();
+    let bases: Vec<&str> = sums.iter().map(|(base_name, _)| *base_name).collect();
+    let nodes = node_names();
 
-    // Fields.
-    let fields: Vec<&str> = field_list.split(", ").collect();
-    for field in &fields {
-        f.write(format!("\t{},\n", field).as_bytes())?;
+    writeln!(
+        f,
+        "// Generated by `pragrat generate ast --mode arena`. Do not edit."
+    )?;
+    writeln!(f, "use std::ops::Index;\n")?;
+    writeln!(
+        f,
+        "use crate::ast::{{self, {}}};",
+        simple_type_names().join(", ")
+    )?;
+    writeln!(f, "use crate::types::{{Identifier, Literal, Span}};\n")?;
+
+    writeln!(f, "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]")?;
+    writeln!(f, "pub enum NodeId {{")?;
+    for base_name in &bases {
+        writeln!(f, "\t{}({}Id),", base_name, base_name)?;
+    }
+    writeln!(f, "}}\n")?;
+    for base_name in &bases {
+        writeln!(
+            f,
+            "#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]"
+        )?;
+        writeln!(f, "pub struct {}Id(u32);\n", base_name)?;
+        writeln!(f, "impl {}Id {{", base_name)?;
+        writeln!(
+            f,
+            "\t/// Position of the node in its arena, usable to index side tables."
+        )?;
+        writeln!(f, "\tpub fn index(self) -> usize {{")?;
+        writeln!(f, "\t\tself.0 as usize")?;
+        writeln!(f, "\t}}")?;
+        writeln!(f, "}}\n")?;
+        writeln!(f, "impl From<{}Id> for NodeId {{", base_name)?;
+        writeln!(f, "\tfn from(id: {}Id) -> Self {{", base_name)?;
+        writeln!(f, "\t\tNodeId::{}(id)", base_name)?;
+        writeln!(f, "\t}}")?;
+        writeln!(f, "}}\n")?;
     }
-    f.write(b"}\n\n")?;
 
-    // Visitor pattern.
-    f.write(format!("impl<T> Expr<T> for {} {{\n", generic_cls_name).as_bytes())?;
-    f.write(b"\tfn accept(&self, visitor: Box<dyn Visitor<T>>) -> T {\n")?;
-    f.write(
-        format!(
-            "\t\treturn visitor.visit_{}_{}(self);\n",
-            class_name.to_lowercase(),
+    for (base_name, types) in &sums {
+        define_enum(f, base_name, types, "")?;
+        writeln!(f, "impl {} {{", base_name)?;
+        writeln!(f, "\t/// Calls `f` with the id of every direct child node.")?;
+        writeln!(
+            f,
+            "\tpub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {{"
+        )?;
+        writeln!(f, "\t\tmatch self {{")?;
+        for t in types {
+            let (class_name, _) = parse_type(t);
+            writeln!(
+                f,
+                "\t\t\t{}::{}(node) => node.for_each_child(f),",
+                base_name, class_name
+            )?;
+        }
+        writeln!(f, "\t\t}}")?;
+        writeln!(f, "\t}}")?;
+        writeln!(f, "}}\n")?;
+        for t in types {
+            let (class_name, fields) = parse_type(t);
+            define_arena_struct(f, class_name, &fields, &bases, &nodes)?;
+            writeln!(f, "impl From<{}> for {} {{", class_name, base_name)?;
+            writeln!(f, "\tfn from(node: {}) -> Self {{", class_name)?;
+            writeln!(f, "\t\t{}::{}(node)", base_name, class_name)?;
+            writeln!(f, "\t}}")?;
+            writeln!(f, "}}\n")?;
+        }
+    }
+    for t in &products {
+        let (class_name, fields) = parse_type(t);
+        define_arena_struct(f, class_name, &fields, &bases, &nodes)?;
+    }
+
+    // The arena itself.
+    writeln!(f, "/// Owns every node of a tree, one `Vec` per node kind.")?;
+    writeln!(f, "#[derive(Clone, Debug, Default, PartialEq, Eq)]")?;
+    writeln!(f, "pub struct Ast {{")?;
+    for base_name in &bases {
+        let name = base_name.to_lowercase();
+        writeln!(f, "\t{}s: Vec<{}>,", name, base_name)?;
+        writeln!(f, "\t{}_parents: Vec<Option<NodeId>>,", name)?;
+    }
+    writeln!(f, "}}\n")?;
+    writeln!(f, "impl Ast {{")?;
+    writeln!(
+        f,
+        "\t/// Returns the node that has `id` as a direct child, if any."
+    )?;
+    writeln!(
+        f,
+        "\tpub fn parent(&self, id: impl Into<NodeId>) -> Option<NodeId> {{"
+    )?;
+    writeln!(f, "\t\tmatch id.into() {{")?;
+    for base_name in &bases {
+        writeln!(
+            f,
+            "\t\t\tNodeId::{}(id) => self.{}_parents[id.index()],",
+            base_name,
             base_name.to_lowercase()
-        )
-        .as_bytes(),
+        )?;
+    }
+    writeln!(f, "\t\t}}")?;
+    writeln!(f, "\t}}\n")?;
+    writeln!(
+        f,
+        "\tfn set_parent(&mut self, id: NodeId, parent: NodeId) {{"
     )?;
-    f.write(b"\t}\n")?;
-    f.write(b"}\n\n")?;
+    writeln!(f, "\t\tmatch id {{")?;
+    for base_name in &bases {
+        writeln!(
+            f,
+            "\t\t\tNodeId::{}(id) => self.{}_parents[id.index()] = Some(parent),",
+            base_name,
+            base_name.to_lowercase()
+        )?;
+    }
+    writeln!(f, "\t\t}}")?;
+    writeln!(f, "\t}}\n")?;
+    for (base_name, types) in &sums {
+        let name = base_name.to_lowercase();
+        writeln!(
+            f,
+            "\t/// Adds a node whose children are already in the arena and becomes their parent."
+        )?;
+        writeln!(
+            f,
+            "\tpub fn alloc_{}(&mut self, node: impl Into<{}>) -> {}Id {{",
+            name, base_name, base_name
+        )?;
+        writeln!(f, "\t\tlet node = node.into();")?;
+        writeln!(
+            f,
+            "\t\tlet id = {}Id(u32::try_from(self.{}s.len()).expect(\"Arena is full\"));",
+            base_name, name
+        )?;
+        writeln!(
+            f,
+            "\t\tnode.for_each_child(&mut |child| self.set_parent(child, id.into()));"
+        )?;
+        writeln!(f, "\t\tself.{}s.push(node);", name)?;
+        writeln!(f, "\t\tself.{}_parents.push(None);", name)?;
+        writeln!(f, "\t\tid")?;
+        writeln!(f, "\t}}\n")?;
+        writeln!(
+            f,
+            "\tpub fn {}(&self, id: {}Id) -> &{} {{",
+            ident(&name),
+            base_name,
+            base_name
+        )?;
+        writeln!(f, "\t\t&self.{}s[id.index()]", name)?;
+        writeln!(f, "\t}}\n")?;
+        writeln!(
+            f,
+            "\t/// Children replaced through this reference keep their old parent entries."
+        )?;
+        writeln!(
+            f,
+            "\tpub fn {}_mut(&mut self, id: {}Id) -> &mut {} {{",
+            name, base_name, base_name
+        )?;
+        writeln!(f, "\t\t&mut self.{}s[id.index()]", name)?;
+        writeln!(f, "\t}}\n")?;
+        writeln!(
+            f,
+            "\tpub fn {}s(&self) -> impl ExactSizeIterator<Item = ({}Id, &{})> {{",
+            name, base_name, base_name
+        )?;
+        writeln!(
+            f,
+            "\t\tself.{}s.iter().enumerate().map(|(i, node)| ({}Id(i as u32), node))",
+            name, base_name
+        )?;
+        writeln!(f, "\t}}\n")?;
+        writeln!(
+            f,
+            "\t/// Copies a tree of the enum AST into the arena, returning the id of its root."
+        )?;
+        writeln!(
+            f,
+            "\tpub fn lower_{}(&mut self, node: &ast::{}) -> {}Id {{",
+            name, base_name, base_name
+        )?;
+        writeln!(f, "\t\tlet node = match node {{")?;
+        for t in types {
+            let (class_name, fields) = parse_type(t);
+            writeln!(
+                f,
+                "\t\t\tast::{}::{}(node) => {}::{}({} {{",
+                base_name, class_name, base_name, class_name, class_name
+            )?;
+            for (field, ty) in &fields {
+                let place = format!("node.{}", ident(field));
+                let value = lower_field(&place, true, ty, false, &nodes);
+                writeln!(f, "\t\t\t\t{}: {},", ident(field), value)?;
+            }
+            writeln!(f, "\t\t\t}}),")?;
+        }
+        writeln!(f, "\t\t}};")?;
+        writeln!(f, "\t\tself.alloc_{}(node)", name)?;
+        writeln!(f, "\t}}\n")?;
+    }
+    for t in &products {
+        let (class_name, fields) = parse_type(t);
+        writeln!(
+            f,
+            "\tfn lower_{}(&mut self, node: &ast::{}) -> {} {{",
+            class_name.to_lowercase(),
+            class_name,
+            class_name
+        )?;
+        writeln!(f, "\t\t{} {{", class_name)?;
+        for (field, ty) in &fields {
+            let place = format!("node.{}", ident(field));
+            let value = lower_field(&place, true, ty, false, &nodes);
+            writeln!(f, "\t\t\t{}: {},", ident(field), value)?;
+        }
+        writeln!(f, "\t\t}}")?;
+        writeln!(f, "\t}}\n")?;
+    }
+    writeln!(f, "}}\n")?;
+
+    for base_name in &bases {
+        writeln!(f, "impl Index<{}Id> for Ast {{", base_name)?;
+        writeln!(f, "\ttype Output = {};\n", base_name)?;
+        writeln!(
+            f,
+            "\tfn index(&self, id: {}Id) -> &{} {{",
+            base_name, base_name
+        )?;
+        writeln!(f, "\t\tself.{}(id)", ident(&base_name.to_lowercase()))?;
+        writeln!(f, "\t}}")?;
+        writeln!(f, "}}\n")?;
+    }
+    Ok(())
+}
+
+fn define_arena_struct(
+    f: &mut impl Write,
+    class_name: &str,
+    fields: &[(&str, &str)],
+    bases: &[&str],
+    nodes: &[&str],
+) -> Result<()> {
+    writeln!(f, "{}", ENUM_DERIVES)?;
+    writeln!(f, "pub struct {} {{", class_name)?;
+    for (name, ty) in fields {
+        // Products held directly are boxed to keep the enums small.
+        let ty = map_field_type(ty, false, &|leaf, in_vec| {
+            if bases.contains(&leaf) {
+                format!("{}Id", leaf)
+            } else if !in_vec && nodes.contains(&leaf) {
+                format!("Box<{}>", leaf)
+            } else {
+                leaf.to_string()
+            }
+        });
+        writeln!(f, "\tpub {}: {},", ident(name), ty)?;
+    }
+    writeln!(f, "}}\n")?;
+
+    let body: String = fields
+        .iter()
+        .filter(|(_, ty)| nodes.contains(&leaf_type(ty)))
+        .map(|(name, ty)| {
+            let place = format!("self.{}", ident(name));
+            for_each_node(
+                &place,
+                false,
+                ty,
+                Borrow::Shared,
+                2,
+                &|place, is_ref, ty| {
+                    if !bases.contains(&ty) {
+                        format!("{}.for_each_child(f);", place)
+                    } else if is_ref {
+                        format!("f(NodeId::{}(*{}));", ty, place)
+                    } else {
+                        format!("f(NodeId::{}({}));", ty, place)
+                    }
+                },
+            )
+        })
+        .collect();
+    writeln!(f, "impl {} {{", class_name)?;
+    writeln!(f, "\t/// Calls `f` with the id of every direct child node.")?;
+    if body.is_empty() {
+        writeln!(
+            f,
+            "\tpub fn for_each_child(&self, _f: &mut impl FnMut(NodeId)) {{}}"
+        )?;
+    } else {
+        writeln!(
+            f,
+            "\tpub fn for_each_child(&self, f: &mut impl FnMut(NodeId)) {{"
+        )?;
+        write!(f, "{}", body)?;
+        writeln!(f, "\t}}")?;
+    }
+    writeln!(f, "}}\n")?;
     Ok(())
 }
+
+/// How generated traversals borrow the fields they walk.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Borrow {
+    Shared,
+    Mut,
+}
+
+/// Emits statements running `leaf` on every node held in `place`, looping over `Vec`s and
+/// unwrapping `Option`s on the way. `is_ref` tells whether `place` is a reference binding
+/// rather than a field; `leaf` receives the same pair for each node, plus the node's type.
+fn for_each_node(
+    place: &str,
+    is_ref: bool,
+    ty: &str,
+    borrow: Borrow,
+    depth: usize,
+    leaf: &dyn Fn(&str, bool, &str) -> String,
+) -> String {
+    let pad = "\t".repeat(depth);
+    let prefix = match (is_ref, borrow) {
+        (true, _) => "",
+        (false, Borrow::Shared) => "&",
+        (false, Borrow::Mut) => "&mut ",
+    };
+    if let Some(inner) = strip_wrapper(ty, "Vec") {
+        let (iter, inner) = match strip_wrapper(inner, "Option") {
+            Some(inner) if borrow == Borrow::Mut => {
+                (format!("{}.iter_mut().flatten()", place), inner)
+            }
+            Some(inner) => (format!("{}.iter().flatten()", place), inner),
+            None => (format!("{}{}", prefix, place), inner),
+        };
+        let body = for_each_node("x", true, inner, borrow, depth + 1, leaf);
+        format!("{pad}for x in {} {{\n{}{pad}}}\n", iter, body)
+    } else if let Some(inner) = strip_wrapper(ty, "Option") {
+        let body = for_each_node("x", true, inner, borrow, depth + 1, leaf);
+        format!(
+            "{pad}if let Some(x) = {}{} {{\n{}{pad}}}\n",
+            prefix, place, body
+        )
+    } else {
+        format!("{pad}{}\n", leaf(place, is_ref, ty))
+    }
+}
+
+/// Field types other than the simple enums that are `Copy`.
+const COPY_TYPES: [&str; 4] = ["bool", "i32", "usize", "Span"];
+
+/// Builds the expression converting `place`, a field of the enum AST, to its arena counterpart.
+fn lower_field(place: &str, is_field: bool, ty: &str, in_vec: bool, nodes: &[&str]) -> String {
+    let leaf = leaf_type(ty);
+    if !nodes.contains(&leaf) {
+        // Fields without children are copied as is.
+        return if simple_type_names().contains(&ty) || COPY_TYPES.contains(&ty) {
+            place.to_string()
+        } else {
+            format!("{}.clone()", place)
+        };
+    }
+    if let Some(inner) = strip_wrapper(ty, "Vec") {
+        let value = lower_field("x", false, inner, true, nodes);
+        format!("{}.iter().map(|x| {}).collect()", place, value)
+    } else if let Some(inner) = strip_wrapper(ty, "Option") {
+        let value = lower_field("x", false, inner, in_vec, nodes);
+        format!("{}.as_ref().map(|x| {})", place, value)
+    } else {
+        let borrow = if is_field { "&" } else { "" };
+        let value = format!("self.lower_{}({}{})", leaf.to_lowercase(), borrow, place);
+        if in_vec || leaf_is_sum(leaf) {
+            value
+        } else {
+            format!("Box::new({})", value)
+        }
+    }
+}
+
+fn leaf_is_sum(ty: &str) -> bool {
+    sum_types().iter().any(|(base_name, _)| *base_name == ty)
+}
+
+#[test]
+fn test_enum_field_types() {
+    let mut out = Vec::new();
+    let (class_name, fields) =
+        parse_type("Dict = keys: Vec<Option<Expr>>, values: Vec<Expr>, ctx: Option<Expr>");
+    define_enum_struct(&mut out, class_name, &fields, &["Expr"], &serde_derives("")).unwrap();
+    let expected = "#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = \"serde\", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dict {
+\tpub keys: Vec<Option<Expr>>,
+\tpub values: Vec<Expr>,
+\tpub ctx: Option<Box<Expr>>,
+\t#[cfg_attr(feature = \"serde\", serde(flatten))]
+\tpub span: Span,
+}
+
+";
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+}
+
+#[test]
+fn test_arena_lowering() {
+    use crate::{arena, ast, types::Span};
+
+    let name = |id: &str| {
+        ast::Expr::Name(ast::Name {
+            id: id.to_string(),
+            ctx: ast::ExprContext::Load,
+            span: Span::default(),
+        })
+    };
+    // a + f(b)
+    let tree = ast::Expr::BinOp(ast::BinOp {
+        left: Box::new(name("a")),
+        op: ast::Operator::Add,
+        right: Box::new(ast::Expr::Call(ast::Call {
+            func: Box::new(name("f")),
+            args: vec![name("b")],
+            keywords: vec![],
+            span: Span::default(),
+        })),
+        span: Span::default(),
+    });
+    let mut arena = arena::Ast::default();
+    let root = arena.lower_expr(&tree);
+    assert_eq!(arena.exprs().len(), 5);
+    assert_eq!(arena.parent(root), None);
+    let arena::Expr::BinOp(binop) = &arena[root] else {
+        panic!("Expected a BinOp, got {:?}", arena[root]);
+    };
+    assert_eq!(arena.parent(binop.left), Some(root.into()));
+    let arena::Expr::Call(call) = &arena[binop.right] else {
+        panic!("Expected a Call, got {:?}", arena[binop.right]);
+    };
+    assert_eq!(arena.parent(call.args[0]), Some(binop.right.into()));
+}
+
+#[test]
+fn test_visitors() {
+    use crate::ast::{BinOp, Constant, Expr, ExprContext, Name, Operator};
+    use crate::types::{Literal, Span};
+    use crate::visit::{walk_name_expr_mut, Fold, Visitor, VisitorMut};
+
+    struct Names(Vec<String>);
+    impl Visitor for Names {
+        fn visit_name_expr(&mut self, node: &Name) {
+            self.0.push(node.id.clone());
+        }
+    }
+    struct Rename;
+    impl VisitorMut for Rename {
+        fn visit_name_expr_mut(&mut self, node: &mut Name) {
+            node.id.make_ascii_uppercase();
+            walk_name_expr_mut(self, node);
+        }
+    }
+    struct Inline;
+    impl Fold for Inline {
+        fn fold_name_expr(&mut self, node: Name) -> Expr {
+            Expr::Constant(Constant {
+                value: Literal::String(node.id),
+                kind: None,
+                span: node.span,
+            })
+        }
+    }
+
+    let name = |id: &str| {
+        Box::new(Expr::Name(Name {
+            id: id.to_string(),
+            ctx: ExprContext::Load,
+            span: Span::default(),
+        }))
+    };
+    // a * (b - c)
+    let mut tree = Expr::BinOp(BinOp {
+        left: name("a"),
+        op: Operator::Mult,
+        right: Box::new(Expr::BinOp(BinOp {
+            left: name("b"),
+            op: Operator::Sub,
+            right: name("c"),
+            span: Span::default(),
+        })),
+        span: Span::default(),
+    });
+    let mut names = Names(vec![]);
+    names.visit_expr(&tree);
+    assert_eq!(names.0, ["a", "b", "c"]);
+
+    Rename.visit_expr_mut(&mut tree);
+    let mut names = Names(vec![]);
+    names.visit_expr(&tree);
+    assert_eq!(names.0, ["A", "B", "C"]);
+
+    let Expr::BinOp(folded) = Inline.fold_expr(tree) else {
+        panic!("Expected the BinOp to be kept");
+    };
+    let expected = Expr::Constant(Constant {
+        value: Literal::String("A".to_string()),
+        kind: None,
+        span: Span::default(),
+    });
+    assert_eq!(*folded.left, expected);
+}
+
+#[test]
+fn test_dump() {
+    use crate::ast::{BinOp, Call, Constant, Expr, ExprContext, Keyword, Name, Operator};
+    use crate::dump::dump;
+    use crate::types::{Literal, Span};
+
+    let span = |col_offset, end_col_offset| Span {
+        lineno: 1,
+        col_offset,
+        end_lineno: 1,
+        end_col_offset,
+    };
+    let constant = |value: &str, span| {
+        Expr::Constant(Constant {
+            value: Literal::Number(value.to_string()),
+            kind: None,
+            span,
+        })
+    };
+    let name = |id: &str, span| {
+        Expr::Name(Name {
+            id: id.to_string(),
+            ctx: ExprContext::Load,
+            span,
+        })
+    };
+    // f(a, k=1) + 0x10
+    let keyword = Keyword {
+        arg: Some("k".to_string()),
+        value: Box::new(constant("1", span(7, 8))),
+        span: span(5, 8),
+    };
+    let tree = Expr::BinOp(BinOp {
+        left: Box::new(Expr::Call(Call {
+            func: Box::new(name("f", span(0, 1))),
+            args: vec![name("a", span(2, 3))],
+            keywords: vec![keyword.clone()],
+            span: span(0, 9),
+        })),
+        op: Operator::Add,
+        right: Box::new(constant("0x10", span(12, 16))),
+        span: span(0, 16),
+    });
+    // Expected outputs were produced by CPython's `ast.dump`.
+    assert_eq!(
+        dump(&tree, None, false),
+        "BinOp(left=Call(func=Name(id='f', ctx=Load()), args=[Name(id='a', ctx=Load())], \
+         keywords=[keyword(arg='k', value=Constant(value=1))]), op=Add(), right=Constant(value=16))"
+    );
+    let expected = "BinOp(
+  left=Call(
+    func=Name(id='f', ctx=Load()),
+    args=[
+      Name(id='a', ctx=Load())],
+    keywords=[
+      keyword(
+        arg='k',
+        value=Constant(value=1))]),
+  op=Add(),
+  right=Constant(value=16))";
+    assert_eq!(dump(&tree, Some(2), false), expected);
+    assert_eq!(
+        dump(&keyword, None, true),
+        "keyword(arg='k', value=Constant(value=1, lineno=1, col_offset=7, end_lineno=1, \
+         end_col_offset=8), lineno=1, col_offset=5, end_lineno=1, end_col_offset=8)"
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_json() {
+    use crate::ast::{BinOp, Constant, Expr, ExprContext, ExprStmt, Keyword, Name, Operator, Stmt};
+    use crate::types::{Literal, Span};
+
+    let span = |col_offset, end_col_offset| Span {
+        lineno: 1,
+        col_offset,
+        end_lineno: 1,
+        end_col_offset,
+    };
+    // a + 1.5
+    let tree = Expr::BinOp(BinOp {
+        left: Box::new(Expr::Name(Name {
+            id: "a".to_string(),
+            ctx: ExprContext::Load,
+            span: span(0, 1),
+        })),
+        op: Operator::Add,
+        right: Box::new(Expr::Constant(Constant {
+            value: Literal::Number("1.5".to_string()),
+            kind: None,
+            span: span(4, 7),
+        })),
+        span: span(0, 7),
+    });
+    let json = serde_json::to_value(&tree).unwrap();
+    let expected = serde_json::json!({
+        "_type": "BinOp",
+        "left": {
+            "_type": "Name",
+            "id": "a",
+            "ctx": {"_type": "Load"},
+            "lineno": 1, "col_offset": 0, "end_lineno": 1, "end_col_offset": 1,
+        },
+        "op": {"_type": "Add"},
+        "right": {
+            "_type": "Constant",
+            "value": 1.5,
+            "kind": null,
+            "lineno": 1, "col_offset": 4, "end_lineno": 1, "end_col_offset": 7,
+        },
+        "lineno": 1, "col_offset": 0, "end_lineno": 1, "end_col_offset": 7,
+    });
+    assert_eq!(json, expected);
+    assert_eq!(serde_json::from_value::<Expr>(json).unwrap(), tree);
+
+    let keyword = Keyword {
+        arg: None,
+        value: Box::new(Expr::Constant(Constant {
+            value: Literal::Number("1j".to_string()),
+            kind: None,
+            span: span(2, 4),
+        })),
+        span: span(0, 4),
+    };
+    let json = serde_json::to_value(&keyword).unwrap();
+    assert_eq!(json["_type"], "keyword");
+    assert_eq!(
+        json["value"]["value"],
+        serde_json::json!({"_type": "Number", "value": "1j"})
+    );
+    assert_eq!(serde_json::from_value::<Keyword>(json).unwrap(), keyword);
+
+    // The expression statement keeps CPython's name.
+    let stmt = Stmt::ExprStmt(ExprStmt {
+        value: Box::new(tree),
+        span: span(0, 7),
+    });
+    let json = serde_json::to_value(&stmt).unwrap();
+    assert_eq!(json["_type"], "Expr");
+    assert_eq!(serde_json::from_value::<Stmt>(json).unwrap(), stmt);
+
+    // Numbers keep their spelling, writing a plain value only when it reads back the same.
+    let source = "x = (16, 0x10, 1_000, 1.5, 0.9468822170900693, 1e5, 1e+16, 00, 1.0, 2j, 99999999999999999999, ...)\n";
+    let tree = crate::parser::parse(source, crate::parser::Mode::Exec).unwrap();
+    let json = serde_json::to_string(&tree).unwrap();
+    assert!(json.contains(r#""value":16,"#) && json.contains(r#""value":1.5,"#));
+    assert!(json.contains(r#""value":{"_type":"Number","value":"0x10"}"#));
+    assert_eq!(
+        serde_json::from_str::<crate::ast::Mod>(&json).unwrap(),
+        tree
+    );
+}
+
+#[test]
+fn test_generated_files_are_up_to_date() {
+    let src = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+    for mode in [AstMode::Trait, AstMode::Enum, AstMode::Arena] {
+        let diffs = check_ast(src, mode);
+        assert!(
+            diffs.is_empty(),
+            "Run `cargo run -- --path src generate ast --mode {:?}`:\n{}",
+            mode,
+            diffs.concat()
+        );
+    }
+}
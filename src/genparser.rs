@@ -0,0 +1,1010 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs,
+    path::Path,
+};
+
+use similar::TextDiff;
+
+use crate::tokenizer::{KEYWORDS, OPERATORS};
+
+/// Why a grammar could not be turned into a parser.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrammarError {
+    pub message: String,
+    pub line: usize,
+}
+
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Writes the parser generated from the grammar at `grammar_path` to `output_path`.
+pub fn generate_parser(grammar_path: &str, output_path: &str) -> Result<(), GrammarError> {
+    let contents = render_parser(grammar_path)?;
+    fs::write(output_path, contents).expect("Failed to write generated parser");
+    Ok(())
+}
+
+/// Generates the parser module for the grammar at `grammar_path` in memory.
+pub fn render_parser(grammar_path: &str) -> Result<String, GrammarError> {
+    let text = fs::read_to_string(grammar_path).map_err(|error| GrammarError {
+        message: format!("cannot read {}: {}", grammar_path, error),
+        line: 0,
+    })?;
+    let grammar = parse_grammar(&text)?;
+    let file_name = Path::new(grammar_path)
+        .file_name()
+        .map_or(grammar_path.into(), |name| name.to_string_lossy());
+    define_parser(&grammar, &file_name)
+}
+
+/// Compares the parser generated from `grammar_path` with `output_path`, returning a unified
+/// diff if it is missing or out of date.
+pub fn check_parser(grammar_path: &str, output_path: &str) -> Result<Option<String>, GrammarError> {
+    let expected = render_parser(grammar_path)?;
+    let actual = fs::read_to_string(output_path).unwrap_or_default();
+    if actual == expected {
+        return Ok(None);
+    }
+    let diff = TextDiff::from_lines(&actual, &expected)
+        .unified_diff()
+        .header(output_path, &format!("{} (generated)", output_path))
+        .to_string();
+    Ok(Some(diff))
+}
+
+/// A PEG grammar in the syntax of CPython's `Grammar/*.gram` files, whose actions are Rust
+/// expressions.
+#[derive(Debug)]
+struct Grammar {
+    /// Code from the `@subheader` directive, placed after the imports of the module.
+    subheader: Option<String>,
+    /// Code from the `@trailer` directive, placed at the end of the module.
+    trailer: Option<String>,
+    rules: Vec<GramRule>,
+}
+
+/// `name[Type] (memo): alternatives`
+#[derive(Debug)]
+struct GramRule {
+    name: String,
+    /// The Rust type of the result, `()` if not given.
+    ty: String,
+    memo: bool,
+    alts: Vec<Alt>,
+    line: usize,
+}
+
+#[derive(Debug)]
+struct Alt {
+    items: Vec<NamedItem>,
+    action: Option<String>,
+}
+
+#[derive(Debug)]
+struct NamedItem {
+    name: Option<String>,
+    item: Item,
+}
+
+#[derive(Debug)]
+enum Item {
+    /// A rule, or a token such as `NAME` when in upper case.
+    Name(String),
+    /// A keyword or operator such as `'def'` or `'->'`, holding its `TokenType` variant.
+    Token(String, String),
+    /// A soft keyword such as `"match"`, read as a `NAME`.
+    SoftKeyword(String),
+    Group(Vec<Alt>),
+    Optional(Box<Item>),
+    Repeat0(Box<Item>),
+    Repeat1(Box<Item>),
+    /// `sep.elem+`: one or more `elem` separated by `sep`.
+    Gather(Box<Item>, Box<Item>),
+    /// `&item` if positive, `!item` otherwise.
+    Lookahead(bool, Box<Item>),
+    Cut,
+}
+
+/// `TokenType` variants of the token names usable in grammars.
+const TOKENS: [(&str, &str); 13] = [
+    ("NAME", "Name"),
+    ("NUMBER", "Number"),
+    ("STRING", "String"),
+    ("NEWLINE", "Newline"),
+    ("INDENT", "Indent"),
+    ("DEDENT", "Dedent"),
+    ("ENDMARKER", "Endmarker"),
+    ("ASYNC", "Async"),
+    ("AWAIT", "Await"),
+    ("TYPE_COMMENT", "TypeComment"),
+    ("FSTRING_START", "FStringStart"),
+    ("FSTRING_MIDDLE", "FStringMiddle"),
+    ("FSTRING_END", "FStringEnd"),
+];
+
+fn token_type(name: &str) -> Option<&'static str> {
+    TOKENS.iter().find(|(n, _)| *n == name).map(|(_, t)| *t)
+}
+
+fn is_token_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+impl fmt::Display for GramRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.name)?;
+        write_alts(f, &self.alts)
+    }
+}
+
+fn write_alts(f: &mut fmt::Formatter<'_>, alts: &[Alt]) -> fmt::Result {
+    for (i, alt) in alts.iter().enumerate() {
+        if i > 0 {
+            write!(f, " | ")?;
+        }
+        write!(f, "{}", alt)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Alt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", item.item)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Item::Name(name) => write!(f, "{}", name),
+            Item::Token(text, _) => write!(f, "'{}'", text),
+            Item::SoftKeyword(text) => write!(f, "\"{}\"", text),
+            Item::Group(alts) => {
+                write!(f, "(")?;
+                write_alts(f, alts)?;
+                write!(f, ")")
+            }
+            Item::Optional(item) => match &**item {
+                Item::Group(alts) => {
+                    write!(f, "[")?;
+                    write_alts(f, alts)?;
+                    write!(f, "]")
+                }
+                item => write!(f, "[{}]", item),
+            },
+            Item::Repeat0(item) => write!(f, "{}*", item),
+            Item::Repeat1(item) => write!(f, "{}+", item),
+            Item::Gather(sep, elem) => write!(f, "{}.{}+", sep, elem),
+            Item::Lookahead(true, item) => write!(f, "&{}", item),
+            Item::Lookahead(false, item) => write!(f, "!{}", item),
+            Item::Cut => write!(f, "~"),
+        }
+    }
+}
+
+fn parse_grammar(text: &str) -> Result<Grammar, GrammarError> {
+    let mut parser = GrammarParser { text, pos: 0 };
+    let mut grammar = Grammar {
+        subheader: None,
+        trailer: None,
+        rules: vec![],
+    };
+    parser.skip_trivia();
+    while parser.peek().is_some() {
+        if parser.eat('@') {
+            let name = parser
+                .name()
+                .ok_or_else(|| parser.error("expected a directive"))?;
+            parser.skip_trivia();
+            let (value, _) = parser
+                .string()?
+                .ok_or_else(|| parser.error("expected a string"))?;
+            match name.as_str() {
+                "subheader" => grammar.subheader = Some(value),
+                "trailer" => grammar.trailer = Some(value),
+                _ => return Err(parser.error(&format!("unknown directive @{}", name))),
+            }
+        } else {
+            let rule = parser.rule()?;
+            if grammar.rules.iter().any(|r| r.name == rule.name) {
+                return Err(GrammarError {
+                    message: format!("rule '{}' is defined twice", rule.name),
+                    line: rule.line,
+                });
+            }
+            grammar.rules.push(rule);
+        }
+        parser.skip_trivia();
+    }
+    Ok(grammar)
+}
+
+struct GrammarParser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl GrammarParser<'_> {
+    fn error(&self, message: &str) -> GrammarError {
+        GrammarError {
+            message: message.to_string(),
+            line: self.text[..self.pos].matches('\n').count() + 1,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    /// Skips whitespace, newlines and comments.
+    fn skip_trivia(&mut self) {
+        loop {
+            let rest = &self.text[self.pos..];
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if !trimmed.starts_with('#') {
+                break;
+            }
+            self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+    }
+
+    /// Whether the next rule or directive starts here, at the start of a line.
+    fn at_rule_start(&self) -> bool {
+        self.peek().is_none() || self.pos == 0 || self.text[..self.pos].ends_with('\n')
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), GrammarError> {
+        self.skip_trivia();
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected '{}'", c))),
+        }
+    }
+
+    fn name(&mut self) -> Option<String> {
+        let rest = &self.text[self.pos..];
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            return None;
+        }
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        self.pos += len;
+        Some(rest[..len].to_string())
+    }
+
+    /// A string in single, double or triple quotes, with its quote character.
+    fn string(&mut self) -> Result<Option<(String, char)>, GrammarError> {
+        let Some(quote) = self.peek().filter(|c| *c == '\'' || *c == '"') else {
+            return Ok(None);
+        };
+        let rest = &self.text[self.pos..];
+        let delimiter = match rest.starts_with(&quote.to_string().repeat(3)) {
+            true => &rest[..3],
+            false => &rest[..1],
+        };
+        let body = &rest[delimiter.len()..];
+        let Some(len) = body.find(delimiter) else {
+            return Err(self.error("unterminated string"));
+        };
+        self.pos += 2 * delimiter.len() + len;
+        Ok(Some((body[..len].to_string(), quote)))
+    }
+
+    /// Text up to the bracket closing the one just consumed, skipping nested brackets and
+    /// Rust string literals.
+    fn balanced(&mut self, open: char, close: char) -> Result<String, GrammarError> {
+        let start = self.pos;
+        let mut depth = 0;
+        let mut chars = self.text[start..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    while let Some((_, c)) = chars.next() {
+                        match c {
+                            '\\' => {
+                                chars.next();
+                            }
+                            '"' => break,
+                            _ => {}
+                        }
+                    }
+                }
+                c if c == open => depth += 1,
+                c if c == close && depth == 0 => {
+                    self.pos = start + i + 1;
+                    return Ok(self.text[start..start + i].trim().to_string());
+                }
+                c if c == close => depth -= 1,
+                _ => {}
+            }
+        }
+        Err(self.error(&format!("'{}' was never closed", open)))
+    }
+
+    /// name[Type] (memo): alternatives
+    fn rule(&mut self) -> Result<GramRule, GrammarError> {
+        let line = self.error("").line;
+        let name = self.name().ok_or_else(|| self.error("expected a rule"))?;
+        if is_token_name(&name) {
+            return Err(self.error(&format!("rule '{}' is named like a token", name)));
+        }
+        let ty = match self.eat('[') {
+            true => self.balanced('[', ']')?,
+            false => "()".to_string(),
+        };
+        self.skip_trivia();
+        let memo = self.eat('(');
+        if memo {
+            self.skip_trivia();
+            if self.name().as_deref() != Some("memo") {
+                return Err(self.error("expected 'memo'"));
+            }
+            self.expect(')')?;
+        }
+        self.expect(':')?;
+        let alts = self.alts()?;
+        if !self.at_rule_start() {
+            return Err(self.error("expected an item"));
+        }
+        Ok(GramRule {
+            name,
+            ty,
+            memo,
+            alts,
+            line,
+        })
+    }
+
+    /// ['|'] alt ('|' alt)*
+    fn alts(&mut self) -> Result<Vec<Alt>, GrammarError> {
+        self.skip_trivia();
+        self.eat('|');
+        let mut alts = vec![self.alt()?];
+        while self.eat('|') {
+            alts.push(self.alt()?);
+        }
+        Ok(alts)
+    }
+
+    /// named_item+ ['{' action '}']
+    fn alt(&mut self) -> Result<Alt, GrammarError> {
+        let mut items = vec![];
+        loop {
+            self.skip_trivia();
+            if self.at_rule_start() || matches!(self.peek(), Some('|' | ')' | ']' | '{')) {
+                break;
+            }
+            items.push(self.named_item()?);
+        }
+        if items.is_empty() {
+            return Err(self.error("expected an item"));
+        }
+        let action = match self.eat('{') {
+            true => Some(self.balanced('{', '}')?),
+            false => None,
+        };
+        self.skip_trivia();
+        Ok(Alt { items, action })
+    }
+
+    /// [NAME '='] item
+    fn named_item(&mut self) -> Result<NamedItem, GrammarError> {
+        let start = self.pos;
+        if let Some(name) = self.name() {
+            self.skip_trivia();
+            if self.eat('=') {
+                self.skip_trivia();
+                let item = self.item()?;
+                return Ok(NamedItem {
+                    name: Some(name),
+                    item,
+                });
+            }
+            self.pos = start;
+        }
+        Ok(NamedItem {
+            name: None,
+            item: self.item()?,
+        })
+    }
+
+    /// '&' atom | '!' atom | '~' | atom ['?' | '*' | '+' | '.' atom '+']
+    fn item(&mut self) -> Result<Item, GrammarError> {
+        if self.eat('&') {
+            if self.peek() == Some('&') {
+                return Err(self.error("forced tokens '&&' are not supported"));
+            }
+            return Ok(Item::Lookahead(true, Box::new(self.atom()?)));
+        }
+        if self.eat('!') {
+            return Ok(Item::Lookahead(false, Box::new(self.atom()?)));
+        }
+        if self.eat('~') {
+            return Ok(Item::Cut);
+        }
+        let atom = self.atom()?;
+        Ok(match self.peek() {
+            Some('?') => {
+                self.pos += 1;
+                Item::Optional(Box::new(atom))
+            }
+            Some('*') => {
+                self.pos += 1;
+                Item::Repeat0(Box::new(atom))
+            }
+            Some('+') => {
+                self.pos += 1;
+                Item::Repeat1(Box::new(atom))
+            }
+            Some('.') => {
+                self.pos += 1;
+                let elem = self.atom()?;
+                if !self.eat('+') {
+                    return Err(self.error("expected '+' after a separated item"));
+                }
+                Item::Gather(Box::new(atom), Box::new(elem))
+            }
+            _ => atom,
+        })
+    }
+
+    /// NAME | STRING | '(' alts ')' | '[' alts ']'
+    fn atom(&mut self) -> Result<Item, GrammarError> {
+        if self.eat('(') {
+            let alts = self.alts()?;
+            self.expect(')')?;
+            return Ok(Item::Group(alts));
+        }
+        if self.eat('[') {
+            let alts = self.alts()?;
+            self.expect(']')?;
+            return Ok(Item::Optional(Box::new(Item::Group(alts))));
+        }
+        if let Some(name) = self.name() {
+            if is_token_name(&name) && token_type(&name).is_none() {
+                return Err(self.error(&format!("unknown token {}", name)));
+            }
+            return Ok(Item::Name(name));
+        }
+        match self.string()? {
+            Some((text, '"')) => Ok(Item::SoftKeyword(text)),
+            Some((text, _)) => match KEYWORDS.get(&*text).or_else(|| OPERATORS.get(&*text)) {
+                Some(token_type) => Ok(Item::Token(text, format!("{:?}", token_type))),
+                None => Err(self.error(&format!("unknown keyword or operator '{}'", text))),
+            },
+            None => Err(self.error("expected an item")),
+        }
+    }
+}
+
+/// Rules that can be reached from the rule itself without consuming a token, as
+/// `memo_left_rec` handles them. Every cycle of rules needs one of them, taken in grammar
+/// order; a cycle left over after removing the leader of a group of mutually left-recursive
+/// rules is an error, since nested seeds would be memoized before they stop growing.
+fn left_recursive_leaders(grammar: &Grammar) -> Result<HashSet<&str>, GrammarError> {
+    let rules: HashMap<&str, &GramRule> = grammar
+        .rules
+        .iter()
+        .map(|rule| (rule.name.as_str(), rule))
+        .collect();
+
+    let mut nullable = HashSet::new();
+    loop {
+        let before = nullable.len();
+        for rule in &grammar.rules {
+            if rule.alts.iter().any(|alt| alt_nullable(alt, &nullable)) {
+                nullable.insert(rule.name.as_str());
+            }
+        }
+        if nullable.len() == before {
+            break;
+        }
+    }
+
+    let graph: HashMap<&str, HashSet<&str>> = grammar
+        .rules
+        .iter()
+        .map(|rule| {
+            let mut callees = HashSet::new();
+            for alt in &rule.alts {
+                alt_firsts(alt, &nullable, &mut callees);
+            }
+            callees.retain(|callee| rules.contains_key(callee));
+            (rule.name.as_str(), callees)
+        })
+        .collect();
+    let reaches = |from: &str, to: &str, excluded: &str| {
+        let mut seen = HashSet::new();
+        let mut stack = vec![from];
+        while let Some(name) = stack.pop() {
+            for &callee in &graph[name] {
+                if callee == to {
+                    return true;
+                }
+                if callee != excluded && seen.insert(callee) {
+                    stack.push(callee);
+                }
+            }
+        }
+        false
+    };
+
+    let mut leaders = HashSet::new();
+    let mut grouped = HashSet::new();
+    for rule in &grammar.rules {
+        let name = rule.name.as_str();
+        if grouped.contains(name) || !reaches(name, name, "") {
+            continue;
+        }
+        let group: Vec<&str> = grammar
+            .rules
+            .iter()
+            .map(|r| r.name.as_str())
+            .filter(|&other| {
+                other == name || (reaches(name, other, "") && reaches(other, name, ""))
+            })
+            .collect();
+        for &other in &group {
+            if other != name && reaches(other, other, name) {
+                return Err(GrammarError {
+                    message: format!(
+                        "left-recursive rules {} need more than one leader",
+                        group.join(", ")
+                    ),
+                    line: rule.line,
+                });
+            }
+        }
+        grouped.extend(group);
+        leaders.insert(name);
+    }
+    Ok(leaders)
+}
+
+fn alt_nullable(alt: &Alt, nullable: &HashSet<&str>) -> bool {
+    alt.items
+        .iter()
+        .all(|item| item_nullable(&item.item, nullable))
+}
+
+fn item_nullable(item: &Item, nullable: &HashSet<&str>) -> bool {
+    match item {
+        Item::Name(name) => nullable.contains(name.as_str()),
+        Item::Token(..) | Item::SoftKeyword(_) => false,
+        Item::Group(alts) => alts.iter().any(|alt| alt_nullable(alt, nullable)),
+        Item::Optional(_) | Item::Repeat0(_) | Item::Lookahead(..) | Item::Cut => true,
+        Item::Repeat1(item) | Item::Gather(_, item) => item_nullable(item, nullable),
+    }
+}
+
+/// Adds the rules `alt` may call before consuming a token to `firsts`.
+fn alt_firsts<'a>(alt: &'a Alt, nullable: &HashSet<&str>, firsts: &mut HashSet<&'a str>) {
+    for item in &alt.items {
+        item_firsts(&item.item, nullable, firsts);
+        if !item_nullable(&item.item, nullable) {
+            break;
+        }
+    }
+}
+
+fn item_firsts<'a>(item: &'a Item, nullable: &HashSet<&str>, firsts: &mut HashSet<&'a str>) {
+    match item {
+        Item::Name(name) if !is_token_name(name) => {
+            firsts.insert(name);
+        }
+        Item::Group(alts) => {
+            for alt in alts {
+                alt_firsts(alt, nullable, firsts);
+            }
+        }
+        Item::Optional(item)
+        | Item::Repeat0(item)
+        | Item::Repeat1(item)
+        | Item::Gather(_, item)
+        | Item::Lookahead(_, item) => item_firsts(item, nullable, firsts),
+        _ => {}
+    }
+}
+
+fn define_parser(grammar: &Grammar, file_name: &str) -> Result<String, GrammarError> {
+    let leaders = left_recursive_leaders(grammar)?;
+    let mut body = String::new();
+    for (i, rule) in grammar.rules.iter().enumerate() {
+        if i > 0 {
+            body.push('\n');
+        }
+        define_rule(&mut body, rule, leaders.contains(rule.name.as_str()));
+    }
+
+    let mut out = format!(
+        "// Generated by `pragrat generate parser` from `{}`. Do not edit.\n",
+        file_name
+    );
+    if body.contains("TokenType::") {
+        out.push_str("use crate::types::TokenType;\n");
+    }
+    match body.contains("Rule::") {
+        true => out.push_str("use super::{Parser, Rule};\n"),
+        false => out.push_str("use super::Parser;\n"),
+    }
+    if let Some(subheader) = &grammar.subheader {
+        out.push_str(subheader.trim_matches('\n'));
+        out.push('\n');
+    }
+    out.push_str("\nimpl Parser {\n");
+    out.push_str(&body);
+    out.push_str("}\n");
+    if let Some(trailer) = &grammar.trailer {
+        out.push('\n');
+        out.push_str(trailer.trim_matches('\n'));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn define_rule(out: &mut String, rule: &GramRule, leader: bool) {
+    let doc = rule.to_string();
+    if doc.len() <= 92 || rule.alts.len() == 1 {
+        out.push_str(&format!("\t/// {}\n", doc));
+    } else {
+        out.push_str(&format!("\t/// {}:\n", rule.name));
+        for alt in &rule.alts {
+            out.push_str(&format!("\t///     | {}\n", alt));
+        }
+    }
+    out.push_str(&format!(
+        "\tpub(super) fn {}(&mut self) -> Option<{}> {{\n",
+        rule.name, rule.ty
+    ));
+    let unit = rule.ty == "()";
+    if leader || rule.memo {
+        let memo = if leader { "memo_left_rec" } else { "memo" };
+        out.push_str(&format!(
+            "\t\tself.{}(Rule::Generated(\"{}\"), |p| {{\n",
+            memo, rule.name
+        ));
+        out.push_str(&indent(&alts_body(&rule.alts, unit), 3));
+        out.push_str("\t\t})\n");
+    } else {
+        out.push_str("\t\tlet p = self;\n");
+        out.push_str(&indent(&alts_body(&rule.alts, unit), 2));
+    }
+    out.push_str("\t}\n");
+}
+
+/// Prefixes every line of `code` with `depth` tabs.
+fn indent(code: &str, depth: usize) -> String {
+    code.lines()
+        .map(|line| format!("{}{}\n", "\t".repeat(depth), line))
+        .collect()
+}
+
+/// Statements trying each alternative in turn with the parser `p`, ending with the result.
+/// A cut `~` in an alternative that fails returns `None` without trying the next ones.
+/// Alternatives without an action result in `()` if `unit`, or else in what their item
+/// matched, or a tuple if there are several. An alternative that is only an `invalid_` rule
+/// runs it with `Parser::invalid` in the second pass, and never matches.
+fn alts_body(alts: &[Alt], unit: bool) -> String {
+    let (last, init) = alts.split_last().expect("rules have alternatives");
+    let mut out = String::new();
+    if init.iter().any(has_cut) {
+        out.push_str("let mut cut = false;\n");
+    }
+    for alt in init {
+        if let Some(rule) = invalid_rule(alt) {
+            out.push_str(&format!("p.invalid(Self::{});\n", rule));
+            continue;
+        }
+        out.push_str(&format!(
+            "if let Some(value) = {} {{\n",
+            alt_expr(alt, unit, true)
+        ));
+        out.push_str("\treturn Some(value);\n}\n");
+        if has_cut(alt) {
+            out.push_str("if cut {\n\treturn None;\n}\n");
+        }
+    }
+    match invalid_rule(last) {
+        Some(rule) => out.push_str(&format!("p.invalid(Self::{});\nNone", rule)),
+        None => out.push_str(&alt_expr(last, unit, false)),
+    }
+    out.push('\n');
+    out
+}
+
+/// The `invalid_` rule `alt` consists of, if any.
+fn invalid_rule(alt: &Alt) -> Option<&str> {
+    match (&alt.items[..], &alt.action) {
+        (
+            [NamedItem {
+                name: None,
+                item: Item::Name(name),
+            }],
+            None,
+        ) if name.starts_with("invalid_") => Some(name),
+        _ => None,
+    }
+}
+
+fn has_cut(alt: &Alt) -> bool {
+    alt.items.iter().any(|item| matches!(item.item, Item::Cut))
+}
+
+/// An expression matching the items of `alt` in sequence and evaluating its action.
+fn alt_expr(alt: &Alt, unit: bool, cut: bool) -> String {
+    let action = alt.action.as_deref();
+    if let ([named], None, false) = (&alt.items[..], action, unit) {
+        if !matches!(named.item, Item::Cut | Item::Lookahead(..)) {
+            return attempt(&option_expr(&named.item));
+        }
+    }
+    let mut out = String::from("p.attempt(|p| {\n");
+    if action.is_some_and(|action| mentions(action, "EXTRA")) {
+        out.push_str("\tlet start = p.pos;\n");
+    }
+    let mut values = vec![];
+    for (i, named) in alt.items.iter().enumerate() {
+        if let Item::Cut = named.item {
+            if cut {
+                out.push_str("\tcut = true;\n");
+            }
+            continue;
+        }
+        let (code, fallible) = item_expr(&named.item);
+        let question = if fallible { "?" } else { "" };
+        let binding = match (action, &named.name) {
+            (Some(action), Some(name)) if mentions(action, name) => Some(name.clone()),
+            (None, _) if !unit && !matches!(named.item, Item::Lookahead(..)) => {
+                let name = named.name.clone().unwrap_or(format!("item{}", i + 1));
+                values.push(name.clone());
+                Some(name)
+            }
+            _ => None,
+        };
+        let statement = match (binding, &named.item) {
+            (None, Item::Repeat0(item)) => {
+                format!("while {}.is_some() {{}}", attempt(&option_expr(item)))
+            }
+            (None, Item::Lookahead(positive, item)) => format!(
+                "let mark = p.pos;\nlet found = {}.is_some();\np.pos = mark;\nif {}found {{\n\treturn None;\n}}",
+                option_expr(item),
+                if *positive { "!" } else { "" }
+            ),
+            (Some(name), _) => format!("let {} = {}{};", name, code, question),
+            (None, _) if fallible => format!("{}?;", code),
+            (None, _) => format!("let _ = {};", code),
+        };
+        out.push_str(&indent(&statement, 1));
+    }
+    let value = match action {
+        Some(action) => {
+            let action = dedent(&action.replace("EXTRA", "p.span_from(start)"));
+            match action.contains(';') {
+                true => format!("{{\n{}\t}}", indent(&action, 2)),
+                false => action.replace('\n', "\n\t"),
+            }
+        }
+        None if values.len() == 1 => values.remove(0),
+        None => format!("({})", values.join(", ")),
+    };
+    out.push_str(&format!("\tSome({})\n}})", value));
+    out
+}
+
+/// `action` without the indentation its lines share after the first, indented with tabs.
+fn dedent(action: &str) -> String {
+    let mut lines = action.lines();
+    let first = lines.next().unwrap_or_default().trim();
+    let rest: Vec<&str> = lines.collect();
+    let shared = rest
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let mut out = first.to_string();
+    for line in rest {
+        let line = line.get(shared..).unwrap_or_default().trim_end();
+        let text = line.trim_start_matches(' ');
+        let depth = (line.len() - text.len()) / 4;
+        out.push_str(&format!("\n{}{}", "\t".repeat(depth), text));
+    }
+    out
+}
+
+/// `p.attempt` running the `Option` expression `code`, which is returned as is if already one.
+fn attempt(code: &str) -> String {
+    match code.starts_with("p.attempt(") {
+        true => code.to_string(),
+        false => format!("p.attempt(|p| {})", code),
+    }
+}
+
+/// Whether `code` uses the identifier `name`.
+fn mentions(code: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    code.match_indices(name).any(|(i, _)| {
+        !code[..i].ends_with(is_ident) && !code[i + name.len()..].starts_with(is_ident)
+    })
+}
+
+/// An expression matching `item` with the parser `p`, and whether it is an `Option` that is
+/// `None` when the item does not match.
+fn item_expr(item: &Item) -> (String, bool) {
+    let token = |condition: String| format!("p.advance_if(|token| {}).cloned()", condition);
+    match item {
+        Item::Name(name) => match token_type(name) {
+            Some(ty) => (format!("p.token(TokenType::{})", ty), true),
+            None => (format!("p.{}()", name), true),
+        },
+        Item::Token(_, ty) => (format!("p.token(TokenType::{})", ty), true),
+        Item::SoftKeyword(text) => (
+            token(format!(
+                "token.token_type == TokenType::Name && token.value == {:?}",
+                text
+            )),
+            true,
+        ),
+        Item::Group(alts) => match &alts[..] {
+            [Alt {
+                items,
+                action: None,
+            }] if items.len() == 1 && items[0].name.is_none() => item_expr(&items[0].item),
+            _ => (
+                format!(
+                    "p.attempt(|p| {{\n{}}})",
+                    indent(&alts_body(alts, false), 1)
+                ),
+                true,
+            ),
+        },
+        Item::Optional(item) => (attempt(&option_expr(item)), false),
+        Item::Repeat0(inner) | Item::Repeat1(inner) => {
+            let repeat1 = matches!(item, Item::Repeat1(_));
+            let mut out = String::from("{\n\tlet mut items = vec![];\n");
+            out.push_str(&format!(
+                "\twhile let Some(item) = {} {{\n",
+                attempt(&option_expr(inner)).replace('\n', "\n\t")
+            ));
+            out.push_str("\t\titems.push(item);\n\t}\n");
+            match repeat1 {
+                true => out.push_str("\t(!items.is_empty()).then_some(items)\n}"),
+                false => out.push_str("\titems\n}"),
+            }
+            (out, repeat1)
+        }
+        Item::Gather(sep, elem) => {
+            let (sep, fallible) = item_expr(sep);
+            let sep = match fallible {
+                true => format!("{}?;", sep),
+                false => format!("let _ = {};", sep),
+            };
+            let mut out = String::from("p.attempt(|p| {\n");
+            out.push_str(&format!(
+                "\tlet mut items = vec![{}?];\n",
+                option_expr(elem).replace('\n', "\n\t")
+            ));
+            out.push_str("\twhile let Some(item) = p.attempt(|p| {\n");
+            out.push_str(&indent(&sep, 2));
+            out.push_str(&indent(&option_expr(elem), 2));
+            out.push_str("\t}) {\n\t\titems.push(item);\n\t}\n\tSome(items)\n})");
+            (out, true)
+        }
+        Item::Lookahead(positive, item) => {
+            let mut out = String::from("{\n\tlet mark = p.pos;\n");
+            out.push_str(&format!(
+                "\tlet found = {}.is_some();\n",
+                option_expr(item).replace('\n', "\n\t")
+            ));
+            out.push_str("\tp.pos = mark;\n");
+            match positive {
+                true => out.push_str("\tfound.then_some(())\n}"),
+                false => out.push_str("\t(!found).then_some(())\n}"),
+            }
+            (out, true)
+        }
+        Item::Cut => unreachable!("cuts are not expressions"),
+    }
+}
+
+/// `item_expr` as an `Option`.
+fn option_expr(item: &Item) -> String {
+    match item_expr(item) {
+        (code, true) => code,
+        (code, false) => format!("Some({})", code),
+    }
+}
+
+#[test]
+fn test_parse_grammar() {
+    let grammar = parse_grammar(
+        "# comment\nstart[Vec<Expr>]: a=item* ENDMARKER { a }\nitem[Expr] (memo):\n    | &'(' ~ b=group { b }\n    | !\"match\" [','.NAME+ ','] (NUMBER | STRING)+\n",
+    )
+    .unwrap();
+    let rules: Vec<String> = grammar.rules.iter().map(|rule| rule.to_string()).collect();
+    assert_eq!(
+        rules,
+        [
+            "start: item* ENDMARKER",
+            "item: &'(' ~ group | !\"match\" [','.NAME+ ','] (NUMBER | STRING)+",
+        ]
+    );
+    assert_eq!(grammar.rules[0].ty, "Vec<Expr>");
+    assert_eq!(grammar.rules[0].alts[0].action.as_deref(), Some("a"));
+    assert!(grammar.rules[1].memo);
+    assert_eq!(grammar.rules[1].ty, "Expr");
+
+    let error = |text| parse_grammar(text).unwrap_err().to_string();
+    assert_eq!(
+        error("a: 'if'\nc: '=>'\n"),
+        "line 2: unknown keyword or operator '=>'"
+    );
+    assert_eq!(error("a: NAMES\n"), "line 1: unknown token NAMES");
+    assert_eq!(error("a: b\na: c\n"), "line 2: rule 'a' is defined twice");
+    assert_eq!(error("a: (b | c\n"), "line 2: expected ')'");
+    assert_eq!(error("a: b { c\n"), "line 1: '{' was never closed");
+}
+
+#[test]
+fn test_left_recursive_leaders() {
+    let leaders = |text| {
+        let grammar = parse_grammar(text).unwrap();
+        let mut leaders: Vec<String> = left_recursive_leaders(&grammar)
+            .map_err(|error| error.to_string())?
+            .into_iter()
+            .map(String::from)
+            .collect();
+        leaders.sort();
+        Ok::<_, String>(leaders)
+    };
+    assert_eq!(
+        leaders("sum: sum '+' term | term\nterm: NAME\n").unwrap(),
+        ["sum"]
+    );
+    // Reached through a nullable item, and through another rule.
+    assert_eq!(
+        leaders("a: ['.'] a '+' | '-'\nb: c '(' ')' | NAME\nc: b\n").unwrap(),
+        ["a", "b"]
+    );
+    assert_eq!(leaders("a: '.' a | '+'\n").unwrap(), Vec::<String>::new());
+    assert_eq!(
+        leaders("a: b | c '.'\nb: c | a '+'\nc: a | b '-'\n").unwrap_err(),
+        "line 1: left-recursive rules a, b, c need more than one leader"
+    );
+}
+
+#[test]
+fn test_generated_parsers_are_up_to_date() {
+    let parser = concat!(env!("CARGO_MANIFEST_DIR"), "/src/parser");
+    for name in ["func_type", "pattern", "test_grammar", "type_params"] {
+        let grammar = format!("{}/{}.gram", parser, name);
+        let diff = check_parser(&grammar, &format!("{}/{}.rs", parser, name)).unwrap();
+        assert!(
+            diff.is_none(),
+            "Run `cargo run -- generate parser src/parser/{}.gram`:\n{}",
+            name,
+            diff.unwrap_or_default()
+        );
+    }
+}
@@ -1,4 +1,22 @@
+#[rustfmt::skip]
+pub mod arena;
+#[rustfmt::skip]
+pub mod ast;
 // mod expr;
+pub mod check;
+pub mod cst;
+pub mod dump;
+pub mod files;
+pub mod format;
 pub mod genast;
-mod tokenizer;
+pub mod genparser;
+pub mod parser;
+pub mod symtable;
+pub mod tokenize;
+pub mod tokenizer;
 pub mod types;
+#[rustfmt::skip]
+mod unicode;
+pub mod unparse;
+#[rustfmt::skip]
+pub mod visit;
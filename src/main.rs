@@ -1,10 +1,46 @@
+use std::{fs, io::Read, path::Path, process::ExitCode};
+
 use clap::{Parser, Subcommand};
-use pragrat::genast::generate_ast;
+use pragrat::check::{check_files, find_sources, render_error as render_syntax_error, Sources};
+use pragrat::dump::{dump, dump_sexpr, DumpFormat};
+use pragrat::files::ParseOptions;
+use pragrat::format::{format_source, FormatOptions};
+use pragrat::genast::{check_ast, generate_ast, render_ast, AstMode};
+use pragrat::genparser::{check_parser, generate_parser, render_parser};
+use pragrat::parser::{parse_with_version, Mode};
+use pragrat::tokenize::{is_trivia, render_error, render_tokens, TokenFormat};
+use pragrat::tokenizer::Tokenizer;
+use pragrat::types::PythonVersion;
+use similar::TextDiff;
 
 #[derive(Subcommand, Debug)]
 enum Generate {
     /// Generate modules used to parse the AST in pragrat.
-    Ast,
+    Ast {
+        /// Layout of the generated nodes.
+        #[arg(long, value_enum, default_value_t = AstMode::Trait)]
+        mode: AstMode,
+        /// Fail with a diff instead of writing if the files on disk are out of date.
+        #[arg(long, conflicts_with = "stdout")]
+        check: bool,
+        /// Print the generated code instead of writing it.
+        #[arg(long)]
+        stdout: bool,
+    },
+    /// Generate a packrat parser module from a grammar in the syntax of CPython's `.gram` files.
+    Parser {
+        /// Path of the grammar.
+        grammar: String,
+        /// Path of the generated module, the grammar's with an `.rs` extension by default.
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Fail with a diff instead of writing if the module on disk is out of date.
+        #[arg(long, conflicts_with = "stdout")]
+        check: bool,
+        /// Print the generated code instead of writing it.
+        #[arg(long)]
+        stdout: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -13,25 +49,420 @@ enum Commands {
         #[clap(subcommand)]
         commmand: Generate,
     },
+    /// Print the tokens of Python files, failing if any could not be tokenized.
+    Tokenize {
+        /// Paths of the files, or `-` for standard input.
+        #[arg(required = true)]
+        files: Vec<String>,
+        /// Layout of the listing.
+        #[arg(long, value_enum, default_value_t = TokenFormat::Table)]
+        format: TokenFormat,
+        /// Also print comments and the newlines that do not end a logical line.
+        #[arg(long)]
+        include_trivia: bool,
+    },
+    /// Check the syntax of the `.py` and `.pyi` files under `--path`, skipping those ignored by
+    /// `.gitignore` files.
+    Check {
+        /// Only check files matching one of these globs, relative to `--path`.
+        #[arg(long)]
+        include: Vec<String>,
+        /// Skip files and directories matching one of these globs, relative to `--path`.
+        #[arg(long)]
+        exclude: Vec<String>,
+        /// Reject syntax added after this Python version.
+        #[arg(long, value_enum, default_value_t = PythonVersion::default())]
+        target_version: PythonVersion,
+        /// Number of threads parsing, one per core by default.
+        #[arg(short, long)]
+        jobs: Option<usize>,
+        /// Print the throughput and the slowest files.
+        #[arg(long)]
+        stats: bool,
+    },
+    /// Parse Python files and print their AST, failing on syntax errors.
+    #[command(alias = "dump")]
+    Parse {
+        /// Paths of the files, or `-` for standard input.
+        #[arg(required = true)]
+        files: Vec<String>,
+        /// Layout of the AST. JSON requires the `serde` feature.
+        #[arg(long, value_enum, default_value_t = DumpFormat::Text)]
+        format: DumpFormat,
+        /// Break nested nodes over lines indented by this many spaces.
+        #[arg(long)]
+        indent: Option<usize>,
+        /// What the files hold, as with `ast.parse`.
+        #[arg(long, value_enum, default_value_t = Mode::Exec)]
+        mode: Mode,
+        /// Reject syntax added after this Python version.
+        #[arg(long, value_enum, default_value_t = PythonVersion::default())]
+        target_version: PythonVersion,
+        /// Also print the line and column attributes of nodes.
+        #[arg(long)]
+        include_attributes: bool,
+    },
+    /// Format Python files in the style of Black, rewriting them in place.
+    Fmt {
+        /// Paths of files, directories searched like `check` does, or `-` for standard input.
+        #[arg(required = true)]
+        paths: Vec<String>,
+        /// Only list the files that would be reformatted, failing if there are any.
+        #[arg(long)]
+        check: bool,
+        /// Print a diff of the changes instead of writing them.
+        #[arg(long)]
+        diff: bool,
+        /// Width that lines are split to fit in.
+        #[arg(short, long, default_value_t = 88)]
+        line_length: usize,
+        /// Reject syntax added after this Python version.
+        #[arg(long, value_enum, default_value_t = PythonVersion::default())]
+        target_version: PythonVersion,
+        /// Remove trailing commas where the brackets fit on a line, rather than exploding them.
+        #[arg(long)]
+        skip_magic_trailing_comma: bool,
+        /// Leave the prefixes and quotes of strings as they are.
+        #[arg(long)]
+        skip_string_normalization: bool,
+    },
 }
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Path of source code directory
-    #[arg(short, long, default_value = ".")]
+    /// Directory written by `generate ast`, or checked by `check`.
+    #[arg(short, long, default_value = ".", global = true)]
     path: String,
 
     #[clap(subcommand)]
     command: Commands,
 }
 
-fn main() {
+fn main() -> ExitCode {
     let args = Args::parse();
     match args.command {
         Commands::Generate { ref commmand } => match commmand {
-            Generate::Ast => generate_ast(&args.path),
+            Generate::Ast {
+                mode, check: true, ..
+            } => {
+                let diffs = check_ast(&args.path, *mode);
+                for diff in &diffs {
+                    print!("{}", diff);
+                }
+                if !diffs.is_empty() {
+                    return ExitCode::FAILURE;
+                }
+            }
+            Generate::Ast {
+                mode, stdout: true, ..
+            } => {
+                for (_, contents) in render_ast(*mode) {
+                    print!("{}", contents);
+                }
+            }
+            Generate::Ast { mode, .. } => generate_ast(&args.path, *mode),
+            Generate::Parser {
+                grammar,
+                output,
+                check,
+                stdout,
+            } => {
+                let output = output.clone().unwrap_or_else(|| {
+                    Path::new(grammar)
+                        .with_extension("rs")
+                        .display()
+                        .to_string()
+                });
+                let result = match (check, stdout) {
+                    (true, _) => check_parser(grammar, &output).map(|diff| match diff {
+                        Some(diff) => {
+                            print!("{}", diff);
+                            ExitCode::FAILURE
+                        }
+                        None => ExitCode::SUCCESS,
+                    }),
+                    (_, true) => render_parser(grammar).map(|contents| {
+                        print!("{}", contents);
+                        ExitCode::SUCCESS
+                    }),
+                    _ => generate_parser(grammar, &output).map(|()| ExitCode::SUCCESS),
+                };
+                return result.unwrap_or_else(|error| {
+                    eprintln!("{}: {}", grammar, error);
+                    ExitCode::FAILURE
+                });
+            }
         },
+        Commands::Tokenize {
+            ref files,
+            format,
+            include_trivia,
+        } => return tokenize(files, format, include_trivia),
+        Commands::Check {
+            ref include,
+            ref exclude,
+            target_version,
+            jobs,
+            stats,
+        } => {
+            let options = ParseOptions {
+                version: target_version,
+                threads: jobs,
+                ..ParseOptions::default()
+            };
+            return check(Path::new(&args.path), include, exclude, &options, stats);
+        }
+        Commands::Parse {
+            ref files,
+            format,
+            indent,
+            mode,
+            target_version,
+            include_attributes,
+        } => {
+            let mut code = ExitCode::SUCCESS;
+            for file in files {
+                let Some(source) = read_source(file) else {
+                    code = ExitCode::FAILURE;
+                    continue;
+                };
+                let tree = match parse_with_version(&source, mode, target_version) {
+                    Ok(tree) => tree,
+                    Err(error) => {
+                        eprintln!("{}", error.render(file, &source));
+                        code = ExitCode::FAILURE;
+                        continue;
+                    }
+                };
+                if files.len() > 1 && format != DumpFormat::Json {
+                    println!("==> {} <==", file);
+                }
+                match format {
+                    DumpFormat::Text => println!("{}", dump(&tree, indent, include_attributes)),
+                    DumpFormat::Sexpr => {
+                        println!("{}", dump_sexpr(&tree, indent, include_attributes))
+                    }
+                    #[cfg(feature = "serde")]
+                    DumpFormat::Json => println!(
+                        "{}",
+                        pragrat::dump::dump_json(&tree, indent, include_attributes)
+                    ),
+                    #[cfg(not(feature = "serde"))]
+                    DumpFormat::Json => {
+                        eprintln!("JSON output requires building with the `serde` feature");
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+            return code;
+        }
+        Commands::Fmt {
+            ref paths,
+            check,
+            diff,
+            line_length,
+            target_version,
+            skip_magic_trailing_comma,
+            skip_string_normalization,
+        } => {
+            let options = FormatOptions {
+                line_length,
+                version: target_version,
+                magic_trailing_comma: !skip_magic_trailing_comma,
+                string_normalization: !skip_string_normalization,
+            };
+            return format(paths, check, diff, &options);
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn check(
+    root: &Path,
+    include: &[String],
+    exclude: &[String],
+    options: &ParseOptions,
+    print_stats: bool,
+) -> ExitCode {
+    if !root.exists() {
+        eprintln!("{}: No such file or directory", root.display());
+        return ExitCode::FAILURE;
+    }
+    let Sources { paths, errors } = match find_sources(root, include, exclude) {
+        Ok(sources) => sources,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let (mut failed, stats) = check_files(&paths, options);
+    // Paths that could not be walked fail like files that could not be read.
+    failed.extend(errors.into_iter().map(|(path, error)| (path, vec![error])));
+    failed.sort_by(|a, b| a.0.cmp(&b.0));
+    for (path, errors) in &failed {
+        for error in errors {
+            println!("{}", render_syntax_error(path, error));
+        }
+    }
+    if print_stats {
+        eprint!("{}", stats);
+    }
+    let count = failed.iter().map(|(_, errors)| errors.len()).sum();
+    let plural = |n: usize, noun: &str| format!("{} {}{}", n, noun, if n == 1 { "" } else { "s" });
+    if failed.is_empty() && paths.is_empty() {
+        println!("No Python files found");
+        return ExitCode::SUCCESS;
+    }
+    if failed.is_empty() {
+        println!("Checked {}, no errors", plural(paths.len(), "file"));
+        return ExitCode::SUCCESS;
+    }
+    println!(
+        "Found {} in {} (checked {})",
+        plural(count, "error"),
+        plural(failed.len(), "file"),
+        plural(paths.len(), "file")
+    );
+    ExitCode::FAILURE
+}
+
+fn format(paths: &[String], check: bool, diff: bool, options: &FormatOptions) -> ExitCode {
+    let mut files = vec![];
+    let mut unwalked = 0;
+    for path in paths {
+        if path == "-" || !Path::new(path).is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        match find_sources(Path::new(path), &[], &[]) {
+            Ok(sources) => {
+                for (path, error) in &sources.errors {
+                    eprintln!("{}", render_syntax_error(path, error));
+                }
+                unwalked += sources.errors.len();
+                files.extend(sources.paths.iter().map(|path| path.display().to_string()));
+            }
+            Err(error) => {
+                eprintln!("{}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let (mut changed, mut unchanged, mut failed) = (0, 0, unwalked);
+    for file in &files {
+        let Some(source) = read_source(file) else {
+            failed += 1;
+            continue;
+        };
+        let formatted = match format_source(&source, options) {
+            Ok(formatted) => formatted,
+            Err(error) => {
+                eprintln!("error: cannot format {}: {}", file, error);
+                failed += 1;
+                continue;
+            }
+        };
+        if formatted == source {
+            unchanged += 1;
+            if file == "-" && !check && !diff {
+                print!("{}", formatted);
+            }
+            continue;
+        }
+        changed += 1;
+        if diff {
+            print!(
+                "{}",
+                TextDiff::from_lines(&source, &formatted)
+                    .unified_diff()
+                    .header(file, &format!("{} (formatted)", file))
+            );
+        }
+        if check || diff {
+            eprintln!("would reformat {}", file);
+        } else if file == "-" {
+            print!("{}", formatted);
+        } else if let Err(error) = fs::write(file, &formatted) {
+            eprintln!("error: cannot write {}: {}", file, error);
+            failed += 1;
+        } else {
+            eprintln!("reformatted {}", file);
+        }
+    }
+    let plural = |n: usize| format!("{} file{}", n, if n == 1 { "" } else { "s" });
+    let (done, left) = match check || diff {
+        true => ("would be reformatted", "would be left unchanged"),
+        false => ("reformatted", "left unchanged"),
+    };
+    let mut summary = vec![];
+    if changed > 0 {
+        summary.push(format!("{} {}", plural(changed), done));
+    }
+    if unchanged > 0 {
+        summary.push(format!("{} {}", plural(unchanged), left));
+    }
+    if failed > 0 {
+        summary.push(format!("{} failed to reformat", plural(failed)));
+    }
+    if summary.is_empty() {
+        summary.push("No Python files found".to_string());
+    }
+    eprintln!("{}.", summary.join(", "));
+    match failed > 0 || (check && changed > 0) {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    }
+}
+
+/// Reads `file`, or standard input for `-`, reporting why it could not be read.
+fn read_source(file: &str) -> Option<String> {
+    let source = if file == "-" {
+        let mut source = String::new();
+        std::io::stdin().read_to_string(&mut source).map(|_| source)
+    } else {
+        fs::read_to_string(file)
+    };
+    source
+        .map_err(|error| eprintln!("{}: {}", file, error))
+        .ok()
+}
+
+fn tokenize(files: &[String], format: TokenFormat, include_trivia: bool) -> ExitCode {
+    let mut code = ExitCode::SUCCESS;
+    for file in files {
+        let Some(source) = read_source(file) else {
+            code = ExitCode::FAILURE;
+            continue;
+        };
+        let mut tokenizer = Tokenizer::new(source.clone());
+        tokenizer.scan_tokens();
+        for error in tokenizer.errors() {
+            eprintln!("{}", render_error(file, error));
+            code = ExitCode::FAILURE;
+        }
+        let tokens: Vec<_> = tokenizer
+            .tokens()
+            .iter()
+            .filter(|token| include_trivia || !is_trivia(&token.token_type))
+            .cloned()
+            .collect();
+        if files.len() > 1 && format != TokenFormat::Json {
+            println!("==> {} <==", file);
+        }
+        print!("{}", render_tokens(file, &source, &tokens, format));
+    }
+    code
+}
+
+#[test]
+fn test_args() {
+    for args in [
+        ["pragrat", "check", "--path", "src"],
+        ["pragrat", "--path", "src", "check"],
+    ] {
+        let args = Args::try_parse_from(args).unwrap();
+        assert_eq!(args.path, "src");
+        assert!(matches!(args.command, Commands::Check { .. }));
     }
-    dbg!("{}", args);
 }
@@ -0,0 +1,975 @@
+//! Syntax errors, and the `invalid_` rules of `Grammar/python.gram` that explain them. Like
+//! CPython, the parser only runs these rules in a second pass over source it failed to parse,
+//! where the first of them to match raises its error. Each returns the error it raises, or
+//! `None` if it does not match, and is run through `invalid`.
+
+use std::fmt;
+
+use super::Parser;
+use crate::ast::*;
+use crate::types::{ErrorKind, Literal, PythonVersion, Span, Token, TokenType};
+
+/// Why the source could not be parsed, worded like CPython's `SyntaxError`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub message: String,
+    /// A suggestion following the message, such as "Perhaps you forgot a comma?".
+    pub hint: Option<String>,
+    pub span: Span,
+    /// Whether it is raised as a `SyntaxError` or as one of its subclasses.
+    pub kind: ErrorKind,
+}
+
+impl SyntaxError {
+    pub fn new(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            hint: None,
+            span,
+            kind: ErrorKind::Syntax,
+        }
+    }
+
+    /// An `IndentationError`, such as "unexpected indent".
+    pub fn indentation(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            kind: ErrorKind::Indentation,
+            ..Self::new(message, span)
+        }
+    }
+
+    /// The error at `token`, the furthest one reached, when no invalid rule raised one.
+    /// Like CPython, an unexpected indent is reported at its last character.
+    pub(super) fn at_token(token: &Token) -> Self {
+        match token.token_type {
+            TokenType::Indent => {
+                let col_offset = token.span.end_col_offset.saturating_sub(1);
+                let span = Span {
+                    col_offset,
+                    ..token.span
+                };
+                Self::indentation("unexpected indent", span)
+            }
+            TokenType::Dedent => Self::indentation("unexpected unindent", token.span),
+            _ => Self::new("invalid syntax", token.span),
+        }
+    }
+
+    pub fn with_hint(self, hint: impl Into<String>) -> Self {
+        Self {
+            hint: Some(hint.into()),
+            ..self
+        }
+    }
+
+    /// Renders the error the way CPython reports it, quoting the line of `source` it is on
+    /// with its span underlined:
+    ///
+    /// ```text
+    ///   File "example.py", line 1
+    ///     f(a b)
+    ///       ^^^
+    /// SyntaxError: invalid syntax. Perhaps you forgot a comma?
+    /// ```
+    pub fn render(&self, filename: &str, source: &str) -> String {
+        let mut rendered = format!("  File \"{}\", line {}\n", filename, self.span.lineno);
+        if let Some(line) = source.lines().nth(self.span.lineno.saturating_sub(1)) {
+            let line = line.trim_end();
+            let indent = line.len() - line.trim_start().len();
+            // Offsets count bytes, while carets line up with characters.
+            let column = |offset: usize| {
+                line[indent..]
+                    .char_indices()
+                    .take_while(|&(i, _)| indent + i < offset)
+                    .count()
+            };
+            let start = column(self.span.col_offset);
+            let end = match self.span.end_lineno == self.span.lineno {
+                true => column(self.span.end_col_offset),
+                false => column(line.len()),
+            };
+            rendered.push_str(&format!(
+                "    {}\n    {}{}\n",
+                &line[indent..],
+                " ".repeat(start),
+                "^".repeat(end.max(start + 1) - start)
+            ));
+        }
+        rendered.push_str(&format!("{}: {}", self.kind, self));
+        rendered
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        match &self.hint {
+            Some(hint) => write!(f, ". {}", hint),
+            None => Ok(()),
+        }
+    }
+}
+
+/// How a target is bound, which decides what is invalid in it and how that is worded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Targets {
+    Star,
+    Del,
+    For,
+}
+
+impl Parser {
+    /// Runs the invalid rule `rule` in the second pass, raising the error it returns unless
+    /// one was raised already. The position is restored, as invalid rules never match.
+    pub(super) fn invalid(&mut self, rule: impl FnOnce(&mut Self) -> Option<SyntaxError>) {
+        if !self.call_invalid_rules || self.error.is_some() {
+            return;
+        }
+        let mark = self.pos;
+        if let Some(error) = rule(self) {
+            self.error.get_or_insert(error);
+        }
+        self.pos = mark;
+    }
+
+    /// Runs `rule` with the invalid rules turned off, like CPython's `expression_without_invalid`.
+    fn without_invalid<T>(&mut self, rule: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let call_invalid_rules = std::mem::replace(&mut self.call_invalid_rules, false);
+        let result = rule(self);
+        self.call_invalid_rules = call_invalid_rules;
+        result
+    }
+
+    /// Consumes a token of type `token_type`, spelled `text`, that must follow here, like
+    /// the forced tokens `&&':'` of the grammar.
+    pub(super) fn forced(&mut self, token_type: TokenType, text: &str) -> Option<Span> {
+        let span = self.expect(token_type);
+        if span.is_none() {
+            self.invalid(|p| {
+                let message = format!("expected '{}'", text);
+                Some(SyntaxError::new(message, p.peek().span))
+            });
+        }
+        span
+    }
+
+    /// The `IndentationError` of a clause starting with `keyword` that is followed by a
+    /// NEWLINE but no INDENT, as raised by the `invalid_*_stmt` rules. It is reported at the
+    /// token following the NEWLINE.
+    pub(super) fn expected_block(&self, keyword: &Token) -> SyntaxError {
+        let clause = match keyword.token_type {
+            TokenType::Def => "function definition".to_string(),
+            TokenType::Class => "class definition".to_string(),
+            _ => format!("'{}' statement", keyword.value),
+        };
+        let message = format!(
+            "expected an indented block after {} on line {}",
+            clause, keyword.span.lineno
+        );
+        SyntaxError::indentation(message, self.peek().span)
+    }
+
+    /// Reports the syntax at `span` if it was added after the target version, like CPython's
+    /// `CHECK_VERSION`. `feature` is the subject of the message, e.g. "Pattern matching is".
+    /// The second pass only looks for the syntax error, so what its invalid rules parse on the
+    /// way, like `x := 1` as an annotation target, is not reported.
+    pub(super) fn check_version(&mut self, version: PythonVersion, feature: &str, span: Span) {
+        if self.version < version && !self.call_invalid_rules {
+            let message = format!(
+                "{} only supported in Python {} and greater",
+                feature, version
+            );
+            self.errors.push(SyntaxError::new(message, span));
+        }
+    }
+
+    /// The error of a generic `RAISE_SYNTAX_ERROR`, at the furthest token reached.
+    fn error_here(&self, message: impl Into<String>) -> SyntaxError {
+        let token = &self.tokens[self.furthest.min(self.tokens.len() - 1)];
+        SyntaxError::new(message, token.span)
+    }
+
+    /// The error of a `RAISE_SYNTAX_ERROR_STARTING_FROM`, from `start` up to the token
+    /// following what was parsed.
+    pub(super) fn error_starting_from(&self, start: Span, message: &str) -> SyntaxError {
+        let next = self.peek().span;
+        let span = Span {
+            end_lineno: next.lineno,
+            end_col_offset: next.col_offset,
+            ..start
+        };
+        SyntaxError::new(message, span)
+    }
+
+    /// How many brackets are open after the token at `pos`.
+    fn level(&self, pos: usize) -> usize {
+        self.tokens[..=pos]
+            .iter()
+            .fold(0, |level, token| match token.token_type {
+                TokenType::LPar | TokenType::LSqB | TokenType::LBrace => level + 1,
+                TokenType::RPar | TokenType::RSqB | TokenType::RBrace => level.saturating_sub(1),
+                _ => level,
+            })
+    }
+
+    /// invalid_expression:
+    ///     | invalid_legacy_expression
+    ///     | !(NAME STRING | SOFT_KEYWORD) disjunction expression_without_invalid
+    ///     | disjunction 'if' disjunction !('else' | ':')
+    ///     | invalid_python2_expression
+    pub(super) fn invalid_expression(&mut self) -> Option<SyntaxError> {
+        let start = self.pos;
+        if let Some(error) = self.attempt(Self::invalid_legacy_expression) {
+            return Some(error);
+        }
+        let token = self.peek();
+        let excluded = token.token_type == TokenType::Name
+            && (*self.peek_type(1) == TokenType::String
+                || ["_", "case", "match", "type"].contains(&token.value.as_str()));
+        let python2 = |p: &mut Self| {
+            p.pos = start;
+            p.invalid_python2_expression()
+        };
+        let Some(a) = self.disjunction() else {
+            return python2(self);
+        };
+        if !excluded {
+            if let Some(b) = self.without_invalid(Self::expression) {
+                // Python 2 statements such as `print x` are not missing a comma.
+                let legacy =
+                    matches!(&a, Expr::Name(Name { id, .. }) if id == "print" || id == "exec");
+                return (!legacy && self.level(self.pos - 1) > 0).then(|| {
+                    SyntaxError::new("invalid syntax", a.span().to(b.span()))
+                        .with_hint("Perhaps you forgot a comma?")
+                });
+            }
+        }
+        if self.expect(TokenType::If).is_none() {
+            return python2(self);
+        }
+        let b = self.disjunction()?;
+        if matches!(self.peek_type(0), TokenType::Else | TokenType::Colon) {
+            return None;
+        }
+        let message = "expected 'else' after 'if' expression";
+        Some(SyntaxError::new(message, a.span().to(b.span())))
+    }
+
+    /// invalid_legacy_expression: NAME !'(' star_expressions
+    ///
+    /// Python 2's `print` and `exec` statements, which only raise if NAME is one of them.
+    fn invalid_legacy_expression(&mut self) -> Option<SyntaxError> {
+        let (name, span) = self.name()?;
+        if !matches!(name.as_str(), "print" | "exec") || self.at(TokenType::LPar) {
+            return None;
+        }
+        let b = self.star_expressions()?;
+        let message = format!("Missing parentheses in call to '{}'", name);
+        Some(
+            SyntaxError::new(message, span.to(b.span()))
+                .with_hint(format!("Did you mean {}(...)?", name)),
+        )
+    }
+
+    /// invalid_python2_expression:
+    ///     | disjunction '<' '>'
+    ///     | NAME STRING
+    ///
+    /// Python 2's `<>` operator, read as `<` and `>`, and its `ur` string prefix, read as a
+    /// name followed by a string. Neither token may be separated from the next.
+    fn invalid_python2_expression(&mut self) -> Option<SyntaxError> {
+        let adjacent = |p: &Self, pos: usize| {
+            let (a, b) = (p.tokens[pos].span, p.tokens[pos + 1].span);
+            (a.end_lineno, a.end_col_offset) == (b.lineno, b.col_offset)
+        };
+        let token = self.peek().clone();
+        if token.token_type == TokenType::Name
+            && token.value.eq_ignore_ascii_case("ur")
+            && *self.peek_type(1) == TokenType::String
+            && adjacent(self, self.pos)
+        {
+            let string = &self.tokens[self.pos + 1];
+            let quote = &string.value[..1];
+            let hint = format!(
+                "The 'ur' prefix is Python 2 syntax. Did you mean r{}...{}?",
+                quote, quote
+            );
+            return Some(
+                SyntaxError::new("invalid syntax", token.span.to(string.span)).with_hint(hint),
+            );
+        }
+        self.disjunction()?;
+        let less = self.expect(TokenType::Less)?;
+        let greater = self.expect(TokenType::Greater)?;
+        if !adjacent(self, self.pos - 2) {
+            return None;
+        }
+        let hint = "'<>' is Python 2 syntax. Did you mean '!='?";
+        Some(SyntaxError::new("invalid syntax", less.to(greater)).with_hint(hint))
+    }
+
+    /// invalid_named_expression:
+    ///     | expression ':=' expression
+    ///     | NAME '=' bitwise_or !('=' | ':=')
+    ///     | !(list | tuple | genexp | 'True' | 'None' | 'False') bitwise_or '=' bitwise_or !('=' | ':=')
+    pub(super) fn invalid_named_expression(&mut self) -> Option<SyntaxError> {
+        let assigned = self.attempt(|p| {
+            let a = p.expression()?;
+            p.expect(TokenType::ColonEqual)?;
+            p.expression()?;
+            let message = format!("cannot use assignment expressions with {}", expr_name(&a));
+            Some(SyntaxError::new(message, a.span()))
+        });
+        if assigned.is_some() {
+            return assigned;
+        }
+        let start = self.pos;
+        let not_assignment =
+            |p: &mut Self| !matches!(p.peek_type(0), TokenType::Equal | TokenType::ColonEqual);
+        let named = self.attempt(|p| {
+            let (_, a) = p.name()?;
+            p.expect(TokenType::Equal)?;
+            let b = p.bitwise_or()?;
+            not_assignment(p).then(|| {
+                SyntaxError::new("invalid syntax", a.to(b.span()))
+                    .with_hint("Maybe you meant '==' or ':=' instead of '='?")
+            })
+        });
+        if named.is_some() {
+            return named;
+        }
+        let excluded = matches!(
+            self.peek_type(0),
+            TokenType::True | TokenType::None | TokenType::False
+        ) || self.attempt(Self::list).is_some()
+            || self.attempt(Self::tuple).is_some()
+            || self.attempt(Self::genexp).is_some();
+        self.pos = start;
+        if excluded {
+            return None;
+        }
+        let a = self.bitwise_or()?;
+        self.expect(TokenType::Equal)?;
+        self.bitwise_or()?;
+        not_assignment(self).then(|| {
+            let message = format!("cannot assign to {} here", expr_name(&a));
+            SyntaxError::new(message, a.span()).with_hint("Maybe you meant '==' instead of '='?")
+        })
+    }
+
+    /// invalid_assignment:
+    ///     | invalid_ann_assign_target ':' expression
+    ///     | star_named_expression ',' star_named_expressions* ':' expression
+    ///     | expression ':' expression
+    ///     | (star_targets '=')* star_expressions '='
+    ///     | (star_targets '=')* yield_expr '='
+    ///     | star_expressions augassign (yield_expr | star_expressions)
+    pub(super) fn invalid_assignment(&mut self) -> Option<SyntaxError> {
+        let annotated = self.attempt(|p| {
+            let a = p.invalid_ann_assign_target()?;
+            p.expect(TokenType::Colon)?;
+            p.expression()?;
+            let message = format!(
+                "only single target (not {}) can be annotated",
+                expr_name(&a)
+            );
+            Some(SyntaxError::new(message, a.span()))
+        });
+        let annotated = annotated.or_else(|| {
+            self.attempt(|p| {
+                let a = p.star_named_expression()?;
+                p.expect(TokenType::Comma)?;
+                while p.attempt(Self::star_named_expressions).is_some() {}
+                p.expect(TokenType::Colon)?;
+                p.expression()?;
+                let message = "only single target (not tuple) can be annotated";
+                Some(SyntaxError::new(message, a.span()))
+            })
+        });
+        let annotated = annotated.or_else(|| {
+            self.attempt(|p| {
+                let a = p.expression()?;
+                p.expect(TokenType::Colon)?;
+                p.expression()?;
+                Some(SyntaxError::new("illegal target for annotation", a.span()))
+            })
+        });
+        if annotated.is_some() {
+            return annotated;
+        }
+        let start = self.pos;
+        while self
+            .attempt(|p| {
+                p.star_targets()?;
+                p.expect(TokenType::Equal)
+            })
+            .is_some()
+        {}
+        let targets = self.pos;
+        if let Some(a) = self.attempt(Self::star_expressions) {
+            if self.expect(TokenType::Equal).is_some() {
+                return invalid_target(Targets::Star, &a);
+            }
+        }
+        self.pos = targets;
+        if let Some(a) = self.attempt(Self::yield_expr) {
+            if self.expect(TokenType::Equal).is_some() {
+                let message = "assignment to yield expression not possible";
+                return Some(SyntaxError::new(message, a.span()));
+            }
+        }
+        self.pos = start;
+        let a = self.star_expressions()?;
+        self.augassign()?;
+        if self.at(TokenType::Yield) {
+            self.yield_expr()?;
+        } else {
+            self.star_expressions()?;
+        }
+        let message = format!(
+            "'{}' is an illegal expression for augmented assignment",
+            expr_name(&a)
+        );
+        Some(SyntaxError::new(message, a.span()))
+    }
+
+    /// invalid_ann_assign_target: list | tuple | '(' invalid_ann_assign_target ')'
+    fn invalid_ann_assign_target(&mut self) -> Option<Expr> {
+        if let Some(target) = self
+            .attempt(Self::list)
+            .or_else(|| self.attempt(Self::tuple))
+        {
+            return Some(target);
+        }
+        self.expect(TokenType::LPar)?;
+        let target = self.invalid_ann_assign_target()?;
+        self.expect(TokenType::RPar)?;
+        Some(target)
+    }
+
+    /// invalid_del_stmt: 'del' star_expressions
+    pub(super) fn invalid_del_stmt(&mut self) -> Option<SyntaxError> {
+        self.expect(TokenType::Del)?;
+        invalid_target(Targets::Del, &self.star_expressions()?)
+    }
+
+    /// invalid_for_target: [ASYNC] 'for' star_expressions
+    pub(super) fn invalid_for_target(&mut self) -> Option<SyntaxError> {
+        self.expect(TokenType::Async);
+        self.expect(TokenType::For)?;
+        invalid_target(Targets::For, &self.star_expressions()?)
+    }
+
+    /// invalid_except_stmt: 'except' '*'? expression ',' expressions ['as' NAME] ':'
+    ///
+    /// Python 2's `except E, e:` binds `e` rather than catching it too.
+    pub(super) fn invalid_except_stmt(&mut self) -> Option<SyntaxError> {
+        self.expect(TokenType::Except)?;
+        self.expect(TokenType::Star);
+        let a = self.expression()?;
+        self.expect(TokenType::Comma)?;
+        let b = self.expressions()?;
+        let name = match self.expect(TokenType::As) {
+            Some(_) => Some(self.name()?),
+            None => None,
+        };
+        self.expect(TokenType::Colon)?;
+        let error = SyntaxError::new(
+            "multiple exception types must be parenthesized",
+            a.span().to(b.span()),
+        );
+        if name.is_some() || !matches!(b, Expr::Name(_)) {
+            return Some(error);
+        }
+        let hint = format!(
+            "'except E, e' is Python 2 syntax. Did you mean 'except {} as {}'?",
+            self.span_text(a.span()),
+            self.span_text(b.span())
+        );
+        Some(error.with_hint(hint))
+    }
+
+    /// invalid_raise_stmt: 'raise' expression ',' expression NEWLINE
+    ///
+    /// Python 2's `raise E, V`, which raises `E(V)`.
+    pub(super) fn invalid_raise_stmt(&mut self) -> Option<SyntaxError> {
+        self.expect(TokenType::Raise)?;
+        let a = self.expression()?;
+        self.expect(TokenType::Comma)?;
+        let b = self.expression()?;
+        self.expect(TokenType::Newline)?;
+        let hint = format!(
+            "'raise E, V' is Python 2 syntax. Did you mean 'raise {}({})'?",
+            self.span_text(a.span()),
+            self.span_text(b.span())
+        );
+        Some(SyntaxError::new("invalid syntax", a.span().to(b.span())).with_hint(hint))
+    }
+
+    /// The source text at `span`.
+    fn span_text(&self, span: Span) -> &str {
+        self.source_text(
+            (span.lineno, span.col_offset),
+            (span.end_lineno, span.end_col_offset),
+        )
+    }
+
+    /// invalid_with_stmt:
+    ///     | [ASYNC] 'with' ','.(expression ['as' star_target])+ NEWLINE
+    ///     | [ASYNC] 'with' '(' ','.(expressions ['as' star_target])+ ','? ')' NEWLINE
+    pub(super) fn invalid_with_stmt(&mut self) -> Option<SyntaxError> {
+        self.expect(TokenType::Async);
+        self.expect(TokenType::With)?;
+        let item = |p: &mut Self, expression: fn(&mut Self) -> Option<Expr>| {
+            expression(p)?;
+            if p.expect(TokenType::As).is_some() {
+                p.star_target()?;
+            }
+            Some(())
+        };
+        let items = self.attempt(|p| p.comma_list(|p| item(p, Self::expression)));
+        if items.is_none() {
+            self.expect(TokenType::LPar)?;
+            self.comma_list(|p| item(p, Self::expressions))?;
+            self.expect(TokenType::RPar)?;
+        }
+        let newline = self.expect(TokenType::Newline)?;
+        Some(SyntaxError::new("expected ':'", newline))
+    }
+
+    /// invalid_with_item: expression 'as' expression &(',' | ')' | ':')
+    pub(super) fn invalid_with_item(&mut self) -> Option<SyntaxError> {
+        self.expression()?;
+        self.expect(TokenType::As)?;
+        let a = self.expression()?;
+        match self.peek_type(0) {
+            TokenType::Comma | TokenType::RPar | TokenType::Colon => {
+                invalid_target(Targets::Star, &a)
+            }
+            _ => None,
+        }
+    }
+
+    /// invalid_kwarg:
+    ///     | ('True' | 'False' | 'None') '='
+    ///     | NAME '=' expression for_if_clauses
+    ///     | !(NAME '=') expression '='
+    ///     | '**' expression '=' expression
+    pub(super) fn invalid_kwarg(&mut self) -> Option<SyntaxError> {
+        let start = self.pos;
+        match (self.peek_type(0), self.peek_type(1)) {
+            (TokenType::True | TokenType::False | TokenType::None, TokenType::Equal) => {
+                let message = format!("cannot assign to {}", self.peek().value);
+                self.pos += 2;
+                return Some(SyntaxError::new(message, self.span_from(start)));
+            }
+            (TokenType::Name, TokenType::Equal) => {
+                let (_, a) = self.name()?;
+                let b = self.expect(TokenType::Equal)?;
+                self.expression()?;
+                self.for_if_clauses()?;
+                return Some(
+                    SyntaxError::new("invalid syntax", a.to(b))
+                        .with_hint("Maybe you meant '==' or ':=' instead of '='?"),
+                );
+            }
+            (TokenType::DoubleStar, _) => {
+                self.pos += 1;
+                self.expression()?;
+                self.expect(TokenType::Equal)?;
+                self.expression()?;
+                let message = "cannot assign to keyword argument unpacking";
+                return Some(SyntaxError::new(message, self.span_from(start)));
+            }
+            _ => (),
+        }
+        let a = self.expression()?;
+        let b = self.expect(TokenType::Equal)?;
+        let message = "expression cannot contain assignment, perhaps you meant \"==\"?";
+        Some(SyntaxError::new(message, a.span().to(b)))
+    }
+
+    /// invalid_double_starred_kvpairs:
+    ///     | ','.double_starred_kvpair+ ',' invalid_kvpair
+    ///     | expression ':' '*' bitwise_or
+    ///     | expression ':' &('}' | ',')
+    /// invalid_kvpair: expression !(':') | expression ':' '*' bitwise_or | expression ':' &('}' | ',')
+    pub(super) fn invalid_double_starred_kvpairs(&mut self) -> Option<SyntaxError> {
+        let mut first = true;
+        while self
+            .attempt(|p| {
+                p.double_starred_kvpair()?;
+                p.expect(TokenType::Comma)
+            })
+            .is_some()
+        {
+            first = false;
+        }
+        let a = self.expression()?;
+        let Some(colon) = self.expect(TokenType::Colon) else {
+            let span = a.span();
+            let span = Span {
+                col_offset: span.end_col_offset - 1,
+                lineno: span.end_lineno,
+                ..span
+            };
+            return (!first).then(|| SyntaxError::new("':' expected after dictionary key", span));
+        };
+        if let Some(star) = self.expect(TokenType::Star) {
+            let b = self.bitwise_or()?;
+            let message = "cannot use a starred expression in a dictionary value";
+            return Some(SyntaxError::new(message, star.to(b.span())));
+        }
+        matches!(self.peek_type(0), TokenType::RBrace | TokenType::Comma)
+            .then(|| SyntaxError::new("expression expected after dictionary key and ':'", colon))
+    }
+
+    /// `args ',' expression for_if_clauses` and `expression for_if_clauses ',' [args]` of
+    /// invalid_arguments, for a positional argument starting at `start` that is followed by
+    /// `for`, where the argument is the first one if `first`.
+    pub(super) fn invalid_generator_argument(
+        &mut self,
+        start: usize,
+        first: bool,
+    ) -> Option<SyntaxError> {
+        self.for_if_clauses()?;
+        let span = self.span_from(start);
+        (!first || self.at(TokenType::Comma))
+            .then(|| SyntaxError::new("Generator expression must be parenthesized", span))
+    }
+
+    /// `args ',' args` of invalid_arguments, for a positional argument following the
+    /// keywords `keywords`.
+    pub(super) fn invalid_positional_argument(
+        &mut self,
+        keywords: &[Keyword],
+    ) -> Option<SyntaxError> {
+        self.expression()?;
+        Some(
+            self.error_here(match keywords.iter().any(|k| k.arg.is_none()) {
+                true => "positional argument follows keyword argument unpacking",
+                false => "positional argument follows keyword argument",
+            }),
+        )
+    }
+}
+
+/// Reports the first part of `target` that cannot be bound as `targets`, as
+/// `RAISE_SYNTAX_ERROR_INVALID_TARGET` does.
+fn invalid_target(targets: Targets, target: &Expr) -> Option<SyntaxError> {
+    let invalid = find_invalid_target(targets, target)?;
+    let message = match targets {
+        Targets::Star | Targets::For => format!("cannot assign to {}", expr_name(invalid)),
+        Targets::Del => format!("cannot delete {}", expr_name(invalid)),
+    };
+    Some(SyntaxError::new(message, invalid.span()))
+}
+
+fn find_invalid_target(targets: Targets, expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::List(List { elts, .. }) | Expr::Tuple(Tuple { elts, .. }) => elts
+            .iter()
+            .find_map(|elt| find_invalid_target(targets, elt)),
+        Expr::Starred(_) if targets == Targets::Del => Some(expr),
+        Expr::Starred(Starred { value, .. }) => find_invalid_target(targets, value),
+        // The `in` of `for x in y` is read as part of a comparison.
+        Expr::Compare(Compare { left, ops, .. }) if targets == Targets::For => match ops[0] {
+            CmpOperator::In => find_invalid_target(targets, left),
+            _ => None,
+        },
+        Expr::Name(_) | Expr::Subscript(_) | Expr::Attribute(_) => None,
+        _ => Some(expr),
+    }
+}
+
+/// How errors refer to the kind of `expr`, as in CPython's `_PyPegen_get_expr_name`.
+fn expr_name(expr: &Expr) -> &'static str {
+    match expr {
+        Expr::Attribute(_) => "attribute",
+        Expr::Subscript(_) => "subscript",
+        Expr::Starred(_) => "starred",
+        Expr::Name(_) => "name",
+        Expr::List(_) => "list",
+        Expr::Tuple(_) => "tuple",
+        Expr::Lambda(_) => "lambda",
+        Expr::Call(_) => "function call",
+        Expr::BoolOp(_) | Expr::BinOp(_) | Expr::UnaryOp(_) => "expression",
+        Expr::GeneratorExp(_) => "generator expression",
+        Expr::Yield(_) | Expr::YieldFrom(_) => "yield expression",
+        Expr::Await(_) => "await expression",
+        Expr::ListComp(_) => "list comprehension",
+        Expr::SetComp(_) => "set comprehension",
+        Expr::DictComp(_) => "dict comprehension",
+        Expr::Dict(_) => "dict literal",
+        Expr::Set(_) => "set display",
+        Expr::JoinedStr(_) | Expr::FormattedValue(_) => "f-string expression",
+        Expr::Constant(Constant { value, .. }) => match value {
+            Literal::None => "None",
+            Literal::Bool(true) => "True",
+            Literal::Bool(false) => "False",
+            Literal::Ellipsis => "ellipsis",
+            _ => "literal",
+        },
+        Expr::Compare(_) => "comparison",
+        Expr::IfExp(_) => "conditional expression",
+        Expr::NamedExpr(_) => "named expression",
+        Expr::Slice(_) => "slice",
+        Expr::ErrorExpr(_) => "expression",
+    }
+}
+
+#[test]
+fn test_syntax_errors() {
+    use super::{parse, Mode};
+
+    let case = |body: &str| format!("match x:\n    case {}:\n        pass\n", body);
+    for (source, message, (lineno, col_offset, end_col_offset)) in [
+        (
+            "f(a b)\n".into(),
+            "invalid syntax. Perhaps you forgot a comma?",
+            (1, 2, 5),
+        ),
+        ("if x\n    pass\n".into(), "expected ':'", (1, 4, 5)),
+        ("def f\n".into(), "expected '('", (1, 5, 6)),
+        (
+            "x = 1 if y\n".into(),
+            "expected 'else' after 'if' expression",
+            (1, 4, 10),
+        ),
+        (
+            "f() = 1\n".into(),
+            "cannot assign to function call here. Maybe you meant '==' instead of '='?",
+            (1, 0, 3),
+        ),
+        ("del f()\n".into(), "cannot delete function call", (1, 4, 7)),
+        (
+            "f() += 1\n".into(),
+            "'function call' is an illegal expression for augmented assignment",
+            (1, 0, 3),
+        ),
+        (
+            "[a, b]: int = 1\n".into(),
+            "only single target (not list) can be annotated",
+            (1, 0, 6),
+        ),
+        (
+            "(a.b := 1)\n".into(),
+            "cannot use assignment expressions with attribute",
+            (1, 1, 4),
+        ),
+        (
+            "f(a.b=1)\n".into(),
+            "expression cannot contain assignment, perhaps you meant \"==\"?",
+            (1, 2, 6),
+        ),
+        (
+            "f(a=1, b)\n".into(),
+            "positional argument follows keyword argument",
+            (1, 8, 9),
+        ),
+        (
+            "f(x for x in y, 1)\n".into(),
+            "Generator expression must be parenthesized",
+            (1, 2, 14),
+        ),
+        (
+            "for 1 in x: pass\n".into(),
+            "cannot assign to literal",
+            (1, 4, 5),
+        ),
+        (
+            "with a as f(): pass\n".into(),
+            "cannot assign to function call",
+            (1, 10, 13),
+        ),
+        (
+            "match x\n    case 1:\n        pass\n".into(),
+            "expected ':'",
+            (1, 7, 8),
+        ),
+        (case("1 as _"), "cannot use '_' as a target", (2, 14, 15)),
+        (
+            case("C(a=1, b)"),
+            "positional patterns follow keyword patterns",
+            (2, 16, 17),
+        ),
+        (
+            case("1 + 1"),
+            "imaginary number required in complex literal",
+            (2, 13, 14),
+        ),
+        ("x\n  y\n".into(), "unexpected indent", (2, 1, 2)),
+        ("x\n\ty\n".into(), "unexpected indent", (2, 0, 1)),
+        ("async = 1\n".into(), "invalid syntax", (1, 6, 7)),
+        ("class C(:\n    pass\n".into(), "invalid syntax", (1, 8, 9)),
+        ("f(1 2\n".into(), "'(' was never closed", (1, 1, 2)),
+        ("f(x\ny = 1\n".into(), "'(' was never closed", (1, 1, 2)),
+        (
+            "if x:\npass\n".into(),
+            "expected an indented block after 'if' statement on line 1",
+            (2, 0, 4),
+        ),
+        (
+            "def f():\nreturn 1\n".into(),
+            "expected an indented block after function definition on line 1",
+            (2, 0, 6),
+        ),
+        (
+            "try:\n    a\nexcept:\npass\n".into(),
+            "expected an indented block after 'except' statement on line 3",
+            (4, 0, 4),
+        ),
+        (
+            "match x:\n    case 1:\n    pass\n".into(),
+            "expected an indented block after 'case' statement on line 2",
+            (3, 4, 8),
+        ),
+        (
+            "{1: 2, 3}\n".into(),
+            "':' expected after dictionary key",
+            (1, 7, 8),
+        ),
+        (
+            "{1: *a}\n".into(),
+            "cannot use a starred expression in a dictionary value",
+            (1, 4, 6),
+        ),
+        (
+            "{1:}\n".into(),
+            "expression expected after dictionary key and ':'",
+            (1, 2, 3),
+        ),
+        (
+            "def f(a=1, b): pass\n".into(),
+            "parameter without a default follows parameter with a default",
+            (1, 11, 12),
+        ),
+        (
+            "def f(**k, a): pass\n".into(),
+            "arguments cannot follow var-keyword argument",
+            (1, 11, 12),
+        ),
+        (
+            "def f(**k=1): pass\n".into(),
+            "var-keyword argument cannot have default value",
+            (1, 9, 10),
+        ),
+        (
+            "def f(a, /, /): pass\n".into(),
+            "/ may appear only once",
+            (1, 12, 13),
+        ),
+        (
+            "def f(*): pass\n".into(),
+            "named arguments must follow bare *",
+            (1, 6, 7),
+        ),
+        (
+            "def f(a=): pass\n".into(),
+            "expected default value expression",
+            (1, 7, 8),
+        ),
+        (
+            "f\"{x!z}\"\n".into(),
+            "f-string: invalid conversion character 'z': expected 's', 'r', or 'a'",
+            (1, 5, 6),
+        ),
+        (
+            "f\"{x!}\"\n".into(),
+            "f-string: missing conversion character",
+            (1, 5, 6),
+        ),
+    ] {
+        let error = parse(&source, Mode::Exec).unwrap_err();
+        assert_eq!(error.to_string(), message, "{:?}", source);
+        let span = (
+            error.span.lineno,
+            error.span.col_offset,
+            error.span.end_col_offset,
+        );
+        assert_eq!(span, (lineno, col_offset, end_col_offset), "{:?}", source);
+    }
+}
+
+#[test]
+fn test_render() {
+    let source = "def f():\n    f(a b)\n";
+    let error = super::parse(source, super::Mode::Exec).unwrap_err();
+    assert_eq!(
+        error.render("<test>", source),
+        "  File \"<test>\", line 2\n    f(a b)\n      ^^^\nSyntaxError: invalid syntax. Perhaps you forgot a comma?"
+    );
+    let source = "if x:\npass\n";
+    let error = super::parse(source, super::Mode::Exec).unwrap_err();
+    assert_eq!(
+        error.render("<test>", source),
+        "  File \"<test>\", line 2\n    pass\n    ^^^^\nIndentationError: expected an indented block after 'if' statement on line 1"
+    );
+}
+
+#[test]
+fn test_python2_errors() {
+    use super::{parse, parse_recovering, Mode};
+    use crate::types::PythonVersion;
+
+    for (source, error) in [
+        (
+            "print 'a', b\n",
+            "Missing parentheses in call to 'print'. Did you mean print(...)?",
+        ),
+        (
+            "exec code in ns\n",
+            "Missing parentheses in call to 'exec'. Did you mean exec(...)?",
+        ),
+        (
+            "try:\n    pass\nexcept E, e:\n    pass\n",
+            "multiple exception types must be parenthesized. 'except E, e' is Python 2 syntax. Did you mean 'except E as e'?",
+        ),
+        (
+            "try:\n    pass\nexcept E, F:\n    pass\n",
+            "multiple exception types must be parenthesized. 'except E, e' is Python 2 syntax. Did you mean 'except E as F'?",
+        ),
+        (
+            "try:\n    pass\nexcept E, F as e:\n    pass\n",
+            "multiple exception types must be parenthesized",
+        ),
+        (
+            "raise E, 'message'\n",
+            "invalid syntax. 'raise E, V' is Python 2 syntax. Did you mean 'raise E('message')'?",
+        ),
+        (
+            "x = `a`\n",
+            "invalid syntax. Backquotes are Python 2 syntax. Did you mean repr(...)?",
+        ),
+        (
+            "if a <> b: pass\n",
+            "invalid syntax. '<>' is Python 2 syntax. Did you mean '!='?",
+        ),
+        ("if a < > b: pass\n", "invalid syntax"),
+        (
+            "x = 0777\n",
+            "leading zeros in decimal integer literals are not permitted; use an 0o prefix for octal integers. Did you mean 0o777?",
+        ),
+        (
+            "x = 0x1fL\n",
+            "invalid hexadecimal literal. Long integer suffixes are Python 2 syntax. Did you mean 0x1f?",
+        ),
+        (
+            "x = ur'abc'\n",
+            "invalid syntax. The 'ur' prefix is Python 2 syntax. Did you mean r'...'?",
+        ),
+    ] {
+        let message = parse(source, Mode::Exec).unwrap_err().to_string();
+        assert_eq!(message, error, "{:?}", source);
+    }
+    for source in ["print\n", "print >>f, x\n", "x = 00 + 0777.5 + 0e1 + 0_0\n"] {
+        assert!(parse(source, Mode::Exec).is_ok(), "{:?}", source);
+    }
+    // A pair of backquotes is reported once, and a second pair again.
+    let (_, errors) = parse_recovering("x = `a`\ny = `b`\n", PythonVersion::default());
+    let columns: Vec<_> = errors
+        .iter()
+        .map(|e| (e.span.lineno, e.span.col_offset))
+        .collect();
+    assert_eq!(columns, [(1, 4), (2, 4)]);
+}
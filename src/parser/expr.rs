@@ -0,0 +1,1379 @@
+//! Expression rules, in the order of `Grammar/python.gram`.
+
+use super::{Parser, Rule, SyntaxError};
+use crate::ast::*;
+use crate::types::{Literal, PythonVersion, Span, TokenType};
+
+impl Parser {
+    /// expressions: expression (',' expression)* [',']
+    pub(super) fn expressions(&mut self) -> Option<Expr> {
+        self.comma_tuple(Self::expression, ExprContext::Load)
+    }
+
+    /// expression:
+    ///     | invalid_expression
+    ///     | disjunction 'if' disjunction 'else' expression
+    ///     | disjunction
+    ///     | lambdef
+    pub(super) fn expression(&mut self) -> Option<Expr> {
+        self.memo(Rule::Expression, |p| {
+            p.invalid(Self::invalid_expression);
+            if p.at(TokenType::Lambda) {
+                return p.lambdef();
+            }
+            let start = p.pos;
+            let body = p.disjunction()?;
+            let conditional = p.attempt(|p| {
+                p.expect(TokenType::If)?;
+                let test = p.disjunction()?;
+                p.expect(TokenType::Else)?;
+                Some((test, p.expression()?))
+            });
+            let Some((test, orelse)) = conditional else {
+                return Some(body);
+            };
+            Some(Expr::IfExp(IfExp {
+                test: Box::new(test),
+                body: Box::new(body),
+                orelse: Box::new(orelse),
+                span: p.span_from(start),
+            }))
+        })
+    }
+
+    /// yield_expr: 'yield' 'from' expression | 'yield' [star_expressions]
+    pub(super) fn yield_expr(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        self.expect(TokenType::Yield)?;
+        if self.expect(TokenType::From).is_some() {
+            let value = self.expression()?;
+            return Some(Expr::YieldFrom(YieldFrom {
+                value: Box::new(value),
+                span: self.span_from(start),
+            }));
+        }
+        let value = self.attempt(Self::star_expressions);
+        Some(Expr::Yield(Yield {
+            value: value.map(Box::new),
+            span: self.span_from(start),
+        }))
+    }
+
+    /// star_expressions: star_expression (',' star_expression)* [',']
+    pub(super) fn star_expressions(&mut self) -> Option<Expr> {
+        self.comma_tuple(Self::star_expression, ExprContext::Load)
+    }
+
+    /// star_expression: '*' bitwise_or | expression
+    pub(super) fn star_expression(&mut self) -> Option<Expr> {
+        if self.at(TokenType::Star) {
+            return self.starred(Self::bitwise_or);
+        }
+        self.expression()
+    }
+
+    /// star_named_expressions: ','.star_named_expression+ [',']
+    pub(super) fn star_named_expressions(&mut self) -> Option<Vec<Expr>> {
+        self.comma_list(Self::star_named_expression)
+    }
+
+    /// star_named_expression: '*' bitwise_or | named_expression
+    pub(super) fn star_named_expression(&mut self) -> Option<Expr> {
+        if self.at(TokenType::Star) {
+            return self.starred(Self::bitwise_or);
+        }
+        self.named_expression()
+    }
+
+    /// `'*' operand`, as a `Starred` in a load context.
+    fn starred(&mut self, operand: fn(&mut Self) -> Option<Expr>) -> Option<Expr> {
+        let start = self.pos;
+        self.expect(TokenType::Star)?;
+        let value = operand(self)?;
+        Some(Expr::Starred(Starred {
+            value: Box::new(value),
+            ctx: ExprContext::Load,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// assignment_expression: NAME ':=' ~ expression
+    fn assignment_expression(&mut self) -> Option<Expr> {
+        if *self.peek_type(1) != TokenType::ColonEqual {
+            return None;
+        }
+        let start = self.pos;
+        let (id, target) = self.name()?;
+        self.expect(TokenType::ColonEqual)?;
+        let value = self.expression()?;
+        let span = self.span_from(start);
+        self.check_version(PythonVersion::Py38, "Assignment expressions are", span);
+        Some(Expr::NamedExpr(NamedExpr {
+            target: Box::new(Expr::Name(Name {
+                id,
+                ctx: ExprContext::Store,
+                span: target,
+            })),
+            value: Box::new(value),
+            span,
+        }))
+    }
+
+    /// named_expression: assignment_expression | invalid_named_expression | expression !':='
+    pub(super) fn named_expression(&mut self) -> Option<Expr> {
+        self.assignment_or_expression(true)
+    }
+
+    /// `assignment_expression | expression !':='`, as in arguments and generator expressions,
+    /// trying the invalid rule of `named_expression` in between if `invalid`.
+    fn assignment_or_expression(&mut self, invalid: bool) -> Option<Expr> {
+        if let Some(expr) = self.attempt(Self::assignment_expression) {
+            return Some(expr);
+        }
+        if invalid {
+            self.invalid(Self::invalid_named_expression);
+        }
+        self.attempt(|p| {
+            let expr = p.expression()?;
+            (!p.at(TokenType::ColonEqual)).then_some(expr)
+        })
+    }
+
+    /// disjunction: conjunction ('or' conjunction)+ | conjunction
+    pub(super) fn disjunction(&mut self) -> Option<Expr> {
+        self.memo(Rule::Disjunction, |p| {
+            p.bool_op(TokenType::Or, BoolOperator::Or, Self::conjunction)
+        })
+    }
+
+    /// conjunction: inversion ('and' inversion)+ | inversion
+    fn conjunction(&mut self) -> Option<Expr> {
+        self.memo(Rule::Conjunction, |p| {
+            p.bool_op(TokenType::And, BoolOperator::And, Self::inversion)
+        })
+    }
+
+    fn bool_op(
+        &mut self,
+        keyword: TokenType,
+        op: BoolOperator,
+        operand: fn(&mut Self) -> Option<Expr>,
+    ) -> Option<Expr> {
+        let start = self.pos;
+        let first = operand(self)?;
+        let mut values = vec![first];
+        while let Some(value) = self.attempt(|p| {
+            p.expect(keyword.clone())?;
+            operand(p)
+        }) {
+            values.push(value);
+        }
+        if values.len() == 1 {
+            return values.pop();
+        }
+        Some(Expr::BoolOp(BoolOp {
+            op,
+            values,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// inversion: 'not' inversion | comparison
+    fn inversion(&mut self) -> Option<Expr> {
+        self.memo(Rule::Inversion, |p| {
+            let start = p.pos;
+            if p.expect(TokenType::Not).is_some() {
+                let operand = p.inversion()?;
+                return Some(Expr::UnaryOp(UnaryOp {
+                    op: UnaryOperator::Not,
+                    operand: Box::new(operand),
+                    span: p.span_from(start),
+                }));
+            }
+            p.comparison()
+        })
+    }
+
+    /// comparison: bitwise_or compare_op_bitwise_or_pair+ | bitwise_or
+    fn comparison(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        let left = self.bitwise_or()?;
+        let mut ops = vec![];
+        let mut comparators = vec![];
+        while let Some((op, comparator)) = self.attempt(|p| {
+            let op = p.compare_op()?;
+            Some((op, p.bitwise_or()?))
+        }) {
+            ops.push(op);
+            comparators.push(comparator);
+        }
+        if ops.is_empty() {
+            return Some(left);
+        }
+        Some(Expr::Compare(Compare {
+            left: Box::new(left),
+            ops,
+            comparators,
+            span: self.span_from(start),
+        }))
+    }
+
+    fn compare_op(&mut self) -> Option<CmpOperator> {
+        if let Some(op) = self.advance_map(|t| match t {
+            TokenType::EqEqual => Some(CmpOperator::Eq),
+            TokenType::NotEqual => Some(CmpOperator::NotEq),
+            TokenType::LessEqual => Some(CmpOperator::LtE),
+            TokenType::Less => Some(CmpOperator::Lt),
+            TokenType::GreaterEqual => Some(CmpOperator::GtE),
+            TokenType::Greater => Some(CmpOperator::Gt),
+            TokenType::In => Some(CmpOperator::In),
+            _ => None,
+        }) {
+            return Some(op);
+        }
+        if self.expect(TokenType::Is).is_some() {
+            if self.expect(TokenType::Not).is_some() {
+                return Some(CmpOperator::IsNot);
+            }
+            return Some(CmpOperator::Is);
+        }
+        self.attempt(|p| {
+            p.expect(TokenType::Not)?;
+            p.expect(TokenType::In)?;
+            Some(CmpOperator::NotIn)
+        })
+    }
+
+    /// bitwise_or: bitwise_or '|' bitwise_xor | bitwise_xor
+    pub(super) fn bitwise_or(&mut self) -> Option<Expr> {
+        self.bin_op(Rule::BitwiseOr, Self::bitwise_or, Self::bitwise_xor, |t| {
+            (*t == TokenType::VBar).then_some(Operator::BitOr)
+        })
+    }
+
+    /// bitwise_xor: bitwise_xor '^' bitwise_and | bitwise_and
+    fn bitwise_xor(&mut self) -> Option<Expr> {
+        self.bin_op(
+            Rule::BitwiseXor,
+            Self::bitwise_xor,
+            Self::bitwise_and,
+            |t| (*t == TokenType::Circumflex).then_some(Operator::BitXor),
+        )
+    }
+
+    /// bitwise_and: bitwise_and '&' shift_expr | shift_expr
+    fn bitwise_and(&mut self) -> Option<Expr> {
+        self.bin_op(Rule::BitwiseAnd, Self::bitwise_and, Self::shift_expr, |t| {
+            (*t == TokenType::Amper).then_some(Operator::BitAnd)
+        })
+    }
+
+    /// shift_expr: shift_expr ('<<' | '>>') sum | sum
+    fn shift_expr(&mut self) -> Option<Expr> {
+        self.bin_op(Rule::ShiftExpr, Self::shift_expr, Self::sum, |t| match t {
+            TokenType::LeftShift => Some(Operator::LShift),
+            TokenType::RightShift => Some(Operator::RShift),
+            _ => None,
+        })
+    }
+
+    /// sum: sum ('+' | '-') term | term
+    fn sum(&mut self) -> Option<Expr> {
+        self.bin_op(Rule::Sum, Self::sum, Self::term, |t| match t {
+            TokenType::Plus => Some(Operator::Add),
+            TokenType::Minus => Some(Operator::Sub),
+            _ => None,
+        })
+    }
+
+    /// term: term ('*' | '/' | '//' | '%' | '@') factor | factor
+    fn term(&mut self) -> Option<Expr> {
+        self.bin_op(Rule::Term, Self::term, Self::factor, |t| match t {
+            TokenType::Star => Some(Operator::Mult),
+            TokenType::Slash => Some(Operator::Div),
+            TokenType::DoubleSlash => Some(Operator::FloorDiv),
+            TokenType::Percent => Some(Operator::Mod),
+            TokenType::At => Some(Operator::MatMult),
+            _ => None,
+        })
+    }
+
+    /// `rule: rule operator operand | operand`, for the left-associative binary operators.
+    fn bin_op(
+        &mut self,
+        rule: Rule,
+        this: fn(&mut Self) -> Option<Expr>,
+        operand: fn(&mut Self) -> Option<Expr>,
+        operator: fn(&TokenType) -> Option<Operator>,
+    ) -> Option<Expr> {
+        self.memo_left_rec(rule, |p| {
+            let start = p.pos;
+            let bin_op = p.attempt(|p| {
+                let left = this(p)?;
+                let op = p.advance_map(operator)?;
+                let right = operand(p)?;
+                Some(Expr::BinOp(BinOp {
+                    left: Box::new(left),
+                    op,
+                    right: Box::new(right),
+                    span: p.span_from(start),
+                }))
+            });
+            bin_op.or_else(|| operand(p))
+        })
+    }
+
+    /// factor: '+' factor | '-' factor | '~' factor | power
+    fn factor(&mut self) -> Option<Expr> {
+        self.memo(Rule::Factor, |p| {
+            let start = p.pos;
+            let op = p.advance_map(|t| match t {
+                TokenType::Plus => Some(UnaryOperator::UAdd),
+                TokenType::Minus => Some(UnaryOperator::USub),
+                TokenType::Tilde => Some(UnaryOperator::Invert),
+                _ => None,
+            });
+            let Some(op) = op else {
+                return p.power();
+            };
+            let operand = p.factor()?;
+            Some(Expr::UnaryOp(UnaryOp {
+                op,
+                operand: Box::new(operand),
+                span: p.span_from(start),
+            }))
+        })
+    }
+
+    /// power: await_primary '**' factor | await_primary
+    fn power(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        let left = self.await_primary()?;
+        let Some(right) = self.attempt(|p| {
+            p.expect(TokenType::DoubleStar)?;
+            p.factor()
+        }) else {
+            return Some(left);
+        };
+        Some(Expr::BinOp(BinOp {
+            left: Box::new(left),
+            op: Operator::Pow,
+            right: Box::new(right),
+            span: self.span_from(start),
+        }))
+    }
+
+    /// await_primary: AWAIT primary | primary
+    fn await_primary(&mut self) -> Option<Expr> {
+        self.memo(Rule::AwaitPrimary, |p| {
+            let start = p.pos;
+            if p.expect(TokenType::Await).is_none() {
+                return p.primary();
+            }
+            let value = p.primary()?;
+            Some(Expr::Await(Await {
+                value: Box::new(value),
+                span: p.span_from(start),
+            }))
+        })
+    }
+
+    /// primary:
+    ///     | primary '.' NAME
+    ///     | primary genexp
+    ///     | primary '(' [arguments] ')'
+    ///     | primary '[' slices ']'
+    ///     | atom
+    pub(super) fn primary(&mut self) -> Option<Expr> {
+        self.memo_left_rec(Rule::Primary, |p| {
+            let start = p.pos;
+            let trailed = p.attempt(|p| {
+                let value = Box::new(p.primary()?);
+                p.primary_trailer(start, value)
+            });
+            trailed.or_else(|| p.atom())
+        })
+    }
+
+    /// The attribute, call or subscript following `value`, a primary starting at `start`.
+    fn primary_trailer(&mut self, start: usize, value: Box<Expr>) -> Option<Expr> {
+        match self.peek_type(0) {
+            TokenType::Dot => {
+                self.expect(TokenType::Dot)?;
+                let (attr, _) = self.name()?;
+                Some(Expr::Attribute(Attribute {
+                    value,
+                    attr,
+                    ctx: ExprContext::Load,
+                    span: self.span_from(start),
+                }))
+            }
+            TokenType::LPar => {
+                let (args, keywords) = match self.attempt(Self::genexp) {
+                    Some(genexp) => (vec![genexp], vec![]),
+                    None => {
+                        self.expect(TokenType::LPar)?;
+                        let arguments = if self.at(TokenType::RPar) {
+                            (vec![], vec![])
+                        } else {
+                            self.arguments()?
+                        };
+                        self.expect(TokenType::RPar)?;
+                        arguments
+                    }
+                };
+                Some(Expr::Call(Call {
+                    func: value,
+                    args,
+                    keywords,
+                    span: self.span_from(start),
+                }))
+            }
+            TokenType::LSqB => {
+                self.expect(TokenType::LSqB)?;
+                let slice = self.slices()?;
+                self.expect(TokenType::RSqB)?;
+                Some(Expr::Subscript(Subscript {
+                    value,
+                    slice: Box::new(slice),
+                    ctx: ExprContext::Load,
+                    span: self.span_from(start),
+                }))
+            }
+            _ => None,
+        }
+    }
+
+    /// slices: slice !',' | ','.(slice | starred_expression)+ [',']
+    fn slices(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        if let Some(slice) = self.attempt(|p| {
+            let slice = p.slice()?;
+            (!p.at(TokenType::Comma)).then_some(slice)
+        }) {
+            return Some(slice);
+        }
+        let elts = self.comma_list(|p| {
+            if p.at(TokenType::Star) {
+                p.starred(Self::expression)
+            } else {
+                p.slice()
+            }
+        })?;
+        Some(Expr::Tuple(Tuple {
+            elts,
+            ctx: ExprContext::Load,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// slice: [expression] ':' [expression] [':' [expression]] | named_expression
+    fn slice(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        let slice = self.attempt(|p| {
+            let lower = p.attempt(Self::expression);
+            p.expect(TokenType::Colon)?;
+            let upper = p.attempt(Self::expression);
+            let step = match p.expect(TokenType::Colon) {
+                Some(_) => p.attempt(Self::expression),
+                None => None,
+            };
+            Some(Expr::Slice(Slice {
+                lower: lower.map(Box::new),
+                upper: upper.map(Box::new),
+                step: step.map(Box::new),
+                span: p.span_from(start),
+            }))
+        });
+        slice.or_else(|| self.named_expression())
+    }
+
+    /// atom:
+    ///     | NAME
+    ///     | 'True' | 'False' | 'None'
+    ///     | strings
+    ///     | NUMBER
+    ///     | (tuple | group | genexp)
+    ///     | (list | listcomp)
+    ///     | (dict | set | dictcomp | setcomp)
+    ///     | '...'
+    ///     | ERRORTOKEN
+    ///
+    /// Error tokens stand for what the tokenizer or error recovery could not read.
+    fn atom(&mut self) -> Option<Expr> {
+        let token = self.peek();
+        let span = token.span;
+        let value = match token.token_type {
+            TokenType::ErrorToken => {
+                let span = self.expect(TokenType::ErrorToken)?;
+                return Some(Expr::ErrorExpr(ErrorExpr { span }));
+            }
+            TokenType::Name => {
+                let (id, span) = self.name()?;
+                return Some(Expr::Name(Name {
+                    id,
+                    ctx: ExprContext::Load,
+                    span,
+                }));
+            }
+            TokenType::String | TokenType::FStringStart => return self.strings(),
+            TokenType::LPar => {
+                return self
+                    .attempt(Self::tuple)
+                    .or_else(|| self.attempt(Self::group))
+                    .or_else(|| self.attempt(Self::genexp));
+            }
+            TokenType::LSqB => {
+                return self
+                    .attempt(Self::list)
+                    .or_else(|| self.attempt(Self::listcomp));
+            }
+            TokenType::LBrace => {
+                return self
+                    .attempt(Self::dict)
+                    .or_else(|| self.attempt(Self::set))
+                    .or_else(|| self.attempt(Self::dictcomp))
+                    .or_else(|| self.attempt(Self::setcomp));
+            }
+            TokenType::True => Literal::Bool(true),
+            TokenType::False => Literal::Bool(false),
+            TokenType::None => Literal::None,
+            TokenType::Ellipsis => Literal::Ellipsis,
+            TokenType::Number => token.literal.clone()?,
+            _ => {
+                self.furthest = self.furthest.max(self.pos);
+                return None;
+            }
+        };
+        self.pos += 1;
+        Some(Expr::Constant(Constant {
+            value,
+            kind: None,
+            span,
+        }))
+    }
+
+    /// group: '(' (yield_expr | named_expression) ')'
+    fn group(&mut self) -> Option<Expr> {
+        self.expect(TokenType::LPar)?;
+        let expr = if self.at(TokenType::Yield) {
+            self.yield_expr()?
+        } else {
+            self.named_expression()?
+        };
+        self.expect(TokenType::RPar)?;
+        Some(expr)
+    }
+
+    /// tuple: '(' [star_named_expression ',' [star_named_expressions]] ')'
+    pub(super) fn tuple(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        self.expect(TokenType::LPar)?;
+        let mut elts = vec![];
+        if !self.at(TokenType::RPar) {
+            elts.push(self.star_named_expression()?);
+            self.expect(TokenType::Comma)?;
+            if !self.at(TokenType::RPar) {
+                elts.extend(self.star_named_expressions()?);
+            }
+        }
+        self.expect(TokenType::RPar)?;
+        Some(Expr::Tuple(Tuple {
+            elts,
+            ctx: ExprContext::Load,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// genexp: '(' (assignment_expression | expression !':=') for_if_clauses ')'
+    pub(super) fn genexp(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        self.expect(TokenType::LPar)?;
+        let elt = self.assignment_or_expression(false)?;
+        let generators = self.for_if_clauses()?;
+        self.expect(TokenType::RPar)?;
+        Some(Expr::GeneratorExp(GeneratorExp {
+            elt: Box::new(elt),
+            generators,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// list: '[' [star_named_expressions] ']'
+    pub(super) fn list(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        self.expect(TokenType::LSqB)?;
+        let elts = if self.at(TokenType::RSqB) {
+            vec![]
+        } else {
+            self.star_named_expressions()?
+        };
+        self.expect(TokenType::RSqB)?;
+        Some(Expr::List(List {
+            elts,
+            ctx: ExprContext::Load,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// listcomp: '[' named_expression for_if_clauses ']'
+    fn listcomp(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        self.expect(TokenType::LSqB)?;
+        let elt = self.named_expression()?;
+        let generators = self.for_if_clauses()?;
+        self.expect(TokenType::RSqB)?;
+        Some(Expr::ListComp(ListComp {
+            elt: Box::new(elt),
+            generators,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// set: '{' star_named_expressions '}'
+    fn set(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        self.expect(TokenType::LBrace)?;
+        let elts = self.star_named_expressions()?;
+        self.expect(TokenType::RBrace)?;
+        Some(Expr::Set(Set {
+            elts,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// setcomp: '{' named_expression for_if_clauses '}'
+    fn setcomp(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        self.expect(TokenType::LBrace)?;
+        let elt = self.named_expression()?;
+        let generators = self.for_if_clauses()?;
+        self.expect(TokenType::RBrace)?;
+        Some(Expr::SetComp(SetComp {
+            elt: Box::new(elt),
+            generators,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// dict: '{' [double_starred_kvpairs] '}' | '{' invalid_double_starred_kvpairs '}'
+    fn dict(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        self.expect(TokenType::LBrace)?;
+        let pairs = match self.at(TokenType::RBrace) {
+            true => Some(vec![]),
+            false => self.comma_list(Self::double_starred_kvpair),
+        };
+        let Some(pairs) = pairs.filter(|_| self.at(TokenType::RBrace)) else {
+            self.pos = start + 1;
+            self.invalid(Self::invalid_double_starred_kvpairs);
+            return None;
+        };
+        self.expect(TokenType::RBrace)?;
+        let (keys, values) = pairs.into_iter().unzip();
+        Some(Expr::Dict(Dict {
+            keys,
+            values,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// dictcomp: '{' kvpair for_if_clauses '}'
+    fn dictcomp(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        self.expect(TokenType::LBrace)?;
+        let (key, value) = self.kvpair()?;
+        let generators = self.for_if_clauses()?;
+        self.expect(TokenType::RBrace)?;
+        Some(Expr::DictComp(DictComp {
+            key: Box::new(key),
+            value: Box::new(value),
+            generators,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// double_starred_kvpair: '**' bitwise_or | kvpair
+    pub(super) fn double_starred_kvpair(&mut self) -> Option<(Option<Expr>, Expr)> {
+        if self.expect(TokenType::DoubleStar).is_some() {
+            return Some((None, self.bitwise_or()?));
+        }
+        let (key, value) = self.kvpair()?;
+        Some((Some(key), value))
+    }
+
+    /// kvpair: expression ':' expression
+    fn kvpair(&mut self) -> Option<(Expr, Expr)> {
+        let key = self.expression()?;
+        self.expect(TokenType::Colon)?;
+        Some((key, self.expression()?))
+    }
+
+    /// for_if_clauses: for_if_clause+
+    pub(super) fn for_if_clauses(&mut self) -> Option<Vec<Comprehension>> {
+        let mut generators = vec![];
+        while let Some(generator) = self.attempt(Self::for_if_clause) {
+            generators.push(generator);
+        }
+        (!generators.is_empty()).then_some(generators)
+    }
+
+    /// for_if_clause:
+    ///     | [ASYNC] 'for' star_targets 'in' ~ disjunction ('if' disjunction)*
+    ///     | invalid_for_target
+    fn for_if_clause(&mut self) -> Option<Comprehension> {
+        let start = self.pos;
+        let is_async = self.expect(TokenType::Async).is_some();
+        self.expect(TokenType::For)?;
+        let Some(target) = self.attempt(|p| {
+            let target = p.star_targets()?;
+            p.expect(TokenType::In)?;
+            Some(target)
+        }) else {
+            self.pos = start;
+            self.invalid(Self::invalid_for_target);
+            return None;
+        };
+        let iter = self.disjunction()?;
+        let mut ifs = vec![];
+        while let Some(test) = self.attempt(|p| {
+            p.expect(TokenType::If)?;
+            p.disjunction()
+        }) {
+            ifs.push(test);
+        }
+        Some(Comprehension {
+            target: Box::new(target),
+            iter: Box::new(iter),
+            ifs,
+            is_async,
+        })
+    }
+
+    /// arguments: args [','] &')'
+    ///
+    /// Positional and `*` arguments go to the first list, keyword and `**` arguments to the
+    /// second. Positional arguments may not follow keywords, nor `*` arguments follow `**`.
+    pub(super) fn arguments(&mut self) -> Option<(Vec<Expr>, Vec<Keyword>)> {
+        self.memo(Rule::Arguments, |p| {
+            let mut args = vec![];
+            let mut keywords: Vec<Keyword> = vec![];
+            let mut double_starred = false;
+            loop {
+                let start = p.pos;
+                p.invalid(Self::invalid_kwarg);
+                if p.at(TokenType::Star) {
+                    if double_starred {
+                        p.invalid(|p| {
+                            let message =
+                                "iterable argument unpacking follows keyword argument unpacking";
+                            Some(SyntaxError::new(message, p.peek().span))
+                        });
+                        return None;
+                    }
+                    args.push(p.starred(Self::expression)?);
+                } else if p.expect(TokenType::DoubleStar).is_some() {
+                    double_starred = true;
+                    let value = p.expression()?;
+                    keywords.push(Keyword {
+                        arg: None,
+                        value: Box::new(value),
+                        span: p.span_from(start),
+                    });
+                } else if p.at(TokenType::Name) && *p.peek_type(1) == TokenType::Equal {
+                    let (arg, _) = p.name()?;
+                    p.expect(TokenType::Equal)?;
+                    let value = p.expression()?;
+                    keywords.push(Keyword {
+                        arg: Some(arg),
+                        value: Box::new(value),
+                        span: p.span_from(start),
+                    });
+                } else if keywords.is_empty() {
+                    args.push(p.assignment_or_expression(false)?);
+                    if matches!(p.peek_type(0), TokenType::For | TokenType::Async) {
+                        let first = args.len() == 1;
+                        p.invalid(|p| p.invalid_generator_argument(start, first));
+                    }
+                } else {
+                    p.invalid(|p| p.invalid_positional_argument(&keywords));
+                    return None;
+                }
+                if p.expect(TokenType::Comma).is_none() || p.at(TokenType::RPar) {
+                    break;
+                }
+            }
+            p.at(TokenType::RPar).then_some((args, keywords))
+        })
+    }
+
+    /// lambdef: 'lambda' [lambda_params] ':' expression
+    fn lambdef(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        self.expect(TokenType::Lambda)?;
+        let args = self.parameters(false, TokenType::Colon)?;
+        self.expect(TokenType::Colon)?;
+        let body = self.expression()?;
+        Some(Expr::Lambda(Lambda {
+            args: Box::new(args),
+            body: Box::new(body),
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parameters up to `end`, which is left unconsumed, with annotations if `annotated`:
+    /// positional-only ones before `/`, keyword-only ones after `*` or `*args`, then `**kwargs`.
+    /// Once a positional parameter has a default, all the following ones need one too.
+    pub(super) fn parameters(&mut self, annotated: bool, end: TokenType) -> Option<Arguments> {
+        let mut arguments = Arguments {
+            posonlyargs: vec![],
+            args: vec![],
+            vararg: None,
+            kwonlyargs: vec![],
+            kw_defaults: vec![],
+            kwarg: None,
+            defaults: vec![],
+        };
+        let mut star = false;
+        while !self.at(end.clone()) {
+            if let Some(slash) = self.expect(TokenType::Slash) {
+                if star || arguments.args.is_empty() || !arguments.posonlyargs.is_empty() {
+                    let message = match star {
+                        true => Some("/ must be ahead of *"),
+                        false if !arguments.posonlyargs.is_empty() => {
+                            Some("/ may appear only once")
+                        }
+                        false => self
+                            .at(TokenType::Comma)
+                            .then_some("at least one argument must precede /"),
+                    };
+                    if let Some(message) = message {
+                        self.invalid(|_| Some(SyntaxError::new(message, slash)));
+                    }
+                    return None;
+                }
+                self.check_version(PythonVersion::Py38, "Positional-only parameters are", slash);
+                arguments.posonlyargs = std::mem::take(&mut arguments.args);
+            } else if let Some(asterisk) = self.expect(TokenType::Star) {
+                if star {
+                    if matches!(self.peek_type(0), TokenType::Name | TokenType::Comma) {
+                        let message = "* argument may appear only once";
+                        self.invalid(|_| Some(SyntaxError::new(message, asterisk)));
+                    }
+                    return None;
+                }
+                star = true;
+                if self.at(TokenType::Name) {
+                    // `*args: *Ts` unpacks a TypeVarTuple.
+                    arguments.vararg = Some(Box::new(self.param(annotated, true)?));
+                    if self.at(TokenType::Equal) {
+                        let message = "var-positional argument cannot have default value";
+                        let span = self.peek().span;
+                        self.invalid(|_| Some(SyntaxError::new(message, span)));
+                        return None;
+                    }
+                } else if !self.at(TokenType::Comma)
+                    || [end.clone(), TokenType::DoubleStar].contains(self.peek_type(1))
+                {
+                    if self.at(end.clone()) || self.at(TokenType::Comma) {
+                        // Lambdas report it at the token following the `*` or `*,`.
+                        let span = match annotated {
+                            true => asterisk,
+                            false => {
+                                self.tokens[self.pos + self.at(TokenType::Comma) as usize].span
+                            }
+                        };
+                        let message = "named arguments must follow bare *";
+                        self.invalid(|_| Some(SyntaxError::new(message, span)));
+                    }
+                    return None;
+                }
+            } else if self.expect(TokenType::DoubleStar).is_some() {
+                arguments.kwarg = Some(Box::new(self.param(annotated, false)?));
+                if self.at(TokenType::Equal) {
+                    let message = "var-keyword argument cannot have default value";
+                    let span = self.peek().span;
+                    self.invalid(|_| Some(SyntaxError::new(message, span)));
+                    return None;
+                }
+                if self.expect(TokenType::Comma).is_some() && !self.at(end.clone()) {
+                    self.invalid(|p| {
+                        let span = match p.peek_type(0) {
+                            TokenType::Star | TokenType::DoubleStar | TokenType::Slash => {
+                                p.peek().span
+                            }
+                            _ => p.param(annotated, false)?.span,
+                        };
+                        let message = "arguments cannot follow var-keyword argument";
+                        Some(SyntaxError::new(message, span))
+                    });
+                    return None;
+                }
+                break;
+            } else {
+                let param = self.param(annotated, false)?;
+                let default = match self.expect(TokenType::Equal) {
+                    Some(equal) if self.at(TokenType::Comma) || self.at(TokenType::RPar) => {
+                        let message = "expected default value expression";
+                        self.invalid(|_| Some(SyntaxError::new(message, equal)));
+                        return None;
+                    }
+                    Some(_) => Some(self.expression()?),
+                    None => None,
+                };
+                if star {
+                    arguments.kwonlyargs.push(param);
+                    arguments.kw_defaults.push(default);
+                } else {
+                    let span = param.span;
+                    arguments.args.push(param);
+                    match default {
+                        Some(default) => arguments.defaults.push(default),
+                        None if !arguments.defaults.is_empty() => {
+                            if self.at(TokenType::Comma) || self.at(end.clone()) {
+                                let message =
+                                    "parameter without a default follows parameter with a default";
+                                self.invalid(|_| Some(SyntaxError::new(message, span)));
+                            }
+                            return None;
+                        }
+                        None => (),
+                    }
+                }
+            }
+            if self.expect(TokenType::Comma).is_none() {
+                break;
+            }
+        }
+        // A bare `*` must be followed by keyword-only parameters.
+        if star && arguments.vararg.is_none() && arguments.kwonlyargs.is_empty() {
+            return None;
+        }
+        self.at(end).then_some(arguments)
+    }
+
+    /// param: NAME [':' expression], or `':' star_expression` for `*args` if `starred`.
+    fn param(&mut self, annotated: bool, starred: bool) -> Option<Arg> {
+        let start = self.pos;
+        let (arg, _) = self.name()?;
+        let annotation = match annotated && self.expect(TokenType::Colon).is_some() {
+            true if starred => Some(self.star_expression()?),
+            true => Some(self.expression()?),
+            false => None,
+        };
+        Some(Arg {
+            arg,
+            annotation: annotation.map(Box::new),
+            type_comment: None,
+            span: self.span_from(start),
+        })
+    }
+
+    /// strings: (STRING | fstring)+
+    ///
+    /// Adjacent literals are concatenated into a `Constant`, or a `JoinedStr` if any of them
+    /// is an f-string. Bytes cannot be mixed with other strings.
+    pub(super) fn strings(&mut self) -> Option<Expr> {
+        self.memo(Rule::Strings, |p| {
+            let start = p.pos;
+            let kind = p
+                .peek()
+                .value
+                .starts_with(['u', 'U'])
+                .then(|| "u".to_string());
+            let mut values: Vec<Expr> = vec![];
+            let mut bytes: Option<Vec<u8>> = None;
+            let mut fstring = false;
+            loop {
+                match p.peek_type(0) {
+                    TokenType::String => {
+                        let token = p.peek();
+                        let literal = token.literal.clone()?;
+                        let span = token.span;
+                        p.pos += 1;
+                        match (literal, &mut bytes) {
+                            (Literal::Bytes(value), Some(bytes)) => bytes.extend(value),
+                            (Literal::Bytes(value), None) if values.is_empty() => {
+                                bytes = Some(value)
+                            }
+                            (Literal::String(value), None) => {
+                                push_str(&mut values, value, span);
+                            }
+                            _ => return None,
+                        }
+                    }
+                    TokenType::FStringStart if bytes.is_none() => {
+                        fstring = true;
+                        for value in p.fstring()? {
+                            match value {
+                                Expr::Constant(Constant {
+                                    value: Literal::String(value),
+                                    span,
+                                    ..
+                                }) => push_str(&mut values, value, span),
+                                value => values.push(value),
+                            }
+                        }
+                    }
+                    _ if p.pos == start => {
+                        p.furthest = p.furthest.max(p.pos);
+                        return None;
+                    }
+                    _ => break,
+                }
+            }
+            let span = p.span_from(start);
+            if let Some(bytes) = bytes {
+                return Some(Expr::Constant(Constant {
+                    value: Literal::Bytes(bytes),
+                    kind: None,
+                    span,
+                }));
+            }
+            if fstring {
+                // Empty strings leave no constant among the values.
+                values.retain(|value| match value {
+                    Expr::Constant(Constant {
+                        value: Literal::String(value),
+                        ..
+                    }) => !value.is_empty(),
+                    _ => true,
+                });
+                return Some(Expr::JoinedStr(JoinedStr { values, span }));
+            }
+            let value = match values.pop() {
+                Some(Expr::Constant(constant)) => constant.value,
+                _ => Literal::String(String::new()),
+            };
+            Some(Expr::Constant(Constant { value, kind, span }))
+        })
+    }
+
+    /// fstring: FSTRING_START fstring_middle* FSTRING_END
+    fn fstring(&mut self) -> Option<Vec<Expr>> {
+        self.expect(TokenType::FStringStart)?;
+        let values = self.fstring_middle(TokenType::FStringEnd)?;
+        self.expect(TokenType::FStringEnd)?;
+        Some(values)
+    }
+
+    /// Literal text and replacement fields up to `end`, which is left unconsumed.
+    fn fstring_middle(&mut self, end: TokenType) -> Option<Vec<Expr>> {
+        let mut values = vec![];
+        while !self.at(end.clone()) {
+            if self.at(TokenType::LBrace) {
+                // The text of a `=` field joins the literal text before it.
+                for value in self.fstring_replacement_field()? {
+                    match value {
+                        Expr::Constant(Constant {
+                            value: Literal::String(value),
+                            span,
+                            ..
+                        }) => push_str(&mut values, value, span),
+                        value => values.push(value),
+                    }
+                }
+                continue;
+            }
+            let token = self.advance_if(|token| token.token_type == TokenType::FStringMiddle)?;
+            let (value, span) = (token.literal.clone()?, token.span);
+            if let Literal::String(value) = value {
+                push_str(&mut values, value, span);
+            }
+        }
+        Some(values)
+    }
+
+    /// fstring_replacement_field:
+    ///     '{' (yield_expr | star_expressions) ['='] ['!' NAME] [':' fstring_middle*] '}'
+    ///
+    /// A `=` adds the text of the field before its value, which is then shown with `repr()`
+    /// unless a conversion or format spec is given.
+    fn fstring_replacement_field(&mut self) -> Option<Vec<Expr>> {
+        let start = self.pos;
+        let lbrace = self.expect(TokenType::LBrace)?;
+        let value = if self.at(TokenType::Yield) {
+            self.yield_expr()?
+        } else {
+            self.star_expressions()?
+        };
+        let mut values = vec![];
+        if let Some(equal) = self.expect(TokenType::Equal) {
+            let feature = "f-string: self documenting expressions are";
+            self.check_version(PythonVersion::Py38, feature, equal);
+            // The text keeps the whitespace after the `=`, up to the `!`, `:` or `}`.
+            let next = self.peek().span;
+            let text = self.source_text(
+                (lbrace.end_lineno, lbrace.end_col_offset),
+                (next.lineno, next.col_offset),
+            );
+            let span = Span {
+                lineno: lbrace.end_lineno,
+                col_offset: lbrace.end_col_offset,
+                end_lineno: next.lineno,
+                end_col_offset: next.col_offset,
+            };
+            values.push(Expr::Constant(Constant {
+                value: Literal::String(strip_comments(text)),
+                kind: None,
+                span,
+            }));
+        }
+        let mut conversion = -1;
+        if let Some(exclamation) = self.expect(TokenType::Exclamation) {
+            let Some((name, span)) = self.name() else {
+                let message = match self.peek_type(0) {
+                    TokenType::Colon | TokenType::RBrace => {
+                        "f-string: missing conversion character"
+                    }
+                    _ => "f-string: invalid conversion character",
+                };
+                let span = self.peek().span;
+                self.invalid(|_| Some(SyntaxError::new(message, span)));
+                return None;
+            };
+            let adjacent = (span.lineno, span.col_offset)
+                == (exclamation.end_lineno, exclamation.end_col_offset);
+            if !adjacent || !matches!(name.as_str(), "s" | "r" | "a") {
+                let error = match adjacent {
+                    // CPython misspells "exclamation" here.
+                    false => SyntaxError::new(
+                        "f-string: conversion type must come right after the exclamanation mark",
+                        exclamation.to(span),
+                    ),
+                    true => SyntaxError::new(
+                        format!(
+                            "f-string: invalid conversion character '{}': \
+                             expected 's', 'r', or 'a'",
+                            name
+                        ),
+                        span,
+                    ),
+                };
+                self.invalid(|_| Some(error));
+                return None;
+            }
+            conversion = name.as_bytes()[0] as i32;
+        }
+        let mut format_spec = None;
+        let spec_start = self.pos;
+        if self.expect(TokenType::Colon).is_some() {
+            let values = self.fstring_middle(TokenType::RBrace)?;
+            format_spec = Some(Box::new(Expr::JoinedStr(JoinedStr {
+                values,
+                span: self.span_from(spec_start),
+            })));
+        }
+        self.expect(TokenType::RBrace)?;
+        if !values.is_empty() && conversion == -1 && format_spec.is_none() {
+            conversion = 'r' as i32;
+        }
+        values.push(Expr::FormattedValue(FormattedValue {
+            value: Box::new(value),
+            conversion,
+            format_spec,
+            span: self.span_from(start),
+        }));
+        Some(values)
+    }
+
+    /// star_targets: star_target !',' | star_target (',' star_target)* [',']
+    pub(super) fn star_targets(&mut self) -> Option<Expr> {
+        self.comma_tuple(Self::star_target, ExprContext::Store)
+    }
+
+    /// star_target: '*' (!'*' star_target) | target_with_star_atom
+    pub(super) fn star_target(&mut self) -> Option<Expr> {
+        self.memo(Rule::StarTarget, |p| {
+            let start = p.pos;
+            if p.expect(TokenType::Star).is_none() {
+                return p.target_with_star_atom();
+            }
+            if p.at(TokenType::Star) {
+                return None;
+            }
+            let value = p.star_target()?;
+            Some(Expr::Starred(Starred {
+                value: Box::new(value),
+                ctx: ExprContext::Store,
+                span: p.span_from(start),
+            }))
+        })
+    }
+
+    /// target_with_star_atom:
+    ///     | t_primary '.' NAME !t_lookahead
+    ///     | t_primary '[' slices ']' !t_lookahead
+    ///     | star_atom
+    pub(super) fn target_with_star_atom(&mut self) -> Option<Expr> {
+        self.memo(Rule::TargetWithStarAtom, |p| {
+            p.attempt(Self::single_subscript_attribute_target)
+                .or_else(|| p.star_atom())
+        })
+    }
+
+    /// single_subscript_attribute_target:
+    ///     | t_primary '.' NAME !t_lookahead
+    ///     | t_primary '[' slices ']' !t_lookahead
+    pub(super) fn single_subscript_attribute_target(&mut self) -> Option<Expr> {
+        let expr = self.primary()?;
+        let lookahead = matches!(
+            self.peek_type(0),
+            TokenType::LPar | TokenType::LSqB | TokenType::Dot
+        );
+        match expr {
+            Expr::Attribute(_) | Expr::Subscript(_) if !lookahead => {
+                Some(set_context(expr, ExprContext::Store))
+            }
+            _ => None,
+        }
+    }
+
+    /// star_atom:
+    ///     | NAME
+    ///     | '(' target_with_star_atom ')'
+    ///     | '(' [star_targets_tuple_seq] ')'
+    ///     | '[' [star_targets_list_seq] ']'
+    fn star_atom(&mut self) -> Option<Expr> {
+        let start = self.pos;
+        if let Some((id, span)) = self.name() {
+            return Some(Expr::Name(Name {
+                id,
+                ctx: ExprContext::Store,
+                span,
+            }));
+        }
+        let close = match self.peek_type(0) {
+            TokenType::LPar => TokenType::RPar,
+            TokenType::LSqB => TokenType::RSqB,
+            _ => return None,
+        };
+        self.pos += 1;
+        if close == TokenType::RPar {
+            if let Some(target) = self.attempt(|p| {
+                let target = p.target_with_star_atom()?;
+                p.expect(TokenType::RPar)?;
+                Some(target)
+            }) {
+                return Some(target);
+            }
+        }
+        let elts = if self.at(close.clone()) {
+            vec![]
+        } else {
+            let elts = self.comma_list(Self::star_target)?;
+            // A parenthesized target needs a comma to be a tuple.
+            let trailing = self.tokens[self.pos - 1].token_type == TokenType::Comma;
+            if close == TokenType::RPar && elts.len() == 1 && !trailing {
+                return None;
+            }
+            elts
+        };
+        self.expect(close.clone())?;
+        let span = self.span_from(start);
+        let ctx = ExprContext::Store;
+        Some(match close {
+            TokenType::RPar => Expr::Tuple(Tuple { elts, ctx, span }),
+            _ => Expr::List(List { elts, ctx, span }),
+        })
+    }
+
+    /// `item (',' item)* [',']`, as a tuple in context `ctx` if there is a comma.
+    fn comma_tuple(
+        &mut self,
+        item: fn(&mut Self) -> Option<Expr>,
+        ctx: ExprContext,
+    ) -> Option<Expr> {
+        let start = self.pos;
+        let first = item(self)?;
+        if !self.at(TokenType::Comma) {
+            return Some(first);
+        }
+        let mut elts = vec![first];
+        while self.expect(TokenType::Comma).is_some() {
+            match self.attempt(item) {
+                Some(elt) => elts.push(elt),
+                None => break,
+            }
+        }
+        Some(Expr::Tuple(Tuple {
+            elts,
+            ctx,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// `','.item+ [',']`
+    pub(super) fn comma_list<T>(
+        &mut self,
+        item: impl Fn(&mut Self) -> Option<T>,
+    ) -> Option<Vec<T>> {
+        let mut items = vec![item(self)?];
+        while self.expect(TokenType::Comma).is_some() {
+            match self.attempt(&item) {
+                Some(next) => items.push(next),
+                None => break,
+            }
+        }
+        Some(items)
+    }
+}
+
+/// The text of a `=` field without its comments. Like CPython, this cuts from any `#` to
+/// the end of its line, even one within a string.
+fn strip_comments(text: &str) -> String {
+    text.split_inclusive('\n')
+        .map(|line| match line.find('#') {
+            Some(hash) if line.ends_with('\n') => format!("{}\n", &line[..hash]),
+            Some(hash) => line[..hash].to_string(),
+            None => line.to_string(),
+        })
+        .collect()
+}
+
+/// Appends literal text to the values of a string, merging it with a preceding constant.
+fn push_str(values: &mut Vec<Expr>, value: String, span: Span) {
+    if let Some(Expr::Constant(Constant {
+        value: Literal::String(last),
+        span: last_span,
+        ..
+    })) = values.last_mut()
+    {
+        last.push_str(&value);
+        *last_span = last_span.to(span);
+        return;
+    }
+    if value.is_empty() && !values.is_empty() {
+        return;
+    }
+    values.push(Expr::Constant(Constant {
+        value: Literal::String(value),
+        kind: None,
+        span,
+    }));
+}
+
+/// Gives `expr`, a valid target, the context `ctx`, along with its starred or nested elements.
+pub(super) fn set_context(expr: Expr, ctx: ExprContext) -> Expr {
+    match expr {
+        Expr::Name(node) => Expr::Name(Name { ctx, ..node }),
+        Expr::Attribute(node) => Expr::Attribute(Attribute { ctx, ..node }),
+        Expr::Subscript(node) => Expr::Subscript(Subscript { ctx, ..node }),
+        Expr::Starred(node) => Expr::Starred(Starred {
+            value: Box::new(set_context(*node.value, ctx)),
+            ctx,
+            span: node.span,
+        }),
+        Expr::Tuple(node) => Expr::Tuple(Tuple {
+            elts: node.elts.into_iter().map(|e| set_context(e, ctx)).collect(),
+            ctx,
+            span: node.span,
+        }),
+        Expr::List(node) => Expr::List(List {
+            elts: node.elts.into_iter().map(|e| set_context(e, ctx)).collect(),
+            ctx,
+            span: node.span,
+        }),
+        expr => expr,
+    }
+}
@@ -0,0 +1,104 @@
+// Generated by `pragrat generate parser` from `func_type.gram`. Do not edit.
+use crate::types::TokenType;
+use super::{Parser, Rule};
+use crate::ast::{Expr, FunctionType, Mod};
+
+impl Parser {
+	/// func_type: '(' [type_expressions] ')' '->' expression NEWLINE* ENDMARKER
+	pub(super) fn func_type(&mut self) -> Option<Mod> {
+		let p = self;
+		p.attempt(|p| {
+			p.token(TokenType::LPar)?;
+			let a = p.attempt(|p| p.type_expressions());
+			p.token(TokenType::RPar)?;
+			p.token(TokenType::RArrow)?;
+			let b = p.expression()?;
+			while p.attempt(|p| p.token(TokenType::Newline)).is_some() {}
+			p.token(TokenType::Endmarker)?;
+			Some(Mod::FunctionType(FunctionType { argtypes: a.unwrap_or_default(), returns: Box::new(b) }))
+		})
+	}
+
+	/// type_expressions:
+	///     | type_expression_list ',' '*' expression ',' '**' expression
+	///     | type_expression_list ',' '*' expression
+	///     | type_expression_list ',' '**' expression
+	///     | '*' expression ',' '**' expression
+	///     | '*' expression
+	///     | '**' expression
+	///     | type_expression_list
+	pub(super) fn type_expressions(&mut self) -> Option<Vec<Expr>> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			let a = p.type_expression_list()?;
+			p.token(TokenType::Comma)?;
+			p.token(TokenType::Star)?;
+			let b = p.expression()?;
+			p.token(TokenType::Comma)?;
+			p.token(TokenType::DoubleStar)?;
+			let c = p.expression()?;
+			Some([a, vec![b, c]].concat())
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let a = p.type_expression_list()?;
+			p.token(TokenType::Comma)?;
+			p.token(TokenType::Star)?;
+			let b = p.expression()?;
+			Some([a, vec![b]].concat())
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let a = p.type_expression_list()?;
+			p.token(TokenType::Comma)?;
+			p.token(TokenType::DoubleStar)?;
+			let b = p.expression()?;
+			Some([a, vec![b]].concat())
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			p.token(TokenType::Star)?;
+			let a = p.expression()?;
+			p.token(TokenType::Comma)?;
+			p.token(TokenType::DoubleStar)?;
+			let b = p.expression()?;
+			Some(vec![a, b])
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			p.token(TokenType::Star)?;
+			let a = p.expression()?;
+			Some(vec![a])
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			p.token(TokenType::DoubleStar)?;
+			let a = p.expression()?;
+			Some(vec![a])
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| p.type_expression_list())
+	}
+
+	/// type_expression_list: ','.expression+
+	pub(super) fn type_expression_list(&mut self) -> Option<Vec<Expr>> {
+		self.memo(Rule::Generated("type_expression_list"), |p| {
+			p.attempt(|p| {
+				let mut items = vec![p.expression()?];
+				while let Some(item) = p.attempt(|p| {
+					p.token(TokenType::Comma)?;
+					p.expression()
+				}) {
+					items.push(item);
+				}
+				Some(items)
+			})
+		})
+	}
+}
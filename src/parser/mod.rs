@@ -0,0 +1,1054 @@
+//! Packrat parser building the enum AST from the token stream, following the PEG grammar of
+//! CPython's `Grammar/python.gram`. Rules are methods returning `None` on failure, after
+//! which the caller backtracks; results of the rules in `Rule` are memoized per position.
+//! Left-recursive rules such as `sum: sum '+' term | term` are written as in the grammar and
+//! memoized with `memo_left_rec`, which grows their result from a failing seed. The rules of
+//! `func_type.rs`, `pattern.rs` and `type_params.rs` are generated from the `.gram` grammars
+//! next to them by `pragrat generate parser`. When parsing fails, a second pass with the
+//! `invalid_` rules of `error.rs` looks for a more specific error than "invalid syntax".
+//! `parse_recovering` instead carries on past each error, as described in `recovery.rs`.
+
+mod error;
+mod expr;
+#[rustfmt::skip]
+mod func_type;
+#[rustfmt::skip]
+mod pattern;
+mod recovery;
+#[cfg(test)]
+#[rustfmt::skip]
+mod test_grammar;
+mod stmt;
+#[rustfmt::skip]
+mod type_params;
+
+use std::{any::Any, collections::HashMap};
+
+use unicode_normalization::UnicodeNormalization;
+
+pub use self::error::SyntaxError;
+use crate::ast::{Expr, Expression, Interactive, Mod, Module, Stmt};
+use crate::tokenizer::Tokenizer;
+use crate::types::{Identifier, PythonVersion, Span, Token, TokenType};
+
+/// What the source holds, like the `mode` argument of `ast.parse`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Mode {
+    /// A sequence of statements, parsed into a `Module`.
+    Exec,
+    /// A single expression, parsed into an `Expression`.
+    Eval,
+    /// A single statement as typed at the interactive prompt, parsed into an `Interactive`.
+    Single,
+    /// A function signature type comment such as `(int, str) -> bool`, parsed into a
+    /// `FunctionType`.
+    #[value(name = "func_type")]
+    FuncType,
+}
+
+/// Parses `source` into the root node of `mode`, like `ast.parse(source, mode=...)`.
+pub fn parse(source: &str, mode: Mode) -> Result<Mod, SyntaxError> {
+    parse_with_version(source, mode, PythonVersion::default())
+}
+
+/// Parses `source` like `parse`, rejecting syntax added after `version`, like
+/// `ast.parse(source, mode=..., feature_version=...)`.
+pub fn parse_with_version(
+    source: &str,
+    mode: Mode,
+    version: PythonVersion,
+) -> Result<Mod, SyntaxError> {
+    let mut parser = Parser::new(source, version)?;
+    match mode {
+        Mode::Exec => parser.finish(Parser::file),
+        Mode::Eval => parser.finish(|p| {
+            let body = Box::new(p.eval()?);
+            Some(Mod::Expression(Expression { body }))
+        }),
+        Mode::Single => parser.finish(Parser::interactive),
+        Mode::FuncType => parser.finish(Parser::func_type),
+    }
+}
+
+/// Parses `source` as a module in spite of syntax errors, returning every error along with
+/// the module, in which the statements and expressions that could not be parsed are replaced
+/// by `ErrorStmt` and `ErrorExpr` nodes. Syntax added after `version` is reported but kept.
+pub fn parse_recovering(source: &str, version: PythonVersion) -> (Module, Vec<SyntaxError>) {
+    let (mut parser, mut errors) = Parser::scan(source, version);
+    parser.recovering = true;
+    let body = parser.attempt(Parser::statements).unwrap_or_default();
+    errors.append(&mut parser.errors);
+    errors.extend(parser.unclosed);
+    // Statements parsed more than once, such as those of a block of a failed statement,
+    // report their errors again.
+    errors.sort_by_key(|error| (error.span.lineno, error.span.col_offset));
+    errors.dedup();
+    let module = Module {
+        body,
+        type_ignores: vec![],
+    };
+    (module, errors)
+}
+
+/// Parses a single expression, like `ast.parse(source, mode="eval").body`.
+pub fn parse_expression(source: &str) -> Result<Expr, SyntaxError> {
+    Parser::new(source, PythonVersion::default())?.finish(|p| p.eval())
+}
+
+/// Rules whose results are memoized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Rule {
+    Expression,
+    Disjunction,
+    Conjunction,
+    Inversion,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseAnd,
+    ShiftExpr,
+    Sum,
+    Term,
+    Factor,
+    AwaitPrimary,
+    Primary,
+    Arguments,
+    Strings,
+    StarTarget,
+    TargetWithStarAtom,
+    /// A rule of a generated parser, by name.
+    Generated(&'static str),
+    #[cfg(test)]
+    TestChain,
+    #[cfg(test)]
+    TestCall,
+    #[cfg(test)]
+    TestCallee,
+}
+
+pub(crate) struct Parser {
+    source: String,
+    /// Byte offset at which each line of `source` starts.
+    line_starts: Vec<usize>,
+    /// Tokens without comments and non-logical newlines, ending with an `Endmarker`.
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Result of a rule at a position, and the position following it.
+    memo: HashMap<(Rule, usize), (Box<dyn Any>, usize)>,
+    /// The furthest position a token was expected at, where a failed parse is reported.
+    furthest: usize,
+    /// Positions at which a left-recursive rule is growing its seed. Other rules are not
+    /// memoized there meanwhile, since their results may still change.
+    growing: Vec<usize>,
+    /// Whether this is the second pass, which runs the `invalid_` rules.
+    call_invalid_rules: bool,
+    /// The error raised by an invalid rule, after which no more tokens are consumed.
+    error: Option<SyntaxError>,
+    /// Whether to carry on past statements that fail to parse, see `recovery.rs`.
+    recovering: bool,
+    /// The release to parse for, whose later syntax `check_version` reports.
+    version: PythonVersion,
+    /// Uses of syntax added after `version`, and the errors of the statements recovered from.
+    errors: Vec<SyntaxError>,
+    /// What stands in for each statement recovered from, by position, and the position
+    /// following it, for when a statement around it is parsed again.
+    recovered: HashMap<usize, (Vec<Stmt>, usize)>,
+    /// The error of the bracket left open at the end of the source, which CPython only
+    /// reports once the parser reads up to the end, or fails on a later line.
+    unclosed: Option<SyntaxError>,
+}
+
+impl Parser {
+    fn new(source: &str, version: PythonVersion) -> Result<Self, SyntaxError> {
+        let (parser, errors) = Self::scan(source, version);
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(parser),
+        }
+    }
+
+    /// Tokenizes `source`, returning the parser for its tokens and the tokenizer's errors.
+    fn scan(source: &str, version: PythonVersion) -> (Self, Vec<SyntaxError>) {
+        let mut tokenizer = Tokenizer::new(source.to_string()).with_version(version);
+        tokenizer.scan_tokens();
+        let mut errors: Vec<SyntaxError> = tokenizer
+            .errors()
+            .iter()
+            .map(|error| SyntaxError {
+                message: error.message.clone(),
+                hint: error.hint.clone(),
+                span: error.span,
+                kind: error.kind,
+            })
+            .collect();
+        let mut tokens: Vec<Token> = tokenizer
+            .into_tokens()
+            .into_iter()
+            .filter(|token| !matches!(token.token_type, TokenType::Comment | TokenType::NL))
+            .collect();
+        // The bracket left open is reported last, along with the last error token.
+        let unclosed = errors.pop_if(|error| error.message.ends_with("was never closed"));
+        if unclosed.is_some() {
+            let last = tokens
+                .iter()
+                .rposition(|token| token.token_type == TokenType::ErrorToken);
+            tokens.remove(last.expect("Error without a token"));
+        }
+        let source = source.to_string();
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let parser = Self {
+            source,
+            line_starts,
+            tokens,
+            pos: 0,
+            memo: HashMap::new(),
+            furthest: 0,
+            growing: Vec::new(),
+            call_invalid_rules: false,
+            error: None,
+            recovering: false,
+            version,
+            errors: Vec::new(),
+            recovered: HashMap::new(),
+            unclosed,
+        };
+        (parser, errors)
+    }
+
+    /// Runs the start rule `rule`. If it fails, runs it again with the invalid rules for the
+    /// error they raise, or else reports one at the furthest token the first pass reached.
+    /// Syntax added after the target version parses, but is reported unless an error comes
+    /// before it.
+    fn finish<T>(&mut self, rule: impl Fn(&mut Self) -> Option<T>) -> Result<T, SyntaxError> {
+        match rule(self) {
+            Some(result) if self.errors.is_empty() => return Ok(result),
+            Some(_) => {}
+            None => {
+                let error = self.syntax_error(rule);
+                self.errors.push(error);
+            }
+        }
+        let error = self
+            .errors
+            .iter()
+            .min_by_key(|error| (error.span.lineno, error.span.col_offset))
+            .expect("Failed without an error");
+        Err(error.clone())
+    }
+
+    /// The error of the start rule `rule`, after it failed to parse.
+    fn syntax_error<T>(&mut self, rule: impl Fn(&mut Self) -> Option<T>) -> SyntaxError {
+        let furthest = self.furthest;
+        self.pos = 0;
+        self.memo.clear();
+        self.call_invalid_rules = true;
+        rule(self);
+        if let Some(error) = self.error.take() {
+            return self.or_unclosed(error);
+        }
+        let token = &self.tokens[furthest.min(self.tokens.len() - 1)];
+        let error = SyntaxError::at_token(token);
+        self.or_unclosed(error)
+    }
+
+    /// `error`, or the error of the bracket left open if CPython reports that instead: when
+    /// either pass read up to the end, or past the line of the bracket.
+    fn or_unclosed(&mut self, error: SyntaxError) -> SyntaxError {
+        let furthest = self.furthest.min(self.tokens.len() - 1);
+        let at_end = self.tokens[furthest..].iter().all(|token| {
+            matches!(
+                token.token_type,
+                TokenType::Newline | TokenType::Dedent | TokenType::Endmarker
+            )
+        });
+        let line = self.tokens[furthest].span.lineno;
+        self.unclosed
+            .take_if(|unclosed| at_end || line > unclosed.span.lineno)
+            .unwrap_or(error)
+    }
+
+    /// file: [statements] ENDMARKER
+    fn file(&mut self) -> Option<Mod> {
+        let body = self.attempt(Self::statements).unwrap_or_default();
+        self.expect(TokenType::Endmarker)?;
+        Some(Mod::Module(Module {
+            body,
+            type_ignores: vec![],
+        }))
+    }
+
+    /// interactive: statement_newline NEWLINE* ENDMARKER
+    ///
+    /// CPython reads a single statement here and rejects any that follow it when compiling.
+    fn interactive(&mut self) -> Option<Mod> {
+        let body: Vec<Stmt> = self.statement_newline()?;
+        while self.expect(TokenType::Newline).is_some() {}
+        self.expect(TokenType::Endmarker)?;
+        Some(Mod::Interactive(Interactive { body }))
+    }
+
+    /// eval: expressions NEWLINE* ENDMARKER
+    fn eval(&mut self) -> Option<Expr> {
+        let body = self.expressions()?;
+        while self.expect(TokenType::Newline).is_some() {}
+        self.expect(TokenType::Endmarker)?;
+        Some(body)
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos.min(self.tokens.len() - 1)]
+    }
+
+    /// Type of the token `n` positions ahead.
+    fn peek_type(&self, n: usize) -> &TokenType {
+        let pos = (self.pos + n).min(self.tokens.len() - 1);
+        &self.tokens[pos].token_type
+    }
+
+    fn at(&self, token_type: TokenType) -> bool {
+        *self.peek_type(0) == token_type
+    }
+
+    /// Consumes a token of type `token_type`, returning its span.
+    fn expect(&mut self, token_type: TokenType) -> Option<Span> {
+        self.advance_if(|token| token.token_type == token_type)
+            .map(|token| token.span)
+    }
+
+    /// Consumes a token of type `token_type`, returning it.
+    fn token(&mut self, token_type: TokenType) -> Option<Token> {
+        self.advance_if(|token| token.token_type == token_type)
+            .cloned()
+    }
+
+    /// Consumes the next token if it satisfies `pred`.
+    fn advance_if(&mut self, pred: impl FnOnce(&Token) -> bool) -> Option<&Token> {
+        self.furthest = self.furthest.max(self.pos);
+        if self.error.is_none() && self.pos < self.tokens.len() && pred(&self.tokens[self.pos]) {
+            self.pos += 1;
+            Some(&self.tokens[self.pos - 1])
+        } else {
+            None
+        }
+    }
+
+    /// Consumes the next token if `map` accepts its type, e.g. to read an operator.
+    fn advance_map<T>(&mut self, map: impl FnOnce(&TokenType) -> Option<T>) -> Option<T> {
+        self.furthest = self.furthest.max(self.pos);
+        if self.error.is_some() {
+            return None;
+        }
+        let value = map(self.peek_type(0))?;
+        self.pos += 1;
+        Some(value)
+    }
+
+    /// NAME, returning the identifier and its span.
+    fn name(&mut self) -> Option<(String, Span)> {
+        self.advance_if(|token| token.token_type == TokenType::Name)
+            .map(|token| (identifier(token), token.span))
+    }
+
+    /// Runs `rule`, restoring the position if it fails.
+    fn attempt<T>(&mut self, rule: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let mark = self.pos;
+        let result = rule(self);
+        if result.is_none() {
+            self.pos = mark;
+        }
+        result
+    }
+
+    /// Runs `rule` once per position, replaying its result and end position afterwards.
+    fn memo<T: Clone + 'static>(
+        &mut self,
+        rule: Rule,
+        parse: impl FnOnce(&mut Self) -> Option<T>,
+    ) -> Option<T> {
+        let key = (rule, self.pos);
+        if let Some((result, end)) = self.memo.get(&key) {
+            let result = result
+                .downcast_ref::<Option<T>>()
+                .expect("Rule memoized with another type")
+                .clone();
+            self.pos = *end;
+            return result;
+        }
+        let result = self.attempt(parse);
+        if !self.growing.contains(&key.1) {
+            self.memo.insert(key, (Box::new(result.clone()), self.pos));
+        }
+        result
+    }
+
+    /// Memoizes a left-recursive rule, or the leader of a cycle of rules reaching back to it
+    /// at the same position. The rule first fails there, then is parsed again with its
+    /// previous result memoized for as long as that consumes more tokens.
+    fn memo_left_rec<T: Clone + 'static>(
+        &mut self,
+        rule: Rule,
+        parse: impl Fn(&mut Self) -> Option<T>,
+    ) -> Option<T> {
+        let key = (rule, self.pos);
+        if let Some((result, end)) = self.memo.get(&key) {
+            let result = result
+                .downcast_ref::<Option<T>>()
+                .expect("Rule memoized with another type")
+                .clone();
+            self.pos = *end;
+            return result;
+        }
+        let start = self.pos;
+        self.memo.insert(key, (Box::new(None::<T>), start));
+        self.growing.push(start);
+        let mut end = start;
+        loop {
+            self.pos = start;
+            let Some(grown) = parse(self) else {
+                break;
+            };
+            if self.pos <= end {
+                break;
+            }
+            end = self.pos;
+            self.memo.insert(key, (Box::new(Some(grown)), end));
+        }
+        self.growing.pop();
+        self.pos = end;
+        self.memo[&key]
+            .0
+            .downcast_ref::<Option<T>>()
+            .expect("Rule memoized with another type")
+            .clone()
+    }
+
+    /// Span from the token at `start` through the last consumed token, leaving out the
+    /// newlines and dedents closing a block.
+    fn span_from(&self, start: usize) -> Span {
+        let mut end = self.pos.max(start + 1) - 1;
+        while end > start
+            && matches!(
+                self.tokens[end].token_type,
+                TokenType::Newline | TokenType::Indent | TokenType::Dedent
+            )
+        {
+            end -= 1;
+        }
+        self.tokens[start].span.to(self.tokens[end].span)
+    }
+
+    /// Source text between two positions given as (line, column) pairs.
+    fn source_text(&self, from: (usize, usize), to: (usize, usize)) -> &str {
+        let offset = |(lineno, col): (usize, usize)| self.line_starts[lineno - 1] + col;
+        &self.source[offset(from)..offset(to)]
+    }
+}
+
+/// The identifier a NAME token spells, NFKC-normalized as PEP 3131 requires, so that
+/// `ｗｉｄｔｈ` is `width`.
+fn identifier(token: &Token) -> Identifier {
+    if token.value.is_ascii() {
+        return token.value.clone();
+    }
+    token.value.nfkc().collect()
+}
+
+#[test]
+fn test_parse() {
+    use crate::dump::dump;
+
+    // Expected outputs were produced by CPython 3.12's `ast.dump(ast.parse(source, mode=...))`.
+    let cases = [
+        (
+            Mode::Exec,
+            "import a.b as c, d\nfrom ..m import (x, y as z,)\nfrom . import *\nglobal g, h\n",
+            "Module(body=[Import(names=[alias(name='a.b', asname='c'), alias(name='d')]), ImportFrom(module='m', names=[alias(name='x'), alias(name='y', asname='z')], level=2), ImportFrom(names=[alias(name='*')], level=1), Global(names=['g', 'h'])], type_ignores=[])",
+        ),
+        (
+            Mode::Exec,
+            "x = y = 1, 2\nx: int = 3\n(a): b\na.b[0] += yield\ndel a, (b, c), d[0]\nassert x, 'm'; pass\n",
+            "Module(body=[Assign(targets=[Name(id='x', ctx=Store()), Name(id='y', ctx=Store())], value=Tuple(elts=[Constant(value=1), Constant(value=2)], ctx=Load())), AnnAssign(target=Name(id='x', ctx=Store()), annotation=Name(id='int', ctx=Load()), value=Constant(value=3), simple=1), AnnAssign(target=Name(id='a', ctx=Store()), annotation=Name(id='b', ctx=Load()), simple=0), AugAssign(target=Subscript(value=Attribute(value=Name(id='a', ctx=Load()), attr='b', ctx=Load()), slice=Constant(value=0), ctx=Store()), op=Add(), value=Yield()), Delete(targets=[Name(id='a', ctx=Del()), Tuple(elts=[Name(id='b', ctx=Del()), Name(id='c', ctx=Del())], ctx=Del()), Subscript(value=Name(id='d', ctx=Load()), slice=Constant(value=0), ctx=Del())]), Assert(test=Name(id='x', ctx=Load()), msg=Constant(value='m')), Pass()], type_ignores=[])",
+        ),
+        (
+            Mode::Exec,
+            "@dec\n@d.e(1)\nasync def f(a, /, b: int = 1, *args: str, c, d=2, **kw) -> None:\n    return await g()\n\nclass C(B, metaclass=M):\n    '''doc'''\n    x = 1\n",
+            "Module(body=[AsyncFunctionDef(name='f', args=arguments(posonlyargs=[arg(arg='a')], args=[arg(arg='b', annotation=Name(id='int', ctx=Load()))], vararg=arg(arg='args', annotation=Name(id='str', ctx=Load())), kwonlyargs=[arg(arg='c'), arg(arg='d')], kw_defaults=[None, Constant(value=2)], kwarg=arg(arg='kw'), defaults=[Constant(value=1)]), body=[Return(value=Await(value=Call(func=Name(id='g', ctx=Load()), args=[], keywords=[])))], decorator_list=[Name(id='dec', ctx=Load()), Call(func=Attribute(value=Name(id='d', ctx=Load()), attr='e', ctx=Load()), args=[Constant(value=1)], keywords=[])], returns=Constant(value=None), type_params=[]), ClassDef(name='C', bases=[Name(id='B', ctx=Load())], keywords=[keyword(arg='metaclass', value=Name(id='M', ctx=Load()))], body=[Expr(value=Constant(value='doc')), Assign(targets=[Name(id='x', ctx=Store())], value=Constant(value=1))], decorator_list=[], type_params=[])], type_ignores=[])",
+        ),
+        (
+            Mode::Exec,
+            "if a:\n    pass\nelif b:\n    break\nelse:\n    continue\nwhile x: y\nelse: z\nfor i, *j in k:\n    if i: raise E from e\nelse:\n    raise\n",
+            "Module(body=[If(test=Name(id='a', ctx=Load()), body=[Pass()], orelse=[If(test=Name(id='b', ctx=Load()), body=[Break()], orelse=[Continue()])]), While(test=Name(id='x', ctx=Load()), body=[Expr(value=Name(id='y', ctx=Load()))], orelse=[Expr(value=Name(id='z', ctx=Load()))]), For(target=Tuple(elts=[Name(id='i', ctx=Store()), Starred(value=Name(id='j', ctx=Store()), ctx=Store())], ctx=Store()), iter=Name(id='k', ctx=Load()), body=[If(test=Name(id='i', ctx=Load()), body=[Raise(exc=Name(id='E', ctx=Load()), cause=Name(id='e', ctx=Load()))], orelse=[])], orelse=[Raise()])], type_ignores=[])",
+        ),
+        (
+            Mode::Exec,
+            "with (open(a) as f, b as [c, d],):\n    pass\nasync with a, b as c: pass\nwith (a): pass\n",
+            "Module(body=[With(items=[withitem(context_expr=Call(func=Name(id='open', ctx=Load()), args=[Name(id='a', ctx=Load())], keywords=[]), optional_vars=Name(id='f', ctx=Store())), withitem(context_expr=Name(id='b', ctx=Load()), optional_vars=List(elts=[Name(id='c', ctx=Store()), Name(id='d', ctx=Store())], ctx=Store()))], body=[Pass()]), AsyncWith(items=[withitem(context_expr=Name(id='a', ctx=Load())), withitem(context_expr=Name(id='b', ctx=Load()), optional_vars=Name(id='c', ctx=Store()))], body=[Pass()]), With(items=[withitem(context_expr=Name(id='a', ctx=Load()))], body=[Pass()])], type_ignores=[])",
+        ),
+        (
+            Mode::Exec,
+            "try:\n    pass\nexcept (A, B) as e:\n    pass\nexcept:\n    pass\nelse:\n    pass\nfinally:\n    pass\ntry:\n    pass\nexcept* E:\n    pass\n",
+            "Module(body=[Try(body=[Pass()], handlers=[ExceptHandler(type=Tuple(elts=[Name(id='A', ctx=Load()), Name(id='B', ctx=Load())], ctx=Load()), name='e', body=[Pass()]), ExceptHandler(body=[Pass()])], orelse=[Pass()], finalbody=[Pass()]), TryStar(body=[Pass()], handlers=[ExceptHandler(type=Name(id='E', ctx=Load()), body=[Pass()])], orelse=[], finalbody=[])], type_ignores=[])",
+        ),
+        (
+            Mode::Exec,
+            "def f():\n\tnonlocal x\n\tif x:\n\t\treturn\n\n# done\n",
+            "Module(body=[FunctionDef(name='f', args=arguments(posonlyargs=[], args=[], kwonlyargs=[], kw_defaults=[], defaults=[]), body=[Nonlocal(names=['x']), If(test=Name(id='x', ctx=Load()), body=[Return()], orelse=[])], decorator_list=[], type_params=[])], type_ignores=[])",
+        ),
+        (
+            Mode::Exec,
+            "match x, *y:\n    case 1 | -1j | 1 + 2j | 'a' | None:\n        pass\n    case [a, *_] | (b, c) if a:\n        pass\n    case {1: a, m.n: b, **rest}:\n        pass\n    case p.Q(1, z=_) as q:\n        pass\nmatch = 1\n",
+            "Module(body=[Match(subject=Tuple(elts=[Name(id='x', ctx=Load()), Starred(value=Name(id='y', ctx=Load()), ctx=Load())], ctx=Load()), cases=[match_case(pattern=MatchOr(patterns=[MatchValue(value=Constant(value=1)), MatchValue(value=UnaryOp(op=USub(), operand=Constant(value=1j))), MatchValue(value=BinOp(left=Constant(value=1), op=Add(), right=Constant(value=2j))), MatchValue(value=Constant(value='a')), MatchSingleton(value=None)]), body=[Pass()]), match_case(pattern=MatchOr(patterns=[MatchSequence(patterns=[MatchAs(name='a'), MatchStar()]), MatchSequence(patterns=[MatchAs(name='b'), MatchAs(name='c')])]), guard=Name(id='a', ctx=Load()), body=[Pass()]), match_case(pattern=MatchMapping(keys=[Constant(value=1), Attribute(value=Name(id='m', ctx=Load()), attr='n', ctx=Load())], patterns=[MatchAs(name='a'), MatchAs(name='b')], rest='rest'), body=[Pass()]), match_case(pattern=MatchAs(pattern=MatchClass(cls=Attribute(value=Name(id='p', ctx=Load()), attr='Q', ctx=Load()), patterns=[MatchValue(value=Constant(value=1))], kwd_attrs=['z'], kwd_patterns=[MatchAs()]), name='q'), body=[Pass()])]), Assign(targets=[Name(id='match', ctx=Store())], value=Constant(value=1))], type_ignores=[])",
+        ),
+        (
+            Mode::Exec,
+            "def f[T](x: T): pass\nclass C[T: int, *Ts, **P,](B): pass\ntype Alias[T] = list[T]\ntype = 1\n",
+            "Module(body=[FunctionDef(name='f', args=arguments(posonlyargs=[], args=[arg(arg='x', annotation=Name(id='T', ctx=Load()))], kwonlyargs=[], kw_defaults=[], defaults=[]), body=[Pass()], decorator_list=[], type_params=[TypeVar(name='T')]), ClassDef(name='C', bases=[Name(id='B', ctx=Load())], keywords=[], body=[Pass()], decorator_list=[], type_params=[TypeVar(name='T', bound=Name(id='int', ctx=Load())), TypeVarTuple(name='Ts'), ParamSpec(name='P')]), TypeAlias(name=Name(id='Alias', ctx=Store()), type_params=[TypeVar(name='T')], value=Subscript(value=Name(id='list', ctx=Load()), slice=Name(id='T', ctx=Load()), ctx=Load())), Assign(targets=[Name(id='type', ctx=Store())], value=Constant(value=1))], type_ignores=[])",
+        ),
+        (
+            Mode::Exec,
+            "f'{x = }{y=!s:>{w}}'\n",
+            "Module(body=[Expr(value=JoinedStr(values=[Constant(value='x = '), FormattedValue(value=Name(id='x', ctx=Load()), conversion=114), Constant(value='y='), FormattedValue(value=Name(id='y', ctx=Load()), conversion=115, format_spec=JoinedStr(values=[Constant(value='>'), FormattedValue(value=Name(id='w', ctx=Load()), conversion=-1)]))]))], type_ignores=[])",
+        ),
+        (
+            Mode::Exec,
+            "\u{ff57}\u{ff49}\u{ff44}\u{ff54}\u{ff48} = x\u{e0100} = \u{2118}\n",
+            "Module(body=[Assign(targets=[Name(id='width', ctx=Store()), Name(id='x\u{e0100}', ctx=Store())], value=Name(id='\u{2118}', ctx=Load()))], type_ignores=[])",
+        ),
+        (
+            Mode::Single,
+            "x = 1",
+            "Interactive(body=[Assign(targets=[Name(id='x', ctx=Store())], value=Constant(value=1))])",
+        ),
+        (
+            Mode::Single,
+            "if x:\n    y\n\n",
+            "Interactive(body=[If(test=Name(id='x', ctx=Load()), body=[Expr(value=Name(id='y', ctx=Load()))], orelse=[])])",
+        ),
+        (
+            Mode::Eval,
+            "a + 1\n",
+            "Expression(body=BinOp(left=Name(id='a', ctx=Load()), op=Add(), right=Constant(value=1)))",
+        ),
+        (
+            Mode::FuncType,
+            "(int, *str, **bool) -> None",
+            "FunctionType(argtypes=[Name(id='int', ctx=Load()), Name(id='str', ctx=Load()), Name(id='bool', ctx=Load())], returns=Constant(value=None))",
+        ),
+        (
+            Mode::Exec,
+            "",
+            "Module(body=[], type_ignores=[])",
+        ),
+    ];
+    for (mode, source, expected) in cases {
+        match parse(source, mode) {
+            Ok(tree) => assert_eq!(dump(&tree, None, false), expected, "{}", source),
+            Err(error) => panic!("{:?} failed to parse: {:?}", source, error),
+        }
+    }
+}
+
+#[test]
+fn test_parse_patterns() {
+    use crate::ast::Stmt;
+    use crate::dump::dump;
+
+    // Expected outputs were produced by CPython's `ast.dump` of the pattern of the case.
+    let cases = [
+        (
+            "Point(x=0, y=0)",
+            "MatchClass(cls=Name(id='Point', ctx=Load()), patterns=[], kwd_attrs=['x', 'y'], kwd_patterns=[MatchValue(value=Constant(value=0)), MatchValue(value=Constant(value=0))])",
+        ),
+        (
+            "a.b.C(1, [2, *rest], k=D())",
+            "MatchClass(cls=Attribute(value=Attribute(value=Name(id='a', ctx=Load()), attr='b', ctx=Load()), attr='C', ctx=Load()), patterns=[MatchValue(value=Constant(value=1)), MatchSequence(patterns=[MatchValue(value=Constant(value=2)), MatchStar(name='rest')])], kwd_attrs=['k'], kwd_patterns=[MatchClass(cls=Name(id='D', ctx=Load()), patterns=[], kwd_attrs=[], kwd_patterns=[])])",
+        ),
+        (
+            "C()",
+            "MatchClass(cls=Name(id='C', ctx=Load()), patterns=[], kwd_attrs=[], kwd_patterns=[])",
+        ),
+        (
+            "{}",
+            "MatchMapping(keys=[], patterns=[])",
+        ),
+        (
+            "{'k': v, -1: _, 1 + 2j: [*_], None: None, a.b: C(), **kw}",
+            "MatchMapping(keys=[Constant(value='k'), UnaryOp(op=USub(), operand=Constant(value=1)), BinOp(left=Constant(value=1), op=Add(), right=Constant(value=2j)), Constant(value=None), Attribute(value=Name(id='a', ctx=Load()), attr='b', ctx=Load())], patterns=[MatchAs(name='v'), MatchAs(), MatchSequence(patterns=[MatchStar()]), MatchSingleton(value=None), MatchClass(cls=Name(id='C', ctx=Load()), patterns=[], kwd_attrs=[], kwd_patterns=[])], rest='kw')",
+        ),
+        (
+            "{True: (x | y) as z}",
+            "MatchMapping(keys=[Constant(value=True)], patterns=[MatchAs(pattern=MatchOr(patterns=[MatchAs(name='x'), MatchAs(name='y')]), name='z')])",
+        ),
+        (
+            "[*rest]",
+            "MatchSequence(patterns=[MatchStar(name='rest')])",
+        ),
+        (
+            "(a, *_, b)",
+            "MatchSequence(patterns=[MatchAs(name='a'), MatchStar(), MatchAs(name='b')])",
+        ),
+        (
+            "[]",
+            "MatchSequence(patterns=[])",
+        ),
+        (
+            "()",
+            "MatchSequence(patterns=[])",
+        ),
+        (
+            "(x)",
+            "MatchAs(name='x')",
+        ),
+        (
+            "a, *b",
+            "MatchSequence(patterns=[MatchAs(name='a'), MatchStar(name='b')])",
+        ),
+        (
+            "*_, c",
+            "MatchSequence(patterns=[MatchStar(), MatchAs(name='c')])",
+        ),
+    ];
+    for (pattern, expected) in cases {
+        let source = format!("match s:\n    case {}:\n        pass\n", pattern);
+        let Ok(Mod::Module(module)) = parse(&source, Mode::Exec) else {
+            panic!("{:?} failed to parse", pattern);
+        };
+        let Stmt::Match(node) = &module.body[0] else {
+            panic!("{:?} is not a match statement", pattern);
+        };
+        assert_eq!(
+            dump(&node.cases[0].pattern, None, false),
+            expected,
+            "{}",
+            pattern
+        );
+    }
+    for (pattern, message, col_offset) in [
+        ("{**rest, 'k': v}", "invalid syntax", 18),
+        ("{'e': *f}", "invalid syntax", 15),
+        ("{**_}", "invalid syntax", 12),
+        ("{a: 1}", "invalid syntax", 11),
+        ("*a", "invalid syntax", 11),
+        (
+            "C(x=1, 2)",
+            "positional patterns follow keyword patterns",
+            16,
+        ),
+    ] {
+        let source = format!("match s:\n    case {}:\n        pass\n", pattern);
+        let error = parse(&source, Mode::Exec).unwrap_err();
+        assert_eq!(error.message, message, "{}", pattern);
+        assert_eq!(error.span.col_offset, col_offset, "{}", pattern);
+    }
+}
+
+#[test]
+fn test_parse_type_params() {
+    use crate::dump::dump;
+
+    // Expected outputs were produced by CPython's `ast.dump`.
+    let tree = parse("type X[T: int, *Ts, **P] = ...\n", Mode::Exec).unwrap();
+    assert_eq!(
+        dump(&tree, None, true),
+        "Module(body=[TypeAlias(name=Name(id='X', ctx=Store(), lineno=1, col_offset=5, end_lineno=1, end_col_offset=6), type_params=[TypeVar(name='T', bound=Name(id='int', ctx=Load(), lineno=1, col_offset=10, end_lineno=1, end_col_offset=13), lineno=1, col_offset=7, end_lineno=1, end_col_offset=13), TypeVarTuple(name='Ts', lineno=1, col_offset=15, end_lineno=1, end_col_offset=18), ParamSpec(name='P', lineno=1, col_offset=20, end_lineno=1, end_col_offset=23)], value=Constant(value=Ellipsis, lineno=1, col_offset=27, end_lineno=1, end_col_offset=30), lineno=1, col_offset=0, end_lineno=1, end_col_offset=30)], type_ignores=[])"
+    );
+    // `type` is a keyword only where it starts a type alias.
+    let source = "type(x)\ntype.x = 1\ntype = 1\ntype X[T: (int, str),] = type\n";
+    assert_eq!(
+        dump(&parse(source, Mode::Exec).unwrap(), None, false),
+        "Module(body=[Expr(value=Call(func=Name(id='type', ctx=Load()), args=[Name(id='x', ctx=Load())], keywords=[])), Assign(targets=[Attribute(value=Name(id='type', ctx=Load()), attr='x', ctx=Store())], value=Constant(value=1)), Assign(targets=[Name(id='type', ctx=Store())], value=Constant(value=1)), TypeAlias(name=Name(id='X', ctx=Store()), type_params=[TypeVar(name='T', bound=Tuple(elts=[Name(id='int', ctx=Load()), Name(id='str', ctx=Load())], ctx=Load()))], value=Name(id='type', ctx=Load()))], type_ignores=[])"
+    );
+    for (source, message, span) in [
+        (
+            "type X[] = int",
+            "Type parameter list cannot be empty",
+            (7, 9),
+        ),
+        (
+            "def f[](): pass",
+            "Type parameter list cannot be empty",
+            (6, 7),
+        ),
+        (
+            "type X[*Ts: int] = T",
+            "cannot use bound with TypeVarTuple",
+            (10, 15),
+        ),
+        (
+            "class C[*Ts: (int, str)]: pass",
+            "cannot use constraints with TypeVarTuple",
+            (11, 23),
+        ),
+        (
+            "type X[**P: int] = T",
+            "cannot use bound with ParamSpec",
+            (10, 15),
+        ),
+        ("type X[T]", "invalid syntax", (9, 10)),
+    ] {
+        let error = parse(source, Mode::Exec).unwrap_err();
+        assert_eq!(error.message, message, "{}", source);
+        let columns = (error.span.col_offset, error.span.end_col_offset);
+        assert_eq!(columns, span, "{}", source);
+    }
+}
+
+#[test]
+fn test_parse_spans() {
+    use crate::dump::dump;
+
+    let source = "@d\ndef f(x):\n    if x:\n        return 1\n    else:\n        pass\n";
+    let tree = parse(source, Mode::Exec).unwrap();
+    assert_eq!(
+        dump(&tree, None, true),
+        "Module(body=[FunctionDef(name='f', args=arguments(posonlyargs=[], args=[arg(arg='x', lineno=2, col_offset=6, end_lineno=2, end_col_offset=7)], kwonlyargs=[], kw_defaults=[], defaults=[]), body=[If(test=Name(id='x', ctx=Load(), lineno=3, col_offset=7, end_lineno=3, end_col_offset=8), body=[Return(value=Constant(value=1, lineno=4, col_offset=15, end_lineno=4, end_col_offset=16), lineno=4, col_offset=8, end_lineno=4, end_col_offset=16)], orelse=[Pass(lineno=6, col_offset=8, end_lineno=6, end_col_offset=12)], lineno=3, col_offset=4, end_lineno=6, end_col_offset=12)], decorator_list=[Name(id='d', ctx=Load(), lineno=1, col_offset=1, end_lineno=1, end_col_offset=2)], type_params=[], lineno=2, col_offset=0, end_lineno=6, end_col_offset=12)], type_ignores=[])"
+    );
+    // Columns count UTF-8 bytes. A parenthesized tuple spans its parentheses, a name does not.
+    let source = "try:\n    import a.b as c\nexcept (E, F) as e:\n    x = {'\u{e9}': f(a, *b, k=lambda y=1: y[1:2, ...])}.get\n    del (a), [b.c]\n";
+    let tree = parse(source, Mode::Exec).unwrap();
+    assert_eq!(
+        dump(&tree, None, true),
+        "Module(body=[Try(body=[Import(names=[alias(name='a.b', asname='c', lineno=2, col_offset=11, end_lineno=2, end_col_offset=19)], lineno=2, col_offset=4, end_lineno=2, end_col_offset=19)], handlers=[ExceptHandler(type=Tuple(elts=[Name(id='E', ctx=Load(), lineno=3, col_offset=8, end_lineno=3, end_col_offset=9), Name(id='F', ctx=Load(), lineno=3, col_offset=11, end_lineno=3, end_col_offset=12)], ctx=Load(), lineno=3, col_offset=7, end_lineno=3, end_col_offset=13), name='e', body=[Assign(targets=[Name(id='x', ctx=Store(), lineno=4, col_offset=4, end_lineno=4, end_col_offset=5)], value=Attribute(value=Dict(keys=[Constant(value='\u{e9}', lineno=4, col_offset=9, end_lineno=4, end_col_offset=13)], values=[Call(func=Name(id='f', ctx=Load(), lineno=4, col_offset=15, end_lineno=4, end_col_offset=16), args=[Name(id='a', ctx=Load(), lineno=4, col_offset=17, end_lineno=4, end_col_offset=18), Starred(value=Name(id='b', ctx=Load(), lineno=4, col_offset=21, end_lineno=4, end_col_offset=22), ctx=Load(), lineno=4, col_offset=20, end_lineno=4, end_col_offset=22)], keywords=[keyword(arg='k', value=Lambda(args=arguments(posonlyargs=[], args=[arg(arg='y', lineno=4, col_offset=33, end_lineno=4, end_col_offset=34)], kwonlyargs=[], kw_defaults=[], defaults=[Constant(value=1, lineno=4, col_offset=35, end_lineno=4, end_col_offset=36)]), body=Subscript(value=Name(id='y', ctx=Load(), lineno=4, col_offset=38, end_lineno=4, end_col_offset=39), slice=Tuple(elts=[Slice(lower=Constant(value=1, lineno=4, col_offset=40, end_lineno=4, end_col_offset=41), upper=Constant(value=2, lineno=4, col_offset=42, end_lineno=4, end_col_offset=43), lineno=4, col_offset=40, end_lineno=4, end_col_offset=43), Constant(value=Ellipsis, lineno=4, col_offset=45, end_lineno=4, end_col_offset=48)], ctx=Load(), lineno=4, col_offset=40, end_lineno=4, end_col_offset=48), ctx=Load(), lineno=4, col_offset=38, end_lineno=4, end_col_offset=49), lineno=4, col_offset=26, end_lineno=4, end_col_offset=49), lineno=4, col_offset=24, end_lineno=4, end_col_offset=49)], lineno=4, col_offset=15, end_lineno=4, end_col_offset=50)], lineno=4, col_offset=8, end_lineno=4, end_col_offset=51), attr='get', ctx=Load(), lineno=4, col_offset=8, end_lineno=4, end_col_offset=55), lineno=4, col_offset=4, end_lineno=4, end_col_offset=55), Delete(targets=[Name(id='a', ctx=Del(), lineno=5, col_offset=9, end_lineno=5, end_col_offset=10), List(elts=[Attribute(value=Name(id='b', ctx=Load(), lineno=5, col_offset=14, end_lineno=5, end_col_offset=15), attr='c', ctx=Del(), lineno=5, col_offset=14, end_lineno=5, end_col_offset=17)], ctx=Del(), lineno=5, col_offset=13, end_lineno=5, end_col_offset=18)], lineno=5, col_offset=4, end_lineno=5, end_col_offset=18)], lineno=3, col_offset=0, end_lineno=5, end_col_offset=18)], orelse=[], finalbody=[], lineno=1, col_offset=0, end_lineno=5, end_col_offset=18)], type_ignores=[])"
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    for source in [
+        "if x:\npass\n",
+        "x\n  y\n",
+        "x = 1 =\n",
+        "f() = 1\n",
+        "del *a\n",
+        "import a,\n",
+        "from a import b,\n",
+        "try:\n    pass\n",
+        "with a as b,: pass\n",
+        "def f(*): pass\n",
+        "@d\nx = 1\n",
+        "class C(x for x in y): pass\n",
+        "match x:\n    case 1 + 1:\n        pass\n",
+        "match x:\n    case a.b(c=1, 2):\n        pass\n",
+        "def f[](): pass\n",
+        "class C[*Ts: int]: pass\n",
+    ] {
+        assert!(
+            parse(source, Mode::Exec).is_err(),
+            "{:?} should not parse",
+            source
+        );
+    }
+    for source in [
+        "1_000.000_1e1_0j\n",
+        "0x_1f\n",
+        "1if x else 2\n",
+        "0x1for x in y\n",
+    ] {
+        assert!(parse(source, Mode::Exec).is_ok(), "{:?}", source);
+    }
+    assert!(parse("x = 1\ny = 2\n", Mode::Single).is_err());
+    assert!(parse("(int, *str, *bool) -> None", Mode::FuncType).is_err());
+    for (source, message) in [
+        (
+            "if x:\n    a\n  b\n",
+            "unindent does not match any outer indentation level",
+        ),
+        (
+            "if x:\n        a\n\tb\n",
+            "inconsistent use of tabs and spaces in indentation",
+        ),
+        ("(a,\n b", "'(' was never closed"),
+        ("a)", "unmatched ')'"),
+        (
+            "(a]",
+            "closing parenthesis ']' does not match opening parenthesis '('",
+        ),
+        (
+            "x = \\ 1",
+            "unexpected character after line continuation character",
+        ),
+        ("1_", "invalid decimal literal"),
+        ("1__0", "invalid decimal literal"),
+        ("1_.5", "invalid decimal literal"),
+        ("1._5", "invalid decimal literal"),
+        ("1e5_", "invalid decimal literal"),
+        ("1e+x", "invalid decimal literal"),
+        ("1abc", "invalid decimal literal"),
+        ("1j_", "invalid imaginary literal"),
+        ("0x_", "invalid hexadecimal literal"),
+        ("0x1g", "invalid hexadecimal literal"),
+        ("0o8", "invalid digit '8' in octal literal"),
+        ("0o1_8", "invalid digit '8' in octal literal"),
+        ("0b2", "invalid digit '2' in binary literal"),
+        (
+            "'ab\\x4g'",
+            "(unicode error) 'unicodeescape' codec can't decode bytes in position 2-4: truncated \\xXX escape",
+        ),
+        (
+            "'\u{e9}\\u12'",
+            "(unicode error) 'unicodeescape' codec can't decode bytes in position 10-13: truncated \\uXXXX escape",
+        ),
+        (
+            "f'{x}\\U1'",
+            "(unicode error) 'unicodeescape' codec can't decode bytes in position 0-2: truncated \\UXXXXXXXX escape",
+        ),
+        (
+            "'\\U00110000'",
+            "(unicode error) 'unicodeescape' codec can't decode bytes in position 0-9: illegal Unicode character",
+        ),
+        (
+            "'\\N{NO SUCH NAME}'",
+            "(unicode error) 'unicodeescape' codec can't decode bytes in position 0-15: unknown Unicode character name",
+        ),
+        (
+            "'\\N{}'",
+            "(unicode error) 'unicodeescape' codec can't decode bytes in position 0-2: malformed \\N character escape",
+        ),
+        ("b'\\x4'", "(value error) invalid \\x escape at position 0"),
+        ("a\u{20ac}b = 1", "invalid character '\u{20ac}' (U+20AC)"),
+        ("x\u{b2} = 1", "invalid character '\u{b2}' (U+00B2)"),
+        ("ab\u{200b} = 1", "invalid non-printable character U+200B"),
+    ] {
+        let error = parse(source, Mode::Exec).unwrap_err();
+        assert_eq!(error.message, message, "{:?}", source);
+    }
+}
+
+#[test]
+fn test_target_versions() {
+    for (source, minor, feature) in [
+        ("(x := 1)\n", 8, "Assignment expressions are"),
+        ("def f(a, /): pass\n", 8, "Positional-only parameters are"),
+        ("f'{x=}'\n", 8, "f-string: self documenting expressions are"),
+        (
+            "with (a as b, c): pass\n",
+            9,
+            "Parenthesized context managers are",
+        ),
+        ("match x:\n    case 1: pass\n", 10, "Pattern matching is"),
+        ("try: pass\nexcept* E: pass\n", 11, "Exception groups are"),
+        ("type X = int\n", 12, "Type statement is"),
+        ("class C[T]: pass\n", 12, "Type parameter lists are"),
+    ] {
+        let error = parse_with_version(source, Mode::Exec, PythonVersion::Py37).unwrap_err();
+        let expected = format!(
+            "{} only supported in Python 3.{} and greater",
+            feature, minor
+        );
+        assert_eq!(error.message, expected, "{:?}", source);
+        assert!(parse(source, Mode::Exec).is_ok(), "{:?}", source);
+    }
+    let source = "def f[T = int, *Ts = *tuple[int], **P = [int]](): pass\n";
+    let error = parse_with_version(source, Mode::Exec, PythonVersion::Py312).unwrap_err();
+    assert_eq!(
+        error.message,
+        "Type parameter defaults are only supported in Python 3.13 and greater"
+    );
+    assert_eq!(error.span.col_offset, 10);
+    assert!(parse(source, Mode::Exec).is_ok());
+    // Unparenthesized, it is an error in every version.
+    let error = parse_with_version("x := 1\n", Mode::Exec, PythonVersion::Py37).unwrap_err();
+    assert_eq!(error.message, "invalid syntax");
+    // Before 3.9, the items read as a tuple.
+    assert!(parse_with_version("with (a, b): pass\n", Mode::Exec, PythonVersion::Py37).is_ok());
+    // The earliest error is reported.
+    let error = parse_with_version("x = (y := 1)\nz = 1 +\n", Mode::Exec, PythonVersion::Py37);
+    assert_eq!(error.unwrap_err().span.lineno, 1);
+}
+
+#[test]
+fn test_left_recursion() {
+    // chain: chain '-' NAME | NAME
+    fn chain(p: &mut Parser) -> Option<String> {
+        p.memo_left_rec(Rule::TestChain, |p| {
+            let difference = p.attempt(|p| {
+                let left = chain(p)?;
+                p.expect(TokenType::Minus)?;
+                Some(format!("({} - {})", left, p.name()?.0))
+            });
+            difference.or_else(|| p.name().map(|(name, _)| name))
+        })
+    }
+    // call: callee '(' ')' | NAME, with callee: call
+    fn call(p: &mut Parser) -> Option<String> {
+        p.memo_left_rec(Rule::TestCall, |p| {
+            let called = p.attempt(|p| {
+                let callee = callee(p)?;
+                p.expect(TokenType::LPar)?;
+                p.expect(TokenType::RPar)?;
+                Some(format!("{}()", callee))
+            });
+            called.or_else(|| p.name().map(|(name, _)| name))
+        })
+    }
+    fn callee(p: &mut Parser) -> Option<String> {
+        p.memo(Rule::TestCallee, call)
+    }
+
+    let mut parser = Parser::new("a - b - c", PythonVersion::default()).unwrap();
+    assert_eq!(chain(&mut parser).as_deref(), Some("((a - b) - c)"));
+    assert!(parser.at(TokenType::Newline));
+    let mut parser = Parser::new("f()() - g", PythonVersion::default()).unwrap();
+    assert_eq!(call(&mut parser).as_deref(), Some("f()()"));
+    assert!(parser.at(TokenType::Minus));
+    // Replayed from the memo.
+    parser.pos = 0;
+    assert_eq!(callee(&mut parser).as_deref(), Some("f()()"));
+    assert_eq!(parser.pos, 5);
+}
+
+#[test]
+fn test_generated_parser() {
+    use crate::dump::dump;
+
+    // `test_start` is generated from `test_grammar.gram`.
+    let parse = |source: &str| {
+        let exprs = Parser::new(source, PythonVersion::default())
+            .ok()?
+            .test_start()?;
+        let dumps: Vec<String> = exprs.iter().map(|e| dump(e, None, true)).collect();
+        Some(dumps.join("; "))
+    };
+    assert_eq!(
+        parse("a - (b, c,) + [d None]").as_deref(),
+        Some("BinOp(left=BinOp(left=Name(id='a', ctx=Load(), lineno=1, col_offset=0, end_lineno=1, end_col_offset=1), op=Sub(), right=Tuple(elts=[Name(id='b', ctx=Load(), lineno=1, col_offset=5, end_lineno=1, end_col_offset=6), Name(id='c', ctx=Load(), lineno=1, col_offset=8, end_lineno=1, end_col_offset=9)], ctx=Load(), lineno=1, col_offset=4, end_lineno=1, end_col_offset=11), lineno=1, col_offset=0, end_lineno=1, end_col_offset=11), op=Add(), right=List(elts=[Name(id='d', ctx=Load(), lineno=1, col_offset=15, end_lineno=1, end_col_offset=16), Name(id='None', ctx=Load(), lineno=1, col_offset=17, end_lineno=1, end_col_offset=21)], ctx=Load(), lineno=1, col_offset=14, end_lineno=1, end_col_offset=22), lineno=1, col_offset=0, end_lineno=1, end_col_offset=22)"),
+    );
+    assert_eq!(
+        parse("soft a; b").as_deref(),
+        Some("Name(id='a', ctx=Load(), lineno=1, col_offset=5, end_lineno=1, end_col_offset=6); Name(id='b', ctx=Load(), lineno=1, col_offset=8, end_lineno=1, end_col_offset=9)"),
+    );
+    // The cut after the soft keyword keeps it from being read as a name.
+    assert_eq!(parse("soft a"), None);
+    assert_eq!(parse("a soft"), None);
+    assert_eq!(parse("a = b"), None);
+    assert_eq!(parse("[]"), None);
+}
+
+#[test]
+fn test_parse_expression() {
+    use crate::dump::dump;
+
+    // Expected outputs were produced by CPython's `ast.dump(ast.parse(source, mode="eval").body)`.
+    let cases = [
+        (
+            "a + b * -c ** 2",
+            "BinOp(left=Name(id='a', ctx=Load()), op=Add(), right=BinOp(left=Name(id='b', ctx=Load()), op=Mult(), right=UnaryOp(op=USub(), operand=BinOp(left=Name(id='c', ctx=Load()), op=Pow(), right=Constant(value=2)))))",
+        ),
+        (
+            "a < b <= c is not d not in e",
+            "Compare(left=Name(id='a', ctx=Load()), ops=[Lt(), LtE(), IsNot(), NotIn()], comparators=[Name(id='b', ctx=Load()), Name(id='c', ctx=Load()), Name(id='d', ctx=Load()), Name(id='e', ctx=Load())])",
+        ),
+        (
+            "not a or b and c",
+            "BoolOp(op=Or(), values=[UnaryOp(op=Not(), operand=Name(id='a', ctx=Load())), BoolOp(op=And(), values=[Name(id='b', ctx=Load()), Name(id='c', ctx=Load())])])",
+        ),
+        (
+            "x if y else lambda a, /, b=1, *c, d, **e: a",
+            "IfExp(test=Name(id='y', ctx=Load()), body=Name(id='x', ctx=Load()), orelse=Lambda(args=arguments(posonlyargs=[arg(arg='a')], args=[arg(arg='b')], vararg=arg(arg='c'), kwonlyargs=[arg(arg='d')], kw_defaults=[None], kwarg=arg(arg='e'), defaults=[Constant(value=1)]), body=Name(id='a', ctx=Load())))",
+        ),
+        (
+            "(y := f(x, *a, k=1, **kw))",
+            "NamedExpr(target=Name(id='y', ctx=Store()), value=Call(func=Name(id='f', ctx=Load()), args=[Name(id='x', ctx=Load()), Starred(value=Name(id='a', ctx=Load()), ctx=Load())], keywords=[keyword(arg='k', value=Constant(value=1)), keyword(value=Name(id='kw', ctx=Load()))]))",
+        ),
+        (
+            "a.b[1:2, ::3][c]",
+            "Subscript(value=Subscript(value=Attribute(value=Name(id='a', ctx=Load()), attr='b', ctx=Load()), slice=Tuple(elts=[Slice(lower=Constant(value=1), upper=Constant(value=2)), Slice(step=Constant(value=3))], ctx=Load()), ctx=Load()), slice=Name(id='c', ctx=Load()), ctx=Load())",
+        ),
+        (
+            "[x for x, *y in z if x async for w in x]",
+            "ListComp(elt=Name(id='x', ctx=Load()), generators=[comprehension(target=Tuple(elts=[Name(id='x', ctx=Store()), Starred(value=Name(id='y', ctx=Store()), ctx=Store())], ctx=Store()), iter=Name(id='z', ctx=Load()), ifs=[Name(id='x', ctx=Load())], is_async=0), comprehension(target=Name(id='w', ctx=Store()), iter=Name(id='x', ctx=Load()), ifs=[], is_async=1)])",
+        ),
+        (
+            "{**a, 'b': 2}, {k: v for k, v in x}, {1, *s}, f(i for i in j)",
+            "Tuple(elts=[Dict(keys=[None, Constant(value='b')], values=[Name(id='a', ctx=Load()), Constant(value=2)]), DictComp(key=Name(id='k', ctx=Load()), value=Name(id='v', ctx=Load()), generators=[comprehension(target=Tuple(elts=[Name(id='k', ctx=Store()), Name(id='v', ctx=Store())], ctx=Store()), iter=Name(id='x', ctx=Load()), ifs=[], is_async=0)]), Set(elts=[Constant(value=1), Starred(value=Name(id='s', ctx=Load()), ctx=Load())]), Call(func=Name(id='f', ctx=Load()), args=[GeneratorExp(elt=Name(id='i', ctx=Load()), generators=[comprehension(target=Name(id='i', ctx=Store()), iter=Name(id='j', ctx=Load()), ifs=[], is_async=0)])], keywords=[])], ctx=Load())",
+        ),
+        (
+            "'a' \"b\" u'c', b'\\x00' rb'\\d', None, True, ..., 0x_f, 1.5e3j, ()",
+            "Tuple(elts=[Constant(value='abc'), Constant(value=b'\\x00\\\\d'), Constant(value=None), Constant(value=True), Constant(value=Ellipsis), Constant(value=15), Constant(value=1500j), Tuple(elts=[], ctx=Load())], ctx=Load())",
+        ),
+        (
+            "'\\N{BULLET} \\N{em dash}\\x41\\u00e9', f'\\N{LINE FEED}{x}', b'\\N{BULLET}'",
+            "Tuple(elts=[Constant(value='• —Aé'), JoinedStr(values=[Constant(value='\\n'), FormattedValue(value=Name(id='x', ctx=Load()), conversion=-1)]), Constant(value=b'\\\\N{BULLET}')], ctx=Load())",
+        ),
+        (
+            "f'a{b!r:>{w}} {c=}' 'd'",
+            "JoinedStr(values=[Constant(value='a'), FormattedValue(value=Name(id='b', ctx=Load()), conversion=114, format_spec=JoinedStr(values=[Constant(value='>'), FormattedValue(value=Name(id='w', ctx=Load()), conversion=-1)])), Constant(value=' c='), FormattedValue(value=Name(id='c', ctx=Load()), conversion=114), Constant(value='d')])",
+        ),
+        (
+            "await x ** -y",
+            "BinOp(left=Await(value=Name(id='x', ctx=Load())), op=Pow(), right=UnaryOp(op=USub(), operand=Name(id='y', ctx=Load())))",
+        ),
+        (
+            "(yield), (yield from a), (*a, b), [*a]",
+            "Tuple(elts=[Yield(), YieldFrom(value=Name(id='a', ctx=Load())), Tuple(elts=[Starred(value=Name(id='a', ctx=Load()), ctx=Load()), Name(id='b', ctx=Load())], ctx=Load()), List(elts=[Starred(value=Name(id='a', ctx=Load()), ctx=Load())], ctx=Load())], ctx=Load())",
+        ),
+        (
+            "f'{abs(val):.{digits}f}'",
+            "JoinedStr(values=[FormattedValue(value=Call(func=Name(id='abs', ctx=Load()), args=[Name(id='val', ctx=Load())], keywords=[]), conversion=-1, format_spec=JoinedStr(values=[Constant(value='.'), FormattedValue(value=Name(id='digits', ctx=Load()), conversion=-1), Constant(value='f')]))])",
+        ),
+        (
+            "rf'a\\'b{x}', fr\"\\\"{x}\", rf'`{s}\\''",
+            "Tuple(elts=[JoinedStr(values=[Constant(value=\"a\\\\'b\"), FormattedValue(value=Name(id='x', ctx=Load()), conversion=-1)]), JoinedStr(values=[Constant(value='\\\\\"'), FormattedValue(value=Name(id='x', ctx=Load()), conversion=-1)]), JoinedStr(values=[Constant(value='`'), FormattedValue(value=Name(id='s', ctx=Load()), conversion=-1), Constant(value=\"\\\\'\")])], ctx=Load())",
+        ),
+        (
+            "f'\\{6*7}', rf'\\{x}\\}}'",
+            "Tuple(elts=[JoinedStr(values=[Constant(value='\\\\'), FormattedValue(value=BinOp(left=Constant(value=6), op=Mult(), right=Constant(value=7)), conversion=-1)]), JoinedStr(values=[Constant(value='\\\\'), FormattedValue(value=Name(id='x', ctx=Load()), conversion=-1), Constant(value='\\\\}')])], ctx=Load())",
+        ),
+        (
+            "'' f'{x}', f'' '', f'a' '' f'{x}' ''",
+            "Tuple(elts=[JoinedStr(values=[FormattedValue(value=Name(id='x', ctx=Load()), conversion=-1)]), JoinedStr(values=[]), JoinedStr(values=[Constant(value='a'), FormattedValue(value=Name(id='x', ctx=Load()), conversion=-1)])], ctx=Load())",
+        ),
+        (
+            "f'{x:a{y=}b}', f'''{x = # c\n}'''",
+            "Tuple(elts=[JoinedStr(values=[FormattedValue(value=Name(id='x', ctx=Load()), conversion=-1, format_spec=JoinedStr(values=[Constant(value='ay='), FormattedValue(value=Name(id='y', ctx=Load()), conversion=114), Constant(value='b')]))]), JoinedStr(values=[Constant(value='x = \\n'), FormattedValue(value=Name(id='x', ctx=Load()), conversion=114)])], ctx=Load())",
+        ),
+        (
+            "f'{v:{w:{0}}.{p}}'",
+            "JoinedStr(values=[FormattedValue(value=Name(id='v', ctx=Load()), conversion=-1, format_spec=JoinedStr(values=[FormattedValue(value=Name(id='w', ctx=Load()), conversion=-1, format_spec=JoinedStr(values=[FormattedValue(value=Constant(value=0), conversion=-1)])), Constant(value='.'), FormattedValue(value=Name(id='p', ctx=Load()), conversion=-1)]))])",
+        ),
+    ];
+    for (source, expected) in cases {
+        match parse_expression(source) {
+            Ok(tree) => assert_eq!(dump(&tree, None, false), expected, "{}", source),
+            Err(error) => panic!("Failed to parse {:?}: {:?}", source, error),
+        }
+    }
+}
+
+#[test]
+fn test_parse_expression_spans() {
+    use crate::dump::dump;
+
+    let tree = parse_expression("f(a,\n  b[1])").unwrap();
+    assert_eq!(
+        dump(&tree, None, true),
+        "Call(func=Name(id='f', ctx=Load(), lineno=1, col_offset=0, end_lineno=1, end_col_offset=1), args=[Name(id='a', ctx=Load(), lineno=1, col_offset=2, end_lineno=1, end_col_offset=3), Subscript(value=Name(id='b', ctx=Load(), lineno=2, col_offset=2, end_lineno=2, end_col_offset=3), slice=Constant(value=1, lineno=2, col_offset=4, end_lineno=2, end_col_offset=5), ctx=Load(), lineno=2, col_offset=2, end_lineno=2, end_col_offset=6)], keywords=[], lineno=1, col_offset=0, end_lineno=2, end_col_offset=7)"
+    );
+}
+
+#[test]
+fn test_parse_expression_errors() {
+    for source in [
+        "a +",
+        "f(a=1, b)",
+        "(a",
+        "[x for x in]",
+        "a := 1",
+        "lambda a=1, b: a",
+    ] {
+        assert!(
+            parse_expression(source).is_err(),
+            "{:?} should not parse",
+            source
+        );
+    }
+    let error = parse_expression("a b").unwrap_err();
+    assert_eq!(error.message, "invalid syntax");
+    assert_eq!((error.span.col_offset, error.span.end_col_offset), (2, 3));
+    let error = parse_expression("'abc").unwrap_err();
+    assert_eq!(
+        error.message,
+        "unterminated string literal (detected at line 1)"
+    );
+}
@@ -0,0 +1,907 @@
+// Generated by `pragrat generate parser` from `pattern.gram`. Do not edit.
+use crate::types::TokenType;
+use super::{Parser, Rule};
+use crate::ast::{
+	Attribute, BinOp, Constant, Expr, ExprContext, Match, MatchAs, MatchCase, MatchClass,
+	MatchMapping, MatchOr, MatchSequence, MatchSingleton, MatchStar, MatchValue, Name, Operator,
+	Pattern, Stmt, Tuple, UnaryOp, UnaryOperator,
+};
+use super::{identifier, SyntaxError};
+use crate::types::{Identifier, Literal, PythonVersion, Span, Token};
+
+impl Parser {
+	/// match_stmt: "match" subject_expr ':' NEWLINE INDENT case_block+ DEDENT | invalid_match_stmt
+	pub(super) fn match_stmt(&mut self) -> Option<Stmt> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			let keyword = p.advance_if(|token| token.token_type == TokenType::Name && token.value == "match").cloned()?;
+			let subject = p.subject_expr()?;
+			p.token(TokenType::Colon)?;
+			p.token(TokenType::Newline)?;
+			p.token(TokenType::Indent)?;
+			let cases = {
+				let mut items = vec![];
+				while let Some(item) = p.attempt(|p| p.case_block()) {
+					items.push(item);
+				}
+				(!items.is_empty()).then_some(items)
+			}?;
+			p.token(TokenType::Dedent)?;
+			Some({
+				p.check_version(PythonVersion::Py310, "Pattern matching is", keyword.span);
+				Stmt::Match(Match { subject: Box::new(subject), cases, span: p.span_from(start) })
+			})
+		}) {
+			return Some(value);
+		}
+		p.invalid(Self::invalid_match_stmt);
+		None
+	}
+
+	/// subject_expr: star_named_expression ',' [star_named_expressions] | named_expression
+	pub(super) fn subject_expr(&mut self) -> Option<Expr> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			let value = p.star_named_expression()?;
+			p.token(TokenType::Comma)?;
+			let values = p.attempt(|p| p.star_named_expressions());
+			Some(Expr::Tuple(Tuple {
+				elts: [vec![value], values.unwrap_or_default()].concat(),
+				ctx: ExprContext::Load,
+				span: p.span_from(start),
+			}))
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| p.named_expression())
+	}
+
+	/// case_block: invalid_case_block | "case" patterns [guard] ':' block
+	pub(super) fn case_block(&mut self) -> Option<MatchCase> {
+		let p = self;
+		p.invalid(Self::invalid_case_block);
+		p.attempt(|p| {
+			p.advance_if(|token| token.token_type == TokenType::Name && token.value == "case").cloned()?;
+			let pattern = p.patterns()?;
+			let guard = p.attempt(|p| p.guard());
+			p.token(TokenType::Colon)?;
+			let body = p.block()?;
+			Some(MatchCase { pattern: Box::new(pattern), guard: guard.map(Box::new), body })
+		})
+	}
+
+	/// guard: 'if' named_expression
+	pub(super) fn guard(&mut self) -> Option<Expr> {
+		let p = self;
+		p.attempt(|p| {
+			p.token(TokenType::If)?;
+			let guard = p.named_expression()?;
+			Some(guard)
+		})
+	}
+
+	/// patterns: open_sequence_pattern | pattern
+	pub(super) fn patterns(&mut self) -> Option<Pattern> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			let patterns = p.open_sequence_pattern()?;
+			Some(Pattern::MatchSequence(MatchSequence { patterns, span: p.span_from(start) }))
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| p.pattern())
+	}
+
+	/// pattern: as_pattern | or_pattern
+	pub(super) fn pattern(&mut self) -> Option<Pattern> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| p.as_pattern()) {
+			return Some(value);
+		}
+		p.attempt(|p| p.or_pattern())
+	}
+
+	/// as_pattern: or_pattern 'as' pattern_capture_target | invalid_as_pattern
+	pub(super) fn as_pattern(&mut self) -> Option<Pattern> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			let pattern = p.or_pattern()?;
+			p.token(TokenType::As)?;
+			let target = p.pattern_capture_target()?;
+			Some(Pattern::MatchAs(MatchAs { pattern: Some(Box::new(pattern)), name: Some(target), span: p.span_from(start) }))
+		}) {
+			return Some(value);
+		}
+		p.invalid(Self::invalid_as_pattern);
+		None
+	}
+
+	/// or_pattern: '|'.closed_pattern+
+	pub(super) fn or_pattern(&mut self) -> Option<Pattern> {
+		let p = self;
+		p.attempt(|p| {
+			let start = p.pos;
+			let patterns = p.attempt(|p| {
+				let mut items = vec![p.closed_pattern()?];
+				while let Some(item) = p.attempt(|p| {
+					p.token(TokenType::VBar)?;
+					p.closed_pattern()
+				}) {
+					items.push(item);
+				}
+				Some(items)
+			})?;
+			Some(or_pattern(patterns, p.span_from(start)))
+		})
+	}
+
+	/// closed_pattern:
+	///     | literal_pattern
+	///     | capture_pattern
+	///     | wildcard_pattern
+	///     | value_pattern
+	///     | group_pattern
+	///     | sequence_pattern
+	///     | mapping_pattern
+	///     | class_pattern
+	pub(super) fn closed_pattern(&mut self) -> Option<Pattern> {
+		self.memo(Rule::Generated("closed_pattern"), |p| {
+			if let Some(value) = p.attempt(|p| p.literal_pattern()) {
+				return Some(value);
+			}
+			if let Some(value) = p.attempt(|p| p.capture_pattern()) {
+				return Some(value);
+			}
+			if let Some(value) = p.attempt(|p| p.wildcard_pattern()) {
+				return Some(value);
+			}
+			if let Some(value) = p.attempt(|p| p.value_pattern()) {
+				return Some(value);
+			}
+			if let Some(value) = p.attempt(|p| p.group_pattern()) {
+				return Some(value);
+			}
+			if let Some(value) = p.attempt(|p| p.sequence_pattern()) {
+				return Some(value);
+			}
+			if let Some(value) = p.attempt(|p| p.mapping_pattern()) {
+				return Some(value);
+			}
+			p.attempt(|p| p.class_pattern())
+		})
+	}
+
+	/// literal_pattern:
+	///     | signed_number !('+' | '-')
+	///     | complex_number
+	///     | strings
+	///     | 'None'
+	///     | 'True'
+	///     | 'False'
+	pub(super) fn literal_pattern(&mut self) -> Option<Pattern> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			let value = p.signed_number()?;
+			let mark = p.pos;
+			let found = p.attempt(|p| {
+				if let Some(value) = p.attempt(|p| p.token(TokenType::Plus)) {
+					return Some(value);
+				}
+				p.attempt(|p| p.token(TokenType::Minus))
+			}).is_some();
+			p.pos = mark;
+			if found {
+				return None;
+			}
+			Some(match_value(value, p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			let value = p.complex_number()?;
+			Some(match_value(value, p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			let value = p.strings()?;
+			Some(match_value(value, p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::None)?;
+			Some(Pattern::MatchSingleton(MatchSingleton { value: Literal::None, span: p.span_from(start) }))
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::True)?;
+			Some(Pattern::MatchSingleton(MatchSingleton { value: Literal::Bool(true), span: p.span_from(start) }))
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::False)?;
+			Some(Pattern::MatchSingleton(MatchSingleton { value: Literal::Bool(false), span: p.span_from(start) }))
+		})
+	}
+
+	/// literal_expr:
+	///     | signed_number !('+' | '-')
+	///     | complex_number
+	///     | strings
+	///     | 'None'
+	///     | 'True'
+	///     | 'False'
+	pub(super) fn literal_expr(&mut self) -> Option<Expr> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			let item1 = p.signed_number()?;
+			let mark = p.pos;
+			let found = p.attempt(|p| {
+				if let Some(value) = p.attempt(|p| p.token(TokenType::Plus)) {
+					return Some(value);
+				}
+				p.attempt(|p| p.token(TokenType::Minus))
+			}).is_some();
+			p.pos = mark;
+			if found {
+				return None;
+			}
+			Some(item1)
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| p.complex_number()) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| p.strings()) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::None)?;
+			Some(constant(Literal::None, p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::True)?;
+			Some(constant(Literal::Bool(true), p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::False)?;
+			Some(constant(Literal::Bool(false), p.span_from(start)))
+		})
+	}
+
+	/// complex_number:
+	///     | signed_real_number '+' imaginary_number
+	///     | signed_real_number '-' imaginary_number
+	pub(super) fn complex_number(&mut self) -> Option<Expr> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			let real = p.signed_real_number()?;
+			p.token(TokenType::Plus)?;
+			let imag = p.imaginary_number()?;
+			Some(bin_op(real, Operator::Add, imag, p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| {
+			let start = p.pos;
+			let real = p.signed_real_number()?;
+			p.token(TokenType::Minus)?;
+			let imag = p.imaginary_number()?;
+			Some(bin_op(real, Operator::Sub, imag, p.span_from(start)))
+		})
+	}
+
+	/// signed_number: NUMBER | '-' NUMBER
+	pub(super) fn signed_number(&mut self) -> Option<Expr> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			let token = p.token(TokenType::Number)?;
+			Some(number(token))
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::Minus)?;
+			let token = p.token(TokenType::Number)?;
+			Some(negative(number(token), p.span_from(start)))
+		})
+	}
+
+	/// signed_real_number: real_number | '-' real_number
+	pub(super) fn signed_real_number(&mut self) -> Option<Expr> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| p.real_number()) {
+			return Some(value);
+		}
+		p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::Minus)?;
+			let real = p.real_number()?;
+			Some(negative(real, p.span_from(start)))
+		})
+	}
+
+	/// capture_pattern: pattern_capture_target
+	pub(super) fn capture_pattern(&mut self) -> Option<Pattern> {
+		let p = self;
+		p.attempt(|p| {
+			let start = p.pos;
+			let target = p.pattern_capture_target()?;
+			Some(Pattern::MatchAs(MatchAs { pattern: None, name: Some(target), span: p.span_from(start) }))
+		})
+	}
+
+	/// pattern_capture_target: !"_" NAME !('.' | '(' | '=')
+	pub(super) fn pattern_capture_target(&mut self) -> Option<Identifier> {
+		let p = self;
+		p.attempt(|p| {
+			let mark = p.pos;
+			let found = p.advance_if(|token| token.token_type == TokenType::Name && token.value == "_").cloned().is_some();
+			p.pos = mark;
+			if found {
+				return None;
+			}
+			let name = p.token(TokenType::Name)?;
+			let mark = p.pos;
+			let found = p.attempt(|p| {
+				if let Some(value) = p.attempt(|p| p.token(TokenType::Dot)) {
+					return Some(value);
+				}
+				if let Some(value) = p.attempt(|p| p.token(TokenType::LPar)) {
+					return Some(value);
+				}
+				p.attempt(|p| p.token(TokenType::Equal))
+			}).is_some();
+			p.pos = mark;
+			if found {
+				return None;
+			}
+			Some(identifier(&name))
+		})
+	}
+
+	/// wildcard_pattern: "_"
+	pub(super) fn wildcard_pattern(&mut self) -> Option<Pattern> {
+		let p = self;
+		p.attempt(|p| {
+			let start = p.pos;
+			p.advance_if(|token| token.token_type == TokenType::Name && token.value == "_").cloned()?;
+			Some(Pattern::MatchAs(MatchAs { pattern: None, name: None, span: p.span_from(start) }))
+		})
+	}
+
+	/// value_pattern: attr !('.' | '(' | '=')
+	pub(super) fn value_pattern(&mut self) -> Option<Pattern> {
+		let p = self;
+		p.attempt(|p| {
+			let start = p.pos;
+			let attr = p.attr()?;
+			let mark = p.pos;
+			let found = p.attempt(|p| {
+				if let Some(value) = p.attempt(|p| p.token(TokenType::Dot)) {
+					return Some(value);
+				}
+				if let Some(value) = p.attempt(|p| p.token(TokenType::LPar)) {
+					return Some(value);
+				}
+				p.attempt(|p| p.token(TokenType::Equal))
+			}).is_some();
+			p.pos = mark;
+			if found {
+				return None;
+			}
+			Some(match_value(attr, p.span_from(start)))
+		})
+	}
+
+	/// attr: name_or_attr '.' NAME
+	pub(super) fn attr(&mut self) -> Option<Expr> {
+		self.memo_left_rec(Rule::Generated("attr"), |p| {
+			p.attempt(|p| {
+				let start = p.pos;
+				let value = p.name_or_attr()?;
+				p.token(TokenType::Dot)?;
+				let attr = p.token(TokenType::Name)?;
+				Some(Expr::Attribute(Attribute {
+					value: Box::new(value),
+					attr: identifier(&attr),
+					ctx: ExprContext::Load,
+					span: p.span_from(start),
+				}))
+			})
+		})
+	}
+
+	/// name_or_attr: attr | NAME
+	pub(super) fn name_or_attr(&mut self) -> Option<Expr> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| p.attr()) {
+			return Some(value);
+		}
+		p.attempt(|p| {
+			let name = p.token(TokenType::Name)?;
+			Some(Expr::Name(Name { id: identifier(&name), ctx: ExprContext::Load, span: name.span }))
+		})
+	}
+
+	/// group_pattern: '(' pattern ')'
+	pub(super) fn group_pattern(&mut self) -> Option<Pattern> {
+		let p = self;
+		p.attempt(|p| {
+			p.token(TokenType::LPar)?;
+			let pattern = p.pattern()?;
+			p.token(TokenType::RPar)?;
+			Some(pattern)
+		})
+	}
+
+	/// sequence_pattern: '[' [maybe_sequence_pattern] ']' | '(' [open_sequence_pattern] ')'
+	pub(super) fn sequence_pattern(&mut self) -> Option<Pattern> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::LSqB)?;
+			let patterns = p.attempt(|p| p.maybe_sequence_pattern());
+			p.token(TokenType::RSqB)?;
+			Some(match_sequence(patterns, p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::LPar)?;
+			let patterns = p.attempt(|p| p.open_sequence_pattern());
+			p.token(TokenType::RPar)?;
+			Some(match_sequence(patterns, p.span_from(start)))
+		})
+	}
+
+	/// open_sequence_pattern: maybe_star_pattern ',' [maybe_sequence_pattern]
+	pub(super) fn open_sequence_pattern(&mut self) -> Option<Vec<Pattern>> {
+		let p = self;
+		p.attempt(|p| {
+			let pattern = p.maybe_star_pattern()?;
+			p.token(TokenType::Comma)?;
+			let patterns = p.attempt(|p| p.maybe_sequence_pattern());
+			Some([vec![pattern], patterns.unwrap_or_default()].concat())
+		})
+	}
+
+	/// maybe_sequence_pattern: ','.maybe_star_pattern+ [',']
+	pub(super) fn maybe_sequence_pattern(&mut self) -> Option<Vec<Pattern>> {
+		let p = self;
+		p.attempt(|p| {
+			let patterns = p.attempt(|p| {
+				let mut items = vec![p.maybe_star_pattern()?];
+				while let Some(item) = p.attempt(|p| {
+					p.token(TokenType::Comma)?;
+					p.maybe_star_pattern()
+				}) {
+					items.push(item);
+				}
+				Some(items)
+			})?;
+			let _ = p.attempt(|p| p.token(TokenType::Comma));
+			Some(patterns)
+		})
+	}
+
+	/// maybe_star_pattern: star_pattern | pattern
+	pub(super) fn maybe_star_pattern(&mut self) -> Option<Pattern> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| p.star_pattern()) {
+			return Some(value);
+		}
+		p.attempt(|p| p.pattern())
+	}
+
+	/// star_pattern: '*' pattern_capture_target | '*' wildcard_pattern
+	pub(super) fn star_pattern(&mut self) -> Option<Pattern> {
+		self.memo(Rule::Generated("star_pattern"), |p| {
+			if let Some(value) = p.attempt(|p| {
+				let start = p.pos;
+				p.token(TokenType::Star)?;
+				let target = p.pattern_capture_target()?;
+				Some(Pattern::MatchStar(MatchStar { name: Some(target), span: p.span_from(start) }))
+			}) {
+				return Some(value);
+			}
+			p.attempt(|p| {
+				let start = p.pos;
+				p.token(TokenType::Star)?;
+				p.wildcard_pattern()?;
+				Some(Pattern::MatchStar(MatchStar { name: None, span: p.span_from(start) }))
+			})
+		})
+	}
+
+	/// mapping_pattern:
+	///     | '{' '}'
+	///     | '{' double_star_pattern [','] '}'
+	///     | '{' items_pattern ',' double_star_pattern [','] '}'
+	///     | '{' items_pattern [','] '}'
+	pub(super) fn mapping_pattern(&mut self) -> Option<Pattern> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::LBrace)?;
+			p.token(TokenType::RBrace)?;
+			Some(match_mapping(vec![], None, p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::LBrace)?;
+			let rest = p.double_star_pattern()?;
+			let _ = p.attempt(|p| p.token(TokenType::Comma));
+			p.token(TokenType::RBrace)?;
+			Some(match_mapping(vec![], Some(rest), p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::LBrace)?;
+			let items = p.items_pattern()?;
+			p.token(TokenType::Comma)?;
+			let rest = p.double_star_pattern()?;
+			let _ = p.attempt(|p| p.token(TokenType::Comma));
+			p.token(TokenType::RBrace)?;
+			Some(match_mapping(items, Some(rest), p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::LBrace)?;
+			let items = p.items_pattern()?;
+			let _ = p.attempt(|p| p.token(TokenType::Comma));
+			p.token(TokenType::RBrace)?;
+			Some(match_mapping(items, None, p.span_from(start)))
+		})
+	}
+
+	/// items_pattern: ','.key_value_pattern+
+	pub(super) fn items_pattern(&mut self) -> Option<Vec<(Expr, Pattern)>> {
+		let p = self;
+		p.attempt(|p| {
+			let mut items = vec![p.key_value_pattern()?];
+			while let Some(item) = p.attempt(|p| {
+				p.token(TokenType::Comma)?;
+				p.key_value_pattern()
+			}) {
+				items.push(item);
+			}
+			Some(items)
+		})
+	}
+
+	/// key_value_pattern: (literal_expr | attr) ':' pattern
+	pub(super) fn key_value_pattern(&mut self) -> Option<(Expr, Pattern)> {
+		let p = self;
+		p.attempt(|p| {
+			let key = p.attempt(|p| {
+				if let Some(value) = p.attempt(|p| p.literal_expr()) {
+					return Some(value);
+				}
+				p.attempt(|p| p.attr())
+			})?;
+			p.token(TokenType::Colon)?;
+			let pattern = p.pattern()?;
+			Some((key, pattern))
+		})
+	}
+
+	/// double_star_pattern: '**' pattern_capture_target
+	pub(super) fn double_star_pattern(&mut self) -> Option<Identifier> {
+		let p = self;
+		p.attempt(|p| {
+			p.token(TokenType::DoubleStar)?;
+			let target = p.pattern_capture_target()?;
+			Some(target)
+		})
+	}
+
+	/// class_pattern:
+	///     | name_or_attr '(' ')'
+	///     | name_or_attr '(' positional_patterns [','] ')'
+	///     | name_or_attr '(' keyword_patterns [','] ')'
+	///     | name_or_attr '(' positional_patterns ',' keyword_patterns [','] ')'
+	///     | invalid_class_pattern
+	pub(super) fn class_pattern(&mut self) -> Option<Pattern> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			let cls = p.name_or_attr()?;
+			p.token(TokenType::LPar)?;
+			p.token(TokenType::RPar)?;
+			Some(match_class(cls, vec![], vec![], p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			let cls = p.name_or_attr()?;
+			p.token(TokenType::LPar)?;
+			let patterns = p.positional_patterns()?;
+			let _ = p.attempt(|p| p.token(TokenType::Comma));
+			p.token(TokenType::RPar)?;
+			Some(match_class(cls, patterns, vec![], p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			let cls = p.name_or_attr()?;
+			p.token(TokenType::LPar)?;
+			let keywords = p.keyword_patterns()?;
+			let _ = p.attempt(|p| p.token(TokenType::Comma));
+			p.token(TokenType::RPar)?;
+			Some(match_class(cls, vec![], keywords, p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		if let Some(value) = p.attempt(|p| {
+			let start = p.pos;
+			let cls = p.name_or_attr()?;
+			p.token(TokenType::LPar)?;
+			let patterns = p.positional_patterns()?;
+			p.token(TokenType::Comma)?;
+			let keywords = p.keyword_patterns()?;
+			let _ = p.attempt(|p| p.token(TokenType::Comma));
+			p.token(TokenType::RPar)?;
+			Some(match_class(cls, patterns, keywords, p.span_from(start)))
+		}) {
+			return Some(value);
+		}
+		p.invalid(Self::invalid_class_pattern);
+		None
+	}
+
+	/// positional_patterns: ','.pattern+
+	pub(super) fn positional_patterns(&mut self) -> Option<Vec<Pattern>> {
+		let p = self;
+		p.attempt(|p| {
+			let mut items = vec![p.pattern()?];
+			while let Some(item) = p.attempt(|p| {
+				p.token(TokenType::Comma)?;
+				p.pattern()
+			}) {
+				items.push(item);
+			}
+			Some(items)
+		})
+	}
+
+	/// keyword_patterns: ','.keyword_pattern+
+	pub(super) fn keyword_patterns(&mut self) -> Option<Vec<(Identifier, Pattern)>> {
+		let p = self;
+		p.attempt(|p| {
+			let mut items = vec![p.keyword_pattern()?];
+			while let Some(item) = p.attempt(|p| {
+				p.token(TokenType::Comma)?;
+				p.keyword_pattern()
+			}) {
+				items.push(item);
+			}
+			Some(items)
+		})
+	}
+
+	/// keyword_pattern: NAME '=' pattern
+	pub(super) fn keyword_pattern(&mut self) -> Option<(Identifier, Pattern)> {
+		let p = self;
+		p.attempt(|p| {
+			let arg = p.token(TokenType::Name)?;
+			p.token(TokenType::Equal)?;
+			let value = p.pattern()?;
+			Some((identifier(&arg), value))
+		})
+	}
+
+	/// invalid_match_stmt: "match" subject_expr NEWLINE | "match" subject_expr ':' NEWLINE !INDENT
+	pub(super) fn invalid_match_stmt(&mut self) -> Option<SyntaxError> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			p.advance_if(|token| token.token_type == TokenType::Name && token.value == "match").cloned()?;
+			p.subject_expr()?;
+			let newline = p.token(TokenType::Newline)?;
+			Some(SyntaxError::new("expected ':'", newline.span))
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| {
+			let keyword = p.advance_if(|token| token.token_type == TokenType::Name && token.value == "match").cloned()?;
+			p.subject_expr()?;
+			p.token(TokenType::Colon)?;
+			p.token(TokenType::Newline)?;
+			let mark = p.pos;
+			let found = p.token(TokenType::Indent).is_some();
+			p.pos = mark;
+			if found {
+				return None;
+			}
+			Some(p.expected_block(&keyword))
+		})
+	}
+
+	/// invalid_case_block:
+	///     | "case" patterns [guard] NEWLINE
+	///     | "case" patterns [guard] ':' NEWLINE !INDENT
+	pub(super) fn invalid_case_block(&mut self) -> Option<SyntaxError> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			p.advance_if(|token| token.token_type == TokenType::Name && token.value == "case").cloned()?;
+			p.patterns()?;
+			let _ = p.attempt(|p| p.guard());
+			let newline = p.token(TokenType::Newline)?;
+			Some(SyntaxError::new("expected ':'", newline.span))
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| {
+			let keyword = p.advance_if(|token| token.token_type == TokenType::Name && token.value == "case").cloned()?;
+			p.patterns()?;
+			let _ = p.attempt(|p| p.guard());
+			p.token(TokenType::Colon)?;
+			p.token(TokenType::Newline)?;
+			let mark = p.pos;
+			let found = p.token(TokenType::Indent).is_some();
+			p.pos = mark;
+			if found {
+				return None;
+			}
+			Some(p.expected_block(&keyword))
+		})
+	}
+
+	/// invalid_as_pattern: or_pattern 'as' "_" | or_pattern 'as' !NAME expression
+	pub(super) fn invalid_as_pattern(&mut self) -> Option<SyntaxError> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			p.or_pattern()?;
+			p.token(TokenType::As)?;
+			let target = p.advance_if(|token| token.token_type == TokenType::Name && token.value == "_").cloned()?;
+			Some(SyntaxError::new("cannot use '_' as a target", target.span))
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| {
+			p.or_pattern()?;
+			p.token(TokenType::As)?;
+			let mark = p.pos;
+			let found = p.token(TokenType::Name).is_some();
+			p.pos = mark;
+			if found {
+				return None;
+			}
+			let target = p.expression()?;
+			Some(SyntaxError::new("invalid pattern target", target.span()))
+		})
+	}
+
+	/// invalid_class_pattern: name_or_attr '(' invalid_class_argument_pattern
+	pub(super) fn invalid_class_pattern(&mut self) -> Option<SyntaxError> {
+		let p = self;
+		p.attempt(|p| {
+			p.name_or_attr()?;
+			p.token(TokenType::LPar)?;
+			let patterns = p.invalid_class_argument_pattern()?;
+			Some({
+				let span = patterns[0].span().to(patterns[patterns.len() - 1].span());
+				SyntaxError::new("positional patterns follow keyword patterns", span)
+			})
+		})
+	}
+
+	/// invalid_class_argument_pattern: [positional_patterns ','] keyword_patterns ',' positional_patterns
+	pub(super) fn invalid_class_argument_pattern(&mut self) -> Option<Vec<Pattern>> {
+		let p = self;
+		p.attempt(|p| {
+			let _ = p.attempt(|p| {
+				p.attempt(|p| {
+					let item1 = p.positional_patterns()?;
+					let item2 = p.token(TokenType::Comma)?;
+					Some((item1, item2))
+				})
+			});
+			p.keyword_patterns()?;
+			p.token(TokenType::Comma)?;
+			let patterns = p.positional_patterns()?;
+			Some(patterns)
+		})
+	}
+}
+
+impl Parser {
+	/// real_number: NUMBER, which must not be imaginary
+	fn real_number(&mut self) -> Option<Expr> {
+		self.complex_part(false, "real number required in complex literal")
+	}
+
+	/// imaginary_number: NUMBER, which must be imaginary
+	fn imaginary_number(&mut self) -> Option<Expr> {
+		self.complex_part(true, "imaginary number required in complex literal")
+	}
+
+	/// A NUMBER that is `imaginary` or not, which is the error `message` otherwise.
+	fn complex_part(&mut self, imaginary: bool, message: &str) -> Option<Expr> {
+		let token = self.token(TokenType::Number)?;
+		if is_imaginary(&token) != imaginary {
+			self.invalid(|_| Some(SyntaxError::new(message, token.span)));
+			return None;
+		}
+		Some(number(token))
+	}
+}
+
+fn is_imaginary(token: &Token) -> bool {
+	token.value.ends_with(['j', 'J'])
+}
+
+fn number(token: Token) -> Expr {
+	let value = token.literal.unwrap_or(Literal::Number(token.value));
+	constant(value, token.span)
+}
+
+fn constant(value: Literal, span: Span) -> Expr {
+	Expr::Constant(Constant { value, kind: None, span })
+}
+
+fn negative(operand: Expr, span: Span) -> Expr {
+	Expr::UnaryOp(UnaryOp { op: UnaryOperator::USub, operand: Box::new(operand), span })
+}
+
+fn bin_op(left: Expr, op: Operator, right: Expr, span: Span) -> Expr {
+	Expr::BinOp(BinOp { left: Box::new(left), op, right: Box::new(right), span })
+}
+
+fn match_value(value: Expr, span: Span) -> Pattern {
+	Pattern::MatchValue(MatchValue { value: Box::new(value), span })
+}
+
+fn match_sequence(patterns: Option<Vec<Pattern>>, span: Span) -> Pattern {
+	Pattern::MatchSequence(MatchSequence { patterns: patterns.unwrap_or_default(), span })
+}
+
+/// A single alternative is not an or-pattern.
+fn or_pattern(mut patterns: Vec<Pattern>, span: Span) -> Pattern {
+	match patterns.len() {
+		1 => patterns.remove(0),
+		_ => Pattern::MatchOr(MatchOr { patterns, span }),
+	}
+}
+
+fn match_mapping(items: Vec<(Expr, Pattern)>, rest: Option<Identifier>, span: Span) -> Pattern {
+	let (keys, patterns) = items.into_iter().unzip();
+	Pattern::MatchMapping(MatchMapping { keys, patterns, rest, span })
+}
+
+fn match_class(cls: Expr, patterns: Vec<Pattern>, keywords: Vec<(Identifier, Pattern)>, span: Span) -> Pattern {
+	let (kwd_attrs, kwd_patterns) = keywords.into_iter().unzip();
+	Pattern::MatchClass(MatchClass { cls: Box::new(cls), patterns, kwd_attrs, kwd_patterns, span })
+}
@@ -0,0 +1,199 @@
+//! Error recovery, which parses a module in spite of its syntax errors. When a statement
+//! fails to parse, its error is found by a second pass over it alone, and parsing resumes at
+//! the next statement: past the NEWLINE ending its line and the indented block that may
+//! follow, or at the DEDENT closing the enclosing block. What was skipped is kept as an
+//! `ErrorStmt`, unless the statement parses with the rest of its line, from where it failed,
+//! standing in an `ErrorExpr`.
+
+use super::{Parser, SyntaxError};
+use crate::ast::{ErrorStmt, Stmt};
+use crate::types::{Span, Token, TokenType};
+
+impl Parser {
+    /// Recovers from the statement that failed to parse at `start`, returning what stands
+    /// in for it.
+    pub(super) fn recover_statement(&mut self, start: usize) -> Vec<Stmt> {
+        if let Some((stmts, end)) = self.recovered.get(&start) {
+            self.pos = *end;
+            return stmts.clone();
+        }
+        let stmts = self.recover_uncached(start);
+        self.recovered.insert(start, (stmts.clone(), self.pos));
+        stmts
+    }
+
+    fn recover_uncached(&mut self, start: usize) -> Vec<Stmt> {
+        let furthest = self.furthest;
+        let error = self.statement_error(start, furthest);
+        let error = self.or_unclosed(error);
+        let end = self.synchronize(start);
+        // A bracket left open within the statement is not reported besides its error.
+        let next = self.tokens[end.min(self.tokens.len() - 1)].span;
+        self.unclosed.take_if(|unclosed| {
+            (unclosed.span.lineno, unclosed.span.col_offset) < (next.lineno, next.col_offset)
+        });
+        // The tokenizer already reported what is wrong with its error tokens.
+        if !self.tokens[start..end]
+            .iter()
+            .any(|token| token.token_type == TokenType::ErrorToken)
+        {
+            self.errors.push(error);
+        }
+        if let Some(stmts) = self.statement_with_placeholder(start, furthest, end) {
+            return stmts;
+        }
+        self.pos = end;
+        vec![Stmt::ErrorStmt(ErrorStmt {
+            span: self.span_from(start),
+        })]
+    }
+
+    /// The error of the statement at `start`, raised by the invalid rules or else reported
+    /// at `furthest`, as `finish` does for a whole file.
+    fn statement_error(&mut self, start: usize, furthest: usize) -> SyntaxError {
+        self.pos = start;
+        self.memo.clear();
+        let call_invalid_rules = std::mem::replace(&mut self.call_invalid_rules, true);
+        self.statement();
+        self.call_invalid_rules = call_invalid_rules;
+        self.memo.clear();
+        self.error.take().unwrap_or_else(|| {
+            let token = &self.tokens[furthest.min(self.tokens.len() - 1)];
+            SyntaxError::at_token(token)
+        })
+    }
+
+    /// Skips the statement at `start`, returning the position of the next one. Clauses such
+    /// as `else:` following its block are skipped along with it.
+    fn synchronize(&self, start: usize) -> usize {
+        let mut pos = start;
+        let mut depth = 0;
+        loop {
+            let next = |pos: usize| &self.tokens[(pos + 1).min(self.tokens.len() - 1)].token_type;
+            let clause = |pos: usize| {
+                matches!(
+                    next(pos),
+                    TokenType::Elif | TokenType::Else | TokenType::Except | TokenType::Finally
+                )
+            };
+            match self.tokens[pos].token_type {
+                TokenType::Endmarker => return pos,
+                TokenType::Dedent if depth == 0 => return pos,
+                TokenType::Indent => depth += 1,
+                TokenType::Dedent => {
+                    depth -= 1;
+                    if depth == 0 && !clause(pos) {
+                        return pos + 1;
+                    }
+                }
+                TokenType::Newline
+                    if depth == 0 && *next(pos) != TokenType::Indent && !clause(pos) =>
+                {
+                    return pos + 1;
+                }
+                _ => {}
+            }
+            pos += 1;
+        }
+    }
+
+    /// Parses the statement at `start` again with an error token standing in for where it
+    /// failed, at `furthest`: first before that token, then in place of the rest of its line.
+    /// The statement must end at `end`, where parsing resumes.
+    fn statement_with_placeholder(
+        &mut self,
+        start: usize,
+        furthest: usize,
+        end: usize,
+    ) -> Option<Vec<Stmt>> {
+        let newline =
+            (furthest..end).find(|&pos| self.tokens[pos].token_type == TokenType::Newline)?;
+        let from = self.tokens[furthest].span;
+        let before = Span {
+            end_lineno: from.lineno,
+            end_col_offset: from.col_offset,
+            ..from
+        };
+        let rest = from.to(self.tokens[newline.max(furthest + 1) - 1].span);
+        let mut replacements = vec![(furthest..furthest, before)];
+        if newline > furthest {
+            replacements.push((furthest..newline, rest));
+        }
+        for (range, span) in replacements {
+            let placeholder = Token {
+                token_type: TokenType::ErrorToken,
+                value: String::new(),
+                literal: None,
+                line: span.lineno,
+                span,
+            };
+            let removed: Vec<Token> = self.tokens.splice(range.clone(), [placeholder]).collect();
+            // Positions past the placeholder are shifted by this much while it is in place.
+            let shift = range.len() as isize - 1;
+            self.memo.clear();
+            let recovered = std::mem::take(&mut self.recovered);
+            self.pos = start;
+            let stmts = self.attempt(Self::statement);
+            let parsed_end = (self.pos as isize + shift) as usize;
+            self.tokens.splice(range.start..range.start + 1, removed);
+            self.memo.clear();
+            self.recovered = recovered;
+            if stmts.is_some() && self.pos > range.start && parsed_end == end {
+                self.pos = end;
+                return stmts;
+            }
+        }
+        None
+    }
+}
+
+#[test]
+fn test_recovery() {
+    use super::parse_recovering;
+    use crate::dump::dump;
+    use crate::types::PythonVersion;
+
+    for (source, expected, messages) in [
+        (
+            "x = 1 +\ny = 2\n",
+            "Module(body=[Assign(targets=[Name(id='x', ctx=Store())], value=BinOp(left=Constant(value=1), op=Add(), right=Error())), Assign(targets=[Name(id='y', ctx=Store())], value=Constant(value=2))], type_ignores=[])",
+            vec!["invalid syntax"],
+        ),
+        (
+            "def f():\n    a = (1 if 2)\n    return 1\nclass C:\n    x = $\n",
+            "Module(body=[FunctionDef(name='f', args=arguments(posonlyargs=[], args=[], kwonlyargs=[], kw_defaults=[], defaults=[]), body=[Error(), Return(value=Constant(value=1))], decorator_list=[], type_params=[]), ClassDef(name='C', bases=[], keywords=[], body=[Assign(targets=[Name(id='x', ctx=Store())], value=Error())], decorator_list=[], type_params=[])], type_ignores=[])",
+            vec![
+                "expected 'else' after 'if' expression",
+                "invalid character '$' (U+0024)",
+            ],
+        ),
+        (
+            "for x in 1 +:\n    f(a b)\nelse:\n    y\n",
+            "Module(body=[For(target=Name(id='x', ctx=Store()), iter=BinOp(left=Constant(value=1), op=Add(), right=Error()), body=[Error()], orelse=[Expr(value=Name(id='y', ctx=Load()))])], type_ignores=[])",
+            vec!["invalid syntax", "invalid syntax. Perhaps you forgot a comma?"],
+        ),
+        (
+            "if x y:\n    a\nelse:\n    b\n  c\nd\n",
+            "Module(body=[Error(), Error(), Expr(value=Name(id='d', ctx=Load()))], type_ignores=[])",
+            vec![
+                "invalid syntax",
+                "unindent does not match any outer indentation level",
+            ],
+        ),
+        (
+            "x\n  y\nz\n",
+            "Module(body=[Expr(value=Name(id='x', ctx=Load())), Error(), Expr(value=Name(id='z', ctx=Load()))], type_ignores=[])",
+            vec!["unexpected indent"],
+        ),
+        (
+            "f(a,\n  b\n",
+            "Module(body=[Error()], type_ignores=[])",
+            vec!["'(' was never closed"],
+        ),
+    ] {
+        let (module, errors) = parse_recovering(source, PythonVersion::default());
+        assert_eq!(dump(&module, None, false), expected, "{:?}", source);
+        let errors: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+        assert_eq!(errors, messages, "{:?}", source);
+    }
+}
@@ -0,0 +1,817 @@
+//! Statement rules, in the order of `Grammar/python.gram`.
+
+use super::expr::set_context;
+use super::{Parser, SyntaxError};
+use crate::ast::*;
+use crate::types::{Identifier, PythonVersion, TokenType};
+
+impl Parser {
+    /// statements: statement+
+    ///
+    /// When recovering, statements that fail to parse are recovered from up to the end of
+    /// the block.
+    pub(super) fn statements(&mut self) -> Option<Vec<Stmt>> {
+        let mut body = vec![];
+        loop {
+            let start = self.pos;
+            if self.recovering {
+                self.furthest = start;
+            }
+            if let Some(stmts) = self.attempt(Self::statement) {
+                body.extend(stmts);
+                continue;
+            }
+            let end = matches!(self.peek_type(0), TokenType::Dedent | TokenType::Endmarker);
+            if !self.recovering || self.error.is_some() || end {
+                break;
+            }
+            body.extend(self.recover_statement(start));
+        }
+        (!body.is_empty()).then_some(body)
+    }
+
+    /// statement: compound_stmt | simple_stmts
+    pub(super) fn statement(&mut self) -> Option<Vec<Stmt>> {
+        if let Some(stmt) = self.attempt(Self::compound_stmt) {
+            return Some(vec![stmt]);
+        }
+        self.simple_stmts()
+    }
+
+    /// statement_newline: compound_stmt NEWLINE | simple_stmts | NEWLINE
+    pub(super) fn statement_newline(&mut self) -> Option<Vec<Stmt>> {
+        if let Some(stmt) = self.attempt(Self::compound_stmt) {
+            while self.expect(TokenType::Newline).is_some() {}
+            return Some(vec![stmt]);
+        }
+        if self.expect(TokenType::Newline).is_some() {
+            return Some(vec![]);
+        }
+        self.simple_stmts()
+    }
+
+    /// simple_stmts: ';'.simple_stmt+ [';'] NEWLINE
+    fn simple_stmts(&mut self) -> Option<Vec<Stmt>> {
+        let mut stmts = vec![self.simple_stmt()?];
+        while self.expect(TokenType::Semi).is_some() {
+            if self.at(TokenType::Newline) {
+                break;
+            }
+            stmts.push(self.simple_stmt()?);
+        }
+        self.expect(TokenType::Newline)?;
+        Some(stmts)
+    }
+
+    /// simple_stmt:
+    ///     | assignment
+    ///     | &"type" type_alias
+    ///     | star_expressions
+    ///     | return_stmt | import_stmt | raise_stmt | 'pass' | del_stmt | yield_stmt
+    ///     | assert_stmt | 'break' | 'continue' | global_stmt | nonlocal_stmt
+    fn simple_stmt(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        match self.peek_type(0) {
+            TokenType::Return => return self.return_stmt(),
+            TokenType::Import => return self.import_name(),
+            TokenType::From => return self.import_from(),
+            TokenType::Raise => return self.raise_stmt(),
+            TokenType::Del => return self.del_stmt(),
+            TokenType::Assert => return self.assert_stmt(),
+            TokenType::Global | TokenType::Nonlocal => return self.global_stmt(),
+            TokenType::Pass => {
+                self.pos += 1;
+                let span = self.span_from(start);
+                return Some(Stmt::Pass(Pass { span }));
+            }
+            TokenType::Break => {
+                self.pos += 1;
+                let span = self.span_from(start);
+                return Some(Stmt::Break(Break { span }));
+            }
+            TokenType::Continue => {
+                self.pos += 1;
+                let span = self.span_from(start);
+                return Some(Stmt::Continue(Continue { span }));
+            }
+            _ => (),
+        }
+        if let Some(stmt) = self.attempt(Self::assignment) {
+            return Some(stmt);
+        }
+        self.invalid(Self::invalid_assignment);
+        if let Some(stmt) = self.attempt(Self::type_alias) {
+            return Some(stmt);
+        }
+        let value = if self.at(TokenType::Yield) {
+            self.yield_expr()?
+        } else {
+            self.star_expressions()?
+        };
+        Some(Stmt::ExprStmt(ExprStmt {
+            value: Box::new(value),
+            span: self.span_from(start),
+        }))
+    }
+
+    /// compound_stmt:
+    ///     | &('def' | '@' | ASYNC) function_def
+    ///     | &'if' if_stmt
+    ///     | &('class' | '@') class_def
+    ///     | &('with' | ASYNC) with_stmt
+    ///     | &('for' | ASYNC) for_stmt
+    ///     | &'try' try_stmt
+    ///     | &'while' while_stmt
+    ///     | match_stmt
+    fn compound_stmt(&mut self) -> Option<Stmt> {
+        match self.peek_type(0) {
+            TokenType::At => {
+                let decorators = self.decorators()?;
+                match self.peek_type(0) {
+                    TokenType::Class => self.class_def_raw(decorators),
+                    _ => self.function_def_raw(decorators),
+                }
+            }
+            TokenType::Def => self.function_def_raw(vec![]),
+            TokenType::Class => self.class_def_raw(vec![]),
+            TokenType::If => self.if_stmt(),
+            TokenType::While => self.while_stmt(),
+            TokenType::For => self.for_stmt(),
+            TokenType::With => self.with_stmt(),
+            TokenType::Try => self.try_stmt(),
+            TokenType::Async => match self.peek_type(1) {
+                TokenType::For => self.for_stmt(),
+                TokenType::With => self.with_stmt(),
+                // Otherwise fails past the `async`, where CPython reports the error.
+                _ => self.function_def_raw(vec![]),
+            },
+            TokenType::Name => self.match_stmt(),
+            _ => None,
+        }
+    }
+
+    /// assignment:
+    ///     | NAME ':' expression ['=' annotated_rhs]
+    ///     | ('(' single_target ')' | single_subscript_attribute_target) ':' expression ['=' annotated_rhs]
+    ///     | (star_targets '=')+ (yield_expr | star_expressions) !'='
+    ///     | single_target augassign ~ (yield_expr | star_expressions)
+    ///     | invalid_assignment
+    fn assignment(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        if let Some(stmt) = self.attempt(Self::ann_assignment) {
+            return Some(stmt);
+        }
+        let mut targets = vec![];
+        while let Some(target) = self.attempt(|p| {
+            let target = p.star_targets()?;
+            p.expect(TokenType::Equal)?;
+            Some(target)
+        }) {
+            targets.push(target);
+        }
+        if !targets.is_empty() {
+            let value = self.annotated_rhs()?;
+            if self.at(TokenType::Equal) {
+                return None;
+            }
+            return Some(Stmt::Assign(Assign {
+                targets,
+                value: Box::new(value),
+                type_comment: None,
+                span: self.span_from(start),
+            }));
+        }
+        let target = self.single_target()?;
+        let op = self.augassign()?;
+        let value = self.annotated_rhs()?;
+        Some(Stmt::AugAssign(AugAssign {
+            target: Box::new(target),
+            op,
+            value: Box::new(value),
+            span: self.span_from(start),
+        }))
+    }
+
+    /// augassign: '+=' | '-=' | '*=' | '@=' | '/=' | '%=' | '&=' | '|=' | '^=' | '<<=' | '>>=' | '**=' | '//='
+    pub(super) fn augassign(&mut self) -> Option<Operator> {
+        self.advance_map(|t| match t {
+            TokenType::PlusEqual => Some(Operator::Add),
+            TokenType::MinEqual => Some(Operator::Sub),
+            TokenType::StarEqual => Some(Operator::Mult),
+            TokenType::AtEqual => Some(Operator::MatMult),
+            TokenType::SlashEqual => Some(Operator::Div),
+            TokenType::PercentEqual => Some(Operator::Mod),
+            TokenType::AmperEqual => Some(Operator::BitAnd),
+            TokenType::VBarEqual => Some(Operator::BitOr),
+            TokenType::CircumflexEqual => Some(Operator::BitXor),
+            TokenType::LeftShiftEqual => Some(Operator::LShift),
+            TokenType::RightShiftEqual => Some(Operator::RShift),
+            TokenType::DoubleStarEqual => Some(Operator::Pow),
+            TokenType::DoubleSlashEqual => Some(Operator::FloorDiv),
+            _ => None,
+        })
+    }
+
+    /// The annotated forms of `assignment`. Only a bare name is a `simple` target.
+    fn ann_assignment(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        let (target, simple) = if self.at(TokenType::Name) && *self.peek_type(1) == TokenType::Colon
+        {
+            let (id, span) = self.name()?;
+            let ctx = ExprContext::Store;
+            (Expr::Name(Name { id, ctx, span }), 1)
+        } else {
+            let target = self
+                .attempt(|p| {
+                    p.expect(TokenType::LPar)?;
+                    let target = p.single_target()?;
+                    p.expect(TokenType::RPar)?;
+                    Some(target)
+                })
+                .or_else(|| self.attempt(Self::single_subscript_attribute_target))?;
+            (target, 0)
+        };
+        self.expect(TokenType::Colon)?;
+        let annotation = self.expression()?;
+        let value = match self.expect(TokenType::Equal) {
+            Some(_) => Some(self.annotated_rhs()?),
+            None => None,
+        };
+        Some(Stmt::AnnAssign(AnnAssign {
+            target: Box::new(target),
+            annotation: Box::new(annotation),
+            value: value.map(Box::new),
+            simple,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// annotated_rhs: yield_expr | star_expressions
+    fn annotated_rhs(&mut self) -> Option<Expr> {
+        if self.at(TokenType::Yield) {
+            return self.yield_expr();
+        }
+        self.star_expressions()
+    }
+
+    /// single_target: single_subscript_attribute_target | NAME | '(' single_target ')'
+    fn single_target(&mut self) -> Option<Expr> {
+        if let Some(target) = self.attempt(Self::single_subscript_attribute_target) {
+            return Some(target);
+        }
+        if let Some((id, span)) = self.name() {
+            let ctx = ExprContext::Store;
+            return Some(Expr::Name(Name { id, ctx, span }));
+        }
+        self.expect(TokenType::LPar)?;
+        let target = self.single_target()?;
+        self.expect(TokenType::RPar)?;
+        Some(target)
+    }
+
+    /// return_stmt: 'return' [star_expressions]
+    fn return_stmt(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        self.expect(TokenType::Return)?;
+        let value = self.attempt(Self::star_expressions);
+        Some(Stmt::Return(Return {
+            value: value.map(Box::new),
+            span: self.span_from(start),
+        }))
+    }
+
+    /// raise_stmt: 'raise' expression ['from' expression] | 'raise' | invalid_raise_stmt
+    fn raise_stmt(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        self.expect(TokenType::Raise)?;
+        let exc = self.attempt(Self::expression);
+        let cause = match exc.is_some() && self.expect(TokenType::From).is_some() {
+            true => Some(self.expression()?),
+            false => None,
+        };
+        if exc.is_some() && cause.is_none() && self.at(TokenType::Comma) {
+            self.pos = start;
+            self.invalid(Self::invalid_raise_stmt);
+            return None;
+        }
+        Some(Stmt::Raise(Raise {
+            exc: exc.map(Box::new),
+            cause: cause.map(Box::new),
+            span: self.span_from(start),
+        }))
+    }
+
+    /// global_stmt: 'global' ','.NAME+
+    /// nonlocal_stmt: 'nonlocal' ','.NAME+
+    fn global_stmt(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        let global = self.expect(TokenType::Global).is_some();
+        if !global {
+            self.expect(TokenType::Nonlocal)?;
+        }
+        let mut names = vec![self.name()?.0];
+        while self.expect(TokenType::Comma).is_some() {
+            names.push(self.name()?.0);
+        }
+        let span = self.span_from(start);
+        Some(match global {
+            true => Stmt::Global(Global { names, span }),
+            false => Stmt::Nonlocal(Nonlocal { names, span }),
+        })
+    }
+
+    /// del_stmt: 'del' del_targets &(';' | NEWLINE) | invalid_del_stmt
+    fn del_stmt(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        self.expect(TokenType::Del)?;
+        let targets = self.attempt(|p| {
+            let targets = p.comma_list(|p| {
+                let target = p.star_target()?;
+                (!has_starred(&target)).then(|| set_context(target, ExprContext::Del))
+            })?;
+            matches!(p.peek_type(0), TokenType::Semi | TokenType::Newline).then_some(targets)
+        });
+        let Some(targets) = targets else {
+            self.pos = start;
+            self.invalid(Self::invalid_del_stmt);
+            return None;
+        };
+        Some(Stmt::Delete(Delete {
+            targets,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// assert_stmt: 'assert' expression [',' expression]
+    fn assert_stmt(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        self.expect(TokenType::Assert)?;
+        let test = self.expression()?;
+        let msg = match self.expect(TokenType::Comma) {
+            Some(_) => Some(self.expression()?),
+            None => None,
+        };
+        Some(Stmt::Assert(Assert {
+            test: Box::new(test),
+            msg: msg.map(Box::new),
+            span: self.span_from(start),
+        }))
+    }
+
+    /// import_name: 'import' dotted_as_names
+    fn import_name(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        self.expect(TokenType::Import)?;
+        let names = self.comma_list(|p| {
+            let start = p.pos;
+            let name = p.dotted_name()?;
+            p.alias(start, name)
+        })?;
+        // `comma_list` allows a trailing comma, which `import` does not.
+        if self.tokens[self.pos - 1].token_type == TokenType::Comma {
+            return None;
+        }
+        Some(Stmt::Import(Import {
+            names,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// import_from:
+    ///     | 'from' ('.' | '...')* dotted_name 'import' import_from_targets
+    ///     | 'from' ('.' | '...')+ 'import' import_from_targets
+    fn import_from(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        self.expect(TokenType::From)?;
+        let mut level = 0;
+        while let Some(dots) = self.advance_map(|t| match t {
+            TokenType::Dot => Some(1),
+            TokenType::Ellipsis => Some(3),
+            _ => None,
+        }) {
+            level += dots;
+        }
+        let module = match level {
+            0 => Some(self.dotted_name()?),
+            _ => self.attempt(Self::dotted_name),
+        };
+        self.expect(TokenType::Import)?;
+        let names = self.import_from_targets()?;
+        Some(Stmt::ImportFrom(ImportFrom {
+            module,
+            names,
+            level,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// import_from_targets:
+    ///     | '(' import_from_as_names [','] ')'
+    ///     | import_from_as_names !','
+    ///     | '*'
+    fn import_from_targets(&mut self) -> Option<Vec<Alias>> {
+        let start = self.pos;
+        if self.expect(TokenType::Star).is_some() {
+            return Some(vec![Alias {
+                name: "*".to_string(),
+                asname: None,
+                span: self.span_from(start),
+            }]);
+        }
+        let parenthesized = self.expect(TokenType::LPar).is_some();
+        let names = self.comma_list(|p| {
+            let start = p.pos;
+            let (name, _) = p.name()?;
+            p.alias(start, name)
+        })?;
+        if parenthesized {
+            self.expect(TokenType::RPar)?;
+        } else if self.tokens[self.pos - 1].token_type == TokenType::Comma {
+            return None;
+        }
+        Some(names)
+    }
+
+    /// `['as' NAME]` following the name of an import, which starts at `start`.
+    fn alias(&mut self, start: usize, name: Identifier) -> Option<Alias> {
+        let asname = match self.expect(TokenType::As) {
+            Some(_) => Some(self.name()?.0),
+            None => None,
+        };
+        Some(Alias {
+            name,
+            asname,
+            span: self.span_from(start),
+        })
+    }
+
+    /// dotted_name: dotted_name '.' NAME | NAME
+    fn dotted_name(&mut self) -> Option<Identifier> {
+        let mut name = self.name()?.0;
+        while let Some((part, _)) = self.attempt(|p| {
+            p.expect(TokenType::Dot)?;
+            p.name()
+        }) {
+            name.push('.');
+            name.push_str(&part);
+        }
+        Some(name)
+    }
+
+    /// block: NEWLINE INDENT statements DEDENT | simple_stmts
+    pub(super) fn block(&mut self) -> Option<Vec<Stmt>> {
+        if self.expect(TokenType::Newline).is_none() {
+            return self.simple_stmts();
+        }
+        self.expect(TokenType::Indent)?;
+        let body = self.statements()?;
+        self.expect(TokenType::Dedent)?;
+        Some(body)
+    }
+
+    /// `':' block`, as ends every clause of a compound statement, for the clause starting
+    /// with the keyword at `keyword`. A missing colon is an error wherever it is `forced`,
+    /// and otherwise only at the end of the line.
+    fn suite(&mut self, keyword: usize, forced: bool) -> Option<Vec<Stmt>> {
+        if forced {
+            self.forced(TokenType::Colon, ":")?;
+        } else if self.expect(TokenType::Colon).is_none() {
+            self.invalid(|p| {
+                let span = p.peek().span;
+                p.at(TokenType::Newline)
+                    .then(|| SyntaxError::new("expected ':'", span))
+            });
+            return None;
+        }
+        self.invalid(|p| {
+            p.expect(TokenType::Newline)?;
+            (!p.at(TokenType::Indent)).then(|| p.expected_block(&p.tokens[keyword]))
+        });
+        self.block()
+    }
+
+    /// decorators: ('@' named_expression NEWLINE)+
+    fn decorators(&mut self) -> Option<Vec<Expr>> {
+        let mut decorators = vec![];
+        while self.expect(TokenType::At).is_some() {
+            decorators.push(self.named_expression()?);
+            self.expect(TokenType::Newline)?;
+        }
+        Some(decorators)
+    }
+
+    /// class_def_raw: 'class' NAME [type_params] ['(' [arguments] ')'] ':' block
+    fn class_def_raw(&mut self, decorator_list: Vec<Expr>) -> Option<Stmt> {
+        let start = self.pos;
+        self.expect(TokenType::Class)?;
+        let (name, _) = self.name()?;
+        let type_params = self.attempt(Self::type_params).unwrap_or_default();
+        let (mut bases, mut keywords) = (vec![], vec![]);
+        if self.expect(TokenType::LPar).is_some() {
+            if !self.at(TokenType::RPar) {
+                (bases, keywords) = self.arguments()?;
+            }
+            self.expect(TokenType::RPar)?;
+        }
+        let body = self.suite(start, false)?;
+        Some(Stmt::ClassDef(ClassDef {
+            name,
+            bases,
+            keywords,
+            body,
+            decorator_list,
+            type_params,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// function_def_raw:
+    ///     [ASYNC] 'def' NAME [type_params] &&'(' [params] ')' ['->' expression] &&':' block
+    fn function_def_raw(&mut self, decorator_list: Vec<Expr>) -> Option<Stmt> {
+        let start = self.pos;
+        let is_async = self.expect(TokenType::Async).is_some();
+        self.expect(TokenType::Def)?;
+        let (name, _) = self.name()?;
+        let type_params = self.attempt(Self::type_params).unwrap_or_default();
+        self.forced(TokenType::LPar, "(")?;
+        let args = self.parameters(true, TokenType::RPar)?;
+        self.expect(TokenType::RPar)?;
+        let returns = match self.expect(TokenType::RArrow) {
+            Some(_) => Some(Box::new(self.expression()?)),
+            None => None,
+        };
+        let body = self.suite(start + is_async as usize, true)?;
+        let (args, span) = (Box::new(args), self.span_from(start));
+        Some(match is_async {
+            true => Stmt::AsyncFunctionDef(AsyncFunctionDef {
+                name,
+                args,
+                body,
+                decorator_list,
+                returns,
+                type_comment: None,
+                type_params,
+                span,
+            }),
+            false => Stmt::FunctionDef(FunctionDef {
+                name,
+                args,
+                body,
+                decorator_list,
+                returns,
+                type_comment: None,
+                type_params,
+                span,
+            }),
+        })
+    }
+
+    /// if_stmt: 'if' named_expression ':' block (elif_stmt | [else_block])
+    /// elif_stmt: 'elif' named_expression ':' block (elif_stmt | [else_block])
+    fn if_stmt(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        self.advance_map(|t| matches!(t, TokenType::If | TokenType::Elif).then_some(()))?;
+        let test = self.named_expression()?;
+        let body = self.suite(start, false)?;
+        let orelse = match self.peek_type(0) {
+            TokenType::Elif => vec![self.if_stmt()?],
+            _ => self.else_block()?,
+        };
+        Some(Stmt::If(If {
+            test: Box::new(test),
+            body,
+            orelse,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// else_block: 'else' &&':' block, or nothing.
+    fn else_block(&mut self) -> Option<Vec<Stmt>> {
+        match self.expect(TokenType::Else) {
+            Some(_) => self.suite(self.pos - 1, true),
+            None => Some(vec![]),
+        }
+    }
+
+    /// while_stmt: 'while' named_expression ':' block [else_block]
+    fn while_stmt(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        self.expect(TokenType::While)?;
+        let test = self.named_expression()?;
+        let body = self.suite(start, false)?;
+        let orelse = self.else_block()?;
+        Some(Stmt::While(While {
+            test: Box::new(test),
+            body,
+            orelse,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// for_stmt:
+    ///     | [ASYNC] 'for' star_targets 'in' ~ star_expressions ':' block [else_block]
+    ///     | invalid_for_target
+    fn for_stmt(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        let is_async = self.expect(TokenType::Async).is_some();
+        self.expect(TokenType::For)?;
+        let Some(target) = self.attempt(|p| {
+            let target = p.star_targets()?;
+            p.expect(TokenType::In)?;
+            Some(Box::new(target))
+        }) else {
+            self.pos = start;
+            self.invalid(Self::invalid_for_target);
+            return None;
+        };
+        let iter = Box::new(self.star_expressions()?);
+        let body = self.suite(start + is_async as usize, false)?;
+        let orelse = self.else_block()?;
+        let span = self.span_from(start);
+        Some(match is_async {
+            true => Stmt::AsyncFor(AsyncFor {
+                target,
+                iter,
+                body,
+                orelse,
+                type_comment: None,
+                span,
+            }),
+            false => Stmt::For(For {
+                target,
+                iter,
+                body,
+                orelse,
+                type_comment: None,
+                span,
+            }),
+        })
+    }
+
+    /// with_stmt:
+    ///     | [ASYNC] 'with' '(' ','.with_item+ ','? ')' ':' block
+    ///     | [ASYNC] 'with' ','.with_item+ ':' block
+    ///     | invalid_with_stmt
+    fn with_stmt(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        let is_async = self.expect(TokenType::Async).is_some();
+        self.expect(TokenType::With)?;
+        let items = self
+            .attempt(|p| {
+                let open = p.pos;
+                p.expect(TokenType::LPar)?;
+                let items = p.comma_list(Self::with_item)?;
+                p.expect(TokenType::RPar)?;
+                p.at(TokenType::Colon).then_some(())?;
+                // Without targets, the items read as a tuple before 3.9.
+                if items.iter().any(|item| item.optional_vars.is_some()) {
+                    let span = p.span_from(open);
+                    let feature = "Parenthesized context managers are";
+                    p.check_version(PythonVersion::Py39, feature, span);
+                }
+                Some(items)
+            })
+            .or_else(|| {
+                let items = self.comma_list(Self::with_item)?;
+                // A trailing comma is only allowed between parentheses.
+                let trailing = self.tokens[self.pos - 1].token_type == TokenType::Comma;
+                (!trailing).then_some(items)
+            });
+        let Some(items) = items else {
+            self.pos = start;
+            self.invalid(Self::invalid_with_stmt);
+            return None;
+        };
+        let body = self.suite(start + is_async as usize, false)?;
+        let span = self.span_from(start);
+        Some(match is_async {
+            true => Stmt::AsyncWith(AsyncWith {
+                items,
+                body,
+                type_comment: None,
+                span,
+            }),
+            false => Stmt::With(With {
+                items,
+                body,
+                type_comment: None,
+                span,
+            }),
+        })
+    }
+
+    /// with_item:
+    ///     | expression 'as' star_target &(',' | ')' | ':')
+    ///     | invalid_with_item
+    ///     | expression
+    fn with_item(&mut self) -> Option<WithItem> {
+        let start = self.pos;
+        let context_expr = Box::new(self.expression()?);
+        let optional_vars = self.attempt(|p| {
+            p.expect(TokenType::As)?;
+            let target = p.star_target()?;
+            matches!(
+                p.peek_type(0),
+                TokenType::Comma | TokenType::RPar | TokenType::Colon
+            )
+            .then_some(target)
+        });
+        if optional_vars.is_none() && self.at(TokenType::As) {
+            self.pos = start;
+            self.invalid(Self::invalid_with_item);
+            return None;
+        }
+        Some(WithItem {
+            context_expr,
+            optional_vars: optional_vars.map(Box::new),
+        })
+    }
+
+    /// try_stmt:
+    ///     | 'try' &&':' block finally_block
+    ///     | 'try' &&':' block except_block+ [else_block] [finally_block]
+    ///     | 'try' &&':' block except_star_block+ [else_block] [finally_block]
+    /// finally_block: 'finally' &&':' block
+    fn try_stmt(&mut self) -> Option<Stmt> {
+        let start = self.pos;
+        self.expect(TokenType::Try)?;
+        let body = self.suite(start, true)?;
+        let star = self.at(TokenType::Except) && *self.peek_type(1) == TokenType::Star;
+        let mut handlers = vec![];
+        while let Some(handler) = self.attempt(|p| p.except_block(star)) {
+            handlers.push(handler);
+        }
+        let orelse = match handlers.is_empty() {
+            true => vec![],
+            false => self.else_block()?,
+        };
+        let finalbody = match self.expect(TokenType::Finally) {
+            Some(_) => self.suite(self.pos - 1, true)?,
+            None if handlers.is_empty() => return None,
+            None => vec![],
+        };
+        let span = self.span_from(start);
+        Some(match star {
+            true => Stmt::TryStar(TryStar {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+                span,
+            }),
+            false => Stmt::Try(Try {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+                span,
+            }),
+        })
+    }
+
+    /// except_block:
+    ///     | 'except' expression ['as' NAME] ':' block
+    ///     | 'except' ':' block
+    ///     | invalid_except_stmt
+    /// except_star_block: 'except' '*' expression ['as' NAME] ':' block | invalid_except_stmt
+    fn except_block(&mut self, star: bool) -> Option<ExceptHandler> {
+        let start = self.pos;
+        self.expect(TokenType::Except)?;
+        if star {
+            self.expect(TokenType::Star)?;
+            let span = self.span_from(start);
+            self.check_version(PythonVersion::Py311, "Exception groups are", span);
+        }
+        let mut r#type = None;
+        let mut name = None;
+        if star || !matches!(self.peek_type(0), TokenType::Colon | TokenType::Newline) {
+            r#type = Some(Box::new(self.expression()?));
+            if self.at(TokenType::Comma) {
+                self.pos = start;
+                self.invalid(Self::invalid_except_stmt);
+                return None;
+            }
+            if self.expect(TokenType::As).is_some() {
+                name = Some(self.name()?.0);
+            }
+        }
+        let body = self.suite(start, false)?;
+        Some(ExceptHandler {
+            r#type,
+            name,
+            body,
+            span: self.span_from(start),
+        })
+    }
+}
+
+/// Whether a target unpacks with `*`, which `del` does not allow.
+fn has_starred(target: &Expr) -> bool {
+    match target {
+        Expr::Starred(_) => true,
+        Expr::Tuple(Tuple { elts, .. }) | Expr::List(List { elts, .. }) => {
+            elts.iter().any(has_starred)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,156 @@
+// Generated by `pragrat generate parser` from `test_grammar.gram`. Do not edit.
+use crate::types::TokenType;
+use super::{Parser, Rule};
+use crate::ast::{BinOp, Expr, ExprContext, List, Name, Operator, Tuple};
+use crate::types::Token;
+
+impl Parser {
+	/// test_start: test_item* &(NEWLINE | ENDMARKER) test_end
+	pub(super) fn test_start(&mut self) -> Option<Vec<Expr>> {
+		let p = self;
+		p.attempt(|p| {
+			let a = {
+				let mut items = vec![];
+				while let Some(item) = p.attempt(|p| p.test_item()) {
+					items.push(item);
+				}
+				items
+			};
+			let mark = p.pos;
+			let found = p.attempt(|p| {
+				if let Some(value) = p.attempt(|p| p.token(TokenType::Newline)) {
+					return Some(value);
+				}
+				p.attempt(|p| p.token(TokenType::Endmarker))
+			}).is_some();
+			p.pos = mark;
+			if !found {
+				return None;
+			}
+			p.test_end()?;
+			Some(a)
+		})
+	}
+
+	/// test_item: "soft" ~ test_sum ';' | test_sum !'=' [';']
+	pub(super) fn test_item(&mut self) -> Option<Expr> {
+		let p = self;
+		let mut cut = false;
+		if let Some(value) = p.attempt(|p| {
+			p.advance_if(|token| token.token_type == TokenType::Name && token.value == "soft").cloned()?;
+			cut = true;
+			let a = p.test_sum()?;
+			p.token(TokenType::Semi)?;
+			Some(a)
+		}) {
+			return Some(value);
+		}
+		if cut {
+			return None;
+		}
+		p.attempt(|p| {
+			let a = p.test_sum()?;
+			let mark = p.pos;
+			let found = p.token(TokenType::Equal).is_some();
+			p.pos = mark;
+			if found {
+				return None;
+			}
+			let _ = p.attempt(|p| p.token(TokenType::Semi));
+			Some(a)
+		})
+	}
+
+	/// test_sum: test_sum ('+' | '-') test_atom | test_atom
+	pub(super) fn test_sum(&mut self) -> Option<Expr> {
+		self.memo_left_rec(Rule::Generated("test_sum"), |p| {
+			if let Some(value) = p.attempt(|p| {
+				let start = p.pos;
+				let a = p.test_sum()?;
+				let op = p.attempt(|p| {
+					if let Some(value) = p.attempt(|p| {
+						p.token(TokenType::Plus)?;
+						Some(Operator::Add)
+					}) {
+						return Some(value);
+					}
+					p.attempt(|p| {
+						p.token(TokenType::Minus)?;
+						Some(Operator::Sub)
+					})
+				})?;
+				let b = p.test_atom()?;
+				Some(Expr::BinOp(BinOp { left: Box::new(a), op, right: Box::new(b), span: p.span_from(start) }))
+			}) {
+				return Some(value);
+			}
+			p.attempt(|p| p.test_atom())
+		})
+	}
+
+	/// test_atom: NAME | '(' ','.test_sum+ [','] ')' | '[' (NAME | 'None')+ ']'
+	pub(super) fn test_atom(&mut self) -> Option<Expr> {
+		self.memo(Rule::Generated("test_atom"), |p| {
+			if let Some(value) = p.attempt(|p| {
+				let a = p.token(TokenType::Name)?;
+				Some(name(a))
+			}) {
+				return Some(value);
+			}
+			if let Some(value) = p.attempt(|p| {
+				let start = p.pos;
+				p.token(TokenType::LPar)?;
+				let a = p.attempt(|p| {
+					let mut items = vec![p.test_sum()?];
+					while let Some(item) = p.attempt(|p| {
+						p.token(TokenType::Comma)?;
+						p.test_sum()
+					}) {
+						items.push(item);
+					}
+					Some(items)
+				})?;
+				let _ = p.attempt(|p| p.token(TokenType::Comma));
+				p.token(TokenType::RPar)?;
+				Some(Expr::Tuple(Tuple { elts: a, ctx: ExprContext::Load, span: p.span_from(start) }))
+			}) {
+				return Some(value);
+			}
+			p.attempt(|p| {
+				let start = p.pos;
+				p.token(TokenType::LSqB)?;
+				let a = {
+					let mut items = vec![];
+					while let Some(item) = p.attempt(|p| {
+						if let Some(value) = p.attempt(|p| p.token(TokenType::Name)) {
+							return Some(value);
+						}
+						p.attempt(|p| p.token(TokenType::None))
+					}) {
+						items.push(item);
+					}
+					(!items.is_empty()).then_some(items)
+				}?;
+				p.token(TokenType::RSqB)?;
+				Some({
+					let elts = a.into_iter().map(name).collect();
+					Expr::List(List { elts, ctx: ExprContext::Load, span: p.span_from(start) })
+				})
+			})
+		})
+	}
+
+	/// test_end: NEWLINE* ENDMARKER
+	pub(super) fn test_end(&mut self) -> Option<()> {
+		let p = self;
+		p.attempt(|p| {
+			while p.attempt(|p| p.token(TokenType::Newline)).is_some() {}
+			p.token(TokenType::Endmarker)?;
+			Some(())
+		})
+	}
+}
+
+fn name(token: Token) -> Expr {
+	Expr::Name(Name { id: token.value, ctx: ExprContext::Load, span: token.span })
+}
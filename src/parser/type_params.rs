@@ -0,0 +1,187 @@
+// Generated by `pragrat generate parser` from `type_params.gram`. Do not edit.
+use crate::types::TokenType;
+use super::{Parser, Rule};
+use crate::ast::{
+	Expr, ExprContext, Name, ParamSpec, Stmt, TypeAlias, TypeParam, TypeVar, TypeVarTuple,
+};
+use super::{identifier, SyntaxError};
+use crate::types::PythonVersion;
+
+impl Parser {
+	/// type_alias: "type" NAME [type_params] '=' expression
+	pub(super) fn type_alias(&mut self) -> Option<Stmt> {
+		let p = self;
+		p.attempt(|p| {
+			let start = p.pos;
+			let keyword = p.advance_if(|token| token.token_type == TokenType::Name && token.value == "type").cloned()?;
+			let name = p.token(TokenType::Name)?;
+			let params = p.attempt(|p| p.type_params());
+			p.token(TokenType::Equal)?;
+			let value = p.expression()?;
+			Some({
+				p.check_version(PythonVersion::Py312, "Type statement is", keyword.span);
+				Stmt::TypeAlias(TypeAlias {
+					name: Box::new(Expr::Name(Name { id: identifier(&name), ctx: ExprContext::Store, span: name.span })),
+					type_params: params.unwrap_or_default(),
+					value: Box::new(value),
+					span: p.span_from(start),
+				})
+			})
+		})
+	}
+
+	/// type_params: invalid_type_params | '[' type_param_seq ']'
+	pub(super) fn type_params(&mut self) -> Option<Vec<TypeParam>> {
+		let p = self;
+		p.invalid(Self::invalid_type_params);
+		p.attempt(|p| {
+			let start = p.pos;
+			p.token(TokenType::LSqB)?;
+			let params = p.type_param_seq()?;
+			p.token(TokenType::RSqB)?;
+			Some({
+				p.check_version(PythonVersion::Py312, "Type parameter lists are", p.span_from(start));
+				params
+			})
+		})
+	}
+
+	/// type_param_seq: ','.type_param+ [',']
+	pub(super) fn type_param_seq(&mut self) -> Option<Vec<TypeParam>> {
+		let p = self;
+		p.attempt(|p| {
+			let params = p.attempt(|p| {
+				let mut items = vec![p.type_param()?];
+				while let Some(item) = p.attempt(|p| {
+					p.token(TokenType::Comma)?;
+					p.type_param()
+				}) {
+					items.push(item);
+				}
+				Some(items)
+			})?;
+			let _ = p.attempt(|p| p.token(TokenType::Comma));
+			Some(params)
+		})
+	}
+
+	/// type_param:
+	///     | NAME [type_param_bound] [type_param_default]
+	///     | invalid_type_param
+	///     | '*' NAME [type_param_starred_default]
+	///     | '**' NAME [type_param_default]
+	pub(super) fn type_param(&mut self) -> Option<TypeParam> {
+		self.memo(Rule::Generated("type_param"), |p| {
+			if let Some(value) = p.attempt(|p| {
+				let start = p.pos;
+				let name = p.token(TokenType::Name)?;
+				let bound = p.attempt(|p| p.type_param_bound());
+				let default = p.attempt(|p| p.type_param_default());
+				Some(TypeParam::TypeVar(TypeVar {
+					name: identifier(&name),
+					bound: bound.map(Box::new),
+					default_value: default.map(Box::new),
+					span: p.span_from(start),
+				}))
+			}) {
+				return Some(value);
+			}
+			p.invalid(Self::invalid_type_param);
+			if let Some(value) = p.attempt(|p| {
+				let start = p.pos;
+				p.token(TokenType::Star)?;
+				let name = p.token(TokenType::Name)?;
+				let default = p.attempt(|p| p.type_param_starred_default());
+				Some(TypeParam::TypeVarTuple(TypeVarTuple { name: identifier(&name), default_value: default.map(Box::new), span: p.span_from(start) }))
+			}) {
+				return Some(value);
+			}
+			p.attempt(|p| {
+				let start = p.pos;
+				p.token(TokenType::DoubleStar)?;
+				let name = p.token(TokenType::Name)?;
+				let default = p.attempt(|p| p.type_param_default());
+				Some(TypeParam::ParamSpec(ParamSpec { name: identifier(&name), default_value: default.map(Box::new), span: p.span_from(start) }))
+			})
+		})
+	}
+
+	/// type_param_bound: ':' expression
+	pub(super) fn type_param_bound(&mut self) -> Option<Expr> {
+		let p = self;
+		p.attempt(|p| {
+			p.token(TokenType::Colon)?;
+			let bound = p.expression()?;
+			Some(bound)
+		})
+	}
+
+	/// type_param_default: '=' expression
+	pub(super) fn type_param_default(&mut self) -> Option<Expr> {
+		let p = self;
+		p.attempt(|p| {
+			p.token(TokenType::Equal)?;
+			let default = p.expression()?;
+			Some({
+				p.check_version(PythonVersion::Py313, "Type parameter defaults are", default.span());
+				default
+			})
+		})
+	}
+
+	/// type_param_starred_default: '=' star_expression
+	pub(super) fn type_param_starred_default(&mut self) -> Option<Expr> {
+		let p = self;
+		p.attempt(|p| {
+			p.token(TokenType::Equal)?;
+			let default = p.star_expression()?;
+			Some({
+				p.check_version(PythonVersion::Py313, "Type parameter defaults are", default.span());
+				default
+			})
+		})
+	}
+
+	/// invalid_type_params: '[' ']'
+	pub(super) fn invalid_type_params(&mut self) -> Option<SyntaxError> {
+		let p = self;
+		p.attempt(|p| {
+			p.token(TokenType::LSqB)?;
+			let token = p.token(TokenType::RSqB)?;
+			Some(p.error_starting_from(token.span, "Type parameter list cannot be empty"))
+		})
+	}
+
+	/// invalid_type_param: '*' NAME ':' expression | '**' NAME ':' expression
+	pub(super) fn invalid_type_param(&mut self) -> Option<SyntaxError> {
+		let p = self;
+		if let Some(value) = p.attempt(|p| {
+			p.token(TokenType::Star)?;
+			p.token(TokenType::Name)?;
+			let colon = p.token(TokenType::Colon)?;
+			let bound = p.expression()?;
+			Some({
+				let message = match bound {
+					Expr::Tuple(_) => "cannot use constraints with TypeVarTuple",
+					_ => "cannot use bound with TypeVarTuple",
+				};
+				p.error_starting_from(colon.span, message)
+			})
+		}) {
+			return Some(value);
+		}
+		p.attempt(|p| {
+			p.token(TokenType::DoubleStar)?;
+			p.token(TokenType::Name)?;
+			let colon = p.token(TokenType::Colon)?;
+			let bound = p.expression()?;
+			Some({
+				let message = match bound {
+					Expr::Tuple(_) => "cannot use constraints with ParamSpec",
+					_ => "cannot use bound with ParamSpec",
+				};
+				p.error_starting_from(colon.span, message)
+			})
+		})
+	}
+}
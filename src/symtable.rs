@@ -0,0 +1,1643 @@
+//! Scope analysis of a parsed module, like CPython's `symtable`. Every module, class,
+//! function, lambda and comprehension gets a table of the names it binds or uses, each
+//! resolved as local, cell, free, or global, declared or implicit. The two passes follow
+//! `Python/symtable.c`: the tree is walked once to record how each block binds and uses its
+//! names, then the blocks are analyzed from the top down to resolve the names each one does
+//! not bind. From Python 3.12, list, set and dict comprehensions are inlined into the block
+//! they appear in (PEP 709), so their names and nested blocks move into its table.
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::{BitOr, BitOrAssign},
+};
+
+use crate::ast::*;
+use crate::parser::SyntaxError;
+use crate::types::{Identifier, Literal, PythonVersion, Span};
+use crate::visit::{self, Visitor};
+
+/// How a block binds and uses a name, with the bits of CPython's `DEF_*` flags.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SymbolFlags(u16);
+
+impl SymbolFlags {
+    /// Declared `global`, or bound by an assignment expression in a comprehension at the top
+    /// level.
+    pub const DEF_GLOBAL: Self = SymbolFlags(1);
+    /// Assigned to, deleted, or bound by a definition or a `for`, `with`, `except` or
+    /// `match` target.
+    pub const DEF_LOCAL: Self = SymbolFlags(2);
+    pub const DEF_PARAM: Self = SymbolFlags(2 << 1);
+    pub const DEF_NONLOCAL: Self = SymbolFlags(2 << 2);
+    pub const USE: Self = SymbolFlags(2 << 3);
+    /// Free in a method of the class that also binds or declares the name.
+    pub const DEF_FREE_CLASS: Self = SymbolFlags(2 << 5);
+    pub const DEF_IMPORT: Self = SymbolFlags(2 << 6);
+    pub const DEF_ANNOT: Self = SymbolFlags(2 << 7);
+    /// Bound as the target of a comprehension's `for`.
+    pub const DEF_COMP_ITER: Self = SymbolFlags(2 << 8);
+    pub const DEF_TYPE_PARAM: Self = SymbolFlags(2 << 9);
+    /// A cell in a comprehension inlined into the block.
+    pub const DEF_COMP_CELL: Self = SymbolFlags(2 << 10);
+    /// Any of the flags binding the name in the block.
+    pub const DEF_BOUND: Self = SymbolFlags(2 | 2 << 1 | 2 << 6);
+
+    pub fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Whether all of the flags of `other` are set.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether any of the flags of `other` is set.
+    pub fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl BitOr for SymbolFlags {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        SymbolFlags(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for SymbolFlags {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+/// Where a name is looked up at runtime, CPython's scopes of a symbol.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SymbolScope {
+    /// Bound in the block and used by no nested block.
+    Local,
+    /// Declared `global`.
+    GlobalExplicit,
+    /// Neither bound in the block nor in an enclosing function, so looked up in the
+    /// globals, then the builtins.
+    GlobalImplicit,
+    /// Bound in an enclosing function, or declared `nonlocal`.
+    Free,
+    /// Bound in the block and free in a nested block.
+    Cell,
+}
+
+/// The kind of comprehension a block is for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ComprehensionKind {
+    List,
+    Set,
+    Dict,
+    Generator,
+}
+
+impl ComprehensionKind {
+    /// The name of the block, as in CPython.
+    fn name(self) -> &'static str {
+        match self {
+            ComprehensionKind::List => "listcomp",
+            ComprehensionKind::Set => "setcomp",
+            ComprehensionKind::Dict => "dictcomp",
+            ComprehensionKind::Generator => "genexpr",
+        }
+    }
+}
+
+/// The kind of block a table is for. CPython has a single function block type for
+/// functions, lambdas and comprehensions, which are told apart here.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TableType {
+    Module,
+    Class,
+    Function,
+    Lambda,
+    Comprehension(ComprehensionKind),
+    /// The annotations under `from __future__ import annotations`, which are never
+    /// evaluated. Their blocks are checked, then dropped from the tree as in CPython.
+    Annotation,
+    /// The value of a `type` statement, evaluated lazily.
+    TypeAlias,
+    /// The type parameters of a generic class, function or type alias, and the scope the
+    /// definition is evaluated in.
+    TypeParameters,
+    /// The bound or constraints of a type variable, or the default of a type parameter,
+    /// evaluated lazily.
+    TypeVarBound,
+}
+
+impl TableType {
+    /// Whether the block has fast locals, so that the names it binds are visible to the
+    /// blocks nested in it.
+    fn is_function_like(self) -> bool {
+        !matches!(
+            self,
+            TableType::Module | TableType::Class | TableType::Annotation
+        )
+    }
+}
+
+/// A name in a block, with how the block binds and uses it and where it is looked up.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    /// The name, mangled to `_Class__name` for a private name in a class.
+    pub name: String,
+    pub flags: SymbolFlags,
+    pub scope: SymbolScope,
+}
+
+impl Symbol {
+    pub fn is_referenced(&self) -> bool {
+        self.flags.intersects(SymbolFlags::USE)
+    }
+
+    pub fn is_assigned(&self) -> bool {
+        self.flags.intersects(SymbolFlags::DEF_LOCAL)
+    }
+
+    pub fn is_imported(&self) -> bool {
+        self.flags.intersects(SymbolFlags::DEF_IMPORT)
+    }
+
+    pub fn is_parameter(&self) -> bool {
+        self.flags.intersects(SymbolFlags::DEF_PARAM)
+    }
+
+    pub fn is_annotated(&self) -> bool {
+        self.flags.intersects(SymbolFlags::DEF_ANNOT)
+    }
+
+    pub fn is_nonlocal(&self) -> bool {
+        self.flags.intersects(SymbolFlags::DEF_NONLOCAL)
+    }
+
+    pub fn is_declared_global(&self) -> bool {
+        self.scope == SymbolScope::GlobalExplicit
+    }
+
+    pub fn is_global(&self) -> bool {
+        matches!(
+            self.scope,
+            SymbolScope::GlobalExplicit | SymbolScope::GlobalImplicit
+        )
+    }
+
+    pub fn is_local(&self) -> bool {
+        matches!(self.scope, SymbolScope::Local | SymbolScope::Cell)
+    }
+
+    pub fn is_free(&self) -> bool {
+        self.scope == SymbolScope::Free
+    }
+}
+
+/// The symbols of a block and the tables of the blocks nested in it, like CPython's
+/// `symtable.SymbolTable`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymbolTable {
+    pub table_type: TableType,
+    /// `top` for the module, the name of a class, function, type alias or type variable,
+    /// `lambda`, or `listcomp`, `setcomp`, `dictcomp` or `genexpr`.
+    pub name: String,
+    /// The span of the definition, empty for the module.
+    pub span: Span,
+    /// The symbols in the order the block first mentions them, then the names free in
+    /// nested blocks only.
+    pub symbols: Vec<Symbol>,
+    pub children: Vec<SymbolTable>,
+    /// Whether the block is within a function-like block.
+    pub is_nested: bool,
+    pub is_generator: bool,
+    pub is_coroutine: bool,
+    pub has_varargs: bool,
+    pub has_varkeywords: bool,
+    pub returns_value: bool,
+    /// Whether the names the block does not bind are first looked up in the enclosing
+    /// class, for the type parameters, type aliases and bounds within a class.
+    pub can_see_class_scope: bool,
+    /// Whether a method of the class uses `__class__` or `super`, so the class creates a
+    /// `__class__` cell.
+    pub needs_class_closure: bool,
+    /// Whether a block within the class can see its namespace, so the class creates a
+    /// `__classdict__` cell.
+    pub needs_classdict: bool,
+    index: HashMap<String, usize>,
+    /// The `global` and `nonlocal` declarations, where errors about them are reported.
+    directives: Vec<(String, Span)>,
+}
+
+impl SymbolTable {
+    fn new(table_type: TableType, name: &str, span: Span, is_nested: bool) -> Self {
+        SymbolTable {
+            table_type,
+            name: name.to_string(),
+            span,
+            symbols: vec![],
+            children: vec![],
+            is_nested,
+            is_generator: false,
+            is_coroutine: false,
+            has_varargs: false,
+            has_varkeywords: false,
+            returns_value: false,
+            can_see_class_scope: false,
+            needs_class_closure: false,
+            needs_classdict: false,
+            index: HashMap::new(),
+            directives: vec![],
+        }
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<&Symbol> {
+        self.index.get(name).map(|&i| &self.symbols[i])
+    }
+
+    /// The parameters of the function, in order.
+    pub fn parameters(&self) -> Vec<&str> {
+        self.names(|symbol| symbol.is_parameter())
+    }
+
+    /// The names local to the block, including its cells.
+    pub fn locals(&self) -> Vec<&str> {
+        self.names(Symbol::is_local)
+    }
+
+    pub fn globals(&self) -> Vec<&str> {
+        self.names(Symbol::is_global)
+    }
+
+    pub fn nonlocals(&self) -> Vec<&str> {
+        self.names(Symbol::is_nonlocal)
+    }
+
+    pub fn frees(&self) -> Vec<&str> {
+        self.names(Symbol::is_free)
+    }
+
+    fn names(&self, predicate: impl Fn(&Symbol) -> bool) -> Vec<&str> {
+        self.symbols
+            .iter()
+            .filter(|symbol| predicate(symbol))
+            .map(|symbol| symbol.name.as_str())
+            .collect()
+    }
+
+    /// The flags of `name`, empty if the block does not mention it.
+    fn flags(&self, name: &str) -> SymbolFlags {
+        self.lookup(name)
+            .map_or(SymbolFlags::default(), |symbol| symbol.flags)
+    }
+
+    /// Sets the flags of `name`, adding it with `scope` if it is new.
+    fn set(&mut self, name: &str, flags: SymbolFlags, scope: SymbolScope) {
+        match self.index.get(name) {
+            Some(&i) => self.symbols[i].flags = flags,
+            None => {
+                self.index.insert(name.to_string(), self.symbols.len());
+                self.symbols.push(Symbol {
+                    name: name.to_string(),
+                    flags,
+                    scope,
+                });
+            }
+        }
+    }
+
+    fn error_at_directive(&self, name: &str, message: String) -> SyntaxError {
+        let span = self
+            .directives
+            .iter()
+            .find(|(directive, _)| directive == name)
+            .map_or(self.span, |(_, span)| *span);
+        SyntaxError::new(message, span)
+    }
+}
+
+/// Builds the symbol tables of `module`, like `symtable.symtable(source, filename, "exec")`
+/// in the latest Python release.
+pub fn symtable(module: &Mod) -> Result<SymbolTable, SyntaxError> {
+    symtable_with_version(module, PythonVersion::default())
+}
+
+/// Builds the symbol tables of `module` as CPython `version` does. Before Python 3.12,
+/// comprehensions keep blocks of their own.
+pub fn symtable_with_version(
+    module: &Mod,
+    version: PythonVersion,
+) -> Result<SymbolTable, SyntaxError> {
+    if let Mod::FunctionType(_) = module {
+        return Err(SyntaxError::new(
+            "this compiler does not handle FunctionTypes",
+            Span::default(),
+        ));
+    }
+    let mut builder = Builder {
+        stack: vec![],
+        private: None,
+        future_annotations: has_future_annotations(module),
+        version,
+        error: None,
+    };
+    builder.enter_block("top", TableType::Module, Span::default());
+    builder.visit_mod(module);
+    if let Some(error) = builder.error {
+        return Err(error);
+    }
+    let mut top = builder.stack.pop().expect("Unbalanced blocks").table;
+    let mut analysis = Analysis {
+        inline_comprehensions: version >= PythonVersion::Py312,
+        free_class_unbound: version >= PythonVersion::Py312,
+    };
+    analysis.analyze_block(
+        &mut top,
+        None,
+        &mut HashSet::new(),
+        &mut HashSet::new(),
+        &mut HashSet::new(),
+        None,
+    )?;
+    Ok(top)
+}
+
+/// Whether the future statements at the top of `module` import `annotations`.
+fn has_future_annotations(module: &Mod) -> bool {
+    let Mod::Module(module) = module else {
+        return false;
+    };
+    let docstring = matches!(module.body.first(), Some(Stmt::ExprStmt(ExprStmt { value, .. }))
+        if matches!(&**value, Expr::Constant(Constant { value: Literal::String(_), .. })));
+    module
+        .body
+        .iter()
+        .skip(usize::from(docstring))
+        .map_while(|stmt| match stmt {
+            Stmt::ImportFrom(node) if node.module.as_deref() == Some("__future__") => Some(node),
+            _ => None,
+        })
+        .any(|node| node.names.iter().any(|alias| alias.name == "annotations"))
+}
+
+/// Mangles a private name used in `class` to `_class__name`, as CPython's `_Py_Mangle`.
+fn mangle(class: Option<&str>, name: &str) -> String {
+    match class.map(|class| class.trim_start_matches('_')) {
+        Some(class)
+            if !class.is_empty()
+                && name.starts_with("__")
+                && !name.ends_with("__")
+                && !name.contains('.') =>
+        {
+            format!("_{}{}", class, name)
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// A block being built, with the state of the walk within it.
+struct Block {
+    table: SymbolTable,
+    /// Whether the names bound are the targets of a comprehension's `for`.
+    comp_iter_target: bool,
+    /// The depth within the iterables of comprehensions, where assignment expressions are
+    /// not allowed.
+    comp_iter_expr: usize,
+    /// What a `TypeVarBound` block evaluates, e.g. `a TypeVar bound`, for its errors.
+    scope_info: &'static str,
+}
+
+/// The first pass, recording the names each block binds and uses.
+struct Builder {
+    /// The blocks being built, the innermost last.
+    stack: Vec<Block>,
+    /// The class whose private names are mangled.
+    private: Option<String>,
+    future_annotations: bool,
+    version: PythonVersion,
+    error: Option<SyntaxError>,
+}
+
+impl Builder {
+    fn current(&mut self) -> &mut Block {
+        self.stack.last_mut().expect("Unbalanced blocks")
+    }
+
+    fn table_type(&self) -> TableType {
+        self.stack
+            .last()
+            .expect("Unbalanced blocks")
+            .table
+            .table_type
+    }
+
+    fn fail(&mut self, message: String, span: Span) {
+        if self.error.is_none() {
+            self.error = Some(SyntaxError::new(message, span));
+        }
+    }
+
+    fn enter_block(&mut self, name: &str, table_type: TableType, span: Span) {
+        let (is_nested, comp_iter_expr) = match self.stack.last() {
+            Some(block) => (
+                block.table.is_nested || block.table.table_type.is_function_like(),
+                block.comp_iter_expr,
+            ),
+            None => (false, 0),
+        };
+        self.stack.push(Block {
+            table: SymbolTable::new(table_type, name, span, is_nested),
+            comp_iter_target: false,
+            comp_iter_expr,
+            scope_info: "",
+        });
+    }
+
+    fn exit_block(&mut self) {
+        let block = self.stack.pop().expect("Unbalanced blocks");
+        if block.table.table_type != TableType::Annotation {
+            self.current().table.children.push(block.table);
+        }
+    }
+
+    /// The flags of `name` in the current block.
+    fn lookup(&self, name: &str) -> SymbolFlags {
+        let block = self.stack.last().expect("Unbalanced blocks");
+        block.table.flags(&mangle(self.private.as_deref(), name))
+    }
+
+    fn add_def(&mut self, name: &str, flag: SymbolFlags, span: Span) {
+        self.add_def_in(self.stack.len() - 1, name, flag, span);
+    }
+
+    /// Records that the block at `index` in the stack binds or uses `name` as `flag` says.
+    /// A name declared global is also marked so in the module.
+    fn add_def_in(&mut self, index: usize, name: &str, flag: SymbolFlags, span: Span) {
+        let mangled = mangle(self.private.as_deref(), name);
+        let block = &mut self.stack[index];
+        let mut flags = block.table.flags(&mangled);
+        if flag.intersects(SymbolFlags::DEF_PARAM) && flags.intersects(SymbolFlags::DEF_PARAM) {
+            let message = format!("duplicate argument '{}' in function definition", name);
+            return self.fail(message, span);
+        }
+        if flag.intersects(SymbolFlags::DEF_TYPE_PARAM)
+            && flags.intersects(SymbolFlags::DEF_TYPE_PARAM)
+        {
+            return self.fail(format!("duplicate type parameter '{}'", name), span);
+        }
+        flags |= flag;
+        if block.comp_iter_target {
+            if flags.intersects(SymbolFlags::DEF_GLOBAL | SymbolFlags::DEF_NONLOCAL) {
+                let message = format!(
+                    "comprehension inner loop cannot rebind assignment expression target '{}'",
+                    name
+                );
+                return self.fail(message, span);
+            }
+            flags |= SymbolFlags::DEF_COMP_ITER;
+        }
+        block.table.set(&mangled, flags, SymbolScope::Local);
+        if flag.intersects(SymbolFlags::DEF_GLOBAL) && !flag.intersects(SymbolFlags::DEF_PARAM) {
+            let module = &mut self.stack[0].table;
+            let flags = module.flags(&mangled) | flag;
+            module.set(&mangled, flags, SymbolScope::Local);
+        }
+    }
+
+    fn record_directive(&mut self, name: &str, span: Span) {
+        let mangled = mangle(self.private.as_deref(), name);
+        self.current().table.directives.push((mangled, span));
+    }
+
+    /// Fails if the current block is one where `what` cannot be used.
+    fn check_allowed(&mut self, what: &str, span: Span) -> bool {
+        let message = match self.table_type() {
+            TableType::Annotation if self.version < PythonVersion::Py312 => {
+                format!("'{}' can not be used within an annotation", what)
+            }
+            TableType::Annotation => format!("{} cannot be used within an annotation", what),
+            TableType::TypeVarBound => {
+                let scope_info = self.stack.last().expect("Unbalanced blocks").scope_info;
+                format!("{} cannot be used within {}", what, scope_info)
+            }
+            TableType::TypeAlias => format!("{} cannot be used within a type alias", what),
+            TableType::TypeParameters => {
+                format!("{} cannot be used within the definition of a generic", what)
+            }
+            _ => return true,
+        };
+        self.fail(message, span);
+        false
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn function(
+        &mut self,
+        name: &str,
+        args: &Arguments,
+        body: &[Stmt],
+        decorator_list: &[Expr],
+        returns: Option<&Expr>,
+        type_params: &[TypeParam],
+        span: Span,
+        is_async: bool,
+    ) {
+        self.add_def(name, SymbolFlags::DEF_LOCAL, span);
+        self.defaults(args);
+        for decorator in decorator_list {
+            self.visit_expr(decorator);
+        }
+        if !type_params.is_empty() {
+            let has_kwdefaults = args.kw_defaults.iter().any(Option::is_some);
+            self.enter_type_params(name, span, !args.defaults.is_empty(), has_kwdefaults, false);
+            for type_param in type_params {
+                self.visit_typeparam(type_param);
+            }
+        }
+        self.annotations(args, returns, span);
+        self.enter_block(name, TableType::Function, span);
+        self.current().table.is_coroutine = is_async;
+        self.parameters(args);
+        for stmt in body {
+            self.visit_stmt(stmt);
+        }
+        self.exit_block();
+        if !type_params.is_empty() {
+            self.exit_block();
+        }
+    }
+
+    /// Visits the defaults of `args`, which are evaluated in the enclosing block.
+    fn defaults(&mut self, args: &Arguments) {
+        for default in &args.defaults {
+            self.visit_expr(default);
+        }
+        for default in args.kw_defaults.iter().flatten() {
+            self.visit_expr(default);
+        }
+    }
+
+    fn parameters(&mut self, args: &Arguments) {
+        for arg in args
+            .posonlyargs
+            .iter()
+            .chain(&args.args)
+            .chain(&args.kwonlyargs)
+        {
+            self.add_def(&arg.arg, SymbolFlags::DEF_PARAM, arg.span);
+        }
+        if let Some(arg) = &args.vararg {
+            self.add_def(&arg.arg, SymbolFlags::DEF_PARAM, arg.span);
+            self.current().table.has_varargs = true;
+        }
+        if let Some(arg) = &args.kwarg {
+            self.add_def(&arg.arg, SymbolFlags::DEF_PARAM, arg.span);
+            self.current().table.has_varkeywords = true;
+        }
+    }
+
+    /// Visits the annotations of a function, in a block of their own under
+    /// `from __future__ import annotations`.
+    fn annotations(&mut self, args: &Arguments, returns: Option<&Expr>, span: Span) {
+        if self.future_annotations {
+            self.enter_block("_annotation", TableType::Annotation, span);
+        }
+        let args = (args.posonlyargs.iter().chain(&args.args))
+            .chain(args.vararg.as_deref())
+            .chain(args.kwarg.as_deref())
+            .chain(&args.kwonlyargs);
+        for annotation in args.filter_map(|arg| arg.annotation.as_ref()) {
+            self.visit_expr(annotation);
+        }
+        if self.future_annotations {
+            self.exit_block();
+        }
+        if let Some(returns) = returns {
+            self.annotation(returns);
+        }
+    }
+
+    fn annotation(&mut self, annotation: &Expr) {
+        if self.future_annotations {
+            self.enter_block("_annotation", TableType::Annotation, annotation.span());
+        }
+        self.visit_expr(annotation);
+        if self.future_annotations {
+            self.exit_block();
+        }
+    }
+
+    /// Visits the bound or default of the type parameter `name` in a block of its own.
+    fn type_var_bound(&mut self, name: &str, expr: &Expr, scope_info: &'static str, span: Span) {
+        let in_class = self.current().table.can_see_class_scope;
+        self.enter_block(name, TableType::TypeVarBound, span);
+        self.current().scope_info = scope_info;
+        if in_class {
+            self.current().table.can_see_class_scope = true;
+            self.add_def("__classdict__", SymbolFlags::USE, expr.span());
+        }
+        self.visit_expr(expr);
+        self.exit_block();
+    }
+
+    /// Enters the block of the type parameters of a generic class, function or type alias.
+    fn enter_type_params(
+        &mut self,
+        name: &str,
+        span: Span,
+        has_defaults: bool,
+        has_kwdefaults: bool,
+        is_class: bool,
+    ) {
+        let in_class = self.table_type() == TableType::Class;
+        self.enter_block(name, TableType::TypeParameters, span);
+        if in_class {
+            self.current().table.can_see_class_scope = true;
+            self.add_def("__classdict__", SymbolFlags::USE, span);
+        }
+        if is_class {
+            // Set when the tuple of type parameters is created, and used for the bases.
+            self.add_def(".type_params", SymbolFlags::DEF_LOCAL, span);
+            self.add_def(".type_params", SymbolFlags::USE, span);
+            self.private = Some(name.to_string());
+            self.add_def(".generic_base", SymbolFlags::DEF_LOCAL, span);
+            self.add_def(".generic_base", SymbolFlags::USE, span);
+        }
+        if has_defaults {
+            self.add_def(".defaults", SymbolFlags::DEF_PARAM, span);
+        }
+        if has_kwdefaults {
+            self.add_def(".kwdefaults", SymbolFlags::DEF_PARAM, span);
+        }
+    }
+
+    /// Records the `global` or `nonlocal` declaration of `names`.
+    fn declare(&mut self, names: &[Identifier], flag: SymbolFlags, span: Span) {
+        let keyword = if flag == SymbolFlags::DEF_GLOBAL {
+            "global"
+        } else {
+            "nonlocal"
+        };
+        for name in names {
+            let flags = self.lookup(name);
+            let message = if flags.intersects(SymbolFlags::DEF_PARAM) {
+                format!("name '{}' is parameter and {}", name, keyword)
+            } else if flags.intersects(SymbolFlags::USE) {
+                format!("name '{}' is used prior to {} declaration", name, keyword)
+            } else if flags.intersects(SymbolFlags::DEF_ANNOT) {
+                format!("annotated name '{}' can't be {}", name, keyword)
+            } else if flags.intersects(SymbolFlags::DEF_LOCAL) {
+                format!(
+                    "name '{}' is assigned to before {} declaration",
+                    name, keyword
+                )
+            } else {
+                self.add_def(name, flag, span);
+                self.record_directive(name, span);
+                continue;
+            };
+            return self.fail(message, span);
+        }
+    }
+
+    fn comprehension(
+        &mut self,
+        kind: ComprehensionKind,
+        generators: &[Comprehension],
+        elt: &Expr,
+        value: Option<&Expr>,
+        span: Span,
+    ) {
+        let Some(outermost) = generators.first() else {
+            return;
+        };
+        // The outermost iterable is evaluated in the enclosing block, and passed as `.0`.
+        self.current().comp_iter_expr += 1;
+        self.visit_expr(&outermost.iter);
+        self.current().comp_iter_expr -= 1;
+        self.enter_block(kind.name(), TableType::Comprehension(kind), span);
+        if outermost.is_async {
+            self.current().table.is_coroutine = true;
+        }
+        self.add_def(".0", SymbolFlags::DEF_PARAM, span);
+        self.current().comp_iter_target = true;
+        self.visit_expr(&outermost.target);
+        self.current().comp_iter_target = false;
+        for condition in &outermost.ifs {
+            self.visit_expr(condition);
+        }
+        for generator in &generators[1..] {
+            self.visit_comprehension(generator);
+        }
+        if let Some(value) = value {
+            self.visit_expr(value);
+        }
+        self.visit_expr(elt);
+        let table = &mut self.current().table;
+        let is_generator = kind == ComprehensionKind::Generator;
+        table.is_generator |= is_generator;
+        let is_async = table.is_coroutine && !is_generator;
+        self.exit_block();
+        if is_async {
+            self.current().table.is_coroutine = true;
+        }
+    }
+
+    /// Binds the target of an assignment expression within a comprehension in the block
+    /// enclosing the comprehensions, declared there as it is in the function or module.
+    fn extend_namedexpr_scope(&mut self, target: &Name) {
+        let name = &target.id;
+        for index in (0..self.stack.len()).rev() {
+            let table = &self.stack[index].table;
+            let flags = table.flags(name);
+            let message = match table.table_type {
+                TableType::Comprehension(_) => {
+                    if flags.contains(SymbolFlags::DEF_COMP_ITER | SymbolFlags::DEF_LOCAL) {
+                        let message = format!(
+                            "assignment expression cannot rebind comprehension iteration variable '{}'",
+                            name
+                        );
+                        return self.fail(message, target.span);
+                    }
+                    continue;
+                }
+                TableType::Annotation => continue,
+                TableType::Function | TableType::Lambda => {
+                    let flag = if flags.intersects(SymbolFlags::DEF_GLOBAL) {
+                        SymbolFlags::DEF_GLOBAL
+                    } else {
+                        SymbolFlags::DEF_NONLOCAL
+                    };
+                    self.add_def(name, flag, target.span);
+                    self.record_directive(name, target.span);
+                    return self.add_def_in(index, name, SymbolFlags::DEF_LOCAL, target.span);
+                }
+                TableType::Module => {
+                    self.add_def(name, SymbolFlags::DEF_GLOBAL, target.span);
+                    self.record_directive(name, target.span);
+                    return self.add_def_in(index, name, SymbolFlags::DEF_GLOBAL, target.span);
+                }
+                TableType::Class => "cannot be used in a class body",
+                TableType::TypeParameters => "cannot be used within the definition of a generic",
+                TableType::TypeAlias => "cannot be used in a type alias",
+                TableType::TypeVarBound => "cannot be used in a TypeVar bound",
+            };
+            let message = format!("assignment expression within a comprehension {}", message);
+            return self.fail(message, target.span);
+        }
+    }
+
+    fn try_statement(
+        &mut self,
+        body: &[Stmt],
+        handlers: &[ExceptHandler],
+        orelse: &[Stmt],
+        finalbody: &[Stmt],
+    ) {
+        for stmt in body.iter().chain(orelse) {
+            self.visit_stmt(stmt);
+        }
+        for handler in handlers {
+            self.visit_excepthandler(handler);
+        }
+        for stmt in finalbody {
+            self.visit_stmt(stmt);
+        }
+    }
+
+    /// Fails on a `yield` in a comprehension.
+    fn check_yield(&mut self, span: Span) {
+        let TableType::Comprehension(kind) = self.table_type() else {
+            return;
+        };
+        let message = match kind {
+            ComprehensionKind::List => "'yield' inside list comprehension",
+            ComprehensionKind::Set => "'yield' inside set comprehension",
+            ComprehensionKind::Dict => "'yield' inside dict comprehension",
+            ComprehensionKind::Generator => "'yield' inside generator expression",
+        };
+        self.fail(message.to_string(), span);
+    }
+}
+
+impl Visitor for Builder {
+    fn visit_functiondef_stmt(&mut self, node: &FunctionDef) {
+        self.function(
+            &node.name,
+            &node.args,
+            &node.body,
+            &node.decorator_list,
+            node.returns.as_deref(),
+            &node.type_params,
+            node.span,
+            false,
+        );
+    }
+
+    fn visit_asyncfunctiondef_stmt(&mut self, node: &AsyncFunctionDef) {
+        self.function(
+            &node.name,
+            &node.args,
+            &node.body,
+            &node.decorator_list,
+            node.returns.as_deref(),
+            &node.type_params,
+            node.span,
+            true,
+        );
+    }
+
+    fn visit_classdef_stmt(&mut self, node: &ClassDef) {
+        self.add_def(&node.name, SymbolFlags::DEF_LOCAL, node.span);
+        for decorator in &node.decorator_list {
+            self.visit_expr(decorator);
+        }
+        let private = self.private.clone();
+        let is_generic = !node.type_params.is_empty();
+        if is_generic {
+            self.enter_type_params(&node.name, node.span, false, false, true);
+            for type_param in &node.type_params {
+                self.visit_typeparam(type_param);
+            }
+        }
+        for base in &node.bases {
+            self.visit_expr(base);
+        }
+        for keyword in &node.keywords {
+            self.visit_keyword(keyword);
+        }
+        self.enter_block(&node.name, TableType::Class, node.span);
+        self.private = Some(node.name.clone());
+        if is_generic {
+            self.add_def("__type_params__", SymbolFlags::DEF_LOCAL, node.span);
+            self.add_def(".type_params", SymbolFlags::USE, node.span);
+        }
+        for stmt in &node.body {
+            self.visit_stmt(stmt);
+        }
+        self.exit_block();
+        if is_generic {
+            self.exit_block();
+        }
+        self.private = private;
+    }
+
+    fn visit_typealias_stmt(&mut self, node: &TypeAlias) {
+        self.visit_expr(&node.name);
+        let Expr::Name(name) = &*node.name else {
+            return;
+        };
+        let in_class = self.table_type() == TableType::Class;
+        let is_generic = !node.type_params.is_empty();
+        if is_generic {
+            self.enter_type_params(&name.id, node.span, false, false, false);
+            for type_param in &node.type_params {
+                self.visit_typeparam(type_param);
+            }
+        }
+        self.enter_block(&name.id, TableType::TypeAlias, node.span);
+        if in_class {
+            self.current().table.can_see_class_scope = true;
+            self.add_def("__classdict__", SymbolFlags::USE, node.value.span());
+        }
+        self.visit_expr(&node.value);
+        self.exit_block();
+        if is_generic {
+            self.exit_block();
+        }
+    }
+
+    fn visit_return_stmt(&mut self, node: &Return) {
+        if let Some(value) = &node.value {
+            self.visit_expr(value);
+            self.current().table.returns_value = true;
+        }
+    }
+
+    fn visit_annassign_stmt(&mut self, node: &AnnAssign) {
+        if let Expr::Name(target) = &*node.target {
+            let flags = self.lookup(&target.id);
+            if flags.intersects(SymbolFlags::DEF_GLOBAL | SymbolFlags::DEF_NONLOCAL)
+                && self.stack.len() > 1
+                && node.simple != 0
+            {
+                let keyword = if flags.intersects(SymbolFlags::DEF_GLOBAL) {
+                    "global"
+                } else {
+                    "nonlocal"
+                };
+                let message = format!("annotated name '{}' can't be {}", target.id, keyword);
+                return self.fail(message, node.span);
+            }
+            if node.simple != 0 {
+                let flags = SymbolFlags::DEF_ANNOT | SymbolFlags::DEF_LOCAL;
+                self.add_def(&target.id, flags, target.span);
+            } else if node.value.is_some() {
+                self.add_def(&target.id, SymbolFlags::DEF_LOCAL, target.span);
+            }
+        } else {
+            self.visit_expr(&node.target);
+        }
+        self.annotation(&node.annotation);
+        if let Some(value) = &node.value {
+            self.visit_expr(value);
+        }
+    }
+
+    fn visit_global_stmt(&mut self, node: &Global) {
+        self.declare(&node.names, SymbolFlags::DEF_GLOBAL, node.span);
+    }
+
+    fn visit_nonlocal_stmt(&mut self, node: &Nonlocal) {
+        self.declare(&node.names, SymbolFlags::DEF_NONLOCAL, node.span);
+    }
+
+    fn visit_alias(&mut self, node: &Alias) {
+        let name = node.asname.as_ref().unwrap_or(&node.name);
+        if name == "*" {
+            if self.table_type() != TableType::Module {
+                let message = "import * only allowed at module level".to_string();
+                self.fail(message, node.span);
+            }
+            return;
+        }
+        // `import a.b` binds `a`.
+        let name = name.split('.').next().unwrap_or(name);
+        self.add_def(name, SymbolFlags::DEF_IMPORT, node.span);
+    }
+
+    // The `else` block is visited before the handlers, as in CPython.
+    fn visit_try_stmt(&mut self, node: &Try) {
+        self.try_statement(&node.body, &node.handlers, &node.orelse, &node.finalbody);
+    }
+
+    fn visit_trystar_stmt(&mut self, node: &TryStar) {
+        self.try_statement(&node.body, &node.handlers, &node.orelse, &node.finalbody);
+    }
+
+    fn visit_excepthandler(&mut self, node: &ExceptHandler) {
+        if let Some(r#type) = &node.r#type {
+            self.visit_expr(r#type);
+        }
+        if let Some(name) = &node.name {
+            self.add_def(name, SymbolFlags::DEF_LOCAL, node.span);
+        }
+        for stmt in &node.body {
+            self.visit_stmt(stmt);
+        }
+    }
+
+    fn visit_namedexpr_expr(&mut self, node: &NamedExpr) {
+        if !self.check_allowed("named expression", node.span) {
+            return;
+        }
+        if self.current().comp_iter_expr > 0 {
+            let message =
+                "assignment expression cannot be used in a comprehension iterable expression";
+            return self.fail(message.to_string(), node.span);
+        }
+        if let (TableType::Comprehension(_), Expr::Name(target)) =
+            (self.table_type(), &*node.target)
+        {
+            self.extend_namedexpr_scope(target);
+        }
+        self.visit_expr(&node.value);
+        self.visit_expr(&node.target);
+    }
+
+    fn visit_lambda_expr(&mut self, node: &Lambda) {
+        self.defaults(&node.args);
+        self.enter_block("lambda", TableType::Lambda, node.span);
+        self.parameters(&node.args);
+        self.visit_expr(&node.body);
+        self.exit_block();
+    }
+
+    fn visit_listcomp_expr(&mut self, node: &ListComp) {
+        let kind = ComprehensionKind::List;
+        self.comprehension(kind, &node.generators, &node.elt, None, node.span);
+    }
+
+    fn visit_setcomp_expr(&mut self, node: &SetComp) {
+        let kind = ComprehensionKind::Set;
+        self.comprehension(kind, &node.generators, &node.elt, None, node.span);
+    }
+
+    fn visit_dictcomp_expr(&mut self, node: &DictComp) {
+        let kind = ComprehensionKind::Dict;
+        let value = Some(&*node.value);
+        self.comprehension(kind, &node.generators, &node.key, value, node.span);
+    }
+
+    fn visit_generatorexp_expr(&mut self, node: &GeneratorExp) {
+        let kind = ComprehensionKind::Generator;
+        self.comprehension(kind, &node.generators, &node.elt, None, node.span);
+    }
+
+    fn visit_comprehension(&mut self, node: &Comprehension) {
+        self.current().comp_iter_target = true;
+        self.visit_expr(&node.target);
+        self.current().comp_iter_target = false;
+        self.current().comp_iter_expr += 1;
+        self.visit_expr(&node.iter);
+        self.current().comp_iter_expr -= 1;
+        for condition in &node.ifs {
+            self.visit_expr(condition);
+        }
+        if node.is_async {
+            self.current().table.is_coroutine = true;
+        }
+    }
+
+    fn visit_yield_expr(&mut self, node: &Yield) {
+        if !self.check_allowed("yield expression", node.span) {
+            return;
+        }
+        visit::walk_yield_expr(self, node);
+        self.current().table.is_generator = true;
+        self.check_yield(node.span);
+    }
+
+    fn visit_yieldfrom_expr(&mut self, node: &YieldFrom) {
+        if !self.check_allowed("yield expression", node.span) {
+            return;
+        }
+        visit::walk_yieldfrom_expr(self, node);
+        self.current().table.is_generator = true;
+        self.check_yield(node.span);
+    }
+
+    fn visit_await_expr(&mut self, node: &Await) {
+        if !self.check_allowed("await expression", node.span) {
+            return;
+        }
+        visit::walk_await_expr(self, node);
+        self.current().table.is_coroutine = true;
+    }
+
+    fn visit_name_expr(&mut self, node: &Name) {
+        if node.ctx == ExprContext::Load {
+            self.add_def(&node.id, SymbolFlags::USE, node.span);
+            // `super()` finds the class through the `__class__` cell.
+            if node.id == "super" && self.table_type().is_function_like() {
+                self.add_def("__class__", SymbolFlags::USE, node.span);
+            }
+        } else {
+            self.add_def(&node.id, SymbolFlags::DEF_LOCAL, node.span);
+        }
+    }
+
+    fn visit_matchmapping_pattern(&mut self, node: &MatchMapping) {
+        visit::walk_matchmapping_pattern(self, node);
+        if let Some(rest) = &node.rest {
+            self.add_def(rest, SymbolFlags::DEF_LOCAL, node.span);
+        }
+    }
+
+    fn visit_matchstar_pattern(&mut self, node: &MatchStar) {
+        if let Some(name) = &node.name {
+            self.add_def(name, SymbolFlags::DEF_LOCAL, node.span);
+        }
+    }
+
+    fn visit_matchas_pattern(&mut self, node: &MatchAs) {
+        visit::walk_matchas_pattern(self, node);
+        if let Some(name) = &node.name {
+            self.add_def(name, SymbolFlags::DEF_LOCAL, node.span);
+        }
+    }
+
+    fn visit_typeparam(&mut self, node: &TypeParam) {
+        let (name, bound, default_value, scope_info) = match node {
+            TypeParam::TypeVar(node) => (
+                &node.name,
+                node.bound.as_deref(),
+                node.default_value.as_deref(),
+                "a TypeVar default",
+            ),
+            TypeParam::ParamSpec(node) => (
+                &node.name,
+                None,
+                node.default_value.as_deref(),
+                "a ParamSpec default",
+            ),
+            TypeParam::TypeVarTuple(node) => (
+                &node.name,
+                None,
+                node.default_value.as_deref(),
+                "a TypeVarTuple default",
+            ),
+        };
+        let flags = SymbolFlags::DEF_TYPE_PARAM | SymbolFlags::DEF_LOCAL;
+        self.add_def(name, flags, node.span());
+        if let Some(bound) = bound {
+            self.type_var_bound(name, bound, "a TypeVar bound", node.span());
+        }
+        if let Some(default_value) = default_value {
+            self.type_var_bound(name, default_value, scope_info, node.span());
+        }
+    }
+}
+
+/// The second pass, resolving the scope of every name from the top block down. Each block
+/// gets the names bound in the functions enclosing it, and the names declared global, and
+/// hands back the names free in it or in the blocks nested in it.
+struct Analysis {
+    inline_comprehensions: bool,
+    /// Whether a name free in a method is flagged in its class even if the class neither
+    /// binds it nor declares it global, as from Python 3.12.
+    free_class_unbound: bool,
+}
+
+impl Analysis {
+    /// Resolves the names of `table` and the blocks nested in it, adding the names free in
+    /// them to `free`. `bound` is `None` for the module. For the type parameters, type
+    /// aliases and bounds that can see a class, `class_table` is that class.
+    fn analyze_block(
+        &mut self,
+        table: &mut SymbolTable,
+        mut bound: Option<&mut HashSet<String>>,
+        free: &mut HashSet<String>,
+        global: &mut HashSet<String>,
+        type_params: &mut HashSet<String>,
+        class_table: Option<&SymbolTable>,
+    ) -> Result<(), SyntaxError> {
+        let mut local = HashSet::new();
+        let mut scopes = HashMap::new();
+        let mut new_global = HashSet::new();
+        let mut new_free = HashSet::new();
+        let mut new_bound = HashSet::new();
+        let mut inlined_cells = HashSet::new();
+
+        // The names bound in a class are not visible in its methods, so only those passed
+        // down are.
+        if table.table_type == TableType::Class {
+            new_global.extend(global.iter().cloned());
+            if let Some(bound) = &bound {
+                new_bound.extend(bound.iter().cloned());
+            }
+        }
+        for symbol in &table.symbols {
+            let (name, flags) = (&symbol.name, symbol.flags);
+            let scope = if flags.intersects(SymbolFlags::DEF_GLOBAL) {
+                if flags.intersects(SymbolFlags::DEF_NONLOCAL) {
+                    let message = format!("name '{}' is nonlocal and global", name);
+                    return Err(table.error_at_directive(name, message));
+                }
+                global.insert(name.clone());
+                if let Some(bound) = bound.as_mut() {
+                    bound.remove(name);
+                }
+                SymbolScope::GlobalExplicit
+            } else if flags.intersects(SymbolFlags::DEF_NONLOCAL) {
+                let message = match &bound {
+                    None => "nonlocal declaration not allowed at module level".to_string(),
+                    Some(bound) if !bound.contains(name) => {
+                        format!("no binding for nonlocal '{}' found", name)
+                    }
+                    Some(_) if type_params.contains(name) => {
+                        format!("nonlocal binding not allowed for type parameter '{}'", name)
+                    }
+                    Some(_) => {
+                        free.insert(name.clone());
+                        scopes.insert(name.clone(), SymbolScope::Free);
+                        continue;
+                    }
+                };
+                return Err(table.error_at_directive(name, message));
+            } else if flags.intersects(SymbolFlags::DEF_BOUND) {
+                local.insert(name.clone());
+                global.remove(name);
+                if flags.intersects(SymbolFlags::DEF_TYPE_PARAM) {
+                    type_params.insert(name.clone());
+                } else {
+                    type_params.remove(name);
+                }
+                SymbolScope::Local
+            } else {
+                // Bound by the class as well as an enclosing function, the name is looked up
+                // in the class namespace first.
+                let class_flags =
+                    class_table.map_or(SymbolFlags::default(), |class| class.flags(name));
+                if class_flags.intersects(SymbolFlags::DEF_GLOBAL) {
+                    SymbolScope::GlobalExplicit
+                } else if class_flags.intersects(SymbolFlags::DEF_BOUND)
+                    && !class_flags.intersects(SymbolFlags::DEF_NONLOCAL)
+                {
+                    SymbolScope::GlobalImplicit
+                } else if bound.as_ref().is_some_and(|bound| bound.contains(name)) {
+                    free.insert(name.clone());
+                    SymbolScope::Free
+                } else {
+                    SymbolScope::GlobalImplicit
+                }
+            };
+            scopes.insert(name.clone(), scope);
+        }
+
+        if table.table_type != TableType::Class {
+            if table.table_type.is_function_like() {
+                new_bound.extend(local.iter().cloned());
+            }
+            if let Some(bound) = &bound {
+                new_bound.extend(bound.iter().cloned());
+            }
+            new_global.extend(global.iter().cloned());
+        } else {
+            new_bound.insert("__class__".to_string());
+            new_bound.insert("__classdict__".to_string());
+        }
+
+        let mut children = vec![];
+        for mut child in std::mem::take(&mut table.children) {
+            let class_table = match child.can_see_class_scope {
+                true if table.table_type == TableType::Class => Some(&*table),
+                true => class_table,
+                false => None,
+            };
+            let inline = self.inline_comprehensions
+                && matches!(child.table_type, TableType::Comprehension(_))
+                && !child.is_generator
+                && !table.can_see_class_scope;
+            let mut child_free = HashSet::new();
+            self.analyze_block(
+                &mut child,
+                Some(&mut new_bound.clone()),
+                &mut child_free,
+                &mut new_global.clone(),
+                &mut type_params.clone(),
+                class_table,
+            )?;
+            if inline {
+                inline_comprehension(
+                    table,
+                    &child,
+                    &mut scopes,
+                    &mut child_free,
+                    &mut inlined_cells,
+                );
+                children.extend(child.children);
+            } else {
+                children.push(child);
+            }
+            new_free.extend(child_free);
+        }
+        table.children = children;
+
+        // The names bound here and free in a nested block become cells.
+        if table.table_type.is_function_like() {
+            for (name, scope) in &mut scopes {
+                if *scope == SymbolScope::Local
+                    && (new_free.contains(name) || inlined_cells.contains(name))
+                {
+                    *scope = SymbolScope::Cell;
+                    new_free.remove(name);
+                }
+            }
+        } else if table.table_type == TableType::Class {
+            table.needs_class_closure = new_free.remove("__class__");
+            table.needs_classdict = new_free.remove("__classdict__");
+        }
+
+        let class_flag = table.table_type == TableType::Class || table.can_see_class_scope;
+        for symbol in &mut table.symbols {
+            if inlined_cells.contains(&symbol.name) {
+                symbol.flags |= SymbolFlags::DEF_COMP_CELL;
+            }
+            symbol.scope = scopes[&symbol.name];
+        }
+        // The names free in nested blocks only pass through this one, unless they are
+        // global.
+        let mut passing: Vec<&String> = new_free.iter().collect();
+        passing.sort();
+        for name in passing {
+            if let Some(&i) = table.index.get(name) {
+                let flags = table.symbols[i].flags;
+                if class_flag
+                    && (self.free_class_unbound
+                        || flags.intersects(SymbolFlags::DEF_BOUND | SymbolFlags::DEF_GLOBAL))
+                {
+                    table.symbols[i].flags |= SymbolFlags::DEF_FREE_CLASS;
+                }
+            } else if bound.as_ref().is_none_or(|bound| bound.contains(name)) {
+                table.set(name, SymbolFlags::default(), SymbolScope::Free);
+            }
+        }
+        free.extend(new_free);
+        Ok(())
+    }
+}
+
+/// Moves the names of the comprehension `comp`, just analyzed, into `table`, which it is
+/// inlined into.
+fn inline_comprehension(
+    table: &mut SymbolTable,
+    comp: &SymbolTable,
+    scopes: &mut HashMap<String, SymbolScope>,
+    comp_free: &mut HashSet<String>,
+    inlined_cells: &mut HashSet<String>,
+) {
+    for symbol in &comp.symbols {
+        // The iterable, `.0`.
+        if symbol.is_parameter() {
+            continue;
+        }
+        let name = &symbol.name;
+        let mut scope = symbol.scope;
+        if scope == SymbolScope::Cell || symbol.flags.intersects(SymbolFlags::DEF_COMP_CELL) {
+            inlined_cells.insert(name.clone());
+        }
+        // `__class__` is never free through a class.
+        if scope == SymbolScope::Free && table.table_type == TableType::Class && name == "__class__"
+        {
+            scope = SymbolScope::GlobalImplicit;
+            comp_free.remove(name);
+        }
+        match table.lookup(name) {
+            None => {
+                table.set(name, symbol.flags, scope);
+                scopes.insert(name.clone(), scope);
+            }
+            // A name free in the comprehension and bound in a function is just local,
+            // unless a block nested in the comprehension needs it.
+            Some(existing) => {
+                if existing.flags.intersects(SymbolFlags::DEF_BOUND)
+                    && table.table_type != TableType::Class
+                    && !comp
+                        .children
+                        .iter()
+                        .any(|child| child.lookup(name).is_some_and(Symbol::is_free))
+                {
+                    comp_free.remove(name);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn table(source: &str, version: PythonVersion) -> SymbolTable {
+    use crate::parser::{parse, Mode};
+
+    symtable_with_version(&parse(source, Mode::Exec).unwrap(), version).unwrap()
+}
+
+#[cfg(test)]
+fn scope(table: &SymbolTable, name: &str) -> SymbolScope {
+    table.lookup(name).unwrap().scope
+}
+
+#[test]
+fn test_symtable() {
+    let source = "\
+import os
+g = 1
+def f(a, *args, b=1, **kw):
+    global g
+    x = y = 0
+    def inner():
+        nonlocal x
+        return x + y + os.sep
+    return inner
+class C:
+    attr = 1
+    def __m(self):
+        return __class__, attr
+";
+    let module = table(source, PythonVersion::Py313);
+    assert!(module.lookup("os").unwrap().is_imported());
+    assert_eq!(module.locals(), ["os", "f", "C"]);
+    assert_eq!(scope(&module, "g"), SymbolScope::GlobalExplicit);
+
+    let f = &module.children[0];
+    assert_eq!(f.parameters(), ["a", "b", "args", "kw"]);
+    assert!(f.has_varargs && f.has_varkeywords && f.returns_value);
+    assert_eq!(scope(f, "g"), SymbolScope::GlobalExplicit);
+    assert_eq!(scope(f, "x"), SymbolScope::Cell);
+    assert_eq!(scope(f, "y"), SymbolScope::Cell);
+
+    let inner = &f.children[0];
+    assert!(inner.is_nested);
+    assert_eq!(inner.nonlocals(), ["x"]);
+    assert_eq!(inner.frees(), ["x", "y"]);
+    assert_eq!(scope(inner, "os"), SymbolScope::GlobalImplicit);
+
+    let class = &module.children[1];
+    assert_eq!(class.table_type, TableType::Class);
+    assert!(class.needs_class_closure);
+    assert!(class.lookup("_C__m").is_some());
+    let method = &class.children[0];
+    assert_eq!(method.name, "__m");
+    assert_eq!(scope(method, "__class__"), SymbolScope::Free);
+    assert_eq!(scope(method, "attr"), SymbolScope::GlobalImplicit);
+
+    let module = table(
+        "from __future__ import annotations\nx: int = 1",
+        PythonVersion::Py313,
+    );
+    assert!(module.lookup("x").unwrap().is_annotated());
+    assert!(module.lookup("int").is_none());
+    assert!(module.children.is_empty());
+
+    // Names free in a method that its class binds, uses, or only passes through.
+    let source = "\
+def f():
+    x = y = z = 1
+    class C:
+        x = 2
+        w = z
+        def m(self):
+            return x, y, z
+";
+    let free_class = |table: &SymbolTable, name: &str| {
+        let symbol = table.lookup(name).unwrap();
+        (
+            symbol.flags.contains(SymbolFlags::DEF_FREE_CLASS),
+            symbol.scope,
+        )
+    };
+    let module = table(source, PythonVersion::Py313);
+    let class = &module.children[0].children[0];
+    assert_eq!(free_class(class, "x"), (true, SymbolScope::Local));
+    assert_eq!(free_class(class, "y"), (false, SymbolScope::Free));
+    assert_eq!(free_class(class, "z"), (true, SymbolScope::Free));
+    // Before Python 3.12, only a name the class binds or declares global.
+    let module = table(source, PythonVersion::Py311);
+    let class = &module.children[0].children[0];
+    assert_eq!(free_class(class, "x"), (true, SymbolScope::Local));
+    assert_eq!(free_class(class, "z"), (false, SymbolScope::Free));
+}
+
+#[test]
+fn test_symtable_comprehensions() {
+    let source = "\
+def f():
+    x = 1
+    [(n := i) for i in r]
+    return [lambda: x + y for y in z]
+";
+    let module = table(source, PythonVersion::Py311);
+    let f = &module.children[0];
+    let names: Vec<_> = f.children.iter().map(|child| child.name.as_str()).collect();
+    assert_eq!(names, ["listcomp", "listcomp"]);
+    assert_eq!(scope(f, "n"), SymbolScope::Cell);
+    assert!(f.lookup("i").is_none());
+    assert_eq!(scope(&f.children[0], "n"), SymbolScope::Free);
+    assert_eq!(scope(&f.children[1], "y"), SymbolScope::Cell);
+
+    let module = table(source, PythonVersion::Py312);
+    let f = &module.children[0];
+    assert_eq!(f.children.len(), 1);
+    assert_eq!(f.children[0].table_type, TableType::Lambda);
+    assert_eq!(f.frees(), Vec::<&str>::new());
+    assert_eq!(scope(f, "n"), SymbolScope::Local);
+    assert_eq!(scope(f, "i"), SymbolScope::Local);
+    assert_eq!(scope(f, "x"), SymbolScope::Cell);
+    assert!(f
+        .lookup("y")
+        .unwrap()
+        .flags
+        .contains(SymbolFlags::DEF_COMP_CELL));
+    assert_eq!(f.children[0].frees(), ["x", "y"]);
+
+    // Generator expressions keep their blocks.
+    let module = table("(x for x in y)", PythonVersion::Py313);
+    assert_eq!(module.children[0].name, "genexpr");
+    assert_eq!(module.children[0].parameters(), [".0"]);
+}
+
+#[test]
+fn test_symtable_type_params() {
+    let source = "\
+def f[T: int, *Ts](x: T) -> T:
+    return x
+class C[T]:
+    def m(self) -> T: ...
+type A[K] = dict[K, A]
+def g[T](x: T = 1, *, y: T = 2): ...
+";
+    let module = table(source, PythonVersion::Py313);
+    assert_eq!(module.locals(), ["f", "C", "A", "g"]);
+
+    let params = &module.children[0];
+    assert_eq!(params.table_type, TableType::TypeParameters);
+    assert!(params
+        .lookup("T")
+        .unwrap()
+        .flags
+        .contains(SymbolFlags::DEF_TYPE_PARAM));
+    assert_eq!(params.children[0].table_type, TableType::TypeVarBound);
+    assert_eq!(params.children[1].table_type, TableType::Function);
+    // A default is evaluated lazily too, in a block of its own after that of the bound.
+    let defaults = table(
+        "class C:\n    def m[T: int = C](self): ...\n",
+        PythonVersion::Py313,
+    );
+    let params = &defaults.children[0].children[0];
+    let blocks: Vec<_> = params
+        .children
+        .iter()
+        .map(|child| child.table_type)
+        .collect();
+    assert_eq!(
+        blocks,
+        [
+            TableType::TypeVarBound,
+            TableType::TypeVarBound,
+            TableType::Function
+        ]
+    );
+    assert_eq!(scope(&params.children[1], "C"), SymbolScope::GlobalImplicit);
+    assert!(params.children[1].can_see_class_scope);
+
+    let params = &module.children[1];
+    assert_eq!(scope(params, "T"), SymbolScope::Cell);
+    assert_eq!(scope(params, ".type_params"), SymbolScope::Cell);
+    let class = &params.children[0];
+    assert_eq!(scope(class, "T"), SymbolScope::Free);
+    assert!(class.lookup("__type_params__").unwrap().is_assigned());
+
+    let alias = &module.children[2].children[0];
+    assert_eq!(alias.table_type, TableType::TypeAlias);
+    assert_eq!(scope(alias, "K"), SymbolScope::Free);
+    assert_eq!(scope(alias, "A"), SymbolScope::GlobalImplicit);
+
+    // The defaults are evaluated outside, and passed in to create the function.
+    assert!(module.children[0].parameters().is_empty());
+    let params = &module.children[3];
+    assert_eq!(params.parameters(), [".defaults", ".kwdefaults"]);
+    assert_eq!(scope(params, ".defaults"), SymbolScope::Local);
+}
+
+#[test]
+fn test_symtable_errors() {
+    use crate::parser::{parse, Mode};
+
+    let cases = [
+        (
+            "def f(a, a): pass",
+            "duplicate argument 'a' in function definition",
+        ),
+        (
+            "def f(a):\n    global a",
+            "name 'a' is parameter and global",
+        ),
+        (
+            "def f():\n    x = 1\n    global x",
+            "name 'x' is assigned to before global declaration",
+        ),
+        (
+            "nonlocal x",
+            "nonlocal declaration not allowed at module level",
+        ),
+        (
+            "def f():\n    nonlocal x",
+            "no binding for nonlocal 'x' found",
+        ),
+        (
+            "def f():\n    from m import *",
+            "import * only allowed at module level",
+        ),
+        (
+            "[(i := 0) for i in x]",
+            "assignment expression cannot rebind comprehension iteration variable 'i'",
+        ),
+        (
+            "class C:\n    [(y := 0) for i in x]",
+            "assignment expression within a comprehension cannot be used in a class body",
+        ),
+        (
+            "def f():\n    [(yield) for i in x]",
+            "'yield' inside list comprehension",
+        ),
+        ("def f[T, T](): pass", "duplicate type parameter 'T'"),
+        (
+            "type A = (yield)",
+            "yield expression cannot be used within a type alias",
+        ),
+        (
+            "def f[T: int = (yield)](): pass",
+            "yield expression cannot be used within a TypeVar default",
+        ),
+        (
+            "class C[*Ts = (await x)]: pass",
+            "await expression cannot be used within a TypeVarTuple default",
+        ),
+    ];
+    for (source, message) in cases {
+        let tree = parse(source, Mode::Exec).unwrap();
+        assert_eq!(symtable(&tree).unwrap_err().message, message, "{}", source);
+    }
+
+    let source = "from __future__ import annotations\ndef f(x: (yield)): pass";
+    let tree = parse(source, Mode::Exec).unwrap();
+    assert_eq!(
+        symtable_with_version(&tree, PythonVersion::Py311)
+            .unwrap_err()
+            .message,
+        "'yield expression' can not be used within an annotation"
+    );
+    let error = symtable(&parse("x = 1\nglobal x", Mode::Exec).unwrap()).unwrap_err();
+    assert_eq!((error.span.lineno, error.span.col_offset), (2, 0));
+}
@@ -0,0 +1,206 @@
+//! Listings of the tokens of a file, printed by `pragrat tokenize` to debug the tokenizer.
+
+use crate::dump::{str_repr, Dump, DumpValue};
+use crate::tokenizer::{TokenizeError, KEYWORDS};
+use crate::types::{Span, Token, TokenType};
+
+/// How tokens are listed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum TokenFormat {
+    /// Aligned columns of span, type, text and literal value.
+    Table,
+    /// The output of `python -m tokenize`, with CPython's token names and character columns.
+    Cpython,
+    /// One JSON object per token.
+    Json,
+}
+
+/// Whether `token_type` is a comment or a newline that does not end a logical line, which
+/// the parser never sees.
+pub fn is_trivia(token_type: &TokenType) -> bool {
+    matches!(token_type, TokenType::Comment | TokenType::NL)
+}
+
+/// Lists `tokens`, scanned from `source`, one per line. JSON objects also name the `file`.
+pub fn render_tokens(file: &str, source: &str, tokens: &[Token], format: TokenFormat) -> String {
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let mut out = String::new();
+    if format == TokenFormat::Cpython {
+        // `tokenize` first reports the encoding the source was decoded with.
+        out.push_str(&format!(
+            "{:<20}{:<15}{:<15}\n",
+            "0,0-0,0:", "ENCODING", "'utf-8'"
+        ));
+    }
+    for token in tokens {
+        let line = match format {
+            TokenFormat::Table => format!(
+                "{:<20}{:<16}{:<24}{}",
+                format!(
+                    "{}:{}-{}:{}",
+                    token.span.lineno,
+                    token.span.col_offset,
+                    token.span.end_lineno,
+                    token.span.end_col_offset
+                ),
+                token.token_type.to_string(),
+                str_repr(&token.value),
+                literal_repr(token).unwrap_or_default()
+            )
+            .trim_end()
+            .to_string(),
+            TokenFormat::Cpython => {
+                let span = char_span(&lines, token.span);
+                // Padded like `tokenize`, trailing spaces included, so that listings diff.
+                format!(
+                    "{:<20}{:<15}{:<15}",
+                    format!(
+                        "{},{}-{},{}:",
+                        span.lineno, span.col_offset, span.end_lineno, span.end_col_offset
+                    ),
+                    cpython_name(&token.token_type),
+                    str_repr(&token.value)
+                )
+            }
+            TokenFormat::Json => format!(
+                "{{\"file\": {}, \"type\": {}, \"start\": [{}, {}], \"end\": [{}, {}], \"text\": {}, \"literal\": {}}}",
+                json_string(file),
+                json_string(&token.token_type.to_string()),
+                token.span.lineno,
+                token.span.col_offset,
+                token.span.end_lineno,
+                token.span.end_col_offset,
+                json_string(&token.value),
+                literal_repr(token).map_or("null".to_string(), |repr| json_string(&repr))
+            ),
+        };
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Renders `error`, found in `file`, as `file:line:column: message` with a 1-based column.
+pub fn render_error(file: &str, error: &TokenizeError) -> String {
+    let mut message = format!(
+        "{}:{}:{}: {}",
+        file,
+        error.span.lineno,
+        error.span.col_offset + 1,
+        error.message
+    );
+    if let Some(hint) = &error.hint {
+        message.push_str(". ");
+        message.push_str(hint);
+    }
+    message
+}
+
+/// The name of `token_type` in CPython's `token` module, which calls keywords `NAME` and
+/// operators `OP`.
+pub fn cpython_name(token_type: &TokenType) -> &'static str {
+    match token_type {
+        TokenType::Endmarker => "ENDMARKER",
+        TokenType::Name | TokenType::Await | TokenType::Async | TokenType::SoftKeyword => "NAME",
+        TokenType::Number => "NUMBER",
+        TokenType::String => "STRING",
+        TokenType::Newline => "NEWLINE",
+        TokenType::Indent => "INDENT",
+        TokenType::Dedent => "DEDENT",
+        TokenType::TypeIgnore => "TYPE_IGNORE",
+        TokenType::TypeComment => "TYPE_COMMENT",
+        TokenType::FStringStart => "FSTRING_START",
+        TokenType::FStringMiddle => "FSTRING_MIDDLE",
+        TokenType::FStringEnd => "FSTRING_END",
+        TokenType::Comment => "COMMENT",
+        TokenType::NL => "NL",
+        TokenType::ErrorToken => "ERRORTOKEN",
+        t if KEYWORDS.values().any(|keyword| keyword == t) => "NAME",
+        _ => "OP",
+    }
+}
+
+/// The `repr()` of the value of a literal token.
+fn literal_repr(token: &Token) -> Option<String> {
+    match token.literal.as_ref()?.to_dump() {
+        DumpValue::Repr(repr) => Some(repr),
+        _ => None,
+    }
+}
+
+/// `span` with its byte columns converted to the character columns of `tokenize`.
+fn char_span(lines: &[&str], span: Span) -> Span {
+    let column = |lineno: usize, col: usize| match lines.get(lineno - 1) {
+        Some(line) => line.get(..col).map_or(col, |prefix| prefix.chars().count()),
+        None => col,
+    };
+    Span {
+        col_offset: column(span.lineno, span.col_offset),
+        end_col_offset: column(span.end_lineno, span.end_col_offset),
+        ..span
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[test]
+fn test_render_tokens() {
+    let source = "é = 0x1f  # c\n";
+    let mut tokenizer = crate::tokenizer::Tokenizer::new(source.to_string());
+    tokenizer.scan_tokens();
+    let tokens = tokenizer.tokens();
+    assert_eq!(
+        render_tokens("t.py", source, tokens, TokenFormat::Table),
+        "1:0-1:2             Name            'é'\n\
+         1:3-1:4             Equal           '='\n\
+         1:5-1:9             Number          '0x1f'                  31\n\
+         1:11-1:14           Comment         '# c'\n\
+         1:14-1:15           Newline         '\\n'\n\
+         2:0-2:0             Endmarker       ''\n"
+    );
+    let listing = render_tokens("t.py", source, tokens, TokenFormat::Cpython);
+    let lines: Vec<&str> = listing.lines().map(str::trim_end).collect();
+    assert_eq!(
+        lines,
+        [
+            "0,0-0,0:            ENCODING       'utf-8'",
+            "1,0-1,1:            NAME           'é'",
+            "1,2-1,3:            OP             '='",
+            "1,4-1,8:            NUMBER         '0x1f'",
+            "1,10-1,13:          COMMENT        '# c'",
+            "1,13-1,14:          NEWLINE        '\\n'",
+            "2,0-2,0:            ENDMARKER      ''",
+        ]
+    );
+    assert_eq!(
+        render_tokens("t.py", source, &tokens[2..3], TokenFormat::Json),
+        "{\"file\": \"t.py\", \"type\": \"Number\", \"start\": [1, 5], \"end\": [1, 9], \"text\": \"0x1f\", \"literal\": \"31\"}\n"
+    );
+    assert!(!is_trivia(&tokens[2].token_type) && is_trivia(&tokens[3].token_type));
+}
+
+#[test]
+fn test_render_error() {
+    let mut tokenizer = crate::tokenizer::Tokenizer::new("x = 0777\n".to_string());
+    tokenizer.scan_tokens();
+    assert_eq!(
+        render_error("t.py", &tokenizer.errors()[0]),
+        "t.py:1:5: leading zeros in decimal integer literals are not permitted; use an 0o prefix for octal integers. Did you mean 0o777?"
+    );
+}
@@ -1,30 +1,84 @@
-use std::collections::HashMap;
-
 use phf::phf_map;
 
-use crate::types::{Literal, Token, TokenType};
+use crate::dump::is_printable;
+use crate::types::{ErrorKind, Literal, PythonVersion, Span, Token, TokenType};
 
 pub struct Tokenizer {
     current: usize,
     line: usize,
+    line_start: usize,
     source: String,
     start: usize,
+    /// Line of `start`, and the offset at which that line begins.
+    start_line: usize,
+    start_line_start: usize,
     tokens: Vec<Token>,
+    errors: Vec<TokenizeError>,
+    /// Brackets opened in code and where, innermost last. Newlines inside them do not end the
+    /// line.
+    brackets: Vec<(char, Span)>,
+    /// Columns of the enclosing indentation levels, with tabs expanded to multiples of 8 and,
+    /// to detect inconsistent mixes, of 1.
+    indents: Vec<(usize, usize)>,
+    /// Whether the next token starts a logical line, after its indentation.
+    at_line_start: bool,
+    /// The f-strings being scanned, innermost last.
+    modes: Vec<Mode>,
+    /// Whether a backquote was reported and the next one closes its Python 2 `repr`.
+    backquoted: bool,
+    version: PythonVersion,
+}
+
+/// A problem found while scanning, also recorded as an `ErrorToken` in the token stream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenizeError {
+    pub message: String,
+    /// A suggestion following the message, like that of a `SyntaxError`.
+    pub hint: Option<String>,
+    pub span: Span,
+    /// The class of `SyntaxError` CPython raises it as.
+    pub kind: ErrorKind,
+}
+
+/// The quoting of an f-string, e.g. `rf"""`.
+#[derive(Clone, Debug)]
+struct FString {
+    quote: &'static str,
+    raw: bool,
+}
+
+/// Where the tokenizer is inside an f-string, following the PEP 701 tokenization of 3.12.
+#[derive(Clone, Debug)]
+enum Mode {
+    /// Literal text, up to a replacement field or the closing quote.
+    Text(FString),
+    /// The expression of a replacement field, opened when `brackets` had the given length.
+    Field(usize),
+    /// Literal text of a format spec, up to a nested field or the end of its field.
+    Spec(FString),
 }
 
 trait PySource {
     fn is_snakecase(&self, is_first: bool) -> bool;
 }
 impl PySource for char {
+    /// Whether the character may be part of a name. Every non-ASCII character may, until
+    /// the whole name is checked against `XID_Start` and `XID_Continue`, as CPython does.
     fn is_snakecase(&self, is_first: bool) -> bool {
+        if !self.is_ascii() {
+            return true;
+        }
         if is_first {
             return self.is_ascii_alphabetic() || self == &'_';
         }
-        return self.is_ascii_alphanumeric() || self == &'_';
+        self.is_ascii_alphanumeric() || self == &'_'
     }
 }
 
-const KEYWORDS: phf::Map<&'static str, TokenType> = phf_map! {
+pub(crate) const KEYWORDS: phf::Map<&'static str, TokenType> = phf_map! {
+    "False" => TokenType::False,
+    "None" => TokenType::None,
+    "True" => TokenType::True,
     "and" => TokenType::And,
     "as" => TokenType::As,
     "async" => TokenType::Async,
@@ -38,198 +92,715 @@ const KEYWORDS: phf::Map<&'static str, TokenType> = phf_map! {
     "elif" => TokenType::Elif,
     "else" => TokenType::Else,
     "except" => TokenType::Except,
-    "false" => TokenType::False,
     "finally" => TokenType::Finally,
     "for" => TokenType::For,
+    "from" => TokenType::From,
     "global" => TokenType::Global,
     "if" => TokenType::If,
     "import" => TokenType::Import,
     "in" => TokenType::In,
     "is" => TokenType::Is,
     "lambda" => TokenType::Lambda,
-    "none" => TokenType::None,
     "nonlocal" => TokenType::Nonlocal,
     "not" => TokenType::Not,
     "or" => TokenType::Or,
     "pass" => TokenType::Pass,
     "raise" => TokenType::Raise,
     "return" => TokenType::Return,
-    "true" => TokenType::True,
     "try" => TokenType::Try,
     "while" => TokenType::While,
     "with" => TokenType::With,
     "yield" => TokenType::Yield,
 };
 
+/// Operators and delimiters, matched longest first.
+pub(crate) const OPERATORS: phf::Map<&'static str, TokenType> = phf_map! {
+    "(" => TokenType::LPar,
+    ")" => TokenType::RPar,
+    "[" => TokenType::LSqB,
+    "]" => TokenType::RSqB,
+    "{" => TokenType::LBrace,
+    "}" => TokenType::RBrace,
+    ":" => TokenType::Colon,
+    "," => TokenType::Comma,
+    ";" => TokenType::Semi,
+    "+" => TokenType::Plus,
+    "-" => TokenType::Minus,
+    "*" => TokenType::Star,
+    "/" => TokenType::Slash,
+    "|" => TokenType::VBar,
+    "&" => TokenType::Amper,
+    "<" => TokenType::Less,
+    ">" => TokenType::Greater,
+    "=" => TokenType::Equal,
+    "." => TokenType::Dot,
+    "%" => TokenType::Percent,
+    "~" => TokenType::Tilde,
+    "^" => TokenType::Circumflex,
+    "@" => TokenType::At,
+    "!" => TokenType::Exclamation,
+    "==" => TokenType::EqEqual,
+    "!=" => TokenType::NotEqual,
+    "<=" => TokenType::LessEqual,
+    ">=" => TokenType::GreaterEqual,
+    "<<" => TokenType::LeftShift,
+    ">>" => TokenType::RightShift,
+    "**" => TokenType::DoubleStar,
+    "+=" => TokenType::PlusEqual,
+    "-=" => TokenType::MinEqual,
+    "*=" => TokenType::StarEqual,
+    "/=" => TokenType::SlashEqual,
+    "%=" => TokenType::PercentEqual,
+    "&=" => TokenType::AmperEqual,
+    "|=" => TokenType::VBarEqual,
+    "^=" => TokenType::CircumflexEqual,
+    "//" => TokenType::DoubleSlash,
+    "@=" => TokenType::AtEqual,
+    "->" => TokenType::RArrow,
+    ":=" => TokenType::ColonEqual,
+    "<<=" => TokenType::LeftShiftEqual,
+    ">>=" => TokenType::RightShiftEqual,
+    "**=" => TokenType::DoubleStarEqual,
+    "//=" => TokenType::DoubleSlashEqual,
+    "..." => TokenType::Ellipsis,
+};
+
 impl Tokenizer {
-    fn new(source: String) -> Self {
+    pub fn new(source: String) -> Self {
         Self {
-            // TODO: make pre-formatter for source.
-            source: source.replace("\t", "    "),
+            source,
             line: 1,
+            line_start: 0,
             current: 0,
             start: 0,
+            start_line: 1,
+            start_line_start: 0,
             tokens: Vec::new(),
+            errors: Vec::new(),
+            brackets: Vec::new(),
+            indents: vec![(0, 0)],
+            at_line_start: true,
+            modes: Vec::new(),
+            backquoted: false,
+            version: PythonVersion::default(),
         }
     }
-    fn scan_tokens(&mut self) {
+    /// Tokenizes for `version`, in which f-strings before 3.12 are restricted as they were
+    /// before PEP 701.
+    pub fn with_version(self, version: PythonVersion) -> Self {
+        Self { version, ..self }
+    }
+    pub fn scan_tokens(&mut self) {
         while !self.reached_eof() {
-            self.start = self.current;
-            self.scan_token();
+            self.mark_start();
+            match self.modes.last().cloned() {
+                Some(Mode::Text(fstring)) => self.scan_fstring_text(&fstring, false),
+                Some(Mode::Spec(fstring)) => self.scan_fstring_text(&fstring, true),
+                Some(Mode::Field(_)) if self.version < PythonVersion::Py312 => {
+                    self.scan_legacy_field_token()
+                }
+                _ if self.at_line_start => {
+                    self.at_line_start = false;
+                    self.scan_indentation();
+                }
+                _ => self.scan_token(),
+            }
+        }
+        self.mark_start();
+        if self.modes.iter().any(|mode| matches!(mode, Mode::Text(_))) {
+            self.modes.clear();
+            self.add_error("unterminated f-string literal".to_string());
+        }
+        if let Some(&(bracket, span)) = self.brackets.first() {
+            self.add_error(format!("'{}' was never closed", bracket));
+            self.errors.last_mut().unwrap().span = span;
+        }
+        if !self.at_blank_line() {
+            // Like CPython, end the last line even without a trailing newline.
+            self.add_token(TokenType::Newline, None);
+            self.tokens.last_mut().unwrap().span.end_col_offset += 1;
+            self.line += 1;
+            self.line_start = self.current;
+        }
+        // The remaining dedents and the end marker sit at the start of the last line.
+        self.current = self.line_start;
+        self.mark_start();
+        for _ in 1..self.indents.len() {
+            self.add_token(TokenType::Dedent, None);
+        }
+        self.indents.truncate(1);
+        self.add_token(TokenType::Endmarker, None);
+    }
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+    pub fn into_tokens(self) -> Vec<Token> {
+        self.tokens
+    }
+    pub fn errors(&self) -> &[TokenizeError] {
+        &self.errors
+    }
+    fn mark_start(&mut self) {
+        self.start = self.current;
+        self.start_line = self.line;
+        self.start_line_start = self.line_start;
+    }
+    /// Whether no token has been scanned on the current logical line yet.
+    fn at_blank_line(&self) -> bool {
+        let last = self
+            .tokens
+            .iter()
+            .rev()
+            .find(|token| token.token_type != TokenType::Comment);
+        match last {
+            Some(token) => matches!(
+                token.token_type,
+                TokenType::Newline | TokenType::NL | TokenType::Indent | TokenType::Dedent
+            ),
+            None => true,
+        }
+    }
+    /// Scans the leading whitespace of a line, emitting an `Indent` if it is deeper than the
+    /// current level or a `Dedent` per level it closes. Blank lines and lines holding only a
+    /// comment leave the indentation untouched.
+    fn scan_indentation(&mut self) {
+        let (mut col, mut alt_col) = (0, 0);
+        loop {
+            match self.get_char() {
+                Some(' ') => (col, alt_col) = (col + 1, alt_col + 1),
+                Some('\t') => (col, alt_col) = ((col / 8 + 1) * 8, alt_col + 1),
+                Some('\x0c') => (col, alt_col) = (0, 0),
+                _ => break,
+            }
+            self.advance();
+        }
+        if matches!(self.get_char(), None | Some('#' | '\n' | '\r' | '\\')) {
+            return;
+        }
+        let &(current, alt_current) = self.indents.last().unwrap();
+        if col > current {
+            if alt_col <= alt_current {
+                return self.add_error_of_kind(ErrorKind::Tab, INCONSISTENT_TABS);
+            }
+            self.indents.push((col, alt_col));
+            return self.add_token(TokenType::Indent, None);
+        }
+        self.start = self.current;
+        while col < self.indents.last().unwrap().0 {
+            self.indents.pop();
+            self.add_token(TokenType::Dedent, None);
+        }
+        let &(current, alt_current) = self.indents.last().unwrap();
+        if col != current {
+            let message = "unindent does not match any outer indentation level";
+            self.add_error_of_kind(ErrorKind::Indentation, message);
+        } else if alt_col != alt_current {
+            self.add_error_of_kind(ErrorKind::Tab, INCONSISTENT_TABS);
         }
     }
     fn scan_token(&mut self) {
         if let Some(c) = self.advance() {
             match c {
-                '(' => self.add_token(TokenType::LPar, None),
-                ')' => self.add_token(TokenType::RPar, None),
-                '}' => self.add_token(TokenType::LBrace, None),
-                '{' => self.add_token(TokenType::RBrace, None),
-                '[' => self.add_token(TokenType::LSqB, None),
-                ']' => self.add_token(TokenType::RSqB, None),
-                ',' => self.add_token(TokenType::Comma, None),
-                '.' => self.add_token(TokenType::Dot, None),
-                ';' => self.add_token(TokenType::Semi, None),
-                ':' => self.add_token(TokenType::Colon, None),
-                '!' => {
-                    if self.matches('=') {
-                        self.add_token(TokenType::NotEqual, None)
-                    } else {
-                        self.add_token(TokenType::Exclamation, None)
+                '#' => {
+                    while self.get_char().is_some_and(|c| c != '\n') {
+                        self.advance();
                     }
+                    self.add_token(TokenType::Comment, None);
                 }
-                '*' => {
-                    if self.matches('=') {
-                        self.add_token(TokenType::StarEqual, None)
-                    } else if self.matches('*') {
-                        if self.matches('=') {
-                            self.add_token(TokenType::DoubleStarEqual, None)
-                        }
-                        self.add_token(TokenType::DoubleStar, None)
+                '\n' => {
+                    let in_field = matches!(self.modes.last(), Some(Mode::Field(_)));
+                    if !self.brackets.is_empty() || in_field {
+                        self.add_token(TokenType::NL, None);
+                    } else if self.at_blank_line() {
+                        self.add_token(TokenType::NL, None);
+                        self.at_line_start = true;
                     } else {
-                        self.add_token(TokenType::Star, None)
+                        self.add_token(TokenType::Newline, None);
+                        self.at_line_start = true;
                     }
+                    self.line += 1;
+                    self.line_start = self.current;
                 }
-                '/' => {
-                    if self.matches('=') {
-                        self.add_token(TokenType::SlashEqual, None)
-                    } else if self.matches('/') {
-                        if self.matches('=') {
-                            self.add_token(TokenType::DoubleSlashEqual, None)
-                        }
-                        self.add_token(TokenType::DoubleSlash, None)
-                    } else {
-                        self.add_token(TokenType::Slash, None)
+                ' ' | '\t' | '\r' | '\x0c' => (),
+                '\\' => {
+                    if self.source[self.current..].starts_with("\r\n") {
+                        self.advance();
                     }
-                }
-                '-' => {
-                    if self.matches('=') {
-                        self.add_token(TokenType::MinEqual, None)
+                    if self.advance() == Some('\n') {
+                        self.line += 1;
+                        self.line_start = self.current;
                     } else {
-                        self.add_token(TokenType::Minus, None)
+                        self.add_error(
+                            "unexpected character after line continuation character".to_string(),
+                        );
                     }
                 }
-                '+' => {
-                    if self.matches('=') {
-                        self.add_token(TokenType::PlusEqual, None)
+                '\'' | '"' => self.scan_string(""),
+                '.' if self.get_char().is_some_and(|c| c.is_ascii_digit()) => self.scan_number(c),
+                c if c.is_ascii_digit() => self.scan_number(c),
+                c if c.is_snakecase(true) => {
+                    while self.get_char().is_some_and(|c| c.is_snakecase(false)) {
+                        self.advance();
+                    }
+                    let text = &self.source[self.start..self.current];
+                    if !text.is_ascii() {
+                        return self.verify_identifier();
+                    }
+                    if is_string_prefix(text) && matches!(self.get_char(), Some('\'' | '"')) {
+                        let prefix = text.to_ascii_lowercase();
+                        self.advance();
+                        self.scan_string(&prefix);
+                    } else if let Some(t) = KEYWORDS.get(text) {
+                        self.add_token(t.clone(), None)
                     } else {
-                        self.add_token(TokenType::Plus, None)
+                        self.add_token(TokenType::Name, None)
                     }
                 }
-                '#' => {
-                    while !self.reached_eof() && self.peek(1).unwrap() != '\n' {
-                        self.advance();
+                _ => self.scan_operator(c),
+            }
+        }
+    }
+    /// Scans a token of a replacement field as before Python 3.12, which read an f-string as
+    /// a single string, so that its quotes ended the f-string wherever they were and the
+    /// expressions of its fields could hold neither backslashes nor comments.
+    fn scan_legacy_field_token(&mut self) {
+        let quotes: Vec<&str> = self
+            .modes
+            .iter()
+            .filter_map(|mode| match mode {
+                Mode::Text(fstring) => Some(fstring.quote),
+                _ => None,
+            })
+            .collect();
+        let len = self.tokens.len();
+        self.scan_token();
+        let text = &self.source[self.start..self.current];
+        let message = if text.starts_with('#') {
+            "f-string expression part cannot include '#'"
+        } else if text.contains('\\') {
+            "f-string expression part cannot include a backslash"
+        } else if quotes.iter().any(|quote| text.contains(quote)) {
+            "f-string: expecting '}'"
+        } else {
+            return;
+        };
+        self.tokens.truncate(len);
+        self.add_error(message.to_string());
+    }
+    /// Scans an operator or delimiter starting with `c`, which was just consumed.
+    fn scan_operator(&mut self, c: char) {
+        let field = match self.modes.last() {
+            Some(Mode::Field(depth)) if *depth == self.brackets.len() => Some(*depth),
+            _ => None,
+        };
+        if field.is_some() {
+            // Delimiters ending the expression of a replacement field.
+            match c {
+                '}' => {
+                    // Back to the enclosing f-string text, or to the spec holding this field.
+                    self.modes.pop();
+                    return self.add_token(TokenType::RBrace, None);
+                }
+                ':' => {
+                    let fstring = self.enclosing_fstring();
+                    self.modes.push(Mode::Spec(fstring));
+                    return self.add_token(TokenType::Colon, None);
+                }
+                _ => (),
+            }
+        }
+        for len in [3, 2, 1] {
+            let end = self.start + len;
+            if let Some(t) = self
+                .source
+                .get(self.start..end)
+                .and_then(|op| OPERATORS.get(op))
+            {
+                self.current = end;
+                return self.add_operator(t.clone());
+            }
+        }
+        if c == '`' {
+            self.backquoted = !self.backquoted;
+            if !self.backquoted {
+                return self.add_token(TokenType::ErrorToken, None);
+            }
+            let hint = "Backquotes are Python 2 syntax. Did you mean repr(...)?".to_string();
+            return self.add_error_with_hint("invalid syntax", hint);
+        }
+        self.add_error(format!("invalid character '{}' (U+{:04X})", c, c as u32));
+    }
+    /// Adds the name just scanned, or an error at its first character that is neither
+    /// `XID_Start` nor, past the first, `XID_Continue`.
+    fn verify_identifier(&mut self) {
+        let text = &self.source[self.start..self.current];
+        let invalid = text.char_indices().find(|&(i, c)| {
+            let valid = match i {
+                0 => c == '_' || unicode_ident::is_xid_start(c),
+                _ => unicode_ident::is_xid_continue(c),
+            };
+            !valid
+        });
+        let Some((i, c)) = invalid else {
+            return self.add_token(TokenType::Name, None);
+        };
+        self.start += i;
+        self.current = self.start + c.len_utf8();
+        if is_printable(c) {
+            self.add_error(format!("invalid character '{}' (U+{:04X})", c, c as u32));
+        } else {
+            self.add_error(format!(
+                "invalid non-printable character U+{:04X}",
+                c as u32
+            ));
+        }
+    }
+    fn add_operator(&mut self, token_type: TokenType) {
+        let c = self.source[self.start..].chars().next().unwrap();
+        let opening = match token_type {
+            TokenType::RPar => '(',
+            TokenType::RSqB => '[',
+            TokenType::RBrace => '{',
+            _ => {
+                self.add_token(token_type.clone(), None);
+                if matches!(c, '(' | '[' | '{') {
+                    let span = self.tokens.last().unwrap().span;
+                    self.brackets.push((c, span));
+                }
+                return;
+            }
+        };
+        match self.brackets.pop() {
+            Some((bracket, _)) if bracket == opening => self.add_token(token_type, None),
+            Some((bracket, span)) if span.lineno == self.line => self.add_error(format!(
+                "closing parenthesis '{}' does not match opening parenthesis '{}'",
+                c, bracket
+            )),
+            Some((bracket, span)) => self.add_error(format!(
+                "closing parenthesis '{}' does not match opening parenthesis '{}' on line {}",
+                c, bracket, span.lineno
+            )),
+            None => self.add_error(format!("unmatched '{}'", c)),
+        }
+    }
+    /// The quoting of the innermost f-string.
+    fn enclosing_fstring(&self) -> FString {
+        self.modes
+            .iter()
+            .rev()
+            .find_map(|mode| match mode {
+                Mode::Text(fstring) => Some(fstring.clone()),
+                _ => None,
+            })
+            .expect("Replacement field outside of an f-string")
+    }
+    /// Scans a string whose lowercase `prefix` and opening quote were just consumed.
+    fn scan_string(&mut self, prefix: &str) {
+        let first = self.source[..self.current].chars().next_back().unwrap();
+        let rest = &self.source[self.current..];
+        let quote = match (
+            first,
+            rest.starts_with(first) && rest[1..].starts_with(first),
+        ) {
+            ('\'', true) => "'''",
+            ('"', true) => "\"\"\"",
+            ('\'', false) => "'",
+            _ => "\"",
+        };
+        if quote.len() == 3 {
+            self.current += 2;
+        }
+        let raw = prefix.contains('r');
+        if prefix.contains('f') {
+            self.add_token(TokenType::FStringStart, None);
+            self.modes.push(Mode::Text(FString { quote, raw }));
+            return;
+        }
+        let body_start = self.current;
+        loop {
+            if self.source[self.current..].starts_with(quote) {
+                break;
+            }
+            match self.advance() {
+                None => return self.add_unterminated_string(quote),
+                Some('\n') if quote.len() == 1 => {
+                    self.current -= 1;
+                    return self.add_unterminated_string(quote);
+                }
+                Some('\n') => {
+                    self.line += 1;
+                    self.line_start = self.current;
+                }
+                Some('\\') => {
+                    if self.advance() == Some('\n') {
+                        self.line += 1;
+                        self.line_start = self.current;
                     }
                 }
+                Some(_) => (),
+            }
+        }
+        let body = &self.source[body_start..self.current];
+        self.current += quote.len();
+        let literal = if prefix.contains('b') {
+            match decode_bytes(body, raw) {
+                Ok(value) => Literal::Bytes(value),
+                Err(message) => return self.add_error(message),
+            }
+        } else {
+            match decode_str(body, raw) {
+                Ok(value) => Literal::String(value),
+                Err(message) => return self.add_error(message),
+            }
+        };
+        self.add_token(TokenType::String, Some(literal));
+    }
+    fn add_unterminated_string(&mut self, quote: &str) {
+        let kind = if quote.len() == 3 {
+            "triple-quoted string"
+        } else {
+            "string"
+        };
+        let message = format!(
+            "unterminated {} literal (detected at line {})",
+            kind, self.line
+        );
+        self.add_error(message);
+    }
+    /// Scans literal text of an f-string, or of a format spec if `spec` is set, up to the next
+    /// replacement field, the end of the spec or the closing quote.
+    fn scan_fstring_text(&mut self, fstring: &FString, spec: bool) {
+        let mut text = String::new();
+        loop {
+            let rest = &self.source[self.current..];
+            if rest.starts_with(fstring.quote) || rest.is_empty() {
+                break;
+            }
+            // Braces are never doubled in a spec, where `}}` ends a nested field and its own.
+            if !spec && (rest.starts_with("{{") || rest.starts_with("}}")) {
+                text.push_str(&rest[..1]);
+                self.current += 2;
+                continue;
+            }
+            if rest.starts_with(['{', '}']) {
+                break;
+            }
+            let named_escape = rest.starts_with("\\N{");
+            let c = self.advance().unwrap();
+            text.push(c);
+            match c {
+                '\n' if fstring.quote.len() == 1 => {
+                    self.current -= 1;
+                    text.pop();
+                    break;
+                }
                 '\n' => {
-                    if let Some(last) = self.tokens.last() {
-                        if last.token_type == TokenType::Newline {
-                            self.add_token(TokenType::NL, None);
-                            self.line += 1
-                        }
-                    }
-                    self.add_token(TokenType::Newline, None);
-                    self.line += 1
+                    self.line += 1;
+                    self.line_start = self.current;
                 }
-                ' ' | '\r' => (),
-                '\'' => {
-                    let c_first = self.peek(1);
-                    let c_second = self.peek(2);
-                    if c_first.is_some()
-                        && c_second.is_some()
-                        && c_first.unwrap() == '\''
-                        && c_second.unwrap() == '\''
-                    {
-                        // Found docstring.
-                        self.advance();
-                        // Find terminating triple quotes.
-                        while !(self.get_char().unwrap() == '\''
-                            && self.peek(1).unwrap() == '\''
-                            && self.peek(2).unwrap() == '\'')
-                        {
-                            let c = self.advance();
-                            if self.reached_eof() {
-                                panic!("Unterminated docstring.")
-                            } else if c.unwrap() == '\n' {
-                                self.line += 1;
-                            }
-                        }
-                    } else {
-                        // Handle string.
-                        while let Some(next) = self.peek(1) {
-                            self.advance();
-                            if next == '\'' {
+                // A backslash before a brace leaves it to start or end a field, even in a
+                // raw f-string, where it still keeps a quote from closing the string.
+                '\\' if self.source[self.current..].starts_with(['{', '}']) => {}
+                '\\' => {
+                    // Keep escapes whole, so that the braces of `\N{...}` are not fields.
+                    if named_escape && !fstring.raw {
+                        while let Some(c) = self.advance() {
+                            text.push(c);
+                            if c == '}' {
                                 break;
                             }
                         }
-                        self.advance();
-                        let str = &self.source[self.start + 1..self.current - 1];
-                        self.add_token(TokenType::String, Some(Literal::String(str.to_string())))
+                    } else if let Some(c) = self.advance() {
+                        text.push(c);
+                        if c == '\n' {
+                            self.line += 1;
+                            self.line_start = self.current;
+                        }
                     }
                 }
-                _ => {
-                    if c.is_ascii_digit() {
-                        while let Some(next) = self.peek(1) {
-                            if next.is_ascii_digit() || next == '.' {
-                                self.advance();
-                                continue;
-                            }
-                            break;
-                        }
-                        let num = &self.source[self.start..=self.current];
-                        self.add_token(TokenType::Number, Some(Literal::Number(num.to_string())))
-                    } else if c.is_snakecase(true) {
-                        while let Some(next) = self.peek(1) {
-                            if next.is_snakecase(false) {
-                                self.advance();
-                                continue;
-                            }
-                            break;
-                        }
+                _ => (),
+            }
+        }
+        if self.current > self.start {
+            match decode_str(&text, fstring.raw) {
+                Ok(value) => self.add_token(TokenType::FStringMiddle, Some(Literal::String(value))),
+                Err(message) => self.add_error(message),
+            }
+            self.start = self.current;
+            self.start_line = self.line;
+            self.start_line_start = self.line_start;
+        }
+        let rest = &self.source[self.current..];
+        if rest.starts_with('{') {
+            self.current += 1;
+            self.modes.push(Mode::Field(self.brackets.len()));
+            let fields = self
+                .modes
+                .iter()
+                .rev()
+                .take_while(|mode| !matches!(mode, Mode::Text(_)))
+                .filter(|mode| matches!(mode, Mode::Field(_)))
+                .count();
+            // Fields nest in format specs at most twice, or three times from Python 3.12.
+            let nesting = match self.version < PythonVersion::Py312 {
+                true => 2,
+                false => 3,
+            };
+            if fields > nesting {
+                self.add_error("f-string: expressions nested too deeply".to_string());
+            } else {
+                self.add_token(TokenType::LBrace, None);
+            }
+        } else if rest.starts_with('}') {
+            self.current += 1;
+            if spec {
+                // The end of the spec is also the end of its field.
+                self.modes.pop();
+                self.modes.pop();
+                self.add_token(TokenType::RBrace, None);
+            } else {
+                self.add_error("f-string: single '}' is not allowed".to_string());
+            }
+        } else if rest.starts_with(fstring.quote) && !spec {
+            self.current += fstring.quote.len();
+            self.modes.pop();
+            self.add_token(TokenType::FStringEnd, None);
+        } else if !rest.is_empty() {
+            // A newline in a single-quoted f-string, or its closing quote inside a spec.
+            self.modes.clear();
+            self.add_error(format!(
+                "unterminated f-string literal (detected at line {})",
+                self.line
+            ));
+        }
+    }
+    /// Scans a number starting with `first`, which was just consumed, like CPython's
+    /// tokenizer: a single `_` may only separate two digits, and a number may not run into
+    /// a name. Python 2's octal literals such as `0777` and long integers such as `10L` are
+    /// errors.
+    fn scan_number(&mut self, first: char) {
+        let radix = match (first, self.get_char()) {
+            ('0', Some('x' | 'X')) => 16,
+            ('0', Some('o' | 'O')) => 8,
+            ('0', Some('b' | 'B')) => 2,
+            _ => 10,
+        };
+        let kind = match radix {
+            16 => "hexadecimal",
+            8 => "octal",
+            2 => "binary",
+            _ => "decimal",
+        };
+        if radix != 10 {
+            self.advance();
+            loop {
+                if self.get_char() == Some('_') {
+                    self.advance();
+                }
+                match self.get_char() {
+                    Some(c) if c.is_digit(radix) => {}
+                    Some(c) if c.is_ascii_digit() => {
                         self.advance();
-                        let text = &self.source[self.start..self.current];
-                        if let Some(t) = KEYWORDS.get(text) {
-                            self.add_token(t.clone(), None)
-                        } else {
-                            self.add_token(TokenType::Name, None)
-                        }
-                    } else {
-                        panic!("Unexpected character {c:?}")
+                        return self
+                            .add_error(format!("invalid digit '{}' in {} literal", c, kind));
                     }
+                    _ => return self.add_error(format!("invalid {} literal", kind)),
+                }
+                while self.get_char().is_some_and(|c| c.is_digit(radix)) {
+                    self.advance();
+                }
+                if self.get_char() != Some('_') {
+                    break;
                 }
             }
+            if let Some(c) = self.get_char().filter(char::is_ascii_digit) {
+                self.advance();
+                return self.add_error(format!("invalid digit '{}' in {} literal", c, kind));
+            }
+        } else {
+            if !self.scan_digits() {
+                return;
+            }
+            if first != '.' && self.get_char() == Some('.') {
+                self.advance();
+                if self.get_char().is_some_and(|c| c.is_ascii_digit()) && !self.scan_digits() {
+                    return;
+                }
+            }
+            if matches!(self.get_char(), Some('e' | 'E')) {
+                let exponent = &self.source[self.current + 1..];
+                let sign = usize::from(exponent.starts_with(['+', '-']));
+                if exponent[sign..].starts_with(|c: char| c.is_ascii_digit()) {
+                    self.current += 1 + sign;
+                    if !self.scan_digits() {
+                        return;
+                    }
+                } else if sign == 1 {
+                    self.current += 2;
+                    return self.add_error("invalid decimal literal".to_string());
+                }
+            }
+            if matches!(self.get_char(), Some('j' | 'J')) {
+                self.advance();
+            }
         }
+        let num = &self.source[self.start..self.current];
+        let kind_at_end = match num.ends_with(['j', 'J']) {
+            true => "imaginary",
+            false => kind,
+        };
+        let integer = radix != 10 || num.bytes().all(|b| b.is_ascii_digit() || b == b'_');
+        let rest = &self.source[self.current..];
+        if integer
+            && rest.starts_with(['l', 'L'])
+            && !rest[1..].starts_with(|c: char| c.is_snakecase(false))
+        {
+            let hint = format!(
+                "Long integer suffixes are Python 2 syntax. Did you mean {}?",
+                num
+            );
+            self.advance();
+            return self.add_error_with_hint(&format!("invalid {} literal", kind), hint);
+        }
+        if radix == 10
+            && integer
+            && num.starts_with('0')
+            && num.bytes().any(|b| matches!(b, b'1'..=b'9'))
+        {
+            let message = "leading zeros in decimal integer literals are not permitted; \
+                use an 0o prefix for octal integers";
+            let digits = num.trim_start_matches(['0', '_']);
+            if digits.bytes().all(|b| matches!(b, b'0'..=b'7' | b'_')) {
+                let hint = format!("Did you mean 0o{}?", digits);
+                return self.add_error_with_hint(message, hint);
+            }
+            return self.add_error(message.to_string());
+        }
+        // A keyword that can follow a number in valid code, as in `1if x else y`, is only
+        // deprecated.
+        let keyword = ["and", "else", "for", "if", "in", "is", "not", "or"]
+            .iter()
+            .any(|keyword| rest.starts_with(keyword));
+        if !keyword && rest.starts_with(|c: char| c.is_ascii() && c.is_snakecase(false)) {
+            return self.add_error(format!("invalid {} literal", kind_at_end));
+        }
+        self.add_token(TokenType::Number, Some(Literal::Number(num.to_string())))
     }
-    fn peek(&self, offset: usize) -> Option<char> {
-        self.source.chars().nth(self.current + offset)
-    }
-    fn matches(&mut self, expected: char) -> bool {
-        let c = self.get_char();
-        if c.is_some() && c.unwrap() == expected {
-            self.current += 1;
-            return true;
-        } else {
-            return false;
+    /// Scans digits separated by single underscores, adding an error and returning false
+    /// on an underscore not followed by a digit.
+    fn scan_digits(&mut self) -> bool {
+        loop {
+            while self.get_char().is_some_and(|c| c.is_ascii_digit()) {
+                self.advance();
+            }
+            if self.get_char() != Some('_') {
+                return true;
+            }
+            self.advance();
+            if !self.get_char().is_some_and(|c| c.is_ascii_digit()) {
+                self.add_error("invalid decimal literal".to_string());
+                return false;
+            }
         }
     }
     fn add_token(&mut self, token_type: TokenType, literal: Option<Literal>) {
@@ -237,16 +808,42 @@ impl Tokenizer {
         self.tokens.push(Token {
             token_type,
             literal,
-            line: self.line,
+            line: self.start_line,
+            span: Span {
+                lineno: self.start_line,
+                col_offset: self.start - self.start_line_start,
+                end_lineno: self.line,
+                end_col_offset: self.current - self.line_start,
+            },
             value,
         });
     }
+    fn add_error(&mut self, message: String) {
+        self.add_token(TokenType::ErrorToken, None);
+        let span = self.tokens.last().unwrap().span;
+        self.errors.push(TokenizeError {
+            message,
+            hint: None,
+            span,
+            kind: ErrorKind::Syntax,
+        });
+    }
+    fn add_error_with_hint(&mut self, message: &str, hint: String) {
+        self.add_error(message.to_string());
+        self.errors.last_mut().unwrap().hint = Some(hint);
+    }
+    fn add_error_of_kind(&mut self, kind: ErrorKind, message: &str) {
+        self.add_error(message.to_string());
+        self.errors.last_mut().unwrap().kind = kind;
+    }
     fn get_char(&self) -> Option<char> {
-        self.source.chars().nth(self.current)
+        self.source[self.current..].chars().next()
     }
     fn advance(&mut self) -> Option<char> {
         let c = self.get_char();
-        self.current += 1;
+        if let Some(c) = c {
+            self.current += c.len_utf8();
+        }
         c
     }
     fn reached_eof(&self) -> bool {
@@ -254,6 +851,150 @@ impl Tokenizer {
     }
 }
 
+const INCONSISTENT_TABS: &str = "inconsistent use of tabs and spaces in indentation";
+
+fn is_string_prefix(text: &str) -> bool {
+    matches!(
+        text.to_ascii_lowercase().as_str(),
+        "r" | "u" | "b" | "f" | "br" | "rb" | "fr" | "rf"
+    )
+}
+
+/// Decodes the body of a `str` literal, processing escapes unless `raw`.
+fn decode_str(body: &str, raw: bool) -> Result<String, String> {
+    if raw {
+        return Ok(body.to_string());
+    }
+    let mut out = String::with_capacity(body.len());
+    decode_escapes(body, false, &mut |unit| {
+        out.push(char::from_u32(unit).unwrap_or(char::REPLACEMENT_CHARACTER))
+    })
+    .map_err(|(start, end, reason)| {
+        format!(
+            "(unicode error) 'unicodeescape' codec can't decode bytes in position {}-{}: {}",
+            start,
+            end - 1,
+            reason
+        )
+    })?;
+    Ok(out)
+}
+
+/// Decodes the body of a `bytes` literal, which may only hold ASCII characters.
+fn decode_bytes(body: &str, raw: bool) -> Result<Vec<u8>, String> {
+    if !body.is_ascii() {
+        return Err("bytes can only contain ASCII literal characters".to_string());
+    }
+    if raw {
+        return Ok(body.as_bytes().to_vec());
+    }
+    let mut out = Vec::with_capacity(body.len());
+    decode_escapes(body, true, &mut |unit| out.push(unit as u8)).map_err(|(start, _, _)| {
+        format!("(value error) invalid \\x escape at position {}", start)
+    })?;
+    Ok(out)
+}
+
+/// Calls `emit` with the code point, or byte when `bytes` is set, of every character of `body`
+/// once escape sequences are resolved. Unknown escapes are kept with their backslash.
+///
+/// A malformed `\x`, `\u`, `\U` or `\N` escape fails with its range and the reason, as
+/// CPython's `unicodeescape` codec reports them: positions count each non-ASCII character of
+/// a `str` as 10, the length of the `\UXXXXXXXX` escape CPython rewrites it to first.
+fn decode_escapes(
+    body: &str,
+    bytes: bool,
+    emit: &mut dyn FnMut(u32),
+) -> Result<(), (usize, usize, &'static str)> {
+    let width = |c: char| if c.is_ascii() { 1 } else { 10 };
+    let mut chars = body.chars().peekable();
+    let mut pos = 0;
+    while let Some(c) = chars.next() {
+        let start = pos;
+        pos += width(c);
+        if c != '\\' {
+            emit(c as u32);
+            continue;
+        }
+        let Some(escape) = chars.next() else {
+            emit('\\' as u32);
+            break;
+        };
+        pos += width(escape);
+        let simple = match escape {
+            '\n' => continue,
+            '\\' | '\'' | '"' => Some(escape as u32),
+            'a' => Some(0x07),
+            'b' => Some(0x08),
+            'f' => Some(0x0c),
+            'n' => Some(0x0a),
+            'r' => Some(0x0d),
+            't' => Some(0x09),
+            'v' => Some(0x0b),
+            _ => None,
+        };
+        if let Some(unit) = simple {
+            emit(unit);
+            continue;
+        }
+        let (radix, len, reason) = match escape {
+            '0'..='7' => (8, 3, ""),
+            'x' => (16, 2, "truncated \\xXX escape"),
+            'u' if !bytes => (16, 4, "truncated \\uXXXX escape"),
+            'U' if !bytes => (16, 8, "truncated \\UXXXXXXXX escape"),
+            'N' if !bytes => {
+                let malformed = "malformed \\N character escape";
+                if chars.next_if_eq(&'{').is_none() {
+                    return Err((start, pos, malformed));
+                }
+                pos += 1;
+                let mut name = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    pos += width(c);
+                    if c == '}' {
+                        closed = true;
+                        break;
+                    }
+                    name.push(c);
+                }
+                if !closed || name.is_empty() {
+                    let end = if closed { pos - 1 } else { pos };
+                    return Err((start, end, malformed));
+                }
+                match unicode_names2::character(&name) {
+                    Some(c) => emit(c as u32),
+                    None => return Err((start, pos, "unknown Unicode character name")),
+                }
+                continue;
+            }
+            _ => {
+                emit('\\' as u32);
+                emit(escape as u32);
+                continue;
+            }
+        };
+        let mut digits = String::new();
+        if radix == 8 {
+            digits.push(escape);
+        }
+        while digits.len() < len && chars.peek().is_some_and(|c| c.is_digit(radix)) {
+            digits.push(chars.next().unwrap());
+            pos += 1;
+        }
+        match u32::from_str_radix(&digits, radix) {
+            Ok(unit) if radix == 8 || digits.len() == len => {
+                if unit > 0x10ffff {
+                    return Err((start, pos, "illegal Unicode character"));
+                }
+                emit(unit)
+            }
+            _ => return Err((start, pos, reason)),
+        }
+    }
+    Ok(())
+}
+
 #[test]
 fn test_simple() {
     let source = r"
@@ -262,81 +1003,248 @@ fn test_simple() {
     ";
     let expected = [
         Token {
-            token_type: TokenType::Newline,
+            token_type: TokenType::NL,
             value: "\n".to_string(),
             literal: None,
             line: 1,
+            span: Span {
+                lineno: 1,
+                col_offset: 0,
+                end_lineno: 1,
+                end_col_offset: 1,
+            },
+        },
+        Token {
+            token_type: TokenType::Indent,
+            value: "    ".to_string(),
+            literal: None,
+            line: 2,
+            span: Span {
+                lineno: 2,
+                col_offset: 0,
+                end_lineno: 2,
+                end_col_offset: 4,
+            },
         },
         Token {
             token_type: TokenType::Def,
             value: "def".to_string(),
             literal: None,
             line: 2,
+            span: Span {
+                lineno: 2,
+                col_offset: 4,
+                end_lineno: 2,
+                end_col_offset: 7,
+            },
         },
         Token {
             token_type: TokenType::Name,
             value: "my_func".to_string(),
             literal: None,
             line: 2,
+            span: Span {
+                lineno: 2,
+                col_offset: 8,
+                end_lineno: 2,
+                end_col_offset: 15,
+            },
         },
         Token {
             token_type: TokenType::LPar,
             value: "(".to_string(),
             literal: None,
             line: 2,
+            span: Span {
+                lineno: 2,
+                col_offset: 15,
+                end_lineno: 2,
+                end_col_offset: 16,
+            },
         },
         Token {
             token_type: TokenType::RPar,
             value: ")".to_string(),
             literal: None,
             line: 2,
+            span: Span {
+                lineno: 2,
+                col_offset: 16,
+                end_lineno: 2,
+                end_col_offset: 17,
+            },
         },
         Token {
             token_type: TokenType::Colon,
             value: ":".to_string(),
             literal: None,
             line: 2,
+            span: Span {
+                lineno: 2,
+                col_offset: 17,
+                end_lineno: 2,
+                end_col_offset: 18,
+            },
         },
         Token {
             token_type: TokenType::Newline,
             value: "\n".to_string(),
             literal: None,
             line: 2,
+            span: Span {
+                lineno: 2,
+                col_offset: 18,
+                end_lineno: 2,
+                end_col_offset: 19,
+            },
+        },
+        Token {
+            token_type: TokenType::Indent,
+            value: "        ".to_string(),
+            literal: None,
+            line: 3,
+            span: Span {
+                lineno: 3,
+                col_offset: 0,
+                end_lineno: 3,
+                end_col_offset: 8,
+            },
         },
         Token {
             token_type: TokenType::Name,
             value: "print".to_string(),
             literal: None,
             line: 3,
+            span: Span {
+                lineno: 3,
+                col_offset: 8,
+                end_lineno: 3,
+                end_col_offset: 13,
+            },
         },
         Token {
             token_type: TokenType::LPar,
             value: "(".to_string(),
             literal: None,
             line: 3,
+            span: Span {
+                lineno: 3,
+                col_offset: 13,
+                end_lineno: 3,
+                end_col_offset: 14,
+            },
         },
         Token {
             token_type: TokenType::String,
             value: "'hello world!'".to_string(),
             literal: Some(Literal::String("hello world!".to_string())),
             line: 3,
+            span: Span {
+                lineno: 3,
+                col_offset: 14,
+                end_lineno: 3,
+                end_col_offset: 28,
+            },
         },
         Token {
             token_type: TokenType::RPar,
             value: ")".to_string(),
             literal: None,
             line: 3,
+            span: Span {
+                lineno: 3,
+                col_offset: 28,
+                end_lineno: 3,
+                end_col_offset: 29,
+            },
         },
         Token {
             token_type: TokenType::Newline,
             value: "\n".to_string(),
             literal: None,
             line: 3,
+            span: Span {
+                lineno: 3,
+                col_offset: 29,
+                end_lineno: 3,
+                end_col_offset: 30,
+            },
+        },
+        Token {
+            token_type: TokenType::Dedent,
+            value: "".to_string(),
+            literal: None,
+            line: 4,
+            span: Span {
+                lineno: 4,
+                col_offset: 0,
+                end_lineno: 4,
+                end_col_offset: 0,
+            },
+        },
+        Token {
+            token_type: TokenType::Dedent,
+            value: "".to_string(),
+            literal: None,
+            line: 4,
+            span: Span {
+                lineno: 4,
+                col_offset: 0,
+                end_lineno: 4,
+                end_col_offset: 0,
+            },
+        },
+        Token {
+            token_type: TokenType::Endmarker,
+            value: "".to_string(),
+            literal: None,
+            line: 4,
+            span: Span {
+                lineno: 4,
+                col_offset: 0,
+                end_lineno: 4,
+                end_col_offset: 0,
+            },
         },
     ];
     let mut t = Tokenizer::new(source.to_string());
     t.scan_tokens();
-    for (i, token) in t.tokens.iter().enumerate() {
-        assert_eq!(expected[i], *token);
+    assert_eq!(t.tokens(), expected);
+    assert!(t.errors().is_empty());
+}
+
+#[test]
+fn test_legacy_fstrings() {
+    for (source, message) in [
+        ("f'{'a'}'", Some("f-string: expecting '}'")),
+        ("f'{x[\"a\"]}'", None),
+        ("f'''{'a'}'''", None),
+        ("f'{a:{b:0}}'", None),
+        (
+            "f'{a # c\n}'",
+            Some("f-string expression part cannot include '#'"),
+        ),
+        (
+            "f'{\"\\n\".join(a)}'",
+            Some("f-string expression part cannot include a backslash"),
+        ),
+        (
+            "f'{x:{y:{z}}}'",
+            Some("f-string: expressions nested too deeply"),
+        ),
+    ] {
+        let mut t = Tokenizer::new(source.to_string()).with_version(PythonVersion::Py311);
+        t.scan_tokens();
+        let error = t.errors().first().map(|error| error.message.as_str());
+        assert_eq!(error, message, "{:?}", source);
+        let mut t = Tokenizer::new(source.to_string());
+        t.scan_tokens();
+        assert!(t.errors().is_empty(), "{:?}", source);
     }
+    let mut t = Tokenizer::new("f'{w:{x:{y:{z}}}}'".to_string());
+    t.scan_tokens();
+    assert_eq!(
+        t.errors()[0].message,
+        "f-string: expressions nested too deeply"
+    );
 }
@@ -1,6 +1,7 @@
 use core::fmt;
 
 #[derive(Eq, PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TokenType {
     /// Single-character tokens.
     LPar,
@@ -78,6 +79,7 @@ pub enum TokenType {
     False,
     Finally,
     For,
+    From,
     Global,
     If,
     Import,
@@ -109,6 +111,8 @@ pub enum TokenType {
     FStringEnd,
     Comment,
     NL,
+    /// Text the tokenizer could not make sense of, e.g. an unterminated string.
+    ErrorToken,
 }
 
 impl fmt::Display for TokenType {
@@ -180,6 +184,7 @@ impl fmt::Display for TokenType {
             TokenType::FStringEnd => write!(f, "FStringEnd"),
             TokenType::Comment => write!(f, "Comment"),
             TokenType::NL => write!(f, "NL"),
+            TokenType::ErrorToken => write!(f, "ErrorToken"),
             TokenType::And => write!(f, "And"),
             TokenType::As => write!(f, "As"),
             TokenType::Assert => write!(f, "Assert"),
@@ -194,6 +199,7 @@ impl fmt::Display for TokenType {
             TokenType::False => write!(f, "False"),
             TokenType::Finally => write!(f, "Finally"),
             TokenType::For => write!(f, "For"),
+            TokenType::From => write!(f, "From"),
             TokenType::Global => write!(f, "Global"),
             TokenType::If => write!(f, "If"),
             TokenType::Import => write!(f, "Import"),
@@ -216,22 +222,238 @@ impl fmt::Display for TokenType {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
 pub enum Literal {
     String(String),
+    Bytes(Vec<u8>),
+    /// The source text of a number, e.g. `0x_ff` or `1e10j`.
     Number(String),
+    Bool(bool),
+    None,
+    Ellipsis,
+}
+
+/// Literals are written as the JSON value of the Python constant, e.g. `16` or `null` for
+/// `None`. Constants JSON cannot hold are written as objects tagged like nodes:
+/// `{"_type": "Ellipsis"}`, `{"_type": "Bytes", "value": "<bytes as Latin-1>"}`, and
+/// `{"_type": "Number", "value": "<source text>"}` for complex numbers, integers past `u64`,
+/// and numbers spelled other than as their `repr()`, such as `0x10`, so that they read back
+/// unchanged.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Literal {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        fn tagged<S: serde::Serializer>(
+            serializer: S,
+            tag: &str,
+            value: Option<&str>,
+        ) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(Some(1 + usize::from(value.is_some())))?;
+            map.serialize_entry("_type", tag)?;
+            if let Some(value) = value {
+                map.serialize_entry("value", value)?;
+            }
+            map.end()
+        }
+
+        let text = match self {
+            Literal::String(value) => return serializer.serialize_str(value),
+            Literal::Bool(value) => return serializer.serialize_bool(*value),
+            Literal::None => return serializer.serialize_unit(),
+            Literal::Ellipsis => return tagged(serializer, "Ellipsis", None),
+            Literal::Bytes(value) => {
+                let value: String = value.iter().map(|b| char::from(*b)).collect();
+                return tagged(serializer, "Bytes", Some(&value));
+            }
+            Literal::Number(text) => text,
+        };
+        if crate::dump::number_repr(text) != *text {
+            return tagged(serializer, "Number", Some(text));
+        }
+        if let Ok(value) = text.parse::<u64>() {
+            return serializer.serialize_u64(value);
+        }
+        match text.parse::<f64>() {
+            Ok(value) if value.is_finite() && text.contains(['.', 'e']) => {
+                serializer.serialize_f64(value)
+            }
+            _ => tagged(serializer, "Number", Some(text)),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Literal {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{self, MapAccess, Visitor};
+
+        struct LiteralVisitor;
+
+        impl<'de> Visitor<'de> for LiteralVisitor {
+            type Value = Literal;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a JSON scalar or a Number, Bytes or Ellipsis object")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Literal, E> {
+                Ok(Literal::String(value.to_string()))
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Literal, E> {
+                Ok(Literal::Bool(value))
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Literal, E> {
+                Ok(Literal::None)
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Literal, E> {
+                Ok(Literal::None)
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Literal, E> {
+                Ok(Literal::Number(value.to_string()))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Literal, E> {
+                Ok(Literal::Number(value.to_string()))
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Literal, E> {
+                Ok(Literal::Number(crate::dump::float_repr(value)))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Literal, A::Error> {
+                let mut tag: Option<String> = None;
+                let mut value: Option<String> = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "_type" => tag = Some(map.next_value()?),
+                        "value" => value = Some(map.next_value()?),
+                        _ => return Err(de::Error::unknown_field(&key, &["_type", "value"])),
+                    }
+                }
+                let tag = tag.ok_or_else(|| de::Error::missing_field("_type"))?;
+                let value = || {
+                    value
+                        .clone()
+                        .ok_or_else(|| de::Error::missing_field("value"))
+                };
+                match tag.as_str() {
+                    "Ellipsis" => Ok(Literal::Ellipsis),
+                    "Number" => Ok(Literal::Number(value()?)),
+                    "Bytes" => value()?
+                        .chars()
+                        .map(|c| {
+                            u8::try_from(c).map_err(|_| de::Error::custom("byte out of range"))
+                        })
+                        .collect::<Result<_, _>>()
+                        .map(Literal::Bytes),
+                    _ => Err(de::Error::unknown_variant(
+                        &tag,
+                        &["Ellipsis", "Number", "Bytes"],
+                    )),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(LiteralVisitor)
+    }
+}
+
+pub type Identifier = String;
+
+/// Where a token or node sits in the source, following CPython's `ast` conventions:
+/// lines are 1-based and columns are 0-based offsets into their line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    pub lineno: usize,
+    pub col_offset: usize,
+    pub end_lineno: usize,
+    pub end_col_offset: usize,
+}
+
+impl Span {
+    /// Returns the span covering `self` through the end of `end`.
+    pub fn to(self, end: Span) -> Span {
+        Span {
+            end_lineno: end.end_lineno,
+            end_col_offset: end.end_col_offset,
+            ..self
+        }
+    }
+}
+
+/// Which of CPython's `SyntaxError` classes an error is raised as.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    #[default]
+    Syntax,
+    /// An `IndentationError`, such as a missing indented block.
+    Indentation,
+    /// A `TabError`, for indentation mixing tabs and spaces inconsistently.
+    Tab,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::Syntax => write!(f, "SyntaxError"),
+            ErrorKind::Indentation => write!(f, "IndentationError"),
+            ErrorKind::Tab => write!(f, "TabError"),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// The Python release source is parsed for. Syntax added in later releases is reported as
+/// an error, like with `ast.parse(source, feature_version=...)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, clap::ValueEnum)]
+pub enum PythonVersion {
+    #[value(name = "3.7")]
+    Py37,
+    #[value(name = "3.8")]
+    Py38,
+    #[value(name = "3.9")]
+    Py39,
+    #[value(name = "3.10")]
+    Py310,
+    #[value(name = "3.11")]
+    Py311,
+    #[value(name = "3.12")]
+    Py312,
+    #[default]
+    #[value(name = "3.13")]
+    Py313,
+}
+
+impl PythonVersion {
+    /// The minor version, e.g. 12 for Python 3.12.
+    pub fn minor(self) -> u8 {
+        self as u8 + 7
+    }
+}
+
+impl fmt::Display for PythonVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "3.{}", self.minor())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Token {
     pub token_type: TokenType,
     pub value: String,
     pub literal: Option<Literal>,
     pub line: usize,
+    pub span: Span,
 }
 
-impl Token {
-    fn to_string(&self) -> String {
-        format!("{} {} {}", self.token_type, self.value, self.line)
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.token_type, self.value, self.line)
     }
 }
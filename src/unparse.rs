@@ -0,0 +1,1552 @@
+//! Python source regenerated from the AST, a port of `ast.unparse` from CPython's
+//! `Lib/ast.py` (as of Python 3.13). Parentheses are only written where the precedence of
+//! the parent requires them, which each node is passed along with it.
+
+use std::{collections::HashMap, fmt};
+
+use crate::ast::*;
+use crate::dump::{bytes_repr, is_printable, number_repr, str_repr};
+use crate::types::{Literal, PythonVersion};
+
+/// Why a tree could not be unparsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnparseError {
+    pub message: String,
+}
+
+impl fmt::Display for UnparseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Returns Python source that parses back into `node`, like `ast.unparse(node)`.
+pub fn unparse(node: &(impl Unparse + ?Sized)) -> Result<String, UnparseError> {
+    unparse_with_version(node, PythonVersion::default())
+}
+
+/// Returns source like `unparse` that also parses with `version`. Before Python 3.12, the
+/// expressions of f-string replacement fields can neither hold backslashes nor reuse the
+/// quotes of the f-string, which is an error if they cannot be avoided.
+pub fn unparse_with_version(
+    node: &(impl Unparse + ?Sized),
+    version: PythonVersion,
+) -> Result<String, UnparseError> {
+    let mut unparser = Unparser::new(version);
+    node.unparse_into(&mut unparser);
+    match unparser.error {
+        Some(error) => Err(error),
+        None => Ok(unparser.source),
+    }
+}
+
+/// Implemented by the nodes `unparse` accepts.
+pub trait Unparse {
+    fn unparse_into(&self, unparser: &mut Unparser);
+}
+
+/// Port of `_Precedence` in `Lib/ast.py`, from the loosest binding to the tightest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    NamedExpr,
+    Tuple,
+    Yield,
+    Test,
+    Or,
+    And,
+    Not,
+    Cmp,
+    /// Also that of `|`.
+    Expr,
+    BXor,
+    BAnd,
+    Shift,
+    Arith,
+    Term,
+    Factor,
+    Power,
+    Await,
+    Atom,
+}
+
+impl Precedence {
+    const BOR: Precedence = Precedence::Expr;
+
+    fn next(self) -> Self {
+        use Precedence::*;
+        const ALL: [Precedence; 18] = [
+            NamedExpr, Tuple, Yield, Test, Or, And, Not, Cmp, Expr, BXor, BAnd, Shift, Arith, Term,
+            Factor, Power, Await, Atom,
+        ];
+        ALL[(self as usize + 1).min(ALL.len() - 1)]
+    }
+}
+
+const SINGLE_QUOTES: [&str; 2] = ["'", "\""];
+const MULTI_QUOTES: [&str; 2] = ["\"\"\"", "'''"];
+const ALL_QUOTES: [&str; 4] = ["'", "\"", "\"\"\"", "'''"];
+
+/// Infinities come from float literals too large for `f64`, such as this one.
+const INFSTR: &str = "1e309";
+
+/// The state of an `unparse`, port of `_Unparser` in `Lib/ast.py`.
+pub struct Unparser {
+    source: String,
+    indent: usize,
+    in_try_star: bool,
+    /// Tags of the `# type: ignore` comments of the module, by line.
+    type_ignores: HashMap<usize, String>,
+    version: PythonVersion,
+    /// Whether string constants are written without backslashes if possible, as inside the
+    /// replacement fields of f-strings before 3.12.
+    avoid_backslashes: bool,
+    error: Option<UnparseError>,
+}
+
+impl Unparser {
+    fn new(version: PythonVersion) -> Self {
+        Self {
+            source: String::new(),
+            indent: 0,
+            in_try_star: false,
+            type_ignores: HashMap::new(),
+            version,
+            avoid_backslashes: false,
+            error: None,
+        }
+    }
+
+    fn fail(&mut self, message: &str) {
+        self.error.get_or_insert_with(|| UnparseError {
+            message: message.to_string(),
+        });
+    }
+
+    fn write(&mut self, text: &str) {
+        self.source.push_str(text);
+    }
+
+    fn maybe_newline(&mut self) {
+        if !self.source.is_empty() {
+            self.write("\n");
+        }
+    }
+
+    /// Starts a line at the current indentation with `text`.
+    fn fill(&mut self, text: &str) {
+        self.maybe_newline();
+        self.write(&"    ".repeat(self.indent));
+        self.write(text);
+    }
+
+    /// Runs `f` writing to an empty buffer, returning what it wrote.
+    fn buffered(&mut self, f: impl FnOnce(&mut Self)) -> String {
+        let source = std::mem::take(&mut self.source);
+        f(self);
+        std::mem::replace(&mut self.source, source)
+    }
+
+    /// Writes the colon of a block, followed by `extra`, and its body indented.
+    fn block(&mut self, extra: Option<String>, f: impl FnOnce(&mut Self)) {
+        self.write(":");
+        if let Some(extra) = extra {
+            self.write(&extra);
+        }
+        self.indent += 1;
+        f(self);
+        self.indent -= 1;
+    }
+
+    fn delimit(&mut self, start: &str, end: &str, f: impl FnOnce(&mut Self)) {
+        self.write(start);
+        f(self);
+        self.write(end);
+    }
+
+    fn delimit_if(&mut self, start: &str, end: &str, condition: bool, f: impl FnOnce(&mut Self)) {
+        match condition {
+            true => self.delimit(start, end, f),
+            false => f(self),
+        }
+    }
+
+    /// Writes `f` in parentheses if the node written binds looser than its context requires.
+    fn require_parens(
+        &mut self,
+        precedence: Precedence,
+        context: Precedence,
+        f: impl FnOnce(&mut Self),
+    ) {
+        self.delimit_if("(", ")", context > precedence, f);
+    }
+
+    /// Writes `items` separated by commas, with a trailing one if there is a single item.
+    fn items_view(&mut self, items: &[Expr]) {
+        if let [item] = items {
+            self.expr(item, Precedence::Test);
+            self.write(",");
+        } else {
+            self.exprs(items);
+        }
+    }
+
+    fn exprs(&mut self, exprs: &[Expr]) {
+        for (i, expr) in exprs.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+            self.expr(expr, Precedence::Test);
+        }
+    }
+
+    fn names(&mut self, names: &[String]) {
+        self.write(&names.join(", "));
+    }
+
+    fn type_comment(&self, lineno: usize, type_comment: &Option<String>) -> Option<String> {
+        let comment = self.type_ignores.get(&lineno).or(type_comment.as_ref())?;
+        Some(format!(" # type: {}", comment))
+    }
+
+    fn stmts(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            self.stmt(stmt);
+        }
+    }
+
+    /// Writes `body`, its docstring as a triple-quoted string if it starts with one.
+    fn docstring_and_body(&mut self, body: &[Stmt]) {
+        if let Some(Stmt::ExprStmt(ExprStmt { value, .. })) = body.first() {
+            if let Expr::Constant(Constant {
+                value: Literal::String(docstring),
+                kind,
+                ..
+            }) = &**value
+            {
+                self.fill("");
+                if kind.as_deref() == Some("u") {
+                    self.write("u");
+                }
+                self.str_avoiding_backslashes(docstring, &MULTI_QUOTES);
+                return self.stmts(&body[1..]);
+            }
+        }
+        self.stmts(body)
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::FunctionDef(node) => self.function(
+                "def",
+                &node.name,
+                &node.args,
+                &node.body,
+                &node.decorator_list,
+                &node.returns,
+                &node.type_params,
+                self.type_comment(node.span.lineno, &node.type_comment),
+            ),
+            Stmt::AsyncFunctionDef(node) => self.function(
+                "async def",
+                &node.name,
+                &node.args,
+                &node.body,
+                &node.decorator_list,
+                &node.returns,
+                &node.type_params,
+                self.type_comment(node.span.lineno, &node.type_comment),
+            ),
+            Stmt::ClassDef(node) => {
+                self.maybe_newline();
+                for decorator in &node.decorator_list {
+                    self.fill("@");
+                    self.expr(decorator, Precedence::Test);
+                }
+                self.fill(&format!("class {}", node.name));
+                self.type_params(&node.type_params);
+                let parens = !node.bases.is_empty() || !node.keywords.is_empty();
+                self.delimit_if("(", ")", parens, |u| {
+                    u.call_args(&node.bases, &node.keywords)
+                });
+                self.block(None, |u| u.docstring_and_body(&node.body));
+            }
+            Stmt::Return(node) => {
+                self.fill("return");
+                if let Some(value) = &node.value {
+                    self.write(" ");
+                    self.expr(value, Precedence::Test);
+                }
+            }
+            Stmt::Delete(node) => {
+                self.fill("del ");
+                self.exprs(&node.targets);
+            }
+            Stmt::Assign(node) => {
+                self.fill("");
+                for target in &node.targets {
+                    self.expr(target, Precedence::Tuple);
+                    self.write(" = ");
+                }
+                self.expr(&node.value, Precedence::Test);
+                if let Some(comment) = self.type_comment(node.span.lineno, &node.type_comment) {
+                    self.write(&comment);
+                }
+            }
+            Stmt::TypeAlias(node) => {
+                self.fill("type ");
+                self.expr(&node.name, Precedence::Test);
+                self.type_params(&node.type_params);
+                self.write(" = ");
+                self.expr(&node.value, Precedence::Test);
+            }
+            Stmt::AugAssign(node) => {
+                self.fill("");
+                self.expr(&node.target, Precedence::Test);
+                self.write(&format!(" {}= ", binop(&node.op).0));
+                self.expr(&node.value, Precedence::Test);
+            }
+            Stmt::AnnAssign(node) => {
+                self.fill("");
+                let parens = node.simple == 0 && matches!(*node.target, Expr::Name(_));
+                self.delimit_if("(", ")", parens, |u| u.expr(&node.target, Precedence::Test));
+                self.write(": ");
+                self.expr(&node.annotation, Precedence::Test);
+                if let Some(value) = &node.value {
+                    self.write(" = ");
+                    self.expr(value, Precedence::Test);
+                }
+            }
+            Stmt::For(node) => self.for_loop(
+                "for ",
+                &node.target,
+                &node.iter,
+                &node.body,
+                &node.orelse,
+                self.type_comment(node.span.lineno, &node.type_comment),
+            ),
+            Stmt::AsyncFor(node) => self.for_loop(
+                "async for ",
+                &node.target,
+                &node.iter,
+                &node.body,
+                &node.orelse,
+                self.type_comment(node.span.lineno, &node.type_comment),
+            ),
+            Stmt::While(node) => {
+                self.fill("while ");
+                self.expr(&node.test, Precedence::Test);
+                self.block(None, |u| u.stmts(&node.body));
+                self.orelse(&node.orelse);
+            }
+            Stmt::If(node) => {
+                self.fill("if ");
+                self.expr(&node.test, Precedence::Test);
+                self.block(None, |u| u.stmts(&node.body));
+                // Nested ifs collapse into elifs.
+                let mut orelse = &node.orelse;
+                while let [Stmt::If(node)] = orelse.as_slice() {
+                    self.fill("elif ");
+                    self.expr(&node.test, Precedence::Test);
+                    self.block(None, |u| u.stmts(&node.body));
+                    orelse = &node.orelse;
+                }
+                self.orelse(orelse);
+            }
+            Stmt::With(node) => {
+                let comment = self.type_comment(node.span.lineno, &node.type_comment);
+                self.with("with ", &node.items, &node.body, comment);
+            }
+            Stmt::AsyncWith(node) => {
+                let comment = self.type_comment(node.span.lineno, &node.type_comment);
+                self.with("async with ", &node.items, &node.body, comment);
+            }
+            Stmt::Match(node) => {
+                self.fill("match ");
+                self.expr(&node.subject, Precedence::Test);
+                self.block(None, |u| {
+                    for case in &node.cases {
+                        u.match_case(case);
+                    }
+                });
+            }
+            Stmt::Raise(node) => {
+                self.fill("raise");
+                let Some(exc) = &node.exc else {
+                    if node.cause.is_some() {
+                        self.fail("Node can't use cause without an exception.");
+                    }
+                    return;
+                };
+                self.write(" ");
+                self.expr(exc, Precedence::Test);
+                if let Some(cause) = &node.cause {
+                    self.write(" from ");
+                    self.expr(cause, Precedence::Test);
+                }
+            }
+            Stmt::Try(node) => self.try_stmt(
+                false,
+                &node.body,
+                &node.handlers,
+                &node.orelse,
+                &node.finalbody,
+            ),
+            Stmt::TryStar(node) => self.try_stmt(
+                true,
+                &node.body,
+                &node.handlers,
+                &node.orelse,
+                &node.finalbody,
+            ),
+            Stmt::Assert(node) => {
+                self.fill("assert ");
+                self.expr(&node.test, Precedence::Test);
+                if let Some(msg) = &node.msg {
+                    self.write(", ");
+                    self.expr(msg, Precedence::Test);
+                }
+            }
+            Stmt::Import(node) => {
+                self.fill("import ");
+                self.aliases(&node.names);
+            }
+            Stmt::ImportFrom(node) => {
+                self.fill("from ");
+                self.write(&".".repeat(node.level.max(0) as usize));
+                if let Some(module) = &node.module {
+                    self.write(module);
+                }
+                self.write(" import ");
+                self.aliases(&node.names);
+            }
+            Stmt::Global(node) => {
+                self.fill("global ");
+                self.names(&node.names);
+            }
+            Stmt::Nonlocal(node) => {
+                self.fill("nonlocal ");
+                self.names(&node.names);
+            }
+            Stmt::ExprStmt(node) => {
+                self.fill("");
+                self.expr(&node.value, Precedence::Yield);
+            }
+            Stmt::Pass(_) => self.fill("pass"),
+            Stmt::Break(_) => self.fill("break"),
+            Stmt::Continue(_) => self.fill("continue"),
+            Stmt::ErrorStmt(_) => self.fail("Error nodes cannot be unparsed"),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn function(
+        &mut self,
+        keyword: &str,
+        name: &str,
+        args: &Arguments,
+        body: &[Stmt],
+        decorator_list: &[Expr],
+        returns: &Option<Box<Expr>>,
+        type_params: &[TypeParam],
+        type_comment: Option<String>,
+    ) {
+        self.maybe_newline();
+        for decorator in decorator_list {
+            self.fill("@");
+            self.expr(decorator, Precedence::Test);
+        }
+        self.fill(&format!("{} {}", keyword, name));
+        self.type_params(type_params);
+        self.delimit("(", ")", |u| u.arguments(args));
+        if let Some(returns) = returns {
+            self.write(" -> ");
+            self.expr(returns, Precedence::Test);
+        }
+        self.block(type_comment, |u| u.docstring_and_body(body));
+    }
+
+    fn type_params(&mut self, type_params: &[TypeParam]) {
+        if type_params.is_empty() {
+            return;
+        }
+        self.delimit("[", "]", |u| {
+            for (i, type_param) in type_params.iter().enumerate() {
+                if i > 0 {
+                    u.write(", ");
+                }
+                u.type_param(type_param);
+            }
+        });
+    }
+
+    fn type_param(&mut self, type_param: &TypeParam) {
+        match type_param {
+            TypeParam::TypeVar(node) => {
+                self.write(&node.name);
+                if let Some(bound) = &node.bound {
+                    self.write(": ");
+                    self.expr(bound, Precedence::Test);
+                }
+            }
+            TypeParam::ParamSpec(node) => self.write(&format!("**{}", node.name)),
+            TypeParam::TypeVarTuple(node) => self.write(&format!("*{}", node.name)),
+        }
+        let default_value = match type_param {
+            TypeParam::TypeVar(node) => &node.default_value,
+            TypeParam::ParamSpec(node) => &node.default_value,
+            TypeParam::TypeVarTuple(node) => &node.default_value,
+        };
+        if let Some(default_value) = default_value {
+            self.write(" = ");
+            self.expr(default_value, Precedence::Test);
+        }
+    }
+
+    fn for_loop(
+        &mut self,
+        keyword: &str,
+        target: &Expr,
+        iter: &Expr,
+        body: &[Stmt],
+        orelse: &[Stmt],
+        type_comment: Option<String>,
+    ) {
+        self.fill(keyword);
+        self.expr(target, Precedence::Tuple);
+        self.write(" in ");
+        self.expr(iter, Precedence::Test);
+        self.block(type_comment, |u| u.stmts(body));
+        self.orelse(orelse);
+    }
+
+    fn orelse(&mut self, orelse: &[Stmt]) {
+        if !orelse.is_empty() {
+            self.fill("else");
+            self.block(None, |u| u.stmts(orelse));
+        }
+    }
+
+    fn with(
+        &mut self,
+        keyword: &str,
+        items: &[WithItem],
+        body: &[Stmt],
+        type_comment: Option<String>,
+    ) {
+        self.fill(keyword);
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+            self.with_item(item);
+        }
+        self.block(type_comment, |u| u.stmts(body));
+    }
+
+    fn with_item(&mut self, item: &WithItem) {
+        self.expr(&item.context_expr, Precedence::Test);
+        if let Some(vars) = &item.optional_vars {
+            self.write(" as ");
+            self.expr(vars, Precedence::Test);
+        }
+    }
+
+    fn try_stmt(
+        &mut self,
+        star: bool,
+        body: &[Stmt],
+        handlers: &[ExceptHandler],
+        orelse: &[Stmt],
+        finalbody: &[Stmt],
+    ) {
+        let in_try_star = std::mem::replace(&mut self.in_try_star, star);
+        self.fill("try");
+        self.block(None, |u| u.stmts(body));
+        for handler in handlers {
+            self.except_handler(handler);
+        }
+        self.orelse(orelse);
+        if !finalbody.is_empty() {
+            self.fill("finally");
+            self.block(None, |u| u.stmts(finalbody));
+        }
+        self.in_try_star = in_try_star;
+    }
+
+    fn except_handler(&mut self, handler: &ExceptHandler) {
+        self.fill(if self.in_try_star {
+            "except*"
+        } else {
+            "except"
+        });
+        if let Some(r#type) = &handler.r#type {
+            self.write(" ");
+            self.expr(r#type, Precedence::Test);
+        }
+        if let Some(name) = &handler.name {
+            self.write(" as ");
+            self.write(name);
+        }
+        self.block(None, |u| u.stmts(&handler.body));
+    }
+
+    fn aliases(&mut self, aliases: &[Alias]) {
+        for (i, alias) in aliases.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+            self.alias(alias);
+        }
+    }
+
+    fn alias(&mut self, alias: &Alias) {
+        self.write(&alias.name);
+        if let Some(asname) = &alias.asname {
+            self.write(" as ");
+            self.write(asname);
+        }
+    }
+
+    fn match_case(&mut self, case: &MatchCase) {
+        self.fill("case ");
+        self.pattern(&case.pattern, Precedence::Test);
+        if let Some(guard) = &case.guard {
+            self.write(" if ");
+            self.expr(guard, Precedence::Test);
+        }
+        self.block(None, |u| u.stmts(&case.body));
+    }
+
+    /// Writes `expr` where its context requires a binding of at least `precedence`.
+    fn expr(&mut self, expr: &Expr, precedence: Precedence) {
+        match expr {
+            Expr::BoolOp(node) => {
+                let (operator, mut operand) = match node.op {
+                    BoolOperator::And => (" and ", Precedence::And),
+                    BoolOperator::Or => (" or ", Precedence::Or),
+                };
+                self.require_parens(operand, precedence, |u| {
+                    for (i, value) in node.values.iter().enumerate() {
+                        if i > 0 {
+                            u.write(operator);
+                        }
+                        // Like `ast.unparse`, each operand binds tighter than the last.
+                        operand = operand.next();
+                        u.expr(value, operand);
+                    }
+                });
+            }
+            Expr::NamedExpr(node) => {
+                self.require_parens(Precedence::NamedExpr, precedence, |u| {
+                    u.expr(&node.target, Precedence::Atom);
+                    u.write(" := ");
+                    u.expr(&node.value, Precedence::Atom);
+                });
+            }
+            Expr::BinOp(node) => {
+                let (operator, operator_precedence) = binop(&node.op);
+                let (left, right) = match node.op {
+                    Operator::Pow => (operator_precedence.next(), operator_precedence),
+                    _ => (operator_precedence, operator_precedence.next()),
+                };
+                self.require_parens(operator_precedence, precedence, |u| {
+                    u.expr(&node.left, left);
+                    u.write(&format!(" {} ", operator));
+                    u.expr(&node.right, right);
+                });
+            }
+            Expr::UnaryOp(node) => {
+                let (operator, operator_precedence) = match node.op {
+                    UnaryOperator::Invert => ("~", Precedence::Factor),
+                    UnaryOperator::Not => ("not ", Precedence::Not),
+                    UnaryOperator::UAdd => ("+", Precedence::Factor),
+                    UnaryOperator::USub => ("-", Precedence::Factor),
+                };
+                self.require_parens(operator_precedence, precedence, |u| {
+                    u.write(operator);
+                    u.expr(&node.operand, operator_precedence);
+                });
+            }
+            Expr::Lambda(node) => {
+                self.require_parens(Precedence::Test, precedence, |u| {
+                    u.write("lambda");
+                    let args = u.buffered(|u| u.arguments(&node.args));
+                    if !args.is_empty() {
+                        u.write(" ");
+                        u.write(&args);
+                    }
+                    u.write(": ");
+                    u.expr(&node.body, Precedence::Test);
+                });
+            }
+            Expr::IfExp(node) => {
+                self.require_parens(Precedence::Test, precedence, |u| {
+                    u.expr(&node.body, Precedence::Test.next());
+                    u.write(" if ");
+                    u.expr(&node.test, Precedence::Test.next());
+                    u.write(" else ");
+                    u.expr(&node.orelse, Precedence::Test);
+                });
+            }
+            Expr::Dict(node) => {
+                self.delimit("{", "}", |u| {
+                    for (i, (key, value)) in node.keys.iter().zip(&node.values).enumerate() {
+                        if i > 0 {
+                            u.write(", ");
+                        }
+                        match key {
+                            Some(key) => {
+                                u.expr(key, Precedence::Test);
+                                u.write(": ");
+                                u.expr(value, Precedence::Test);
+                            }
+                            None => {
+                                u.write("**");
+                                u.expr(value, Precedence::Expr);
+                            }
+                        }
+                    }
+                });
+            }
+            // `{}` would be a dict, and `set()` may be shadowed.
+            Expr::Set(node) if node.elts.is_empty() => self.write("{*()}"),
+            Expr::Set(node) => self.delimit("{", "}", |u| u.exprs(&node.elts)),
+            Expr::ListComp(node) => {
+                self.delimit("[", "]", |u| {
+                    u.expr(&node.elt, Precedence::Test);
+                    u.comprehensions(&node.generators);
+                });
+            }
+            Expr::SetComp(node) => {
+                self.delimit("{", "}", |u| {
+                    u.expr(&node.elt, Precedence::Test);
+                    u.comprehensions(&node.generators);
+                });
+            }
+            Expr::DictComp(node) => {
+                self.delimit("{", "}", |u| {
+                    u.expr(&node.key, Precedence::Test);
+                    u.write(": ");
+                    u.expr(&node.value, Precedence::Test);
+                    u.comprehensions(&node.generators);
+                });
+            }
+            Expr::GeneratorExp(node) => {
+                self.delimit("(", ")", |u| {
+                    u.expr(&node.elt, Precedence::Test);
+                    u.comprehensions(&node.generators);
+                });
+            }
+            Expr::Await(node) => {
+                self.require_parens(Precedence::Await, precedence, |u| {
+                    u.write("await ");
+                    u.expr(&node.value, Precedence::Atom);
+                });
+            }
+            Expr::Yield(node) => {
+                self.require_parens(Precedence::Yield, precedence, |u| {
+                    u.write("yield");
+                    if let Some(value) = &node.value {
+                        u.write(" ");
+                        u.expr(value, Precedence::Atom);
+                    }
+                });
+            }
+            Expr::YieldFrom(node) => {
+                self.require_parens(Precedence::Yield, precedence, |u| {
+                    u.write("yield from ");
+                    u.expr(&node.value, Precedence::Atom);
+                });
+            }
+            Expr::Compare(node) => {
+                self.require_parens(Precedence::Cmp, precedence, |u| {
+                    u.expr(&node.left, Precedence::Cmp.next());
+                    for (op, comparator) in node.ops.iter().zip(&node.comparators) {
+                        u.write(&format!(" {} ", cmpop(op)));
+                        u.expr(comparator, Precedence::Cmp.next());
+                    }
+                });
+            }
+            Expr::Call(node) => {
+                self.expr(&node.func, Precedence::Atom);
+                self.delimit("(", ")", |u| u.call_args(&node.args, &node.keywords));
+            }
+            Expr::FormattedValue(node) => self.formatted_value(node),
+            Expr::JoinedStr(node) => self.joined_str(node),
+            Expr::Constant(node) => match &node.value {
+                Literal::Ellipsis => self.write("..."),
+                value => {
+                    if node.kind.as_deref() == Some("u") {
+                        self.write("u");
+                    }
+                    self.constant(value);
+                }
+            },
+            Expr::Attribute(node) => {
+                self.expr(&node.value, Precedence::Atom);
+                // `3.__abs__()` is a syntax error, unlike `3 .__abs__()`.
+                if let Expr::Constant(Constant { value, .. }) = &*node.value {
+                    let int = match value {
+                        Literal::Number(number) => {
+                            number_repr(number).bytes().all(|b| b.is_ascii_digit())
+                        }
+                        Literal::Bool(_) => true,
+                        _ => false,
+                    };
+                    if int {
+                        self.write(" ");
+                    }
+                }
+                self.write(".");
+                self.write(&node.attr);
+            }
+            Expr::Subscript(node) => {
+                self.expr(&node.value, Precedence::Atom);
+                self.delimit("[", "]", |u| match &*node.slice {
+                    // A tuple needs no parentheses here, unless it is empty.
+                    Expr::Tuple(tuple) if !tuple.elts.is_empty() => u.items_view(&tuple.elts),
+                    slice => u.expr(slice, Precedence::Test),
+                });
+            }
+            Expr::Starred(node) => {
+                self.write("*");
+                self.expr(&node.value, Precedence::Expr);
+            }
+            Expr::Name(node) => self.write(&node.id),
+            Expr::List(node) => self.delimit("[", "]", |u| u.exprs(&node.elts)),
+            Expr::Tuple(node) => {
+                let parens = node.elts.is_empty() || precedence > Precedence::Tuple;
+                self.delimit_if("(", ")", parens, |u| u.items_view(&node.elts));
+            }
+            Expr::Slice(node) => {
+                if let Some(lower) = &node.lower {
+                    self.expr(lower, Precedence::Test);
+                }
+                self.write(":");
+                if let Some(upper) = &node.upper {
+                    self.expr(upper, Precedence::Test);
+                }
+                if let Some(step) = &node.step {
+                    self.write(":");
+                    self.expr(step, Precedence::Test);
+                }
+            }
+            Expr::ErrorExpr(_) => self.fail("Error nodes cannot be unparsed"),
+        }
+    }
+
+    fn call_args(&mut self, args: &[Expr], keywords: &[Keyword]) {
+        self.exprs(args);
+        for (i, keyword) in keywords.iter().enumerate() {
+            if i > 0 || !args.is_empty() {
+                self.write(", ");
+            }
+            self.keyword(keyword);
+        }
+    }
+
+    fn keyword(&mut self, keyword: &Keyword) {
+        match &keyword.arg {
+            Some(arg) => {
+                self.write(arg);
+                self.write("=");
+            }
+            None => self.write("**"),
+        }
+        self.expr(&keyword.value, Precedence::Test);
+    }
+
+    fn comprehensions(&mut self, generators: &[Comprehension]) {
+        for generator in generators {
+            self.comprehension(generator);
+        }
+    }
+
+    fn comprehension(&mut self, generator: &Comprehension) {
+        self.write(if generator.is_async {
+            " async for "
+        } else {
+            " for "
+        });
+        self.expr(&generator.target, Precedence::Tuple);
+        self.write(" in ");
+        self.expr(&generator.iter, Precedence::Test.next());
+        for condition in &generator.ifs {
+            self.write(" if ");
+            self.expr(condition, Precedence::Test.next());
+        }
+    }
+
+    fn arguments(&mut self, args: &Arguments) {
+        let mut first = true;
+        let mut separate = |u: &mut Self| {
+            if !std::mem::take(&mut first) {
+                u.write(", ");
+            }
+        };
+        let positional = args.posonlyargs.iter().chain(&args.args);
+        let count = args.posonlyargs.len() + args.args.len();
+        let defaults = std::iter::repeat_n(None, count.saturating_sub(args.defaults.len()))
+            .chain(args.defaults.iter().map(Some));
+        for (index, (arg, default)) in positional.zip(defaults).enumerate() {
+            separate(self);
+            self.arg(arg);
+            if let Some(default) = default {
+                self.write("=");
+                self.expr(default, Precedence::Test);
+            }
+            if index + 1 == args.posonlyargs.len() {
+                self.write(", /");
+            }
+        }
+        // The varargs, or a bare `*` before keyword-only arguments.
+        if args.vararg.is_some() || !args.kwonlyargs.is_empty() {
+            separate(self);
+            self.write("*");
+            if let Some(vararg) = &args.vararg {
+                self.arg(vararg);
+            }
+        }
+        for (arg, default) in args.kwonlyargs.iter().zip(&args.kw_defaults) {
+            self.write(", ");
+            self.arg(arg);
+            if let Some(default) = default {
+                self.write("=");
+                self.expr(default, Precedence::Test);
+            }
+        }
+        if let Some(kwarg) = &args.kwarg {
+            separate(self);
+            self.write("**");
+            self.arg(kwarg);
+        }
+    }
+
+    fn arg(&mut self, arg: &Arg) {
+        self.write(&arg.arg);
+        if let Some(annotation) = &arg.annotation {
+            self.write(": ");
+            self.expr(annotation, Precedence::Test);
+        }
+    }
+
+    fn pattern(&mut self, pattern: &Pattern, precedence: Precedence) {
+        match pattern {
+            Pattern::MatchValue(node) => self.expr(&node.value, Precedence::Test),
+            Pattern::MatchSingleton(node) => self.constant(&node.value),
+            Pattern::MatchSequence(node) => {
+                self.delimit("[", "]", |u| u.patterns(&node.patterns, Precedence::Test));
+            }
+            Pattern::MatchMapping(node) => {
+                self.delimit("{", "}", |u| {
+                    for (i, (key, pattern)) in node.keys.iter().zip(&node.patterns).enumerate() {
+                        if i > 0 {
+                            u.write(", ");
+                        }
+                        u.expr(key, Precedence::Test);
+                        u.write(": ");
+                        u.pattern(pattern, Precedence::Test);
+                    }
+                    if let Some(rest) = &node.rest {
+                        if !node.keys.is_empty() {
+                            u.write(", ");
+                        }
+                        u.write(&format!("**{}", rest));
+                    }
+                });
+            }
+            Pattern::MatchClass(node) => {
+                self.expr(&node.cls, Precedence::Atom);
+                self.delimit("(", ")", |u| {
+                    u.patterns(&node.patterns, Precedence::Test);
+                    let keywords = node.kwd_attrs.iter().zip(&node.kwd_patterns);
+                    for (i, (attr, pattern)) in keywords.enumerate() {
+                        if i > 0 || !node.patterns.is_empty() {
+                            u.write(", ");
+                        }
+                        u.write(&format!("{}=", attr));
+                        u.pattern(pattern, Precedence::Test);
+                    }
+                });
+            }
+            Pattern::MatchStar(node) => {
+                self.write(&format!("*{}", node.name.as_deref().unwrap_or("_")));
+            }
+            Pattern::MatchAs(node) => match (&node.pattern, &node.name) {
+                (_, None) => self.write("_"),
+                (None, Some(name)) => self.write(name),
+                (Some(pattern), Some(name)) => {
+                    self.require_parens(Precedence::Test, precedence, |u| {
+                        u.pattern(pattern, Precedence::BOR);
+                        u.write(&format!(" as {}", name));
+                    });
+                }
+            },
+            Pattern::MatchOr(node) => {
+                self.require_parens(Precedence::BOR, precedence, |u| {
+                    for (i, pattern) in node.patterns.iter().enumerate() {
+                        if i > 0 {
+                            u.write(" | ");
+                        }
+                        u.pattern(pattern, Precedence::BOR.next());
+                    }
+                });
+            }
+        }
+    }
+
+    fn patterns(&mut self, patterns: &[Pattern], precedence: Precedence) {
+        for (i, pattern) in patterns.iter().enumerate() {
+            if i > 0 {
+                self.write(", ");
+            }
+            self.pattern(pattern, precedence);
+        }
+    }
+
+    fn constant(&mut self, value: &Literal) {
+        match value {
+            Literal::String(value) if self.avoid_backslashes => {
+                self.str_avoiding_backslashes(value, &ALL_QUOTES)
+            }
+            Literal::String(value) => self.write(&str_repr(value)),
+            Literal::Bytes(value) => self.write(&bytes_repr(value)),
+            Literal::Number(number) => self.write(&number_repr(number).replace("inf", INFSTR)),
+            Literal::Bool(true) => self.write("True"),
+            Literal::Bool(false) => self.write("False"),
+            Literal::None => self.write("None"),
+            Literal::Ellipsis => self.write("..."),
+        }
+    }
+
+    /// Writes `string` quoted with the first of `quote_types` that needs no escapes.
+    fn str_avoiding_backslashes(&mut self, string: &str, quote_types: &[&'static str]) {
+        let (string, quote_types) = str_literal_helper(string, quote_types, false);
+        self.write(quote_types[0]);
+        self.write(&string);
+        self.write(quote_types[0]);
+    }
+
+    fn joined_str(&mut self, node: &JoinedStr) {
+        self.write("f");
+        if self.avoid_backslashes {
+            let string = self.buffered(|u| u.fstring_inner_values(&node.values, false));
+            return self.str_avoiding_backslashes(&string, &ALL_QUOTES);
+        }
+        let parts: Vec<(String, bool)> = node
+            .values
+            .iter()
+            .map(|value| {
+                let part = self.buffered(|u| u.fstring_inner(value, false));
+                (part, matches!(value, Expr::Constant(_)))
+            })
+            .collect();
+        let legacy = self.version < PythonVersion::Py312;
+        let mut quote_types = ALL_QUOTES.to_vec();
+        let mut new_parts = vec![];
+        let mut fallback_to_repr = false;
+        for (part, is_constant) in &parts {
+            // Before 3.12 the expressions count too, as they may not hold the quotes.
+            if *is_constant || legacy {
+                let (part, new_quote_types) = str_literal_helper(part, &quote_types, *is_constant);
+                if legacy {
+                    new_parts.push(part.clone());
+                }
+                if new_quote_types
+                    .iter()
+                    .all(|quote| !quote_types.contains(quote))
+                {
+                    fallback_to_repr = true;
+                    break;
+                }
+                quote_types = new_quote_types;
+                if !legacy {
+                    new_parts.push(part);
+                }
+            } else {
+                if part.contains('\n') {
+                    quote_types.retain(|quote| MULTI_QUOTES.contains(quote));
+                }
+                new_parts.push(part.clone());
+            }
+        }
+        if fallback_to_repr {
+            // No quotes suit every part: use triple single quotes, and `repr` escapes.
+            quote_types = vec!["'''"];
+            new_parts = parts
+                .into_iter()
+                .map(|(part, is_constant)| match is_constant || legacy {
+                    // `repr` uses single quotes for a string with a double quote.
+                    true => {
+                        let repr = str_repr(&format!("\"{}", part));
+                        repr[2..repr.len() - 1].to_string()
+                    }
+                    false => part,
+                })
+                .collect();
+        }
+        self.write(quote_types[0]);
+        self.write(&new_parts.concat());
+        self.write(quote_types[0]);
+    }
+
+    fn fstring_inner_values(&mut self, values: &[Expr], is_format_spec: bool) {
+        for value in values {
+            self.fstring_inner(value, is_format_spec);
+        }
+    }
+
+    fn fstring_inner(&mut self, node: &Expr, is_format_spec: bool) {
+        match node {
+            Expr::JoinedStr(node) => self.fstring_inner_values(&node.values, is_format_spec),
+            Expr::Constant(Constant {
+                value: Literal::String(value),
+                ..
+            }) => {
+                let mut value = value.replace('{', "{{").replace('}', "}}");
+                if is_format_spec && self.version >= PythonVersion::Py312 {
+                    value = value
+                        .replace('\\', "\\\\")
+                        .replace('\'', "\\'")
+                        .replace('"', "\\\"")
+                        .replace('\n', "\\n");
+                }
+                self.write(&value);
+            }
+            Expr::FormattedValue(node) => self.formatted_value(node),
+            _ => self.fail("Unexpected node inside JoinedStr"),
+        }
+    }
+
+    fn formatted_value(&mut self, node: &FormattedValue) {
+        let mut inner = Unparser::new(self.version);
+        inner.avoid_backslashes = self.version < PythonVersion::Py312;
+        inner.expr(&node.value, Precedence::Test.next());
+        if let Some(error) = inner.error {
+            self.error.get_or_insert(error);
+        }
+        let expr = inner.source;
+        if inner.avoid_backslashes && expr.contains('\\') {
+            self.fail("Unable to avoid backslash in f-string expression part");
+        }
+        self.delimit("{", "}", |u| {
+            // `{{` would be an escaped brace.
+            if expr.starts_with('{') {
+                u.write(" ");
+            }
+            u.write(&expr);
+            if let Some(conversion) = u8::try_from(node.conversion).ok().map(char::from) {
+                u.write(&format!("!{}", conversion));
+            }
+            if let Some(format_spec) = &node.format_spec {
+                u.write(":");
+                u.fstring_inner(format_spec, true);
+            }
+        });
+    }
+}
+
+/// Port of `_str_literal_helper`: `string` escaped as little as possible, with the quotes of
+/// `quote_types` it can be written between, best first.
+fn str_literal_helper(
+    string: &str,
+    quote_types: &[&'static str],
+    escape_special_whitespace: bool,
+) -> (String, Vec<&'static str>) {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        match c {
+            '\n' | '\t' if !escape_special_whitespace => escaped.push(c),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            c if is_printable(c) => escaped.push(c),
+            c if (c as u32) < 0x100 => escaped.push_str(&format!("\\x{:02x}", c as u32)),
+            c if (c as u32) < 0x10000 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push_str(&format!("\\U{:08x}", c as u32)),
+        }
+    }
+    let mut possible: Vec<&'static str> = quote_types
+        .iter()
+        .copied()
+        .filter(|quote| !escaped.contains('\n') || MULTI_QUOTES.contains(quote))
+        .filter(|quote| !escaped.contains(quote))
+        .collect();
+    if possible.is_empty() {
+        // Fall back to `repr`, with a quote of `quote_types` if it has the same character.
+        let repr = str_repr(string);
+        let first = &repr[..1];
+        let quote = quote_types
+            .iter()
+            .copied()
+            .find(|quote| quote.contains(first))
+            .unwrap_or(if first == "'" {
+                SINGLE_QUOTES[0]
+            } else {
+                SINGLE_QUOTES[1]
+            });
+        return (repr[1..repr.len() - 1].to_string(), vec![quote]);
+    }
+    if let Some(last) = escaped.chars().last() {
+        // Prefer '''"''' over """\"""".
+        possible.sort_by_key(|quote| quote.starts_with(last));
+        // A triple quote matching the last character needs it escaped.
+        if possible[0].starts_with(last) {
+            escaped.pop();
+            escaped.push('\\');
+            escaped.push(last);
+        }
+    }
+    (escaped, possible)
+}
+
+fn binop(op: &Operator) -> (&'static str, Precedence) {
+    match op {
+        Operator::Add => ("+", Precedence::Arith),
+        Operator::Sub => ("-", Precedence::Arith),
+        Operator::Mult => ("*", Precedence::Term),
+        Operator::MatMult => ("@", Precedence::Term),
+        Operator::Div => ("/", Precedence::Term),
+        Operator::Mod => ("%", Precedence::Term),
+        Operator::Pow => ("**", Precedence::Power),
+        Operator::LShift => ("<<", Precedence::Shift),
+        Operator::RShift => (">>", Precedence::Shift),
+        Operator::BitOr => ("|", Precedence::BOR),
+        Operator::BitXor => ("^", Precedence::BXor),
+        Operator::BitAnd => ("&", Precedence::BAnd),
+        Operator::FloorDiv => ("//", Precedence::Term),
+    }
+}
+
+fn cmpop(op: &CmpOperator) -> &'static str {
+    match op {
+        CmpOperator::Eq => "==",
+        CmpOperator::NotEq => "!=",
+        CmpOperator::Lt => "<",
+        CmpOperator::LtE => "<=",
+        CmpOperator::Gt => ">",
+        CmpOperator::GtE => ">=",
+        CmpOperator::Is => "is",
+        CmpOperator::IsNot => "is not",
+        CmpOperator::In => "in",
+        CmpOperator::NotIn => "not in",
+    }
+}
+
+impl Unparse for Mod {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        match self {
+            Mod::Module(node) => node.unparse_into(unparser),
+            Mod::Interactive(node) => unparser.stmts(&node.body),
+            Mod::Expression(node) => unparser.expr(&node.body, Precedence::Test),
+            Mod::FunctionType(node) => {
+                unparser.delimit("(", ")", |u| u.exprs(&node.argtypes));
+                unparser.write(" -> ");
+                unparser.expr(&node.returns, Precedence::Test);
+            }
+        }
+    }
+}
+
+impl Unparse for Module {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.type_ignores = self
+            .type_ignores
+            .iter()
+            .map(|ignore| (ignore.lineno as usize, format!("ignore{}", ignore.tag)))
+            .collect();
+        unparser.docstring_and_body(&self.body);
+        unparser.type_ignores.clear();
+    }
+}
+
+impl Unparse for [Stmt] {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.stmts(self);
+    }
+}
+
+impl Unparse for Stmt {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.stmt(self);
+    }
+}
+
+impl Unparse for Expr {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.expr(self, Precedence::Test);
+    }
+}
+
+impl Unparse for Pattern {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.pattern(self, Precedence::Test);
+    }
+}
+
+impl Unparse for TypeParam {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.type_param(self);
+    }
+}
+
+impl Unparse for Arguments {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.arguments(self);
+    }
+}
+
+impl Unparse for Arg {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.arg(self);
+    }
+}
+
+impl Unparse for Keyword {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.keyword(self);
+    }
+}
+
+impl Unparse for Alias {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.alias(self);
+    }
+}
+
+impl Unparse for WithItem {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.with_item(self);
+    }
+}
+
+impl Unparse for Comprehension {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.comprehension(self);
+    }
+}
+
+impl Unparse for ExceptHandler {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.except_handler(self);
+    }
+}
+
+impl Unparse for MatchCase {
+    fn unparse_into(&self, unparser: &mut Unparser) {
+        unparser.match_case(self);
+    }
+}
+
+#[cfg(test)]
+fn assert_round_trip(source: &str, version: PythonVersion) -> String {
+    use crate::dump::dump;
+    use crate::parser::{parse_with_version, Mode};
+
+    let tree = parse_with_version(source, Mode::Exec, PythonVersion::default()).unwrap();
+    let unparsed = unparse_with_version(&tree, version).unwrap();
+    let reparsed = parse_with_version(&unparsed, Mode::Exec, version)
+        .unwrap_or_else(|error| panic!("{:?} unparsed to {:?}: {}", source, unparsed, error));
+    assert_eq!(
+        dump(&reparsed, None, false),
+        dump(&tree, None, false),
+        "{:?} unparsed to {:?}",
+        source,
+        unparsed
+    );
+    unparsed
+}
+
+#[test]
+fn test_unparse() {
+    use crate::parser::{parse, Mode};
+
+    let cases = [
+        ("(a + b) * c - -d ** -e", "(a + b) * c - -d ** (-e)"),
+        ("(a ** b) ** c, a ** (b ** c)", "((a ** b) ** c, a ** b ** c)"),
+        ("not (a and (b or c)) == d", "not (a and (b or c)) == d"),
+        ("(a if b else c) if d else e", "(a if b else c) if d else e"),
+        ("x = (yield)", "x = (yield)"),
+        ("(a := 1)", "(a := 1)"),
+        ("lambda: (yield)", "lambda: (yield)"),
+        ("lambda *a, b=1, **k: 0", "lambda *a, b=1, **k: 0"),
+        ("x[1:2, ::3], x[()], x[a,]", "(x[1:2, ::3], x[()], x[a,])"),
+        ("1 .real, 1.5.real, 1e400, -1e400j", "(1 .real, 1.5.real, 1e309, -1e309j)"),
+        ("{*()}, {**a, 'b': 1}", "({*()}, {**a, 'b': 1})"),
+        ("f'{x!r:>{w}} {{}} {\"a\"}'", "f'{x!r:>{w}} {{}} {'a'}'"),
+        ("f'{ {1}}'", "f'{ {1}}'"),
+        ("x = 'it\\'s \"q\"'", "x = 'it\\'s \"q\"'"),
+        ("u'u', b'\\0'", "(u'u', b'\\x00')"),
+        (
+            "def f(a, /, b=2, *, c, **d) -> int:\n    \"\"\"Doc.\"\"\"\n    return 1",
+            "def f(a, /, b=2, *, c, **d) -> int:\n    \"\"\"Doc.\"\"\"\n    return 1",
+        ),
+        (
+            "@d\nclass C[T: int, *Ts, **P](B, metaclass=M): pass",
+            "@d\nclass C[T: int, *Ts, **P](B, metaclass=M):\n    pass",
+        ),
+        (
+            "type A[T: int=bool, *Ts=*tuple[T], **P=[T]] = T",
+            "type A[T: int = bool, *Ts = *tuple[T], **P = [T]] = T",
+        ),
+        (
+            "if a: pass\nelse:\n    if b: pass\n    else: pass",
+            "if a:\n    pass\nelif b:\n    pass\nelse:\n    pass",
+        ),
+        (
+            "match p:\n    case [1, *_] | {'k': v, **r} if v: pass\n    case C(1, a=(b | c) as d): pass",
+            "match p:\n    case [1, *_] | {'k': v, **r} if v:\n        pass\n    case C(1, a=b | c as d):\n        pass",
+        ),
+        ("try: pass\nexcept* E as e: pass", "try:\n    pass\nexcept* E as e:\n    pass"),
+        ("(x): int = 1", "(x): int = 1"),
+        ("from ..a import b as c", "from ..a import b as c"),
+    ];
+    for (source, expected) in cases {
+        let tree = parse(source, Mode::Exec).unwrap();
+        assert_eq!(unparse(&tree).unwrap(), expected, "{:?}", source);
+        assert_round_trip(source, PythonVersion::default());
+    }
+    let Mod::Expression(expression) = parse("a  +  b", Mode::Eval).unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(unparse(&*expression.body).unwrap(), "a + b");
+    let tree = parse("(int, str) -> None", Mode::FuncType).unwrap();
+    assert_eq!(unparse(&tree).unwrap(), "(int, str) -> None");
+}
+
+#[test]
+fn test_unparse_fstring_versions() {
+    let cases = [
+        (
+            "f\"{'\\n'.join(a)}\"",
+            "f'{'\\n'.join(a)}'",
+            "f'''{\"\"\"\n\"\"\".join(a)}'''",
+        ),
+        ("f'{f\"{y}\"}'", "f'{f'{y}'}'", "f\"{f'{y}'}\""),
+        ("f'{x:{\"}\"}}'", "f'{x:{'}'}}'", "f\"{x:{'}'}}\""),
+        (
+            "f'a\\'b\"c{x}'",
+            "f\"\"\"a'b\"c{x}\"\"\"",
+            "f\"\"\"a'b\"c{x}\"\"\"",
+        ),
+    ];
+    for (source, py313, py311) in cases {
+        assert_eq!(assert_round_trip(source, PythonVersion::Py313), py313);
+        assert_eq!(assert_round_trip(source, PythonVersion::Py311), py311);
+    }
+}
+
+#[test]
+fn test_unparse_errors() {
+    use crate::parser::{parse, parse_recovering, Mode};
+
+    let tree = parse("f\"{'\\\\'.join(a)}\"", Mode::Exec).unwrap();
+    assert_eq!(
+        unparse_with_version(&tree, PythonVersion::Py311)
+            .unwrap_err()
+            .to_string(),
+        "Unable to avoid backslash in f-string expression part"
+    );
+    assert!(unparse(&tree).is_ok());
+    let (module, errors) = parse_recovering("x = (\ny = 1\n", PythonVersion::default());
+    assert!(!errors.is_empty());
+    assert_eq!(
+        unparse(&module).unwrap_err().message,
+        "Error nodes cannot be unparsed"
+    );
+    let raise = Stmt::Raise(Raise {
+        exc: None,
+        cause: Some(Box::new(Expr::Name(Name {
+            id: "e".to_string(),
+            ctx: ExprContext::Load,
+            span: Default::default(),
+        }))),
+        span: Default::default(),
+    });
+    assert_eq!(
+        unparse(&raise).unwrap_err().message,
+        "Node can't use cause without an exception."
+    );
+}
+
+#[test]
+fn test_unparse_round_trip() {
+    let sources = [
+        "x = y = (1, 2)\ndel a, b\nglobal g\nassert x, 'm'\nraise E from c",
+        "async def f():\n    async with a as b, c: await x\n    async for i in y: [j async for j in z if j]",
+        "for (a, b) in c: pass\nelse: pass\nwhile x: break\nelse: continue",
+        "with (open(a) as b, c): pass",
+        "type X[T] = list[T]\nx[*a]\nprint(*a, **k, sep='')",
+        "'\\x7f\\u1234\\U0001f600\\t\\r\\\\', b'\\x00\\'\"', r'\\d'",
+        "'''a\n\"b\"'''\n\"\"\"c'''\"\"\"\n'x\"\"\"'",
+        "f'{x=}', f'{x!a}', f'{x:>10}', f'{x:{y}.{z}}', rf'\\d{x}'",
+        "f'''{x}\n{y}''', f\"{'a'!r}\", f'{3.14:.2f}', f'{a[\"b\"]}'",
+        "(yield)\nx = yield a, b\nawait (yield from g())",
+        "a if (b := c) else lambda: (d, e)",
+        "(-1) ** 2, -(1 ** 2), (~a).b, (a.b)(c), (a, b)[0], (await x) ** 2",
+        "[*a, *b], {**a}, (*a,)\na, *b = c",
+        "class A(B, *C, **D):\n    'doc'\n    x: int\n    def f(self, *args: int): ...",
+        "match x:\n    case (1 | 2) as y: pass\n    case {1: _, 'a': [*r]}: pass\n    case A.B(c=None, d=True): pass\n    case -1 + 2j | 'x' 'y': pass",
+        "if a:\n    pass\nelif b:\n    x\nelif c:\n    y\nelse:\n    z",
+        "try:\n    pass\nexcept (A, B):\n    pass\nexcept:\n    pass\nelse:\n    pass\nfinally:\n    pass",
+        "def f[T](x: T = ..., /) -> T:\n    nonlocal y\n    return",
+        "x[a:b], x[:], x[::], x[a, b:c], x[lambda: 1]",
+        "not not a, a < b < c, a is not b, a not in b, (a < b) < c",
+    ];
+    for source in sources {
+        for version in [PythonVersion::Py311, PythonVersion::Py313] {
+            if version == PythonVersion::Py311 && source.contains("[T]") {
+                continue;
+            }
+            assert_round_trip(source, version);
+        }
+    }
+}
+
+#[test]
+fn test_unparse_random_expressions() {
+    // A linear congruential generator, so that failures reproduce.
+    struct Random(u64);
+    impl Random {
+        fn below(&mut self, n: u64) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (self.0 >> 33) % n
+        }
+    }
+    /// A random expression, fully parenthesized so that the AST has every shape.
+    fn expression(random: &mut Random, depth: u32) -> String {
+        const ATOMS: [&str; 8] = ["a", "1", "2.5", "'s'", "None", "f'{b}'", "...", "x.y"];
+        const BINARY: [&str; 18] = [
+            "+", "-", "*", "/", "//", "%", "**", "@", "<<", ">>", "|", "^", "&", "and", "or", "<",
+            "is not", "not in",
+        ];
+        const UNARY: [&str; 4] = ["-", "+", "~", "not "];
+        if depth == 0 {
+            return ATOMS[random.below(ATOMS.len() as u64) as usize].to_string();
+        }
+        let mut sub = || expression(random, depth - 1);
+        let (a, b, c) = (sub(), sub(), sub());
+        match random.below(14) {
+            0..=4 => format!("({} {} {})", a, BINARY[random.below(18) as usize], b),
+            5 => format!("({}{})", UNARY[random.below(4) as usize], a),
+            6 => format!("({} if {} else {})", a, b, c),
+            7 => format!("(lambda q: {})", a),
+            8 => format!("({})({}, *{}, k={})", a, b, c, a),
+            9 => format!("({})[{}:{}]", a, b, c),
+            10 => format!("({}).attr", a),
+            11 => format!("[{} for v in {} if {}]", a, b, c),
+            12 => format!("(w := {})", a),
+            _ => format!("f'{{{}!r:>{{{}}}}}'", a, b),
+        }
+    }
+    let mut random = Random(1);
+    for _ in 0..500 {
+        let depth = random.below(4) as u32 + 1;
+        let source = expression(&mut random, depth);
+        assert_round_trip(&source, PythonVersion::Py313);
+    }
+}
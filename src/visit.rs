@@ -0,0 +1,2827 @@
+// Generated by `pragrat generate ast --mode enum`. Do not edit.
+use crate::ast::*;
+
+/// Walks a tree by reference, like Python's `ast.NodeVisitor`. Every method defaults to
+/// visiting the node's children, so a pass only overrides the nodes it cares about.
+pub trait Visitor {
+	fn visit_mod(&mut self, node: &Mod) {
+		walk_mod(self, node);
+	}
+	fn visit_module_mod(&mut self, node: &Module) {
+		walk_module_mod(self, node);
+	}
+	fn visit_interactive_mod(&mut self, node: &Interactive) {
+		walk_interactive_mod(self, node);
+	}
+	fn visit_expression_mod(&mut self, node: &Expression) {
+		walk_expression_mod(self, node);
+	}
+	fn visit_functiontype_mod(&mut self, node: &FunctionType) {
+		walk_functiontype_mod(self, node);
+	}
+	fn visit_stmt(&mut self, node: &Stmt) {
+		walk_stmt(self, node);
+	}
+	fn visit_functiondef_stmt(&mut self, node: &FunctionDef) {
+		walk_functiondef_stmt(self, node);
+	}
+	fn visit_asyncfunctiondef_stmt(&mut self, node: &AsyncFunctionDef) {
+		walk_asyncfunctiondef_stmt(self, node);
+	}
+	fn visit_classdef_stmt(&mut self, node: &ClassDef) {
+		walk_classdef_stmt(self, node);
+	}
+	fn visit_return_stmt(&mut self, node: &Return) {
+		walk_return_stmt(self, node);
+	}
+	fn visit_delete_stmt(&mut self, node: &Delete) {
+		walk_delete_stmt(self, node);
+	}
+	fn visit_assign_stmt(&mut self, node: &Assign) {
+		walk_assign_stmt(self, node);
+	}
+	fn visit_typealias_stmt(&mut self, node: &TypeAlias) {
+		walk_typealias_stmt(self, node);
+	}
+	fn visit_augassign_stmt(&mut self, node: &AugAssign) {
+		walk_augassign_stmt(self, node);
+	}
+	fn visit_annassign_stmt(&mut self, node: &AnnAssign) {
+		walk_annassign_stmt(self, node);
+	}
+	fn visit_for_stmt(&mut self, node: &For) {
+		walk_for_stmt(self, node);
+	}
+	fn visit_asyncfor_stmt(&mut self, node: &AsyncFor) {
+		walk_asyncfor_stmt(self, node);
+	}
+	fn visit_while_stmt(&mut self, node: &While) {
+		walk_while_stmt(self, node);
+	}
+	fn visit_if_stmt(&mut self, node: &If) {
+		walk_if_stmt(self, node);
+	}
+	fn visit_with_stmt(&mut self, node: &With) {
+		walk_with_stmt(self, node);
+	}
+	fn visit_asyncwith_stmt(&mut self, node: &AsyncWith) {
+		walk_asyncwith_stmt(self, node);
+	}
+	fn visit_match_stmt(&mut self, node: &Match) {
+		walk_match_stmt(self, node);
+	}
+	fn visit_raise_stmt(&mut self, node: &Raise) {
+		walk_raise_stmt(self, node);
+	}
+	fn visit_try_stmt(&mut self, node: &Try) {
+		walk_try_stmt(self, node);
+	}
+	fn visit_trystar_stmt(&mut self, node: &TryStar) {
+		walk_trystar_stmt(self, node);
+	}
+	fn visit_assert_stmt(&mut self, node: &Assert) {
+		walk_assert_stmt(self, node);
+	}
+	fn visit_import_stmt(&mut self, node: &Import) {
+		walk_import_stmt(self, node);
+	}
+	fn visit_importfrom_stmt(&mut self, node: &ImportFrom) {
+		walk_importfrom_stmt(self, node);
+	}
+	fn visit_global_stmt(&mut self, node: &Global) {
+		walk_global_stmt(self, node);
+	}
+	fn visit_nonlocal_stmt(&mut self, node: &Nonlocal) {
+		walk_nonlocal_stmt(self, node);
+	}
+	fn visit_exprstmt_stmt(&mut self, node: &ExprStmt) {
+		walk_exprstmt_stmt(self, node);
+	}
+	fn visit_pass_stmt(&mut self, node: &Pass) {
+		walk_pass_stmt(self, node);
+	}
+	fn visit_break_stmt(&mut self, node: &Break) {
+		walk_break_stmt(self, node);
+	}
+	fn visit_continue_stmt(&mut self, node: &Continue) {
+		walk_continue_stmt(self, node);
+	}
+	fn visit_errorstmt_stmt(&mut self, node: &ErrorStmt) {
+		walk_errorstmt_stmt(self, node);
+	}
+	fn visit_expr(&mut self, node: &Expr) {
+		walk_expr(self, node);
+	}
+	fn visit_boolop_expr(&mut self, node: &BoolOp) {
+		walk_boolop_expr(self, node);
+	}
+	fn visit_namedexpr_expr(&mut self, node: &NamedExpr) {
+		walk_namedexpr_expr(self, node);
+	}
+	fn visit_binop_expr(&mut self, node: &BinOp) {
+		walk_binop_expr(self, node);
+	}
+	fn visit_unaryop_expr(&mut self, node: &UnaryOp) {
+		walk_unaryop_expr(self, node);
+	}
+	fn visit_lambda_expr(&mut self, node: &Lambda) {
+		walk_lambda_expr(self, node);
+	}
+	fn visit_ifexp_expr(&mut self, node: &IfExp) {
+		walk_ifexp_expr(self, node);
+	}
+	fn visit_dict_expr(&mut self, node: &Dict) {
+		walk_dict_expr(self, node);
+	}
+	fn visit_set_expr(&mut self, node: &Set) {
+		walk_set_expr(self, node);
+	}
+	fn visit_listcomp_expr(&mut self, node: &ListComp) {
+		walk_listcomp_expr(self, node);
+	}
+	fn visit_setcomp_expr(&mut self, node: &SetComp) {
+		walk_setcomp_expr(self, node);
+	}
+	fn visit_dictcomp_expr(&mut self, node: &DictComp) {
+		walk_dictcomp_expr(self, node);
+	}
+	fn visit_generatorexp_expr(&mut self, node: &GeneratorExp) {
+		walk_generatorexp_expr(self, node);
+	}
+	fn visit_await_expr(&mut self, node: &Await) {
+		walk_await_expr(self, node);
+	}
+	fn visit_yield_expr(&mut self, node: &Yield) {
+		walk_yield_expr(self, node);
+	}
+	fn visit_yieldfrom_expr(&mut self, node: &YieldFrom) {
+		walk_yieldfrom_expr(self, node);
+	}
+	fn visit_compare_expr(&mut self, node: &Compare) {
+		walk_compare_expr(self, node);
+	}
+	fn visit_call_expr(&mut self, node: &Call) {
+		walk_call_expr(self, node);
+	}
+	fn visit_formattedvalue_expr(&mut self, node: &FormattedValue) {
+		walk_formattedvalue_expr(self, node);
+	}
+	fn visit_joinedstr_expr(&mut self, node: &JoinedStr) {
+		walk_joinedstr_expr(self, node);
+	}
+	fn visit_constant_expr(&mut self, node: &Constant) {
+		walk_constant_expr(self, node);
+	}
+	fn visit_attribute_expr(&mut self, node: &Attribute) {
+		walk_attribute_expr(self, node);
+	}
+	fn visit_subscript_expr(&mut self, node: &Subscript) {
+		walk_subscript_expr(self, node);
+	}
+	fn visit_starred_expr(&mut self, node: &Starred) {
+		walk_starred_expr(self, node);
+	}
+	fn visit_name_expr(&mut self, node: &Name) {
+		walk_name_expr(self, node);
+	}
+	fn visit_list_expr(&mut self, node: &List) {
+		walk_list_expr(self, node);
+	}
+	fn visit_tuple_expr(&mut self, node: &Tuple) {
+		walk_tuple_expr(self, node);
+	}
+	fn visit_slice_expr(&mut self, node: &Slice) {
+		walk_slice_expr(self, node);
+	}
+	fn visit_errorexpr_expr(&mut self, node: &ErrorExpr) {
+		walk_errorexpr_expr(self, node);
+	}
+	fn visit_pattern(&mut self, node: &Pattern) {
+		walk_pattern(self, node);
+	}
+	fn visit_matchvalue_pattern(&mut self, node: &MatchValue) {
+		walk_matchvalue_pattern(self, node);
+	}
+	fn visit_matchsingleton_pattern(&mut self, node: &MatchSingleton) {
+		walk_matchsingleton_pattern(self, node);
+	}
+	fn visit_matchsequence_pattern(&mut self, node: &MatchSequence) {
+		walk_matchsequence_pattern(self, node);
+	}
+	fn visit_matchmapping_pattern(&mut self, node: &MatchMapping) {
+		walk_matchmapping_pattern(self, node);
+	}
+	fn visit_matchclass_pattern(&mut self, node: &MatchClass) {
+		walk_matchclass_pattern(self, node);
+	}
+	fn visit_matchstar_pattern(&mut self, node: &MatchStar) {
+		walk_matchstar_pattern(self, node);
+	}
+	fn visit_matchas_pattern(&mut self, node: &MatchAs) {
+		walk_matchas_pattern(self, node);
+	}
+	fn visit_matchor_pattern(&mut self, node: &MatchOr) {
+		walk_matchor_pattern(self, node);
+	}
+	fn visit_typeparam(&mut self, node: &TypeParam) {
+		walk_typeparam(self, node);
+	}
+	fn visit_typevar_typeparam(&mut self, node: &TypeVar) {
+		walk_typevar_typeparam(self, node);
+	}
+	fn visit_paramspec_typeparam(&mut self, node: &ParamSpec) {
+		walk_paramspec_typeparam(self, node);
+	}
+	fn visit_typevartuple_typeparam(&mut self, node: &TypeVarTuple) {
+		walk_typevartuple_typeparam(self, node);
+	}
+	fn visit_comprehension(&mut self, node: &Comprehension) {
+		walk_comprehension(self, node);
+	}
+	fn visit_arguments(&mut self, node: &Arguments) {
+		walk_arguments(self, node);
+	}
+	fn visit_arg(&mut self, node: &Arg) {
+		walk_arg(self, node);
+	}
+	fn visit_keyword(&mut self, node: &Keyword) {
+		walk_keyword(self, node);
+	}
+	fn visit_excepthandler(&mut self, node: &ExceptHandler) {
+		walk_excepthandler(self, node);
+	}
+	fn visit_alias(&mut self, node: &Alias) {
+		walk_alias(self, node);
+	}
+	fn visit_withitem(&mut self, node: &WithItem) {
+		walk_withitem(self, node);
+	}
+	fn visit_matchcase(&mut self, node: &MatchCase) {
+		walk_matchcase(self, node);
+	}
+	fn visit_typeignore(&mut self, node: &TypeIgnore) {
+		walk_typeignore(self, node);
+	}
+}
+
+/// Walks a tree by mutable reference so that nodes can be edited in place.
+pub trait VisitorMut {
+	fn visit_mod_mut(&mut self, node: &mut Mod) {
+		walk_mod_mut(self, node);
+	}
+	fn visit_module_mod_mut(&mut self, node: &mut Module) {
+		walk_module_mod_mut(self, node);
+	}
+	fn visit_interactive_mod_mut(&mut self, node: &mut Interactive) {
+		walk_interactive_mod_mut(self, node);
+	}
+	fn visit_expression_mod_mut(&mut self, node: &mut Expression) {
+		walk_expression_mod_mut(self, node);
+	}
+	fn visit_functiontype_mod_mut(&mut self, node: &mut FunctionType) {
+		walk_functiontype_mod_mut(self, node);
+	}
+	fn visit_stmt_mut(&mut self, node: &mut Stmt) {
+		walk_stmt_mut(self, node);
+	}
+	fn visit_functiondef_stmt_mut(&mut self, node: &mut FunctionDef) {
+		walk_functiondef_stmt_mut(self, node);
+	}
+	fn visit_asyncfunctiondef_stmt_mut(&mut self, node: &mut AsyncFunctionDef) {
+		walk_asyncfunctiondef_stmt_mut(self, node);
+	}
+	fn visit_classdef_stmt_mut(&mut self, node: &mut ClassDef) {
+		walk_classdef_stmt_mut(self, node);
+	}
+	fn visit_return_stmt_mut(&mut self, node: &mut Return) {
+		walk_return_stmt_mut(self, node);
+	}
+	fn visit_delete_stmt_mut(&mut self, node: &mut Delete) {
+		walk_delete_stmt_mut(self, node);
+	}
+	fn visit_assign_stmt_mut(&mut self, node: &mut Assign) {
+		walk_assign_stmt_mut(self, node);
+	}
+	fn visit_typealias_stmt_mut(&mut self, node: &mut TypeAlias) {
+		walk_typealias_stmt_mut(self, node);
+	}
+	fn visit_augassign_stmt_mut(&mut self, node: &mut AugAssign) {
+		walk_augassign_stmt_mut(self, node);
+	}
+	fn visit_annassign_stmt_mut(&mut self, node: &mut AnnAssign) {
+		walk_annassign_stmt_mut(self, node);
+	}
+	fn visit_for_stmt_mut(&mut self, node: &mut For) {
+		walk_for_stmt_mut(self, node);
+	}
+	fn visit_asyncfor_stmt_mut(&mut self, node: &mut AsyncFor) {
+		walk_asyncfor_stmt_mut(self, node);
+	}
+	fn visit_while_stmt_mut(&mut self, node: &mut While) {
+		walk_while_stmt_mut(self, node);
+	}
+	fn visit_if_stmt_mut(&mut self, node: &mut If) {
+		walk_if_stmt_mut(self, node);
+	}
+	fn visit_with_stmt_mut(&mut self, node: &mut With) {
+		walk_with_stmt_mut(self, node);
+	}
+	fn visit_asyncwith_stmt_mut(&mut self, node: &mut AsyncWith) {
+		walk_asyncwith_stmt_mut(self, node);
+	}
+	fn visit_match_stmt_mut(&mut self, node: &mut Match) {
+		walk_match_stmt_mut(self, node);
+	}
+	fn visit_raise_stmt_mut(&mut self, node: &mut Raise) {
+		walk_raise_stmt_mut(self, node);
+	}
+	fn visit_try_stmt_mut(&mut self, node: &mut Try) {
+		walk_try_stmt_mut(self, node);
+	}
+	fn visit_trystar_stmt_mut(&mut self, node: &mut TryStar) {
+		walk_trystar_stmt_mut(self, node);
+	}
+	fn visit_assert_stmt_mut(&mut self, node: &mut Assert) {
+		walk_assert_stmt_mut(self, node);
+	}
+	fn visit_import_stmt_mut(&mut self, node: &mut Import) {
+		walk_import_stmt_mut(self, node);
+	}
+	fn visit_importfrom_stmt_mut(&mut self, node: &mut ImportFrom) {
+		walk_importfrom_stmt_mut(self, node);
+	}
+	fn visit_global_stmt_mut(&mut self, node: &mut Global) {
+		walk_global_stmt_mut(self, node);
+	}
+	fn visit_nonlocal_stmt_mut(&mut self, node: &mut Nonlocal) {
+		walk_nonlocal_stmt_mut(self, node);
+	}
+	fn visit_exprstmt_stmt_mut(&mut self, node: &mut ExprStmt) {
+		walk_exprstmt_stmt_mut(self, node);
+	}
+	fn visit_pass_stmt_mut(&mut self, node: &mut Pass) {
+		walk_pass_stmt_mut(self, node);
+	}
+	fn visit_break_stmt_mut(&mut self, node: &mut Break) {
+		walk_break_stmt_mut(self, node);
+	}
+	fn visit_continue_stmt_mut(&mut self, node: &mut Continue) {
+		walk_continue_stmt_mut(self, node);
+	}
+	fn visit_errorstmt_stmt_mut(&mut self, node: &mut ErrorStmt) {
+		walk_errorstmt_stmt_mut(self, node);
+	}
+	fn visit_expr_mut(&mut self, node: &mut Expr) {
+		walk_expr_mut(self, node);
+	}
+	fn visit_boolop_expr_mut(&mut self, node: &mut BoolOp) {
+		walk_boolop_expr_mut(self, node);
+	}
+	fn visit_namedexpr_expr_mut(&mut self, node: &mut NamedExpr) {
+		walk_namedexpr_expr_mut(self, node);
+	}
+	fn visit_binop_expr_mut(&mut self, node: &mut BinOp) {
+		walk_binop_expr_mut(self, node);
+	}
+	fn visit_unaryop_expr_mut(&mut self, node: &mut UnaryOp) {
+		walk_unaryop_expr_mut(self, node);
+	}
+	fn visit_lambda_expr_mut(&mut self, node: &mut Lambda) {
+		walk_lambda_expr_mut(self, node);
+	}
+	fn visit_ifexp_expr_mut(&mut self, node: &mut IfExp) {
+		walk_ifexp_expr_mut(self, node);
+	}
+	fn visit_dict_expr_mut(&mut self, node: &mut Dict) {
+		walk_dict_expr_mut(self, node);
+	}
+	fn visit_set_expr_mut(&mut self, node: &mut Set) {
+		walk_set_expr_mut(self, node);
+	}
+	fn visit_listcomp_expr_mut(&mut self, node: &mut ListComp) {
+		walk_listcomp_expr_mut(self, node);
+	}
+	fn visit_setcomp_expr_mut(&mut self, node: &mut SetComp) {
+		walk_setcomp_expr_mut(self, node);
+	}
+	fn visit_dictcomp_expr_mut(&mut self, node: &mut DictComp) {
+		walk_dictcomp_expr_mut(self, node);
+	}
+	fn visit_generatorexp_expr_mut(&mut self, node: &mut GeneratorExp) {
+		walk_generatorexp_expr_mut(self, node);
+	}
+	fn visit_await_expr_mut(&mut self, node: &mut Await) {
+		walk_await_expr_mut(self, node);
+	}
+	fn visit_yield_expr_mut(&mut self, node: &mut Yield) {
+		walk_yield_expr_mut(self, node);
+	}
+	fn visit_yieldfrom_expr_mut(&mut self, node: &mut YieldFrom) {
+		walk_yieldfrom_expr_mut(self, node);
+	}
+	fn visit_compare_expr_mut(&mut self, node: &mut Compare) {
+		walk_compare_expr_mut(self, node);
+	}
+	fn visit_call_expr_mut(&mut self, node: &mut Call) {
+		walk_call_expr_mut(self, node);
+	}
+	fn visit_formattedvalue_expr_mut(&mut self, node: &mut FormattedValue) {
+		walk_formattedvalue_expr_mut(self, node);
+	}
+	fn visit_joinedstr_expr_mut(&mut self, node: &mut JoinedStr) {
+		walk_joinedstr_expr_mut(self, node);
+	}
+	fn visit_constant_expr_mut(&mut self, node: &mut Constant) {
+		walk_constant_expr_mut(self, node);
+	}
+	fn visit_attribute_expr_mut(&mut self, node: &mut Attribute) {
+		walk_attribute_expr_mut(self, node);
+	}
+	fn visit_subscript_expr_mut(&mut self, node: &mut Subscript) {
+		walk_subscript_expr_mut(self, node);
+	}
+	fn visit_starred_expr_mut(&mut self, node: &mut Starred) {
+		walk_starred_expr_mut(self, node);
+	}
+	fn visit_name_expr_mut(&mut self, node: &mut Name) {
+		walk_name_expr_mut(self, node);
+	}
+	fn visit_list_expr_mut(&mut self, node: &mut List) {
+		walk_list_expr_mut(self, node);
+	}
+	fn visit_tuple_expr_mut(&mut self, node: &mut Tuple) {
+		walk_tuple_expr_mut(self, node);
+	}
+	fn visit_slice_expr_mut(&mut self, node: &mut Slice) {
+		walk_slice_expr_mut(self, node);
+	}
+	fn visit_errorexpr_expr_mut(&mut self, node: &mut ErrorExpr) {
+		walk_errorexpr_expr_mut(self, node);
+	}
+	fn visit_pattern_mut(&mut self, node: &mut Pattern) {
+		walk_pattern_mut(self, node);
+	}
+	fn visit_matchvalue_pattern_mut(&mut self, node: &mut MatchValue) {
+		walk_matchvalue_pattern_mut(self, node);
+	}
+	fn visit_matchsingleton_pattern_mut(&mut self, node: &mut MatchSingleton) {
+		walk_matchsingleton_pattern_mut(self, node);
+	}
+	fn visit_matchsequence_pattern_mut(&mut self, node: &mut MatchSequence) {
+		walk_matchsequence_pattern_mut(self, node);
+	}
+	fn visit_matchmapping_pattern_mut(&mut self, node: &mut MatchMapping) {
+		walk_matchmapping_pattern_mut(self, node);
+	}
+	fn visit_matchclass_pattern_mut(&mut self, node: &mut MatchClass) {
+		walk_matchclass_pattern_mut(self, node);
+	}
+	fn visit_matchstar_pattern_mut(&mut self, node: &mut MatchStar) {
+		walk_matchstar_pattern_mut(self, node);
+	}
+	fn visit_matchas_pattern_mut(&mut self, node: &mut MatchAs) {
+		walk_matchas_pattern_mut(self, node);
+	}
+	fn visit_matchor_pattern_mut(&mut self, node: &mut MatchOr) {
+		walk_matchor_pattern_mut(self, node);
+	}
+	fn visit_typeparam_mut(&mut self, node: &mut TypeParam) {
+		walk_typeparam_mut(self, node);
+	}
+	fn visit_typevar_typeparam_mut(&mut self, node: &mut TypeVar) {
+		walk_typevar_typeparam_mut(self, node);
+	}
+	fn visit_paramspec_typeparam_mut(&mut self, node: &mut ParamSpec) {
+		walk_paramspec_typeparam_mut(self, node);
+	}
+	fn visit_typevartuple_typeparam_mut(&mut self, node: &mut TypeVarTuple) {
+		walk_typevartuple_typeparam_mut(self, node);
+	}
+	fn visit_comprehension_mut(&mut self, node: &mut Comprehension) {
+		walk_comprehension_mut(self, node);
+	}
+	fn visit_arguments_mut(&mut self, node: &mut Arguments) {
+		walk_arguments_mut(self, node);
+	}
+	fn visit_arg_mut(&mut self, node: &mut Arg) {
+		walk_arg_mut(self, node);
+	}
+	fn visit_keyword_mut(&mut self, node: &mut Keyword) {
+		walk_keyword_mut(self, node);
+	}
+	fn visit_excepthandler_mut(&mut self, node: &mut ExceptHandler) {
+		walk_excepthandler_mut(self, node);
+	}
+	fn visit_alias_mut(&mut self, node: &mut Alias) {
+		walk_alias_mut(self, node);
+	}
+	fn visit_withitem_mut(&mut self, node: &mut WithItem) {
+		walk_withitem_mut(self, node);
+	}
+	fn visit_matchcase_mut(&mut self, node: &mut MatchCase) {
+		walk_matchcase_mut(self, node);
+	}
+	fn visit_typeignore_mut(&mut self, node: &mut TypeIgnore) {
+		walk_typeignore_mut(self, node);
+	}
+}
+
+/// Rebuilds a tree by value, like Python's `ast.NodeTransformer`. The node returned by a
+/// method replaces the one it was given.
+pub trait Fold {
+	fn fold_mod(&mut self, node: Mod) -> Mod {
+		fold_mod(self, node)
+	}
+	fn fold_module_mod(&mut self, node: Module) -> Mod {
+		fold_module_mod(self, node).into()
+	}
+	fn fold_interactive_mod(&mut self, node: Interactive) -> Mod {
+		fold_interactive_mod(self, node).into()
+	}
+	fn fold_expression_mod(&mut self, node: Expression) -> Mod {
+		fold_expression_mod(self, node).into()
+	}
+	fn fold_functiontype_mod(&mut self, node: FunctionType) -> Mod {
+		fold_functiontype_mod(self, node).into()
+	}
+	fn fold_stmt(&mut self, node: Stmt) -> Stmt {
+		fold_stmt(self, node)
+	}
+	fn fold_functiondef_stmt(&mut self, node: FunctionDef) -> Stmt {
+		fold_functiondef_stmt(self, node).into()
+	}
+	fn fold_asyncfunctiondef_stmt(&mut self, node: AsyncFunctionDef) -> Stmt {
+		fold_asyncfunctiondef_stmt(self, node).into()
+	}
+	fn fold_classdef_stmt(&mut self, node: ClassDef) -> Stmt {
+		fold_classdef_stmt(self, node).into()
+	}
+	fn fold_return_stmt(&mut self, node: Return) -> Stmt {
+		fold_return_stmt(self, node).into()
+	}
+	fn fold_delete_stmt(&mut self, node: Delete) -> Stmt {
+		fold_delete_stmt(self, node).into()
+	}
+	fn fold_assign_stmt(&mut self, node: Assign) -> Stmt {
+		fold_assign_stmt(self, node).into()
+	}
+	fn fold_typealias_stmt(&mut self, node: TypeAlias) -> Stmt {
+		fold_typealias_stmt(self, node).into()
+	}
+	fn fold_augassign_stmt(&mut self, node: AugAssign) -> Stmt {
+		fold_augassign_stmt(self, node).into()
+	}
+	fn fold_annassign_stmt(&mut self, node: AnnAssign) -> Stmt {
+		fold_annassign_stmt(self, node).into()
+	}
+	fn fold_for_stmt(&mut self, node: For) -> Stmt {
+		fold_for_stmt(self, node).into()
+	}
+	fn fold_asyncfor_stmt(&mut self, node: AsyncFor) -> Stmt {
+		fold_asyncfor_stmt(self, node).into()
+	}
+	fn fold_while_stmt(&mut self, node: While) -> Stmt {
+		fold_while_stmt(self, node).into()
+	}
+	fn fold_if_stmt(&mut self, node: If) -> Stmt {
+		fold_if_stmt(self, node).into()
+	}
+	fn fold_with_stmt(&mut self, node: With) -> Stmt {
+		fold_with_stmt(self, node).into()
+	}
+	fn fold_asyncwith_stmt(&mut self, node: AsyncWith) -> Stmt {
+		fold_asyncwith_stmt(self, node).into()
+	}
+	fn fold_match_stmt(&mut self, node: Match) -> Stmt {
+		fold_match_stmt(self, node).into()
+	}
+	fn fold_raise_stmt(&mut self, node: Raise) -> Stmt {
+		fold_raise_stmt(self, node).into()
+	}
+	fn fold_try_stmt(&mut self, node: Try) -> Stmt {
+		fold_try_stmt(self, node).into()
+	}
+	fn fold_trystar_stmt(&mut self, node: TryStar) -> Stmt {
+		fold_trystar_stmt(self, node).into()
+	}
+	fn fold_assert_stmt(&mut self, node: Assert) -> Stmt {
+		fold_assert_stmt(self, node).into()
+	}
+	fn fold_import_stmt(&mut self, node: Import) -> Stmt {
+		fold_import_stmt(self, node).into()
+	}
+	fn fold_importfrom_stmt(&mut self, node: ImportFrom) -> Stmt {
+		fold_importfrom_stmt(self, node).into()
+	}
+	fn fold_global_stmt(&mut self, node: Global) -> Stmt {
+		fold_global_stmt(self, node).into()
+	}
+	fn fold_nonlocal_stmt(&mut self, node: Nonlocal) -> Stmt {
+		fold_nonlocal_stmt(self, node).into()
+	}
+	fn fold_exprstmt_stmt(&mut self, node: ExprStmt) -> Stmt {
+		fold_exprstmt_stmt(self, node).into()
+	}
+	fn fold_pass_stmt(&mut self, node: Pass) -> Stmt {
+		fold_pass_stmt(self, node).into()
+	}
+	fn fold_break_stmt(&mut self, node: Break) -> Stmt {
+		fold_break_stmt(self, node).into()
+	}
+	fn fold_continue_stmt(&mut self, node: Continue) -> Stmt {
+		fold_continue_stmt(self, node).into()
+	}
+	fn fold_errorstmt_stmt(&mut self, node: ErrorStmt) -> Stmt {
+		fold_errorstmt_stmt(self, node).into()
+	}
+	fn fold_expr(&mut self, node: Expr) -> Expr {
+		fold_expr(self, node)
+	}
+	fn fold_boolop_expr(&mut self, node: BoolOp) -> Expr {
+		fold_boolop_expr(self, node).into()
+	}
+	fn fold_namedexpr_expr(&mut self, node: NamedExpr) -> Expr {
+		fold_namedexpr_expr(self, node).into()
+	}
+	fn fold_binop_expr(&mut self, node: BinOp) -> Expr {
+		fold_binop_expr(self, node).into()
+	}
+	fn fold_unaryop_expr(&mut self, node: UnaryOp) -> Expr {
+		fold_unaryop_expr(self, node).into()
+	}
+	fn fold_lambda_expr(&mut self, node: Lambda) -> Expr {
+		fold_lambda_expr(self, node).into()
+	}
+	fn fold_ifexp_expr(&mut self, node: IfExp) -> Expr {
+		fold_ifexp_expr(self, node).into()
+	}
+	fn fold_dict_expr(&mut self, node: Dict) -> Expr {
+		fold_dict_expr(self, node).into()
+	}
+	fn fold_set_expr(&mut self, node: Set) -> Expr {
+		fold_set_expr(self, node).into()
+	}
+	fn fold_listcomp_expr(&mut self, node: ListComp) -> Expr {
+		fold_listcomp_expr(self, node).into()
+	}
+	fn fold_setcomp_expr(&mut self, node: SetComp) -> Expr {
+		fold_setcomp_expr(self, node).into()
+	}
+	fn fold_dictcomp_expr(&mut self, node: DictComp) -> Expr {
+		fold_dictcomp_expr(self, node).into()
+	}
+	fn fold_generatorexp_expr(&mut self, node: GeneratorExp) -> Expr {
+		fold_generatorexp_expr(self, node).into()
+	}
+	fn fold_await_expr(&mut self, node: Await) -> Expr {
+		fold_await_expr(self, node).into()
+	}
+	fn fold_yield_expr(&mut self, node: Yield) -> Expr {
+		fold_yield_expr(self, node).into()
+	}
+	fn fold_yieldfrom_expr(&mut self, node: YieldFrom) -> Expr {
+		fold_yieldfrom_expr(self, node).into()
+	}
+	fn fold_compare_expr(&mut self, node: Compare) -> Expr {
+		fold_compare_expr(self, node).into()
+	}
+	fn fold_call_expr(&mut self, node: Call) -> Expr {
+		fold_call_expr(self, node).into()
+	}
+	fn fold_formattedvalue_expr(&mut self, node: FormattedValue) -> Expr {
+		fold_formattedvalue_expr(self, node).into()
+	}
+	fn fold_joinedstr_expr(&mut self, node: JoinedStr) -> Expr {
+		fold_joinedstr_expr(self, node).into()
+	}
+	fn fold_constant_expr(&mut self, node: Constant) -> Expr {
+		fold_constant_expr(self, node).into()
+	}
+	fn fold_attribute_expr(&mut self, node: Attribute) -> Expr {
+		fold_attribute_expr(self, node).into()
+	}
+	fn fold_subscript_expr(&mut self, node: Subscript) -> Expr {
+		fold_subscript_expr(self, node).into()
+	}
+	fn fold_starred_expr(&mut self, node: Starred) -> Expr {
+		fold_starred_expr(self, node).into()
+	}
+	fn fold_name_expr(&mut self, node: Name) -> Expr {
+		fold_name_expr(self, node).into()
+	}
+	fn fold_list_expr(&mut self, node: List) -> Expr {
+		fold_list_expr(self, node).into()
+	}
+	fn fold_tuple_expr(&mut self, node: Tuple) -> Expr {
+		fold_tuple_expr(self, node).into()
+	}
+	fn fold_slice_expr(&mut self, node: Slice) -> Expr {
+		fold_slice_expr(self, node).into()
+	}
+	fn fold_errorexpr_expr(&mut self, node: ErrorExpr) -> Expr {
+		fold_errorexpr_expr(self, node).into()
+	}
+	fn fold_pattern(&mut self, node: Pattern) -> Pattern {
+		fold_pattern(self, node)
+	}
+	fn fold_matchvalue_pattern(&mut self, node: MatchValue) -> Pattern {
+		fold_matchvalue_pattern(self, node).into()
+	}
+	fn fold_matchsingleton_pattern(&mut self, node: MatchSingleton) -> Pattern {
+		fold_matchsingleton_pattern(self, node).into()
+	}
+	fn fold_matchsequence_pattern(&mut self, node: MatchSequence) -> Pattern {
+		fold_matchsequence_pattern(self, node).into()
+	}
+	fn fold_matchmapping_pattern(&mut self, node: MatchMapping) -> Pattern {
+		fold_matchmapping_pattern(self, node).into()
+	}
+	fn fold_matchclass_pattern(&mut self, node: MatchClass) -> Pattern {
+		fold_matchclass_pattern(self, node).into()
+	}
+	fn fold_matchstar_pattern(&mut self, node: MatchStar) -> Pattern {
+		fold_matchstar_pattern(self, node).into()
+	}
+	fn fold_matchas_pattern(&mut self, node: MatchAs) -> Pattern {
+		fold_matchas_pattern(self, node).into()
+	}
+	fn fold_matchor_pattern(&mut self, node: MatchOr) -> Pattern {
+		fold_matchor_pattern(self, node).into()
+	}
+	fn fold_typeparam(&mut self, node: TypeParam) -> TypeParam {
+		fold_typeparam(self, node)
+	}
+	fn fold_typevar_typeparam(&mut self, node: TypeVar) -> TypeParam {
+		fold_typevar_typeparam(self, node).into()
+	}
+	fn fold_paramspec_typeparam(&mut self, node: ParamSpec) -> TypeParam {
+		fold_paramspec_typeparam(self, node).into()
+	}
+	fn fold_typevartuple_typeparam(&mut self, node: TypeVarTuple) -> TypeParam {
+		fold_typevartuple_typeparam(self, node).into()
+	}
+	fn fold_comprehension(&mut self, node: Comprehension) -> Comprehension {
+		fold_comprehension(self, node)
+	}
+	fn fold_arguments(&mut self, node: Arguments) -> Arguments {
+		fold_arguments(self, node)
+	}
+	fn fold_arg(&mut self, node: Arg) -> Arg {
+		fold_arg(self, node)
+	}
+	fn fold_keyword(&mut self, node: Keyword) -> Keyword {
+		fold_keyword(self, node)
+	}
+	fn fold_excepthandler(&mut self, node: ExceptHandler) -> ExceptHandler {
+		fold_excepthandler(self, node)
+	}
+	fn fold_alias(&mut self, node: Alias) -> Alias {
+		fold_alias(self, node)
+	}
+	fn fold_withitem(&mut self, node: WithItem) -> WithItem {
+		fold_withitem(self, node)
+	}
+	fn fold_matchcase(&mut self, node: MatchCase) -> MatchCase {
+		fold_matchcase(self, node)
+	}
+	fn fold_typeignore(&mut self, node: TypeIgnore) -> TypeIgnore {
+		fold_typeignore(self, node)
+	}
+}
+
+pub fn walk_mod<V: Visitor + ?Sized>(visitor: &mut V, node: &Mod) {
+	match node {
+		Mod::Module(node) => visitor.visit_module_mod(node),
+		Mod::Interactive(node) => visitor.visit_interactive_mod(node),
+		Mod::Expression(node) => visitor.visit_expression_mod(node),
+		Mod::FunctionType(node) => visitor.visit_functiontype_mod(node),
+	}
+}
+
+pub fn walk_mod_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Mod) {
+	match node {
+		Mod::Module(node) => visitor.visit_module_mod_mut(node),
+		Mod::Interactive(node) => visitor.visit_interactive_mod_mut(node),
+		Mod::Expression(node) => visitor.visit_expression_mod_mut(node),
+		Mod::FunctionType(node) => visitor.visit_functiontype_mod_mut(node),
+	}
+}
+
+pub fn fold_mod<F: Fold + ?Sized>(folder: &mut F, node: Mod) -> Mod {
+	match node {
+		Mod::Module(node) => folder.fold_module_mod(node),
+		Mod::Interactive(node) => folder.fold_interactive_mod(node),
+		Mod::Expression(node) => folder.fold_expression_mod(node),
+		Mod::FunctionType(node) => folder.fold_functiontype_mod(node),
+	}
+}
+
+pub fn walk_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &Stmt) {
+	match node {
+		Stmt::FunctionDef(node) => visitor.visit_functiondef_stmt(node),
+		Stmt::AsyncFunctionDef(node) => visitor.visit_asyncfunctiondef_stmt(node),
+		Stmt::ClassDef(node) => visitor.visit_classdef_stmt(node),
+		Stmt::Return(node) => visitor.visit_return_stmt(node),
+		Stmt::Delete(node) => visitor.visit_delete_stmt(node),
+		Stmt::Assign(node) => visitor.visit_assign_stmt(node),
+		Stmt::TypeAlias(node) => visitor.visit_typealias_stmt(node),
+		Stmt::AugAssign(node) => visitor.visit_augassign_stmt(node),
+		Stmt::AnnAssign(node) => visitor.visit_annassign_stmt(node),
+		Stmt::For(node) => visitor.visit_for_stmt(node),
+		Stmt::AsyncFor(node) => visitor.visit_asyncfor_stmt(node),
+		Stmt::While(node) => visitor.visit_while_stmt(node),
+		Stmt::If(node) => visitor.visit_if_stmt(node),
+		Stmt::With(node) => visitor.visit_with_stmt(node),
+		Stmt::AsyncWith(node) => visitor.visit_asyncwith_stmt(node),
+		Stmt::Match(node) => visitor.visit_match_stmt(node),
+		Stmt::Raise(node) => visitor.visit_raise_stmt(node),
+		Stmt::Try(node) => visitor.visit_try_stmt(node),
+		Stmt::TryStar(node) => visitor.visit_trystar_stmt(node),
+		Stmt::Assert(node) => visitor.visit_assert_stmt(node),
+		Stmt::Import(node) => visitor.visit_import_stmt(node),
+		Stmt::ImportFrom(node) => visitor.visit_importfrom_stmt(node),
+		Stmt::Global(node) => visitor.visit_global_stmt(node),
+		Stmt::Nonlocal(node) => visitor.visit_nonlocal_stmt(node),
+		Stmt::ExprStmt(node) => visitor.visit_exprstmt_stmt(node),
+		Stmt::Pass(node) => visitor.visit_pass_stmt(node),
+		Stmt::Break(node) => visitor.visit_break_stmt(node),
+		Stmt::Continue(node) => visitor.visit_continue_stmt(node),
+		Stmt::ErrorStmt(node) => visitor.visit_errorstmt_stmt(node),
+	}
+}
+
+pub fn walk_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Stmt) {
+	match node {
+		Stmt::FunctionDef(node) => visitor.visit_functiondef_stmt_mut(node),
+		Stmt::AsyncFunctionDef(node) => visitor.visit_asyncfunctiondef_stmt_mut(node),
+		Stmt::ClassDef(node) => visitor.visit_classdef_stmt_mut(node),
+		Stmt::Return(node) => visitor.visit_return_stmt_mut(node),
+		Stmt::Delete(node) => visitor.visit_delete_stmt_mut(node),
+		Stmt::Assign(node) => visitor.visit_assign_stmt_mut(node),
+		Stmt::TypeAlias(node) => visitor.visit_typealias_stmt_mut(node),
+		Stmt::AugAssign(node) => visitor.visit_augassign_stmt_mut(node),
+		Stmt::AnnAssign(node) => visitor.visit_annassign_stmt_mut(node),
+		Stmt::For(node) => visitor.visit_for_stmt_mut(node),
+		Stmt::AsyncFor(node) => visitor.visit_asyncfor_stmt_mut(node),
+		Stmt::While(node) => visitor.visit_while_stmt_mut(node),
+		Stmt::If(node) => visitor.visit_if_stmt_mut(node),
+		Stmt::With(node) => visitor.visit_with_stmt_mut(node),
+		Stmt::AsyncWith(node) => visitor.visit_asyncwith_stmt_mut(node),
+		Stmt::Match(node) => visitor.visit_match_stmt_mut(node),
+		Stmt::Raise(node) => visitor.visit_raise_stmt_mut(node),
+		Stmt::Try(node) => visitor.visit_try_stmt_mut(node),
+		Stmt::TryStar(node) => visitor.visit_trystar_stmt_mut(node),
+		Stmt::Assert(node) => visitor.visit_assert_stmt_mut(node),
+		Stmt::Import(node) => visitor.visit_import_stmt_mut(node),
+		Stmt::ImportFrom(node) => visitor.visit_importfrom_stmt_mut(node),
+		Stmt::Global(node) => visitor.visit_global_stmt_mut(node),
+		Stmt::Nonlocal(node) => visitor.visit_nonlocal_stmt_mut(node),
+		Stmt::ExprStmt(node) => visitor.visit_exprstmt_stmt_mut(node),
+		Stmt::Pass(node) => visitor.visit_pass_stmt_mut(node),
+		Stmt::Break(node) => visitor.visit_break_stmt_mut(node),
+		Stmt::Continue(node) => visitor.visit_continue_stmt_mut(node),
+		Stmt::ErrorStmt(node) => visitor.visit_errorstmt_stmt_mut(node),
+	}
+}
+
+pub fn fold_stmt<F: Fold + ?Sized>(folder: &mut F, node: Stmt) -> Stmt {
+	match node {
+		Stmt::FunctionDef(node) => folder.fold_functiondef_stmt(node),
+		Stmt::AsyncFunctionDef(node) => folder.fold_asyncfunctiondef_stmt(node),
+		Stmt::ClassDef(node) => folder.fold_classdef_stmt(node),
+		Stmt::Return(node) => folder.fold_return_stmt(node),
+		Stmt::Delete(node) => folder.fold_delete_stmt(node),
+		Stmt::Assign(node) => folder.fold_assign_stmt(node),
+		Stmt::TypeAlias(node) => folder.fold_typealias_stmt(node),
+		Stmt::AugAssign(node) => folder.fold_augassign_stmt(node),
+		Stmt::AnnAssign(node) => folder.fold_annassign_stmt(node),
+		Stmt::For(node) => folder.fold_for_stmt(node),
+		Stmt::AsyncFor(node) => folder.fold_asyncfor_stmt(node),
+		Stmt::While(node) => folder.fold_while_stmt(node),
+		Stmt::If(node) => folder.fold_if_stmt(node),
+		Stmt::With(node) => folder.fold_with_stmt(node),
+		Stmt::AsyncWith(node) => folder.fold_asyncwith_stmt(node),
+		Stmt::Match(node) => folder.fold_match_stmt(node),
+		Stmt::Raise(node) => folder.fold_raise_stmt(node),
+		Stmt::Try(node) => folder.fold_try_stmt(node),
+		Stmt::TryStar(node) => folder.fold_trystar_stmt(node),
+		Stmt::Assert(node) => folder.fold_assert_stmt(node),
+		Stmt::Import(node) => folder.fold_import_stmt(node),
+		Stmt::ImportFrom(node) => folder.fold_importfrom_stmt(node),
+		Stmt::Global(node) => folder.fold_global_stmt(node),
+		Stmt::Nonlocal(node) => folder.fold_nonlocal_stmt(node),
+		Stmt::ExprStmt(node) => folder.fold_exprstmt_stmt(node),
+		Stmt::Pass(node) => folder.fold_pass_stmt(node),
+		Stmt::Break(node) => folder.fold_break_stmt(node),
+		Stmt::Continue(node) => folder.fold_continue_stmt(node),
+		Stmt::ErrorStmt(node) => folder.fold_errorstmt_stmt(node),
+	}
+}
+
+pub fn walk_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Expr) {
+	match node {
+		Expr::BoolOp(node) => visitor.visit_boolop_expr(node),
+		Expr::NamedExpr(node) => visitor.visit_namedexpr_expr(node),
+		Expr::BinOp(node) => visitor.visit_binop_expr(node),
+		Expr::UnaryOp(node) => visitor.visit_unaryop_expr(node),
+		Expr::Lambda(node) => visitor.visit_lambda_expr(node),
+		Expr::IfExp(node) => visitor.visit_ifexp_expr(node),
+		Expr::Dict(node) => visitor.visit_dict_expr(node),
+		Expr::Set(node) => visitor.visit_set_expr(node),
+		Expr::ListComp(node) => visitor.visit_listcomp_expr(node),
+		Expr::SetComp(node) => visitor.visit_setcomp_expr(node),
+		Expr::DictComp(node) => visitor.visit_dictcomp_expr(node),
+		Expr::GeneratorExp(node) => visitor.visit_generatorexp_expr(node),
+		Expr::Await(node) => visitor.visit_await_expr(node),
+		Expr::Yield(node) => visitor.visit_yield_expr(node),
+		Expr::YieldFrom(node) => visitor.visit_yieldfrom_expr(node),
+		Expr::Compare(node) => visitor.visit_compare_expr(node),
+		Expr::Call(node) => visitor.visit_call_expr(node),
+		Expr::FormattedValue(node) => visitor.visit_formattedvalue_expr(node),
+		Expr::JoinedStr(node) => visitor.visit_joinedstr_expr(node),
+		Expr::Constant(node) => visitor.visit_constant_expr(node),
+		Expr::Attribute(node) => visitor.visit_attribute_expr(node),
+		Expr::Subscript(node) => visitor.visit_subscript_expr(node),
+		Expr::Starred(node) => visitor.visit_starred_expr(node),
+		Expr::Name(node) => visitor.visit_name_expr(node),
+		Expr::List(node) => visitor.visit_list_expr(node),
+		Expr::Tuple(node) => visitor.visit_tuple_expr(node),
+		Expr::Slice(node) => visitor.visit_slice_expr(node),
+		Expr::ErrorExpr(node) => visitor.visit_errorexpr_expr(node),
+	}
+}
+
+pub fn walk_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Expr) {
+	match node {
+		Expr::BoolOp(node) => visitor.visit_boolop_expr_mut(node),
+		Expr::NamedExpr(node) => visitor.visit_namedexpr_expr_mut(node),
+		Expr::BinOp(node) => visitor.visit_binop_expr_mut(node),
+		Expr::UnaryOp(node) => visitor.visit_unaryop_expr_mut(node),
+		Expr::Lambda(node) => visitor.visit_lambda_expr_mut(node),
+		Expr::IfExp(node) => visitor.visit_ifexp_expr_mut(node),
+		Expr::Dict(node) => visitor.visit_dict_expr_mut(node),
+		Expr::Set(node) => visitor.visit_set_expr_mut(node),
+		Expr::ListComp(node) => visitor.visit_listcomp_expr_mut(node),
+		Expr::SetComp(node) => visitor.visit_setcomp_expr_mut(node),
+		Expr::DictComp(node) => visitor.visit_dictcomp_expr_mut(node),
+		Expr::GeneratorExp(node) => visitor.visit_generatorexp_expr_mut(node),
+		Expr::Await(node) => visitor.visit_await_expr_mut(node),
+		Expr::Yield(node) => visitor.visit_yield_expr_mut(node),
+		Expr::YieldFrom(node) => visitor.visit_yieldfrom_expr_mut(node),
+		Expr::Compare(node) => visitor.visit_compare_expr_mut(node),
+		Expr::Call(node) => visitor.visit_call_expr_mut(node),
+		Expr::FormattedValue(node) => visitor.visit_formattedvalue_expr_mut(node),
+		Expr::JoinedStr(node) => visitor.visit_joinedstr_expr_mut(node),
+		Expr::Constant(node) => visitor.visit_constant_expr_mut(node),
+		Expr::Attribute(node) => visitor.visit_attribute_expr_mut(node),
+		Expr::Subscript(node) => visitor.visit_subscript_expr_mut(node),
+		Expr::Starred(node) => visitor.visit_starred_expr_mut(node),
+		Expr::Name(node) => visitor.visit_name_expr_mut(node),
+		Expr::List(node) => visitor.visit_list_expr_mut(node),
+		Expr::Tuple(node) => visitor.visit_tuple_expr_mut(node),
+		Expr::Slice(node) => visitor.visit_slice_expr_mut(node),
+		Expr::ErrorExpr(node) => visitor.visit_errorexpr_expr_mut(node),
+	}
+}
+
+pub fn fold_expr<F: Fold + ?Sized>(folder: &mut F, node: Expr) -> Expr {
+	match node {
+		Expr::BoolOp(node) => folder.fold_boolop_expr(node),
+		Expr::NamedExpr(node) => folder.fold_namedexpr_expr(node),
+		Expr::BinOp(node) => folder.fold_binop_expr(node),
+		Expr::UnaryOp(node) => folder.fold_unaryop_expr(node),
+		Expr::Lambda(node) => folder.fold_lambda_expr(node),
+		Expr::IfExp(node) => folder.fold_ifexp_expr(node),
+		Expr::Dict(node) => folder.fold_dict_expr(node),
+		Expr::Set(node) => folder.fold_set_expr(node),
+		Expr::ListComp(node) => folder.fold_listcomp_expr(node),
+		Expr::SetComp(node) => folder.fold_setcomp_expr(node),
+		Expr::DictComp(node) => folder.fold_dictcomp_expr(node),
+		Expr::GeneratorExp(node) => folder.fold_generatorexp_expr(node),
+		Expr::Await(node) => folder.fold_await_expr(node),
+		Expr::Yield(node) => folder.fold_yield_expr(node),
+		Expr::YieldFrom(node) => folder.fold_yieldfrom_expr(node),
+		Expr::Compare(node) => folder.fold_compare_expr(node),
+		Expr::Call(node) => folder.fold_call_expr(node),
+		Expr::FormattedValue(node) => folder.fold_formattedvalue_expr(node),
+		Expr::JoinedStr(node) => folder.fold_joinedstr_expr(node),
+		Expr::Constant(node) => folder.fold_constant_expr(node),
+		Expr::Attribute(node) => folder.fold_attribute_expr(node),
+		Expr::Subscript(node) => folder.fold_subscript_expr(node),
+		Expr::Starred(node) => folder.fold_starred_expr(node),
+		Expr::Name(node) => folder.fold_name_expr(node),
+		Expr::List(node) => folder.fold_list_expr(node),
+		Expr::Tuple(node) => folder.fold_tuple_expr(node),
+		Expr::Slice(node) => folder.fold_slice_expr(node),
+		Expr::ErrorExpr(node) => folder.fold_errorexpr_expr(node),
+	}
+}
+
+pub fn walk_pattern<V: Visitor + ?Sized>(visitor: &mut V, node: &Pattern) {
+	match node {
+		Pattern::MatchValue(node) => visitor.visit_matchvalue_pattern(node),
+		Pattern::MatchSingleton(node) => visitor.visit_matchsingleton_pattern(node),
+		Pattern::MatchSequence(node) => visitor.visit_matchsequence_pattern(node),
+		Pattern::MatchMapping(node) => visitor.visit_matchmapping_pattern(node),
+		Pattern::MatchClass(node) => visitor.visit_matchclass_pattern(node),
+		Pattern::MatchStar(node) => visitor.visit_matchstar_pattern(node),
+		Pattern::MatchAs(node) => visitor.visit_matchas_pattern(node),
+		Pattern::MatchOr(node) => visitor.visit_matchor_pattern(node),
+	}
+}
+
+pub fn walk_pattern_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Pattern) {
+	match node {
+		Pattern::MatchValue(node) => visitor.visit_matchvalue_pattern_mut(node),
+		Pattern::MatchSingleton(node) => visitor.visit_matchsingleton_pattern_mut(node),
+		Pattern::MatchSequence(node) => visitor.visit_matchsequence_pattern_mut(node),
+		Pattern::MatchMapping(node) => visitor.visit_matchmapping_pattern_mut(node),
+		Pattern::MatchClass(node) => visitor.visit_matchclass_pattern_mut(node),
+		Pattern::MatchStar(node) => visitor.visit_matchstar_pattern_mut(node),
+		Pattern::MatchAs(node) => visitor.visit_matchas_pattern_mut(node),
+		Pattern::MatchOr(node) => visitor.visit_matchor_pattern_mut(node),
+	}
+}
+
+pub fn fold_pattern<F: Fold + ?Sized>(folder: &mut F, node: Pattern) -> Pattern {
+	match node {
+		Pattern::MatchValue(node) => folder.fold_matchvalue_pattern(node),
+		Pattern::MatchSingleton(node) => folder.fold_matchsingleton_pattern(node),
+		Pattern::MatchSequence(node) => folder.fold_matchsequence_pattern(node),
+		Pattern::MatchMapping(node) => folder.fold_matchmapping_pattern(node),
+		Pattern::MatchClass(node) => folder.fold_matchclass_pattern(node),
+		Pattern::MatchStar(node) => folder.fold_matchstar_pattern(node),
+		Pattern::MatchAs(node) => folder.fold_matchas_pattern(node),
+		Pattern::MatchOr(node) => folder.fold_matchor_pattern(node),
+	}
+}
+
+pub fn walk_typeparam<V: Visitor + ?Sized>(visitor: &mut V, node: &TypeParam) {
+	match node {
+		TypeParam::TypeVar(node) => visitor.visit_typevar_typeparam(node),
+		TypeParam::ParamSpec(node) => visitor.visit_paramspec_typeparam(node),
+		TypeParam::TypeVarTuple(node) => visitor.visit_typevartuple_typeparam(node),
+	}
+}
+
+pub fn walk_typeparam_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut TypeParam) {
+	match node {
+		TypeParam::TypeVar(node) => visitor.visit_typevar_typeparam_mut(node),
+		TypeParam::ParamSpec(node) => visitor.visit_paramspec_typeparam_mut(node),
+		TypeParam::TypeVarTuple(node) => visitor.visit_typevartuple_typeparam_mut(node),
+	}
+}
+
+pub fn fold_typeparam<F: Fold + ?Sized>(folder: &mut F, node: TypeParam) -> TypeParam {
+	match node {
+		TypeParam::TypeVar(node) => folder.fold_typevar_typeparam(node),
+		TypeParam::ParamSpec(node) => folder.fold_paramspec_typeparam(node),
+		TypeParam::TypeVarTuple(node) => folder.fold_typevartuple_typeparam(node),
+	}
+}
+
+pub fn walk_module_mod<V: Visitor + ?Sized>(visitor: &mut V, node: &Module) {
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+	for x in &node.type_ignores {
+		visitor.visit_typeignore(x);
+	}
+}
+
+pub fn walk_module_mod_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Module) {
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+	for x in &mut node.type_ignores {
+		visitor.visit_typeignore_mut(x);
+	}
+}
+
+pub fn fold_module_mod<F: Fold + ?Sized>(folder: &mut F, node: Module) -> Module {
+	Module {
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		type_ignores: node.type_ignores.into_iter().map(|x| folder.fold_typeignore(x)).collect(),
+	}
+}
+
+pub fn walk_interactive_mod<V: Visitor + ?Sized>(visitor: &mut V, node: &Interactive) {
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+}
+
+pub fn walk_interactive_mod_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Interactive) {
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+}
+
+pub fn fold_interactive_mod<F: Fold + ?Sized>(folder: &mut F, node: Interactive) -> Interactive {
+	Interactive {
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+	}
+}
+
+pub fn walk_expression_mod<V: Visitor + ?Sized>(visitor: &mut V, node: &Expression) {
+	visitor.visit_expr(&node.body);
+}
+
+pub fn walk_expression_mod_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Expression) {
+	visitor.visit_expr_mut(&mut node.body);
+}
+
+pub fn fold_expression_mod<F: Fold + ?Sized>(folder: &mut F, node: Expression) -> Expression {
+	Expression {
+		body: Box::new(folder.fold_expr(*node.body)),
+	}
+}
+
+pub fn walk_functiontype_mod<V: Visitor + ?Sized>(visitor: &mut V, node: &FunctionType) {
+	for x in &node.argtypes {
+		visitor.visit_expr(x);
+	}
+	visitor.visit_expr(&node.returns);
+}
+
+pub fn walk_functiontype_mod_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut FunctionType) {
+	for x in &mut node.argtypes {
+		visitor.visit_expr_mut(x);
+	}
+	visitor.visit_expr_mut(&mut node.returns);
+}
+
+pub fn fold_functiontype_mod<F: Fold + ?Sized>(folder: &mut F, node: FunctionType) -> FunctionType {
+	FunctionType {
+		argtypes: node.argtypes.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		returns: Box::new(folder.fold_expr(*node.returns)),
+	}
+}
+
+pub fn walk_functiondef_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &FunctionDef) {
+	visitor.visit_arguments(&node.args);
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+	for x in &node.decorator_list {
+		visitor.visit_expr(x);
+	}
+	if let Some(x) = &node.returns {
+		visitor.visit_expr(x);
+	}
+	for x in &node.type_params {
+		visitor.visit_typeparam(x);
+	}
+}
+
+pub fn walk_functiondef_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut FunctionDef) {
+	visitor.visit_arguments_mut(&mut node.args);
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+	for x in &mut node.decorator_list {
+		visitor.visit_expr_mut(x);
+	}
+	if let Some(x) = &mut node.returns {
+		visitor.visit_expr_mut(x);
+	}
+	for x in &mut node.type_params {
+		visitor.visit_typeparam_mut(x);
+	}
+}
+
+pub fn fold_functiondef_stmt<F: Fold + ?Sized>(folder: &mut F, node: FunctionDef) -> FunctionDef {
+	FunctionDef {
+		name: node.name,
+		args: Box::new(folder.fold_arguments(*node.args)),
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		decorator_list: node.decorator_list.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		returns: node.returns.map(|x| Box::new(folder.fold_expr(*x))),
+		type_comment: node.type_comment,
+		type_params: node.type_params.into_iter().map(|x| folder.fold_typeparam(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_asyncfunctiondef_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &AsyncFunctionDef) {
+	visitor.visit_arguments(&node.args);
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+	for x in &node.decorator_list {
+		visitor.visit_expr(x);
+	}
+	if let Some(x) = &node.returns {
+		visitor.visit_expr(x);
+	}
+	for x in &node.type_params {
+		visitor.visit_typeparam(x);
+	}
+}
+
+pub fn walk_asyncfunctiondef_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut AsyncFunctionDef) {
+	visitor.visit_arguments_mut(&mut node.args);
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+	for x in &mut node.decorator_list {
+		visitor.visit_expr_mut(x);
+	}
+	if let Some(x) = &mut node.returns {
+		visitor.visit_expr_mut(x);
+	}
+	for x in &mut node.type_params {
+		visitor.visit_typeparam_mut(x);
+	}
+}
+
+pub fn fold_asyncfunctiondef_stmt<F: Fold + ?Sized>(folder: &mut F, node: AsyncFunctionDef) -> AsyncFunctionDef {
+	AsyncFunctionDef {
+		name: node.name,
+		args: Box::new(folder.fold_arguments(*node.args)),
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		decorator_list: node.decorator_list.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		returns: node.returns.map(|x| Box::new(folder.fold_expr(*x))),
+		type_comment: node.type_comment,
+		type_params: node.type_params.into_iter().map(|x| folder.fold_typeparam(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_classdef_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &ClassDef) {
+	for x in &node.bases {
+		visitor.visit_expr(x);
+	}
+	for x in &node.keywords {
+		visitor.visit_keyword(x);
+	}
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+	for x in &node.decorator_list {
+		visitor.visit_expr(x);
+	}
+	for x in &node.type_params {
+		visitor.visit_typeparam(x);
+	}
+}
+
+pub fn walk_classdef_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ClassDef) {
+	for x in &mut node.bases {
+		visitor.visit_expr_mut(x);
+	}
+	for x in &mut node.keywords {
+		visitor.visit_keyword_mut(x);
+	}
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+	for x in &mut node.decorator_list {
+		visitor.visit_expr_mut(x);
+	}
+	for x in &mut node.type_params {
+		visitor.visit_typeparam_mut(x);
+	}
+}
+
+pub fn fold_classdef_stmt<F: Fold + ?Sized>(folder: &mut F, node: ClassDef) -> ClassDef {
+	ClassDef {
+		name: node.name,
+		bases: node.bases.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		keywords: node.keywords.into_iter().map(|x| folder.fold_keyword(x)).collect(),
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		decorator_list: node.decorator_list.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		type_params: node.type_params.into_iter().map(|x| folder.fold_typeparam(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_return_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &Return) {
+	if let Some(x) = &node.value {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_return_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Return) {
+	if let Some(x) = &mut node.value {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_return_stmt<F: Fold + ?Sized>(folder: &mut F, node: Return) -> Return {
+	Return {
+		value: node.value.map(|x| Box::new(folder.fold_expr(*x))),
+		span: node.span,
+	}
+}
+
+pub fn walk_delete_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &Delete) {
+	for x in &node.targets {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_delete_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Delete) {
+	for x in &mut node.targets {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_delete_stmt<F: Fold + ?Sized>(folder: &mut F, node: Delete) -> Delete {
+	Delete {
+		targets: node.targets.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_assign_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &Assign) {
+	for x in &node.targets {
+		visitor.visit_expr(x);
+	}
+	visitor.visit_expr(&node.value);
+}
+
+pub fn walk_assign_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Assign) {
+	for x in &mut node.targets {
+		visitor.visit_expr_mut(x);
+	}
+	visitor.visit_expr_mut(&mut node.value);
+}
+
+pub fn fold_assign_stmt<F: Fold + ?Sized>(folder: &mut F, node: Assign) -> Assign {
+	Assign {
+		targets: node.targets.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		value: Box::new(folder.fold_expr(*node.value)),
+		type_comment: node.type_comment,
+		span: node.span,
+	}
+}
+
+pub fn walk_typealias_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &TypeAlias) {
+	visitor.visit_expr(&node.name);
+	for x in &node.type_params {
+		visitor.visit_typeparam(x);
+	}
+	visitor.visit_expr(&node.value);
+}
+
+pub fn walk_typealias_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut TypeAlias) {
+	visitor.visit_expr_mut(&mut node.name);
+	for x in &mut node.type_params {
+		visitor.visit_typeparam_mut(x);
+	}
+	visitor.visit_expr_mut(&mut node.value);
+}
+
+pub fn fold_typealias_stmt<F: Fold + ?Sized>(folder: &mut F, node: TypeAlias) -> TypeAlias {
+	TypeAlias {
+		name: Box::new(folder.fold_expr(*node.name)),
+		type_params: node.type_params.into_iter().map(|x| folder.fold_typeparam(x)).collect(),
+		value: Box::new(folder.fold_expr(*node.value)),
+		span: node.span,
+	}
+}
+
+pub fn walk_augassign_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &AugAssign) {
+	visitor.visit_expr(&node.target);
+	visitor.visit_expr(&node.value);
+}
+
+pub fn walk_augassign_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut AugAssign) {
+	visitor.visit_expr_mut(&mut node.target);
+	visitor.visit_expr_mut(&mut node.value);
+}
+
+pub fn fold_augassign_stmt<F: Fold + ?Sized>(folder: &mut F, node: AugAssign) -> AugAssign {
+	AugAssign {
+		target: Box::new(folder.fold_expr(*node.target)),
+		op: node.op,
+		value: Box::new(folder.fold_expr(*node.value)),
+		span: node.span,
+	}
+}
+
+pub fn walk_annassign_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &AnnAssign) {
+	visitor.visit_expr(&node.target);
+	visitor.visit_expr(&node.annotation);
+	if let Some(x) = &node.value {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_annassign_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut AnnAssign) {
+	visitor.visit_expr_mut(&mut node.target);
+	visitor.visit_expr_mut(&mut node.annotation);
+	if let Some(x) = &mut node.value {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_annassign_stmt<F: Fold + ?Sized>(folder: &mut F, node: AnnAssign) -> AnnAssign {
+	AnnAssign {
+		target: Box::new(folder.fold_expr(*node.target)),
+		annotation: Box::new(folder.fold_expr(*node.annotation)),
+		value: node.value.map(|x| Box::new(folder.fold_expr(*x))),
+		simple: node.simple,
+		span: node.span,
+	}
+}
+
+pub fn walk_for_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &For) {
+	visitor.visit_expr(&node.target);
+	visitor.visit_expr(&node.iter);
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+	for x in &node.orelse {
+		visitor.visit_stmt(x);
+	}
+}
+
+pub fn walk_for_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut For) {
+	visitor.visit_expr_mut(&mut node.target);
+	visitor.visit_expr_mut(&mut node.iter);
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+	for x in &mut node.orelse {
+		visitor.visit_stmt_mut(x);
+	}
+}
+
+pub fn fold_for_stmt<F: Fold + ?Sized>(folder: &mut F, node: For) -> For {
+	For {
+		target: Box::new(folder.fold_expr(*node.target)),
+		iter: Box::new(folder.fold_expr(*node.iter)),
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		orelse: node.orelse.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		type_comment: node.type_comment,
+		span: node.span,
+	}
+}
+
+pub fn walk_asyncfor_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &AsyncFor) {
+	visitor.visit_expr(&node.target);
+	visitor.visit_expr(&node.iter);
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+	for x in &node.orelse {
+		visitor.visit_stmt(x);
+	}
+}
+
+pub fn walk_asyncfor_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut AsyncFor) {
+	visitor.visit_expr_mut(&mut node.target);
+	visitor.visit_expr_mut(&mut node.iter);
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+	for x in &mut node.orelse {
+		visitor.visit_stmt_mut(x);
+	}
+}
+
+pub fn fold_asyncfor_stmt<F: Fold + ?Sized>(folder: &mut F, node: AsyncFor) -> AsyncFor {
+	AsyncFor {
+		target: Box::new(folder.fold_expr(*node.target)),
+		iter: Box::new(folder.fold_expr(*node.iter)),
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		orelse: node.orelse.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		type_comment: node.type_comment,
+		span: node.span,
+	}
+}
+
+pub fn walk_while_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &While) {
+	visitor.visit_expr(&node.test);
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+	for x in &node.orelse {
+		visitor.visit_stmt(x);
+	}
+}
+
+pub fn walk_while_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut While) {
+	visitor.visit_expr_mut(&mut node.test);
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+	for x in &mut node.orelse {
+		visitor.visit_stmt_mut(x);
+	}
+}
+
+pub fn fold_while_stmt<F: Fold + ?Sized>(folder: &mut F, node: While) -> While {
+	While {
+		test: Box::new(folder.fold_expr(*node.test)),
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		orelse: node.orelse.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_if_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &If) {
+	visitor.visit_expr(&node.test);
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+	for x in &node.orelse {
+		visitor.visit_stmt(x);
+	}
+}
+
+pub fn walk_if_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut If) {
+	visitor.visit_expr_mut(&mut node.test);
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+	for x in &mut node.orelse {
+		visitor.visit_stmt_mut(x);
+	}
+}
+
+pub fn fold_if_stmt<F: Fold + ?Sized>(folder: &mut F, node: If) -> If {
+	If {
+		test: Box::new(folder.fold_expr(*node.test)),
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		orelse: node.orelse.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_with_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &With) {
+	for x in &node.items {
+		visitor.visit_withitem(x);
+	}
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+}
+
+pub fn walk_with_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut With) {
+	for x in &mut node.items {
+		visitor.visit_withitem_mut(x);
+	}
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+}
+
+pub fn fold_with_stmt<F: Fold + ?Sized>(folder: &mut F, node: With) -> With {
+	With {
+		items: node.items.into_iter().map(|x| folder.fold_withitem(x)).collect(),
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		type_comment: node.type_comment,
+		span: node.span,
+	}
+}
+
+pub fn walk_asyncwith_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &AsyncWith) {
+	for x in &node.items {
+		visitor.visit_withitem(x);
+	}
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+}
+
+pub fn walk_asyncwith_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut AsyncWith) {
+	for x in &mut node.items {
+		visitor.visit_withitem_mut(x);
+	}
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+}
+
+pub fn fold_asyncwith_stmt<F: Fold + ?Sized>(folder: &mut F, node: AsyncWith) -> AsyncWith {
+	AsyncWith {
+		items: node.items.into_iter().map(|x| folder.fold_withitem(x)).collect(),
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		type_comment: node.type_comment,
+		span: node.span,
+	}
+}
+
+pub fn walk_match_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &Match) {
+	visitor.visit_expr(&node.subject);
+	for x in &node.cases {
+		visitor.visit_matchcase(x);
+	}
+}
+
+pub fn walk_match_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Match) {
+	visitor.visit_expr_mut(&mut node.subject);
+	for x in &mut node.cases {
+		visitor.visit_matchcase_mut(x);
+	}
+}
+
+pub fn fold_match_stmt<F: Fold + ?Sized>(folder: &mut F, node: Match) -> Match {
+	Match {
+		subject: Box::new(folder.fold_expr(*node.subject)),
+		cases: node.cases.into_iter().map(|x| folder.fold_matchcase(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_raise_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &Raise) {
+	if let Some(x) = &node.exc {
+		visitor.visit_expr(x);
+	}
+	if let Some(x) = &node.cause {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_raise_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Raise) {
+	if let Some(x) = &mut node.exc {
+		visitor.visit_expr_mut(x);
+	}
+	if let Some(x) = &mut node.cause {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_raise_stmt<F: Fold + ?Sized>(folder: &mut F, node: Raise) -> Raise {
+	Raise {
+		exc: node.exc.map(|x| Box::new(folder.fold_expr(*x))),
+		cause: node.cause.map(|x| Box::new(folder.fold_expr(*x))),
+		span: node.span,
+	}
+}
+
+pub fn walk_try_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &Try) {
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+	for x in &node.handlers {
+		visitor.visit_excepthandler(x);
+	}
+	for x in &node.orelse {
+		visitor.visit_stmt(x);
+	}
+	for x in &node.finalbody {
+		visitor.visit_stmt(x);
+	}
+}
+
+pub fn walk_try_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Try) {
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+	for x in &mut node.handlers {
+		visitor.visit_excepthandler_mut(x);
+	}
+	for x in &mut node.orelse {
+		visitor.visit_stmt_mut(x);
+	}
+	for x in &mut node.finalbody {
+		visitor.visit_stmt_mut(x);
+	}
+}
+
+pub fn fold_try_stmt<F: Fold + ?Sized>(folder: &mut F, node: Try) -> Try {
+	Try {
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		handlers: node.handlers.into_iter().map(|x| folder.fold_excepthandler(x)).collect(),
+		orelse: node.orelse.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		finalbody: node.finalbody.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_trystar_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &TryStar) {
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+	for x in &node.handlers {
+		visitor.visit_excepthandler(x);
+	}
+	for x in &node.orelse {
+		visitor.visit_stmt(x);
+	}
+	for x in &node.finalbody {
+		visitor.visit_stmt(x);
+	}
+}
+
+pub fn walk_trystar_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut TryStar) {
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+	for x in &mut node.handlers {
+		visitor.visit_excepthandler_mut(x);
+	}
+	for x in &mut node.orelse {
+		visitor.visit_stmt_mut(x);
+	}
+	for x in &mut node.finalbody {
+		visitor.visit_stmt_mut(x);
+	}
+}
+
+pub fn fold_trystar_stmt<F: Fold + ?Sized>(folder: &mut F, node: TryStar) -> TryStar {
+	TryStar {
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		handlers: node.handlers.into_iter().map(|x| folder.fold_excepthandler(x)).collect(),
+		orelse: node.orelse.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		finalbody: node.finalbody.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_assert_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &Assert) {
+	visitor.visit_expr(&node.test);
+	if let Some(x) = &node.msg {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_assert_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Assert) {
+	visitor.visit_expr_mut(&mut node.test);
+	if let Some(x) = &mut node.msg {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_assert_stmt<F: Fold + ?Sized>(folder: &mut F, node: Assert) -> Assert {
+	Assert {
+		test: Box::new(folder.fold_expr(*node.test)),
+		msg: node.msg.map(|x| Box::new(folder.fold_expr(*x))),
+		span: node.span,
+	}
+}
+
+pub fn walk_import_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &Import) {
+	for x in &node.names {
+		visitor.visit_alias(x);
+	}
+}
+
+pub fn walk_import_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Import) {
+	for x in &mut node.names {
+		visitor.visit_alias_mut(x);
+	}
+}
+
+pub fn fold_import_stmt<F: Fold + ?Sized>(folder: &mut F, node: Import) -> Import {
+	Import {
+		names: node.names.into_iter().map(|x| folder.fold_alias(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_importfrom_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &ImportFrom) {
+	for x in &node.names {
+		visitor.visit_alias(x);
+	}
+}
+
+pub fn walk_importfrom_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ImportFrom) {
+	for x in &mut node.names {
+		visitor.visit_alias_mut(x);
+	}
+}
+
+pub fn fold_importfrom_stmt<F: Fold + ?Sized>(folder: &mut F, node: ImportFrom) -> ImportFrom {
+	ImportFrom {
+		module: node.module,
+		names: node.names.into_iter().map(|x| folder.fold_alias(x)).collect(),
+		level: node.level,
+		span: node.span,
+	}
+}
+
+pub fn walk_global_stmt<V: Visitor + ?Sized>(_visitor: &mut V, _node: &Global) {}
+
+pub fn walk_global_stmt_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut Global) {}
+
+pub fn fold_global_stmt<F: Fold + ?Sized>(_folder: &mut F, node: Global) -> Global {
+	node
+}
+
+pub fn walk_nonlocal_stmt<V: Visitor + ?Sized>(_visitor: &mut V, _node: &Nonlocal) {}
+
+pub fn walk_nonlocal_stmt_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut Nonlocal) {}
+
+pub fn fold_nonlocal_stmt<F: Fold + ?Sized>(_folder: &mut F, node: Nonlocal) -> Nonlocal {
+	node
+}
+
+pub fn walk_exprstmt_stmt<V: Visitor + ?Sized>(visitor: &mut V, node: &ExprStmt) {
+	visitor.visit_expr(&node.value);
+}
+
+pub fn walk_exprstmt_stmt_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ExprStmt) {
+	visitor.visit_expr_mut(&mut node.value);
+}
+
+pub fn fold_exprstmt_stmt<F: Fold + ?Sized>(folder: &mut F, node: ExprStmt) -> ExprStmt {
+	ExprStmt {
+		value: Box::new(folder.fold_expr(*node.value)),
+		span: node.span,
+	}
+}
+
+pub fn walk_pass_stmt<V: Visitor + ?Sized>(_visitor: &mut V, _node: &Pass) {}
+
+pub fn walk_pass_stmt_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut Pass) {}
+
+pub fn fold_pass_stmt<F: Fold + ?Sized>(_folder: &mut F, node: Pass) -> Pass {
+	node
+}
+
+pub fn walk_break_stmt<V: Visitor + ?Sized>(_visitor: &mut V, _node: &Break) {}
+
+pub fn walk_break_stmt_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut Break) {}
+
+pub fn fold_break_stmt<F: Fold + ?Sized>(_folder: &mut F, node: Break) -> Break {
+	node
+}
+
+pub fn walk_continue_stmt<V: Visitor + ?Sized>(_visitor: &mut V, _node: &Continue) {}
+
+pub fn walk_continue_stmt_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut Continue) {}
+
+pub fn fold_continue_stmt<F: Fold + ?Sized>(_folder: &mut F, node: Continue) -> Continue {
+	node
+}
+
+pub fn walk_errorstmt_stmt<V: Visitor + ?Sized>(_visitor: &mut V, _node: &ErrorStmt) {}
+
+pub fn walk_errorstmt_stmt_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut ErrorStmt) {}
+
+pub fn fold_errorstmt_stmt<F: Fold + ?Sized>(_folder: &mut F, node: ErrorStmt) -> ErrorStmt {
+	node
+}
+
+pub fn walk_boolop_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &BoolOp) {
+	for x in &node.values {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_boolop_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut BoolOp) {
+	for x in &mut node.values {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_boolop_expr<F: Fold + ?Sized>(folder: &mut F, node: BoolOp) -> BoolOp {
+	BoolOp {
+		op: node.op,
+		values: node.values.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_namedexpr_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &NamedExpr) {
+	visitor.visit_expr(&node.target);
+	visitor.visit_expr(&node.value);
+}
+
+pub fn walk_namedexpr_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut NamedExpr) {
+	visitor.visit_expr_mut(&mut node.target);
+	visitor.visit_expr_mut(&mut node.value);
+}
+
+pub fn fold_namedexpr_expr<F: Fold + ?Sized>(folder: &mut F, node: NamedExpr) -> NamedExpr {
+	NamedExpr {
+		target: Box::new(folder.fold_expr(*node.target)),
+		value: Box::new(folder.fold_expr(*node.value)),
+		span: node.span,
+	}
+}
+
+pub fn walk_binop_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &BinOp) {
+	visitor.visit_expr(&node.left);
+	visitor.visit_expr(&node.right);
+}
+
+pub fn walk_binop_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut BinOp) {
+	visitor.visit_expr_mut(&mut node.left);
+	visitor.visit_expr_mut(&mut node.right);
+}
+
+pub fn fold_binop_expr<F: Fold + ?Sized>(folder: &mut F, node: BinOp) -> BinOp {
+	BinOp {
+		left: Box::new(folder.fold_expr(*node.left)),
+		op: node.op,
+		right: Box::new(folder.fold_expr(*node.right)),
+		span: node.span,
+	}
+}
+
+pub fn walk_unaryop_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &UnaryOp) {
+	visitor.visit_expr(&node.operand);
+}
+
+pub fn walk_unaryop_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut UnaryOp) {
+	visitor.visit_expr_mut(&mut node.operand);
+}
+
+pub fn fold_unaryop_expr<F: Fold + ?Sized>(folder: &mut F, node: UnaryOp) -> UnaryOp {
+	UnaryOp {
+		op: node.op,
+		operand: Box::new(folder.fold_expr(*node.operand)),
+		span: node.span,
+	}
+}
+
+pub fn walk_lambda_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Lambda) {
+	visitor.visit_arguments(&node.args);
+	visitor.visit_expr(&node.body);
+}
+
+pub fn walk_lambda_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Lambda) {
+	visitor.visit_arguments_mut(&mut node.args);
+	visitor.visit_expr_mut(&mut node.body);
+}
+
+pub fn fold_lambda_expr<F: Fold + ?Sized>(folder: &mut F, node: Lambda) -> Lambda {
+	Lambda {
+		args: Box::new(folder.fold_arguments(*node.args)),
+		body: Box::new(folder.fold_expr(*node.body)),
+		span: node.span,
+	}
+}
+
+pub fn walk_ifexp_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &IfExp) {
+	visitor.visit_expr(&node.test);
+	visitor.visit_expr(&node.body);
+	visitor.visit_expr(&node.orelse);
+}
+
+pub fn walk_ifexp_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut IfExp) {
+	visitor.visit_expr_mut(&mut node.test);
+	visitor.visit_expr_mut(&mut node.body);
+	visitor.visit_expr_mut(&mut node.orelse);
+}
+
+pub fn fold_ifexp_expr<F: Fold + ?Sized>(folder: &mut F, node: IfExp) -> IfExp {
+	IfExp {
+		test: Box::new(folder.fold_expr(*node.test)),
+		body: Box::new(folder.fold_expr(*node.body)),
+		orelse: Box::new(folder.fold_expr(*node.orelse)),
+		span: node.span,
+	}
+}
+
+pub fn walk_dict_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Dict) {
+	for x in node.keys.iter().flatten() {
+		visitor.visit_expr(x);
+	}
+	for x in &node.values {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_dict_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Dict) {
+	for x in node.keys.iter_mut().flatten() {
+		visitor.visit_expr_mut(x);
+	}
+	for x in &mut node.values {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_dict_expr<F: Fold + ?Sized>(folder: &mut F, node: Dict) -> Dict {
+	Dict {
+		keys: node.keys.into_iter().map(|x| x.map(|x| folder.fold_expr(x))).collect(),
+		values: node.values.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_set_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Set) {
+	for x in &node.elts {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_set_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Set) {
+	for x in &mut node.elts {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_set_expr<F: Fold + ?Sized>(folder: &mut F, node: Set) -> Set {
+	Set {
+		elts: node.elts.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_listcomp_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &ListComp) {
+	visitor.visit_expr(&node.elt);
+	for x in &node.generators {
+		visitor.visit_comprehension(x);
+	}
+}
+
+pub fn walk_listcomp_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ListComp) {
+	visitor.visit_expr_mut(&mut node.elt);
+	for x in &mut node.generators {
+		visitor.visit_comprehension_mut(x);
+	}
+}
+
+pub fn fold_listcomp_expr<F: Fold + ?Sized>(folder: &mut F, node: ListComp) -> ListComp {
+	ListComp {
+		elt: Box::new(folder.fold_expr(*node.elt)),
+		generators: node.generators.into_iter().map(|x| folder.fold_comprehension(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_setcomp_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &SetComp) {
+	visitor.visit_expr(&node.elt);
+	for x in &node.generators {
+		visitor.visit_comprehension(x);
+	}
+}
+
+pub fn walk_setcomp_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut SetComp) {
+	visitor.visit_expr_mut(&mut node.elt);
+	for x in &mut node.generators {
+		visitor.visit_comprehension_mut(x);
+	}
+}
+
+pub fn fold_setcomp_expr<F: Fold + ?Sized>(folder: &mut F, node: SetComp) -> SetComp {
+	SetComp {
+		elt: Box::new(folder.fold_expr(*node.elt)),
+		generators: node.generators.into_iter().map(|x| folder.fold_comprehension(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_dictcomp_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &DictComp) {
+	visitor.visit_expr(&node.key);
+	visitor.visit_expr(&node.value);
+	for x in &node.generators {
+		visitor.visit_comprehension(x);
+	}
+}
+
+pub fn walk_dictcomp_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut DictComp) {
+	visitor.visit_expr_mut(&mut node.key);
+	visitor.visit_expr_mut(&mut node.value);
+	for x in &mut node.generators {
+		visitor.visit_comprehension_mut(x);
+	}
+}
+
+pub fn fold_dictcomp_expr<F: Fold + ?Sized>(folder: &mut F, node: DictComp) -> DictComp {
+	DictComp {
+		key: Box::new(folder.fold_expr(*node.key)),
+		value: Box::new(folder.fold_expr(*node.value)),
+		generators: node.generators.into_iter().map(|x| folder.fold_comprehension(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_generatorexp_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &GeneratorExp) {
+	visitor.visit_expr(&node.elt);
+	for x in &node.generators {
+		visitor.visit_comprehension(x);
+	}
+}
+
+pub fn walk_generatorexp_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut GeneratorExp) {
+	visitor.visit_expr_mut(&mut node.elt);
+	for x in &mut node.generators {
+		visitor.visit_comprehension_mut(x);
+	}
+}
+
+pub fn fold_generatorexp_expr<F: Fold + ?Sized>(folder: &mut F, node: GeneratorExp) -> GeneratorExp {
+	GeneratorExp {
+		elt: Box::new(folder.fold_expr(*node.elt)),
+		generators: node.generators.into_iter().map(|x| folder.fold_comprehension(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_await_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Await) {
+	visitor.visit_expr(&node.value);
+}
+
+pub fn walk_await_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Await) {
+	visitor.visit_expr_mut(&mut node.value);
+}
+
+pub fn fold_await_expr<F: Fold + ?Sized>(folder: &mut F, node: Await) -> Await {
+	Await {
+		value: Box::new(folder.fold_expr(*node.value)),
+		span: node.span,
+	}
+}
+
+pub fn walk_yield_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Yield) {
+	if let Some(x) = &node.value {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_yield_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Yield) {
+	if let Some(x) = &mut node.value {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_yield_expr<F: Fold + ?Sized>(folder: &mut F, node: Yield) -> Yield {
+	Yield {
+		value: node.value.map(|x| Box::new(folder.fold_expr(*x))),
+		span: node.span,
+	}
+}
+
+pub fn walk_yieldfrom_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &YieldFrom) {
+	visitor.visit_expr(&node.value);
+}
+
+pub fn walk_yieldfrom_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut YieldFrom) {
+	visitor.visit_expr_mut(&mut node.value);
+}
+
+pub fn fold_yieldfrom_expr<F: Fold + ?Sized>(folder: &mut F, node: YieldFrom) -> YieldFrom {
+	YieldFrom {
+		value: Box::new(folder.fold_expr(*node.value)),
+		span: node.span,
+	}
+}
+
+pub fn walk_compare_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Compare) {
+	visitor.visit_expr(&node.left);
+	for x in &node.comparators {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_compare_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Compare) {
+	visitor.visit_expr_mut(&mut node.left);
+	for x in &mut node.comparators {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_compare_expr<F: Fold + ?Sized>(folder: &mut F, node: Compare) -> Compare {
+	Compare {
+		left: Box::new(folder.fold_expr(*node.left)),
+		ops: node.ops,
+		comparators: node.comparators.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_call_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Call) {
+	visitor.visit_expr(&node.func);
+	for x in &node.args {
+		visitor.visit_expr(x);
+	}
+	for x in &node.keywords {
+		visitor.visit_keyword(x);
+	}
+}
+
+pub fn walk_call_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Call) {
+	visitor.visit_expr_mut(&mut node.func);
+	for x in &mut node.args {
+		visitor.visit_expr_mut(x);
+	}
+	for x in &mut node.keywords {
+		visitor.visit_keyword_mut(x);
+	}
+}
+
+pub fn fold_call_expr<F: Fold + ?Sized>(folder: &mut F, node: Call) -> Call {
+	Call {
+		func: Box::new(folder.fold_expr(*node.func)),
+		args: node.args.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		keywords: node.keywords.into_iter().map(|x| folder.fold_keyword(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_formattedvalue_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &FormattedValue) {
+	visitor.visit_expr(&node.value);
+	if let Some(x) = &node.format_spec {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_formattedvalue_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut FormattedValue) {
+	visitor.visit_expr_mut(&mut node.value);
+	if let Some(x) = &mut node.format_spec {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_formattedvalue_expr<F: Fold + ?Sized>(folder: &mut F, node: FormattedValue) -> FormattedValue {
+	FormattedValue {
+		value: Box::new(folder.fold_expr(*node.value)),
+		conversion: node.conversion,
+		format_spec: node.format_spec.map(|x| Box::new(folder.fold_expr(*x))),
+		span: node.span,
+	}
+}
+
+pub fn walk_joinedstr_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &JoinedStr) {
+	for x in &node.values {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_joinedstr_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut JoinedStr) {
+	for x in &mut node.values {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_joinedstr_expr<F: Fold + ?Sized>(folder: &mut F, node: JoinedStr) -> JoinedStr {
+	JoinedStr {
+		values: node.values.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_constant_expr<V: Visitor + ?Sized>(_visitor: &mut V, _node: &Constant) {}
+
+pub fn walk_constant_expr_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut Constant) {}
+
+pub fn fold_constant_expr<F: Fold + ?Sized>(_folder: &mut F, node: Constant) -> Constant {
+	node
+}
+
+pub fn walk_attribute_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Attribute) {
+	visitor.visit_expr(&node.value);
+}
+
+pub fn walk_attribute_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Attribute) {
+	visitor.visit_expr_mut(&mut node.value);
+}
+
+pub fn fold_attribute_expr<F: Fold + ?Sized>(folder: &mut F, node: Attribute) -> Attribute {
+	Attribute {
+		value: Box::new(folder.fold_expr(*node.value)),
+		attr: node.attr,
+		ctx: node.ctx,
+		span: node.span,
+	}
+}
+
+pub fn walk_subscript_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Subscript) {
+	visitor.visit_expr(&node.value);
+	visitor.visit_expr(&node.slice);
+}
+
+pub fn walk_subscript_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Subscript) {
+	visitor.visit_expr_mut(&mut node.value);
+	visitor.visit_expr_mut(&mut node.slice);
+}
+
+pub fn fold_subscript_expr<F: Fold + ?Sized>(folder: &mut F, node: Subscript) -> Subscript {
+	Subscript {
+		value: Box::new(folder.fold_expr(*node.value)),
+		slice: Box::new(folder.fold_expr(*node.slice)),
+		ctx: node.ctx,
+		span: node.span,
+	}
+}
+
+pub fn walk_starred_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Starred) {
+	visitor.visit_expr(&node.value);
+}
+
+pub fn walk_starred_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Starred) {
+	visitor.visit_expr_mut(&mut node.value);
+}
+
+pub fn fold_starred_expr<F: Fold + ?Sized>(folder: &mut F, node: Starred) -> Starred {
+	Starred {
+		value: Box::new(folder.fold_expr(*node.value)),
+		ctx: node.ctx,
+		span: node.span,
+	}
+}
+
+pub fn walk_name_expr<V: Visitor + ?Sized>(_visitor: &mut V, _node: &Name) {}
+
+pub fn walk_name_expr_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut Name) {}
+
+pub fn fold_name_expr<F: Fold + ?Sized>(_folder: &mut F, node: Name) -> Name {
+	node
+}
+
+pub fn walk_list_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &List) {
+	for x in &node.elts {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_list_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut List) {
+	for x in &mut node.elts {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_list_expr<F: Fold + ?Sized>(folder: &mut F, node: List) -> List {
+	List {
+		elts: node.elts.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		ctx: node.ctx,
+		span: node.span,
+	}
+}
+
+pub fn walk_tuple_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Tuple) {
+	for x in &node.elts {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_tuple_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Tuple) {
+	for x in &mut node.elts {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_tuple_expr<F: Fold + ?Sized>(folder: &mut F, node: Tuple) -> Tuple {
+	Tuple {
+		elts: node.elts.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		ctx: node.ctx,
+		span: node.span,
+	}
+}
+
+pub fn walk_slice_expr<V: Visitor + ?Sized>(visitor: &mut V, node: &Slice) {
+	if let Some(x) = &node.lower {
+		visitor.visit_expr(x);
+	}
+	if let Some(x) = &node.upper {
+		visitor.visit_expr(x);
+	}
+	if let Some(x) = &node.step {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_slice_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Slice) {
+	if let Some(x) = &mut node.lower {
+		visitor.visit_expr_mut(x);
+	}
+	if let Some(x) = &mut node.upper {
+		visitor.visit_expr_mut(x);
+	}
+	if let Some(x) = &mut node.step {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_slice_expr<F: Fold + ?Sized>(folder: &mut F, node: Slice) -> Slice {
+	Slice {
+		lower: node.lower.map(|x| Box::new(folder.fold_expr(*x))),
+		upper: node.upper.map(|x| Box::new(folder.fold_expr(*x))),
+		step: node.step.map(|x| Box::new(folder.fold_expr(*x))),
+		span: node.span,
+	}
+}
+
+pub fn walk_errorexpr_expr<V: Visitor + ?Sized>(_visitor: &mut V, _node: &ErrorExpr) {}
+
+pub fn walk_errorexpr_expr_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut ErrorExpr) {}
+
+pub fn fold_errorexpr_expr<F: Fold + ?Sized>(_folder: &mut F, node: ErrorExpr) -> ErrorExpr {
+	node
+}
+
+pub fn walk_matchvalue_pattern<V: Visitor + ?Sized>(visitor: &mut V, node: &MatchValue) {
+	visitor.visit_expr(&node.value);
+}
+
+pub fn walk_matchvalue_pattern_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut MatchValue) {
+	visitor.visit_expr_mut(&mut node.value);
+}
+
+pub fn fold_matchvalue_pattern<F: Fold + ?Sized>(folder: &mut F, node: MatchValue) -> MatchValue {
+	MatchValue {
+		value: Box::new(folder.fold_expr(*node.value)),
+		span: node.span,
+	}
+}
+
+pub fn walk_matchsingleton_pattern<V: Visitor + ?Sized>(_visitor: &mut V, _node: &MatchSingleton) {}
+
+pub fn walk_matchsingleton_pattern_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut MatchSingleton) {}
+
+pub fn fold_matchsingleton_pattern<F: Fold + ?Sized>(_folder: &mut F, node: MatchSingleton) -> MatchSingleton {
+	node
+}
+
+pub fn walk_matchsequence_pattern<V: Visitor + ?Sized>(visitor: &mut V, node: &MatchSequence) {
+	for x in &node.patterns {
+		visitor.visit_pattern(x);
+	}
+}
+
+pub fn walk_matchsequence_pattern_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut MatchSequence) {
+	for x in &mut node.patterns {
+		visitor.visit_pattern_mut(x);
+	}
+}
+
+pub fn fold_matchsequence_pattern<F: Fold + ?Sized>(folder: &mut F, node: MatchSequence) -> MatchSequence {
+	MatchSequence {
+		patterns: node.patterns.into_iter().map(|x| folder.fold_pattern(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_matchmapping_pattern<V: Visitor + ?Sized>(visitor: &mut V, node: &MatchMapping) {
+	for x in &node.keys {
+		visitor.visit_expr(x);
+	}
+	for x in &node.patterns {
+		visitor.visit_pattern(x);
+	}
+}
+
+pub fn walk_matchmapping_pattern_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut MatchMapping) {
+	for x in &mut node.keys {
+		visitor.visit_expr_mut(x);
+	}
+	for x in &mut node.patterns {
+		visitor.visit_pattern_mut(x);
+	}
+}
+
+pub fn fold_matchmapping_pattern<F: Fold + ?Sized>(folder: &mut F, node: MatchMapping) -> MatchMapping {
+	MatchMapping {
+		keys: node.keys.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		patterns: node.patterns.into_iter().map(|x| folder.fold_pattern(x)).collect(),
+		rest: node.rest,
+		span: node.span,
+	}
+}
+
+pub fn walk_matchclass_pattern<V: Visitor + ?Sized>(visitor: &mut V, node: &MatchClass) {
+	visitor.visit_expr(&node.cls);
+	for x in &node.patterns {
+		visitor.visit_pattern(x);
+	}
+	for x in &node.kwd_patterns {
+		visitor.visit_pattern(x);
+	}
+}
+
+pub fn walk_matchclass_pattern_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut MatchClass) {
+	visitor.visit_expr_mut(&mut node.cls);
+	for x in &mut node.patterns {
+		visitor.visit_pattern_mut(x);
+	}
+	for x in &mut node.kwd_patterns {
+		visitor.visit_pattern_mut(x);
+	}
+}
+
+pub fn fold_matchclass_pattern<F: Fold + ?Sized>(folder: &mut F, node: MatchClass) -> MatchClass {
+	MatchClass {
+		cls: Box::new(folder.fold_expr(*node.cls)),
+		patterns: node.patterns.into_iter().map(|x| folder.fold_pattern(x)).collect(),
+		kwd_attrs: node.kwd_attrs,
+		kwd_patterns: node.kwd_patterns.into_iter().map(|x| folder.fold_pattern(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_matchstar_pattern<V: Visitor + ?Sized>(_visitor: &mut V, _node: &MatchStar) {}
+
+pub fn walk_matchstar_pattern_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut MatchStar) {}
+
+pub fn fold_matchstar_pattern<F: Fold + ?Sized>(_folder: &mut F, node: MatchStar) -> MatchStar {
+	node
+}
+
+pub fn walk_matchas_pattern<V: Visitor + ?Sized>(visitor: &mut V, node: &MatchAs) {
+	if let Some(x) = &node.pattern {
+		visitor.visit_pattern(x);
+	}
+}
+
+pub fn walk_matchas_pattern_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut MatchAs) {
+	if let Some(x) = &mut node.pattern {
+		visitor.visit_pattern_mut(x);
+	}
+}
+
+pub fn fold_matchas_pattern<F: Fold + ?Sized>(folder: &mut F, node: MatchAs) -> MatchAs {
+	MatchAs {
+		pattern: node.pattern.map(|x| Box::new(folder.fold_pattern(*x))),
+		name: node.name,
+		span: node.span,
+	}
+}
+
+pub fn walk_matchor_pattern<V: Visitor + ?Sized>(visitor: &mut V, node: &MatchOr) {
+	for x in &node.patterns {
+		visitor.visit_pattern(x);
+	}
+}
+
+pub fn walk_matchor_pattern_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut MatchOr) {
+	for x in &mut node.patterns {
+		visitor.visit_pattern_mut(x);
+	}
+}
+
+pub fn fold_matchor_pattern<F: Fold + ?Sized>(folder: &mut F, node: MatchOr) -> MatchOr {
+	MatchOr {
+		patterns: node.patterns.into_iter().map(|x| folder.fold_pattern(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_typevar_typeparam<V: Visitor + ?Sized>(visitor: &mut V, node: &TypeVar) {
+	if let Some(x) = &node.bound {
+		visitor.visit_expr(x);
+	}
+	if let Some(x) = &node.default_value {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_typevar_typeparam_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut TypeVar) {
+	if let Some(x) = &mut node.bound {
+		visitor.visit_expr_mut(x);
+	}
+	if let Some(x) = &mut node.default_value {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_typevar_typeparam<F: Fold + ?Sized>(folder: &mut F, node: TypeVar) -> TypeVar {
+	TypeVar {
+		name: node.name,
+		bound: node.bound.map(|x| Box::new(folder.fold_expr(*x))),
+		default_value: node.default_value.map(|x| Box::new(folder.fold_expr(*x))),
+		span: node.span,
+	}
+}
+
+pub fn walk_paramspec_typeparam<V: Visitor + ?Sized>(visitor: &mut V, node: &ParamSpec) {
+	if let Some(x) = &node.default_value {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_paramspec_typeparam_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ParamSpec) {
+	if let Some(x) = &mut node.default_value {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_paramspec_typeparam<F: Fold + ?Sized>(folder: &mut F, node: ParamSpec) -> ParamSpec {
+	ParamSpec {
+		name: node.name,
+		default_value: node.default_value.map(|x| Box::new(folder.fold_expr(*x))),
+		span: node.span,
+	}
+}
+
+pub fn walk_typevartuple_typeparam<V: Visitor + ?Sized>(visitor: &mut V, node: &TypeVarTuple) {
+	if let Some(x) = &node.default_value {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_typevartuple_typeparam_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut TypeVarTuple) {
+	if let Some(x) = &mut node.default_value {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_typevartuple_typeparam<F: Fold + ?Sized>(folder: &mut F, node: TypeVarTuple) -> TypeVarTuple {
+	TypeVarTuple {
+		name: node.name,
+		default_value: node.default_value.map(|x| Box::new(folder.fold_expr(*x))),
+		span: node.span,
+	}
+}
+
+pub fn walk_comprehension<V: Visitor + ?Sized>(visitor: &mut V, node: &Comprehension) {
+	visitor.visit_expr(&node.target);
+	visitor.visit_expr(&node.iter);
+	for x in &node.ifs {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_comprehension_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Comprehension) {
+	visitor.visit_expr_mut(&mut node.target);
+	visitor.visit_expr_mut(&mut node.iter);
+	for x in &mut node.ifs {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_comprehension<F: Fold + ?Sized>(folder: &mut F, node: Comprehension) -> Comprehension {
+	Comprehension {
+		target: Box::new(folder.fold_expr(*node.target)),
+		iter: Box::new(folder.fold_expr(*node.iter)),
+		ifs: node.ifs.into_iter().map(|x| folder.fold_expr(x)).collect(),
+		is_async: node.is_async,
+	}
+}
+
+pub fn walk_arguments<V: Visitor + ?Sized>(visitor: &mut V, node: &Arguments) {
+	for x in &node.posonlyargs {
+		visitor.visit_arg(x);
+	}
+	for x in &node.args {
+		visitor.visit_arg(x);
+	}
+	if let Some(x) = &node.vararg {
+		visitor.visit_arg(x);
+	}
+	for x in &node.kwonlyargs {
+		visitor.visit_arg(x);
+	}
+	for x in node.kw_defaults.iter().flatten() {
+		visitor.visit_expr(x);
+	}
+	if let Some(x) = &node.kwarg {
+		visitor.visit_arg(x);
+	}
+	for x in &node.defaults {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_arguments_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Arguments) {
+	for x in &mut node.posonlyargs {
+		visitor.visit_arg_mut(x);
+	}
+	for x in &mut node.args {
+		visitor.visit_arg_mut(x);
+	}
+	if let Some(x) = &mut node.vararg {
+		visitor.visit_arg_mut(x);
+	}
+	for x in &mut node.kwonlyargs {
+		visitor.visit_arg_mut(x);
+	}
+	for x in node.kw_defaults.iter_mut().flatten() {
+		visitor.visit_expr_mut(x);
+	}
+	if let Some(x) = &mut node.kwarg {
+		visitor.visit_arg_mut(x);
+	}
+	for x in &mut node.defaults {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_arguments<F: Fold + ?Sized>(folder: &mut F, node: Arguments) -> Arguments {
+	Arguments {
+		posonlyargs: node.posonlyargs.into_iter().map(|x| folder.fold_arg(x)).collect(),
+		args: node.args.into_iter().map(|x| folder.fold_arg(x)).collect(),
+		vararg: node.vararg.map(|x| Box::new(folder.fold_arg(*x))),
+		kwonlyargs: node.kwonlyargs.into_iter().map(|x| folder.fold_arg(x)).collect(),
+		kw_defaults: node.kw_defaults.into_iter().map(|x| x.map(|x| folder.fold_expr(x))).collect(),
+		kwarg: node.kwarg.map(|x| Box::new(folder.fold_arg(*x))),
+		defaults: node.defaults.into_iter().map(|x| folder.fold_expr(x)).collect(),
+	}
+}
+
+pub fn walk_arg<V: Visitor + ?Sized>(visitor: &mut V, node: &Arg) {
+	if let Some(x) = &node.annotation {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_arg_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Arg) {
+	if let Some(x) = &mut node.annotation {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_arg<F: Fold + ?Sized>(folder: &mut F, node: Arg) -> Arg {
+	Arg {
+		arg: node.arg,
+		annotation: node.annotation.map(|x| Box::new(folder.fold_expr(*x))),
+		type_comment: node.type_comment,
+		span: node.span,
+	}
+}
+
+pub fn walk_keyword<V: Visitor + ?Sized>(visitor: &mut V, node: &Keyword) {
+	visitor.visit_expr(&node.value);
+}
+
+pub fn walk_keyword_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Keyword) {
+	visitor.visit_expr_mut(&mut node.value);
+}
+
+pub fn fold_keyword<F: Fold + ?Sized>(folder: &mut F, node: Keyword) -> Keyword {
+	Keyword {
+		arg: node.arg,
+		value: Box::new(folder.fold_expr(*node.value)),
+		span: node.span,
+	}
+}
+
+pub fn walk_excepthandler<V: Visitor + ?Sized>(visitor: &mut V, node: &ExceptHandler) {
+	if let Some(x) = &node.r#type {
+		visitor.visit_expr(x);
+	}
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+}
+
+pub fn walk_excepthandler_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ExceptHandler) {
+	if let Some(x) = &mut node.r#type {
+		visitor.visit_expr_mut(x);
+	}
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+}
+
+pub fn fold_excepthandler<F: Fold + ?Sized>(folder: &mut F, node: ExceptHandler) -> ExceptHandler {
+	ExceptHandler {
+		r#type: node.r#type.map(|x| Box::new(folder.fold_expr(*x))),
+		name: node.name,
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+		span: node.span,
+	}
+}
+
+pub fn walk_alias<V: Visitor + ?Sized>(_visitor: &mut V, _node: &Alias) {}
+
+pub fn walk_alias_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut Alias) {}
+
+pub fn fold_alias<F: Fold + ?Sized>(_folder: &mut F, node: Alias) -> Alias {
+	node
+}
+
+pub fn walk_withitem<V: Visitor + ?Sized>(visitor: &mut V, node: &WithItem) {
+	visitor.visit_expr(&node.context_expr);
+	if let Some(x) = &node.optional_vars {
+		visitor.visit_expr(x);
+	}
+}
+
+pub fn walk_withitem_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut WithItem) {
+	visitor.visit_expr_mut(&mut node.context_expr);
+	if let Some(x) = &mut node.optional_vars {
+		visitor.visit_expr_mut(x);
+	}
+}
+
+pub fn fold_withitem<F: Fold + ?Sized>(folder: &mut F, node: WithItem) -> WithItem {
+	WithItem {
+		context_expr: Box::new(folder.fold_expr(*node.context_expr)),
+		optional_vars: node.optional_vars.map(|x| Box::new(folder.fold_expr(*x))),
+	}
+}
+
+pub fn walk_matchcase<V: Visitor + ?Sized>(visitor: &mut V, node: &MatchCase) {
+	visitor.visit_pattern(&node.pattern);
+	if let Some(x) = &node.guard {
+		visitor.visit_expr(x);
+	}
+	for x in &node.body {
+		visitor.visit_stmt(x);
+	}
+}
+
+pub fn walk_matchcase_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut MatchCase) {
+	visitor.visit_pattern_mut(&mut node.pattern);
+	if let Some(x) = &mut node.guard {
+		visitor.visit_expr_mut(x);
+	}
+	for x in &mut node.body {
+		visitor.visit_stmt_mut(x);
+	}
+}
+
+pub fn fold_matchcase<F: Fold + ?Sized>(folder: &mut F, node: MatchCase) -> MatchCase {
+	MatchCase {
+		pattern: Box::new(folder.fold_pattern(*node.pattern)),
+		guard: node.guard.map(|x| Box::new(folder.fold_expr(*x))),
+		body: node.body.into_iter().map(|x| folder.fold_stmt(x)).collect(),
+	}
+}
+
+pub fn walk_typeignore<V: Visitor + ?Sized>(_visitor: &mut V, _node: &TypeIgnore) {}
+
+pub fn walk_typeignore_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut TypeIgnore) {}
+
+pub fn fold_typeignore<F: Fold + ?Sized>(_folder: &mut F, node: TypeIgnore) -> TypeIgnore {
+	node
+}
+